@@ -3,9 +3,12 @@
 //! Provides a custom formatter for tracing that:
 //! - Formats thread IDs as #N instead of ThreadId(N)
 //! - Extracts `runtime_type` and `mod_id` fields to display as `js::mod-id`
+//! - Prints the structured `fields` of mod messages as `key=value` pairs
 //! - Strips common prefixes from targets for cleaner output
 //! - Handles raw mode terminal output with proper `\r\n` line endings
+//! - Optionally buffers stdout output for high-throughput logging
 //! - Filters external dependency logs based on `STAM_LOGDEPS` environment variable
+//! - Optionally collapses repeated mod lines and rate-limits noisy mods (`ModLogLimit`)
 //!
 //! # Environment Variables
 //!
@@ -20,23 +23,30 @@
 //!
 //! // Simple initialization with defaults
 //! let config = LogConfig::new("stam_client::");
-//! init_logging(config)?;
+//! let log = init_logging(config)?;
 //!
 //! // Or with file logging
 //! let file = std::fs::File::create("app.log")?;
 //! let config = LogConfig::new("stam_server::")
 //!     .with_log_file(file)
 //!     .with_level(Level::DEBUG);
-//! init_logging(config)?;
+//! let log = init_logging(config)?;
+//!
+//! // Before exiting, flush stdout and the log file
+//! log.flush();
+//! std::process::exit(0);
 //! ```
 
 use std::fmt as std_fmt;
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 use tracing::Level;
 use tracing::field::Field;
 use tracing_subscriber::field::Visit;
 use tracing_subscriber::fmt::time::OffsetTime;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::fmt::{
     self, FmtContext, FormatEvent, FormatFields, MakeWriter, format::Writer,
 };
@@ -44,6 +54,9 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::registry::LookupSpan;
 use tracing_subscriber::util::SubscriberInitExt;
 
+mod limiter;
+pub use limiter::{ModLogLimit, ModLogLimiter};
+
 /// Global flag indicating whether terminal is in raw mode
 /// Used by logging to determine if \r\n should be used instead of \n
 static RAW_MODE_ACTIVE: AtomicBool = AtomicBool::new(false);
@@ -68,19 +81,38 @@ pub fn is_raw_mode_active() -> bool {
 ///
 /// In raw mode, the terminal doesn't automatically convert newlines,
 /// so we need to explicitly use carriage return + line feed.
+/// A `\n` that is already preceded by `\r` (even from a previous `write` call)
+/// is left untouched, so no doubled carriage returns are emitted.
+///
+/// Tracing creates a writer per event, so whether the last byte was a `\r` is
+/// kept in `last_was_cr`, shared by every writer of the same stream.
 pub struct RawModeWriter<W> {
     inner: W,
+    /// Whether `\n` is converted (raw mode state when the writer was created)
+    raw_mode: bool,
+    /// Whether the last byte written to the stream was a `\r`
+    last_was_cr: Arc<AtomicBool>,
 }
 
 impl<W: Write> RawModeWriter<W> {
-    pub fn new(inner: W) -> Self {
-        Self { inner }
+    /// Wrap `inner`, converting newlines if raw mode is currently active
+    pub fn new(inner: W, last_was_cr: Arc<AtomicBool>) -> Self {
+        Self::with_raw_mode(inner, is_raw_mode_active(), last_was_cr)
+    }
+
+    /// Wrap `inner` with an explicit raw mode state
+    pub fn with_raw_mode(inner: W, raw_mode: bool, last_was_cr: Arc<AtomicBool>) -> Self {
+        Self { inner, raw_mode, last_was_cr }
     }
 }
 
 impl<W: Write> Write for RawModeWriter<W> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        if is_raw_mode_active() {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.raw_mode {
             // Convert \n to \r\n
             let mut start = 0;
             for (i, &byte) in buf.iter().enumerate() {
@@ -89,8 +121,13 @@ impl<W: Write> Write for RawModeWriter<W> {
                     if i > start {
                         self.inner.write_all(&buf[start..i])?;
                     }
-                    // Write \r\n instead of just \n
-                    self.inner.write_all(b"\r\n")?;
+                    // Write \r\n instead of just \n, unless a \r already precedes it
+                    let preceded_by_cr = if i > 0 { buf[i - 1] == b'\r' } else { self.last_was_cr.load(Ordering::Relaxed) };
+                    if preceded_by_cr {
+                        self.inner.write_all(b"\n")?;
+                    } else {
+                        self.inner.write_all(b"\r\n")?;
+                    }
                     start = i + 1;
                 }
             }
@@ -98,9 +135,14 @@ impl<W: Write> Write for RawModeWriter<W> {
             if start < buf.len() {
                 self.inner.write_all(&buf[start..])?;
             }
+            self.last_was_cr.store(buf[buf.len() - 1] == b'\r', Ordering::Relaxed);
             Ok(buf.len())
         } else {
-            self.inner.write(buf)
+            let written = self.inner.write(buf)?;
+            if written > 0 {
+                self.last_was_cr.store(buf[written - 1] == b'\r', Ordering::Relaxed);
+            }
+            Ok(written)
         }
     }
 
@@ -110,26 +152,242 @@ impl<W: Write> Write for RawModeWriter<W> {
 }
 
 /// A MakeWriter that wraps stdout with RawModeWriter
-pub struct RawModeStdoutWriter;
+#[derive(Default)]
+pub struct RawModeStdoutWriter {
+    /// Whether the last byte written to stdout was a `\r`
+    last_was_cr: Arc<AtomicBool>,
+}
 
 impl<'a> MakeWriter<'a> for RawModeStdoutWriter {
     type Writer = RawModeWriter<io::Stdout>;
 
     fn make_writer(&'a self) -> Self::Writer {
-        RawModeWriter::new(io::stdout())
+        RawModeWriter::new(io::stdout(), self.last_was_cr.clone())
+    }
+}
+
+/// Default capacity of the buffered stdout writer
+///
+/// When the buffer would exceed this size it is flushed immediately.
+pub const DEFAULT_STDOUT_BUFFER_CAPACITY: usize = 64 * 1024;
+
+/// Interval at which the buffered stdout writer is flushed in the background
+pub const DEFAULT_STDOUT_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A MakeWriter that batches stdout writes through a shared `BufWriter`
+///
+/// Tracing calls `make_writer` once per event, so the buffer lives behind an
+/// `Arc<Mutex<...>>` shared by all writers. Each event holds the lock while it
+/// is written, so lines from different threads never interleave.
+///
+/// The buffer is flushed when it exceeds its capacity and periodically by a
+/// background thread. Raw mode `\r\n` conversion is applied as with
+/// `RawModeStdoutWriter`.
+///
+/// Event order is preserved within stdout and within the log file, since each
+/// sink is written in event order. Buffered stdout lines may simply appear up to
+/// one flush interval later than the same lines in the file.
+pub struct BufWriterMakeWriter {
+    buffer: Arc<Mutex<BufWriter<io::Stdout>>>,
+    /// Whether the last byte written to the buffer was a `\r`
+    last_was_cr: Arc<AtomicBool>,
+}
+
+impl BufWriterMakeWriter {
+    /// Create a new buffered stdout writer
+    ///
+    /// # Arguments
+    /// * `capacity` - Buffer size in bytes; a full buffer is flushed immediately
+    /// * `flush_interval` - How often the background thread flushes the buffer
+    pub fn new(capacity: usize, flush_interval: Duration) -> Self {
+        let buffer = Arc::new(Mutex::new(BufWriter::with_capacity(capacity, io::stdout())));
+
+        // The flusher only holds a weak reference, so it stops once the writer is gone
+        let weak = Arc::downgrade(&buffer);
+        let spawned = std::thread::Builder::new()
+            .name("stam-log-flush".to_string())
+            .spawn(move || {
+                loop {
+                    std::thread::sleep(flush_interval);
+                    match weak.upgrade() {
+                        Some(buffer) => {
+                            let _ = lock_buffer(&buffer).flush();
+                        }
+                        None => break,
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("Failed to spawn log flush thread, stdout is flushed only when the buffer is full: {}", e);
+        }
+
+        Self { buffer, last_was_cr: Arc::default() }
+    }
+
+    /// Flush any buffered output to stdout
+    pub fn flush(&self) -> io::Result<()> {
+        lock_buffer(&self.buffer).flush()
+    }
+}
+
+impl Default for BufWriterMakeWriter {
+    fn default() -> Self {
+        Self::new(DEFAULT_STDOUT_BUFFER_CAPACITY, DEFAULT_STDOUT_FLUSH_INTERVAL)
+    }
+}
+
+/// Lock the shared buffer, recovering it if a previous writer panicked
+fn lock_buffer(buffer: &Mutex<BufWriter<io::Stdout>>) -> MutexGuard<'_, BufWriter<io::Stdout>> {
+    buffer.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Writer handed out per event by `BufWriterMakeWriter`, holding the buffer lock
+pub struct BufferedStdoutGuard<'a> {
+    guard: MutexGuard<'a, BufWriter<io::Stdout>>,
+}
+
+impl Write for BufferedStdoutGuard<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.guard.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.guard.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for BufWriterMakeWriter {
+    type Writer = RawModeWriter<BufferedStdoutGuard<'a>>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RawModeWriter::new(BufferedStdoutGuard { guard: lock_buffer(&self.buffer) }, self.last_was_cr.clone())
     }
 }
 
-/// Field extractor for game_id, runtime_type, mod_id, and message fields
+/// Log file writer shared between the file layer and `LogHandle::flush()`
+type SharedLogFile = Arc<Mutex<Option<Box<dyn Write + Send>>>>;
+
+/// A MakeWriter for the log file that can be flushed and closed explicitly
+///
+/// Clones share the same file. After `close()` the file is dropped and later
+/// events are silently discarded by this writer.
+#[derive(Clone)]
+pub struct FileMakeWriter {
+    file: SharedLogFile,
+}
+
+impl FileMakeWriter {
+    /// Wrap a log file (or any writer) so it can be shared with `LogHandle::flush()`
+    pub fn new<W: Write + Send + 'static>(file: W) -> Self {
+        Self {
+            file: Arc::new(Mutex::new(Some(Box::new(file)))),
+        }
+    }
+
+    /// Flush buffered writes to the file
+    pub fn flush(&self) -> io::Result<()> {
+        match lock_file(&self.file).as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Flush and drop the file writer
+    pub fn close(&self) -> io::Result<()> {
+        match lock_file(&self.file).take() {
+            Some(mut file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Lock the shared log file, recovering it if a previous writer panicked
+fn lock_file(file: &SharedLogFile) -> MutexGuard<'_, Option<Box<dyn Write + Send>>> {
+    file.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Writer handed out per event by `FileMakeWriter`, holding the file lock
+pub struct LogFileGuard<'a> {
+    guard: MutexGuard<'a, Option<Box<dyn Write + Send>>>,
+}
+
+impl Write for LogFileGuard<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.guard.as_mut() {
+            Some(file) => file.write(buf),
+            // Closed on shutdown: drop late events
+            None => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.guard.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for FileMakeWriter {
+    type Writer = LogFileGuard<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogFileGuard { guard: lock_file(&self.file) }
+    }
+}
+
+/// Handle to the writers installed by `init_logging`
+///
+/// Keep it until the process exits: `flush()` is the only way to push out
+/// buffered stdout lines and the tail of the log file.
+#[derive(Clone, Default)]
+pub struct LogHandle {
+    /// Buffer of the buffered stdout writer (None when stdout is unbuffered)
+    stdout_buffer: Option<Arc<Mutex<BufWriter<io::Stdout>>>>,
+    /// Log file writer (None without a log file)
+    log_file: Option<FileMakeWriter>,
+    /// Mod log limiter, so suppressed lines are reported on flush
+    mod_log_limiter: Option<ModLogLimiter>,
+}
+
+impl LogHandle {
+    /// Flush the buffered stdout writer, if buffered stdout logging is enabled
+    pub fn flush_stdout(&self) {
+        if let Some(buffer) = &self.stdout_buffer {
+            let _ = lock_buffer(buffer).flush();
+        }
+    }
+
+    /// Flush all log output and close the log file
+    ///
+    /// Flushes the buffered stdout writer (if enabled), then flushes and drops the
+    /// log file writer so its tail is not lost. Call this right before exiting the
+    /// process, especially via `std::process::exit`, which skips destructors.
+    /// Events logged afterwards still reach stdout but are no longer written to the file.
+    pub fn flush(&self) {
+        // Report suppressed mod lines first, so they are flushed with the rest
+        if let Some(limiter) = &self.mod_log_limiter {
+            limiter.flush();
+        }
+        self.flush_stdout();
+        if let Some(file) = &self.log_file {
+            let _ = file.close();
+        }
+    }
+}
+
+/// Field extractor for game_id, runtime_type, mod_id, message and fields fields
 ///
 /// Used by the custom formatter to detect mod-related log messages
 /// and format them as `game_id::js::mod-id: message` or `js::mod-id: message`.
+/// `fields` holds the structured fields of a mod message as a JSON object.
 #[derive(Default)]
 pub struct FieldExtractor {
     pub game_id: Option<String>,
     pub runtime_type: Option<String>,
     pub mod_id: Option<String>,
     pub message: Option<String>,
+    pub fields: Option<String>,
 }
 
 impl Visit for FieldExtractor {
@@ -139,6 +397,7 @@ impl Visit for FieldExtractor {
             "runtime_type" => self.runtime_type = Some(value.to_string()),
             "mod_id" => self.mod_id = Some(value.to_string()),
             "message" => self.message = Some(value.to_string()),
+            "fields" => self.fields = Some(value.to_string()),
             _ => {}
         }
     }
@@ -151,11 +410,51 @@ impl Visit for FieldExtractor {
             }
             "mod_id" => self.mod_id = Some(format!("{:?}", value).trim_matches('"').to_string()),
             "message" => self.message = Some(format!("{:?}", value).trim_matches('"').to_string()),
+            "fields" => self.fields = Some(format!("{:?}", value)),
             _ => {}
         }
     }
 }
 
+/// Whether a key or string value can be printed without quotes in `key=value` output
+fn is_bare_word(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '/'))
+}
+
+/// Write a key or string value, JSON-quoted (and escaped) unless it is a bare word
+fn write_field_text(writer: &mut Writer<'_>, text: &str) -> std_fmt::Result {
+    if is_bare_word(text) {
+        write!(writer, "{}", text)
+    } else {
+        write!(writer, "{}", serde_json::Value::from(text))
+    }
+}
+
+/// Write the structured fields of a mod message as ` key=value` pairs
+///
+/// `fields` is a JSON object, written in key order. Strings are quoted when they contain spaces, quotes,
+/// `=` or control characters; arrays and objects are written as compact JSON.
+/// Anything else than an object is written as a single `fields=...` pair.
+fn write_structured_fields(writer: &mut Writer<'_>, fields: &str) -> std_fmt::Result {
+    let Ok(serde_json::Value::Object(fields)) = serde_json::from_str::<serde_json::Value>(fields) else {
+        write!(writer, " fields=")?;
+        return write_field_text(writer, fields);
+    };
+    for (key, value) in &fields {
+        write!(writer, " ")?;
+        write_field_text(writer, key)?;
+        write!(writer, "=")?;
+        match value {
+            serde_json::Value::String(text) => write_field_text(writer, text)?,
+            other => write!(writer, "{}", other)?,
+        }
+    }
+    Ok(())
+}
+
 /// Custom event formatter for Staminal applications
 ///
 /// Features:
@@ -163,11 +462,17 @@ impl Visit for FieldExtractor {
 /// - Mod logs formatted as `js::mod-id: message`
 /// - Configurable ANSI color support
 /// - Configurable target prefix stripping
+/// - Optional source file and line after the target
 pub struct CustomFormatter<T> {
     timer: T,
     ansi: bool,
-    /// Prefix to strip from log targets (e.g., "stam_server::" or "stam_client::")
-    strip_prefix: Option<String>,
+    /// Prefixes to strip from log targets (e.g., "stam_server::" or "stam_client::").
+    /// Tried in order; the first matching prefix wins.
+    strip_prefixes: Vec<String>,
+    /// Whether to append `file:line` after the target for non-mod events
+    source_location: bool,
+    /// Limiter whose summaries of suppressed mod lines are written before mod events
+    mod_log_limiter: Option<ModLogLimiter>,
 }
 
 impl<T> CustomFormatter<T> {
@@ -180,18 +485,86 @@ impl<T> CustomFormatter<T> {
         Self {
             timer,
             ansi,
-            strip_prefix: None,
+            strip_prefixes: Vec::new(),
+            source_location: false,
+            mod_log_limiter: None,
         }
     }
 
-    /// Set the prefix to strip from log targets
+    /// Write the summaries of the lines suppressed by a mod log limiter (default: none)
+    ///
+    /// Must be the limiter installed in the same subscriber, which decides what to
+    /// report while the event is dispatched.
+    pub fn with_mod_log_limiter(mut self, limiter: impl Into<Option<ModLogLimiter>>) -> Self {
+        self.mod_log_limiter = limiter.into();
+        self
+    }
+
+    /// Set whether to show the source file and line of non-mod events (default: false)
+    pub fn with_source_location(mut self, enabled: bool) -> Self {
+        self.source_location = enabled;
+        self
+    }
+
+    /// Add a prefix to strip from log targets
     ///
     /// # Arguments
     /// * `prefix` - The prefix to strip (e.g., "stam_server::")
     pub fn with_strip_prefix(mut self, prefix: impl Into<String>) -> Self {
-        self.strip_prefix = Some(prefix.into());
+        self.strip_prefixes.push(prefix.into());
         self
     }
+
+    /// Add several prefixes to strip from log targets
+    ///
+    /// Prefixes are appended after any already configured ones and are tried
+    /// in order when formatting; the first one matching a target is used.
+    ///
+    /// # Arguments
+    /// * `prefixes` - The prefixes to strip (e.g., `["stam_client::", "stam_shared::"]`)
+    pub fn with_strip_prefixes<I, P>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        self.strip_prefixes.extend(prefixes.into_iter().map(Into::into));
+        self
+    }
+}
+
+impl<T: fmt::time::FormatTime> CustomFormatter<T> {
+    /// Write the timestamp, level and thread that start every line
+    fn write_line_start(&self, writer: &mut Writer<'_>, level: &Level) -> std_fmt::Result {
+        let (dim_start, dim_end) = if self.ansi {
+            ("\x1b[2m", "\x1b[0m")
+        } else {
+            ("", "")
+        };
+        let (level_color, level_str) = match *level {
+            Level::ERROR => (if self.ansi { "\x1b[31m" } else { "" }, "ERROR"),
+            Level::WARN => (if self.ansi { "\x1b[33m" } else { "" }, " WARN"),
+            Level::INFO => (if self.ansi { "\x1b[32m" } else { "" }, " INFO"),
+            Level::DEBUG => (if self.ansi { "\x1b[34m" } else { "" }, "DEBUG"),
+            Level::TRACE => (if self.ansi { "\x1b[35m" } else { "" }, "TRACE"),
+        };
+        let color_end = if self.ansi { "\x1b[0m" } else { "" };
+
+        write!(writer, "{}", dim_start)?;
+        self.timer.format_time(writer)?;
+        write!(writer, "{} ", dim_end)?;
+
+        write!(writer, "{}{}{} ", level_color, level_str, color_end)?;
+
+        let thread_id = format!("{:?}", std::thread::current().id());
+        if let Some(num_str) = thread_id
+            .strip_prefix("ThreadId(")
+            .and_then(|s| s.strip_suffix(")"))
+            && let Ok(num) = num_str.parse::<u64>()
+        {
+            write!(writer, "#{:03} ", num)?;
+        }
+        Ok(())
+    }
 }
 
 impl<T: Clone> Clone for CustomFormatter<T> {
@@ -199,7 +572,9 @@ impl<T: Clone> Clone for CustomFormatter<T> {
         Self {
             timer: self.timer.clone(),
             ansi: self.ansi,
-            strip_prefix: self.strip_prefix.clone(),
+            strip_prefixes: self.strip_prefixes.clone(),
+            source_location: self.source_location,
+            mod_log_limiter: self.mod_log_limiter.clone(),
         }
     }
 }
@@ -223,30 +598,6 @@ where
         } else {
             ("", "")
         };
-        let (level_color, level_str) = match *metadata.level() {
-            Level::ERROR => (if self.ansi { "\x1b[31m" } else { "" }, "ERROR"),
-            Level::WARN => (if self.ansi { "\x1b[33m" } else { "" }, " WARN"),
-            Level::INFO => (if self.ansi { "\x1b[32m" } else { "" }, " INFO"),
-            Level::DEBUG => (if self.ansi { "\x1b[34m" } else { "" }, "DEBUG"),
-            Level::TRACE => (if self.ansi { "\x1b[35m" } else { "" }, "TRACE"),
-        };
-        let color_end = if self.ansi { "\x1b[0m" } else { "" };
-
-        write!(writer, "{}", dim_start)?;
-        self.timer.format_time(&mut writer)?;
-        write!(writer, "{} ", dim_end)?;
-
-        write!(writer, "{}{}{} ", level_color, level_str, color_end)?;
-
-        let thread_id = format!("{:?}", std::thread::current().id());
-        if let Some(num_str) = thread_id
-            .strip_prefix("ThreadId(")
-            .and_then(|s| s.strip_suffix(")"))
-        {
-            if let Ok(num) = num_str.parse::<u64>() {
-                write!(writer, "#{:03} ", num)?;
-            }
-        }
 
         // Extract game_id, runtime_type and mod_id fields if present
         let mut extractor = FieldExtractor::default();
@@ -254,41 +605,66 @@ where
 
         // If both runtime_type and mod_id are present, format as "game_id::runtime_type::mod_id:" or "runtime_type::mod_id:"
         if let (Some(rt), Some(mid)) = (&extractor.runtime_type, &extractor.mod_id) {
-            if let Some(gid) = &extractor.game_id {
-                write!(writer, "{}{}::{}::{}{}: ", dim_start, gid, rt, mid, dim_end)?;
-            } else {
-                write!(writer, "{}{}::{}{}: ", dim_start, rt, mid, dim_end)?;
+            let mod_prefix = match &extractor.game_id {
+                Some(gid) => format!("{}{}::{}::{}{}: ", dim_start, gid, rt, mid, dim_end),
+                None => format!("{}{}::{}{}: ", dim_start, rt, mid, dim_end),
+            };
+            // Lines the mod log limiter suppressed before this one
+            let summaries = self.mod_log_limiter.as_ref().map(ModLogLimiter::pending_summaries).unwrap_or_default();
+            for summary in summaries {
+                self.write_line_start(&mut writer, &summary.level)?;
+                writeln!(writer, "{}{}", mod_prefix, summary.text)?;
             }
+            self.write_line_start(&mut writer, metadata.level())?;
+            write!(writer, "{}", mod_prefix)?;
             // Print the message if present
             if let Some(msg) = &extractor.message {
                 write!(writer, "{}", msg)?;
             }
+            if let Some(fields) = &extractor.fields {
+                write_structured_fields(&mut writer, fields)?;
+            }
         } else {
+            self.write_line_start(&mut writer, metadata.level())?;
+
             // Otherwise use the default target formatting
             let target = metadata.target();
 
-            // Check if this target belongs to our app (starts with our prefix)
-            let is_our_code = self.strip_prefix.as_ref()
-                .is_some_and(|prefix| target.starts_with(prefix.trim_end_matches("::")));
+            // Check if this target belongs to our app (starts with one of our prefixes)
+            let matched_prefix = self.strip_prefixes.iter()
+                .find(|prefix| target.starts_with(prefix.trim_end_matches("::")));
 
-            let display_target = if is_our_code {
+            let display_target = match matched_prefix {
                 // Strip our prefix for cleaner output
-                if let Some(prefix) = &self.strip_prefix {
-                    target.strip_prefix(prefix).unwrap_or(target)
-                } else {
-                    target
-                }
-            } else {
+                Some(prefix) => target.strip_prefix(prefix.as_str()).unwrap_or(target),
                 // External dependency - show full target
-                target
+                None => target,
             };
 
             // Also hide the bare app name when it appears alone
-            let app_name = self.strip_prefix.as_ref().map(|p| p.trim_end_matches("::"));
+            let app_name = matched_prefix.map(|p| p.trim_end_matches("::"));
             let should_hide = app_name.is_some_and(|name| display_target == name);
 
-            if !display_target.is_empty() && !should_hide {
-                write!(writer, "{}{}{}: ", dim_start, display_target, dim_end)?;
+            let show_target = !display_target.is_empty() && !should_hide;
+
+            // Source location, omitted when the file is unknown
+            let location = if self.source_location {
+                match (metadata.file(), metadata.line()) {
+                    (Some(file), Some(line)) => Some(format!("{}:{}", file, line)),
+                    (Some(file), None) => Some(file.to_string()),
+                    (None, _) => None,
+                }
+            } else {
+                None
+            };
+
+            match (show_target, location) {
+                (true, Some(location)) => {
+                    write!(writer, "{}{} {}{}: ", dim_start, display_target, location, dim_end)?
+                }
+                (true, None) => write!(writer, "{}{}{}: ", dim_start, display_target, dim_end)?,
+                (false, Some(location)) => write!(writer, "{}{}{}: ", dim_start, location, dim_end)?,
+                (false, None) => {}
             }
             // Use default field formatting
             ctx.field_format().format_fields(writer.by_ref(), event)?;
@@ -334,7 +710,10 @@ pub fn is_dependency_logging_enabled() -> bool {
 ///
 /// When `STAM_LOGDEPS=0` (default), only logs from Staminal code are shown.
 /// When `STAM_LOGDEPS=1`, all logs are shown including external dependencies.
-pub fn build_filter_directives(level: Level, log_deps: bool) -> String {
+///
+/// Crates named by `strip_prefixes` (e.g. `"stam_shared::"`) are treated as
+/// Staminal code as well, so they are not filtered out as dependencies.
+pub fn build_filter_directives(level: Level, log_deps: bool, strip_prefixes: &[String]) -> String {
     let level_str = match level {
         Level::TRACE => "trace",
         Level::DEBUG => "debug",
@@ -349,35 +728,66 @@ pub fn build_filter_directives(level: Level, log_deps: bool) -> String {
     } else {
         // Only show logs from Staminal code (stam_*) at the specified level
         // External dependencies are filtered to OFF to reduce noise completely
-        format!(
+        let mut directives = format!(
             "off,stam_client={level},stam_server={level},stam_protocol={level},stam_schema={level},stam_mod_runtimes={level},stam_log={level},js={level}",
             level = level_str
-        )
+        );
+
+        // Any additional crates named by our prefixes are our code too
+        for prefix in strip_prefixes {
+            let crate_name = prefix.split("::").next().unwrap_or_default();
+            if crate_name.is_empty() || directives.contains(&format!(",{}=", crate_name)) {
+                continue;
+            }
+            directives.push_str(&format!(",{}={}", crate_name, level_str));
+        }
+
+        directives
     }
 }
 
 /// Detect if ANSI colors should be used based on environment
 ///
-/// Disables ANSI colors if:
-/// - stdout is not a TTY (piped/redirected)
-/// - NO_COLOR env var is set (https://no-color.org/)
-/// - TERM=dumb
+/// Checked in order:
+/// - FORCE_COLOR env var forces colors on (`0` or `false` forces them off)
+/// - NO_COLOR env var disables colors (https://no-color.org/)
+/// - stdout is not a TTY (piped/redirected) disables colors
+/// - TERM=dumb disables colors
 pub fn should_use_ansi() -> bool {
-    atty::is(atty::Stream::Stdout)
-        && std::env::var("NO_COLOR").is_err()
-        && std::env::var("TERM").map(|t| t != "dumb").unwrap_or(true)
+    ansi_from_env(
+        std::env::var("FORCE_COLOR").ok().as_deref(),
+        std::env::var_os("NO_COLOR").is_some(),
+        atty::is(atty::Stream::Stdout),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+/// Resolve the ANSI decision from already-read environment values
+fn ansi_from_env(force_color: Option<&str>, no_color: bool, is_tty: bool, term: Option<&str>) -> bool {
+    if let Some(force) = force_color {
+        return !matches!(force.trim(), "0" | "false");
+    }
+    !no_color && is_tty && term != Some("dumb")
 }
 
 /// Logging configuration
 pub struct LogConfig<W: Write + Send + 'static = std::fs::File> {
     /// Prefix to strip from log targets (e.g., "stam_client::")
     pub strip_prefix: String,
-    /// Whether to use ANSI color codes (auto-detected if None)
+    /// Additional prefixes to strip, tried after `strip_prefix` (e.g., "stam_shared::")
+    pub extra_strip_prefixes: Vec<String>,
+    /// Whether to use ANSI color codes (auto-detected via `should_use_ansi()` if None)
     pub use_ansi: Option<bool>,
     /// Minimum log level
     pub level: Level,
     /// Optional file to write logs to
     pub log_file: Option<W>,
+    /// Whether stdout output is batched through a `BufWriterMakeWriter`
+    pub buffered_stdout: bool,
+    /// Whether to show the source file and line of non-mod events
+    pub source_location: bool,
+    /// Limits on the lines of each mod (None = no limit)
+    pub mod_log_limit: Option<ModLogLimit>,
 }
 
 impl<W: Write + Send + 'static> LogConfig<W> {
@@ -385,18 +795,40 @@ impl<W: Write + Send + 'static> LogConfig<W> {
     pub fn new(strip_prefix: impl Into<String>) -> Self {
         Self {
             strip_prefix: strip_prefix.into(),
+            extra_strip_prefixes: Vec::new(),
             use_ansi: None,
             level: Level::DEBUG,
             log_file: None,
+            buffered_stdout: false,
+            source_location: false,
+            mod_log_limit: None,
         }
     }
 
+    /// Add more prefixes to strip from log targets, tried after the main one
+    pub fn with_strip_prefixes<I, P>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<String>,
+    {
+        self.extra_strip_prefixes.extend(prefixes.into_iter().map(Into::into));
+        self
+    }
+
     /// Set whether to use ANSI colors (default: auto-detect)
-    pub fn with_ansi(mut self, use_ansi: bool) -> Self {
-        self.use_ansi = Some(use_ansi);
+    ///
+    /// `Some(bool)` (or a plain `bool`) skips environment detection entirely and
+    /// applies to both the formatter and the stdout layer. `None` restores auto-detection.
+    pub fn with_ansi(mut self, use_ansi: impl Into<Option<bool>>) -> Self {
+        self.use_ansi = use_ansi.into();
         self
     }
 
+    /// Resolve the effective ANSI setting, honoring an explicit override first
+    pub fn resolve_ansi(&self) -> bool {
+        self.use_ansi.unwrap_or_else(should_use_ansi)
+    }
+
     /// Set the minimum log level
     pub fn with_level(mut self, level: Level) -> Self {
         self.level = level;
@@ -408,6 +840,31 @@ impl<W: Write + Send + 'static> LogConfig<W> {
         self.log_file = Some(file);
         self
     }
+
+    /// Set whether stdout output is buffered (default: false)
+    ///
+    /// Buffered output is flushed periodically and when the buffer is full.
+    /// Call `LogHandle::flush()` before exiting the process.
+    pub fn with_buffered_stdout(mut self, buffered: bool) -> Self {
+        self.buffered_stdout = buffered;
+        self
+    }
+
+    /// Set whether to show `file:line` after the target (default: false)
+    ///
+    /// Only applies to Rust call sites; mod log lines are unchanged.
+    pub fn with_source_location(mut self, enabled: bool) -> Self {
+        self.source_location = enabled;
+        self
+    }
+
+    /// Collapse repeated mod lines and rate-limit noisy mods (default: no limit)
+    ///
+    /// Non-mod events and ERROR events are never limited.
+    pub fn with_mod_log_limit(mut self, limit: impl Into<Option<ModLogLimit>>) -> Self {
+        self.mod_log_limit = limit.into();
+        self
+    }
 }
 
 /// Initialize logging with the given configuration
@@ -415,6 +872,9 @@ impl<W: Write + Send + 'static> LogConfig<W> {
 /// # Arguments
 /// * `config` - Logging configuration
 ///
+/// # Returns
+/// The handle used to flush the installed writers before exiting
+///
 /// # Environment Variables
 ///
 /// * `STAM_LOGDEPS` - Set to `1` to enable logging from external dependencies (bevy, wgpu, etc.).
@@ -429,59 +889,86 @@ impl<W: Write + Send + 'static> LogConfig<W> {
 ///
 /// let config = LogConfig::new("stam_client::")
 ///     .with_level(Level::DEBUG);
-/// init_logging(config)?;
+/// let log = init_logging(config)?;
 /// ```
 pub fn init_logging<W: Write + Send + 'static>(
     config: LogConfig<W>,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<LogHandle, Box<dyn std::error::Error>> {
     use tracing_subscriber::EnvFilter;
 
     let timer = create_custom_timer();
-    let use_ansi = config.use_ansi.unwrap_or_else(should_use_ansi);
+    let use_ansi = config.resolve_ansi();
     let log_deps = is_dependency_logging_enabled();
-    let filter_directives = build_filter_directives(config.level, log_deps);
+    let strip_prefixes: Vec<String> = std::iter::once(config.strip_prefix.clone())
+        .chain(config.extra_strip_prefixes.iter().cloned())
+        .collect();
+    let filter_directives = build_filter_directives(config.level, log_deps, &strip_prefixes);
 
     // Create the env filter - allows RUST_LOG to override our defaults
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(&filter_directives));
 
+    let limiter = config.mod_log_limit.map(ModLogLimiter::new);
+    let mut handle = LogHandle {
+        mod_log_limiter: limiter.clone(),
+        ..LogHandle::default()
+    };
+    let stdout_writer = if config.buffered_stdout {
+        let writer = BufWriterMakeWriter::default();
+        handle.stdout_buffer = Some(writer.buffer.clone());
+        BoxMakeWriter::new(writer)
+    } else {
+        BoxMakeWriter::new(RawModeStdoutWriter::default())
+    };
+
     if let Some(file) = config.log_file {
+        let file_writer = FileMakeWriter::new(file);
+        handle.log_file = Some(file_writer.clone());
+
         let formatter_stdout = CustomFormatter::new(timer.clone(), use_ansi)
-            .with_strip_prefix(&config.strip_prefix);
+            .with_strip_prefixes(strip_prefixes.iter().cloned())
+            .with_source_location(config.source_location)
+            .with_mod_log_limiter(limiter.clone());
         let formatter_file = CustomFormatter::new(timer, false)
-            .with_strip_prefix(&config.strip_prefix);
+            .with_strip_prefixes(strip_prefixes)
+            .with_source_location(config.source_location)
+            .with_mod_log_limiter(limiter.clone());
 
         tracing_subscriber::registry()
             .with(
                 tracing_subscriber::fmt::layer()
                     .event_format(formatter_stdout)
                     .with_ansi(use_ansi)
-                    .with_writer(RawModeStdoutWriter),
+                    .with_writer(stdout_writer),
             )
             .with(
                 tracing_subscriber::fmt::layer()
                     .event_format(formatter_file)
                     .with_ansi(false)
-                    .with_writer(std::sync::Mutex::new(file)),
+                    .with_writer(file_writer),
             )
+            .with(limiter)
             .with(env_filter)
             .init();
     } else {
         let formatter = CustomFormatter::new(timer, use_ansi)
-            .with_strip_prefix(&config.strip_prefix);
+            .with_strip_prefixes(strip_prefixes)
+            .with_source_location(config.source_location)
+            .with_mod_log_limiter(limiter.clone());
 
         tracing_subscriber::registry()
             .with(
                 tracing_subscriber::fmt::layer()
                     .event_format(formatter)
                     .with_ansi(use_ansi)
-                    .with_writer(RawModeStdoutWriter),
+                    .with_writer(stdout_writer),
             )
+            .with(limiter)
             .with(env_filter)
             .init();
     }
 
-    Ok(())
+    Ok(handle)
 }
 
 /// Initialize logging without a log file
@@ -490,8 +977,239 @@ pub fn init_logging<W: Write + Send + 'static>(
 pub fn init_logging_simple(
     strip_prefix: impl Into<String>,
     level: Level,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<LogHandle, Box<dyn std::error::Error>> {
     let config: LogConfig<std::fs::File> = LogConfig::new(strip_prefix)
         .with_level(level);
     init_logging(config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_mode_writer_no_double_cr_across_writes() {
+        let last_was_cr = Arc::new(AtomicBool::new(false));
+
+        let mut writer = RawModeWriter::with_raw_mode(Vec::new(), true, last_was_cr.clone());
+        writer.write_all(b"line\r").unwrap();
+        writer.write_all(b"\nnext").unwrap();
+        writer.write_all(b"\n").unwrap();
+        assert_eq!(writer.inner, b"line\r\nnext\r\n");
+
+        // A new writer per event (as tracing does) still sees the previous `\r`
+        RawModeWriter::with_raw_mode(Vec::new(), true, last_was_cr.clone()).write_all(b"end\r").unwrap();
+        let mut writer = RawModeWriter::with_raw_mode(Vec::new(), true, last_was_cr);
+        writer.write_all(b"\n").unwrap();
+        assert_eq!(writer.inner, b"\n");
+    }
+
+    /// Format a single event with the given formatter and return the output line
+    fn format_with(formatter: CustomFormatter<()>, emit: impl FnOnce()) -> String {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let sink = output.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .event_format(formatter)
+                .with_writer(move || SharedBuf(sink.clone())),
+        );
+        tracing::subscriber::with_default(subscriber, emit);
+        let bytes = output.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_source_location_off_by_default() {
+        let line = format_with(CustomFormatter::new((), false), || {
+            tracing::info!(target: "stam_test::loader", "hello");
+        });
+        assert!(line.contains("stam_test::loader: hello"), "{}", line);
+        assert!(!line.contains("lib.rs"), "{}", line);
+    }
+
+    #[test]
+    fn test_source_location_after_target() {
+        let formatter = CustomFormatter::new((), false).with_source_location(true);
+        let line = format_with(formatter, || {
+            tracing::info!(target: "stam_test::loader", "hello");
+        });
+        assert!(line.contains("stam_test::loader src/lib.rs:"), "{}", line);
+        assert!(line.trim_end().ends_with(": hello"), "{}", line);
+    }
+
+    #[test]
+    fn test_source_location_skipped_for_mod_events() {
+        let formatter = CustomFormatter::new((), false).with_source_location(true);
+        let line = format_with(formatter, || {
+            tracing::info!(runtime_type = "js", mod_id = "my-mod", "from mod");
+        });
+        assert!(line.contains("js::my-mod: from mod"), "{}", line);
+        assert!(!line.contains("lib.rs"), "{}", line);
+    }
+
+    #[test]
+    fn test_mod_fields_as_key_values() {
+        let line = format_with(CustomFormatter::new((), false), || {
+            tracing::info!(
+                runtime_type = "js",
+                mod_id = "my-mod",
+                fields = r#"{"userId":42,"name":"Jo \"J\" Doe","role":"admin","tags":["a","b"],"note":"a\nb"}"#,
+                message = "login"
+            );
+        });
+        assert!(
+            line.contains(r#"js::my-mod: login name="Jo \"J\" Doe" note="a\nb" role=admin tags=["a","b"] userId=42"#),
+            "{}",
+            line
+        );
+        // The escaped newline keeps the event on one line
+        assert_eq!(line.lines().count(), 1, "{}", line);
+    }
+
+    #[test]
+    fn test_limiter_collapses_repeated_mod_lines() {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let sink = output.clone();
+        let limiter = ModLogLimiter::new(ModLogLimit::new());
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .event_format(CustomFormatter::new((), false).with_mod_log_limiter(limiter.clone()))
+                    .with_writer(move || SharedBuf(sink.clone())),
+            )
+            .with(limiter.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..5 {
+                tracing::info!(runtime_type = "js", mod_id = "noisy", message = "tick");
+                tracing::info!(target: "stam_test::loader", "core tick");
+            }
+            tracing::info!(runtime_type = "js", mod_id = "noisy", message = "done");
+            tracing::info!(runtime_type = "js", mod_id = "noisy", message = "done");
+            limiter.flush();
+        });
+        let text = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = text.lines().map(|line| line.split_once("#").map_or(line, |(_, rest)| rest)).collect();
+
+        // Core lines are never limited
+        assert_eq!(lines.iter().filter(|line| line.ends_with("core tick")).count(), 5, "{}", text);
+        let mod_lines: Vec<&str> = lines.iter().filter(|line| line.contains("js::noisy")).copied().collect();
+        assert_eq!(mod_lines.len(), 4, "{}", text);
+        assert!(mod_lines[0].ends_with("js::noisy: tick"), "{}", text);
+        assert!(mod_lines[1].ends_with("js::noisy: (last message repeated 4 times)"), "{}", text);
+        assert!(mod_lines[2].ends_with("js::noisy: done"), "{}", text);
+        assert!(text.find("repeated 4 times").unwrap() > text.rfind("core tick").unwrap(), "{}", text);
+        assert!(mod_lines[3].ends_with("js::noisy: (last message repeated 1 time)"), "{}", text);
+    }
+
+    #[test]
+    fn test_limiter_summaries_reach_every_formatter() {
+        let stdout = Arc::new(Mutex::new(Vec::new()));
+        let file = Arc::new(Mutex::new(Vec::new()));
+        let (stdout_sink, file_sink) = (stdout.clone(), file.clone());
+        let limiter = ModLogLimiter::new(ModLogLimit::new());
+        // Same layout as init_logging with a log file: one limiter, two formatters
+        let subscriber = tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .event_format(CustomFormatter::new((), false).with_mod_log_limiter(limiter.clone()))
+                    .with_writer(move || SharedBuf(stdout_sink.clone())),
+            )
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .event_format(CustomFormatter::new((), false).with_mod_log_limiter(limiter.clone()))
+                    .with_writer(move || SharedBuf(file_sink.clone())),
+            )
+            .with(limiter);
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..3 {
+                tracing::info!(runtime_type = "js", mod_id = "noisy", message = "tick");
+            }
+            tracing::info!(runtime_type = "js", mod_id = "noisy", message = "done");
+            tracing::info!(runtime_type = "js", mod_id = "quiet", message = "hello");
+        });
+
+        for output in [stdout, file] {
+            let text = String::from_utf8(output.lock().unwrap().clone()).unwrap();
+            let lines: Vec<&str> = text.lines().collect();
+            assert_eq!(lines.len(), 4, "{}", text);
+            assert!(lines[1].ends_with("js::noisy: (last message repeated 2 times)"), "{}", text);
+            assert!(lines[2].ends_with("js::noisy: done"), "{}", text);
+            // The summary is written once, not again before the next event
+            assert!(lines[3].ends_with("js::quiet: hello"), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_no_color_disables_ansi() {
+        assert!(!ansi_from_env(None, true, true, Some("xterm-256color")));
+        assert!(ansi_from_env(None, false, true, Some("xterm-256color")));
+    }
+
+    #[test]
+    fn test_force_color_wins_over_no_color() {
+        assert!(ansi_from_env(Some("1"), true, true, Some("xterm")));
+        // Forcing also overrides a missing TTY and TERM=dumb (tmux, CI runners)
+        assert!(ansi_from_env(Some("1"), false, false, Some("dumb")));
+        assert!(ansi_from_env(Some(""), false, false, None));
+    }
+
+    #[test]
+    fn test_force_color_zero_disables_ansi() {
+        assert!(!ansi_from_env(Some("0"), false, true, Some("xterm")));
+        assert!(!ansi_from_env(Some("false"), false, true, Some("xterm")));
+    }
+
+    #[test]
+    fn test_explicit_ansi_bypasses_detection() {
+        let config: LogConfig = LogConfig::new("stam_test::").with_ansi(true);
+        assert!(config.resolve_ansi());
+        let config: LogConfig = LogConfig::new("stam_test::").with_ansi(Some(false));
+        assert!(!config.resolve_ansi());
+    }
+
+    #[test]
+    fn test_flush_writes_and_closes_log_file() {
+        let path = std::env::temp_dir().join(format!("stam_log_flush_{}.log", std::process::id()));
+        // A large BufWriter keeps everything in memory until flushed
+        let file = io::BufWriter::with_capacity(64 * 1024, std::fs::File::create(&path).unwrap());
+        let writer = FileMakeWriter::new(file);
+        let handle = LogHandle {
+            log_file: Some(writer.clone()),
+            ..LogHandle::default()
+        };
+
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .event_format(CustomFormatter::new((), false))
+                .with_writer(writer.clone()),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "stam_test::shutdown", "last words");
+        });
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+
+        handle.flush();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("stam_test::shutdown: last words"), "{}", content);
+
+        // The file is closed: later events are discarded instead of failing
+        writer.make_writer().write_all(b"late\n").unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("late"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,333 @@
+//! Rate limiting of mod log lines
+//!
+//! A mod logging in a tight loop would otherwise flood the terminal and the log file.
+//! `ModLogLimiter` is a layer that looks at every mod event (events with `runtime_type`
+//! and `mod_id` fields, see `FieldExtractor`) and:
+//! - collapses identical consecutive lines of a mod into `(last message repeated N times)`,
+//!   like syslog
+//! - drops the lines of a mod beyond a maximum per second, reporting how many were dropped
+//!
+//! Non-mod events and ERROR events always pass.
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::FieldExtractor;
+
+/// Target of the summary lines emitted by the limiter (never limited themselves)
+const LIMITER_TARGET: &str = "stam_log::limiter";
+
+/// Length of the window `max_lines_per_second` is counted over
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Thresholds of the mod log limiter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModLogLimit {
+    /// Maximum lines per second of a single mod (None = unlimited)
+    pub max_lines_per_second: Option<u32>,
+    /// Whether identical consecutive lines of a mod are collapsed
+    pub collapse_repeats: bool,
+}
+
+impl ModLogLimit {
+    /// Create the default limit: repeats collapsed, at most 100 lines per second per mod
+    pub fn new() -> Self {
+        Self {
+            max_lines_per_second: Some(100),
+            collapse_repeats: true,
+        }
+    }
+
+    /// Set the maximum lines per second of a single mod (None = unlimited)
+    pub fn with_max_lines_per_second(mut self, max: impl Into<Option<u32>>) -> Self {
+        self.max_lines_per_second = max.into();
+        self
+    }
+
+    /// Set whether identical consecutive lines of a mod are collapsed (default: true)
+    pub fn with_collapse_repeats(mut self, enabled: bool) -> Self {
+        self.collapse_repeats = enabled;
+        self
+    }
+}
+
+impl Default for ModLogLimit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A mod as seen in log events
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ModKey {
+    game_id: Option<String>,
+    runtime_type: String,
+    mod_id: String,
+}
+
+/// A line the limiter writes in place of suppressed ones
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Summary {
+    pub level: Level,
+    pub text: String,
+}
+
+/// What the limiter remembers about a mod
+struct ModState {
+    window_start: Instant,
+    /// Lines shown in the current window
+    lines: u32,
+    /// Lines dropped by the rate limit and not reported yet
+    dropped: u32,
+    /// Fingerprint and level of the last line shown
+    last: Option<(u64, Level)>,
+    /// Times the last line was repeated and not reported yet, with its level
+    repeats: u32,
+    repeats_level: Level,
+}
+
+impl ModState {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            lines: 0,
+            dropped: 0,
+            last: None,
+            repeats: 0,
+            repeats_level: Level::INFO,
+        }
+    }
+
+    /// Report (and forget) the suppressed lines
+    fn take_summaries(&mut self) -> Vec<Summary> {
+        let mut summaries = Vec::new();
+        if self.repeats > 0 {
+            let times = if self.repeats == 1 { "time" } else { "times" };
+            summaries.push(Summary {
+                level: self.repeats_level,
+                text: format!("(last message repeated {} {})", self.repeats, times),
+            });
+            self.repeats = 0;
+        }
+        if self.dropped > 0 {
+            summaries.push(Summary {
+                level: Level::WARN,
+                text: format!("({} lines dropped by the log rate limit)", self.dropped),
+            });
+            self.dropped = 0;
+        }
+        summaries
+    }
+}
+
+/// Layer limiting the log lines of each mod
+///
+/// Cloning shares the state, so the same limiter can be installed, given to the
+/// formatters (`CustomFormatter::with_mod_log_limiter`) and flushed.
+#[derive(Clone)]
+pub struct ModLogLimiter {
+    limit: ModLogLimit,
+    mods: Arc<Mutex<HashMap<ModKey, ModState>>>,
+    /// Summaries to write before the mod event being dispatched on each thread
+    ///
+    /// Events can't be emitted while another one is dispatched (tracing drops them), so
+    /// the limiter hands the summaries to `CustomFormatter` instead, which writes them
+    /// before the event line. Every formatter of the event sees the same summaries.
+    pending: Arc<Mutex<HashMap<ThreadId, Vec<Summary>>>>,
+}
+
+impl ModLogLimiter {
+    /// Create a limiter with the given thresholds
+    pub fn new(limit: ModLogLimit) -> Self {
+        Self {
+            limit,
+            mods: Arc::new(Mutex::new(HashMap::new())),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Summaries to write before the event being formatted on the current thread
+    pub(crate) fn pending_summaries(&self) -> Vec<Summary> {
+        let pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.get(&thread::current().id()).cloned().unwrap_or_default()
+    }
+
+    /// Set the summaries of the event being dispatched on the current thread
+    fn set_pending_summaries(&self, summaries: Vec<Summary>) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        // Only threads with something to report keep an entry
+        if summaries.is_empty() {
+            pending.remove(&thread::current().id());
+        } else {
+            pending.insert(thread::current().id(), summaries);
+        }
+    }
+
+    /// Decide whether a mod line is shown, with the summaries to write before it
+    fn check(&self, key: ModKey, level: Level, message: &str, now: Instant) -> Option<Vec<Summary>> {
+        let mut mods = self.mods.lock().unwrap_or_else(|e| e.into_inner());
+        let state = mods.entry(key).or_insert_with(|| ModState::new(now));
+
+        if now.duration_since(state.window_start) >= RATE_WINDOW {
+            state.window_start = now;
+            state.lines = 0;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        message.hash(&mut hasher);
+        let fingerprint = hasher.finish();
+
+        // Repeats of the line just shown don't count towards the rate
+        if self.limit.collapse_repeats && state.last == Some((fingerprint, level)) {
+            state.repeats += 1;
+            state.repeats_level = level;
+            return None;
+        }
+        if self.limit.max_lines_per_second.is_some_and(|max| state.lines >= max) {
+            state.dropped += 1;
+            state.last = None;
+            return None;
+        }
+        state.lines += 1;
+        state.last = Some((fingerprint, level));
+        Some(state.take_summaries())
+    }
+
+    /// Write the summaries of all lines suppressed so far
+    ///
+    /// Without this, the repeats of a mod's last line are only reported when the mod
+    /// logs something else. Called by `LogHandle::flush()`.
+    pub fn flush(&self) {
+        let pending: Vec<(ModKey, Vec<Summary>)> = {
+            let mut mods = self.mods.lock().unwrap_or_else(|e| e.into_inner());
+            mods.iter_mut()
+                .map(|(key, state)| (key.clone(), state.take_summaries()))
+                .filter(|(_, summaries)| !summaries.is_empty())
+                .collect()
+        };
+        for (key, summaries) in &pending {
+            for summary in summaries {
+                emit_summary(key, summary);
+            }
+        }
+    }
+}
+
+/// Write a summary line as an event of the mod
+fn emit_summary(key: &ModKey, summary: &Summary) {
+    macro_rules! summary_event {
+        ($level:expr) => {
+            match &key.game_id {
+                Some(game_id) => tracing::event!(
+                    target: LIMITER_TARGET,
+                    $level,
+                    game_id = game_id.as_str(),
+                    runtime_type = key.runtime_type.as_str(),
+                    mod_id = key.mod_id.as_str(),
+                    message = summary.text.as_str()
+                ),
+                None => tracing::event!(
+                    target: LIMITER_TARGET,
+                    $level,
+                    runtime_type = key.runtime_type.as_str(),
+                    mod_id = key.mod_id.as_str(),
+                    message = summary.text.as_str()
+                ),
+            }
+        };
+    }
+
+    match summary.level {
+        Level::ERROR => summary_event!(Level::ERROR),
+        Level::WARN => summary_event!(Level::WARN),
+        Level::INFO => summary_event!(Level::INFO),
+        Level::DEBUG => summary_event!(Level::DEBUG),
+        Level::TRACE => summary_event!(Level::TRACE),
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ModLogLimiter {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        self.set_pending_summaries(Vec::new());
+
+        let metadata = event.metadata();
+        if *metadata.level() == Level::ERROR || metadata.target() == LIMITER_TARGET {
+            return true;
+        }
+
+        let mut extractor = FieldExtractor::default();
+        event.record(&mut extractor);
+        let (Some(runtime_type), Some(mod_id)) = (extractor.runtime_type, extractor.mod_id) else {
+            return true;
+        };
+
+        let key = ModKey {
+            game_id: extractor.game_id,
+            runtime_type,
+            mod_id,
+        };
+        let message = extractor.message.unwrap_or_default();
+        match self.check(key, *metadata.level(), &message, Instant::now()) {
+            Some(summaries) => {
+                self.set_pending_summaries(summaries);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(mod_id: &str) -> ModKey {
+        ModKey {
+            game_id: None,
+            runtime_type: "js".to_string(),
+            mod_id: mod_id.to_string(),
+        }
+    }
+
+    fn texts(summaries: &[Summary]) -> Vec<&str> {
+        summaries.iter().map(|summary| summary.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_repeats_collapse() {
+        let limiter = ModLogLimiter::new(ModLogLimit::new().with_max_lines_per_second(None));
+        let now = Instant::now();
+
+        assert_eq!(limiter.check(key("a"), Level::INFO, "tick", now), Some(vec![]));
+        assert_eq!(limiter.check(key("a"), Level::INFO, "tick", now), None);
+        assert_eq!(limiter.check(key("a"), Level::INFO, "tick", now), None);
+        // Another mod logging the same line is not a repeat
+        assert_eq!(limiter.check(key("b"), Level::INFO, "tick", now), Some(vec![]));
+
+        let summaries = limiter.check(key("a"), Level::INFO, "done", now).unwrap();
+        assert_eq!(texts(&summaries), vec!["(last message repeated 2 times)"]);
+    }
+
+    #[test]
+    fn test_rate_limit_per_mod() {
+        let limiter = ModLogLimiter::new(ModLogLimit::new().with_max_lines_per_second(2));
+        let now = Instant::now();
+
+        assert!(limiter.check(key("a"), Level::DEBUG, "1", now).is_some());
+        assert!(limiter.check(key("a"), Level::DEBUG, "2", now).is_some());
+        assert!(limiter.check(key("a"), Level::DEBUG, "3", now).is_none());
+        assert!(limiter.check(key("a"), Level::DEBUG, "4", now).is_none());
+        assert!(limiter.check(key("b"), Level::DEBUG, "1", now).is_some());
+
+        // The first line shown in the next window reports the dropped ones
+        let summaries = limiter.check(key("a"), Level::DEBUG, "5", now + RATE_WINDOW).unwrap();
+        assert_eq!(texts(&summaries), vec!["(2 lines dropped by the log rate limit)"]);
+        assert_eq!(summaries[0].level, Level::WARN);
+    }
+}
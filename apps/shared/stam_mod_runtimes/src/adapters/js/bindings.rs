@@ -4,22 +4,23 @@
 //!
 //! # Timer System Architecture
 //!
-//! The timer system (setTimeout, setInterval, etc.) uses a global registry approach:
+//! The timer system (setTimeout, setInterval, etc.) is built on:
 //!
 //! - **`NEXT_TIMER_ID`**: Atomic counter ensuring unique IDs across ALL runtime instances
 //!   (JavaScript, Lua, C#, etc.). This prevents ID collisions even in multi-runtime scenarios.
 //!
-//! - **`TIMER_ABORT_HANDLES`**: Global registry mapping timer IDs to cancellation handles.
-//!   Since IDs are globally unique, this allows `clearTimeout(id)` to work correctly
-//!   regardless of which runtime or mod created the timer.
+//! - **`TimerRegistry`**: Per-adapter registry mapping timer IDs to cancellation handles
+//!   and the mod that created them. The server runs one adapter per game, so keeping
+//!   the registry per adapter means unloading a mod cancels its timers in that game only.
+//!   The owning mod is captured when the context is set up, not read from `__MOD_ID__`.
 //!
 //! This design supports:
 //! - Multiple runtime types (JS + Lua + C#) running simultaneously
-//! - Multiple instances of the same runtime type (e.g., for testing)
+//! - Multiple instances of the same runtime type (one per game on the server, tests)
 //! - Thread-safe timer creation and cancellation
 //!
 //! Note: When a QuickJS runtime is dropped, spawned tasks are automatically cancelled,
-//! but their entries remain in TIMER_ABORT_HANDLES until the task cleanup runs.
+//! but their entries remain in the registry until the task cleanup runs.
 //! This is acceptable because the Notify handles are small and will be cleaned up
 //! when the spawned task completes or is aborted.
 //!
@@ -29,18 +30,22 @@
 //! external .js files in the `glue/` directory. These files are concatenated at compile
 //! time by build.rs and embedded into the binary.
 
-use crate::api::{AppApi, ConsoleApi, FileApi, LocaleApi, NetworkApi, ReadJsonResult, RequestUriProtocol, SystemApi, SystemEvents, ModSide};
+use crate::api::process::{OutputStream, SpawnError, SpawnOutput, spawn_process};
+use crate::api::{AppApi, CancelToken, Capability, ConsoleApi, ConsoleFields, FileApi, FileSystemApi, LocaleApi, NetworkApi, ReadJsonResult, StorageApi, TransferStats, RequestUriProtocol, SystemApi, SystemEvents, ModSide, ShutdownRequest};
 use crate::api::path_security::{validate_path_for_creation, ModPathConfig, resolve_mod_path};
 
 /// JavaScript glue code - embedded at compile time from src/adapters/js/glue/*.js
 /// This code sets up console, error handlers, and other runtime utilities.
 const JS_GLUE_CODE: &str = include_str!("glue/main.js");
-use rquickjs::{Array, Ctx, Function, JsLifetime, Object, Value, class::Trace, function::{Opt, Rest}};
+use base64::Engine;
+use fluent_bundle::{FluentArgs, FluentValue};
+use rquickjs::{Array, Ctx, Function, JsLifetime, Object, Persistent, Value, class::Trace, function::{Opt, Rest}};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 use tokio::sync::Notify;
 
 /// Unique timer ID counter - globally atomic to ensure unique IDs across ALL runtimes
@@ -56,14 +61,96 @@ pub fn next_timer_id() -> u32 {
     NEXT_TIMER_ID.fetch_add(1, Ordering::SeqCst)
 }
 
-/// Global timer cancellation registry
-/// Maps timer_id -> Notify handle for cancellation
+/// A pending timer in a `TimerRegistry`
+struct TimerHandle {
+    /// Notified to cancel the timer
+    abort: Arc<Notify>,
+    /// Mod that created the timer
+    mod_id: String,
+}
+
+/// Timer cancellation registry of one runtime adapter
+/// Maps timer_id -> cancellation handle and owning mod
 ///
-/// This is global (not per-runtime) because timer IDs are globally unique.
-/// This allows clearTimeout/clearInterval to work correctly even if called
-/// from a different context than the one that created the timer.
-static TIMER_ABORT_HANDLES: std::sync::LazyLock<std::sync::Mutex<HashMap<u32, Arc<Notify>>>> =
-    std::sync::LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+/// Shared by every mod context of the adapter, so clearTimeout/clearInterval work
+/// even if called from a different context than the one that created the timer.
+/// Cloning shares the registry.
+#[derive(Clone, Default)]
+pub struct TimerRegistry {
+    handles: Arc<Mutex<HashMap<u32, TimerHandle>>>,
+}
+
+impl TimerRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the abort handle of a timer created by `mod_id`
+    pub fn register(&self, timer_id: u32, mod_id: &str, abort: Arc<Notify>) {
+        let mut handles = self.handles.lock().unwrap();
+        handles.insert(
+            timer_id,
+            TimerHandle {
+                abort,
+                mod_id: mod_id.to_string(),
+            },
+        );
+    }
+
+    /// Remove the abort handle of a timer (called when the timer completes)
+    pub fn remove(&self, timer_id: u32) {
+        let mut handles = self.handles.lock().unwrap();
+        handles.remove(&timer_id);
+    }
+
+    /// Cancel a timer by ID (no-op for unknown IDs)
+    pub fn clear(&self, timer_id: u32) {
+        let abort = {
+            let handles = self.handles.lock().unwrap();
+            handles.get(&timer_id).map(|handle| handle.abort.clone())
+        };
+
+        if let Some(abort) = abort {
+            abort.notify_one();
+        }
+    }
+
+    /// Count the pending timers
+    pub fn pending_count(&self) -> usize {
+        self.handles.lock().unwrap().len()
+    }
+
+    /// Count the pending timers created by a mod
+    pub fn pending_count_for_mod(&self, mod_id: &str) -> usize {
+        let handles = self.handles.lock().unwrap();
+        handles.values().filter(|handle| handle.mod_id == mod_id).count()
+    }
+
+    /// Cancel every pending timer created by a mod
+    ///
+    /// The timers are removed from the registry right away; their tasks exit the
+    /// next time the event loop polls them.
+    ///
+    /// # Returns
+    /// The number of timers cancelled
+    pub fn cancel_for_mod(&self, mod_id: &str) -> usize {
+        let cancelled: Vec<TimerHandle> = {
+            let mut handles = self.handles.lock().unwrap();
+            let ids: Vec<u32> = handles
+                .iter()
+                .filter(|(_, handle)| handle.mod_id == mod_id)
+                .map(|(id, _)| *id)
+                .collect();
+            ids.iter().filter_map(|id| handles.remove(id)).collect()
+        };
+
+        for handle in &cancelled {
+            handle.abort.notify_one();
+        }
+        cancelled.len()
+    }
+}
 
 /// Unique temp file ID counter for generating unique file names
 static NEXT_TEMP_FILE_ID: AtomicU64 = AtomicU64::new(1);
@@ -146,6 +233,13 @@ impl TempFileManager {
         Ok(file_path)
     }
 
+    /// Track a temp file written elsewhere (e.g. streamed by the download callback)
+    /// so it is removed on cleanup
+    pub fn track_file(&self, path: PathBuf) {
+        let mut files = self.files.write().unwrap();
+        files.push(path);
+    }
+
     /// Clean up all tracked temp files
     ///
     /// This removes all temp files that were created by this manager.
@@ -229,68 +323,79 @@ pub fn init_event_handlers_map(ctx: &Ctx<'_>) -> rquickjs::Result<()> {
     Ok(())
 }
 
+/// Parse the structured fields passed by the console glue as a JSON object
+fn parse_console_fields(fields: Opt<String>) -> Option<ConsoleFields> {
+    fields.0.and_then(|json| serde_json::from_str(&json).ok())
+}
+
 /// Setup console API in the JavaScript context
 ///
 /// Provides console.log, console.error, console.warn, console.info, console.debug, console.trace
-/// All functions accept variadic arguments and read the global __GAME_ID__ (optional) and __MOD_ID__ variables
+/// All functions accept variadic arguments and read the global __GAME_ID__ (optional) and __MOD_ID__ variables.
+/// The native functions take the formatted message and, optionally, structured fields as JSON.
 pub fn setup_console_api(ctx: Ctx) -> Result<(), rquickjs::Error> {
     let globals = ctx.globals();
 
     // Create native console object with raw string-based functions
     let console_native = Object::new(ctx.clone())?;
 
-    // Native _log function - accepts a single pre-formatted string message
-    let log_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String| {
+    // Native _log function - accepts a pre-formatted string message and optional JSON fields
+    let log_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String, fields: Opt<String>| {
         let game_id: Option<String> = ctx.globals().get("__GAME_ID__").ok();
         let mod_id: String = ctx
             .globals()
             .get("__MOD_ID__")
             .unwrap_or_else(|_| "unknown".to_string());
-        ConsoleApi::log(game_id.as_deref(), "js", &mod_id, &message);
+        let fields = parse_console_fields(fields);
+        ConsoleApi::log(game_id.as_deref(), "js", &mod_id, &message, fields.as_ref());
     })?;
     console_native.set("_log", log_fn)?;
 
     // Native _error function
-    let error_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String| {
+    let error_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String, fields: Opt<String>| {
         let game_id: Option<String> = ctx.globals().get("__GAME_ID__").ok();
         let mod_id: String = ctx
             .globals()
             .get("__MOD_ID__")
             .unwrap_or_else(|_| "unknown".to_string());
-        ConsoleApi::error(game_id.as_deref(), "js", &mod_id, &message);
+        let fields = parse_console_fields(fields);
+        ConsoleApi::error(game_id.as_deref(), "js", &mod_id, &message, fields.as_ref());
     })?;
     console_native.set("_error", error_fn)?;
 
     // Native _warn function
-    let warn_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String| {
+    let warn_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String, fields: Opt<String>| {
         let game_id: Option<String> = ctx.globals().get("__GAME_ID__").ok();
         let mod_id: String = ctx
             .globals()
             .get("__MOD_ID__")
             .unwrap_or_else(|_| "unknown".to_string());
-        ConsoleApi::warn(game_id.as_deref(), "js", &mod_id, &message);
+        let fields = parse_console_fields(fields);
+        ConsoleApi::warn(game_id.as_deref(), "js", &mod_id, &message, fields.as_ref());
     })?;
     console_native.set("_warn", warn_fn)?;
 
     // Native _info function
-    let info_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String| {
+    let info_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String, fields: Opt<String>| {
         let game_id: Option<String> = ctx.globals().get("__GAME_ID__").ok();
         let mod_id: String = ctx
             .globals()
             .get("__MOD_ID__")
             .unwrap_or_else(|_| "unknown".to_string());
-        ConsoleApi::info(game_id.as_deref(), "js", &mod_id, &message);
+        let fields = parse_console_fields(fields);
+        ConsoleApi::info(game_id.as_deref(), "js", &mod_id, &message, fields.as_ref());
     })?;
     console_native.set("_info", info_fn)?;
 
     // Native _debug function
-    let debug_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String| {
+    let debug_fn = Function::new(ctx.clone(), |ctx: Ctx, message: String, fields: Opt<String>| {
         let game_id: Option<String> = ctx.globals().get("__GAME_ID__").ok();
         let mod_id: String = ctx
             .globals()
             .get("__MOD_ID__")
             .unwrap_or_else(|_| "unknown".to_string());
-        ConsoleApi::debug(game_id.as_deref(), "js", &mod_id, &message);
+        let fields = parse_console_fields(fields);
+        ConsoleApi::debug(game_id.as_deref(), "js", &mod_id, &message, fields.as_ref());
     })?;
     console_native.set("_debug", debug_fn)?;
 
@@ -304,33 +409,155 @@ pub fn setup_console_api(ctx: Ctx) -> Result<(), rquickjs::Error> {
     Ok(())
 }
 
-/// Setup process API in the JavaScript context
+/// JavaScript Process API class
 ///
-/// Provides process.app.data_path and process.app.config_path
-pub fn setup_process_api(ctx: Ctx, app_api: AppApi) -> Result<(), rquickjs::Error> {
-    let globals = ctx.globals();
+/// This class is exposed to JavaScript as the `Process` global object.
+/// It provides the game directories (`Process.app`) and, on the server, lets
+/// mods with the `spawn` capability run child processes.
+#[rquickjs::class]
+#[derive(Clone, Trace, JsLifetime)]
+pub struct ProcessJS {
+    #[qjs(skip_trace)]
+    app_api: AppApi,
+    #[qjs(skip_trace)]
+    system_api: SystemApi,
+    /// Timeout for spawned processes, or why this mod can't spawn them
+    #[qjs(skip_trace)]
+    spawn: Result<Duration, String>,
+}
+
+impl ProcessJS {
+    /// Throw a spawn error as an `Error` with a `code` property
+    fn throw_spawn_error(ctx: &Ctx<'_>, error: &SpawnError) -> rquickjs::Error {
+        let create = || -> rquickjs::Result<Value<'_>> {
+            let error_ctor: Function = ctx.globals().get("Error")?;
+            let error_obj: Object = error_ctor.call((error.to_string(),))?;
+            error_obj.set("code", error.code())?;
+            Ok(error_obj.into_value())
+        };
+        match create() {
+            Ok(value) => ctx.throw(value),
+            Err(e) => e,
+        }
+    }
+
+    /// Timeout to use for a spawn, or the reason spawning is not allowed
+    fn spawn_timeout(&self, ctx: &Ctx<'_>) -> rquickjs::Result<Duration> {
+        match &self.spawn {
+            Ok(timeout) => Ok(*timeout),
+            Err(reason) => Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), reason)?.into())),
+        }
+    }
 
-    // Create process object
-    let process = Object::new(ctx.clone())?;
+    /// Build the `{ exitCode, stdout, stderr }` result object
+    fn output_object<'js>(ctx: &Ctx<'js>, output: SpawnOutput) -> rquickjs::Result<Object<'js>> {
+        let obj = Object::new(ctx.clone())?;
+        match output.exit_code {
+            Some(code) => obj.set("exitCode", code)?,
+            None => obj.set("exitCode", Value::new_null(ctx.clone()))?,
+        }
+        obj.set("stdout", output.stdout)?;
+        obj.set("stderr", output.stderr)?;
+        Ok(obj)
+    }
+}
 
-    // Create process.app object
-    let app = Object::new(ctx.clone())?;
+#[rquickjs::methods]
+impl ProcessJS {
+    /// Game directories: `{ data_path, config_path }`
+    #[qjs(get, rename = "app")]
+    pub fn app<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<Object<'js>> {
+        let app = Object::new(ctx)?;
+        app.set("data_path", self.app_api.data_path())?;
+        app.set("config_path", self.app_api.config_path())?;
+        Ok(app)
+    }
 
-    // Get paths from AppApi
-    let data_path = app_api.data_path();
-    let config_path = app_api.config_path();
+    /// Run a child process and capture its output (server-only, `spawn` capability)
+    ///
+    /// # Returns
+    /// Promise resolving to `{ exitCode, stdout, stderr }`. A nonzero exit code
+    /// resolves normally; `exitCode` is null if the process was killed by a signal.
+    ///
+    /// # Throws
+    /// Error with `code` "NOT_FOUND", "SPAWN_FAILED" or "TIMEOUT" (the child is
+    /// killed once the server's `process_spawn_timeout_secs` elapses)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const { exitCode, stdout } = await Process.spawn("git", ["rev-parse", "HEAD"]);
+    /// ```
+    #[qjs(rename = "spawn")]
+    pub async fn spawn<'js>(&self, ctx: Ctx<'js>, command: String, args: Opt<Vec<String>>) -> rquickjs::Result<Object<'js>> {
+        let timeout = self.spawn_timeout(&ctx)?;
+        let args = args.0.unwrap_or_default();
 
-    // Set process.app.data_path
-    app.set("data_path", data_path)?;
+        match spawn_process(&command, &args, timeout, None).await {
+            Ok(output) => Self::output_object(&ctx, output),
+            Err(e) => Err(Self::throw_spawn_error(&ctx, &e)),
+        }
+    }
 
-    // Set process.app.config_path
-    app.set("config_path", config_path)?;
+    /// Like `spawn`, but also emits each output line as it is printed
+    ///
+    /// Lines are delivered to this mod only, as `process:stdout` / `process:stderr`
+    /// custom events with `{ command, line }` as argument.
+    ///
+    /// # Example
+    /// ```javascript
+    /// System.registerEvent("process:stdout", (req, res) => console.log(req.args[0].line), 0);
+    /// const { exitCode } = await Process.spawnStreaming("./build.sh");
+    /// ```
+    #[qjs(rename = "spawnStreaming")]
+    pub async fn spawn_streaming<'js>(&self, ctx: Ctx<'js>, command: String, args: Opt<Vec<String>>) -> rquickjs::Result<Object<'js>> {
+        let timeout = self.spawn_timeout(&ctx)?;
+        let args = args.0.unwrap_or_default();
+        let mod_id: String = ctx.globals().get("__MOD_ID__").unwrap_or_else(|_| "unknown".to_string());
+
+        let (lines_tx, mut lines_rx) = tokio::sync::mpsc::unbounded_channel::<(OutputStream, String)>();
+        let dispatcher = self.system_api.event_dispatcher();
+        let forward = async {
+            // Ends once spawn_process drops its sender
+            while let Some((stream, line)) = lines_rx.recv().await {
+                let payload = serde_json::json!({ "command": command, "line": line }).to_string();
+                if let Err(e) = dispatcher
+                    .request_send_event_to(&mod_id, stream.event_name().to_string(), vec![payload])
+                    .await
+                {
+                    tracing::warn!("Failed to emit {} for mod '{}': {}", stream.event_name(), mod_id, e);
+                }
+            }
+        };
+
+        let (result, ()) = tokio::join!(spawn_process(&command, &args, timeout, Some(lines_tx)), forward);
+        match result {
+            Ok(output) => Self::output_object(&ctx, output),
+            Err(e) => Err(Self::throw_spawn_error(&ctx, &e)),
+        }
+    }
+}
+
+/// Setup process API in the JavaScript context
+///
+/// Provides Process.app.data_path, Process.app.config_path and Process.spawn()
+///
+/// # Arguments
+/// * `ctx` - The JavaScript context
+/// * `app_api` - Game directories
+/// * `system_api` - Used to emit the output events of `spawnStreaming`
+/// * `spawn` - Timeout for spawned processes, or the error thrown by `spawn`
+pub fn setup_process_api(
+    ctx: Ctx,
+    app_api: AppApi,
+    system_api: SystemApi,
+    spawn: Result<Duration, String>,
+) -> Result<(), rquickjs::Error> {
+    rquickjs::Class::<ProcessJS>::define(&ctx.globals())?;
 
-    // Register app object in process
-    process.set("app", app)?;
+    let process_obj = rquickjs::Class::<ProcessJS>::instance(ctx.clone(), ProcessJS { app_api, system_api, spawn })?;
 
     // Register Process object globally (capitalized for Staminal convention)
-    globals.set("Process", process)?;
+    ctx.globals().set("Process", process_obj)?;
 
     Ok(())
 }
@@ -440,16 +667,237 @@ pub fn setup_file_api(ctx: Ctx, file_api: FileApi) -> Result<(), rquickjs::Error
     Ok(())
 }
 
+/// JavaScript FileSystem API class
+///
+/// This class is exposed to JavaScript as the `FileSystem` global object.
+/// It gives read-only access to files inside the calling mod's own directory.
+#[rquickjs::class]
+#[derive(Clone, Trace, JsLifetime)]
+pub struct FileSystemJS {
+    #[qjs(skip_trace)]
+    filesystem_api: FileSystemApi,
+}
+
+#[rquickjs::methods]
+impl FileSystemJS {
+    /// Read a UTF-8 text file from the mod directory
+    ///
+    /// # Arguments
+    /// * `path` - Path relative to the mod directory
+    ///
+    /// # Throws
+    /// Error if the path leaves the mod directory or the file cannot be read
+    ///
+    /// # Example
+    /// ```javascript
+    /// const map = JSON.parse(FileSystem.readFile("data/maps/level1.json"));
+    /// ```
+    #[qjs(rename = "readFile")]
+    pub fn read_file(&self, ctx: Ctx<'_>, path: String) -> rquickjs::Result<String> {
+        self.filesystem_api
+            .read_file(&path)
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+
+    /// List a directory of the mod
+    ///
+    /// # Arguments
+    /// * `path` - Path relative to the mod directory (default: the mod directory itself)
+    ///
+    /// # Returns
+    /// Array of `{ name, isDirectory }` sorted by name
+    #[qjs(rename = "readDir")]
+    pub fn read_dir<'js>(&self, ctx: Ctx<'js>, path: Opt<String>) -> rquickjs::Result<Array<'js>> {
+        let entries = self
+            .filesystem_api
+            .read_dir(path.0.as_deref().unwrap_or(""))
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))?;
+
+        let array = Array::new(ctx.clone())?;
+        for (index, entry) in entries.into_iter().enumerate() {
+            let obj = Object::new(ctx.clone())?;
+            obj.set("name", entry.name)?;
+            obj.set("isDirectory", entry.is_dir)?;
+            array.set(index, obj)?;
+        }
+        Ok(array)
+    }
+
+    /// Check whether a file or directory exists in the mod directory
+    ///
+    /// # Throws
+    /// Error if the path leaves the mod directory
+    #[qjs(rename = "exists")]
+    pub fn exists(&self, ctx: Ctx<'_>, path: String) -> rquickjs::Result<bool> {
+        self.filesystem_api
+            .exists(&path)
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+}
+
+/// Setup FileSystem API in the JavaScript context
+///
+/// Unlike the other APIs this one is per mod: it must be called with the
+/// mod's own root directory when the mod is loaded.
+///
+/// # Arguments
+/// * `ctx` - The mod's JavaScript context
+/// * `filesystem_api` - FileSystemApi confined to the mod directory
+pub fn setup_filesystem_api(ctx: Ctx, filesystem_api: FileSystemApi) -> Result<(), rquickjs::Error> {
+    rquickjs::Class::<FileSystemJS>::define(&ctx.globals())?;
+
+    let filesystem_obj = rquickjs::Class::<FileSystemJS>::instance(ctx.clone(), FileSystemJS { filesystem_api })?;
+
+    // Register it as global 'FileSystem' object (capitalized for Staminal convention)
+    ctx.globals().set("FileSystem", filesystem_obj)?;
+
+    Ok(())
+}
+
+/// JavaScript Storage API class
+///
+/// This class is exposed to JavaScript as the `Storage` global object.
+/// It persists JSON values per mod. The owning mod is fixed when the context is set up,
+/// not read from `__MOD_ID__`, which scripts can overwrite.
+#[rquickjs::class]
+#[derive(Clone, Trace, JsLifetime)]
+pub struct StorageJS {
+    #[qjs(skip_trace)]
+    storage_api: StorageApi,
+    /// Mod owning the context this instance was created for
+    #[qjs(skip_trace)]
+    mod_id: String,
+}
+
+#[rquickjs::methods]
+impl StorageJS {
+    /// Get a stored value
+    ///
+    /// # Returns
+    /// The stored value, or `undefined` if the key does not exist
+    ///
+    /// # Example
+    /// ```javascript
+    /// const volume = Storage.get("volume") ?? 1.0;
+    /// ```
+    #[qjs(rename = "get")]
+    pub fn get<'js>(&self, ctx: Ctx<'js>, key: String) -> rquickjs::Result<Value<'js>> {
+        match self.storage_api.get(&self.mod_id, &key) {
+            Ok(Some(value)) => ctx.json_parse(value.to_string()),
+            Ok(None) => Ok(Value::new_undefined(ctx)),
+            Err(e) => Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), &e)?.into())),
+        }
+    }
+
+    /// Store a JSON-serializable value, replacing any previous value
+    ///
+    /// # Example
+    /// ```javascript
+    /// Storage.set("settings", { volume: 0.5, fullscreen: true });
+    /// ```
+    #[qjs(rename = "set")]
+    pub fn set<'js>(&self, ctx: Ctx<'js>, key: String, value: Value<'js>) -> rquickjs::Result<()> {
+        let json = ctx.json_stringify(value)?.map(|s| s.to_string()).transpose()?;
+        let parsed = json
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .ok_or_else(|| {
+                let msg = format!("Storage.set(\"{}\") value must be JSON-serializable", key);
+                ctx.throw(rquickjs::String::from_str(ctx.clone(), &msg).unwrap().into())
+            })?;
+        self.storage_api
+            .set(&self.mod_id, &key, parsed)
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+
+    /// Delete a stored key
+    ///
+    /// # Returns
+    /// `true` if the key existed
+    #[qjs(rename = "delete")]
+    pub fn delete(&self, ctx: Ctx<'_>, key: String) -> rquickjs::Result<bool> {
+        self.storage_api
+            .delete(&self.mod_id, &key)
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+
+    /// List all keys stored by the calling mod (sorted)
+    #[qjs(rename = "keys")]
+    pub fn keys(&self, ctx: Ctx<'_>) -> rquickjs::Result<Vec<String>> {
+        self.storage_api
+            .keys(&self.mod_id)
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+}
+
+/// Setup Storage API in the JavaScript context
+///
+/// Provides Storage.get/set/delete/keys backed by `{game_root}/storage/{mod_id}.json`.
+///
+/// # Arguments
+/// * `ctx` - The JavaScript context
+/// * `storage_api` - The StorageApi instance (game root injected by the client/server)
+/// * `mod_id` - The mod owning the context, whose storage file is used
+pub fn setup_storage_api(ctx: Ctx, storage_api: StorageApi, mod_id: &str) -> Result<(), rquickjs::Error> {
+    rquickjs::Class::<StorageJS>::define(&ctx.globals())?;
+
+    let storage_obj = rquickjs::Class::<StorageJS>::instance(
+        ctx.clone(),
+        StorageJS {
+            storage_api,
+            mod_id: mod_id.to_string(),
+        },
+    )?;
+
+    // Register it as global 'Storage' object (capitalized for Staminal convention)
+    ctx.globals().set("Storage", storage_obj)?;
+
+    Ok(())
+}
+
+/// JavaScript globals exposing a capability's APIs
+pub fn capability_globals(capability: Capability) -> &'static [&'static str] {
+    match capability {
+        Capability::Process => &["Process"],
+        Capability::File => &["File"],
+        Capability::FileSystem => &["FileSystem"],
+        Capability::Storage => &["Storage"],
+        Capability::Network => &["Network"],
+        Capability::Graphic => &["Graphic", "Audio", "World"],
+        Capability::Resource => &["Resource"],
+        // Process.spawn() checks this one itself, Process stays usable
+        Capability::Spawn => &[],
+    }
+}
+
+/// Installs a global whose every property access or assignment throws `message`, from
+/// `glue/denied_api.js`
+const DENIED_API_CODE: &str = include_str!("glue/denied_api.js");
+
+/// Setup a placeholder for an API the mod has no capability for
+///
+/// Calls like `Network.download(...)` then fail with `message` (which explains
+/// how to get the capability) instead of "Network is not defined".
+///
+/// # Arguments
+/// * `ctx` - The JavaScript context
+/// * `global_name` - Name of the global to replace (e.g. "Network")
+/// * `message` - Error thrown on any use of the global
+pub fn setup_denied_api(ctx: Ctx, global_name: &str, message: &str) -> Result<(), rquickjs::Error> {
+    let install: Function = ctx.eval(DENIED_API_CODE)?;
+    install.call::<_, ()>((global_name, message))
+}
+
 /// Internal implementation of setTimeout/setInterval
 /// This is a separate function to properly handle lifetimes
 fn set_timeout_interval<'js>(
     ctx: Ctx<'js>,
+    timers: &TimerRegistry,
+    mod_id: &str,
     cb: Function<'js>,
     msec: Option<u64>,
     is_interval: bool,
 ) -> rquickjs::Result<u32> {
     let id = next_timer_id();
-    //let mod_id: String = ctx.globals().get("__MOD_ID__").unwrap_or_else(|_| "unknown".to_string());
 
     // Enforce minimum 4ms delay as per HTML5 spec
     let delay = msec.unwrap_or(0).max(4);
@@ -457,11 +905,9 @@ fn set_timeout_interval<'js>(
     let abort = Arc::new(Notify::new());
     let abort_ref = abort.clone();
 
-    // Store abort handle for clearTimeout/clearInterval
-    {
-        let mut handles = TIMER_ABORT_HANDLES.lock().unwrap();
-        handles.insert(id, abort);
-    }
+    // Store abort handle for clearTimeout/clearInterval and mod unload
+    timers.register(id, mod_id, abort);
+    let timers = timers.clone();
 
     // Timer scheduled - logging disabled for now
     // let timer_type = if is_interval { "setInterval" } else { "setTimeout" };
@@ -500,10 +946,7 @@ fn set_timeout_interval<'js>(
         }
 
         // Cleanup abort handle
-        {
-            let mut handles = TIMER_ABORT_HANDLES.lock().unwrap();
-            handles.remove(&id);
-        }
+        timers.remove(id);
 
         // Cleanup
         drop(cb);
@@ -514,80 +957,68 @@ fn set_timeout_interval<'js>(
     Ok(id)
 }
 
-/// Clear a timer by ID
-///
-/// This function is public so that other runtime adapters (Lua, C#, etc.)
-/// can cancel timers using the same global registry.
-pub fn clear_timer(timer_id: u32) {
-    let abort = {
-        let handles = TIMER_ABORT_HANDLES.lock().unwrap();
-        handles.get(&timer_id).cloned()
-    };
-
-    if let Some(abort) = abort {
-        abort.notify_one();
-    }
-}
-
-/// Register an abort handle for a timer
-///
-/// This function is public so that other runtime adapters (Lua, C#, etc.)
-/// can register their timer abort handles in the global registry.
-pub fn register_timer_abort_handle(timer_id: u32, abort: Arc<Notify>) {
-    let mut handles = TIMER_ABORT_HANDLES.lock().unwrap();
-    handles.insert(timer_id, abort);
-}
-
-/// Remove an abort handle for a timer (called when timer completes)
-///
-/// This function is public so that other runtime adapters (Lua, C#, etc.)
-/// can clean up their timer abort handles from the global registry.
-pub fn remove_timer_abort_handle(timer_id: u32) {
-    let mut handles = TIMER_ABORT_HANDLES.lock().unwrap();
-    handles.remove(&timer_id);
-}
-
 /// Setup timer API in the JavaScript context
 ///
 /// Provides setTimeout, setInterval, clearTimeout, clearInterval functions.
 /// Uses ctx.spawn() for proper async execution within the QuickJS runtime.
 /// Returns numeric timer IDs like browser APIs.
-pub fn setup_timer_api(ctx: Ctx) -> Result<(), rquickjs::Error> {
+///
+/// # Arguments
+/// * `ctx` - The JavaScript context
+/// * `timers` - The registry of the adapter owning the context
+/// * `mod_id` - The mod owning the context, recorded as the owner of its timers
+pub fn setup_timer_api(ctx: Ctx, timers: TimerRegistry, mod_id: &str) -> Result<(), rquickjs::Error> {
     let globals = ctx.globals();
 
     // setTimeout(callback, delay?) -> number
-    let set_timeout_fn = Function::new(ctx.clone(), set_timeout_interval_wrapper::<false>)?;
+    let (registry, owner) = (timers.clone(), mod_id.to_string());
+    let set_timeout_fn = Function::new(ctx.clone(), move |cb: Function, msec: Option<u64>| {
+        set_timeout_interval(cb.ctx().clone(), &registry, &owner, cb, msec, false)
+    })?;
     globals.set("setTimeout", set_timeout_fn)?;
 
     // setInterval(callback, interval?) -> number
-    let set_interval_fn = Function::new(ctx.clone(), set_timeout_interval_wrapper::<true>)?;
+    let (registry, owner) = (timers.clone(), mod_id.to_string());
+    let set_interval_fn = Function::new(ctx.clone(), move |cb: Function, msec: Option<u64>| {
+        set_timeout_interval(cb.ctx().clone(), &registry, &owner, cb, msec, true)
+    })?;
     globals.set("setInterval", set_interval_fn)?;
 
     // clearTimeout(timerId) - cancels a pending timeout
-    let clear_timeout_fn = Function::new(ctx.clone(), |_ctx: Ctx, timer_id: u32| {
+    let registry = timers.clone();
+    let clear_timeout_fn = Function::new(ctx.clone(), move |_ctx: Ctx, timer_id: u32| {
         tracing::trace!("clearTimeout: cancelling timer {}", timer_id);
-        clear_timer(timer_id);
+        registry.clear(timer_id);
     })?;
     globals.set("clearTimeout", clear_timeout_fn)?;
 
     // clearInterval(intervalId) - cancels a pending interval
-    let clear_interval_fn = Function::new(ctx.clone(), |_ctx: Ctx, timer_id: u32| {
+    let clear_interval_fn = Function::new(ctx.clone(), move |_ctx: Ctx, timer_id: u32| {
         tracing::trace!("clearInterval: cancelling interval {}", timer_id);
-        clear_timer(timer_id);
+        timers.clear(timer_id);
     })?;
     globals.set("clearInterval", clear_interval_fn)?;
 
     Ok(())
 }
 
-/// Wrapper function for setTimeout/setInterval that can be used with Function::new
-fn set_timeout_interval_wrapper<'js, const IS_INTERVAL: bool>(
-    ctx: Ctx<'js>,
-    cb: Function<'js>,
-    msec: Option<u64>,
-) -> rquickjs::Result<u32> {
-    set_timeout_interval(ctx, cb, msec, IS_INTERVAL)
-}
+/// Service objects registered with `System.registerService()`, by service name
+///
+/// Shared by all the mod contexts of a runtime, so a mod can reach objects living in
+/// another mod's context. Which mod owns a name is tracked by `SystemApi`; entries must
+/// be removed when their mod unloads and before the runtime is dropped.
+pub type JsServiceStore = Arc<Mutex<HashMap<String, JsService>>>;
+
+/// A service object saved out of its mod's context
+#[derive(Clone)]
+pub struct JsService(Persistent<Object<'static>>);
+
+// JsService is Send because it is only saved and restored inside `with` calls of the
+// runtime it belongs to, which the runtime lock serializes across threads
+unsafe impl Send for JsService {}
+
+/// Builds the object returned by `System.getService()`, from `glue/service_proxy.js`
+const SERVICE_PROXY_CODE: &str = include_str!("glue/service_proxy.js");
 
 /// JavaScript System API class
 ///
@@ -602,6 +1033,12 @@ pub struct SystemJS {
     /// Used by getGameConfigPath() to resolve config file paths
     #[qjs(skip_trace)]
     game_config_dir: Option<PathBuf>,
+    /// Objects of the services registered by the mods of this runtime
+    #[qjs(skip_trace)]
+    services: JsServiceStore,
+    /// Mod owning this context, captured at setup (`__MOD_ID__` can be overwritten by scripts)
+    #[qjs(skip_trace)]
+    mod_id: String,
 }
 
 #[rquickjs::methods]
@@ -676,7 +1113,7 @@ impl SystemJS {
     /// * `event` - Either a SystemEvents enum value (number) or a custom event name (string)
     /// * `handler` - The callback function to invoke
     /// * `priority` - Handler priority (lower numbers execute first)
-    /// * `protocol` - (Optional) Protocol filter string for RequestUri ("stam://", "http://", or "" for all)
+    /// * `protocol` - (Optional) Protocol filter string for RequestUri ("stam://" (also matches stams://), "http://", or "" for all)
     /// * `route` - (Optional) Route prefix filter for RequestUri
     ///
     /// # Returns
@@ -726,7 +1163,7 @@ impl SystemJS {
 
             // Parse protocol filter
             let protocol = match protocol_str.as_deref() {
-                Some("stam://") | Some("stam") => RequestUriProtocol::Stam,
+                Some("stam://") | Some("stams://") | Some("stam") => RequestUriProtocol::Stam,
                 Some("http://") | Some("https://") | Some("http") => RequestUriProtocol::Http,
                 Some("") | None => RequestUriProtocol::All,
                 Some(other) => {
@@ -829,9 +1266,15 @@ impl SystemJS {
                 .unwrap_or_else(|| "null".to_string()))
             .collect();
 
-        tracing::trace!("SystemJS::send_event called: event_name={}, args_count={}", event_name, json_args.len());
+        // The sender keeps its own wildcard handlers from receiving the event
+        let mod_id: String = ctx
+            .globals()
+            .get("__MOD_ID__")
+            .unwrap_or_else(|_| "unknown".to_string());
+
+        tracing::trace!("SystemJS::send_event called: mod={}, event_name={}, args_count={}", mod_id, event_name, json_args.len());
 
-        let result = self.system_api.event_dispatcher().request_send_event(event_name.clone(), json_args).await;
+        let result = self.system_api.event_dispatcher().request_send_event(&mod_id, event_name.clone(), json_args).await;
 
         match result {
             Ok(response) => {
@@ -858,6 +1301,87 @@ impl SystemJS {
         }
     }
 
+    /// Broadcast a message to every client connected to this game (server-only)
+    ///
+    /// On each client the message is dispatched to mods as a custom event named
+    /// after the channel, with `data` as its only argument.
+    ///
+    /// # Arguments
+    /// * `channel` - The channel name (custom event name on the client)
+    /// * `data` - Any JSON-serializable value
+    ///
+    /// # Throws
+    /// Error if called on the client
+    ///
+    /// # Example
+    /// ```javascript
+    /// await system.broadcast("chat", { from: "server", text: "Welcome!" });
+    /// ```
+    #[qjs(rename = "broadcast")]
+    pub async fn broadcast<'js>(&self, ctx: Ctx<'js>, channel: String, data: Opt<Value<'js>>) -> rquickjs::Result<()> {
+        if self.system_api.get_game_info().is_some() {
+            return Err(ctx.throw(rquickjs::String::from_str(
+                ctx.clone(),
+                "system.broadcast() is not available on the client. This method is server-only.",
+            )?
+            .into()));
+        }
+
+        let payload = data.0
+            .and_then(|v| ctx.json_stringify(v).ok().flatten())
+            .map(|s| s.to_string().unwrap_or_default())
+            .unwrap_or_else(|| "null".to_string());
+
+        tracing::trace!("SystemJS::broadcast called: channel={}, payload_len={}", channel, payload.len());
+
+        if let Err(e) = self.system_api.request_broadcast(channel.clone(), payload).await {
+            tracing::error!("Failed to broadcast on channel '{}': {}", channel, e);
+            return Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), &e)?.into()));
+        }
+        Ok(())
+    }
+
+    /// Send a message to a single connected client (server-only)
+    ///
+    /// The client dispatches it to its mods like a broadcast: as a custom event
+    /// named after the channel, with `data` as its only argument.
+    ///
+    /// # Arguments
+    /// * `client_id` - ID of the target client (from the `client:connected` event)
+    /// * `channel` - The channel name (custom event name on the client)
+    /// * `data` - Any JSON-serializable value
+    ///
+    /// # Throws
+    /// - Error if called on the client
+    /// - Error if the target client is not connected
+    ///
+    /// # Example
+    /// ```javascript
+    /// await system.sendToClient(clientId, "chat", { from: "server", text: "Only for you" });
+    /// ```
+    #[qjs(rename = "sendToClient")]
+    pub async fn send_to_client<'js>(&self, ctx: Ctx<'js>, client_id: String, channel: String, data: Opt<Value<'js>>) -> rquickjs::Result<()> {
+        if self.system_api.get_game_info().is_some() {
+            return Err(ctx.throw(rquickjs::String::from_str(
+                ctx.clone(),
+                "system.sendToClient() is not available on the client. This method is server-only.",
+            )?
+            .into()));
+        }
+
+        let payload = data.0
+            .and_then(|v| ctx.json_stringify(v).ok().flatten())
+            .map(|s| s.to_string().unwrap_or_default())
+            .unwrap_or_else(|| "null".to_string());
+
+        tracing::trace!("SystemJS::send_to_client called: client_id={}, channel={}", client_id, channel);
+
+        if let Err(e) = self.system_api.request_send_to_client(client_id, channel, payload).await {
+            return Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), &e)?.into()));
+        }
+        Ok(())
+    }
+
     /// Unregister an event handler
     ///
     /// # Arguments
@@ -891,16 +1415,40 @@ impl SystemJS {
     ///
     /// # Arguments
     /// * `code` - The exit code (0 = success, non-zero = error)
+    /// * `message` - Optional reason, logged before shutdown
+    /// * `is_error` - Whether the reason is logged as an error (default: `code` is non-zero)
     ///
     /// # Note
     /// This function requests a graceful shutdown instead of terminating immediately.
     /// The main loop will receive the shutdown request and perform cleanup before exiting.
+    ///
+    /// # Example
+    /// ```javascript
+    /// System.exit(1, "Failed to load the world save");
+    /// ```
     #[qjs(rename = "exit")]
-    pub fn exit(&self, code: i32) {
-        tracing::debug!("SystemJS::exit called with code {} - requesting graceful shutdown", code);
-        if let Err(e) = self.system_api.request_shutdown(code) {
+    pub fn exit<'js>(&self, ctx: Ctx<'js>, code: i32, message: Opt<String>, is_error: Opt<bool>) {
+        let mod_id: String = ctx
+            .globals()
+            .get("__MOD_ID__")
+            .unwrap_or_else(|_| "unknown".to_string());
+        tracing::debug!("SystemJS::exit called by mod '{}' with code {} - requesting graceful shutdown", mod_id, code);
+
+        let mut request = ShutdownRequest::new(code).with_mod_id(mod_id);
+        if let Some(message) = message.0 {
+            request = request.with_message(message);
+        }
+        if let Some(is_error) = is_error.0 {
+            request = request.with_is_error(is_error);
+        }
+        if let Err(e) = self.system_api.request_shutdown(request.clone()) {
             tracing::error!("Failed to request shutdown: {}", e);
-            // Fallback to immediate exit if channel is not available
+            // Fallback to immediate exit if channel is not available, still explaining why
+            if request.is_error {
+                tracing::error!("{}", request.describe());
+            } else {
+                tracing::info!("{}", request.describe());
+            }
             std::process::exit(code);
         }
     }
@@ -961,7 +1509,14 @@ impl SystemJS {
             manifest_obj.set("name", pkg.manifest.name.as_str())?;
             manifest_obj.set("version", pkg.manifest.version.as_str())?;
             manifest_obj.set("description", pkg.manifest.description.as_str())?;
-            manifest_obj.set("entry_point", pkg.manifest.entry_point.as_deref())?;
+            match &pkg.manifest.entry_point {
+                serde_json::Value::String(entry_point) => manifest_obj.set("entry_point", entry_point.as_str())?,
+                serde_json::Value::Array(entry_points) => manifest_obj.set(
+                    "entry_point",
+                    entry_points.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>(),
+                )?,
+                _ => manifest_obj.set("entry_point", rquickjs::Null)?,
+            }
             manifest_obj.set("priority", pkg.manifest.priority)?;
             if let Some(ref mod_type) = pkg.manifest.mod_type {
                 manifest_obj.set("type", mod_type.as_str())?;
@@ -1099,6 +1654,207 @@ impl SystemJS {
         }
     }
 
+    /// Get information about the connected game server (client-side only)
+    ///
+    /// # Returns
+    /// An object with:
+    /// - serverVersion: string - The server version announced in the handshake
+    /// - gameName: string - The game display name
+    /// - gameVersion: string - The game version
+    /// - protocolVersion: number - The wire protocol version
+    ///
+    /// # Throws
+    /// Error if called on the server (server info not available on server)
+    #[qjs(rename = "getServerInfo")]
+    pub fn get_server_info<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<Object<'js>> {
+        match self.system_api.get_server_info() {
+            Some(server_info) => {
+                let obj = Object::new(ctx)?;
+                obj.set("serverVersion", server_info.server_version.as_str())?;
+                obj.set("gameName", server_info.game_name.as_str())?;
+                obj.set("gameVersion", server_info.game_version.as_str())?;
+                obj.set("protocolVersion", server_info.protocol_version)?;
+                Ok(obj)
+            }
+            None => Err(ctx.throw(
+                rquickjs::String::from_str(
+                    ctx.clone(),
+                    "System.getServerInfo() is not available on the server. This method is client-only.",
+                )?
+                .into(),
+            )),
+        }
+    }
+
+    /// Get the duration of each startup phase
+    ///
+    /// # Returns
+    /// An array of objects, in completion order, with:
+    /// - phase: string - The phase name (e.g., "connect", "js_init", "attach")
+    /// - ms: number - Duration in milliseconds
+    #[qjs(rename = "getStartupTimings")]
+    pub fn get_startup_timings<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<rquickjs::Array<'js>> {
+        let array = rquickjs::Array::new(ctx.clone())?;
+        for (i, timing) in self.system_api.get_startup_timings().iter().enumerate() {
+            let obj = Object::new(ctx.clone())?;
+            obj.set("phase", timing.phase.as_str())?;
+            obj.set("ms", timing.duration.as_secs_f64() * 1000.0)?;
+            array.set(i, obj)?;
+        }
+        Ok(array)
+    }
+
+    /// Get the resolved order in which the mods were attached at startup
+    ///
+    /// # Returns
+    /// An array of objects, in load order, with:
+    /// - id: string - The mod ID
+    /// - priority: number - The manifest priority (lower loads first)
+    /// - reason: string - "bootstrap", "dependency" or "configured" (enabled in the game config)
+    /// - requiredBy: string[] - Mods in the plan that require this one
+    #[qjs(rename = "getLoadOrder")]
+    pub fn get_load_order<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<Array<'js>> {
+        let array = Array::new(ctx.clone())?;
+        for (i, planned) in self.system_api.get_load_plan().iter().enumerate() {
+            let obj = Object::new(ctx.clone())?;
+            obj.set("id", planned.mod_id.as_str())?;
+            obj.set("priority", planned.priority)?;
+            obj.set("reason", planned.reason.as_str())?;
+            obj.set("requiredBy", planned.required_by.clone())?;
+            array.set(i, obj)?;
+        }
+        Ok(array)
+    }
+
+    /// List the registered event handlers, to debug why an event doesn't fire
+    ///
+    /// # Returns
+    /// An object keyed by event name (a custom event name, or a system event key such as
+    /// "system:TerminalKeyPressed"), only for events with handlers, with:
+    /// - count: number - Number of registered handlers
+    /// - mods: string[] - Mods owning the handlers, once each, in dispatch order
+    #[qjs(rename = "listEventHandlers")]
+    pub fn list_event_handlers<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<Object<'js>> {
+        let result = Object::new(ctx.clone())?;
+        for info in self.system_api.describe_event_handlers() {
+            let obj = Object::new(ctx.clone())?;
+            obj.set("count", info.count as f64)?;
+            obj.set("mods", info.mod_ids)?;
+            result.set(info.event, obj)?;
+        }
+        Ok(result)
+    }
+
+    /// Get the latest JavaScript heap sample taken by the host
+    ///
+    /// # Returns
+    /// An object with:
+    /// - usedBytes: number - Bytes in use by values, strings and shapes
+    /// - allocatedBytes: number - Bytes allocated by the QuickJS allocator
+    /// - objectCount: number - Number of live objects
+    ///
+    /// or null if the host hasn't sampled the heap yet. The heap is shared by all mods.
+    #[qjs(rename = "getJsMemoryStats")]
+    pub fn get_js_memory_stats<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<Value<'js>> {
+        match self.system_api.get_js_memory_stats() {
+            Some(stats) => {
+                let obj = Object::new(ctx)?;
+                obj.set("usedBytes", stats.used_bytes as f64)?;
+                obj.set("allocatedBytes", stats.allocated_bytes as f64)?;
+                obj.set("objectCount", stats.object_count as f64)?;
+                Ok(obj.into_value())
+            }
+            None => Ok(Value::new_null(ctx)),
+        }
+    }
+
+    /// Request a garbage collection
+    ///
+    /// The collection runs once the current job returns, never in the middle of it,
+    /// and refreshes the stats returned by `getJsMemoryStats()`.
+    #[qjs(rename = "gc")]
+    pub fn gc(&self) {
+        tracing::trace!("SystemJS::gc called");
+        self.system_api.request_gc();
+    }
+
+    /// Provide a service to other mods
+    ///
+    /// A service name belongs to the mod that registers it first and is released when
+    /// that mod unloads. Registering it again from the same mod replaces the object.
+    ///
+    /// # Arguments
+    /// * `name` - The service name (e.g. "inventory")
+    /// * `service` - Object whose methods other mods can call
+    ///
+    /// # Throws
+    /// Error if the name is empty or already registered by another mod
+    ///
+    /// # Example
+    /// ```javascript
+    /// System.registerService("inventory", {
+    ///     count: (item) => items.get(item) ?? 0,
+    ///     add: (item, amount) => items.set(item, (items.get(item) ?? 0) + amount),
+    /// });
+    /// ```
+    #[qjs(rename = "registerService")]
+    pub fn register_service<'js>(&self, ctx: Ctx<'js>, name: String, service: Object<'js>) -> rquickjs::Result<()> {
+        if let Err(e) = self.system_api.register_service(&name, &self.mod_id) {
+            return Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), &e)?.into()));
+        }
+        tracing::debug!("Mod '{}' registered service '{}'", self.mod_id, name);
+        self.services.lock().unwrap().insert(name, JsService(Persistent::save(&ctx, service)));
+        Ok(())
+    }
+
+    /// Get a service registered by another mod
+    ///
+    /// Methods of the returned object call the provider's methods synchronously.
+    /// Arguments and results are copied as JSON, so only JSON-serializable values
+    /// cross between mods; async methods return a promise of the copied result.
+    ///
+    /// # Arguments
+    /// * `name` - The service name
+    ///
+    /// # Throws
+    /// Error if no mod registered the service. Using the object after its provider
+    /// unloaded throws as well.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const inventory = System.getService("inventory");
+    /// inventory.add("potion", 2);
+    /// console.log(inventory.count("potion"));
+    /// ```
+    #[qjs(rename = "getService")]
+    pub fn get_service<'js>(&self, ctx: Ctx<'js>, name: String) -> rquickjs::Result<Value<'js>> {
+        if self.system_api.service_owner(&name).is_none() {
+            let registered = self.system_api.service_names();
+            let message = if registered.is_empty() {
+                format!("Service '{}' is not registered (no services are registered)", name)
+            } else {
+                format!("Service '{}' is not registered (registered services: {})", name, registered.join(", "))
+            };
+            return Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), &message)?.into()));
+        }
+
+        let services = self.services.clone();
+        let service_name = name.clone();
+        let resolve = Function::new(ctx.clone(), move |ctx: Ctx<'js>| -> rquickjs::Result<Object<'js>> {
+            let service = services.lock().unwrap().get(&service_name).cloned();
+            match service {
+                Some(JsService(service)) => service.restore(&ctx),
+                None => {
+                    let message = format!("Service '{}' is no longer registered", service_name);
+                    Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), &message)?.into()))
+                }
+            }
+        })?;
+
+        let make_proxy: Function = ctx.eval(SERVICE_PROXY_CODE)?;
+        make_proxy.call((name, resolve))
+    }
+
     /// Get the full path for a config file within the game config directory (client-only)
     ///
     /// This method takes a relative path and returns the full absolute path within
@@ -1212,7 +1968,15 @@ impl SystemJS {
 /// * `ctx` - The JavaScript context
 /// * `system_api` - The system API instance
 /// * `game_config_dir` - Optional game config directory (client-only, for getGameConfigPath)
-pub fn setup_system_api(ctx: Ctx, system_api: SystemApi, game_config_dir: Option<PathBuf>) -> Result<(), rquickjs::Error> {
+/// * `services` - Service objects shared by the runtime's mod contexts
+/// * `mod_id` - The mod owning the context, recorded as the owner of the services it registers
+pub fn setup_system_api(
+    ctx: Ctx,
+    system_api: SystemApi,
+    game_config_dir: Option<PathBuf>,
+    services: JsServiceStore,
+    mod_id: &str,
+) -> Result<(), rquickjs::Error> {
     // Initialize the event handlers map (must be done before any handler registration)
     init_event_handlers_map(&ctx)?;
 
@@ -1220,7 +1984,12 @@ pub fn setup_system_api(ctx: Ctx, system_api: SystemApi, game_config_dir: Option
     rquickjs::Class::<SystemJS>::define(&ctx.globals())?;
 
     // Create an instance of SystemJS
-    let system_obj = rquickjs::Class::<SystemJS>::instance(ctx.clone(), SystemJS { system_api, game_config_dir })?;
+    let system_obj = rquickjs::Class::<SystemJS>::instance(ctx.clone(), SystemJS {
+        system_api,
+        game_config_dir,
+        services,
+        mod_id: mod_id.to_string(),
+    })?;
 
     // Register it as global 'System' object (capitalized for Staminal convention)
     ctx.globals().set("System", system_obj)?;
@@ -1231,6 +2000,8 @@ pub fn setup_system_api(ctx: Ctx, system_api: SystemApi, game_config_dir: Option
     system_events.set("TerminalKeyPressed", SystemEvents::TerminalKeyPressed.to_u32())?;
     system_events.set("GraphicEngineReady", SystemEvents::GraphicEngineReady.to_u32())?;
     system_events.set("GraphicEngineWindowClosed", SystemEvents::GraphicEngineWindowClosed.to_u32())?;
+    system_events.set("TerminalMouse", SystemEvents::TerminalMouse.to_u32())?;
+    system_events.set("TerminalPaste", SystemEvents::TerminalPaste.to_u32())?;
     ctx.globals().set("SystemEvents", system_events)?;
 
     // Create RequestUriProtocol enum object
@@ -1291,6 +2062,9 @@ impl LocaleJS {
     ///
     /// First checks the current mod's locale, then falls back to global locale.
     ///
+    /// Numbers (and `Date` objects, as milliseconds since the epoch) are passed
+    /// to Fluent as numbers, so plural selectors and `NUMBER()`/`DATETIME()` work.
+    ///
     /// # Arguments
     /// * `id` - The message ID to look up
     /// * `args` - An object with key-value pairs for substitution
@@ -1310,38 +2084,79 @@ impl LocaleJS {
             .get("__MOD_ID__")
             .unwrap_or_else(|_| "unknown".to_string());
 
-        // Convert JavaScript object to HashMap<String, String>
-        // Handle any JS value type (string, number, boolean, etc.) by converting to string
-        let mut args_map = HashMap::new();
+        // Convert JavaScript object to FluentArgs
+        // Numbers stay numbers (for plural rules), everything else becomes a string
+        let mut fluent_args = FluentArgs::new();
 
         // Iterate over object properties with Value type to handle any JS type
         for result in args.props::<String, rquickjs::Value>() {
             if let Ok((key, value)) = result {
-                // Convert JS value to string representation
-                let string_value = if value.is_string() {
-                    value.as_string().map(|s| s.to_string().unwrap_or_default()).unwrap_or_default()
-                } else if value.is_int() {
-                    value.as_int().map(|n| n.to_string()).unwrap_or_default()
-                } else if value.is_float() {
-                    value.as_float().map(|n| n.to_string()).unwrap_or_default()
+                let fluent_value = if let Some(n) = value.as_int() {
+                    FluentValue::from(n)
+                } else if let Some(n) = value.as_float() {
+                    FluentValue::from(n)
+                } else if let Some(millis) = date_to_millis(&value) {
+                    FluentValue::from(millis)
                 } else if value.is_bool() {
-                    value.as_bool().map(|b| b.to_string()).unwrap_or_default()
+                    FluentValue::from(value.as_bool().map(|b| b.to_string()).unwrap_or_default())
                 } else {
                     // Fallback: try to convert to string via coercion
-                    value.as_string().map(|s| s.to_string().unwrap_or_default()).unwrap_or_default()
+                    FluentValue::from(value.as_string().map(|s| s.to_string().unwrap_or_default()).unwrap_or_default())
                 };
-                args_map.insert(key, string_value);
+                fluent_args.set(key, fluent_value);
+            }
+        }
+
+        Ok(self.locale_api.get_with_args(&mod_id, &id, &fluent_args))
+    }
+
+    /// Get the locales that can be selected with `setLocale()`
+    ///
+    /// # Returns
+    /// Sorted array of locale names (e.g., `["en-US", "it-IT"]`)
+    #[qjs(rename = "getAvailable")]
+    pub fn get_available(&self) -> Vec<String> {
+        self.locale_api.available_locales()
+    }
+
+    /// Switch the active locale for every mod
+    ///
+    /// If the exact locale isn't loaded, a locale of the same language is used.
+    /// If none is, the current locale is kept and a warning is logged.
+    ///
+    /// # Arguments
+    /// * `locale` - The locale to switch to (e.g., "fr-FR")
+    ///
+    /// # Returns
+    /// The locale active after the call
+    #[qjs(rename = "setLocale")]
+    pub fn set_locale(&self, locale: String) -> String {
+        match self.locale_api.set_current_locale(&locale) {
+            Ok(selected) => selected,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                self.locale_api.current_locale()
             }
         }
+    }
+}
 
-        Ok(self.locale_api.get_with_args(&mod_id, &id, &args_map))
+/// Milliseconds since the epoch of a JavaScript `Date`, or `None` for other values
+fn date_to_millis(value: &Value<'_>) -> Option<f64> {
+    if !value.is_object() {
+        return None;
     }
+    let get_time: Function = value
+        .ctx()
+        .eval("(value) => value instanceof Date ? value.getTime() : undefined")
+        .ok()?;
+    get_time.call::<_, Option<f64>>((value.clone(),)).ok().flatten()
 }
 
 /// Setup locale API in the JavaScript context
 ///
-/// Provides locale.get(id) and locale.get_with_args(id, args) functions
-/// for internationalization support in mods.
+/// Provides Locale.get(id), Locale.getWithArgs(id, args), Locale.getAvailable()
+/// and Locale.setLocale(locale) for internationalization support in mods.
 pub fn setup_locale_api(ctx: Ctx, locale_api: LocaleApi) -> Result<(), rquickjs::Error> {
     // First, define the class in the runtime (required before creating instances)
     rquickjs::Class::<LocaleJS>::define(&ctx.globals())?;
@@ -1375,23 +2190,40 @@ impl NetworkJS {
     /// # Arguments
     /// * `uri` - The URI to download from (stam://, http://, https://)
     /// * `progress_callback` - Optional callback function(percentage, receivedBytes, totalBytes)
+    /// * `cancel_token` - Optional token from `createCancelToken()` to abort the download
+    ///
+    /// The returned Promise also has a `cancel()` method (see `CANCELLABLE_DOWNLOAD_CODE`).
     ///
     /// # Returns
     /// A Promise that resolves to an object with:
-    /// - status: HTTP status code (u16)
-    /// - buffer: Uint8Array | null
+    /// - status: HTTP status code (u16), 499 when the download was cancelled
+    /// - bufferString: string | null (buffer responses that are valid UTF-8)
+    /// - isBinary: boolean (true when the buffer was not valid UTF-8)
+    /// - bufferBase64: string | null (base64 of a binary buffer, set when isBinary is true)
     /// - file_name: string | null
     /// - temp_file_path: string | null (path to temp file containing downloaded content)
+    /// - transferStats: { bytesReceived, elapsedMs, bytesPerSec } (see `getLastTransferStats`)
     ///
     /// # Example
     /// ```javascript
     /// const response = await network.download(uri, (percentage, receivedBytes, totalBytes) => {
     ///     console.log(`Progress: ${percentage}% (${receivedBytes}/${totalBytes})`);
     /// });
+    ///
+    /// const pending = Network.download(uri);
+    /// cancelButton.onClick = () => pending.cancel();
     /// ```
     #[qjs(rename = "download")]
-    pub async fn download<'js>(&self, ctx: Ctx<'js>, uri: String, progress_callback: Opt<Function<'js>>) -> rquickjs::Result<Object<'js>> {
+    pub async fn download<'js>(
+        &self,
+        ctx: Ctx<'js>,
+        uri: String,
+        progress_callback: Opt<Option<Function<'js>>>,
+        cancel_token: Opt<rquickjs::Class<'js, CancelTokenJS>>,
+    ) -> rquickjs::Result<Object<'js>> {
         tracing::trace!("NetworkJS::download called: uri={}", uri);
+        let cancel = cancel_token.0.map(|token| token.borrow().token.clone());
+        let progress_callback = progress_callback.0.flatten();
 
         // Shared state for progress updates: (percentage, received, total)
         // The Rust progress callback updates this, and we read it periodically to call JS
@@ -1399,7 +2231,7 @@ impl NetworkJS {
         let state_for_callback = progress_state.clone();
 
         // Create Rust progress callback that updates shared state
-        let progress_cb: Option<crate::api::ProgressCallback> = if progress_callback.0.is_some() {
+        let progress_cb: Option<crate::api::ProgressCallback> = if progress_callback.is_some() {
             Some(Arc::new(move |percentage: f64, received: u64, total: u64| {
                 if let Ok(mut state) = state_for_callback.lock() {
                     *state = (percentage, received, total);
@@ -1410,7 +2242,7 @@ impl NetworkJS {
         };
 
         // Get the JS callback if provided
-        let js_callback = progress_callback.0;
+        let js_callback = progress_callback;
 
         // If we have a JS callback, set up periodic progress reporting
         let response = if let Some(ref callback) = js_callback {
@@ -1419,7 +2251,7 @@ impl NetworkJS {
             let ctx_clone = ctx.clone();
 
             // Use tokio::select to run download and periodic callback together
-            let download_future = self.network_api.download_with_progress(&uri, progress_cb);
+            let download_future = self.network_api.download_with_progress(&uri, progress_cb, cancel);
 
             // We'll poll the state every second while download is in progress
             let mut last_reported = (0.0f64, 0u64, 0u64);
@@ -1455,7 +2287,7 @@ impl NetworkJS {
             }
         } else {
             // No callback, just do the download
-            self.network_api.download_with_progress(&uri, progress_cb).await
+            self.network_api.download_with_progress(&uri, progress_cb, cancel).await
         };
 
         // Call the JS callback one final time with 100% (or current state if failed)
@@ -1468,6 +2300,7 @@ impl NetworkJS {
         // Create response object
         let result = Object::new(ctx.clone())?;
         result.set("status", response.status)?;
+        result.set("transferStats", transfer_stats_to_js(&ctx, &response.transfer_stats)?)?;
 
         // Set buffer_string (or null)
         if let Some(buffer_str) = response.buffer_string {
@@ -1484,6 +2317,27 @@ impl NetworkJS {
             result.set("file_name", rquickjs::Null)?;
         }
 
+        // A binary buffer has no file to save: hand it to JavaScript as base64
+        result.set("isBinary", response.is_binary)?;
+        if response.is_binary {
+            let encoded = response
+                .file_content
+                .as_deref()
+                .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes));
+            result.set("bufferBase64", encoded)?;
+            result.set("temp_file_path", rquickjs::Null)?;
+            return Ok(result);
+        }
+        result.set("bufferBase64", rquickjs::Null)?;
+
+        // Large downloads are streamed to a temp file by the download callback:
+        // expose it as is and take ownership of its cleanup
+        if let Some(temp_path) = response.temp_file_path {
+            self.temp_file_manager.track_file(PathBuf::from(&temp_path));
+            result.set("temp_file_path", temp_path)?;
+            return Ok(result);
+        }
+
         // If file_content is present, save it to a temp file and return temp_file_path
         // Do NOT expose file_content directly to JavaScript
         if let Some(file_content) = response.file_content {
@@ -1505,24 +2359,129 @@ impl NetworkJS {
 
         Ok(result)
     }
+
+    /// List the entries of a directory on the server
+    ///
+    /// # Arguments
+    /// * `uri` - The stam:// URI of the directory
+    ///
+    /// # Returns
+    /// A Promise that resolves to an array of `{ name, size, isDir }` objects,
+    /// sorted by name. Rejects with an error naming the status on failure.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const maps = await Network.list("stam://maps/");
+    /// ```
+    #[qjs(rename = "list")]
+    pub async fn list<'js>(&self, ctx: Ctx<'js>, uri: String) -> rquickjs::Result<Array<'js>> {
+        tracing::trace!("NetworkJS::list called: uri={}", uri);
+
+        let response = self.network_api.list(&uri).await;
+        let Some(listing) = response.listing.filter(|_| response.status == 200) else {
+            let message = format!("Failed to list '{}': status {}", crate::api::sanitize_uri(&uri), response.status);
+            return Err(ctx.throw(rquickjs::String::from_str(ctx.clone(), &message)?.into()));
+        };
+
+        let result = Array::new(ctx.clone())?;
+        for (i, entry) in listing.into_iter().enumerate() {
+            let obj = Object::new(ctx.clone())?;
+            obj.set("name", entry.name)?;
+            obj.set("size", entry.size)?;
+            obj.set("isDir", entry.is_dir)?;
+            result.set(i, obj)?;
+        }
+        Ok(result)
+    }
+
+    /// Create a token to cancel a download
+    ///
+    /// `Network.download(uri)` creates one itself and exposes it as the `cancel()`
+    /// method of the returned Promise; pass a token explicitly to keep the handle
+    /// around after chaining the Promise.
+    ///
+    /// # Example
+    /// ```javascript
+    /// const token = Network.createCancelToken();
+    /// Network.download(uri, null, token).then(onDone);
+    /// token.cancel();
+    /// ```
+    #[qjs(rename = "createCancelToken")]
+    pub fn create_cancel_token<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<rquickjs::Class<'js, CancelTokenJS>> {
+        rquickjs::Class::<CancelTokenJS>::instance(ctx, CancelTokenJS { token: CancelToken::new() })
+    }
+
+    /// Get the statistics of the last completed stam:// transfer
+    ///
+    /// # Returns
+    /// An object with:
+    /// - bytesReceived: number - Bytes received (resumed bytes are not counted)
+    /// - elapsedMs: number - Time spent receiving, in milliseconds
+    /// - bytesPerSec: number - Average speed (0 when nothing was transferred)
+    ///
+    /// Downloads served from the cache report 0 bytes.
+    #[qjs(rename = "getLastTransferStats")]
+    pub fn get_last_transfer_stats<'js>(&self, ctx: Ctx<'js>) -> rquickjs::Result<Object<'js>> {
+        transfer_stats_to_js(&ctx, &self.network_api.last_transfer_stats())
+    }
+}
+
+/// Cancellation handle of a download, created by `Network.createCancelToken()`
+#[rquickjs::class]
+#[derive(Clone, Trace, JsLifetime)]
+pub struct CancelTokenJS {
+    #[qjs(skip_trace)]
+    token: CancelToken,
+}
+
+#[rquickjs::methods]
+impl CancelTokenJS {
+    /// Abort the download: it stops before its next chunk and resolves with status 499
+    #[qjs(rename = "cancel")]
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Whether `cancel()` was called
+    #[qjs(get, rename = "cancelled")]
+    pub fn cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// Wraps `Network.download` so the returned Promise has a `cancel()` method, from
+/// `glue/cancellable_download.js`
+const CANCELLABLE_DOWNLOAD_CODE: &str = include_str!("glue/cancellable_download.js");
+
+/// Convert transfer stats to the object exposed to JavaScript
+fn transfer_stats_to_js<'js>(ctx: &Ctx<'js>, stats: &TransferStats) -> rquickjs::Result<Object<'js>> {
+    let obj = Object::new(ctx.clone())?;
+    obj.set("bytesReceived", stats.bytes_received)?;
+    obj.set("elapsedMs", stats.elapsed.as_secs_f64() * 1000.0)?;
+    obj.set("bytesPerSec", stats.bytes_per_sec)?;
+    Ok(obj)
 }
 
 /// Setup network API in the JavaScript context
 ///
-/// Provides network.download(uri) function that returns a Promise
-/// for downloading resources via stam:// protocol.
+/// Provides network.download(uri) and network.list(uri) functions that return
+/// Promises for downloading resources and listing directories via stam:// protocol.
 ///
 /// The `temp_file_manager` is used to create temp files for downloaded content
 /// and should be cleaned up when the runtime/script finishes.
 pub fn setup_network_api(ctx: Ctx, network_api: NetworkApi, temp_file_manager: TempFileManager) -> Result<(), rquickjs::Error> {
     // First, define the class in the runtime (required before creating instances)
     rquickjs::Class::<NetworkJS>::define(&ctx.globals())?;
+    rquickjs::Class::<CancelTokenJS>::define(&ctx.globals())?;
 
     // Create an instance of NetworkJS
     let network_obj = rquickjs::Class::<NetworkJS>::instance(ctx.clone(), NetworkJS { network_api, temp_file_manager })?;
 
     // Register it as global 'Network' object (capitalized for Staminal convention)
-    ctx.globals().set("Network", network_obj)?;
+    ctx.globals().set("Network", network_obj.clone())?;
+
+    let make_cancellable: Function = ctx.eval(CANCELLABLE_DOWNLOAD_CODE)?;
+    make_cancellable.call::<_, ()>((network_obj,))?;
 
     Ok(())
 }
@@ -1661,6 +2620,23 @@ impl GraphicJS {
         self.graphic_proxy.is_engine_enabled()
     }
 
+    /// Set how many `graphic:frame:stats` events are sent per second
+    ///
+    /// # Arguments
+    /// * `rate_hz` - Reports per second (default 4), `0` to stop reporting
+    #[qjs(rename = "setFrameStatsRate")]
+    pub fn set_frame_stats_rate(&self, ctx: Ctx<'_>, rate_hz: f64) -> rquickjs::Result<()> {
+        self.graphic_proxy
+            .set_frame_stats_rate(rate_hz as f32)
+            .map_err(|e| throw_error(&ctx, &e))
+    }
+
+    /// Get how many `graphic:frame:stats` events are sent per second (0 when disabled)
+    #[qjs(rename = "getFrameStatsRate")]
+    pub fn get_frame_stats_rate(&self) -> f64 {
+        self.graphic_proxy.frame_stats_rate() as f64
+    }
+
     /// Get the current engine type
     ///
     /// # Returns
@@ -1986,6 +2962,82 @@ impl GraphicJS {
     }
 }
 
+/// JavaScript Audio API class
+///
+/// Exposed as the `Audio` global object in JavaScript.
+/// Plays audio resources loaded with `Resource.load()` through the graphic engine.
+///
+/// This is a client-only API. On the server, all methods will throw an error.
+#[rquickjs::class]
+#[derive(Clone, Trace, JsLifetime)]
+pub struct AudioJS {
+    #[qjs(skip_trace)]
+    graphic_proxy: Arc<GraphicProxy>,
+}
+
+#[rquickjs::methods]
+impl AudioJS {
+    /// Play a loaded audio resource
+    ///
+    /// # Arguments
+    /// * `resource_id` - Alias of an audio resource loaded with `Resource.load()`
+    /// * `options` - Optional object with:
+    ///   - `volume`: Linear volume (default: 1.0)
+    ///   - `loop`: Restart from the beginning when playback ends (default: false)
+    ///
+    /// # Returns
+    /// Promise that resolves to a playback handle (number) for `Audio.stop()`
+    ///
+    /// # Throws
+    /// Error if called on server, no engine is enabled, or the resource is not a loaded audio resource
+    ///
+    /// # Example
+    /// ```javascript
+    /// Resource.load("@bme-core/assets/click.ogg", "sfx-click");
+    /// await Resource.whenLoaded("sfx-click");
+    /// await Audio.play("sfx-click");
+    /// const music = await Audio.play("music-theme", { volume: 0.5, loop: true });
+    /// await Audio.stop(music);
+    /// ```
+    #[qjs(rename = "play")]
+    pub async fn play<'js>(
+        &self,
+        ctx: Ctx<'js>,
+        resource_id: String,
+        options: Opt<Object<'js>>,
+    ) -> rquickjs::Result<u64> {
+        let (volume, looping) = match options.0 {
+            Some(opts) => (
+                opts.get::<_, f32>("volume").unwrap_or(1.0),
+                opts.get::<_, bool>("loop").unwrap_or(false),
+            ),
+            None => (1.0, false),
+        };
+
+        self.graphic_proxy
+            .play_audio(resource_id, volume, looping)
+            .await
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+
+    /// Stop a playback started with `Audio.play()`
+    ///
+    /// Stopping a playback that already ended is not an error.
+    ///
+    /// # Arguments
+    /// * `handle` - Playback handle returned by `Audio.play()`
+    ///
+    /// # Throws
+    /// Error if called on server, no engine is enabled, or the handle is unknown
+    #[qjs(rename = "stop")]
+    pub async fn stop<'js>(&self, ctx: Ctx<'js>, handle: u64) -> rquickjs::Result<()> {
+        self.graphic_proxy
+            .stop_audio(handle)
+            .await
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+}
+
 /// JavaScript Window class
 ///
 /// Represents a window created by the graphic engine.
@@ -2082,6 +3134,49 @@ impl WindowJS {
             .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
     }
 
+    /// Set the window icon
+    ///
+    /// # Arguments
+    /// * `resource_id` - Alias of an image loaded via Resource.load()
+    ///
+    /// # Example
+    /// ```javascript
+    /// Resource.load("@my-mod/assets/icon.png", "app-icon");
+    /// await Resource.whenLoaded("app-icon");
+    /// await window.setIcon("app-icon");
+    /// ```
+    #[qjs(rename = "setIcon")]
+    pub async fn set_icon(&self, ctx: Ctx<'_>, resource_id: String) -> rquickjs::Result<()> {
+        self.graphic_proxy
+            .set_window_icon(self.id, resource_id)
+            .await
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+
+    /// Set the default cursor shape shown over this window
+    ///
+    /// # Arguments
+    /// * `icon` - Cursor name, e.g. "default", "pointer", "text", "crosshair"
+    #[qjs(rename = "setCursor")]
+    pub async fn set_cursor(&self, ctx: Ctx<'_>, icon: String) -> rquickjs::Result<()> {
+        self.graphic_proxy
+            .set_cursor_icon(self.id, &icon)
+            .await
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+
+    /// Show or hide the OS cursor while it is over this window
+    ///
+    /// # Arguments
+    /// * `visible` - true to show, false to hide
+    #[qjs(rename = "setCursorVisible")]
+    pub async fn set_cursor_visible(&self, ctx: Ctx<'_>, visible: bool) -> rquickjs::Result<()> {
+        self.graphic_proxy
+            .set_cursor_visible(self.id, visible)
+            .await
+            .map_err(|e| ctx.throw(rquickjs::String::from_str(ctx.clone(), &e).unwrap().into()))
+    }
+
     /// Close the window
     ///
     /// # Returns
@@ -2669,6 +3764,14 @@ pub fn setup_graphic_api(ctx: Ctx, graphic_proxy: Arc<GraphicProxy>) -> Result<(
     // Define classes
     rquickjs::Class::<GraphicJS>::define(&ctx.globals())?;
     rquickjs::Class::<WindowJS>::define(&ctx.globals())?;
+    rquickjs::Class::<AudioJS>::define(&ctx.globals())?;
+
+    // Create Audio instance (shares the proxy with Graphic)
+    let audio_obj = rquickjs::Class::<AudioJS>::instance(
+        ctx.clone(),
+        AudioJS { graphic_proxy: graphic_proxy.clone() },
+    )?;
+    ctx.globals().set("Audio", audio_obj)?;
 
     // Create Graphic instance (capitalized for Staminal convention)
     let graphic_obj =
@@ -3267,10 +4370,8 @@ impl WorldJS {
                         let filtered_obj = Object::new(ctx.clone())?;
                         for prop_result in comp_obj.props::<String, Value>() {
                             if let Ok((prop_key, prop_value)) = prop_result {
-                                // Check if this is an event callback (starts with "on_")
-                                if prop_key.starts_with("on_") {
-                                    // Extract the event type (e.g., "on_click" -> "click")
-                                    let event_type = prop_key.strip_prefix("on_").unwrap().to_string();
+                                // Check if this is an event callback (e.g., "on_click" -> "click")
+                                if let Some(event_type) = entity_event_type(&key, &prop_key) {
                                     // Check if the value is a function
                                     if let Some(func) = prop_value.as_function() {
                                         event_callbacks.push((event_type, func.clone()));
@@ -3332,41 +4433,7 @@ impl WorldJS {
             })?;
 
         // Register event callbacks if any were found
-        if !event_callbacks.is_empty() {
-            // Get or create the global callback registry
-            let globals = ctx.globals();
-            let registry: Object = match globals.get("__ENTITY_EVENT_CALLBACKS__") {
-                Ok(r) => r,
-                Err(_) => {
-                    let new_registry = Object::new(ctx.clone())?;
-                    globals.set("__ENTITY_EVENT_CALLBACKS__", new_registry.clone())?;
-                    new_registry
-                }
-            };
-
-            // Create entity's callback map
-            let entity_callbacks = Object::new(ctx.clone())?;
-
-            for (event_type, callback) in event_callbacks {
-                // Store callback in JS registry
-                entity_callbacks.set(&event_type, callback)?;
-
-                // Register with graphic engine for direct dispatch
-                self.graphic_proxy
-                    .register_entity_event_callback(entity_id, &event_type)
-                    .await
-                    .map_err(|e| {
-                        ctx.throw(
-                            rquickjs::String::from_str(ctx.clone(), &e)
-                                .unwrap()
-                                .into(),
-                        )
-                    })?;
-            }
-
-            // Store under entity ID
-            registry.set(entity_id.to_string(), entity_callbacks)?;
-        }
+        register_entity_event_callbacks(&ctx, &self.graphic_proxy, entity_id, event_callbacks).await?;
 
         // Create and return an Entity handle
         rquickjs::Class::<EntityJS>::instance(
@@ -3407,6 +4474,9 @@ impl WorldJS {
     ///   - withoutComponents: array of component names that entities must NOT have
     ///   - limit: optional maximum number of results
     ///
+    ///   or an array of component names, used as `withComponents`; the other
+    ///   options can then be passed as a second argument
+    ///
     /// # Returns
     /// Promise that resolves to array of query results
     ///
@@ -3420,35 +4490,47 @@ impl WorldJS {
     /// for (const entity of entities) {
     ///     console.log(entity.id, entity.components.Position);
     /// }
+    ///
+    /// // Shorthand
+    /// const players = await World.query(["Transform", "Player"], { limit: 10 });
     /// ```
     #[qjs(rename = "query")]
     pub async fn query<'js>(
         &self,
         ctx: Ctx<'js>,
-        options: Object<'js>,
+        options: Value<'js>,
+        extra_options: Opt<Object<'js>>,
     ) -> rquickjs::Result<Array<'js>> {
+        let component_names = |arr: Array<'js>| -> Vec<String> {
+            arr.iter::<String>()
+                .filter_map(|r| r.ok())
+                .collect()
+        };
+
         // Parse query options
-        let with_components: Vec<String> = options
-            .get::<_, Array>("withComponents")
-            .ok()
-            .map(|arr| {
-                arr.iter::<String>()
-                    .filter_map(|r| r.ok())
-                    .collect()
-            })
-            .unwrap_or_default();
+        let (with_components, options) = if let Some(names) = options.as_array() {
+            (component_names(names.clone()), extra_options.0)
+        } else if let Some(obj) = options.as_object() {
+            let with_components = obj
+                .get::<_, Array>("withComponents")
+                .ok()
+                .map(component_names)
+                .unwrap_or_default();
+            (with_components, Some(obj.clone()))
+        } else {
+            return Err(throw_error(
+                &ctx,
+                "World.query() expects an options object or an array of component names",
+            ));
+        };
 
         let without_components: Vec<String> = options
-            .get::<_, Array>("withoutComponents")
-            .ok()
-            .map(|arr| {
-                arr.iter::<String>()
-                    .filter_map(|r| r.ok())
-                    .collect()
-            })
+            .as_ref()
+            .and_then(|o| o.get::<_, Array>("withoutComponents").ok())
+            .map(component_names)
             .unwrap_or_default();
 
-        let limit: Option<usize> = options.get("limit").ok();
+        let limit: Option<usize> = options.as_ref().and_then(|o| o.get("limit").ok());
 
         let query_options = QueryOptions {
             with_components,
@@ -3491,6 +4573,59 @@ impl WorldJS {
         Ok(result_array)
     }
 
+    /// Run a callback with its ECS commands batched
+    ///
+    /// spawn/insert/update/remove/despawn calls made by the callback are sent to the
+    /// engine as a single batch and applied in order in the same frame. Their promises
+    /// resolve once the batch is flushed, so they must not be awaited inside the
+    /// callback; await them (or the returned promise) afterwards.
+    ///
+    /// # Returns
+    /// Promise resolving to the callback's return value (awaited if it is a promise)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const rows = await World.batch(() =>
+    ///     items.map(item => World.spawn({ Node: { height: 24 }, Text: { value: item } }, list))
+    /// );
+    /// ```
+    #[qjs(rename = "batch")]
+    pub async fn batch<'js>(
+        &self,
+        ctx: Ctx<'js>,
+        callback: Function<'js>,
+    ) -> rquickjs::Result<Value<'js>> {
+        /// Upper bound on the scheduler rounds spent waiting for calls to queue
+        const MAX_SETTLE_ROUNDS: usize = 64;
+
+        self.graphic_proxy
+            .begin_batch()
+            .map_err(|e| throw_error(&ctx, &e))?;
+
+        let result = callback.call::<_, Value>(());
+
+        // The calls made by the callback run as separate async jobs: yield until
+        // they stop queueing commands, then flush everything at once
+        let mut queued = usize::MAX;
+        for _ in 0..MAX_SETTLE_ROUNDS {
+            tokio::task::yield_now().await;
+            let now = self.graphic_proxy.batched_command_count();
+            if now == queued {
+                break;
+            }
+            queued = now;
+        }
+        let flushed = self.graphic_proxy.end_batch();
+
+        let value = result?;
+        flushed.map_err(|e| throw_error(&ctx, &e))?;
+
+        match value.as_promise() {
+            Some(promise) => promise.clone().into_future::<Value>().await,
+            None => Ok(value),
+        }
+    }
+
     /// Register a custom component type with optional schema
     ///
     /// # Arguments
@@ -3870,6 +5005,70 @@ impl EntityJS {
         }
     }
 
+    /// Add a component to this entity
+    ///
+    /// Same as `insert("ComponentName", data)`, except that `on_<event>` functions
+    /// in `data` are registered as entity event callbacks, like `World.spawn()` does.
+    ///
+    /// # Example
+    /// ```javascript
+    /// await entity.addComponent("AnimatedSprite", {
+    ///     atlas_resource_id: "explosion",
+    ///     tile_size: { width: 64, height: 64 },
+    ///     columns: 8,
+    ///     fps: 24,
+    ///     looping: false,
+    ///     on_complete: () => entity.despawn()
+    /// });
+    /// ```
+    #[qjs(rename = "addComponent")]
+    pub async fn add_component<'js>(
+        &self,
+        ctx: Ctx<'js>,
+        component_name: String,
+        data: Value<'js>,
+    ) -> rquickjs::Result<()> {
+        let mut event_callbacks: Vec<(String, Function<'js>)> = Vec::new();
+
+        // Split event callbacks from the serializable component data
+        let data = match data.as_object() {
+            Some(obj) if !data.is_array() => {
+                let filtered_obj = Object::new(ctx.clone())?;
+                for prop_result in obj.props::<String, Value>() {
+                    let (prop_key, prop_value) = prop_result?;
+                    match (entity_event_type(&component_name, &prop_key), prop_value.as_function()) {
+                        (Some(event_type), Some(func)) => event_callbacks.push((event_type, func.clone())),
+                        (Some(_), None) => {}
+                        _ => filtered_obj.set(&prop_key, prop_value)?,
+                    }
+                }
+                filtered_obj.into_value()
+            }
+            _ => data,
+        };
+
+        let json_value = ctx
+            .json_stringify(data)
+            .ok()
+            .flatten()
+            .and_then(|s| s.to_string().ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or(serde_json::Value::Null);
+
+        self.graphic_proxy
+            .insert_component(self.id, component_name, json_value)
+            .await
+            .map_err(|e| {
+                ctx.throw(
+                    rquickjs::String::from_str(ctx.clone(), &e)
+                        .unwrap()
+                        .into(),
+                )
+            })?;
+
+        register_entity_event_callbacks(&ctx, &self.graphic_proxy, self.id, event_callbacks).await
+    }
+
     /// Update specific fields of component(s) on this entity (merge with existing)
     ///
     /// Unlike `insert` which replaces the entire component, `update` merges
@@ -4011,6 +5210,21 @@ impl EntityJS {
         }
     }
 
+    /// Get a component's data from this entity (same as `get`)
+    ///
+    /// # Example
+    /// ```javascript
+    /// const name = (await entity.getComponent("TextInput")).value;
+    /// ```
+    #[qjs(rename = "getComponent")]
+    pub async fn get_component<'js>(
+        &self,
+        ctx: Ctx<'js>,
+        component_name: String,
+    ) -> rquickjs::Result<Value<'js>> {
+        self.get(ctx, component_name).await
+    }
+
     /// Check if this entity has a component
     #[qjs(rename = "has")]
     pub async fn has<'js>(&self, ctx: Ctx<'js>, component_name: String) -> rquickjs::Result<bool> {
@@ -4042,6 +5256,73 @@ impl EntityJS {
     }
 }
 
+/// Entity event type for an `on_<event>` callback property of a component
+///
+/// Callbacks map to the event of the same name (`Button.on_click` -> "click"),
+/// except for the namespaced events of `AnimatedSprite` and `TextInput`.
+fn entity_event_type(component_name: &str, prop_key: &str) -> Option<String> {
+    let event = prop_key.strip_prefix("on_")?;
+    Some(match (component_name, event) {
+        ("AnimatedSprite", "complete") => "animation:complete".to_string(),
+        ("TextInput", "change") => "input:changed".to_string(),
+        ("TextInput", "submit") => "input:submit".to_string(),
+        _ => event.to_string(),
+    })
+}
+
+/// Store entity event callbacks in `__ENTITY_EVENT_CALLBACKS__[entityId][eventType]`
+/// and register them with the graphic engine for direct dispatch
+async fn register_entity_event_callbacks<'js>(
+    ctx: &Ctx<'js>,
+    graphic_proxy: &GraphicProxy,
+    entity_id: u64,
+    event_callbacks: Vec<(String, Function<'js>)>,
+) -> rquickjs::Result<()> {
+    if event_callbacks.is_empty() {
+        return Ok(());
+    }
+
+    // Get or create the global callback registry
+    let globals = ctx.globals();
+    let registry: Object = match globals.get("__ENTITY_EVENT_CALLBACKS__") {
+        Ok(r) => r,
+        Err(_) => {
+            let new_registry = Object::new(ctx.clone())?;
+            globals.set("__ENTITY_EVENT_CALLBACKS__", new_registry.clone())?;
+            new_registry
+        }
+    };
+
+    // Get or create the entity's callback map
+    let entity_callbacks: Object = match registry.get(entity_id.to_string()) {
+        Ok(c) => c,
+        Err(_) => {
+            let new_callbacks = Object::new(ctx.clone())?;
+            registry.set(entity_id.to_string(), new_callbacks.clone())?;
+            new_callbacks
+        }
+    };
+
+    for (event_type, callback) in event_callbacks {
+        // Store callback in JS registry
+        entity_callbacks.set(&event_type, callback)?;
+
+        // Register with graphic engine for direct dispatch
+        graphic_proxy
+            .register_entity_event_callback(entity_id, &event_type)
+            .await
+            .map_err(|e| {
+                ctx.throw(
+                    rquickjs::String::from_str(ctx.clone(), &e)
+                        .unwrap()
+                        .into(),
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
 /// Set up the World API in a JavaScript context
 ///
 /// # Arguments
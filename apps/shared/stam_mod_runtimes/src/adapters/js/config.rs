@@ -11,6 +11,8 @@ pub struct JsRuntimeConfig {
     game_data_dir: PathBuf,
     /// Game-specific config directory
     game_config_dir: PathBuf,
+    /// Maximum heap size of the QuickJS runtime in bytes (None = no limit)
+    memory_limit: Option<usize>,
 }
 
 impl JsRuntimeConfig {
@@ -24,6 +26,7 @@ impl JsRuntimeConfig {
             game_id: None,
             game_data_dir,
             game_config_dir,
+            memory_limit: None,
         }
     }
 
@@ -33,6 +36,20 @@ impl JsRuntimeConfig {
         self
     }
 
+    /// Limit the memory the JavaScript runtime may allocate, shared by all mods
+    ///
+    /// A mod call that exceeds the limit fails with a memory limit error while
+    /// the runtime keeps serving other mods.
+    pub fn with_memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Get the runtime memory limit in bytes (if set)
+    pub fn memory_limit(&self) -> Option<usize> {
+        self.memory_limit
+    }
+
     /// Get the game identifier (if set)
     pub fn game_id(&self) -> Option<&str> {
         self.game_id.as_deref()
@@ -0,0 +1,15 @@
+// Staminal JavaScript Runtime Glue Code - Cancellable downloads
+// Evaluated by setup_network_api() to wrap Network.download so the returned Promise
+// has a cancel() method.
+//
+// The native method only runs once the Promise is polled, so the token has to
+// exist before the call for cancel() to work right away.
+(network) => {
+    const download = network.download;
+    network.download = (uri, onProgress, token) => {
+        token = token ?? network.createCancelToken();
+        const promise = download.call(network, uri, onProgress ?? null, token);
+        promise.cancel = () => token.cancel();
+        return promise;
+    };
+}
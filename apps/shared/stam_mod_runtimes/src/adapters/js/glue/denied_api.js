@@ -0,0 +1,7 @@
+// Staminal JavaScript Runtime Glue Code - Denied API placeholders
+// Evaluated by setup_denied_api() to replace the global of an API the mod has no
+// capability for: every property access or assignment throws `message`.
+(name, message) => {
+    const deny = () => { throw new Error(message); };
+    globalThis[name] = new Proxy({}, { get: deny, set: deny, has: deny });
+}
@@ -260,12 +260,45 @@ const __formatArgs = (...args) => {
 // Console API - Global object
 // =============================================================================
 
+/**
+ * Whether a value is a plain object literal (not an array, Error, class instance...)
+ */
+const __isPlainObject = (value) => {
+    if (value === null || typeof value !== 'object') {
+        return false;
+    }
+    const proto = Object.getPrototypeOf(value);
+    return proto === Object.prototype || proto === null;
+};
+
+/**
+ * Log through a native console function.
+ * A message followed by a single plain object, e.g. console.info("login", { userId: 42 }),
+ * logs the object's keys as structured fields. Anything else is formatted into the message.
+ */
+const __consoleCall = (native, args) => {
+    if (args.length === 2 && typeof args[0] === 'string' && __isPlainObject(args[1])
+        && !/%[sdifoOj]/.test(args[0])) {
+        let fields;
+        try {
+            fields = JSON.stringify(args[1]);
+        } catch (e) {
+            fields = undefined; // Circular or BigInt values: fall back to the message
+        }
+        if (fields !== undefined) {
+            native(args[0], fields);
+            return;
+        }
+    }
+    native(__formatArgs(...args));
+};
+
 globalThis.console = {
-    log: (...args) => __console_native._log(__formatArgs(...args)),
-    error: (...args) => __console_native._error(__formatArgs(...args)),
-    warn: (...args) => __console_native._warn(__formatArgs(...args)),
-    info: (...args) => __console_native._info(__formatArgs(...args)),
-    debug: (...args) => __console_native._debug(__formatArgs(...args)),
+    log: (...args) => __consoleCall(__console_native._log, args),
+    error: (...args) => __consoleCall(__console_native._error, args),
+    warn: (...args) => __consoleCall(__console_native._warn, args),
+    info: (...args) => __consoleCall(__console_native._info, args),
+    debug: (...args) => __consoleCall(__console_native._debug, args),
     trace: (...args) => {
         // Generate stack trace
         const err = new Error();
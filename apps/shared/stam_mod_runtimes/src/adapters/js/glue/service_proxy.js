@@ -0,0 +1,27 @@
+// Staminal JavaScript Runtime Glue Code - Service proxy
+// Evaluated by System.getService() to build the object returned to the caller.
+//
+// Every property access looks the service up again through `resolve`, so a proxy kept
+// after its provider unloaded throws instead of calling into a dropped mod. Arguments
+// and results are copied through JSON, so mods never share mutable objects (results of
+// async methods are copied once they resolve).
+(name, resolve) => {
+    const copy = (value) => {
+        const json = JSON.stringify(value);
+        return json === undefined ? undefined : JSON.parse(json);
+    };
+    return new Proxy({}, {
+        get(_, property) {
+            if (typeof property !== "string" || property === "then") return undefined;
+            const service = resolve();
+            const member = service[property];
+            if (typeof member !== "function") return copy(member);
+            return (...args) => {
+                const result = member.apply(service, copy(args));
+                if (result && typeof result.then === "function") return Promise.resolve(result).then(copy);
+                return copy(result);
+            };
+        },
+        set() { throw new Error(`Service '${name}' is read-only`); },
+    });
+}
@@ -8,8 +8,10 @@ pub mod bindings;
 
 pub use runtime::JsRuntimeAdapter;
 pub use runtime::run_js_event_loop;
-pub use runtime::register_mod_alias;
+pub use runtime::{register_mod_alias, unregister_mod_alias};
 pub use runtime::has_fatal_error;
 pub use runtime::flush_pending_jobs;
+pub use runtime::{drain_pending_jobs, SHUTDOWN_DRAIN_TIMEOUT};
+pub use runtime::{run_js_memory_monitor, MEMORY_STATS_INTERVAL};
 pub use config::JsRuntimeConfig;
 pub use bindings::setup_network_api;
@@ -5,6 +5,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 use tracing::{debug, error, trace, warn};
 
 /// Registry to track already-logged promise rejections to avoid duplicates
@@ -37,8 +38,10 @@ fn signal_fatal_error() {
 }
 
 use super::{JsRuntimeConfig, bindings};
-use crate::api::{AppApi, LocaleApi, NetworkApi, SystemApi, ModInfo, UriResponse};
-use crate::{ModReturnValue, RuntimeAdapter};
+use crate::api::{ApiRegistry, AppApi, Capability, CapabilityPolicy, FileSystemApi, LocaleApi, NetworkApi, StorageApi, SystemApi, JsMemoryStats, ModInfo, UriResponse};
+use crate::call_timeout::{CallDeadline, timeout_error_message};
+use crate::error::line_from_stack;
+use crate::{ModReturnValue, RuntimeAdapter, RuntimeError};
 use bindings::TempFileManager;
 
 /// Format a Promise rejection reason into a readable error message
@@ -241,6 +244,138 @@ pub fn register_mod_alias(mod_id: &str, entry_point: PathBuf) {
     registry.insert(alias, entry_point);
 }
 
+/// Remove a mod alias registered with `register_mod_alias`
+///
+/// # Arguments
+/// * `mod_id` - The mod identifier whose `@mod_id` alias should be removed
+pub fn unregister_mod_alias(mod_id: &str) {
+    let alias = format!("@{}", mod_id);
+    let mut registry = MOD_ALIAS_REGISTRY.write().unwrap();
+    registry.remove(&alias);
+}
+
+/// Convert a JavaScript value returned by a mod function into a `ModReturnValue`
+///
+/// Integral numbers that fit in an `i32` stay `Int` for backward compatibility,
+/// other numbers become `Float`. Arrays and plain objects are converted
+/// recursively. Values with no counterpart (functions, symbols, bigints, ...)
+/// fail with `RuntimeError::UnsupportedReturnType`, also when nested; nesting
+/// beyond `MAX_RETURN_VALUE_DEPTH` (e.g. a cyclic object) fails with `ReturnValueTooDeep`.
+fn js_value_to_return_value(value: &Value) -> Result<ModReturnValue, RuntimeError> {
+    js_value_to_return_value_at(value, 0)
+}
+
+/// `js_value_to_return_value` for a value nested `depth` levels deep
+fn js_value_to_return_value_at(value: &Value, depth: usize) -> Result<ModReturnValue, RuntimeError> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(ModReturnValue::None);
+    }
+    if let Some(b) = value.as_bool() {
+        return Ok(ModReturnValue::Bool(b));
+    }
+    if let Some(n) = value.as_int() {
+        return Ok(ModReturnValue::Int(n));
+    }
+    if let Some(n) = value.as_float() {
+        if n.fract() == 0.0 && n >= i32::MIN as f64 && n <= i32::MAX as f64 {
+            return Ok(ModReturnValue::Int(n as i32));
+        }
+        return Ok(ModReturnValue::Float(n));
+    }
+    if let Some(s) = value.as_string() {
+        return s
+            .to_string()
+            .map(ModReturnValue::String)
+            .map_err(|_| unsupported_js_type(value));
+    }
+    if (value.is_array() || value.is_object()) && depth >= crate::MAX_RETURN_VALUE_DEPTH {
+        return Err(RuntimeError::ReturnValueTooDeep {
+            max_depth: crate::MAX_RETURN_VALUE_DEPTH,
+        });
+    }
+    if let Some(array) = value.as_array() {
+        return array
+            .iter::<Value>()
+            .map(|item| {
+                item.map_err(|_| unsupported_js_type(value))
+                    .and_then(|item| js_value_to_return_value_at(&item, depth + 1))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(ModReturnValue::Array);
+    }
+    if matches!(value.type_of(), rquickjs::Type::Object) {
+        let object = value.as_object().ok_or_else(|| unsupported_js_type(value))?;
+        return object
+            .props::<String, Value>()
+            .map(|prop| {
+                prop.map_err(|_| unsupported_js_type(value))
+                    .and_then(|(key, item)| Ok((key, js_value_to_return_value_at(&item, depth + 1)?)))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map(ModReturnValue::Object);
+    }
+    Err(unsupported_js_type(value))
+}
+
+/// Build the `UnsupportedReturnType` error for a JavaScript value, using `typeof`-like names
+fn unsupported_js_type(value: &Value) -> RuntimeError {
+    let js_type = match value.type_of() {
+        rquickjs::Type::Function | rquickjs::Type::Constructor => "function".to_string(),
+        rquickjs::Type::Symbol => "symbol".to_string(),
+        rquickjs::Type::BigInt => "bigint".to_string(),
+        other => format!("{:?}", other).to_lowercase(),
+    };
+    RuntimeError::UnsupportedReturnType { js_type }
+}
+
+/// Error message returned when a mod call exceeds the runtime memory limit
+fn memory_limit_error_message(mod_id: &str, limit: usize) -> String {
+    format!("mod '{}' exceeded memory limit of {} bytes", mod_id, limit)
+}
+
+/// Build a typed error from an exception formatted by `format_js_error`
+///
+/// The first line (`Name: message`) is prefixed with `context`; a `SyntaxError`
+/// becomes a `CompileError` carrying the line of its first stack frame, any
+/// other exception a `RuntimeException` carrying the stack.
+fn js_error(context: &str, formatted: &str) -> RuntimeError {
+    let (first_line, stack) = formatted.split_once('\n').unwrap_or((formatted, ""));
+    let message = format!("{}: {}", context, first_line);
+    if first_line.starts_with("SyntaxError") {
+        RuntimeError::CompileError {
+            line: line_from_stack(stack),
+            message,
+        }
+    } else {
+        RuntimeError::RuntimeException {
+            message,
+            stack: (!stack.trim().is_empty()).then(|| stack.to_string()),
+        }
+    }
+}
+
+/// Pick the error returned for a failed mod call
+///
+/// QuickJS reports an exhausted memory limit as an "out of memory" exception;
+/// when a limit is configured that case gets a dedicated message instead of
+/// the exception itself.
+fn mod_call_error(
+    error: &rquickjs::Error,
+    formatted: &str,
+    memory_limit: Option<usize>,
+    mod_id: &str,
+    context: String,
+) -> RuntimeError {
+    match memory_limit {
+        Some(limit)
+            if matches!(error, rquickjs::Error::Allocation) || formatted.contains("out of memory") =>
+        {
+            RuntimeError::exception(memory_limit_error_message(mod_id, limit))
+        }
+        _ => js_error(&context, formatted),
+    }
+}
+
 /// Custom resolver that handles @mod-id imports
 ///
 /// This resolver intercepts imports starting with `@` and resolves them
@@ -371,11 +506,37 @@ impl FilesystemLoader {
     }
 }
 
+/// Merge the exports of a mod's additional script into the mod's namespace
+///
+/// Functions exported by both are chained: each runs in load order and the call
+/// returns the last defined result (awaiting both when either returns a Promise).
+const MERGE_NAMESPACES_JS: &str = r#"(merged, added) => {
+    const result = Object.assign({}, merged);
+    for (const [name, value] of Object.entries(added)) {
+        const previous = result[name];
+        if (typeof previous === "function" && typeof value === "function") {
+            result[name] = function (...args) {
+                const first = previous.apply(this, args);
+                const second = value.apply(this, args);
+                if (first instanceof Promise || second instanceof Promise) {
+                    return Promise.all([first, second]).then(([a, b]) => (b === undefined ? a : b));
+                }
+                return second === undefined ? first : second;
+            };
+        } else if (!(name in result)) {
+            result[name] = value;
+        }
+    }
+    return result;
+}"#;
+
 /// Represents a loaded mod with its own isolated context
 struct LoadedMod {
     context: AsyncContext,
     #[allow(dead_code)]
     mod_dir: PathBuf,
+    /// Whether the mod exports `onTick`, so ticks skip mods that don't
+    has_on_tick: bool,
 }
 
 /// JavaScript runtime adapter for QuickJS with async support
@@ -391,6 +552,12 @@ pub struct JsRuntimeAdapter {
     mod_dirs: Vec<PathBuf>,
     /// System API shared across all mod contexts
     system_api: SystemApi,
+    /// Per-mod persistent key-value storage shared across all mod contexts
+    storage_api: StorageApi,
+    /// Capabilities granted to each mod (decides which optional globals are bound)
+    capability_policy: CapabilityPolicy,
+    /// Timeout of `Process.spawn()` children (None = spawning disabled, as on the client)
+    spawn_timeout: Option<Duration>,
     /// Locale API for internationalization (optional)
     locale_api: Option<LocaleApi>,
     /// Network API for downloading resources (optional, client-side only)
@@ -401,6 +568,14 @@ pub struct JsRuntimeAdapter {
     resource_proxy: Option<Arc<crate::api::ResourceProxy>>,
     /// Temp file manager for downloaded content (tracks and cleans up temp files)
     temp_file_manager: TempFileManager,
+    /// Maximum duration of a synchronous mod call (None = no limit)
+    call_timeout: Option<Duration>,
+    /// Deadline polled by the QuickJS interrupt handler
+    call_deadline: CallDeadline,
+    /// Objects of the services registered by mods (`System.registerService()`)
+    services: bindings::JsServiceStore,
+    /// Pending setTimeout/setInterval timers of the mods of this adapter
+    timers: bindings::TimerRegistry,
 }
 
 impl JsRuntimeAdapter {
@@ -408,15 +583,30 @@ impl JsRuntimeAdapter {
     ///
     /// # Arguments
     /// * `config` - Runtime configuration containing game directories
-    pub fn new(config: JsRuntimeConfig) -> Result<Self, Box<dyn std::error::Error>> {
+    /// * `apis` - APIs exposed to mods, bound by their `ApiRegistry` name. Optional
+    ///   APIs that are absent leave their global undefined; unknown names are ignored.
+    pub fn new(config: JsRuntimeConfig, mut apis: ApiRegistry) -> Result<Self, Box<dyn std::error::Error>> {
         debug!("> Initializing javascript async runtime \"QuickJS\" for mods");
 
         let runtime = AsyncRuntime::new()?;
+        let call_deadline = CallDeadline::new();
 
         // Setup promise rejection tracker synchronously using block_on
         // This must be done before any JavaScript code runs
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(async {
+                // Interrupt running JS once the current call's deadline has passed.
+                // The deadline is only armed around mod calls when a call timeout is set.
+                let interrupt_deadline = call_deadline.clone();
+                runtime
+                    .set_interrupt_handler(Some(Box::new(move || interrupt_deadline.is_expired())))
+                    .await;
+
+                if let Some(limit) = config.memory_limit() {
+                    debug!("JavaScript runtime memory limit set to {} bytes", limit);
+                    runtime.set_memory_limit(limit).await;
+                }
+
                 runtime.set_host_promise_rejection_tracker(Some(Box::new(
                     |ctx, _promise, reason, is_handled| {
                         // Only report unhandled rejections (is_handled == false)
@@ -461,29 +651,42 @@ impl JsRuntimeAdapter {
             config,
             loaded_mods: HashMap::new(),
             mod_dirs: Vec::new(),
-            system_api: SystemApi::new(),
-            locale_api: None,
-            network_api: None,
-            graphic_proxy: None,
-            resource_proxy: None,
+            system_api: apis.take(ApiRegistry::SYSTEM).unwrap_or_default(),
+            storage_api: apis.take(ApiRegistry::STORAGE).unwrap_or_default(),
+            capability_policy: CapabilityPolicy::new(),
+            spawn_timeout: None,
+            locale_api: apis.take(ApiRegistry::LOCALE),
+            network_api: apis.take(ApiRegistry::NETWORK),
+            graphic_proxy: apis.take(ApiRegistry::GRAPHIC),
+            resource_proxy: apis.take(ApiRegistry::RESOURCE),
             temp_file_manager: TempFileManager::new(),
+            call_timeout: None,
+            call_deadline,
+            services: bindings::JsServiceStore::default(),
+            timers: bindings::TimerRegistry::new(),
         };
 
+        for name in apis.list() {
+            warn!("API '{}' is unknown to the JavaScript runtime or has an unexpected type, ignoring it", name);
+        }
+
         debug!("< JavaScript async runtime \"QuickJS\" initialized successfully");
         Ok(js_runtime)
     }
 
     /// Set the locale API for internationalization support
     ///
-    /// This should be called before loading any mods to ensure
-    /// the `locale` global object is available in all mod contexts.
+    /// Same as registering it as `ApiRegistry::LOCALE`. This should be called
+    /// before loading any mods to ensure the `locale` global object is available
+    /// in all mod contexts.
     pub fn set_locale_api(&mut self, locale_api: LocaleApi) {
         self.locale_api = Some(locale_api);
     }
 
     /// Set the network API for network operations
     ///
-    /// This should be called before loading any mods to ensure
+    /// Same as registering it as `ApiRegistry::NETWORK`. This should be called
+    /// before loading any mods to ensure
     /// the `network` global object is available in all mod contexts.
     /// Typically only used on the client side.
     pub fn set_network_api(&mut self, network_api: NetworkApi) {
@@ -492,7 +695,8 @@ impl JsRuntimeAdapter {
 
     /// Set the graphic proxy for graphic engine operations
     ///
-    /// This should be called before loading any mods to ensure
+    /// Same as registering it as `ApiRegistry::GRAPHIC`. This should be called
+    /// before loading any mods to ensure
     /// the `graphic` global object is available in all mod contexts.
     /// Only used on the client side.
     pub fn set_graphic_proxy(&mut self, graphic_proxy: Arc<crate::api::GraphicProxy>) {
@@ -501,7 +705,8 @@ impl JsRuntimeAdapter {
 
     /// Set the resource proxy for resource loading and caching
     ///
-    /// This should be called before loading any mods to ensure
+    /// Same as registering it as `ApiRegistry::RESOURCE`. This should be called
+    /// before loading any mods to ensure
     /// the `Resource` global object is available in all mod contexts.
     /// Only used on the client side.
     pub fn set_resource_proxy(&mut self, resource_proxy: Arc<crate::api::ResourceProxy>) {
@@ -533,19 +738,88 @@ impl JsRuntimeAdapter {
         &self.system_api
     }
 
-    /// Setup all global APIs in a context
+    /// Number of pending setTimeout/setInterval timers created by the mods of this adapter
+    pub fn pending_timer_count(&self) -> usize {
+        self.timers.pending_count()
+    }
+
+    /// Cancel every pending setTimeout/setInterval timer created by a mod
+    ///
+    /// # Returns
+    /// The number of timers cancelled
+    pub fn cancel_mod_timers(&self, mod_id: &str) -> usize {
+        self.timers.cancel_for_mod(mod_id)
+    }
+
+    /// Get a reference to the storage API
+    ///
+    /// Call `set_game_root()` on it before loading mods so `Storage` can persist data.
+    pub fn storage_api(&self) -> &StorageApi {
+        &self.storage_api
+    }
+
+    /// Get a reference to the capability policy
+    ///
+    /// Declare each mod's manifest capabilities on it before loading the mod;
+    /// clones share state, so a handle kept by the host stays in effect.
+    pub fn capability_policy(&self) -> &CapabilityPolicy {
+        &self.capability_policy
+    }
+
+    /// Allow mods with the `spawn` capability to run child processes (server-only)
+    ///
+    /// Children still running after `timeout` are killed. Only affects mods loaded afterwards.
+    pub fn enable_process_spawn(&mut self, timeout: Duration) {
+        self.spawn_timeout = Some(timeout);
+    }
+
+    /// Sample the JavaScript heap shared by every mod context
+    pub async fn memory_usage(&self) -> JsMemoryStats {
+        js_memory_stats(&self.runtime).await
+    }
+
+    /// Run the QuickJS garbage collector
+    ///
+    /// Waits for the runtime lock, so it never runs in the middle of a mod call or
+    /// a pending job and is safe to await from the event loop.
+    pub async fn force_gc(&self) {
+        self.runtime.run_gc().await;
+    }
+
+    /// Setup all global APIs in a mod's context
+    ///
+    /// APIs behind a capability the mod is not granted are replaced by
+    /// placeholders that throw the reason on use.
     async fn setup_global_apis(
         &self,
         context: &AsyncContext,
+        mod_id: &str,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let game_data_dir = self.config.game_data_dir().clone();
         let game_config_dir = self.config.game_config_dir().clone();
         let system_api = self.system_api.clone();
+        let storage_api = self.storage_api.clone();
         let locale_api = self.locale_api.clone();
         let network_api = self.network_api.clone();
         let graphic_proxy = self.graphic_proxy.clone();
         let resource_proxy = self.resource_proxy.clone();
         let temp_file_manager = self.temp_file_manager.clone();
+        let services = self.services.clone();
+        let timers = self.timers.clone();
+        let denied: Vec<(Capability, String)> = Capability::ALL
+            .into_iter()
+            .filter_map(|capability| {
+                self.capability_policy
+                    .check(mod_id, capability)
+                    .err()
+                    .map(|reason| (capability, reason))
+            })
+            .collect();
+        let allowed = |capability: Capability| !denied.iter().any(|(c, _)| *c == capability);
+        let spawn = match self.spawn_timeout {
+            Some(timeout) => self.capability_policy.check(mod_id, Capability::Spawn).map(|_| timeout),
+            None => Err("Process.spawn() is not available on the client. This method is server-only.".to_string()),
+        };
 
         // Configure temp directory for downloads (game_data_dir/tmp)
         let temp_dir = game_data_dir.join("tmp");
@@ -563,15 +837,24 @@ impl JsRuntimeAdapter {
                 bindings::setup_console_api(ctx.clone())?;
 
                 // Register process API with game-specific directories
-                let app_api = AppApi::new(game_data_dir.clone(), game_config_dir.clone());
-                bindings::setup_process_api(ctx.clone(), app_api)?;
+                if allowed(Capability::Process) {
+                    let app_api = AppApi::new(game_data_dir.clone(), game_config_dir.clone());
+                    bindings::setup_process_api(ctx.clone(), app_api, system_api.clone(), spawn)?;
+                }
 
                 // Register file API with game-specific directories for path validation
-                let file_api = crate::api::FileApi::new(game_data_dir, game_config_dir.clone());
-                bindings::setup_file_api(ctx.clone(), file_api)?;
+                if allowed(Capability::File) {
+                    let file_api = crate::api::FileApi::new(game_data_dir, game_config_dir.clone());
+                    bindings::setup_file_api(ctx.clone(), file_api)?;
+                }
+
+                // Register storage API (Storage.get(), Storage.set(), ...) scoped to each mod
+                if allowed(Capability::Storage) {
+                    bindings::setup_storage_api(ctx.clone(), storage_api, mod_id)?;
+                }
 
                 // Register timer API (setTimeout, setInterval, etc.)
-                bindings::setup_timer_api(ctx.clone())?;
+                bindings::setup_timer_api(ctx.clone(), timers, mod_id)?;
 
                 // Register system API (system.get_mods(), system.getGameConfigPath())
                 // game_config_dir is passed for client-only getGameConfigPath() method
@@ -582,7 +865,7 @@ impl JsRuntimeAdapter {
                 };
                 // Clone system_api before moving it - we need it later for Resource API
                 let system_api_for_resource = system_api.clone();
-                bindings::setup_system_api(ctx.clone(), system_api, config_dir_for_system)?;
+                bindings::setup_system_api(ctx.clone(), system_api, config_dir_for_system, services, mod_id)?;
 
                 // Register locale API (locale.get(), locale.get_with_args())
                 if let Some(locale) = locale_api {
@@ -590,12 +873,12 @@ impl JsRuntimeAdapter {
                 }
 
                 // Register network API (network.download()) - client-side only
-                if let Some(network) = network_api {
+                if let Some(network) = network_api.filter(|_| allowed(Capability::Network)) {
                     bindings::setup_network_api(ctx.clone(), network, temp_file_manager)?;
                 }
 
                 // Register graphic API (graphic.enableEngine(), etc.) - client-side only
-                if let Some(proxy) = graphic_proxy.clone() {
+                if let Some(proxy) = graphic_proxy.clone().filter(|_| allowed(Capability::Graphic)) {
                     bindings::setup_graphic_api(ctx.clone(), proxy.clone())?;
                     // Register World API for ECS operations
                     bindings::setup_world_api(ctx.clone(), proxy)?;
@@ -603,10 +886,18 @@ impl JsRuntimeAdapter {
 
                 // Register resource API (Resource.load(), Resource.unload(), etc.) - client-side only
                 // Requires both resource_proxy and graphic_proxy to be set
+                let resource_proxy = resource_proxy.filter(|_| allowed(Capability::Resource));
                 if let (Some(res_proxy), Some(gfx_proxy)) = (resource_proxy, graphic_proxy) {
                     bindings::setup_resource_api(ctx.clone(), res_proxy, gfx_proxy, system_api_for_resource)?;
                 }
 
+                // Placeholders explaining why a capability's APIs are missing
+                for (capability, reason) in &denied {
+                    for global_name in bindings::capability_globals(*capability) {
+                        bindings::setup_denied_api(ctx.clone(), global_name, reason)?;
+                    }
+                }
+
                 // Register text API (Text.DecodeUTF8())
                 bindings::setup_text_api(ctx.clone())?;
 
@@ -698,7 +989,7 @@ impl JsRuntimeAdapter {
         &mut self,
         mod_path: &Path,
         mod_id: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), RuntimeError> {
         trace!(
             "Loading JavaScript module: {} from {}",
             mod_id,
@@ -709,9 +1000,9 @@ impl JsRuntimeAdapter {
         let mod_dir = mod_path
             .parent()
             .ok_or_else(|| {
-                format!(
-                    "Cannot determine mod directory for '{}'",
-                    mod_path.display()
+                RuntimeError::load_failed(
+                    mod_id,
+                    format!("Cannot determine mod directory for '{}'", mod_path.display()),
                 )
             })?
             .to_path_buf();
@@ -727,10 +1018,9 @@ impl JsRuntimeAdapter {
         // Register mod alias for cross-mod imports (@mod-id syntax)
         // Use absolute path for reliable resolution - canonicalize to remove ./ and normalize
         let absolute_entry_point = fs::canonicalize(mod_path).map_err(|e| {
-            format!(
-                "Failed to canonicalize path '{}': {}",
-                mod_path.display(),
-                e
+            RuntimeError::load_failed(
+                mod_id,
+                format!("Failed to canonicalize path '{}': {}", mod_path.display(), e),
             )
         })?;
         register_mod_alias(mod_id, absolute_entry_point.clone());
@@ -747,36 +1037,87 @@ impl JsRuntimeAdapter {
         }
 
         // Create a new isolated AsyncContext for this mod
-        let context = AsyncContext::full(&self.runtime).await?;
+        let context = AsyncContext::full(&self.runtime)
+            .await
+            .map_err(|e| RuntimeError::load_failed(mod_id, format!("Failed to create context: {}", e)))?;
 
         // Setup global APIs for this mod's context
-        self.setup_global_apis(&context).await?;
+        self.setup_global_apis(&context, mod_id)
+            .await
+            .map_err(|e| RuntimeError::load_failed(mod_id, format!("Failed to setup global APIs: {}", e)))?;
 
         // Set global __GAME_ID__ (optional) and __MOD_ID__ variables for console logging
         let game_id = self.config.game_id().map(|s| s.to_string());
+        // FileSystem is confined to this mod's own (resolved) directory
+        let filesystem_api = FileSystemApi::for_entry_point(&absolute_entry_point, mod_id);
+        let filesystem_allowed = self.capability_policy.is_allowed(mod_id, Capability::FileSystem);
         context
             .with(|ctx| {
                 if let Some(gid) = game_id {
                     ctx.globals().set("__GAME_ID__", gid)?;
                 }
                 ctx.globals().set("__MOD_ID__", mod_id)?;
+                if filesystem_allowed {
+                    bindings::setup_filesystem_api(ctx.clone(), filesystem_api)?;
+                }
                 Ok::<(), rquickjs::Error>(())
             })
-            .await?;
+            .await
+            .map_err(|e| RuntimeError::load_failed(mod_id, format!("Failed to set mod globals: {}", e)))?;
+
+        // Evaluate the entry point and keep its namespace for later calls
+        let namespace_key = format!("__MODULE_NS_{}__", mod_id.replace("-", "_"));
+        Self::eval_module(&context, mod_id, &absolute_entry_point, &namespace_key).await?;
+
+        // Checked once here so dispatch_tick doesn't enter contexts of mods without onTick
+        let has_on_tick = context
+            .with(|ctx| {
+                ctx.globals()
+                    .get::<_, Object>(&namespace_key)
+                    .and_then(|namespace| namespace.get::<_, Function>("onTick"))
+                    .is_ok()
+            })
+            .await;
+
+        // Store the loaded mod
+        self.loaded_mods.insert(
+            mod_id.to_string(),
+            LoadedMod {
+                context,
+                mod_dir,
+                has_on_tick,
+            },
+        );
 
-        // Use absolute path for the initial module import
+        Ok(())
+    }
+
+    /// Declare and evaluate a script as a module in a mod's context
+    ///
+    /// The module namespace is stored in the global `namespace_key`.
+    async fn eval_module(
+        context: &AsyncContext,
+        mod_id: &str,
+        module_path: &Path,
+        namespace_key: &str,
+    ) -> Result<(), RuntimeError> {
+        // Use absolute path for the module name
         // This ensures the loader can find the file regardless of working directory
-        let module_path_str = absolute_entry_point.to_string_lossy().to_string();
+        let module_path_str = module_path.to_string_lossy().to_string();
 
         let mod_id_owned = mod_id.to_string();
 
         // Read the entry point file content
-        let entry_content = fs::read_to_string(&absolute_entry_point)
-            .map_err(|e| format!("Failed to read entry point '{}': {}", module_path_str, e))?;
+        let entry_content = fs::read_to_string(module_path)
+            .map_err(|e| {
+                RuntimeError::load_failed(
+                    mod_id,
+                    format!("Failed to read entry point '{}': {}", module_path_str, e),
+                )
+            })?;
 
         // Load the module from the filesystem
-        // Use Result<String, String> for ParallelSend compatibility
-        let result: Result<String, String> = context
+        context
             .with(|ctx| {
                 // Declare the module with the file content
                 match Module::declare(ctx.clone(), module_path_str.clone(), entry_content) {
@@ -787,28 +1128,29 @@ impl JsRuntimeAdapter {
                                 Ok(_) => {
                                     // Store the module namespace in a global variable for later access
                                     // This avoids re-importing the module
-                                    let namespace_key =
-                                        format!("__MODULE_NS_{}__", mod_id_owned.replace("-", "_"));
                                     if let Ok(namespace) = evaluated_module.namespace() {
-                                        if let Err(e) = ctx.globals().set(&namespace_key, namespace)
+                                        if let Err(e) = ctx.globals().set(namespace_key, namespace)
                                         {
                                             error!("Failed to store module namespace: {:?}", e);
                                         }
                                     }
-                                    Ok(module_path_str.clone())
+                                    Ok(())
                                 }
                                 Err(e) => {
                                     let error_msg = Self::format_js_error(&ctx, &e);
                                     error!("{}", error_msg);
-                                    Err(format!("JavaScript error in mod '{}'", mod_id_owned))
+                                    Err(js_error(
+                                        &format!("JavaScript error in mod '{}'", mod_id_owned),
+                                        &error_msg,
+                                    ))
                                 }
                             },
                             Err(e) => {
                                 let error_msg = Self::format_js_error(&ctx, &e);
                                 error!("{}", error_msg);
-                                Err(format!(
-                                    "JavaScript error evaluating mod '{}'",
-                                    mod_id_owned
+                                Err(js_error(
+                                    &format!("JavaScript error evaluating mod '{}'", mod_id_owned),
+                                    &error_msg,
                                 ))
                             }
                         }
@@ -816,48 +1158,128 @@ impl JsRuntimeAdapter {
                     Err(e) => {
                         let error_msg = Self::format_js_error(&ctx, &e);
                         error!("{}", error_msg);
-                        Err(format!("JavaScript error declaring mod '{}'", mod_id_owned))
+                        Err(js_error(
+                            &format!("JavaScript error declaring mod '{}'", mod_id_owned),
+                            &error_msg,
+                        ))
                     }
                 }
             })
-            .await;
+            .await
+    }
 
-        let stored_module_path = result.map_err(|e| -> Box<dyn std::error::Error> { e.into() })?;
+    /// Load another entry point script into an already loaded mod
+    ///
+    /// The script is evaluated in the mod's context, so it shares globals with
+    /// the other scripts of the mod. Its exports are merged into the mod's
+    /// namespace: a function exported by several scripts (e.g. `onAttach`) calls
+    /// each of them in load order. `@mod-id` imports keep resolving to the first
+    /// entry point.
+    ///
+    /// # Arguments
+    /// * `mod_path` - Path to the additional script
+    /// * `mod_id` - The mod identifier
+    pub async fn add_script_async(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError> {
+        trace!("Adding JavaScript script {} to mod {}", mod_path.display(), mod_id);
+        let context = match self.loaded_mods.get(mod_id) {
+            Some(loaded_mod) => loaded_mod.context.clone(),
+            None => return Err(RuntimeError::ModNotFound { mod_id: mod_id.to_string() }),
+        };
 
-        // Store the loaded mod
-        let _ = stored_module_path; // Used for logging/debugging if needed
-        self.loaded_mods.insert(
-            mod_id.to_string(),
-            LoadedMod {
-                context,
-                mod_dir,
-            },
-        );
+        let absolute_path = fs::canonicalize(mod_path).map_err(|e| {
+            RuntimeError::load_failed(
+                mod_id,
+                format!("Failed to canonicalize path '{}': {}", mod_path.display(), e),
+            )
+        })?;
+        let namespace_key = format!("__MODULE_NS_{}__", mod_id.replace("-", "_"));
+        let script_key = format!("__MODULE_NS_{}_SCRIPT__", mod_id.replace("-", "_"));
+        Self::eval_module(&context, mod_id, &absolute_path, &script_key).await?;
+
+        let has_on_tick = context
+            .with(|ctx| {
+                let globals = ctx.globals();
+                let merge: Function = ctx.eval(MERGE_NAMESPACES_JS)?;
+                let merged: Object = merge.call((
+                    globals.get::<_, Object>(&namespace_key)?,
+                    globals.get::<_, Object>(&script_key)?,
+                ))?;
+                globals.remove(script_key.as_str())?;
+                let has_on_tick = merged.get::<_, Function>("onTick").is_ok();
+                globals.set(&namespace_key, merged)?;
+                Ok::<bool, rquickjs::Error>(has_on_tick)
+            })
+            .await
+            .map_err(|e| RuntimeError::load_failed(mod_id, format!("Failed to merge script exports: {}", e)))?;
 
+        if let Some(loaded_mod) = self.loaded_mods.get_mut(mod_id) {
+            loaded_mod.has_on_tick = has_on_tick;
+        }
         Ok(())
     }
 
+    /// Call `onTick(deltaMs)` on every loaded mod that exports it
+    ///
+    /// Fast path for the server loop: mods without `onTick` are skipped without
+    /// entering their context, and a returned Promise is left to the event loop
+    /// instead of being awaited. Errors (including call timeouts) are logged and
+    /// don't stop other mods from ticking.
+    pub async fn dispatch_tick(&self, delta: Duration) {
+        let delta_ms = delta.as_secs_f64() * 1000.0;
+
+        for (mod_id, loaded_mod) in self.loaded_mods.iter().filter(|(_, m)| m.has_on_tick) {
+            let namespace_key = format!("__MODULE_NS_{}__", mod_id.replace("-", "_"));
+            let _deadline = self.call_deadline.arm(self.call_timeout);
+
+            let result: Result<(), String> = loaded_mod
+                .context
+                .with(|ctx| {
+                    let on_tick = ctx
+                        .globals()
+                        .get::<_, Object>(&namespace_key)
+                        .and_then(|namespace| namespace.get::<_, Function>("onTick"))
+                        .map_err(|e| format!("onTick not available: {:?}", e))?;
+                    on_tick
+                        .call::<_, Value>((delta_ms,))
+                        .map(|_| ())
+                        .map_err(|e| Self::format_js_error(&ctx, &e))
+                })
+                .await;
+
+            if let Err(e) = result {
+                if let Some(timeout) = self.call_timeout.filter(|_| self.call_deadline.is_expired()) {
+                    error!("{}", timeout_error_message(mod_id, "onTick", timeout));
+                } else {
+                    error!("JavaScript error in 'onTick' for mod '{}': {}", mod_id, e);
+                }
+            }
+        }
+    }
+
     /// Call a mod function asynchronously
     pub async fn call_mod_function_async(
         &mut self,
         mod_id: &str,
         function_name: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), RuntimeError> {
         //debug!("Calling JavaScript function '{}' for mod '{}'", function_name, mod_id);
 
         let loaded_mod = self
             .loaded_mods
             .get(mod_id)
-            .ok_or_else(|| format!("Mod '{}' not loaded", mod_id))?;
+            .ok_or_else(|| RuntimeError::ModNotFound { mod_id: mod_id.to_string() })?;
 
         let mod_id_owned = mod_id.to_string();
         let function_name_owned = function_name.to_string();
 
-        // Use Result<(), String> for ParallelSend compatibility
         // Get the stored module namespace from globals instead of re-importing
         let namespace_key = format!("__MODULE_NS_{}__", mod_id.replace("-", "_"));
 
-        let result: Result<(), String> = loaded_mod
+        // Abort the call if it runs past the configured call timeout
+        let _deadline = self.call_deadline.arm(self.call_timeout);
+        let memory_limit = self.config.memory_limit();
+
+        let result: Result<(), RuntimeError> = loaded_mod
             .context
             .with(|ctx| {
                 // Get the stored module namespace from globals
@@ -879,9 +1301,15 @@ impl JsRuntimeAdapter {
                                                 Err(e) => {
                                                     let error_msg = Self::format_js_error(&ctx, &e);
                                                     error!("{}", error_msg);
-                                                    Err(format!(
-                                                        "JavaScript error in async '{}' for mod '{}'",
-                                                        function_name_owned, mod_id_owned
+                                                    Err(mod_call_error(
+                                                        &e,
+                                                        &error_msg,
+                                                        memory_limit,
+                                                        &mod_id_owned,
+                                                        format!(
+                                                            "JavaScript error in async '{}' for mod '{}'",
+                                                            function_name_owned, mod_id_owned
+                                                        ),
                                                     ))
                                                 }
                                             }
@@ -894,9 +1322,15 @@ impl JsRuntimeAdapter {
                                     Err(e) => {
                                         let error_msg = Self::format_js_error(&ctx, &e);
                                         error!("{}", error_msg);
-                                        Err(format!(
-                                            "JavaScript error in '{}' for mod '{}'",
-                                            function_name_owned, mod_id_owned
+                                        Err(mod_call_error(
+                                            &e,
+                                            &error_msg,
+                                            memory_limit,
+                                            &mod_id_owned,
+                                            format!(
+                                                "JavaScript error in '{}' for mod '{}'",
+                                                function_name_owned, mod_id_owned
+                                            ),
                                         ))
                                     }
                                 }
@@ -915,16 +1349,119 @@ impl JsRuntimeAdapter {
                             "Failed to get module namespace '{}': {:?}",
                             namespace_key, e
                         );
-                        Err(format!(
+                        Err(RuntimeError::exception(format!(
                             "Failed to get module namespace for mod '{}'",
                             mod_id_owned
-                        ))
+                        )))
                     }
                 }
             })
             .await;
 
-        result.map_err(|e| -> Box<dyn std::error::Error> { e.into() })
+        // Reclaim what a call that hit the memory limit left behind so the
+        // runtime keeps serving other mods
+        if result.is_err() && memory_limit.is_some() {
+            self.runtime.run_gc().await;
+        }
+
+        self.check_call_timeout(result, mod_id, function_name)
+    }
+
+    /// Convert a mod call result, reporting calls interrupted by the call timeout
+    fn check_call_timeout<T>(
+        &self,
+        result: Result<T, RuntimeError>,
+        mod_id: &str,
+        function_name: &str,
+    ) -> Result<T, RuntimeError> {
+        match (result, self.call_timeout) {
+            (Err(_), Some(timeout)) if self.call_deadline.is_expired() => {
+                error!("{}", timeout_error_message(mod_id, function_name, timeout));
+                Err(RuntimeError::Timeout {
+                    mod_id: mod_id.to_string(),
+                    function_name: function_name.to_string(),
+                    timeout,
+                })
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Reload a mod in place
+    ///
+    /// Calls `onDetach` on the currently loaded instance (if any), drops its
+    /// event handlers and context, then loads the entry points again into a
+    /// fresh context and calls `onAttach`. Other mods in the same runtime are
+    /// left untouched.
+    ///
+    /// # Arguments
+    /// * `mod_paths` - Paths to the (possibly updated) entry points, in load order
+    /// * `mod_id` - The mod identifier
+    pub async fn reload_mod_async(
+        &mut self,
+        mod_paths: &[PathBuf],
+        mod_id: &str,
+    ) -> Result<(), RuntimeError> {
+        if self.loaded_mods.contains_key(mod_id) {
+            if let Err(e) = self.call_mod_function_async(mod_id, "onDetach").await {
+                // A failing onDetach must not prevent the new code from being loaded
+                tracing::warn!("onDetach failed for mod '{}' during reload: {}", mod_id, e);
+            }
+            self.drop_mod_state(mod_id)?;
+        }
+
+        let Some((first, others)) = mod_paths.split_first() else {
+            return Err(RuntimeError::load_failed(mod_id, "no entry point to load"));
+        };
+        self.load_mod_async(first, mod_id).await?;
+        for mod_path in others {
+            self.add_script_async(mod_path, mod_id).await?;
+        }
+        self.call_mod_function_async(mod_id, "onAttach").await
+    }
+
+    /// Unload a mod, dropping its context and event handlers
+    ///
+    /// Handlers registered by the mod are removed from the event dispatcher so
+    /// they are no longer dispatched to, its `@mod-id` alias is removed, and its
+    /// context (module namespace, stored callbacks) is dropped. `onDetach` is
+    /// not called here; callers run it first if needed.
+    ///
+    /// # Arguments
+    /// * `mod_id` - The mod identifier
+    fn drop_mod_state(&mut self, mod_id: &str) -> Result<(), RuntimeError> {
+        let handler_ids = self.handler_ids_for_mod(mod_id);
+        if self.loaded_mods.remove(mod_id).is_none() {
+            return Err(RuntimeError::ModNotFound { mod_id: mod_id.to_string() });
+        }
+
+        // The handler functions live in the mod's context and are dropped with it
+        let dispatcher = self.system_api.event_dispatcher();
+        for handler_id in &handler_ids {
+            dispatcher.unregister_handler(*handler_id);
+        }
+        unregister_mod_alias(mod_id);
+
+        // Timer tasks outlive the context, so stop them explicitly
+        let timers = self.cancel_mod_timers(mod_id);
+
+        // Service objects would keep the context alive, so they go with it
+        let services = self.system_api.unregister_mod_services(mod_id);
+        {
+            let mut store = self.services.lock().unwrap();
+            for name in &services {
+                store.remove(name);
+            }
+        }
+
+        debug!(
+            "Unloaded JavaScript mod '{}' ({} handlers removed, {} timers cancelled, {} services removed)",
+            mod_id,
+            handler_ids.len(),
+            timers,
+            services.len()
+        );
+        Ok(())
     }
 
     /// Dispatch a RequestUri event to all registered handlers
@@ -1129,8 +1666,18 @@ impl JsRuntimeAdapter {
         &self,
         request: &crate::api::TerminalKeyRequest,
     ) -> crate::api::TerminalKeyResponse {
-        let handlers = self.system_api.event_dispatcher().get_handlers_for_terminal_key();
+        let handlers = self.collect_handlers(&crate::api::EventKey::System(crate::api::SystemEvents::TerminalKeyPressed));
+        self.dispatch_terminal_key_to(request, &handlers).await
+    }
 
+    /// Dispatch a TerminalKeyPressed event to the given handlers, in order
+    ///
+    /// Used by the `RuntimeManager` to interleave handlers of several runtimes.
+    pub async fn dispatch_terminal_key_to(
+        &self,
+        request: &crate::api::TerminalKeyRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::TerminalKeyResponse {
         if handlers.is_empty() {
             return crate::api::TerminalKeyResponse::default();
         }
@@ -1271,91 +1818,468 @@ impl JsRuntimeAdapter {
         response
     }
 
-    /// Call a mod function asynchronously with return value
-    pub async fn call_mod_function_with_return_async(
-        &mut self,
-        mod_id: &str,
-        function_name: &str,
-    ) -> Result<ModReturnValue, Box<dyn std::error::Error>> {
-        //debug!("Calling JavaScript function '{}' for mod '{}' with return", function_name, mod_id);
-
-        let loaded_mod = self
-            .loaded_mods
-            .get(mod_id)
-            .ok_or_else(|| format!("Mod '{}' not loaded", mod_id))?;
-
-        let mod_id_owned = mod_id.to_string();
-        let function_name_owned = function_name.to_string();
+    /// Dispatch a TerminalMouse event to all registered handlers
+    ///
+    /// This method finds all handlers registered for TerminalMouse, calls them
+    /// in priority order (lowest first), and returns whether the event was handled.
+    ///
+    /// # Arguments
+    /// * `request` - The terminal mouse request (position, button and kind)
+    ///
+    /// # Returns
+    /// A `TerminalMouseResponse` containing whether the event was handled
+    pub async fn dispatch_terminal_mouse(
+        &self,
+        request: &crate::api::TerminalMouseRequest,
+    ) -> crate::api::TerminalMouseResponse {
+        let handlers = self.collect_handlers(&crate::api::EventKey::System(crate::api::SystemEvents::TerminalMouse));
+        self.dispatch_terminal_mouse_to(request, &handlers).await
+    }
 
-        // Get the stored module namespace from globals instead of re-importing
-        let namespace_key = format!("__MODULE_NS_{}__", mod_id.replace("-", "_"));
+    /// Dispatch a TerminalMouse event to the given handlers, in order
+    ///
+    /// Used by the `RuntimeManager` to interleave handlers of several runtimes.
+    pub async fn dispatch_terminal_mouse_to(
+        &self,
+        request: &crate::api::TerminalMouseRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::TerminalMouseResponse {
+        if handlers.is_empty() {
+            return crate::api::TerminalMouseResponse::default();
+        }
 
-        // Use Result<ModReturnValue, String> for ParallelSend compatibility
-        let result: Result<ModReturnValue, String> = loaded_mod
-            .context
-            .with(|ctx| {
-                // Get the stored module namespace from globals
-                match ctx.globals().get::<_, Object>(&namespace_key) {
-                    Ok(module_namespace) => {
-                        match module_namespace.get::<_, rquickjs::Function>(&function_name_owned) {
-                            Ok(func) => match func.call::<(), String>(()) {
-                                Ok(value) => {
-                                    debug!(
-                                        "Function '{}' returned string for mod '{}'",
-                                        function_name_owned, mod_id_owned
-                                    );
-                                    Ok(ModReturnValue::String(value))
-                                }
-                                Err(e) => {
-                                    let error_msg = Self::format_js_error(&ctx, &e);
-                                    error!("{}", error_msg);
-                                    Err(format!(
-                                        "JavaScript error in '{}' for mod '{}'",
-                                        function_name_owned, mod_id_owned
-                                    ))
-                                }
-                            },
-                            Err(_) => {
-                                debug!(
-                                    "Function '{}' not found or not exported for mod '{}'",
-                                    function_name_owned, mod_id_owned
-                                );
-                                Ok(ModReturnValue::None)
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!(
-                            "Failed to get module namespace '{}': {:?}",
-                            namespace_key, e
-                        );
-                        Err(format!(
-                            "Failed to get module namespace for mod '{}'",
-                            mod_id_owned
-                        ))
-                    }
-                }
-            })
-            .await;
+        trace!("Dispatching TerminalMouse to {} handlers: {} {} at ({}, {})", handlers.len(), request.kind, request.button, request.x, request.y);
 
-        result.map_err(|e| -> Box<dyn std::error::Error> { e.into() })
-    }
+        let mut response = crate::api::TerminalMouseResponse::default();
+
+        for handler in handlers {
+            // Get the mod's context
+            let loaded_mod = match self.loaded_mods.get(&handler.mod_id) {
+                Some(m) => m,
+                None => {
+                    error!("Handler mod '{}' not loaded", handler.mod_id);
+                    continue;
+                }
+            };
+
+            let handler_id = handler.handler_id;
+            let mod_id = handler.mod_id.clone();
+            let x = request.x;
+            let y = request.y;
+            let button = request.button.clone();
+            let kind = request.kind.clone();
+
+            // Step 1: Call the handler and detect if it returns a Promise
+            let call_result: Result<bool, String> = loaded_mod
+                .context
+                .with(|ctx| {
+                    // Get the handler function from the context's handler map
+                    match bindings::get_js_handler(&ctx, handler_id) {
+                        Ok(Some(func)) => {
+                            // Create request object
+                            let request_obj = Object::new(ctx.clone()).map_err(|e| format!("Failed to create request object: {:?}", e))?;
+                            request_obj.set("x", x).map_err(|e| format!("Failed to set x: {:?}", e))?;
+                            request_obj.set("y", y).map_err(|e| format!("Failed to set y: {:?}", e))?;
+                            request_obj.set("button", button.as_str()).map_err(|e| format!("Failed to set button: {:?}", e))?;
+                            request_obj.set("kind", kind.as_str()).map_err(|e| format!("Failed to set kind: {:?}", e))?;
+
+                            // Create response object
+                            let response_obj = Object::new(ctx.clone()).map_err(|e| format!("Failed to create response object: {:?}", e))?;
+                            response_obj.set("handled", false).map_err(|e| format!("Failed to set handled: {:?}", e))?;
+
+                            // Add setHandled method
+                            let set_handled = Function::new(ctx.clone(), |ctx: Ctx, handled: bool| -> rquickjs::Result<()> {
+                                let this: Object = ctx.globals().get("__currentTerminalMouseResponse")?;
+                                this.set("handled", handled)?;
+                                Ok(())
+                            }).map_err(|e| format!("Failed to create setHandled: {:?}", e))?;
+                            response_obj.set("setHandled", set_handled).map_err(|e| format!("Failed to set setHandled: {:?}", e))?;
+
+                            // Store response object as global for method access and later retrieval
+                            ctx.globals().set("__currentTerminalMouseResponse", response_obj.clone()).map_err(|e| format!("Failed to set __currentTerminalMouseResponse: {:?}", e))?;
+
+                            // Call the handler function
+                            let call_result = func.call::<(Object, Object), Value>((request_obj, response_obj));
+
+                            match call_result {
+                                Ok(result) => {
+                                    // Return true if handler returned a Promise, false otherwise
+                                    Ok(result.is_promise())
+                                }
+                                Err(e) => {
+                                    let error_msg = Self::format_js_error(&ctx, &e);
+                                    error!("Handler call error in mod '{}': {}", mod_id, error_msg);
+                                    Err(format!("Handler call error: {}", error_msg))
+                                }
+                            }
+                        }
+                        Ok(None) => {
+                            error!("Handler {} not found in mod '{}'", handler_id, mod_id);
+                            Err(format!("Handler {} not found", handler_id))
+                        }
+                        Err(e) => {
+                            error!("Failed to get handler {} from mod '{}': {:?}", handler_id, mod_id, e);
+                            Err(format!("Failed to get handler: {:?}", e))
+                        }
+                    }
+                })
+                .await;
+
+            // Check if handler call succeeded
+            let was_promise = match call_result {
+                Ok(is_promise) => is_promise,
+                Err(e) => {
+                    error!("Handler error: {}", e);
+                    continue;
+                }
+            };
+
+            // Step 2: If it was a Promise, we do NOT call runtime.idle() here.
+            // The main event loop will process pending JS jobs naturally via run_js_event_loop().
+            // Calling idle() here could cause deadlocks if the handler calls sendEvent(),
+            // because the sendEvent awaits a response that can't arrive until this function returns.
+            //
+            // Handlers that need async operations (like system.exit()) should set their
+            // response values synchronously before any await points.
+            if was_promise {
+                trace!("TerminalMouse handler returned Promise - async work will complete via event loop");
+            }
+
+            // Step 3: Read the response object
+            let result: Result<bool, String> = loaded_mod
+                .context
+                .with(|ctx| {
+                    // Get the response object from globals
+                    let response_obj: Object = ctx.globals().get("__currentTerminalMouseResponse")
+                        .map_err(|e| format!("Failed to get response object: {:?}", e))?;
+
+                    // Read back the response values
+                    let handled: bool = response_obj.get("handled").unwrap_or(false);
+                    Ok(handled)
+                })
+                .await;
+
+            // Update response based on handler result
+            match result {
+                Ok(handled) => {
+                    response.handled = handled;
+
+                    // If handler set handled=true, stop processing more handlers
+                    if handled {
+                        trace!("Handler in mod '{}' marked TerminalMouse as handled", handler.mod_id);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    error!("Handler execution failed: {}", e);
+                    // Continue to next handler on error
+                }
+            }
+        }
+
+        response
+    }
+
+    /// Dispatch a TerminalPaste event to all registered handlers
+    ///
+    /// This method finds all handlers registered for TerminalPaste, calls them
+    /// in priority order (lowest first), and returns whether the event was handled.
+    ///
+    /// # Arguments
+    /// * `request` - The terminal paste request containing the pasted text
+    ///
+    /// # Returns
+    /// A `TerminalPasteResponse` containing whether the event was handled
+    pub async fn dispatch_terminal_paste(
+        &self,
+        request: &crate::api::TerminalPasteRequest,
+    ) -> crate::api::TerminalPasteResponse {
+        let handlers = self.collect_handlers(&crate::api::EventKey::System(crate::api::SystemEvents::TerminalPaste));
+        self.dispatch_terminal_paste_to(request, &handlers).await
+    }
+
+    /// Dispatch a TerminalPaste event to the given handlers, in order
+    ///
+    /// Used by the `RuntimeManager` to interleave handlers of several runtimes.
+    pub async fn dispatch_terminal_paste_to(
+        &self,
+        request: &crate::api::TerminalPasteRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::TerminalPasteResponse {
+        if handlers.is_empty() {
+            return crate::api::TerminalPasteResponse::default();
+        }
+
+        debug!("Dispatching TerminalPaste to {} handlers ({} characters)", handlers.len(), request.text.chars().count());
+
+        let mut response = crate::api::TerminalPasteResponse::default();
+
+        for handler in handlers {
+            // Get the mod's context
+            let loaded_mod = match self.loaded_mods.get(&handler.mod_id) {
+                Some(m) => m,
+                None => {
+                    error!("Handler mod '{}' not loaded", handler.mod_id);
+                    continue;
+                }
+            };
+
+            let handler_id = handler.handler_id;
+            let mod_id = handler.mod_id.clone();
+            let text = request.text.clone();
+
+            // Step 1: Call the handler and detect if it returns a Promise
+            let call_result: Result<bool, String> = loaded_mod
+                .context
+                .with(|ctx| {
+                    // Get the handler function from the context's handler map
+                    match bindings::get_js_handler(&ctx, handler_id) {
+                        Ok(Some(func)) => {
+                            // Create request object
+                            let request_obj = Object::new(ctx.clone()).map_err(|e| format!("Failed to create request object: {:?}", e))?;
+                            request_obj.set("text", text.as_str()).map_err(|e| format!("Failed to set text: {:?}", e))?;
+
+                            // Create response object
+                            let response_obj = Object::new(ctx.clone()).map_err(|e| format!("Failed to create response object: {:?}", e))?;
+                            response_obj.set("handled", false).map_err(|e| format!("Failed to set handled: {:?}", e))?;
+
+                            // Add setHandled method
+                            let set_handled = Function::new(ctx.clone(), |ctx: Ctx, handled: bool| -> rquickjs::Result<()> {
+                                let this: Object = ctx.globals().get("__currentTerminalPasteResponse")?;
+                                this.set("handled", handled)?;
+                                Ok(())
+                            }).map_err(|e| format!("Failed to create setHandled: {:?}", e))?;
+                            response_obj.set("setHandled", set_handled).map_err(|e| format!("Failed to set setHandled: {:?}", e))?;
+
+                            // Store response object as global for method access and later retrieval
+                            ctx.globals().set("__currentTerminalPasteResponse", response_obj.clone()).map_err(|e| format!("Failed to set __currentTerminalPasteResponse: {:?}", e))?;
+
+                            // Call the handler function
+                            let call_result = func.call::<(Object, Object), Value>((request_obj, response_obj));
+
+                            match call_result {
+                                Ok(result) => {
+                                    // Return true if handler returned a Promise, false otherwise
+                                    Ok(result.is_promise())
+                                }
+                                Err(e) => {
+                                    let error_msg = Self::format_js_error(&ctx, &e);
+                                    error!("Handler call error in mod '{}': {}", mod_id, error_msg);
+                                    Err(format!("Handler call error: {}", error_msg))
+                                }
+                            }
+                        }
+                        Ok(None) => {
+                            error!("Handler {} not found in mod '{}'", handler_id, mod_id);
+                            Err(format!("Handler {} not found", handler_id))
+                        }
+                        Err(e) => {
+                            error!("Failed to get handler {} from mod '{}': {:?}", handler_id, mod_id, e);
+                            Err(format!("Failed to get handler: {:?}", e))
+                        }
+                    }
+                })
+                .await;
+
+            // Check if handler call succeeded
+            let was_promise = match call_result {
+                Ok(is_promise) => is_promise,
+                Err(e) => {
+                    error!("Handler error: {}", e);
+                    continue;
+                }
+            };
+
+            // Step 2: If it was a Promise, we do NOT call runtime.idle() here.
+            // The main event loop will process pending JS jobs naturally via run_js_event_loop().
+            // Calling idle() here could cause deadlocks if the handler calls sendEvent(),
+            // because the sendEvent awaits a response that can't arrive until this function returns.
+            //
+            // Handlers that need async operations (like system.exit()) should set their
+            // response values synchronously before any await points.
+            if was_promise {
+                trace!("TerminalPaste handler returned Promise - async work will complete via event loop");
+            }
+
+            // Step 3: Read the response object
+            let result: Result<bool, String> = loaded_mod
+                .context
+                .with(|ctx| {
+                    // Get the response object from globals
+                    let response_obj: Object = ctx.globals().get("__currentTerminalPasteResponse")
+                        .map_err(|e| format!("Failed to get response object: {:?}", e))?;
+
+                    // Read back the response values
+                    let handled: bool = response_obj.get("handled").unwrap_or(false);
+                    Ok(handled)
+                })
+                .await;
+
+            // Update response based on handler result
+            match result {
+                Ok(handled) => {
+                    response.handled = handled;
+
+                    // If handler set handled=true, stop processing more handlers
+                    if handled {
+                        trace!("Handler in mod '{}' marked TerminalPaste as handled", handler.mod_id);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    error!("Handler execution failed: {}", e);
+                    // Continue to next handler on error
+                }
+            }
+        }
+
+        response
+    }
+
+    /// Call a mod function asynchronously with return value
+    pub async fn call_mod_function_with_return_async(
+        &mut self,
+        mod_id: &str,
+        function_name: &str,
+    ) -> Result<ModReturnValue, RuntimeError> {
+        //debug!("Calling JavaScript function '{}' for mod '{}' with return", function_name, mod_id);
+
+        let loaded_mod = self
+            .loaded_mods
+            .get(mod_id)
+            .ok_or_else(|| RuntimeError::ModNotFound { mod_id: mod_id.to_string() })?;
+
+        let mod_id_owned = mod_id.to_string();
+        let function_name_owned = function_name.to_string();
+
+        // Get the stored module namespace from globals instead of re-importing
+        let namespace_key = format!("__MODULE_NS_{}__", mod_id.replace("-", "_"));
+
+        // Abort the call if it runs past the configured call timeout
+        let _deadline = self.call_deadline.arm(self.call_timeout);
+        let memory_limit = self.config.memory_limit();
+
+        let result: Result<ModReturnValue, RuntimeError> = loaded_mod
+            .context
+            .with(|ctx| {
+                // Get the stored module namespace from globals
+                match ctx.globals().get::<_, Object>(&namespace_key) {
+                    Ok(module_namespace) => {
+                        match module_namespace.get::<_, rquickjs::Function>(&function_name_owned) {
+                            Ok(func) => match func.call::<(), Value>(()).and_then(|value| {
+                                // Async functions: resolve the Promise to its value
+                                match value.clone().into_promise() {
+                                    Some(promise) => promise.finish::<Value>(),
+                                    None => Ok(value),
+                                }
+                            }) {
+                                Ok(value) => {
+                                    let return_value = js_value_to_return_value(&value);
+                                    debug!(
+                                        "Function '{}' returned {:?} for mod '{}'",
+                                        function_name_owned, return_value, mod_id_owned
+                                    );
+                                    return_value
+                                }
+                                Err(e) => {
+                                    let error_msg = Self::format_js_error(&ctx, &e);
+                                    error!("{}", error_msg);
+                                    Err(mod_call_error(
+                                        &e,
+                                        &error_msg,
+                                        memory_limit,
+                                        &mod_id_owned,
+                                        format!(
+                                            "JavaScript error in '{}' for mod '{}'",
+                                            function_name_owned, mod_id_owned
+                                        ),
+                                    ))
+                                }
+                            },
+                            Err(_) => {
+                                debug!(
+                                    "Function '{}' not found or not exported for mod '{}'",
+                                    function_name_owned, mod_id_owned
+                                );
+                                Ok(ModReturnValue::None)
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to get module namespace '{}': {:?}",
+                            namespace_key, e
+                        );
+                        Err(RuntimeError::exception(format!(
+                            "Failed to get module namespace for mod '{}'",
+                            mod_id_owned
+                        )))
+                    }
+                }
+            })
+            .await;
+
+        // Reclaim what a call that hit the memory limit left behind so the
+        // runtime keeps serving other mods
+        if result.is_err() && memory_limit.is_some() {
+            self.runtime.run_gc().await;
+        }
+
+        self.check_call_timeout(result, mod_id, function_name)
+    }
 
     /// Call an event handler asynchronously
     ///
-    /// This method finds the handler by its ID and calls it with the event name and arguments.
-    /// The handler function was stored in the context's handler map during registration.
+    /// This method finds the handler by its ID and calls it with the event name and arguments.
+    /// The handler function was stored in the context's handler map during registration.
+    ///
+    /// # Arguments
+    /// * `handler_id` - The unique handler ID returned from registration
+    /// * `event_name` - The name of the event being dispatched
+    /// * `args` - JSON-serialized arguments to pass to the handler
+    pub async fn call_event_handler_async(
+        &self,
+        handler_id: u64,
+        event_name: &str,
+        args: &[String],
+    ) -> Result<(), RuntimeError> {
+        self.invoke_event_handler(handler_id, event_name, args, false)
+            .await
+            .map(|_| ())
+    }
+
+    /// Call an event handler asynchronously and return its value
+    ///
+    /// The handler's return value is captured synchronously and converted to a
+    /// `ModReturnValue`. A handler that returns a Promise (an `async` handler)
+    /// yields `ModReturnValue::None`: values produced after an `await` are not
+    /// captured, like custom event response values.
     ///
     /// # Arguments
     /// * `handler_id` - The unique handler ID returned from registration
     /// * `event_name` - The name of the event being dispatched
     /// * `args` - JSON-serialized arguments to pass to the handler
-    pub async fn call_event_handler_async(
+    pub async fn call_event_handler_with_return_async(
         &self,
         handler_id: u64,
         event_name: &str,
         args: &[String],
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<ModReturnValue, RuntimeError> {
+        self.invoke_event_handler(handler_id, event_name, args, true).await
+    }
+
+    /// Find an event handler in the loaded mods and call it
+    ///
+    /// With `capture_return` the handler's synchronous return value is converted
+    /// and returned; otherwise a returned Promise is resolved before returning
+    /// and the value is `ModReturnValue::None`.
+    async fn invoke_event_handler(
+        &self,
+        handler_id: u64,
+        event_name: &str,
+        args: &[String],
+        capture_return: bool,
+    ) -> Result<ModReturnValue, RuntimeError> {
         trace!("Calling event handler {} for event '{}'", handler_id, event_name);
 
         // We need to find which mod context has this handler
@@ -1364,7 +2288,8 @@ impl JsRuntimeAdapter {
             let event_name_owned = event_name.to_string();
             let args_owned: Vec<String> = args.to_vec();
 
-            let result: Result<bool, String> = loaded_mod
+            // None when the handler is not in this context
+            let result: Result<Option<ModReturnValue>, RuntimeError> = loaded_mod
                 .context
                 .with(|ctx| {
                     // Try to get the handler function from this context
@@ -1373,8 +2298,9 @@ impl JsRuntimeAdapter {
                             // Found the handler! Call it with event_name and args
 
                             // Convert args to JavaScript values (parse JSON strings)
-                            let js_args = rquickjs::Array::new(ctx.clone())
-                                .map_err(|e| format!("Failed to create args array: {:?}", e))?;
+                            let js_args = rquickjs::Array::new(ctx.clone()).map_err(|e| {
+                                RuntimeError::exception(format!("Failed to create args array: {:?}", e))
+                            })?;
 
                             for (i, arg) in args_owned.iter().enumerate() {
                                 // Try to parse as JSON, otherwise use as string
@@ -1385,63 +2311,67 @@ impl JsRuntimeAdapter {
                                             .map(|s| s.into())
                                             .unwrap_or(Value::new_undefined(ctx.clone()))
                                     });
-                                js_args.set(i, js_value)
-                                    .map_err(|e| format!("Failed to set arg {}: {:?}", i, e))?;
+                                js_args.set(i, js_value).map_err(|e| {
+                                    RuntimeError::exception(format!("Failed to set arg {}: {:?}", i, e))
+                                })?;
                             }
 
                             // Call the handler function with event_name and args array
                             let call_result = func.call::<(String, rquickjs::Array), Value>((event_name_owned.clone(), js_args));
 
                             match call_result {
-                                Ok(result) => {
-                                    // If result is a Promise, resolve it
-                                    if let Some(promise) = result.into_promise() {
+                                Ok(result) => match result.clone().into_promise() {
+                                    Some(_) if capture_return => {
+                                        // Values resolved after an await are not captured;
+                                        // the event loop finishes the Promise
+                                        debug!("Event handler {} is async, its return value is not captured", handler_id);
+                                        Ok(Some(ModReturnValue::None))
+                                    }
+                                    Some(promise) => {
                                         if let Err(e) = promise.finish::<()>() {
                                             let error_msg = Self::format_js_error(&ctx, &e);
                                             error!("Event handler error: {}", error_msg);
-                                            return Err(format!("Event handler error: {}", error_msg));
+                                            return Err(js_error("Event handler error", &error_msg));
                                         }
+                                        Ok(Some(ModReturnValue::None))
                                     }
-                                    Ok(true) // Found and called successfully
-                                }
+                                    None if capture_return => js_value_to_return_value(&result).map(Some),
+                                    None => Ok(Some(ModReturnValue::None)),
+                                },
                                 Err(e) => {
                                     let error_msg = Self::format_js_error(&ctx, &e);
                                     error!("Event handler call error: {}", error_msg);
-                                    Err(format!("Event handler call error: {}", error_msg))
+                                    Err(js_error("Event handler call error", &error_msg))
                                 }
                             }
                         }
                         Ok(None) => {
                             // Handler not found in this context, try next mod
-                            Ok(false)
+                            Ok(None)
                         }
                         Err(e) => {
                             // Error getting handler, try next mod
                             error!("Error getting handler {} from mod '{}': {:?}", handler_id, mod_id, e);
-                            Ok(false)
+                            Ok(None)
                         }
                     }
                 })
                 .await;
 
             match result {
-                Ok(true) => {
-                    // Handler was found and called successfully
-                    return Ok(());
-                }
-                Ok(false) => {
-                    // Handler not in this mod, continue searching
-                    continue;
-                }
-                Err(e) => {
-                    // Handler found but execution failed
-                    return Err(e.into());
-                }
+                // Handler was found and called (successfully or not)
+                Ok(Some(value)) => return Ok(value),
+                // Handler not in this mod, continue searching
+                Ok(None) => continue,
+                Err(e) => return Err(e),
             }
         }
 
         // Handler not found in any mod
-        Err(format!("Event handler {} not found in any loaded mod", handler_id).into())
+        Err(RuntimeError::exception(format!(
+            "Event handler {} not found in any loaded mod",
+            handler_id
+        )))
     }
 
     /// Cleanup temp files created during script execution
@@ -1462,10 +2392,20 @@ impl JsRuntimeAdapter {
     /// to receive commands. This is a client-only event.
     pub async fn dispatch_graphic_engine_ready(
         &self,
-        _request: &crate::api::GraphicEngineReadyRequest,
+        request: &crate::api::GraphicEngineReadyRequest,
     ) -> crate::api::GraphicEngineReadyResponse {
-        let handlers = self.system_api.event_dispatcher().get_handlers_for_graphic_engine_ready();
+        let handlers = self.collect_handlers(&crate::api::EventKey::System(crate::api::SystemEvents::GraphicEngineReady));
+        self.dispatch_graphic_engine_ready_to(request, &handlers).await
+    }
 
+    /// Dispatch GraphicEngineReady event to the given handlers, in order
+    ///
+    /// Used by the `RuntimeManager` to interleave handlers of several runtimes.
+    pub async fn dispatch_graphic_engine_ready_to(
+        &self,
+        _request: &crate::api::GraphicEngineReadyRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::GraphicEngineReadyResponse {
         if handlers.is_empty() {
             return crate::api::GraphicEngineReadyResponse::default();
         }
@@ -1586,8 +2526,18 @@ impl JsRuntimeAdapter {
         &self,
         request: &crate::api::GraphicEngineWindowClosedRequest,
     ) -> crate::api::GraphicEngineWindowClosedResponse {
-        let handlers = self.system_api.event_dispatcher().get_handlers_for_graphic_engine_window_closed();
+        let handlers = self.collect_handlers(&crate::api::EventKey::System(crate::api::SystemEvents::GraphicEngineWindowClosed));
+        self.dispatch_graphic_engine_window_closed_to(request, &handlers).await
+    }
 
+    /// Dispatch GraphicEngineWindowClosed event to the given handlers, in order
+    ///
+    /// Used by the `RuntimeManager` to interleave handlers of several runtimes.
+    pub async fn dispatch_graphic_engine_window_closed_to(
+        &self,
+        request: &crate::api::GraphicEngineWindowClosedRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::GraphicEngineWindowClosedResponse {
         if handlers.is_empty() {
             return crate::api::GraphicEngineWindowClosedResponse::default();
         }
@@ -1710,7 +2660,23 @@ impl JsRuntimeAdapter {
         &self,
         request: &crate::api::CustomEventRequest,
     ) -> crate::api::CustomEventResponse {
-        let handlers = self.system_api.event_dispatcher().get_handlers_for_custom_event(&request.event_name);
+        let handlers = self.collect_handlers(&crate::api::EventKey::Custom(request.event_name.clone()));
+        self.dispatch_custom_event_to(request, &handlers).await
+    }
+
+    /// Dispatch a custom event to the given handlers, in order
+    ///
+    /// Handlers the request doesn't accept (`CustomEventRequest::accepts`) are skipped.
+    /// Used by the `RuntimeManager` to interleave handlers of several runtimes.
+    pub async fn dispatch_custom_event_to(
+        &self,
+        request: &crate::api::CustomEventRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::CustomEventResponse {
+        let handlers: Vec<_> = handlers
+            .iter()
+            .filter(|handler| request.accepts(handler))
+            .collect();
 
         if handlers.is_empty() {
             trace!("No handlers registered for custom event '{}'", request.event_name);
@@ -1896,7 +2862,7 @@ impl JsRuntimeAdapter {
         entity_id: u64,
         event_type: &str,
         event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, RuntimeError> {
         // Entity callbacks are registered globally, not per-mod
         // We need to iterate through all contexts to find the one with the callback
         for loaded_mod in self.loaded_mods.values() {
@@ -1940,6 +2906,9 @@ impl JsRuntimeAdapter {
                     if let Some(y) = event_data_clone.get("y").and_then(|v| v.as_f64()) {
                         event_obj.set("y", y as f32).ok();
                     }
+                    if let Some(value) = event_data_clone.get("value").and_then(|v| v.as_str()) {
+                        event_obj.set("value", value).ok();
+                    }
                     event_obj.set("entityId", entity_id).ok();
                     event_obj.set("eventType", event_type_owned.as_str()).ok();
 
@@ -1967,7 +2936,7 @@ impl JsRuntimeAdapter {
             match result {
                 Ok(true) => return Ok(true), // Callback found and invoked
                 Ok(false) => continue, // Not in this context, try next
-                Err(e) => return Err(e.into()), // Error during invocation
+                Err(e) => return Err(RuntimeError::exception(e)), // Error during invocation
             }
         }
 
@@ -1979,15 +2948,21 @@ impl JsRuntimeAdapter {
     ///
     /// This looks up the callback in __WINDOW_EVENT_CALLBACKS__[windowId][eventType]
     /// and invokes it with the window object and event data.
+    ///
+    /// Every mod that set a callback on this window gets it invoked; callbacks set on
+    /// other windows never are. A failing callback does not prevent the others from
+    /// running. After a "close" event the window's callbacks are dropped.
     pub async fn dispatch_window_event_callback_async(
         &self,
         window_id: u64,
         event_type: &str,
         event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
-        // Window callbacks are registered globally, not per-mod
-        // We need to iterate through all contexts to find the one with the callback
-        for loaded_mod in self.loaded_mods.values() {
+    ) -> Result<bool, RuntimeError> {
+        let mut handled = false;
+        let mut first_error = None;
+
+        // Each mod context keeps its own registry, so visit all of them
+        for (mod_id, loaded_mod) in &self.loaded_mods {
             let event_type_owned = event_type.to_string();
             let event_data_clone = event_data.clone();
             let graphic_proxy = self.graphic_proxy.clone();
@@ -2122,14 +3097,30 @@ impl JsRuntimeAdapter {
                 .await;
 
             match result {
-                Ok(true) => return Ok(true), // Callback found and invoked
-                Ok(false) => continue, // Not in this context, try next
-                Err(e) => return Err(e.into()), // Error during invocation
+                Ok(invoked) => handled |= invoked,
+                Err(e) => {
+                    error!("Window {} '{}' callback of mod '{}' failed: {}", window_id, event_type, mod_id, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+
+            if event_type == "close" {
+                loaded_mod
+                    .context
+                    .with(|ctx| {
+                        let registry: Option<rquickjs::Object> = ctx.globals().get("__WINDOW_EVENT_CALLBACKS__").ok();
+                        if let Some(registry) = registry {
+                            let _ = registry.remove(window_id.to_string());
+                        }
+                    })
+                    .await;
             }
         }
 
-        // No callback found in any context
-        Ok(false)
+        match first_error {
+            Some(e) => Err(RuntimeError::exception(e)),
+            None => Ok(handled),
+        }
     }
 }
 
@@ -2137,6 +3128,8 @@ impl Drop for JsRuntimeAdapter {
     fn drop(&mut self) {
         // Cleanup temp files when the runtime is dropped
         self.cleanup_temp_files();
+        // Service objects must not outlive the runtime they belong to
+        self.services.lock().unwrap().clear();
     }
 }
 
@@ -2146,17 +3139,23 @@ impl RuntimeAdapter for JsRuntimeAdapter {
         &mut self,
         mod_path: &Path,
         mod_id: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), RuntimeError> {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(self.load_mod_async(mod_path, mod_id))
         })
     }
 
+    fn add_mod_script(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(self.add_script_async(mod_path, mod_id))
+        })
+    }
+
     fn call_mod_function(
         &mut self,
         mod_id: &str,
         function_name: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), RuntimeError> {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
                 .block_on(self.call_mod_function_async(mod_id, function_name))
@@ -2169,35 +3168,105 @@ impl RuntimeAdapter for JsRuntimeAdapter {
         &mut self,
         mod_id: &str,
         function_name: &str,
-    ) -> Result<ModReturnValue, Box<dyn std::error::Error>> {
+    ) -> Result<ModReturnValue, RuntimeError> {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
                 .block_on(self.call_mod_function_with_return_async(mod_id, function_name))
         })
     }
 
+    fn set_call_timeout(&mut self, timeout: Option<Duration>) {
+        self.call_timeout = timeout;
+    }
+
+    fn supports_unload(&self) -> bool {
+        true
+    }
+
+    fn unload_mod(&mut self, mod_id: &str) -> Result<(), RuntimeError> {
+        self.drop_mod_state(mod_id)
+    }
+
+    fn handler_ids_for_mod(&self, mod_id: &str) -> Vec<u64> {
+        if !self.loaded_mods.contains_key(mod_id) {
+            return Vec::new();
+        }
+        self.system_api.event_dispatcher().handler_ids_for_mod(mod_id)
+    }
+
     fn call_event_handler(
         &mut self,
         handler_id: u64,
         event_name: &str,
         args: &[String],
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), RuntimeError> {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
                 .block_on(self.call_event_handler_async(handler_id, event_name, args))
         })
     }
 
+    fn call_event_handler_with_return(
+        &mut self,
+        handler_id: u64,
+        event_name: &str,
+        args: &[String],
+    ) -> Result<ModReturnValue, RuntimeError> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(self.call_event_handler_with_return_async(handler_id, event_name, args))
+        })
+    }
+
     fn dispatch_terminal_key(
         &self,
         request: &crate::api::TerminalKeyRequest,
+        handlers: &[crate::api::HandlerRef],
     ) -> crate::api::TerminalKeyResponse {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
-                .block_on(self.dispatch_terminal_key(request))
+                .block_on(self.dispatch_terminal_key_to(request, handlers))
+        })
+    }
+
+    fn dispatch_terminal_mouse(
+        &self,
+        request: &crate::api::TerminalMouseRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::TerminalMouseResponse {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(self.dispatch_terminal_mouse_to(request, handlers))
+        })
+    }
+
+    fn dispatch_terminal_paste(
+        &self,
+        request: &crate::api::TerminalPasteRequest,
+        handlers: &[crate::api::HandlerRef],
+    ) -> crate::api::TerminalPasteResponse {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current()
+                .block_on(self.dispatch_terminal_paste_to(request, handlers))
+        })
+    }
+
+    fn dispatch_tick(&self, delta: Duration) {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(JsRuntimeAdapter::dispatch_tick(self, delta))
         })
     }
 
+    fn collect_handlers(&self, event: &crate::api::EventKey) -> Vec<crate::api::HandlerRef> {
+        self.system_api
+            .event_dispatcher()
+            .get_handlers_for_key(event)
+            .iter()
+            .filter(|handler| self.loaded_mods.contains_key(&handler.mod_id))
+            .map(crate::api::HandlerRef::from)
+            .collect()
+    }
+
     fn terminal_key_handler_count(&self) -> usize {
         self.system_api
             .event_dispatcher()
@@ -2207,30 +3276,33 @@ impl RuntimeAdapter for JsRuntimeAdapter {
     fn dispatch_graphic_engine_ready(
         &self,
         request: &crate::api::GraphicEngineReadyRequest,
+        handlers: &[crate::api::HandlerRef],
     ) -> crate::api::GraphicEngineReadyResponse {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
-                .block_on(self.dispatch_graphic_engine_ready(request))
+                .block_on(self.dispatch_graphic_engine_ready_to(request, handlers))
         })
     }
 
     fn dispatch_graphic_engine_window_closed(
         &self,
         request: &crate::api::GraphicEngineWindowClosedRequest,
+        handlers: &[crate::api::HandlerRef],
     ) -> crate::api::GraphicEngineWindowClosedResponse {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
-                .block_on(self.dispatch_graphic_engine_window_closed(request))
+                .block_on(self.dispatch_graphic_engine_window_closed_to(request, handlers))
         })
     }
 
     fn dispatch_custom_event(
         &self,
         request: &crate::api::CustomEventRequest,
+        handlers: &[crate::api::HandlerRef],
     ) -> crate::api::CustomEventResponse {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
-                .block_on(self.dispatch_custom_event(request))
+                .block_on(self.dispatch_custom_event_to(request, handlers))
         })
     }
 
@@ -2239,7 +3311,7 @@ impl RuntimeAdapter for JsRuntimeAdapter {
         entity_id: u64,
         event_type: &str,
         event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, RuntimeError> {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
                 .block_on(self.dispatch_entity_event_callback_async(entity_id, event_type, event_data))
@@ -2251,7 +3323,7 @@ impl RuntimeAdapter for JsRuntimeAdapter {
         window_id: u64,
         event_type: &str,
         event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, RuntimeError> {
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current()
                 .block_on(self.dispatch_window_event_callback_async(window_id, event_type, event_data))
@@ -2313,3 +3385,853 @@ pub async fn flush_pending_jobs(runtime: &Arc<AsyncRuntime>) -> bool {
     // Check if any fatal error occurred during processing
     has_fatal_error()
 }
+
+/// Longest time the hosts wait for pending JavaScript jobs when shutting down
+pub const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Run pending JavaScript jobs until none is left or `timeout` elapses
+///
+/// Called on shutdown, after `onShutdown`, so promises started by the mods (e.g. saving
+/// state) can settle before the process ends. A mod with a running `setInterval` never
+/// lets the runtime become idle, hence the bound.
+///
+/// Returns `true` if every job drained in time.
+pub async fn drain_pending_jobs(runtime: &Arc<AsyncRuntime>, timeout: Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if tokio::time::timeout_at(deadline, flush_pending_jobs(runtime)).await.is_err() {
+            return false;
+        }
+        if !runtime.is_job_pending().await {
+            return true;
+        }
+    }
+}
+
+/// How often the hosts sample the JavaScript heap
+pub const MEMORY_STATS_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Sample the JavaScript heap of a runtime
+async fn js_memory_stats(runtime: &AsyncRuntime) -> JsMemoryStats {
+    let usage = runtime.memory_usage().await;
+    JsMemoryStats {
+        used_bytes: u64::try_from(usage.memory_used_size).unwrap_or(0),
+        allocated_bytes: u64::try_from(usage.malloc_size).unwrap_or(0),
+        object_count: u64::try_from(usage.obj_count).unwrap_or(0),
+    }
+}
+
+/// Sample the JavaScript heap every `interval` and run collections requested by mods
+///
+/// Each sample is logged at trace level and stored in `system_api` for
+/// `System.getJsMemoryStats()`. `System.gc()` wakes the monitor, which collects
+/// (between jobs, see `JsRuntimeAdapter::force_gc`) and samples right away.
+/// Never returns: hosts poll it next to `run_js_event_loop`.
+pub async fn run_js_memory_monitor(runtime: Arc<AsyncRuntime>, system_api: SystemApi, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = system_api.gc_requested() => {
+                runtime.run_gc().await;
+                trace!("JavaScript garbage collection requested by a mod");
+            }
+        }
+        let stats = js_memory_stats(&runtime).await;
+        trace!(
+            "JavaScript heap: {} bytes used, {} bytes allocated, {} objects",
+            stats.used_bytes, stats.allocated_bytes, stats.object_count
+        );
+        system_api.set_js_memory_stats(stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::CustomEventRequest;
+
+    /// Write a mod whose onAttach registers a "ping" handler setting `res.<property>`
+    fn write_ping_mod(root: &Path, mod_id: &str, property: &str) -> PathBuf {
+        let mod_dir = root.join("mods").join(mod_id);
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            format!(
+                "export function onAttach() {{\n    System.registerEvent(\"ping\", (req, res) => {{ res.{} = true; }}, 0);\n}}\n",
+                property
+            ),
+        )
+        .unwrap();
+        entry_point
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unload_cancels_mod_timers() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+        let storage = adapter.storage_api().clone();
+
+        let mod_dir = temp.path().join("mods").join("ticker");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function onAttach() {\n    setInterval(() => Storage.set(\"ticks\", (Storage.get(\"ticks\") ?? 0) + 1), 5);\n}\n",
+        )
+        .unwrap();
+        adapter.capability_policy().declare("ticker", Some(&["storage"])).unwrap();
+        adapter.load_mod_async(&entry_point, "ticker").await.unwrap();
+        adapter.call_mod_function_async("ticker", "onAttach").await.unwrap();
+        assert_eq!(adapter.pending_timer_count(), 1);
+
+        let runtime = adapter.get_runtime();
+        let pump = || async {
+            let _ = tokio::time::timeout(Duration::from_millis(50), runtime.drive()).await;
+        };
+        let ticks = || {
+            storage
+                .get("ticker", "ticks")
+                .unwrap()
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0)
+        };
+
+        pump().await;
+        assert!(ticks() > 0, "interval should fire while the mod is loaded");
+
+        RuntimeAdapter::unload_mod(&mut adapter, "ticker").unwrap();
+        assert_eq!(adapter.timers.pending_count_for_mod("ticker"), 0);
+
+        let after_unload = ticks();
+        pump().await;
+        assert_eq!(ticks(), after_unload, "interval must stop after unload");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unload_keeps_timers_of_other_adapters() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut adapters = Vec::new();
+        for game in ["game_a", "game_b"] {
+            let game_dir = temp.path().join(game);
+            let config = JsRuntimeConfig::new(game_dir.join("data"), game_dir.join("config"));
+            let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+            adapter.storage_api().set_game_root(game_dir.clone());
+
+            let mod_dir = game_dir.join("mods").join("ticker");
+            fs::create_dir_all(&mod_dir).unwrap();
+            let entry_point = mod_dir.join("index.js");
+            fs::write(
+                &entry_point,
+                "export function onAttach() {\n    globalThis.__MOD_ID__ = \"other\";\n    setInterval(() => {}, 1000);\n}\n",
+            )
+            .unwrap();
+            adapter.load_mod_async(&entry_point, "ticker").await.unwrap();
+            adapter.call_mod_function_async("ticker", "onAttach").await.unwrap();
+            adapters.push(adapter);
+        }
+
+        assert_eq!(adapters[0].pending_timer_count(), 1);
+        assert_eq!(adapters[1].pending_timer_count(), 1);
+        assert_eq!(adapters[0].timers.pending_count_for_mod("ticker"), 1);
+
+        RuntimeAdapter::unload_mod(&mut adapters[0], "ticker").unwrap();
+        assert_eq!(adapters[0].pending_timer_count(), 0);
+        assert_eq!(adapters[1].pending_timer_count(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_storage_owner_ignores_mod_id_global() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+        let storage = adapter.storage_api().clone();
+        storage.set("victim", "secret", serde_json::json!("hidden")).unwrap();
+
+        let mod_dir = temp.path().join("mods").join("spoofer");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function onAttach() {\n    globalThis.__MOD_ID__ = \"victim\";\n    Storage.set(\"seen\", Storage.get(\"secret\") ?? null);\n    Storage.set(\"secret\", \"overwritten\");\n}\n",
+        )
+        .unwrap();
+        adapter.capability_policy().declare("spoofer", Some(&["storage"])).unwrap();
+        adapter.load_mod_async(&entry_point, "spoofer").await.unwrap();
+        adapter.call_mod_function_async("spoofer", "onAttach").await.unwrap();
+
+        assert_eq!(storage.get("spoofer", "seen").unwrap(), Some(serde_json::Value::Null));
+        assert_eq!(storage.get("spoofer", "secret").unwrap(), Some(serde_json::json!("overwritten")));
+        assert_eq!(storage.get("victim", "secret").unwrap(), Some(serde_json::json!("hidden")));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_additional_scripts_share_the_mod() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+        let storage = adapter.storage_api().clone();
+
+        let mod_dir = temp.path().join("mods").join("multi");
+        fs::create_dir_all(&mod_dir).unwrap();
+        fs::write(
+            mod_dir.join("shared.js"),
+            "globalThis.greeting = \"hello\";\nexport function onAttach() { Storage.set(\"shared\", true); }\n",
+        )
+        .unwrap();
+        fs::write(
+            mod_dir.join("client.js"),
+            "export function onAttach() { Storage.set(\"client\", greeting); }\nexport function onBootstrap() { Storage.set(\"bootstrapped\", true); }\n",
+        )
+        .unwrap();
+
+        adapter.capability_policy().declare("multi", Some(&["storage"])).unwrap();
+        adapter.load_mod_async(&mod_dir.join("shared.js"), "multi").await.unwrap();
+        adapter.add_script_async(&mod_dir.join("client.js"), "multi").await.unwrap();
+
+        // onAttach is exported by both scripts, onBootstrap only by the second
+        adapter.call_mod_function_async("multi", "onAttach").await.unwrap();
+        adapter.call_mod_function_async("multi", "onBootstrap").await.unwrap();
+        assert_eq!(storage.get("multi", "shared").unwrap(), Some(serde_json::Value::Bool(true)));
+        assert_eq!(storage.get("multi", "client").unwrap(), Some(serde_json::json!("hello")));
+        assert_eq!(storage.get("multi", "bootstrapped").unwrap(), Some(serde_json::Value::Bool(true)));
+
+        assert!(matches!(
+            adapter.add_script_async(&mod_dir.join("client.js"), "missing").await,
+            Err(RuntimeError::ModNotFound { .. })
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_services_are_shared_between_mods() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+        let storage = adapter.storage_api().clone();
+
+        let items_dir = temp.path().join("mods").join("items");
+        fs::create_dir_all(&items_dir).unwrap();
+        fs::write(
+            items_dir.join("index.js"),
+            "const items = new Map();
+export function onAttach() {
+    System.registerService(\"inventory\", {
+        add(item, amount) { items.set(item, (items.get(item) ?? 0) + amount); return { item, total: items.get(item) }; },
+        count(item) { return items.get(item) ?? 0; },
+    });
+}
+",
+        )
+        .unwrap();
+        let shop_dir = temp.path().join("mods").join("shop");
+        fs::create_dir_all(&shop_dir).unwrap();
+        fs::write(
+            shop_dir.join("index.js"),
+            "const describe = (call) => { try { return call(); } catch (e) { return String(e); } };
+export function onAttach() {
+    Storage.set(\"missing\", describe(() => System.getService(\"quests\")));
+    globalThis.__MOD_ID__ = \"items\";
+    Storage.set(\"taken\", describe(() => System.registerService(\"inventory\", {})));
+    globalThis.inventory = System.getService(\"inventory\");
+    Storage.set(\"added\", inventory.add(\"potion\", 2));
+    Storage.set(\"count\", inventory.count(\"potion\"));
+}
+export function onCheck() {
+    Storage.set(\"stale\", describe(() => inventory.count(\"potion\")));
+}
+",
+        )
+        .unwrap();
+
+        for mod_id in ["items", "shop"] {
+            let dir = temp.path().join("mods").join(mod_id);
+            adapter.capability_policy().declare(mod_id, Some(&["storage"])).unwrap();
+            adapter.load_mod_async(&dir.join("index.js"), mod_id).await.unwrap();
+            adapter.call_mod_function_async(mod_id, "onAttach").await.unwrap();
+        }
+
+        assert_eq!(
+            storage.get("shop", "missing").unwrap(),
+            Some(serde_json::json!("Service 'quests' is not registered (registered services: inventory)"))
+        );
+        assert_eq!(
+            storage.get("shop", "taken").unwrap(),
+            Some(serde_json::json!("Service 'inventory' is already registered by mod 'items'"))
+        );
+        assert_eq!(storage.get("shop", "added").unwrap(), Some(serde_json::json!({ "item": "potion", "total": 2 })));
+        assert_eq!(storage.get("shop", "count").unwrap(), Some(serde_json::json!(2)));
+
+        // Unloading the provider releases the service
+        adapter.unload_mod("items").unwrap();
+        assert!(adapter.system_api().service_owner("inventory").is_none());
+        adapter.call_mod_function_async("shop", "onCheck").await.unwrap();
+        assert_eq!(
+            storage.get("shop", "stale").unwrap(),
+            Some(serde_json::json!("Service 'inventory' is no longer registered"))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_memory_monitor_serves_gc_requests() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+        let storage = adapter.storage_api().clone();
+
+        let mod_dir = temp.path().join("mods").join("leaky");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function onAttach() {
+    Storage.set(\"sampledEarly\", System.getJsMemoryStats() !== null);
+    globalThis.garbage = Array.from({ length: 1000 }, (_, i) => ({ i }));
+    globalThis.garbage = null;
+    System.gc();
+}
+export function onReport() {
+    Storage.set(\"after\", System.getJsMemoryStats().objectCount);
+}
+",
+        )
+        .unwrap();
+        adapter.capability_policy().declare("leaky", Some(&["storage"])).unwrap();
+        adapter.load_mod_async(&entry_point, "leaky").await.unwrap();
+        let before = adapter.memory_usage().await;
+        assert!(before.object_count > 0 && before.used_bytes > 0);
+
+        adapter.call_mod_function_async("leaky", "onAttach").await.unwrap();
+        assert_eq!(storage.get("leaky", "sampledEarly").unwrap(), Some(serde_json::Value::Bool(false)));
+
+        // The monitor's first tick samples right away; the long interval keeps later ticks out
+        let monitor = run_js_memory_monitor(adapter.get_runtime(), adapter.system_api().clone(), Duration::from_secs(3600));
+        let _ = tokio::time::timeout(Duration::from_millis(100), monitor).await;
+        let sampled = adapter.system_api().get_js_memory_stats().unwrap();
+        assert!(sampled.object_count < before.object_count + 1000, "the discarded objects should be collected");
+
+        adapter.call_mod_function_async("leaky", "onReport").await.unwrap();
+        assert_eq!(storage.get("leaky", "after").unwrap().and_then(|v| v.as_u64()), Some(sampled.object_count));
+        adapter.force_gc().await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_registry_apis_are_bound_by_name() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let storage = StorageApi::new();
+        storage.set_game_root(temp.path().to_path_buf());
+        let apis = ApiRegistry::new()
+            .with_api(ApiRegistry::STORAGE, storage.clone())
+            .with_api(ApiRegistry::LOCALE, LocaleApi::new("en-US", "en-US", |_, id| id.to_string(), |_, id, _| id.to_string()))
+            .with_api("unknown", 0u8);
+        let mut adapter = JsRuntimeAdapter::new(config, apis).unwrap();
+
+        let mod_dir = temp.path().join("mods").join("registry");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function onAttach() {\n    Storage.set(\"locale\", typeof Locale);\n    Storage.set(\"graphic\", typeof Graphic);\n}\n",
+        )
+        .unwrap();
+        adapter.capability_policy().declare("registry", Some(&["storage", "graphic"])).unwrap();
+        adapter.load_mod_async(&entry_point, "registry").await.unwrap();
+        adapter.call_mod_function_async("registry", "onAttach").await.unwrap();
+
+        // The registered StorageApi is the one mods write to
+        assert_eq!(storage.get("registry", "locale").unwrap(), Some(serde_json::json!("object")));
+        assert_eq!(storage.get("registry", "graphic").unwrap(), Some(serde_json::json!("undefined")));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_undeclared_capabilities_are_not_bound() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+        adapter.capability_policy().declare("sandboxed", Some(&["storage"])).unwrap();
+        adapter.capability_policy().set_denied([Capability::Process]);
+
+        let mod_dir = temp.path().join("mods").join("sandboxed");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function onAttach() {
+    const attempt = (f) => { try { f(); return \"ok\"; } catch (e) { return e.message; } };
+    Storage.set(\"filesystem\", attempt(() => FileSystem.exists(\"index.js\")));
+    Storage.set(\"process\", attempt(() => Process.app.data_path));
+}
+",
+        )
+        .unwrap();
+        adapter.load_mod_async(&entry_point, "sandboxed").await.unwrap();
+        adapter.call_mod_function_async("sandboxed", "onAttach").await.unwrap();
+
+        let result = |key: &str| adapter.storage_api().get("sandboxed", key).unwrap().unwrap();
+        let filesystem = result("filesystem");
+        assert!(
+            filesystem.as_str().unwrap().contains("did not declare the 'filesystem' capability"),
+            "{}",
+            filesystem
+        );
+        let process = result("process");
+        assert!(process.as_str().unwrap().contains("denied to mod 'sandboxed' by the server"), "{}", process);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unload_removes_only_own_handlers() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+
+        for (mod_id, property) in [("mod-a", "fromA"), ("mod-b", "fromB")] {
+            let entry_point = write_ping_mod(temp.path(), mod_id, property);
+            adapter.load_mod_async(&entry_point, mod_id).await.unwrap();
+            adapter.call_mod_function_async(mod_id, "onAttach").await.unwrap();
+        }
+        assert_eq!(adapter.handler_ids_for_mod("mod-a").len(), 1);
+        assert_eq!(adapter.handler_ids_for_mod("mod-b").len(), 1);
+
+        RuntimeAdapter::unload_mod(&mut adapter, "mod-a").unwrap();
+        assert!(adapter.handler_ids_for_mod("mod-a").is_empty());
+
+        let response = adapter
+            .dispatch_custom_event(&CustomEventRequest::new("ping", Vec::new()))
+            .await;
+        assert!(!response.properties.contains_key("fromA"));
+        assert!(response.properties.contains_key("fromB"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_event_handler_return_value() {
+        let temp = tempfile::tempdir().unwrap();
+        let mod_dir = temp.path().join("mods").join("query");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function onAttach() {\n\
+             System.registerEvent(\"sync\", (name, args) => ({ event: name, first: args[0] }), 0);\n\
+             System.registerEvent(\"async\", async () => { await null; return 1; }, 1);\n\
+             }\n",
+        )
+        .unwrap();
+
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.load_mod_async(&entry_point, "query").await.unwrap();
+        adapter.call_mod_function_async("query", "onAttach").await.unwrap();
+
+        let mut handler_ids = adapter.handler_ids_for_mod("query");
+        handler_ids.sort();
+        let value = adapter
+            .call_event_handler_with_return_async(handler_ids[0], "sync", &["42".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            value,
+            ModReturnValue::Object(HashMap::from([
+                ("event".to_string(), ModReturnValue::String("sync".to_string())),
+                ("first".to_string(), ModReturnValue::Int(42)),
+            ]))
+        );
+
+        // Values resolved after an await are not captured
+        let value = adapter
+            .call_event_handler_with_return_async(handler_ids[1], "async", &[])
+            .await
+            .unwrap();
+        assert_eq!(value, ModReturnValue::None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_return_value_mapping() {
+        let temp = tempfile::tempdir().unwrap();
+        let mod_dir = temp.path().join("mods").join("returns");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function getInt() { return 3; }\n\
+             export function getFloat() { return 1.5; }\n\
+             export function getArray() { return [1, \"a\"]; }\n\
+             export function getObject() { return { x: 2.5, ok: true }; }\n",
+        )
+        .unwrap();
+
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.load_mod_async(&entry_point, "returns").await.unwrap();
+
+        let mut results = HashMap::new();
+        for name in ["getInt", "getFloat", "getArray", "getObject"] {
+            let value = adapter.call_mod_function_with_return_async("returns", name).await.unwrap();
+            results.insert(name, value);
+        }
+
+        assert_eq!(results["getInt"], ModReturnValue::Int(3));
+        assert_eq!(results["getFloat"], ModReturnValue::Float(1.5));
+        assert_eq!(
+            results["getArray"],
+            ModReturnValue::Array(vec![ModReturnValue::Int(1), ModReturnValue::String("a".to_string())])
+        );
+        assert_eq!(
+            results["getObject"],
+            ModReturnValue::Object(HashMap::from([
+                ("x".to_string(), ModReturnValue::Float(2.5)),
+                ("ok".to_string(), ModReturnValue::Bool(true)),
+            ]))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_unsupported_return_types() {
+        let temp = tempfile::tempdir().unwrap();
+        let mod_dir = temp.path().join("mods").join("unsupported");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "export function getFunction() { return () => 1; }\n\
+             export function getBigInt() { return 10n; }\n\
+             export function getNested() { return { ok: true, cb: function() {} }; }\n\
+             export function getCycle() { const o = {}; o.o = o; return o; }\n\
+             export function getArrayCycle() { const a = []; a.push({ a }); return a; }\n",
+        )
+        .unwrap();
+
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.load_mod_async(&entry_point, "unsupported").await.unwrap();
+
+        for (name, expected) in [("getFunction", "function"), ("getBigInt", "bigint"), ("getNested", "function")] {
+            let err = adapter
+                .call_mod_function_with_return_async("unsupported", name)
+                .await
+                .unwrap_err();
+            assert_eq!(
+                err,
+                RuntimeError::UnsupportedReturnType {
+                    js_type: expected.to_string()
+                },
+                "unexpected error for {}: {}",
+                name,
+                err
+            );
+        }
+
+        // Self-referencing values are an error, not a stack overflow
+        for name in ["getCycle", "getArrayCycle"] {
+            let err = adapter
+                .call_mod_function_with_return_async("unsupported", name)
+                .await
+                .unwrap_err();
+            assert_eq!(
+                err,
+                RuntimeError::ReturnValueTooDeep {
+                    max_depth: crate::MAX_RETURN_VALUE_DEPTH
+                }
+            );
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_errors_are_classified() {
+        let temp = tempfile::tempdir().unwrap();
+        let broken_dir = temp.path().join("mods").join("broken");
+        fs::create_dir_all(&broken_dir).unwrap();
+        let broken_entry = broken_dir.join("index.js");
+        fs::write(&broken_entry, "export function onAttach() {\n  let = ;\n}\n").unwrap();
+        let throws_dir = temp.path().join("mods").join("throws");
+        fs::create_dir_all(&throws_dir).unwrap();
+        let throws_entry = throws_dir.join("index.js");
+        fs::write(&throws_entry, "export function onAttach() { null.boom(); }\n").unwrap();
+
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+
+        match adapter.load_mod_async(&broken_entry, "broken").await {
+            Err(RuntimeError::CompileError { message, .. }) => assert!(message.contains("SyntaxError"), "{}", message),
+            other => panic!("expected a compile error, got {:?}", other),
+        }
+
+        adapter.load_mod_async(&throws_entry, "throws").await.unwrap();
+        match adapter.call_mod_function_async("throws", "onAttach").await {
+            Err(RuntimeError::RuntimeException { message, .. }) => assert!(message.contains("TypeError"), "{}", message),
+            other => panic!("expected a runtime exception, got {:?}", other),
+        }
+
+        assert_eq!(
+            adapter.call_mod_function_async("missing", "onAttach").await,
+            Err(RuntimeError::ModNotFound { mod_id: "missing".to_string() })
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_memory_limit_does_not_poison_runtime() {
+        let temp = tempfile::tempdir().unwrap();
+        let mod_dir = temp.path().join("mods").join("hog");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let hog_entry = mod_dir.join("index.js");
+        fs::write(
+            &hog_entry,
+            "export function allocate() { const a = []; while (true) { a.push(new Array(1024).fill(1)); } }\n\
+             export function ready() { return 1; }\n",
+        )
+        .unwrap();
+        let other_dir = temp.path().join("mods").join("other");
+        fs::create_dir_all(&other_dir).unwrap();
+        let other_entry = other_dir.join("index.js");
+        fs::write(&other_entry, "export function ready() { return 2; }\n").unwrap();
+
+        let limit = 64 * 1024 * 1024;
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"))
+            .with_memory_limit(limit);
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.load_mod_async(&hog_entry, "hog").await.unwrap();
+        adapter.load_mod_async(&other_entry, "other").await.unwrap();
+
+        let err = adapter.call_mod_function_async("hog", "allocate").await.unwrap_err();
+        assert_eq!(err.to_string(), memory_limit_error_message("hog", limit));
+
+        let hog_ready = adapter.call_mod_function_with_return_async("hog", "ready").await.unwrap();
+        assert_eq!(hog_ready, ModReturnValue::Int(1));
+        let other_ready = adapter.call_mod_function_with_return_async("other", "ready").await.unwrap();
+        assert_eq!(other_ready, ModReturnValue::Int(2));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_custom_event_target_filters_handlers() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+
+        for (mod_id, property) in [("mod-a", "fromA"), ("mod-b", "fromB")] {
+            let entry_point = write_ping_mod(temp.path(), mod_id, property);
+            adapter.load_mod_async(&entry_point, mod_id).await.unwrap();
+            adapter.call_mod_function_async(mod_id, "onAttach").await.unwrap();
+        }
+
+        let broadcast = adapter
+            .dispatch_custom_event(&CustomEventRequest::new("ping", Vec::new()))
+            .await;
+        assert!(broadcast.properties.contains_key("fromA"));
+        assert!(broadcast.properties.contains_key("fromB"));
+
+        let direct = adapter
+            .dispatch_custom_event(&CustomEventRequest::new("ping", Vec::new()).with_target("mod-b"))
+            .await;
+        assert!(!direct.properties.contains_key("fromA"));
+        assert!(direct.properties.contains_key("fromB"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_window_callbacks_only_fire_for_their_window() {
+        use crate::api::{EnableEngineRequest, GraphicCommand, GraphicEngines, GraphicProxy};
+
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+
+        // Fake engine: answers the enable request, then acknowledges every window creation
+        let (enable_tx, enable_rx) = std::sync::mpsc::channel::<EnableEngineRequest>();
+        std::thread::spawn(move || {
+            let request = enable_rx.recv().unwrap();
+            let (cmd_tx, cmd_rx) = std::sync::mpsc::channel();
+            let (_event_tx, event_rx) = tokio::sync::mpsc::channel(16);
+            request.response_tx.send(Ok((cmd_tx, event_rx))).unwrap();
+            while let Ok(command) = cmd_rx.recv() {
+                if let GraphicCommand::CreateWindow { response_tx, .. } = command {
+                    let _ = response_tx.send(Ok(()));
+                }
+            }
+        });
+        let proxy = Arc::new(GraphicProxy::new_client(enable_tx, None));
+        let ready = async {
+            while proxy.take_event_receiver().await.is_none() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            proxy.mark_engine_ready();
+        };
+        let (enabled, ()) = tokio::join!(proxy.enable_engine(GraphicEngines::Bevy, None), ready);
+        enabled.unwrap();
+        adapter.set_graphic_proxy(proxy);
+
+        let mod_dir = temp.path().join("mods").join("windows");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            "const log = (name) => (win, width) => Storage.set(name, [...(Storage.get(name) ?? []), [win.id, width]]);
+export function onAttach() {
+    Graphic.createWindow({ title: \"First\", onResize: log(\"first\") })
+        .then((win) => Storage.set(\"first-id\", win.id));
+    Graphic.createWindow({ title: \"Second\" }).then((win) => {
+        win.onResize = log(\"second\");
+        Storage.set(\"second-id\", win.id);
+    });
+}
+",
+        )
+        .unwrap();
+        adapter.capability_policy().declare("windows", Some(&["storage", "graphic"])).unwrap();
+        adapter.load_mod_async(&entry_point, "windows").await.unwrap();
+        adapter.call_mod_function_async("windows", "onAttach").await.unwrap();
+
+        let storage = adapter.storage_api().clone();
+        let stored = |key: &str| storage.get("windows", key).unwrap();
+        let runtime = adapter.get_runtime();
+        for _ in 0..100 {
+            if stored("first-id").is_some() && stored("second-id").is_some() {
+                break;
+            }
+            let _ = tokio::time::timeout(Duration::from_millis(10), runtime.drive()).await;
+        }
+        let first = stored("first-id").and_then(|v| v.as_u64()).unwrap();
+        let second = stored("second-id").and_then(|v| v.as_u64()).unwrap();
+        assert_ne!(first, second);
+
+        let resize = |width: u32| serde_json::json!({ "width": width, "height": 100 });
+        assert!(adapter.dispatch_window_event_callback_async(first, "resize", resize(640)).await.unwrap());
+        assert!(adapter.dispatch_window_event_callback_async(second, "resize", resize(800)).await.unwrap());
+        assert_eq!(stored("first"), Some(serde_json::json!([[first, 640]])));
+        assert_eq!(stored("second"), Some(serde_json::json!([[second, 800]])));
+
+        // Closing a window drops its callbacks without touching the other one
+        adapter.dispatch_window_event_callback_async(first, "close", serde_json::json!({})).await.unwrap();
+        assert!(!adapter.dispatch_window_event_callback_async(first, "resize", resize(1)).await.unwrap());
+        assert!(adapter.dispatch_window_event_callback_async(second, "resize", resize(1024)).await.unwrap());
+        assert_eq!(stored("first"), Some(serde_json::json!([[first, 640]])));
+        assert_eq!(stored("second"), Some(serde_json::json!([[second, 800], [second, 1024]])));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_world_batch_on_a_500_node_tree() {
+        use crate::api::{EnableEngineRequest, GraphicCommand, GraphicEngines, GraphicProxy};
+        use std::sync::Mutex;
+
+        /// Nodes of the UI tree: a root plus its rows
+        const NODES: usize = 500;
+        /// Frame length of the fake engine, which drains its channel once per frame like Bevy
+        const FRAME: Duration = Duration::from_millis(2);
+
+        let temp = tempfile::tempdir().unwrap();
+        let config = JsRuntimeConfig::new(temp.path().join("data"), temp.path().join("config"));
+        let mut adapter = JsRuntimeAdapter::new(config, ApiRegistry::new()).unwrap();
+        adapter.storage_api().set_game_root(temp.path().to_path_buf());
+
+        // Fake engine: per frame, answers every queued spawn and records (channel sends, commands)
+        let frames: Arc<Mutex<Vec<(usize, usize)>>> = Arc::default();
+        let engine_frames = frames.clone();
+        let (enable_tx, enable_rx) = std::sync::mpsc::channel::<EnableEngineRequest>();
+        std::thread::spawn(move || {
+            let request = enable_rx.recv().unwrap();
+            let (cmd_tx, cmd_rx) = std::sync::mpsc::channel();
+            let (_event_tx, event_rx) = tokio::sync::mpsc::channel(16);
+            request.response_tx.send(Ok((cmd_tx, event_rx))).unwrap();
+            let mut next_id = 1;
+            let mut answer = |command| {
+                if let GraphicCommand::SpawnEntity { response_tx, .. } = command {
+                    let _ = response_tx.send(Ok(next_id));
+                    next_id += 1;
+                }
+            };
+            loop {
+                std::thread::sleep(FRAME);
+                let (mut sends, mut commands) = (0, 0);
+                loop {
+                    match cmd_rx.try_recv() {
+                        Ok(GraphicCommand::Batch { commands: batch }) => {
+                            sends += 1;
+                            commands += batch.len();
+                            batch.into_iter().for_each(&mut answer);
+                        }
+                        Ok(command) => {
+                            sends += 1;
+                            commands += 1;
+                            answer(command);
+                        }
+                        Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                        Err(std::sync::mpsc::TryRecvError::Disconnected) => return,
+                    }
+                }
+                if sends > 0 {
+                    engine_frames.lock().unwrap().push((sends, commands));
+                }
+            }
+        });
+        let proxy = Arc::new(GraphicProxy::new_client(enable_tx, None));
+        let ready = async {
+            while proxy.take_event_receiver().await.is_none() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            proxy.mark_engine_ready();
+        };
+        let (enabled, ()) = tokio::join!(proxy.enable_engine(GraphicEngines::Bevy, None), ready);
+        enabled.unwrap();
+        adapter.set_graphic_proxy(proxy);
+
+        let mod_dir = temp.path().join("mods").join("tree");
+        fs::create_dir_all(&mod_dir).unwrap();
+        let entry_point = mod_dir.join("index.js");
+        fs::write(
+            &entry_point,
+            format!(
+                "const ROWS = {rows};
+const row = (i, root) => World.spawn({{ Node: {{ width: \"100%\", height: 24 }}, Text: {{ value: `row ${{i}}` }} }}, root);
+async function build(name, spawnRows) {{
+    const start = Date.now();
+    const root = await World.spawn({{ Node: {{ width: \"100%\", height: \"100%\" }} }});
+    const rows = await Promise.all(await spawnRows(root));
+    Storage.set(name, {{ rows: rows.length, ms: Date.now() - start }});
+}}
+export function buildUnbatched() {{
+    build(\"unbatched\", async (root) => [...Array(ROWS).keys()].map((i) => row(i, root)));
+}}
+export function buildBatched() {{
+    build(\"batched\", (root) => World.batch(() => [...Array(ROWS).keys()].map((i) => row(i, root))));
+}}
+",
+                rows = NODES - 1
+            ),
+        )
+        .unwrap();
+        adapter.capability_policy().declare("tree", Some(&["storage", "graphic"])).unwrap();
+        adapter.load_mod_async(&entry_point, "tree").await.unwrap();
+
+        let storage = adapter.storage_api().clone();
+        let runtime = adapter.get_runtime();
+        let mut measure = async |name: &str, function: &str| {
+            frames.lock().unwrap().clear();
+            adapter.call_mod_function_async("tree", function).await.unwrap();
+            for _ in 0..500 {
+                if storage.get("tree", name).unwrap().is_some() {
+                    break;
+                }
+                let _ = tokio::time::timeout(Duration::from_millis(10), runtime.drive()).await;
+            }
+            let result = storage.get("tree", name).unwrap().unwrap();
+            assert_eq!(result["rows"], serde_json::json!(NODES - 1), "{}", name);
+            let frames = frames.lock().unwrap().clone();
+            let sends: usize = frames.iter().map(|(sends, _)| sends).sum();
+            let commands: usize = frames.iter().map(|(_, commands)| commands).sum();
+            assert_eq!(commands, NODES, "{}", name);
+            println!("{}: {} channel sends over {} frames, {} ms", name, sends, frames.len(), result["ms"]);
+            sends
+        };
+
+        // Every spawn is its own message without a batch; the rows share one with it
+        assert_eq!(measure("unbatched", "buildUnbatched").await, NODES);
+        assert_eq!(measure("batched", "buildBatched").await, 2);
+    }
+}
@@ -0,0 +1,7 @@
+//! Lua Runtime Adapter (mlua)
+//!
+//! Provides Lua mod execution using Lua 5.4 via mlua.
+
+mod runtime;
+
+pub use runtime::LuaRuntimeAdapter;
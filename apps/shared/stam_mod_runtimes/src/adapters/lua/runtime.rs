@@ -0,0 +1,805 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use mlua::{Function, HookTriggers, Lua, LuaSerdeExt, Table, Value, VmState};
+use tracing::{debug, error, info, trace, warn};
+
+use crate::api::{
+    CustomEventRequest, CustomEventResponse, EventKey, GraphicEngineReadyRequest,
+    GraphicEngineReadyResponse, GraphicEngineWindowClosedRequest,
+    GraphicEngineWindowClosedResponse, HandlerRef, RequestUriProtocol, SystemApi, SystemEvents,
+    TerminalKeyRequest, TerminalKeyResponse, TerminalMouseRequest, TerminalMouseResponse,
+    TerminalPasteRequest, TerminalPasteResponse,
+};
+use crate::call_timeout::{CallDeadline, timeout_error_message};
+use crate::error::line_from_stack;
+use crate::{ModReturnValue, RuntimeAdapter, RuntimeError};
+
+/// Named registry slot holding the `handler_id -> function` table shared by all mods
+const HANDLERS_REGISTRY_KEY: &str = "__stam_handlers";
+
+/// How many VM instructions run between call timeout checks
+const TIMEOUT_CHECK_INSTRUCTIONS: u32 = 10_000;
+
+/// A loaded Lua mod
+struct LoadedMod {
+    /// Per-mod global environment; unknown names fall back to the shared `_G`
+    env: Table,
+    /// Directory containing the entry point
+    #[allow(dead_code)]
+    mod_dir: PathBuf,
+}
+
+/// Lua runtime adapter
+///
+/// One Lua state is shared by all Lua mods, and each mod runs in its own
+/// environment table so its globals (`onAttach`, `onBootstrap`, ...) don't
+/// clash with other mods. Lifecycle hooks are plain global functions:
+///
+/// ```lua
+/// function onAttach()
+///     console.log("attached")
+/// end
+/// ```
+pub struct LuaRuntimeAdapter {
+    lua: Lua,
+    loaded_mods: HashMap<String, LoadedMod>,
+    system_api: SystemApi,
+    /// Maximum duration of a synchronous mod call (None = no limit)
+    call_timeout: Option<Duration>,
+    /// Deadline polled by the instruction hook
+    call_deadline: CallDeadline,
+}
+
+impl LuaRuntimeAdapter {
+    /// Create a new Lua runtime adapter with its own SystemApi
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_system_api(SystemApi::new())
+    }
+
+    /// Create a new Lua runtime adapter sharing an existing SystemApi
+    ///
+    /// Use this to share the mod registry and event dispatcher with another adapter.
+    pub fn with_system_api(system_api: SystemApi) -> Result<Self, Box<dyn std::error::Error>> {
+        let lua = Lua::new();
+        lua.set_named_registry_value(HANDLERS_REGISTRY_KEY, lua.create_table()?)?;
+
+        // Abort running Lua once the current call's deadline has passed
+        let call_deadline = CallDeadline::new();
+        let hook_deadline = call_deadline.clone();
+        lua.set_hook(
+            HookTriggers::new().every_nth_instruction(TIMEOUT_CHECK_INSTRUCTIONS),
+            move |_lua, _debug| {
+                if hook_deadline.is_expired() {
+                    Err(mlua::Error::runtime("call timeout exceeded"))
+                } else {
+                    Ok(VmState::Continue)
+                }
+            },
+        );
+
+        Ok(Self {
+            lua,
+            loaded_mods: HashMap::new(),
+            system_api,
+            call_timeout: None,
+            call_deadline,
+        })
+    }
+
+    /// Get a reference to the system API
+    pub fn system_api(&self) -> &SystemApi {
+        &self.system_api
+    }
+
+    /// Build the isolated environment for a mod, including its `system` and `console` tables
+    fn create_mod_env(&self, mod_id: &str) -> mlua::Result<Table> {
+        let lua = &self.lua;
+        let env = lua.create_table()?;
+
+        // Fall back to the real globals for the standard library
+        let meta = lua.create_table()?;
+        meta.set("__index", lua.globals())?;
+        env.set_metatable(Some(meta));
+
+        // console.log / console.warn / console.error
+        let console = lua.create_table()?;
+        let log_mod_id = mod_id.to_string();
+        console.set(
+            "log",
+            lua.create_function(move |_, message: String| {
+                info!(runtime_type = "lua", mod_id = log_mod_id.as_str(), "{}", message);
+                Ok(())
+            })?,
+        )?;
+        let warn_mod_id = mod_id.to_string();
+        console.set(
+            "warn",
+            lua.create_function(move |_, message: String| {
+                warn!(runtime_type = "lua", mod_id = warn_mod_id.as_str(), "{}", message);
+                Ok(())
+            })?,
+        )?;
+        let error_mod_id = mod_id.to_string();
+        console.set(
+            "error",
+            lua.create_function(move |_, message: String| {
+                error!(runtime_type = "lua", mod_id = error_mod_id.as_str(), "{}", message);
+                Ok(())
+            })?,
+        )?;
+        env.set("console", console)?;
+
+        // SystemEvents enum, same values as the JavaScript global
+        let system_events = lua.create_table()?;
+        system_events.set("RequestUri", SystemEvents::RequestUri.to_u32())?;
+        system_events.set("TerminalKeyPressed", SystemEvents::TerminalKeyPressed.to_u32())?;
+        system_events.set("GraphicEngineReady", SystemEvents::GraphicEngineReady.to_u32())?;
+        system_events.set("GraphicEngineWindowClosed", SystemEvents::GraphicEngineWindowClosed.to_u32())?;
+        system_events.set("TerminalMouse", SystemEvents::TerminalMouse.to_u32())?;
+        system_events.set("TerminalPaste", SystemEvents::TerminalPaste.to_u32())?;
+        env.set("SystemEvents", system_events)?;
+
+        // system.registerEvent(event, handler, priority) / system.removeEvent(handlerId)
+        let system = lua.create_table()?;
+        let dispatcher = self.system_api.event_dispatcher().clone();
+        let register_mod_id = mod_id.to_string();
+        system.set(
+            "registerEvent",
+            lua.create_function(move |lua, (event, handler, priority): (Value, Function, Option<i32>)| {
+                let priority = priority.unwrap_or(100);
+                let handler_id = match event {
+                    Value::Integer(n) => {
+                        let event = u32::try_from(n).ok().and_then(SystemEvents::from_u32).ok_or_else(|| {
+                            mlua::Error::RuntimeError(format!("Unknown system event: {}", n))
+                        })?;
+                        dispatcher.register_handler(event, register_mod_id.as_str(), priority, RequestUriProtocol::All, "")
+                    }
+                    Value::String(name) => {
+                        dispatcher.register_custom_handler(name.to_string_lossy(), register_mod_id.as_str(), priority)
+                    }
+                    other => {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "system.registerEvent expects a SystemEvents value or an event name, got {}",
+                            other.type_name()
+                        )));
+                    }
+                };
+                let handlers: Table = lua.named_registry_value(HANDLERS_REGISTRY_KEY)?;
+                handlers.set(handler_id, handler)?;
+                Ok(handler_id)
+            })?,
+        )?;
+        let remove_dispatcher = self.system_api.event_dispatcher().clone();
+        system.set(
+            "removeEvent",
+            lua.create_function(move |lua, handler_id: u64| {
+                let handlers: Table = lua.named_registry_value(HANDLERS_REGISTRY_KEY)?;
+                handlers.set(handler_id, Value::Nil)?;
+                Ok(remove_dispatcher.unregister_handler(handler_id))
+            })?,
+        )?;
+        env.set("system", system)?;
+
+        Ok(env)
+    }
+
+    /// Look up a stored handler function by ID
+    fn get_handler(&self, handler_id: u64) -> mlua::Result<Option<Function>> {
+        let handlers: Table = self.lua.named_registry_value(HANDLERS_REGISTRY_KEY)?;
+        handlers.get(handler_id)
+    }
+
+    /// Call each handler with `(request, response)` in priority order until one sets
+    /// `response.handled = true`. Returns the response table of the last handler called.
+    fn dispatch_to_handlers(&self, handlers: &[HandlerRef], request: &Table) -> Option<Table> {
+        let mut last_response = None;
+        for handler in handlers {
+            let response = match self.lua.create_table() {
+                Ok(t) => t,
+                Err(e) => {
+                    error!("Failed to create response table: {}", e);
+                    return last_response;
+                }
+            };
+            if let Err(e) = response.set("handled", false) {
+                error!("Failed to initialize response table: {}", e);
+                continue;
+            }
+
+            let func = match self.get_handler(handler.handler_id) {
+                Ok(Some(func)) => func,
+                Ok(None) => {
+                    error!("Handler {} not found in mod '{}'", handler.handler_id, handler.mod_id);
+                    continue;
+                }
+                Err(e) => {
+                    error!("Failed to get handler {} from mod '{}': {}", handler.handler_id, handler.mod_id, e);
+                    continue;
+                }
+            };
+
+            if let Err(e) = func.call::<()>((request.clone(), response.clone())) {
+                error!("Handler error in mod '{}': {}", handler.mod_id, e);
+                continue;
+            }
+
+            let handled = response.get::<Option<bool>>("handled").ok().flatten().unwrap_or(false);
+            last_response = Some(response);
+            if handled {
+                trace!("Handler in mod '{}' marked event as handled", handler.mod_id);
+                break;
+            }
+        }
+        last_response
+    }
+
+    /// Read `response.handled` from a dispatch result
+    fn is_handled(response: &Option<Table>) -> bool {
+        response
+            .as_ref()
+            .and_then(|r| r.get::<Option<bool>>("handled").ok().flatten())
+            .unwrap_or(false)
+    }
+}
+
+/// Convert a Lua value returned by a mod function into a `ModReturnValue`
+///
+/// Integral numbers that fit in an `i32` map to `Int`, other numbers to `Float`.
+/// Sequence tables become `Array`, other tables become `Object` (string and
+/// integer keys only). Values with no counterpart (functions, userdata, ...) become `None`.
+/// Tables nested beyond `MAX_RETURN_VALUE_DEPTH` (e.g. a table containing itself)
+/// fail with `RuntimeError::ReturnValueTooDeep`.
+fn lua_value_to_return_value(value: &Value) -> Result<ModReturnValue, RuntimeError> {
+    lua_value_to_return_value_at(value, 0)
+}
+
+/// `lua_value_to_return_value` for a value nested `depth` levels deep
+fn lua_value_to_return_value_at(value: &Value, depth: usize) -> Result<ModReturnValue, RuntimeError> {
+    Ok(match value {
+        Value::Nil => ModReturnValue::None,
+        Value::String(s) => ModReturnValue::String(s.to_string_lossy()),
+        Value::Boolean(b) => ModReturnValue::Bool(*b),
+        Value::Integer(n) => match i32::try_from(*n) {
+            Ok(n) => ModReturnValue::Int(n),
+            Err(_) => ModReturnValue::Float(*n as f64),
+        },
+        Value::Number(n) => {
+            if n.fract() == 0.0 && *n >= i32::MIN as f64 && *n <= i32::MAX as f64 {
+                ModReturnValue::Int(*n as i32)
+            } else {
+                ModReturnValue::Float(*n)
+            }
+        }
+        Value::Table(_) if depth >= crate::MAX_RETURN_VALUE_DEPTH => {
+            return Err(RuntimeError::ReturnValueTooDeep {
+                max_depth: crate::MAX_RETURN_VALUE_DEPTH,
+            });
+        }
+        Value::Table(table) => {
+            let entries: Vec<(Value, Value)> = table.pairs::<Value, Value>().filter_map(|e| e.ok()).collect();
+            let len = table.raw_len();
+            if len > 0 && entries.len() == len {
+                ModReturnValue::Array(
+                    table
+                        .sequence_values::<Value>()
+                        .filter_map(|v| v.ok())
+                        .map(|v| lua_value_to_return_value_at(&v, depth + 1))
+                        .collect::<Result<_, _>>()?,
+                )
+            } else {
+                ModReturnValue::Object(
+                    entries
+                        .iter()
+                        .filter_map(|(key, v)| {
+                            let key = match key {
+                                Value::String(s) => s.to_string_lossy(),
+                                Value::Integer(n) => n.to_string(),
+                                _ => return None,
+                            };
+                            Some(lua_value_to_return_value_at(v, depth + 1).map(|v| (key, v)))
+                        })
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+        }
+        _ => ModReturnValue::None,
+    })
+}
+
+/// Build a typed error from a Lua error, prefixing its message with `context`
+///
+/// Syntax errors become a `CompileError` with the line Lua reports; errors
+/// raised while running become a `RuntimeException` with the traceback.
+fn lua_error(context: &str, error: &mlua::Error) -> RuntimeError {
+    match error {
+        mlua::Error::SyntaxError { message, .. } => RuntimeError::CompileError {
+            line: line_from_stack(message),
+            message: format!("{}: {}", context, message),
+        },
+        mlua::Error::RuntimeError(message) => {
+            let (message, stack) = match message.split_once("\nstack traceback:") {
+                Some((message, traceback)) => (message, Some(format!("stack traceback:{}", traceback))),
+                None => (message.as_str(), None),
+            };
+            RuntimeError::RuntimeException {
+                message: format!("{}: {}", context, message),
+                stack,
+            }
+        }
+        mlua::Error::CallbackError { traceback, cause } => RuntimeError::RuntimeException {
+            message: format!("{}: {}", context, cause),
+            stack: Some(traceback.clone()),
+        },
+        other => RuntimeError::exception(format!("{}: {}", context, other)),
+    }
+}
+
+impl From<mlua::Error> for RuntimeError {
+    fn from(error: mlua::Error) -> Self {
+        lua_error("Lua error", &error)
+    }
+}
+
+impl RuntimeAdapter for LuaRuntimeAdapter {
+    fn load_mod(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError> {
+        trace!("Loading Lua module: {} from {}", mod_id, mod_path.display());
+
+        let mod_dir = mod_path
+            .parent()
+            .ok_or_else(|| {
+                RuntimeError::load_failed(
+                    mod_id,
+                    format!("Cannot determine mod directory for '{}'", mod_path.display()),
+                )
+            })?
+            .to_path_buf();
+
+        let source = fs::read_to_string(mod_path).map_err(|e| {
+            RuntimeError::load_failed(
+                mod_id,
+                format!("Failed to read entry point '{}': {}", mod_path.display(), e),
+            )
+        })?;
+
+        let env = self
+            .create_mod_env(mod_id)
+            .map_err(|e| RuntimeError::load_failed(mod_id, format!("Failed to create environment: {}", e)))?;
+        self.lua
+            .load(&source)
+            .set_name(mod_path.to_string_lossy())
+            .set_environment(env.clone())
+            .exec()
+            .map_err(|e| {
+                error!("{}", e);
+                lua_error(&format!("Lua error in mod '{}'", mod_id), &e)
+            })?;
+
+        self.loaded_mods.insert(mod_id.to_string(), LoadedMod { env, mod_dir });
+        Ok(())
+    }
+
+    fn call_mod_function(&mut self, mod_id: &str, function_name: &str) -> Result<(), RuntimeError> {
+        self.call_mod_function_with_return(mod_id, function_name).map(|_| ())
+    }
+
+    fn call_mod_function_with_return(
+        &mut self,
+        mod_id: &str,
+        function_name: &str,
+    ) -> Result<ModReturnValue, RuntimeError> {
+        let loaded_mod = self
+            .loaded_mods
+            .get(mod_id)
+            .ok_or_else(|| RuntimeError::ModNotFound { mod_id: mod_id.to_string() })?;
+
+        let func = match loaded_mod.env.get::<Option<Function>>(function_name)? {
+            Some(func) => func,
+            None => {
+                debug!("Function '{}' not found or not global for mod '{}'", function_name, mod_id);
+                return Ok(ModReturnValue::None);
+            }
+        };
+
+        let value = {
+            let _deadline = self.call_deadline.arm(self.call_timeout);
+            func.call::<Value>(()).map_err(|e| {
+                if let Some(timeout) = self.call_timeout.filter(|_| self.call_deadline.is_expired()) {
+                    error!("{}", timeout_error_message(mod_id, function_name, timeout));
+                    return RuntimeError::Timeout {
+                        mod_id: mod_id.to_string(),
+                        function_name: function_name.to_string(),
+                        timeout,
+                    };
+                }
+                error!("{}", e);
+                lua_error(&format!("Lua error in '{}' for mod '{}'", function_name, mod_id), &e)
+            })?
+        };
+
+        lua_value_to_return_value(&value)
+    }
+
+    fn set_call_timeout(&mut self, timeout: Option<Duration>) {
+        self.call_timeout = timeout;
+    }
+
+    fn supports_unload(&self) -> bool {
+        true
+    }
+
+    fn unload_mod(&mut self, mod_id: &str) -> Result<(), RuntimeError> {
+        let handler_ids = self.handler_ids_for_mod(mod_id);
+        if self.loaded_mods.remove(mod_id).is_none() {
+            return Err(RuntimeError::ModNotFound { mod_id: mod_id.to_string() });
+        }
+
+        // Forget the handlers in the dispatcher and drop their functions from the registry
+        let handlers: Table = self.lua.named_registry_value(HANDLERS_REGISTRY_KEY)?;
+        for handler_id in &handler_ids {
+            self.system_api.event_dispatcher().unregister_handler(*handler_id);
+            handlers.set(*handler_id, Value::Nil)?;
+        }
+
+        debug!("Unloaded Lua mod '{}' ({} handlers removed)", mod_id, handler_ids.len());
+        Ok(())
+    }
+
+    fn handler_ids_for_mod(&self, mod_id: &str) -> Vec<u64> {
+        if !self.loaded_mods.contains_key(mod_id) {
+            return Vec::new();
+        }
+        self.system_api.event_dispatcher().handler_ids_for_mod(mod_id)
+    }
+
+    fn call_event_handler(
+        &mut self,
+        handler_id: u64,
+        event_name: &str,
+        args: &[String],
+    ) -> Result<(), RuntimeError> {
+        let func = self.get_handler(handler_id)?.ok_or_else(|| {
+            RuntimeError::exception(format!("Event handler {} not found in any loaded mod", handler_id))
+        })?;
+
+        // Arguments are JSON-serialized; fall back to plain strings
+        let lua_args = self.lua.create_table()?;
+        for (i, arg) in args.iter().enumerate() {
+            let value = match serde_json::from_str::<serde_json::Value>(arg) {
+                Ok(json) => self.lua.to_value(&json)?,
+                Err(_) => Value::String(self.lua.create_string(arg)?),
+            };
+            lua_args.set(i + 1, value)?;
+        }
+
+        func.call::<()>((event_name, lua_args)).map_err(|e| {
+            error!("Event handler error: {}", e);
+            lua_error("Event handler error", &e)
+        })
+    }
+
+    fn collect_handlers(&self, event: &EventKey) -> Vec<HandlerRef> {
+        self.system_api
+            .event_dispatcher()
+            .get_handlers_for_key(event)
+            .iter()
+            .filter(|h| self.loaded_mods.contains_key(&h.mod_id))
+            .map(HandlerRef::from)
+            .collect()
+    }
+
+    fn dispatch_terminal_key(&self, request: &TerminalKeyRequest, handlers: &[HandlerRef]) -> TerminalKeyResponse {
+        if handlers.is_empty() {
+            return TerminalKeyResponse::default();
+        }
+
+        let request_table = match self.lua.create_table().and_then(|t| {
+            t.set("key", request.key.as_str())?;
+            t.set("ctrl", request.ctrl)?;
+            t.set("alt", request.alt)?;
+            t.set("shift", request.shift)?;
+            t.set("meta", request.meta)?;
+            t.set("combo", request.combo.as_str())?;
+            Ok(t)
+        }) {
+            Ok(t) => t,
+            Err(e) => {
+                error!("Failed to create request table: {}", e);
+                return TerminalKeyResponse::default();
+            }
+        };
+
+        let response = self.dispatch_to_handlers(handlers, &request_table);
+        TerminalKeyResponse {
+            handled: Self::is_handled(&response),
+        }
+    }
+
+    fn terminal_key_handler_count(&self) -> usize {
+        self.collect_handlers(&EventKey::System(SystemEvents::TerminalKeyPressed)).len()
+    }
+
+    fn dispatch_terminal_mouse(&self, request: &TerminalMouseRequest, handlers: &[HandlerRef]) -> TerminalMouseResponse {
+        if handlers.is_empty() {
+            return TerminalMouseResponse::default();
+        }
+
+        let request_table = match self.lua.create_table().and_then(|t| {
+            t.set("x", request.x)?;
+            t.set("y", request.y)?;
+            t.set("button", request.button.as_str())?;
+            t.set("kind", request.kind.as_str())?;
+            Ok(t)
+        }) {
+            Ok(t) => t,
+            Err(e) => {
+                error!("Failed to create request table: {}", e);
+                return TerminalMouseResponse::default();
+            }
+        };
+
+        let response = self.dispatch_to_handlers(handlers, &request_table);
+        TerminalMouseResponse {
+            handled: Self::is_handled(&response),
+        }
+    }
+
+    fn dispatch_terminal_paste(&self, request: &TerminalPasteRequest, handlers: &[HandlerRef]) -> TerminalPasteResponse {
+        if handlers.is_empty() {
+            return TerminalPasteResponse::default();
+        }
+
+        let request_table = match self.lua.create_table().and_then(|t| {
+            t.set("text", request.text.as_str())?;
+            Ok(t)
+        }) {
+            Ok(t) => t,
+            Err(e) => {
+                error!("Failed to create request table: {}", e);
+                return TerminalPasteResponse::default();
+            }
+        };
+
+        let response = self.dispatch_to_handlers(handlers, &request_table);
+        TerminalPasteResponse {
+            handled: Self::is_handled(&response),
+        }
+    }
+
+    fn dispatch_graphic_engine_ready(
+        &self,
+        _request: &GraphicEngineReadyRequest,
+        handlers: &[HandlerRef],
+    ) -> GraphicEngineReadyResponse {
+        if handlers.is_empty() {
+            return GraphicEngineReadyResponse::default();
+        }
+
+        let request_table = match self.lua.create_table() {
+            Ok(t) => t,
+            Err(e) => {
+                error!("Failed to create request table: {}", e);
+                return GraphicEngineReadyResponse::default();
+            }
+        };
+
+        let response = self.dispatch_to_handlers(handlers, &request_table);
+        GraphicEngineReadyResponse {
+            handled: Self::is_handled(&response),
+        }
+    }
+
+    fn dispatch_graphic_engine_window_closed(
+        &self,
+        request: &GraphicEngineWindowClosedRequest,
+        handlers: &[HandlerRef],
+    ) -> GraphicEngineWindowClosedResponse {
+        if handlers.is_empty() {
+            return GraphicEngineWindowClosedResponse::default();
+        }
+
+        let request_table = match self.lua.create_table().and_then(|t| {
+            t.set("windowId", request.window_id)?;
+            Ok(t)
+        }) {
+            Ok(t) => t,
+            Err(e) => {
+                error!("Failed to create request table: {}", e);
+                return GraphicEngineWindowClosedResponse::default();
+            }
+        };
+
+        let response = self.dispatch_to_handlers(handlers, &request_table);
+        GraphicEngineWindowClosedResponse {
+            handled: Self::is_handled(&response),
+        }
+    }
+
+    fn dispatch_custom_event(&self, request: &CustomEventRequest, handlers: &[HandlerRef]) -> CustomEventResponse {
+        let handlers: Vec<_> = handlers
+            .iter()
+            .filter(|h| request.accepts(h))
+            .cloned()
+            .collect();
+        if handlers.is_empty() {
+            return CustomEventResponse::default();
+        }
+
+        let request_table = match self.lua.create_table().and_then(|t| {
+            t.set("eventName", request.event_name.as_str())?;
+            let args = self.lua.create_table()?;
+            for (i, arg) in request.args.iter().enumerate() {
+                let value = match serde_json::from_str::<serde_json::Value>(arg) {
+                    Ok(json) => self.lua.to_value(&json)?,
+                    Err(_) => Value::String(self.lua.create_string(arg)?),
+                };
+                args.set(i + 1, value)?;
+            }
+            t.set("args", args)?;
+            Ok(t)
+        }) {
+            Ok(t) => t,
+            Err(e) => {
+                error!("Failed to create request table: {}", e);
+                return CustomEventResponse::default();
+            }
+        };
+
+        let mut result = CustomEventResponse::default();
+        let Some(response) = self.dispatch_to_handlers(&handlers, &request_table) else {
+            return result;
+        };
+
+        // Every field other than `handled` becomes a JSON-serialized property
+        for pair in response.pairs::<String, Value>() {
+            let (key, value) = match pair {
+                Ok(pair) => pair,
+                Err(e) => {
+                    error!("Failed to read response property: {}", e);
+                    continue;
+                }
+            };
+            if key == "handled" {
+                result.handled = matches!(value, Value::Boolean(true));
+                continue;
+            }
+            match self.lua.from_value::<serde_json::Value>(value) {
+                Ok(json) => result.set_property(key, json.to_string()),
+                Err(e) => warn!("Response property '{}' is not serializable: {}", key, e),
+            }
+        }
+
+        result
+    }
+
+    fn dispatch_tick(&self, delta: Duration) {
+        let delta_ms = delta.as_secs_f64() * 1000.0;
+        for (mod_id, loaded_mod) in &self.loaded_mods {
+            let Ok(Some(on_tick)) = loaded_mod.env.get::<Option<Function>>("onTick") else {
+                continue;
+            };
+            let _deadline = self.call_deadline.arm(self.call_timeout);
+            if let Err(e) = on_tick.call::<()>(delta_ms) {
+                if let Some(timeout) = self.call_timeout.filter(|_| self.call_deadline.is_expired()) {
+                    error!("{}", timeout_error_message(mod_id, "onTick", timeout));
+                } else {
+                    error!("Lua error in 'onTick' for mod '{}': {}", mod_id, e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(adapter: &mut LuaRuntimeAdapter, dir: &tempfile::TempDir, mod_id: &str, source: &str) -> Result<(), RuntimeError> {
+        let path = dir.path().join(format!("{}.lua", mod_id));
+        fs::write(&path, source).unwrap();
+        adapter.load_mod(&path, mod_id)
+    }
+
+    #[test]
+    fn test_return_value_mapping() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = LuaRuntimeAdapter::new().unwrap();
+        load(
+            &mut adapter,
+            &dir,
+            "values",
+            r#"
+            function getNil() return nil end
+            function getString() return "ready" end
+            function getBool() return true end
+            function getInt() return 42 end
+            function getWholeFloat() return 7.0 end
+            function getFloat() return 1.5 end
+            function getArray() return { 1, "two" } end
+            function getObject() return { name = "potion" } end
+            function getCycle() local t = {} t.t = t return t end
+            "#,
+        )
+        .unwrap();
+
+        let mut call = |name| adapter.call_mod_function_with_return("values", name);
+        assert_eq!(call("getNil").unwrap(), ModReturnValue::None);
+        assert_eq!(call("getString").unwrap(), ModReturnValue::String("ready".to_string()));
+        assert_eq!(call("getBool").unwrap(), ModReturnValue::Bool(true));
+        assert_eq!(call("getInt").unwrap(), ModReturnValue::Int(42));
+        assert_eq!(call("getWholeFloat").unwrap(), ModReturnValue::Int(7));
+        assert_eq!(call("getFloat").unwrap(), ModReturnValue::Float(1.5));
+        assert_eq!(
+            call("getArray").unwrap(),
+            ModReturnValue::Array(vec![ModReturnValue::Int(1), ModReturnValue::String("two".to_string())])
+        );
+        assert_eq!(
+            call("getObject").unwrap(),
+            ModReturnValue::Object(HashMap::from([("name".to_string(), ModReturnValue::String("potion".to_string()))]))
+        );
+        assert!(matches!(call("getCycle"), Err(RuntimeError::ReturnValueTooDeep { .. })));
+    }
+
+    #[test]
+    fn test_lifecycle_hooks_and_unload() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = LuaRuntimeAdapter::new().unwrap();
+        load(
+            &mut adapter,
+            &dir,
+            "status-mod",
+            r#"
+            function onAttach()
+                system.registerEvent("status:ping", function(req, res) res.handled = true end, 0)
+            end
+            function onBootstrap() return 42 end
+            "#,
+        )
+        .unwrap();
+        load(&mut adapter, &dir, "other-mod", "function onBootstrap() return 7 end").unwrap();
+
+        assert_eq!(adapter.call_mod_function_with_return("status-mod", "onAttach").unwrap(), ModReturnValue::None);
+        assert_eq!(adapter.call_mod_function_with_return("status-mod", "onBootstrap").unwrap(), ModReturnValue::Int(42));
+        assert_eq!(adapter.call_mod_function_with_return("status-mod", "missing").unwrap(), ModReturnValue::None);
+        // Each mod has its own globals
+        assert_eq!(adapter.call_mod_function_with_return("other-mod", "onBootstrap").unwrap(), ModReturnValue::Int(7));
+        assert_eq!(adapter.handler_ids_for_mod("status-mod").len(), 1);
+
+        adapter.unload_mod("status-mod").unwrap();
+        assert!(adapter.handler_ids_for_mod("status-mod").is_empty());
+        assert!(adapter.system_api().event_dispatcher().handler_ids_for_mod("status-mod").is_empty());
+        assert!(matches!(
+            adapter.call_mod_function("status-mod", "onBootstrap"),
+            Err(RuntimeError::ModNotFound { .. })
+        ));
+        assert!(matches!(adapter.unload_mod("status-mod"), Err(RuntimeError::ModNotFound { .. })));
+        assert_eq!(adapter.call_mod_function_with_return("other-mod", "onBootstrap").unwrap(), ModReturnValue::Int(7));
+    }
+
+    #[test]
+    fn test_errors_are_classified() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = LuaRuntimeAdapter::new().unwrap();
+
+        assert!(matches!(load(&mut adapter, &dir, "broken", "function onAttach("), Err(RuntimeError::CompileError { .. })));
+        load(
+            &mut adapter,
+            &dir,
+            "faulty",
+            r#"
+            function onBootstrap() error("boom") end
+            function onTick() while true do end end
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            adapter.call_mod_function("faulty", "onBootstrap"),
+            Err(RuntimeError::RuntimeException { .. })
+        ));
+
+        adapter.set_call_timeout(Some(Duration::from_millis(50)));
+        assert!(matches!(adapter.call_mod_function("faulty", "onTick"), Err(RuntimeError::Timeout { .. })));
+    }
+}
@@ -11,3 +11,15 @@ pub use js::{
     JsRuntimeAdapter, JsRuntimeConfig,
     run_js_event_loop,
 };
+
+#[cfg(feature = "lua")]
+pub mod lua;
+
+#[cfg(feature = "lua")]
+pub use lua::LuaRuntimeAdapter;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "wasm")]
+pub use wasm::WasmRuntimeAdapter;
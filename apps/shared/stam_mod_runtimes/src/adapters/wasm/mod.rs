@@ -0,0 +1,7 @@
+//! WebAssembly Runtime Adapter (wasmtime)
+//!
+//! Provides sandboxed, language-neutral mod execution for `.wasm` modules via wasmtime.
+
+mod runtime;
+
+pub use runtime::WasmRuntimeAdapter;
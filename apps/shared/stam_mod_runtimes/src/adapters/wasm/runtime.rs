@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, error, info, trace, warn};
+use wasmtime::{Caller, Config, Engine, Extern, Instance, Linker, Module, Store, UpdateDeadline, Val, ValType};
+
+use crate::api::{
+    CustomEventRequest, CustomEventResponse, EventKey, GraphicEngineReadyRequest,
+    GraphicEngineReadyResponse, GraphicEngineWindowClosedRequest,
+    GraphicEngineWindowClosedResponse, HandlerRef, TerminalKeyRequest, TerminalKeyResponse,
+    TerminalMouseRequest, TerminalMouseResponse, TerminalPasteRequest, TerminalPasteResponse,
+};
+use crate::call_timeout::{CallDeadline, timeout_error_message};
+use crate::{ModReturnValue, RuntimeAdapter, RuntimeError};
+
+/// Import module name of the host functions
+const HOST_MODULE: &str = "env";
+
+/// Name of the linear memory export the host reads strings from
+const MEMORY_EXPORT: &str = "memory";
+
+/// Per-mod data stored in its wasmtime `Store`
+struct ModState {
+    mod_id: String,
+}
+
+/// A loaded WASM mod: its own store (memory, globals, tables) and instance
+struct LoadedMod {
+    store: Store<ModState>,
+    instance: Instance,
+}
+
+/// Wakes the engine's epoch once the armed call deadline has passed
+///
+/// Wasm code only checks for interruption when the engine epoch changes, so a
+/// single background thread waits for the deadline of the running call and bumps
+/// the epoch when it is reached. The thread blocks on its channel while no call is
+/// armed and exits when the adapter is dropped.
+struct EpochWatchdog {
+    sender: mpsc::Sender<Option<Instant>>,
+}
+
+impl EpochWatchdog {
+    fn spawn(engine: Engine) -> std::io::Result<Self> {
+        let (sender, receiver) = mpsc::channel::<Option<Instant>>();
+        thread::Builder::new()
+            .name("stam-wasm-epoch".to_string())
+            .spawn(move || {
+                let mut deadline: Option<Instant> = None;
+                loop {
+                    let message = match deadline {
+                        Some(at) => receiver.recv_timeout(at.saturating_duration_since(Instant::now())),
+                        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+                    match message {
+                        Ok(next) => deadline = next,
+                        Err(RecvTimeoutError::Timeout) => {
+                            engine.increment_epoch();
+                            deadline = None;
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+            })?;
+        Ok(Self { sender })
+    }
+
+    /// Interrupt running wasm at `deadline` (`None` cancels)
+    fn watch(&self, deadline: Option<Instant>) {
+        let _ = self.sender.send(deadline);
+    }
+}
+
+/// WebAssembly runtime adapter
+///
+/// All WASM mods share one wasmtime engine, and each mod gets its own store and
+/// instance so modules cannot see each other's memory. The ABI is intentionally
+/// small: lifecycle hooks are exported functions taking no arguments and
+/// returning nothing or an `i32` status, and the host exposes the console API as
+/// imports from the `env` module:
+///
+/// ```wat
+/// (module
+///   (import "env" "console_log" (func $log (param i32 i32)))
+///   (memory (export "memory") 1)
+///   (data (i32.const 0) "attached")
+///   (func (export "onAttach") (call $log (i32.const 0) (i32.const 8))))
+/// ```
+pub struct WasmRuntimeAdapter {
+    engine: Engine,
+    linker: Linker<ModState>,
+    loaded_mods: HashMap<String, LoadedMod>,
+    /// Maximum duration of a synchronous mod call (None = no limit)
+    call_timeout: Option<Duration>,
+    /// Deadline polled when the engine epoch changes
+    call_deadline: CallDeadline,
+    watchdog: EpochWatchdog,
+}
+
+impl WasmRuntimeAdapter {
+    /// Create a new WASM runtime adapter
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = Config::new();
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config)?;
+
+        let mut linker = Linker::new(&engine);
+        define_console(&mut linker)?;
+
+        Ok(Self {
+            watchdog: EpochWatchdog::spawn(engine.clone())?,
+            engine,
+            linker,
+            loaded_mods: HashMap::new(),
+            call_timeout: None,
+            call_deadline: CallDeadline::new(),
+        })
+    }
+
+    /// Create the store of a mod, trapping once the call deadline has passed
+    fn create_store(&self, mod_id: &str) -> Store<ModState> {
+        let mut store = Store::new(&self.engine, ModState { mod_id: mod_id.to_string() });
+        store.set_epoch_deadline(1);
+        let deadline = self.call_deadline.clone();
+        store.epoch_deadline_callback(move |_| {
+            if deadline.is_expired() {
+                Ok(UpdateDeadline::Interrupt)
+            } else {
+                // Epoch bumped for an earlier call that returned in time
+                Ok(UpdateDeadline::Continue(1))
+            }
+        });
+        store
+    }
+}
+
+/// Register `console_log`, `console_warn` and `console_error` as `env` imports
+///
+/// Each takes a `(ptr, len)` pair pointing to a UTF-8 string in the mod's exported memory.
+fn define_console(linker: &mut Linker<ModState>) -> wasmtime::Result<()> {
+    linker.func_wrap(
+        HOST_MODULE,
+        "console_log",
+        |mut caller: Caller<'_, ModState>, ptr: i32, len: i32| -> wasmtime::Result<()> {
+            let message = read_guest_string(&mut caller, ptr, len)?;
+            info!(runtime_type = "wasm", mod_id = caller.data().mod_id.as_str(), "{}", message);
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "console_warn",
+        |mut caller: Caller<'_, ModState>, ptr: i32, len: i32| -> wasmtime::Result<()> {
+            let message = read_guest_string(&mut caller, ptr, len)?;
+            warn!(runtime_type = "wasm", mod_id = caller.data().mod_id.as_str(), "{}", message);
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        HOST_MODULE,
+        "console_error",
+        |mut caller: Caller<'_, ModState>, ptr: i32, len: i32| -> wasmtime::Result<()> {
+            let message = read_guest_string(&mut caller, ptr, len)?;
+            error!(runtime_type = "wasm", mod_id = caller.data().mod_id.as_str(), "{}", message);
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
+
+/// Read `len` bytes at `ptr` from the caller's exported memory as (lossy) UTF-8
+fn read_guest_string(caller: &mut Caller<'_, ModState>, ptr: i32, len: i32) -> wasmtime::Result<String> {
+    let memory = caller
+        .get_export(MEMORY_EXPORT)
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("console functions require the module to export its memory"))?;
+
+    let start = ptr as u32 as usize;
+    let bytes = start
+        .checked_add(len as u32 as usize)
+        .and_then(|end| memory.data(&caller).get(start..end))
+        .ok_or_else(|| wasmtime::Error::msg(format!("string at {}..+{} is out of memory bounds", start, len)))?;
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+impl RuntimeAdapter for WasmRuntimeAdapter {
+    fn load_mod(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError> {
+        trace!("Loading WASM module: {} from {}", mod_id, mod_path.display());
+
+        let bytes = fs::read(mod_path).map_err(|e| {
+            RuntimeError::load_failed(
+                mod_id,
+                format!("Failed to read entry point '{}': {}", mod_path.display(), e),
+            )
+        })?;
+
+        let module = Module::new(&self.engine, &bytes).map_err(|e| {
+            error!("{:#}", e);
+            RuntimeError::CompileError {
+                line: None,
+                message: format!("Invalid WASM module for mod '{}': {:#}", mod_id, e),
+            }
+        })?;
+
+        let mut store = self.create_store(mod_id);
+        let instance = {
+            let _deadline = self.call_deadline.arm(self.call_timeout);
+            self.watchdog.watch(self.call_timeout.map(|t| Instant::now() + t));
+            let instance = self.linker.instantiate(&mut store, &module);
+            self.watchdog.watch(None);
+            instance.map_err(|e| RuntimeError::load_failed(mod_id, format!("{:#}", e)))?
+        };
+
+        self.loaded_mods.insert(mod_id.to_string(), LoadedMod { store, instance });
+        Ok(())
+    }
+
+    fn call_mod_function(&mut self, mod_id: &str, function_name: &str) -> Result<(), RuntimeError> {
+        self.call_mod_function_with_return(mod_id, function_name).map(|_| ())
+    }
+
+    fn call_mod_function_with_return(
+        &mut self,
+        mod_id: &str,
+        function_name: &str,
+    ) -> Result<ModReturnValue, RuntimeError> {
+        let loaded_mod = self
+            .loaded_mods
+            .get_mut(mod_id)
+            .ok_or_else(|| RuntimeError::ModNotFound { mod_id: mod_id.to_string() })?;
+
+        let Some(func) = loaded_mod.instance.get_func(&mut loaded_mod.store, function_name) else {
+            debug!("Function '{}' not exported by mod '{}'", function_name, mod_id);
+            return Ok(ModReturnValue::None);
+        };
+
+        // Only `() -> ()` and `() -> i32` are part of the ABI
+        let ty = func.ty(&loaded_mod.store);
+        let results: Vec<ValType> = ty.results().collect();
+        if ty.params().len() != 0 || results.len() > 1 || results.first().is_some_and(|r| !matches!(r, ValType::I32)) {
+            return Err(RuntimeError::unsupported(format!(
+                "'{}' in mod '{}' has signature {}; WASM mod functions must take no parameters and return nothing or an i32",
+                function_name, mod_id, ty
+            )));
+        }
+
+        let mut values = vec![Val::I32(0); results.len()];
+        {
+            let _deadline = self.call_deadline.arm(self.call_timeout);
+            self.watchdog.watch(self.call_timeout.map(|t| Instant::now() + t));
+            let result = func.call(&mut loaded_mod.store, &[], &mut values);
+            self.watchdog.watch(None);
+            result.map_err(|e| {
+                if let Some(timeout) = self.call_timeout.filter(|_| self.call_deadline.is_expired()) {
+                    error!("{}", timeout_error_message(mod_id, function_name, timeout));
+                    return RuntimeError::Timeout {
+                        mod_id: mod_id.to_string(),
+                        function_name: function_name.to_string(),
+                        timeout,
+                    };
+                }
+                error!("{:#}", e);
+                RuntimeError::exception(format!("WASM trap in '{}' for mod '{}': {:#}", function_name, mod_id, e))
+            })?;
+        }
+
+        Ok(match values.first() {
+            Some(Val::I32(status)) => ModReturnValue::Int(*status),
+            _ => ModReturnValue::None,
+        })
+    }
+
+    fn set_call_timeout(&mut self, timeout: Option<Duration>) {
+        self.call_timeout = timeout;
+    }
+
+    fn supports_unload(&self) -> bool {
+        true
+    }
+
+    fn unload_mod(&mut self, mod_id: &str) -> Result<(), RuntimeError> {
+        // Dropping the store frees the instance and its memory
+        if self.loaded_mods.remove(mod_id).is_none() {
+            return Err(RuntimeError::ModNotFound { mod_id: mod_id.to_string() });
+        }
+        debug!("Unloaded WASM mod '{}'", mod_id);
+        Ok(())
+    }
+
+    // WASM mods cannot register event handlers yet: every dispatch is a no-op
+
+    fn call_event_handler(
+        &mut self,
+        handler_id: u64,
+        _event_name: &str,
+        _args: &[String],
+    ) -> Result<(), RuntimeError> {
+        Err(RuntimeError::exception(format!("Event handler {} not found in any loaded mod", handler_id)))
+    }
+
+    fn collect_handlers(&self, _event: &EventKey) -> Vec<HandlerRef> {
+        Vec::new()
+    }
+
+    fn dispatch_terminal_key(&self, _request: &TerminalKeyRequest, _handlers: &[HandlerRef]) -> TerminalKeyResponse {
+        TerminalKeyResponse::default()
+    }
+
+    fn terminal_key_handler_count(&self) -> usize {
+        0
+    }
+
+    fn dispatch_terminal_mouse(&self, _request: &TerminalMouseRequest, _handlers: &[HandlerRef]) -> TerminalMouseResponse {
+        TerminalMouseResponse::default()
+    }
+
+    fn dispatch_terminal_paste(&self, _request: &TerminalPasteRequest, _handlers: &[HandlerRef]) -> TerminalPasteResponse {
+        TerminalPasteResponse::default()
+    }
+
+    fn dispatch_graphic_engine_ready(
+        &self,
+        _request: &GraphicEngineReadyRequest,
+        _handlers: &[HandlerRef],
+    ) -> GraphicEngineReadyResponse {
+        GraphicEngineReadyResponse::default()
+    }
+
+    fn dispatch_graphic_engine_window_closed(
+        &self,
+        _request: &GraphicEngineWindowClosedRequest,
+        _handlers: &[HandlerRef],
+    ) -> GraphicEngineWindowClosedResponse {
+        GraphicEngineWindowClosedResponse::default()
+    }
+
+    fn dispatch_custom_event(&self, _request: &CustomEventRequest, _handlers: &[HandlerRef]) -> CustomEventResponse {
+        CustomEventResponse::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(adapter: &mut WasmRuntimeAdapter, dir: &tempfile::TempDir, mod_id: &str, wat: &str) -> Result<(), RuntimeError> {
+        let path = dir.path().join(format!("{}.wasm", mod_id));
+        fs::write(&path, wat).unwrap();
+        adapter.load_mod(&path, mod_id)
+    }
+
+    #[test]
+    fn test_lifecycle_hooks_and_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = WasmRuntimeAdapter::new().unwrap();
+        load(
+            &mut adapter,
+            &dir,
+            "status-mod",
+            r#"(module
+                (import "env" "console_log" (func $log (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "attached")
+                (func (export "onAttach") (call $log (i32.const 0) (i32.const 8)))
+                (func (export "onBootstrap") (result i32) (i32.const 42)))"#,
+        )
+        .unwrap();
+
+        assert_eq!(adapter.call_mod_function_with_return("status-mod", "onAttach").unwrap(), ModReturnValue::None);
+        assert_eq!(adapter.call_mod_function_with_return("status-mod", "onBootstrap").unwrap(), ModReturnValue::Int(42));
+        assert_eq!(adapter.call_mod_function_with_return("status-mod", "missing").unwrap(), ModReturnValue::None);
+
+        adapter.unload_mod("status-mod").unwrap();
+        assert!(matches!(
+            adapter.call_mod_function("status-mod", "onAttach"),
+            Err(RuntimeError::ModNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unsupported_signatures_and_traps() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = WasmRuntimeAdapter::new().unwrap();
+        load(
+            &mut adapter,
+            &dir,
+            "bad-mod",
+            r#"(module
+                (func (export "withParam") (param i32))
+                (func (export "returnsFloat") (result f64) (f64.const 1.5))
+                (func (export "traps") unreachable))"#,
+        )
+        .unwrap();
+
+        for name in ["withParam", "returnsFloat"] {
+            assert!(matches!(adapter.call_mod_function("bad-mod", name), Err(RuntimeError::Unsupported { .. })), "{}", name);
+        }
+        assert!(matches!(adapter.call_mod_function("bad-mod", "traps"), Err(RuntimeError::RuntimeException { .. })));
+
+        // Invalid modules and unknown imports fail to load
+        assert!(matches!(load(&mut adapter, &dir, "broken", "(module (func"), Err(RuntimeError::CompileError { .. })));
+        assert!(matches!(
+            load(&mut adapter, &dir, "unknown-import", r#"(module (import "env" "nope" (func)))"#),
+            Err(RuntimeError::LoadFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_call_timeout_interrupts_infinite_loop() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut adapter = WasmRuntimeAdapter::new().unwrap();
+        load(&mut adapter, &dir, "loop-mod", r#"(module (func (export "onBootstrap") (loop (br 0))))"#).unwrap();
+
+        adapter.set_call_timeout(Some(Duration::from_millis(50)));
+        assert!(matches!(
+            adapter.call_mod_function("loop-mod", "onBootstrap"),
+            Err(RuntimeError::Timeout { .. })
+        ));
+    }
+}
@@ -0,0 +1,201 @@
+//! Capabilities API abstraction
+//!
+//! Decides which optional APIs (network, process, storage, ...) each mod may use.
+//! A mod lists the capabilities it needs in the `capabilities` field of its
+//! manifest; a mod without that field (or never declared to the policy) gets
+//! none of them, only the core APIs every mod has. The host (typically the server, from its config) can
+//! additionally deny capabilities to every mod, whatever their manifests say.
+//! This module is runtime-agnostic and can be used by JavaScript, Lua, C#, etc.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// An optional API a mod has to be granted before it can use it
+///
+/// Names mirror `stam_schema::KNOWN_CAPABILITIES`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `Process` (game data/config paths)
+    Process,
+    /// `File` (JSON files in the game directories)
+    File,
+    /// `FileSystem` (files shipped in the mod's own directory)
+    FileSystem,
+    /// `Storage` (persistent key-value store)
+    Storage,
+    /// `Network` (downloads)
+    Network,
+    /// `Graphic`, `Audio` and `World`
+    Graphic,
+    /// `Resource` (asset loading)
+    Resource,
+    /// `Process.spawn()` (child processes, server-only)
+    Spawn,
+}
+
+impl Capability {
+    /// Every capability, in manifest order
+    pub const ALL: [Capability; 8] = [
+        Capability::Process,
+        Capability::File,
+        Capability::FileSystem,
+        Capability::Storage,
+        Capability::Network,
+        Capability::Graphic,
+        Capability::Resource,
+        Capability::Spawn,
+    ];
+
+    /// Name used in manifests and server config
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::Process => "process",
+            Capability::File => "file",
+            Capability::FileSystem => "filesystem",
+            Capability::Storage => "storage",
+            Capability::Network => "network",
+            Capability::Graphic => "graphic",
+            Capability::Resource => "resource",
+            Capability::Spawn => "spawn",
+        }
+    }
+
+    /// Parse a capability name (as written in manifests and server config)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|capability| capability.name() == name)
+    }
+
+    /// Parse a list of capability names, failing on the first unknown one
+    pub fn parse_list<S: AsRef<str>>(names: &[S]) -> Result<Vec<Self>, String> {
+        names
+            .iter()
+            .map(|name| {
+                let name = name.as_ref();
+                Self::from_name(name).ok_or_else(|| {
+                    let known: Vec<&str> = Self::ALL.iter().map(|c| c.name()).collect();
+                    format!("Unknown capability '{}' (known capabilities: {})", name, known.join(", "))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Capabilities granted to each mod
+///
+/// Cloning is cheap and all clones share the same state, so the host can keep a
+/// handle to declare mods loaded later (like `StorageApi`).
+#[derive(Clone, Default)]
+pub struct CapabilityPolicy {
+    /// Capabilities declared by each mod's manifest (absent = none)
+    declared: Arc<RwLock<HashMap<String, HashSet<Capability>>>>,
+    /// Capabilities denied to every mod by the host
+    denied: Arc<RwLock<HashSet<Capability>>>,
+}
+
+impl CapabilityPolicy {
+    /// Create a policy granting no capability to any mod until they are declared
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deny capabilities to every mod regardless of their manifests
+    pub fn set_denied(&self, capabilities: impl IntoIterator<Item = Capability>) {
+        *self.denied.write().unwrap() = capabilities.into_iter().collect();
+    }
+
+    /// Record the capabilities declared in a mod's manifest
+    ///
+    /// `None` (no `capabilities` field) grants no capability.
+    pub fn declare<S: AsRef<str>>(&self, mod_id: &str, capabilities: Option<&[S]>) -> Result<(), String> {
+        let mut declared = self.declared.write().unwrap();
+        match capabilities {
+            Some(names) => {
+                let parsed = Capability::parse_list(names)
+                    .map_err(|e| format!("Mod '{}': {}", mod_id, e))?;
+                declared.insert(mod_id.to_string(), parsed.into_iter().collect());
+            }
+            None => {
+                declared.remove(mod_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether a mod may use a capability
+    ///
+    /// # Returns
+    /// * `Ok(())` - The capability is granted
+    /// * `Err(message)` - Why it is not, ready to be shown to the mod author
+    pub fn check(&self, mod_id: &str, capability: Capability) -> Result<(), String> {
+        if self.denied.read().unwrap().contains(&capability) {
+            return Err(format!(
+                "The '{}' capability is denied to mod '{}' by the server configuration",
+                capability.name(),
+                mod_id
+            ));
+        }
+
+        let declared = self.declared.read().unwrap();
+        match declared.get(mod_id) {
+            Some(granted) if granted.contains(&capability) => Ok(()),
+            _ => Err(format!(
+                "Mod '{}' did not declare the '{}' capability. Add it to \"capabilities\" in its manifest.json",
+                mod_id,
+                capability.name()
+            )),
+        }
+    }
+
+    /// Check whether a mod may use a capability
+    pub fn is_allowed(&self, mod_id: &str, capability: Capability) -> bool {
+        self.check(mod_id, capability).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capability_names_round_trip() {
+        for capability in Capability::ALL {
+            assert_eq!(Capability::from_name(capability.name()), Some(capability));
+        }
+        assert_eq!(Capability::from_name("sockets"), None);
+        assert!(Capability::parse_list(&["network", "sockets"]).unwrap_err().contains("'sockets'"));
+    }
+
+    #[test]
+    fn test_declared_capabilities() {
+        let policy = CapabilityPolicy::new();
+        policy.declare("legacy", None::<&[&str]>).unwrap();
+        policy.declare("sandboxed", Some(&["network", "storage"])).unwrap();
+
+        // Mods without a capabilities field (or unknown to the policy) get none
+        for mod_id in ["legacy", "unknown"] {
+            for capability in Capability::ALL {
+                let err = policy.check(mod_id, capability).unwrap_err();
+                assert!(err.contains(&format!("did not declare the '{}' capability", capability.name())), "{}", err);
+            }
+        }
+
+        assert!(policy.is_allowed("sandboxed", Capability::Network));
+        assert!(policy.is_allowed("sandboxed", Capability::Storage));
+        let err = policy.check("sandboxed", Capability::Process).unwrap_err();
+        assert!(err.contains("did not declare the 'process' capability"), "{}", err);
+
+        assert!(policy.declare("broken", Some(&["sockets"])).is_err());
+    }
+
+    #[test]
+    fn test_denied_capabilities_override_manifest() {
+        let policy = CapabilityPolicy::new();
+        policy.declare("sandboxed", Some(&["network", "process"])).unwrap();
+        policy.set_denied([Capability::Process]);
+
+        let err = policy.check("sandboxed", Capability::Process).unwrap_err();
+        assert!(err.contains("denied to mod 'sandboxed' by the server"), "{}", err);
+        assert!(!policy.is_allowed("legacy", Capability::Process));
+        assert!(policy.is_allowed("sandboxed", Capability::Network));
+    }
+}
@@ -18,8 +18,46 @@
 ///
 /// Note: `console.log` maps to `debug` level to reduce verbosity in production.
 /// Use `console.info` for messages that should appear at INFO level.
+///
+/// ## Structured Fields
+///
+/// Scripts can attach structured fields to a message (in JavaScript,
+/// `console.info("login", { userId: 42 })`). They are emitted as a single `fields`
+/// tracing field holding the JSON object, since tracing field names are static;
+/// the Staminal formatter prints them as `key=value` pairs after the message.
+
+use tracing::Level;
 
-use tracing::{debug, error, info, warn};
+/// Structured fields attached to a console message, by field name
+pub type ConsoleFields = serde_json::Map<String, serde_json::Value>;
+
+/// Emit a console event at `$level`, with the optional `game_id` and `fields`
+macro_rules! console_event {
+    ($level:expr, $game_id:expr, $runtime_type:expr, $mod_id:expr, $message:expr, $fields:expr) => {{
+        let fields = $fields.map(|fields: &ConsoleFields| serde_json::Value::Object(fields.clone()).to_string());
+        match ($game_id, fields.as_deref()) {
+            (Some(gid), Some(fields)) => tracing::event!(
+                $level,
+                game_id = gid,
+                runtime_type = $runtime_type,
+                mod_id = $mod_id,
+                fields = fields,
+                message = $message
+            ),
+            (Some(gid), None) => {
+                tracing::event!($level, game_id = gid, runtime_type = $runtime_type, mod_id = $mod_id, message = $message)
+            }
+            (None, Some(fields)) => tracing::event!(
+                $level,
+                runtime_type = $runtime_type,
+                mod_id = $mod_id,
+                fields = fields,
+                message = $message
+            ),
+            (None, None) => tracing::event!($level, runtime_type = $runtime_type, mod_id = $mod_id, message = $message),
+        }
+    }};
+}
 
 /// Console API implementation
 #[derive(Clone)]
@@ -32,48 +70,28 @@ impl ConsoleApi {
     }
 
     /// Log a debug message (console.log maps to debug level for less verbose output)
-    pub fn log(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str) {
-        if let Some(gid) = game_id {
-            debug!(game_id = gid, runtime_type = runtime_type, mod_id = mod_id, message = message);
-        } else {
-            debug!(runtime_type = runtime_type, mod_id = mod_id, message = message);
-        }
+    pub fn log(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str, fields: Option<&ConsoleFields>) {
+        console_event!(Level::DEBUG, game_id, runtime_type, mod_id, message, fields);
     }
 
     /// Log an error message
-    pub fn error(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str) {
-        if let Some(gid) = game_id {
-            error!(game_id = gid, runtime_type = runtime_type, mod_id = mod_id, message = message);
-        } else {
-            error!(runtime_type = runtime_type, mod_id = mod_id, message = message);
-        }
+    pub fn error(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str, fields: Option<&ConsoleFields>) {
+        console_event!(Level::ERROR, game_id, runtime_type, mod_id, message, fields);
     }
 
     /// Log a warning message
-    pub fn warn(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str) {
-        if let Some(gid) = game_id {
-            warn!(game_id = gid, runtime_type = runtime_type, mod_id = mod_id, message = message);
-        } else {
-            warn!(runtime_type = runtime_type, mod_id = mod_id, message = message);
-        }
+    pub fn warn(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str, fields: Option<&ConsoleFields>) {
+        console_event!(Level::WARN, game_id, runtime_type, mod_id, message, fields);
     }
 
     /// Log an info message (alias for log)
-    pub fn info(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str) {
-        if let Some(gid) = game_id {
-            info!(game_id = gid, runtime_type = runtime_type, mod_id = mod_id, message = message);
-        } else {
-            info!(runtime_type = runtime_type, mod_id = mod_id, message = message);
-        }
+    pub fn info(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str, fields: Option<&ConsoleFields>) {
+        console_event!(Level::INFO, game_id, runtime_type, mod_id, message, fields);
     }
 
     /// Log a debug message
-    pub fn debug(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str) {
-        if let Some(gid) = game_id {
-            debug!(game_id = gid, runtime_type = runtime_type, mod_id = mod_id, message = message);
-        } else {
-            debug!(runtime_type = runtime_type, mod_id = mod_id, message = message);
-        }
+    pub fn debug(game_id: Option<&str>, runtime_type: &str, mod_id: &str, message: &str, fields: Option<&ConsoleFields>) {
+        console_event!(Level::DEBUG, game_id, runtime_type, mod_id, message, fields);
     }
 }
 
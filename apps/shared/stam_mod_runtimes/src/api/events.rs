@@ -16,11 +16,35 @@
 //! - **Custom events**: User-defined events like `"AppStart"` (represented as strings)
 //!
 //! Both use the same registration and dispatch mechanism through `EventKey`.
+//!
+//! # Wildcard Subscriptions
+//!
+//! A custom event name ending with `*` (e.g. `"ui:*"`, or `"*"` alone) subscribes to every
+//! custom event sharing the prefix before the `*`: `"ui:*"` receives `"ui:click"` but not
+//! `"uix:click"`. System events are never matched by wildcards.
+//!
+//! Handlers registered for the exact name run first, by priority, then the matching
+//! wildcard handlers, by priority. A wildcard handler never receives the events sent by
+//! its own mod (`CustomEventRequest::sender`), so a mod logging every event it sees and
+//! re-emitting them doesn't call itself forever.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use tokio::sync::{mpsc, oneshot};
 
+/// Registration order shared by every dispatcher in the process
+///
+/// Handler IDs are per dispatcher, so they can't order handlers of different
+/// runtimes; this sequence breaks ties between equal priorities across runtimes.
+static NEXT_REGISTRATION_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// Prefix of the storage key of custom events (`EventKey::to_string_key`)
+const CUSTOM_KEY_PREFIX: &str = "custom:";
+
+/// Suffix turning a custom event name into a wildcard subscription
+const WILDCARD: char = '*';
+
 /// System events that mods can register handlers for
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u32)]
@@ -37,6 +61,12 @@ pub enum SystemEvents {
     /// Graphic engine window closed event - triggered when a window is closed.
     /// The request contains the window_id of the closed window. This is a client-only event.
     GraphicEngineWindowClosed = 4,
+    /// Terminal mouse event - triggered when the mouse is clicked, dragged, moved
+    /// or scrolled in the terminal (xterm mouse reporting)
+    TerminalMouse = 5,
+    /// Terminal paste event - triggered when text is pasted in the terminal
+    /// (bracketed paste), instead of one TerminalKeyPressed per character
+    TerminalPaste = 6,
 }
 
 impl SystemEvents {
@@ -47,6 +77,8 @@ impl SystemEvents {
             2 => Some(SystemEvents::TerminalKeyPressed),
             3 => Some(SystemEvents::GraphicEngineReady),
             4 => Some(SystemEvents::GraphicEngineWindowClosed),
+            5 => Some(SystemEvents::TerminalMouse),
+            6 => Some(SystemEvents::TerminalPaste),
             _ => None,
         }
     }
@@ -63,6 +95,8 @@ impl SystemEvents {
             SystemEvents::TerminalKeyPressed => "system:TerminalKeyPressed".to_string(),
             SystemEvents::GraphicEngineReady => "system:GraphicEngineReady".to_string(),
             SystemEvents::GraphicEngineWindowClosed => "system:GraphicEngineWindowClosed".to_string(),
+            SystemEvents::TerminalMouse => "system:TerminalMouse".to_string(),
+            SystemEvents::TerminalPaste => "system:TerminalPaste".to_string(),
         }
     }
 
@@ -73,6 +107,8 @@ impl SystemEvents {
             "system:TerminalKeyPressed" => Some(SystemEvents::TerminalKeyPressed),
             "system:GraphicEngineReady" => Some(SystemEvents::GraphicEngineReady),
             "system:GraphicEngineWindowClosed" => Some(SystemEvents::GraphicEngineWindowClosed),
+            "system:TerminalMouse" => Some(SystemEvents::TerminalMouse),
+            "system:TerminalPaste" => Some(SystemEvents::TerminalPaste),
             _ => None,
         }
     }
@@ -98,11 +134,17 @@ impl EventKey {
         EventKey::Custom(name.into())
     }
 
+    /// Key of an event named by the host: a system event key (e.g. "system:TerminalKeyPressed")
+    /// or, for any other name, a custom event
+    pub fn from_name(name: &str) -> Self {
+        SystemEvents::from_key(name).map_or_else(|| EventKey::Custom(name.to_string()), EventKey::System)
+    }
+
     /// Convert to a string representation for internal storage
     pub fn to_string_key(&self) -> String {
         match self {
             EventKey::System(event) => event.to_key(),
-            EventKey::Custom(name) => format!("custom:{}", name),
+            EventKey::Custom(name) => format!("{}{}", CUSTOM_KEY_PREFIX, name),
         }
     }
 
@@ -131,6 +173,25 @@ impl EventKey {
             EventKey::System(_) => None,
         }
     }
+
+    /// Check if this is a wildcard subscription (a custom event name ending with `*`)
+    pub fn is_wildcard(&self) -> bool {
+        self.custom_name().is_some_and(|name| name.ends_with(WILDCARD))
+    }
+
+    /// Check whether handlers registered for this key receive the custom event `event_name`
+    ///
+    /// An exact name matches only itself, a wildcard (`"ui:*"`) every name starting with
+    /// the part before the `*`.
+    pub fn matches_custom(&self, event_name: &str) -> bool {
+        match self.custom_name() {
+            Some(name) => match name.strip_suffix(WILDCARD) {
+                Some(prefix) => event_name.starts_with(prefix),
+                None => name == event_name,
+            },
+            None => false,
+        }
+    }
 }
 
 /// Request object passed to custom event handlers
@@ -143,6 +204,10 @@ pub struct CustomEventRequest {
     pub event_name: String,
     /// Arguments passed to sendEvent (JSON-serialized)
     pub args: Vec<String>,
+    /// Mod that should receive the event (None = broadcast to every mod)
+    pub target: Option<String>,
+    /// Mod that sent the event (None = sent by the host)
+    pub sender: Option<String>,
 }
 
 impl CustomEventRequest {
@@ -151,8 +216,36 @@ impl CustomEventRequest {
         Self {
             event_name: event_name.into(),
             args,
+            target: None,
+            sender: None,
         }
     }
+
+    /// Deliver the event only to handlers registered by `mod_id`
+    pub fn with_target(mut self, mod_id: impl Into<String>) -> Self {
+        self.target = Some(mod_id.into());
+        self
+    }
+
+    /// Record the mod that sent the event
+    pub fn with_sender(mut self, mod_id: impl Into<String>) -> Self {
+        self.sender = Some(mod_id.into());
+        self
+    }
+
+    /// Check whether handlers of the given mod should receive this event
+    pub fn is_addressed_to(&self, mod_id: &str) -> bool {
+        self.target.as_deref().is_none_or(|target| target == mod_id)
+    }
+
+    /// Check whether the given handler should receive this event
+    ///
+    /// Besides the target, a wildcard handler is skipped for the events its own mod sent,
+    /// so re-emitting what it receives can't loop.
+    pub fn accepts(&self, handler: &HandlerRef) -> bool {
+        self.is_addressed_to(&handler.mod_id)
+            && !(handler.wildcard && self.sender.as_deref() == Some(handler.mod_id.as_str()))
+    }
 }
 
 /// Response object for custom event handlers
@@ -200,15 +293,29 @@ pub struct SendEventRequest {
     pub event_name: String,
     /// Arguments to pass to handlers (JSON-serialized)
     pub args: Vec<String>,
+    /// Mod that should receive the event (None = every mod)
+    pub target: Option<String>,
+    /// Mod that sent the event (None = sent by the host)
+    pub sender: Option<String>,
     /// Channel to send the result back to the caller
     pub response_tx: oneshot::Sender<CustomEventResponse>,
 }
 
+impl SendEventRequest {
+    /// Build the CustomEventRequest to dispatch for this request
+    pub fn to_custom_event(&self) -> CustomEventRequest {
+        let mut request = CustomEventRequest::new(self.event_name.clone(), self.args.clone());
+        request.target = self.target.clone();
+        request.sender = self.sender.clone();
+        request
+    }
+}
+
 /// Protocol filter for RequestUri events
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[repr(u32)]
 pub enum RequestUriProtocol {
-    /// Match stam:// protocol only
+    /// Match stam:// and stams:// protocols only
     Stam = 1,
     /// Match http:// and https:// protocols only
     Http = 2,
@@ -237,7 +344,7 @@ impl RequestUriProtocol {
     pub fn matches(&self, uri: &str) -> bool {
         match self {
             RequestUriProtocol::All => true,
-            RequestUriProtocol::Stam => uri.starts_with("stam://"),
+            RequestUriProtocol::Stam => super::network::is_stam_uri(uri),
             RequestUriProtocol::Http => uri.starts_with("http://") || uri.starts_with("https://"),
         }
     }
@@ -414,6 +521,90 @@ impl TerminalKeyResponse {
     }
 }
 
+/// Request object passed to TerminalMouse handlers
+///
+/// Coordinates are 0-based terminal cells, with (0, 0) at the top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalMouseRequest {
+    /// Column of the mouse pointer
+    pub x: u16,
+    /// Row of the mouse pointer
+    pub y: u16,
+    /// Button involved: "Left", "Right", "Middle", or "None" (moves and scrolls)
+    pub button: String,
+    /// What happened: "Down", "Up", "Drag", "Moved", "ScrollUp", "ScrollDown",
+    /// "ScrollLeft" or "ScrollRight"
+    pub kind: String,
+}
+
+impl TerminalMouseRequest {
+    /// Create a new TerminalMouseRequest
+    pub fn new(x: u16, y: u16, button: impl Into<String>, kind: impl Into<String>) -> Self {
+        Self {
+            x,
+            y,
+            button: button.into(),
+            kind: kind.into(),
+        }
+    }
+}
+
+/// Response object for TerminalMouse handlers
+///
+/// Handlers set `handled = true` to stop the event from reaching lower-priority handlers.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalMouseResponse {
+    /// Whether the mouse event has been handled (default: false)
+    pub handled: bool,
+}
+
+impl TerminalMouseResponse {
+    /// Create a new TerminalMouseResponse
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the mouse event has been handled
+    pub fn set_handled(&mut self, handled: bool) {
+        self.handled = handled;
+    }
+}
+
+/// Request object passed to TerminalPaste handlers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalPasteRequest {
+    /// The pasted text, as a single block
+    pub text: String,
+}
+
+impl TerminalPasteRequest {
+    /// Create a new TerminalPasteRequest
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+/// Response object for TerminalPaste handlers
+///
+/// Handlers set `handled = true` to stop the paste from reaching lower-priority handlers.
+#[derive(Debug, Clone, Default)]
+pub struct TerminalPasteResponse {
+    /// Whether the paste has been handled (default: false)
+    pub handled: bool,
+}
+
+impl TerminalPasteResponse {
+    /// Create a new TerminalPasteResponse
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the paste has been handled
+    pub fn set_handled(&mut self, handled: bool) {
+        self.handled = handled;
+    }
+}
+
 /// Request object passed to GraphicEngineReady handlers
 ///
 /// This event is triggered when the graphic engine has been initialized
@@ -523,6 +714,54 @@ pub struct EventHandler {
     pub route: String,
     /// Unique handler ID (for removal)
     pub handler_id: u64,
+    /// Process-wide registration order (ties between equal priorities)
+    pub registration_seq: u64,
+    /// Whether the handler is a wildcard subscription (`"ui:*"`)
+    pub wildcard: bool,
+}
+
+/// Lightweight reference to a registered handler
+///
+/// Runtime adapters expose their handlers as `HandlerRef`s so the `RuntimeManager`
+/// can order handlers from every runtime by `(wildcard, priority, registration_seq)`
+/// and hand each adapter back the ones it must call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerRef {
+    /// ID of the mod that registered this handler
+    pub mod_id: String,
+    /// Handler ID within its runtime's dispatcher
+    pub handler_id: u64,
+    /// Priority (lower numbers execute first)
+    pub priority: i32,
+    /// Process-wide registration order
+    pub registration_seq: u64,
+    /// Whether the handler is a wildcard subscription (called after the exact ones)
+    pub wildcard: bool,
+}
+
+impl From<&EventHandler> for HandlerRef {
+    fn from(handler: &EventHandler) -> Self {
+        Self {
+            mod_id: handler.mod_id.clone(),
+            handler_id: handler.handler_id,
+            priority: handler.priority,
+            registration_seq: handler.registration_seq,
+            wildcard: handler.wildcard,
+        }
+    }
+}
+
+/// Summary of the handlers registered for an event (`System.listEventHandlers()`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventHandlerInfo {
+    /// Event name: the system event key (e.g. "system:TerminalKeyPressed") or the custom event name
+    pub event: String,
+    /// Whether this is a custom event
+    pub custom: bool,
+    /// Number of registered handlers
+    pub count: usize,
+    /// Mods owning the handlers, once each, in dispatch order
+    pub mod_ids: Vec<String>,
 }
 
 /// Event dispatcher that manages handler registration and execution
@@ -583,8 +822,10 @@ impl EventDispatcher {
 
     /// Register an event handler for a custom event
     ///
+    /// A name ending with `*` registers a wildcard handler (see the module docs).
+    ///
     /// # Arguments
-    /// * `event_name` - The custom event name
+    /// * `event_name` - The custom event name, or a wildcard pattern
     /// * `mod_id` - ID of the registering mod
     /// * `priority` - Handler priority (lower executes first)
     ///
@@ -627,6 +868,8 @@ impl EventDispatcher {
             protocol,
             route: route.into(),
             handler_id,
+            registration_seq: NEXT_REGISTRATION_SEQ.fetch_add(1, Ordering::Relaxed),
+            wildcard: event_key.is_wildcard(),
         };
 
         let key = event_key.to_string_key();
@@ -655,6 +898,19 @@ impl EventDispatcher {
         false
     }
 
+    /// Get the IDs of all handlers registered by a specific mod
+    pub fn handler_ids_for_mod(&self, mod_id: &str) -> Vec<u64> {
+        let handlers = self.handlers.read().unwrap();
+        let mut ids: Vec<u64> = handlers
+            .values()
+            .flatten()
+            .filter(|h| h.mod_id == mod_id)
+            .map(|h| h.handler_id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
     /// Unregister all handlers for a specific mod
     pub fn unregister_mod_handlers(&self, mod_id: &str) {
         let mut handlers = self.handlers.write().unwrap();
@@ -693,18 +949,42 @@ impl EventDispatcher {
             .unwrap_or_default()
     }
 
-    /// Get handlers for a custom event by name
+    /// Get handlers for any event key
     ///
-    /// Returns all handlers registered for the given custom event name,
-    /// sorted by priority.
-    pub fn get_handlers_for_custom_event(&self, event_name: &str) -> Vec<EventHandler> {
+    /// Returns all handlers registered for the given event, sorted by priority.
+    /// For a custom event, the matching wildcard handlers follow, sorted by priority
+    /// then registration order. A wildcard key itself only returns its own handlers.
+    pub fn get_handlers_for_key(&self, event_key: &EventKey) -> Vec<EventHandler> {
         let handlers = self.handlers.read().unwrap();
-        let key = EventKey::Custom(event_name.to_string()).to_string_key();
 
-        handlers
-            .get(&key)
+        let mut found = handlers
+            .get(&event_key.to_string_key())
             .cloned()
-            .unwrap_or_default()
+            .unwrap_or_default();
+
+        if let Some(event_name) = event_key.custom_name().filter(|_| !event_key.is_wildcard()) {
+            let exact = found.len();
+            for (key, event_handlers) in handlers.iter() {
+                let matches = key
+                    .strip_prefix(CUSTOM_KEY_PREFIX)
+                    .map(EventKey::custom)
+                    .is_some_and(|pattern| pattern.is_wildcard() && pattern.matches_custom(event_name));
+                if matches {
+                    found.extend(event_handlers.iter().cloned());
+                }
+            }
+            found[exact..].sort_by_key(|h| (h.priority, h.registration_seq));
+        }
+
+        found
+    }
+
+    /// Get handlers for a custom event by name
+    ///
+    /// Returns all handlers registered for the given custom event name,
+    /// sorted by priority, followed by the matching wildcard handlers.
+    pub fn get_handlers_for_custom_event(&self, event_name: &str) -> Vec<EventHandler> {
+        self.get_handlers_for_key(&EventKey::Custom(event_name.to_string()))
     }
 
     /// Get handlers for TerminalKeyPressed event
@@ -749,6 +1029,38 @@ impl EventDispatcher {
             .unwrap_or_default()
     }
 
+    /// Snapshot of the registered handlers, one entry per event with handlers, sorted by name
+    ///
+    /// The handler map is only read-locked while the summary is built, so dispatch is never blocked
+    /// on the caller.
+    pub fn describe(&self) -> Vec<EventHandlerInfo> {
+        let handlers = self.handlers.read().unwrap();
+        let mut infos: Vec<EventHandlerInfo> = handlers
+            .iter()
+            .filter(|(_, event_handlers)| !event_handlers.is_empty())
+            .map(|(key, event_handlers)| {
+                let (event, custom) = match key.strip_prefix(CUSTOM_KEY_PREFIX) {
+                    Some(name) => (name.to_string(), true),
+                    None => (key.clone(), false),
+                };
+                let mut mod_ids: Vec<String> = Vec::new();
+                for handler in event_handlers {
+                    if !mod_ids.contains(&handler.mod_id) {
+                        mod_ids.push(handler.mod_id.clone());
+                    }
+                }
+                EventHandlerInfo {
+                    event,
+                    custom,
+                    count: event_handlers.len(),
+                    mod_ids,
+                }
+            })
+            .collect();
+        infos.sort_by(|a, b| a.event.cmp(&b.event));
+        infos
+    }
+
     /// Get the number of registered handlers for a system event type
     pub fn handler_count(&self, event: SystemEvents) -> usize {
         let handlers = self.handlers.read().unwrap();
@@ -756,29 +1068,42 @@ impl EventDispatcher {
         handlers.get(&key).map(|h| h.len()).unwrap_or(0)
     }
 
-    /// Get the number of registered handlers for a custom event
+    /// Get the number of registered handlers for a custom event, wildcard handlers included
     pub fn custom_handler_count(&self, event_name: &str) -> usize {
-        let handlers = self.handlers.read().unwrap();
-        let key = EventKey::Custom(event_name.to_string()).to_string_key();
-        handlers.get(&key).map(|h| h.len()).unwrap_or(0)
+        self.get_handlers_for_custom_event(event_name).len()
     }
 
     /// Send a request to dispatch a custom event and wait for completion
     ///
-    /// This is called by the JS binding `system.sendEvent(event_name, ...args)`.
-    /// The request is sent to the main loop which will process it and respond.
+    /// This is called by the JS binding `system.sendEvent(event_name, ...args)` on behalf
+    /// of `sender_mod_id`. The request is sent to the main loop which will process it and respond.
     ///
     /// **IMPORTANT**: Handler response values must be set SYNCHRONOUSLY before any
     /// `await` points. Values set after an `await` will not be captured.
     ///
     /// # Returns
     /// A `CustomEventResponse` containing `handled` flag and custom properties
-    pub async fn request_send_event(&self, event_name: String, args: Vec<String>) -> Result<CustomEventResponse, String> {
+    pub async fn request_send_event(&self, sender_mod_id: &str, event_name: String, args: Vec<String>) -> Result<CustomEventResponse, String> {
+        self.send_event_request(event_name, args, None, Some(sender_mod_id.to_string())).await
+    }
+
+    /// Request to send an event to the handlers of a single mod
+    ///
+    /// Used by Rust-side APIs that report back to the mod that called them
+    /// (e.g. the `process:stdout` lines of `Process.spawnStreaming()`).
+    pub async fn request_send_event_to(&self, mod_id: &str, event_name: String, args: Vec<String>) -> Result<CustomEventResponse, String> {
+        self.send_event_request(event_name, args, Some(mod_id.to_string()), None).await
+    }
+
+    /// Send a SendEventRequest to the main loop and wait for its response
+    async fn send_event_request(&self, event_name: String, args: Vec<String>, target: Option<String>, sender: Option<String>) -> Result<CustomEventResponse, String> {
         let (response_tx, response_rx) = oneshot::channel();
 
         let request = SendEventRequest {
             event_name,
             args,
+            target,
+            sender,
             response_tx,
         };
 
@@ -832,6 +1157,12 @@ mod tests {
         assert_eq!(SystemEvents::from_u32(1), Some(SystemEvents::RequestUri));
         assert_eq!(SystemEvents::from_u32(99), None);
         assert_eq!(SystemEvents::RequestUri.to_u32(), 1);
+        assert_eq!(SystemEvents::from_u32(5), Some(SystemEvents::TerminalMouse));
+        assert_eq!(SystemEvents::from_u32(6), Some(SystemEvents::TerminalPaste));
+        assert_eq!(
+            SystemEvents::from_key(&SystemEvents::TerminalMouse.to_key()),
+            Some(SystemEvents::TerminalMouse)
+        );
     }
 
     #[test]
@@ -840,6 +1171,7 @@ mod tests {
         assert!(RequestUriProtocol::All.matches("http://localhost/test"));
 
         assert!(RequestUriProtocol::Stam.matches("stam://localhost/test"));
+        assert!(RequestUriProtocol::Stam.matches("stams://localhost/test"));
         assert!(!RequestUriProtocol::Stam.matches("http://localhost/test"));
 
         assert!(!RequestUriProtocol::Http.matches("stam://localhost/test"));
@@ -1005,4 +1337,140 @@ mod tests {
         let handlers = dispatcher.get_handlers_for_uri_request("stam://localhost/test");
         assert_eq!(handlers[0].mod_id, "mod-b");
     }
+
+    #[test]
+    fn test_handler_ids_for_mod() {
+        let dispatcher = EventDispatcher::new();
+
+        let a1 = dispatcher.register_custom_handler("ping", "mod-a", 0);
+        let b1 = dispatcher.register_custom_handler("ping", "mod-b", 0);
+        let a2 = dispatcher.register_handler(
+            SystemEvents::TerminalKeyPressed,
+            "mod-a",
+            10,
+            RequestUriProtocol::All,
+            "",
+        );
+
+        assert_eq!(dispatcher.handler_ids_for_mod("mod-a"), vec![a1, a2]);
+        assert_eq!(dispatcher.handler_ids_for_mod("mod-b"), vec![b1]);
+
+        dispatcher.unregister_handler(a1);
+        assert_eq!(dispatcher.handler_ids_for_mod("mod-a"), vec![a2]);
+        assert!(dispatcher.handler_ids_for_mod("mod-c").is_empty());
+    }
+
+    #[test]
+    fn test_describe_handlers() {
+        let dispatcher = EventDispatcher::new();
+        dispatcher.register_custom_handler("ping", "mod-b", 10);
+        dispatcher.register_custom_handler("ping", "mod-a", 0);
+        dispatcher.register_custom_handler("ping", "mod-b", 20);
+        let key_handler = dispatcher.register_handler(
+            SystemEvents::TerminalKeyPressed,
+            "mod-a",
+            0,
+            RequestUriProtocol::All,
+            "",
+        );
+        dispatcher.register_custom_handler("pong", "mod-c", 0);
+        dispatcher.unregister_mod_handlers("mod-c");
+
+        assert_eq!(
+            dispatcher.describe(),
+            vec![
+                EventHandlerInfo {
+                    event: "ping".to_string(),
+                    custom: true,
+                    count: 3,
+                    mod_ids: vec!["mod-a".to_string(), "mod-b".to_string()],
+                },
+                EventHandlerInfo {
+                    event: "system:TerminalKeyPressed".to_string(),
+                    custom: false,
+                    count: 1,
+                    mod_ids: vec!["mod-a".to_string()],
+                },
+            ]
+        );
+
+        dispatcher.unregister_handler(key_handler);
+        assert_eq!(dispatcher.describe().len(), 1);
+    }
+
+    #[test]
+    fn test_custom_event_target() {
+        let broadcast = CustomEventRequest::new("ping", Vec::new());
+        assert!(broadcast.is_addressed_to("mod-a"));
+        assert!(broadcast.is_addressed_to("mod-b"));
+
+        let direct = CustomEventRequest::new("ping", Vec::new()).with_target("mod-a");
+        assert_eq!(direct.target.as_deref(), Some("mod-a"));
+        assert!(direct.is_addressed_to("mod-a"));
+        assert!(!direct.is_addressed_to("mod-b"));
+    }
+
+    #[test]
+    fn test_wildcard_handlers() {
+        let dispatcher = EventDispatcher::new();
+        dispatcher.register_custom_handler("ui:*", "logger", 10);
+        dispatcher.register_custom_handler("*", "telemetry", 0);
+        dispatcher.register_custom_handler("ui:click", "button", 50);
+        dispatcher.register_custom_handler("uix:click", "other", 0);
+
+        assert!(EventKey::custom("ui:*").matches_custom("ui:click"));
+        assert!(!EventKey::custom("ui:*").matches_custom("uix:click"));
+        assert!(!EventKey::system(SystemEvents::TerminalKeyPressed).is_wildcard());
+
+        // Exact handlers first, then the wildcard ones by priority
+        let mod_ids = |event: &str| -> Vec<String> {
+            dispatcher
+                .get_handlers_for_custom_event(event)
+                .into_iter()
+                .map(|h| h.mod_id)
+                .collect()
+        };
+        assert_eq!(mod_ids("ui:click"), vec!["button", "telemetry", "logger"]);
+        assert_eq!(mod_ids("uix:click"), vec!["other", "telemetry"]);
+        assert_eq!(dispatcher.custom_handler_count("ui:open"), 2);
+        // Sending the pattern itself doesn't expand it
+        assert_eq!(mod_ids("ui:*"), vec!["logger"]);
+
+        // A wildcard handler doesn't receive the events sent by its own mod
+        let handlers: Vec<HandlerRef> = dispatcher
+            .get_handlers_for_custom_event("ui:click")
+            .iter()
+            .map(HandlerRef::from)
+            .collect();
+        let request = CustomEventRequest::new("ui:click", Vec::new()).with_sender("telemetry");
+        let accepted: Vec<&str> = handlers
+            .iter()
+            .filter(|h| request.accepts(h))
+            .map(|h| h.mod_id.as_str())
+            .collect();
+        assert_eq!(accepted, vec!["button", "logger"]);
+
+        let request = CustomEventRequest::new("ui:click", Vec::new()).with_sender("button");
+        assert!(handlers.iter().all(|h| request.accepts(h)));
+    }
+
+    #[test]
+    fn test_registration_seq_spans_dispatchers() {
+        let first = EventDispatcher::new();
+        let second = EventDispatcher::new();
+
+        first.register_custom_handler("ping", "mod-a", 0);
+        second.register_custom_handler("ping", "mod-b", 0);
+        first.register_custom_handler("ping", "mod-c", 0);
+
+        let key = EventKey::Custom("ping".to_string());
+        let a = &first.get_handlers_for_key(&key);
+        let b = &second.get_handlers_for_key(&key);
+
+        // Both dispatchers hand out ID 1 first, but the sequence keeps registration order
+        assert_eq!(a[0].handler_id, b[0].handler_id);
+        assert!(a[0].registration_seq < b[0].registration_seq);
+        assert!(b[0].registration_seq < a[1].registration_seq);
+        assert_eq!(HandlerRef::from(&a[1]).mod_id, "mod-c");
+    }
 }
@@ -8,7 +8,7 @@ use std::path::PathBuf;
 use std::fs;
 use std::io::Read;
 
-use super::path_security::validate_path_for_creation;
+use super::path_security::PathConfinement;
 
 /// File API implementation
 ///
@@ -61,74 +61,12 @@ impl FileApi {
     /// * `Ok(PathBuf)` - The validated absolute path
     /// * `Err(String)` - Error message if validation failed
     pub fn validate_path(&self, path: &str) -> Result<PathBuf, String> {
-        let path = PathBuf::from(path);
-
-        // If path is absolute, check if it's within one of the permitted directories
-        if path.is_absolute() {
-            // Try to validate against data_dir
-            if !self.data_dir.as_os_str().is_empty() {
-                if let Ok(canonical_data) = self.data_dir.canonicalize() {
-                    if let Ok(canonical_path) = path.canonicalize() {
-                        if canonical_path.starts_with(&canonical_data) {
-                            return Ok(canonical_path);
-                        }
-                    }
-                    // Path doesn't exist yet, check if parent is within data_dir
-                    if let Some(parent) = path.parent() {
-                        if let Ok(canonical_parent) = parent.canonicalize() {
-                            if canonical_parent.starts_with(&canonical_data) {
-                                return Ok(path);
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Try to validate against config_dir
-            if !self.config_dir.as_os_str().is_empty() {
-                if let Ok(canonical_config) = self.config_dir.canonicalize() {
-                    if let Ok(canonical_path) = path.canonicalize() {
-                        if canonical_path.starts_with(&canonical_config) {
-                            return Ok(canonical_path);
-                        }
-                    }
-                    // Path doesn't exist yet, check if parent is within config_dir
-                    if let Some(parent) = path.parent() {
-                        if let Ok(canonical_parent) = parent.canonicalize() {
-                            if canonical_parent.starts_with(&canonical_config) {
-                                return Ok(path);
-                            }
-                        }
-                    }
-                }
-            }
-
-            return Err(format!(
-                "Access denied: absolute path '{}' is not within permitted directories (data_dir or config_dir)",
-                path.display()
-            ));
-        }
-
-        // For relative paths, try data_dir first, then config_dir
-        // First try data_dir
-        if !self.data_dir.as_os_str().is_empty() {
-            if let Ok(validated) = validate_path_for_creation(&path, &self.data_dir) {
-                return Ok(validated);
-            }
-        }
-
-        // Then try config_dir
-        if !self.config_dir.as_os_str().is_empty() {
-            if let Ok(validated) = validate_path_for_creation(&path, &self.config_dir) {
-                return Ok(validated);
-            }
-        }
+        self.confinement().confine(path)
+    }
 
-        Err(format!(
-            "Access denied: path '{}' could not be validated against permitted directories. \
-             Path traversal (../) that escapes the permitted directories is not allowed.",
-            path.display()
-        ))
+    /// Get the filesystem confinement for this API (data_dir first, then config_dir)
+    pub fn confinement(&self) -> PathConfinement {
+        PathConfinement::new(&self.data_dir).with_root(&self.config_dir)
     }
 
     /// Read a JSON file and return its contents as a JSON string
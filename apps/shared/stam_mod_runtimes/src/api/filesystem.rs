@@ -0,0 +1,201 @@
+//! FileSystem API abstraction
+//!
+//! Read-only access to the files a mod ships in its own installed directory
+//! (maps, tables, data files), without going through the download pipeline.
+//! Every path is confined to the mod root: `..` components and symlinks that
+//! lead outside of it are rejected.
+//! This module is runtime-agnostic and can be used by JavaScript, Lua, C#, etc.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::path_security::PathConfinement;
+
+/// Name of the manifest file marking a mod's directory
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// An entry returned by `FileSystemApi::read_dir`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirEntryInfo {
+    /// File or directory name (not a full path)
+    pub name: String,
+    /// Whether the entry is a directory
+    pub is_dir: bool,
+}
+
+/// FileSystem API implementation, scoped to a single mod's directory
+#[derive(Clone, Debug)]
+pub struct FileSystemApi {
+    /// The mod's installed directory (the one holding its manifest)
+    mod_root: PathBuf,
+}
+
+impl FileSystemApi {
+    /// Create a FileSystemApi confined to `mod_root`
+    pub fn new(mod_root: impl Into<PathBuf>) -> Self {
+        Self {
+            mod_root: mod_root.into(),
+        }
+    }
+
+    /// Create a FileSystemApi confined to the directory of the mod owning `entry_point`
+    ///
+    /// Entry points are resolved against the directory holding the manifest
+    /// (`<mods>/<mod-id>/client/`, `server/` or the mod directory itself) and may sit
+    /// in a subdirectory of it, so the root is the nearest ancestor of the entry point
+    /// with a manifest, without going above `<mods>/<mod-id>`. Without any manifest
+    /// the entry point's own directory is used.
+    pub fn for_entry_point(entry_point: &Path, mod_id: &str) -> Self {
+        let fallback = entry_point.parent().unwrap_or(Path::new(".")).to_path_buf();
+        for dir in entry_point.ancestors().skip(1) {
+            if dir.join(MANIFEST_FILE).is_file() {
+                return Self::new(dir);
+            }
+            if dir.file_name().is_some_and(|name| name == mod_id) {
+                break;
+            }
+        }
+        Self::new(fallback)
+    }
+
+    /// Get the mod root directory
+    pub fn mod_root(&self) -> &Path {
+        &self.mod_root
+    }
+
+    /// Read a UTF-8 text file relative to the mod root
+    pub fn read_file(&self, path: &str) -> Result<String, String> {
+        let resolved = self.resolve(path)?;
+        if !resolved.is_file() {
+            return Err(format!("File '{}' not found in mod directory", path));
+        }
+        fs::read_to_string(&resolved).map_err(|e| format!("Failed to read file '{}': {}", path, e))
+    }
+
+    /// List a directory relative to the mod root (sorted by name)
+    ///
+    /// An empty path lists the mod root itself.
+    pub fn read_dir(&self, path: &str) -> Result<Vec<DirEntryInfo>, String> {
+        let resolved = self.resolve(path)?;
+        if !resolved.is_dir() {
+            return Err(format!("Directory '{}' not found in mod directory", path));
+        }
+
+        let read_error = |e: std::io::Error| format!("Failed to read directory '{}': {}", path, e);
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&resolved).map_err(read_error)? {
+            let entry = entry.map_err(read_error)?;
+            entries.push(DirEntryInfo {
+                name: entry.file_name().to_string_lossy().to_string(),
+                is_dir: entry.file_type().map_err(read_error)?.is_dir(),
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Check whether a file or directory exists relative to the mod root
+    ///
+    /// Paths escaping the mod root are an error rather than `false`, so a
+    /// mistake is reported instead of silently ignored.
+    pub fn exists(&self, path: &str) -> Result<bool, String> {
+        Ok(self.resolve(path)?.exists())
+    }
+
+    /// Resolve a mod-relative path, rejecting anything outside the mod root
+    fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        PathConfinement::new(&self.mod_root)
+            .confine(path)
+            .map_err(|_| format!("Access denied: path '{}' is outside the mod directory", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Create `{temp}/mods/my-mod` with a data file and a `{temp}/secret.txt` next to it
+    fn setup_mod(temp: &Path) -> FileSystemApi {
+        let mod_root = temp.join("mods").join("my-mod");
+        fs::create_dir_all(mod_root.join("data")).unwrap();
+        fs::write(mod_root.join("data").join("map.json"), r#"{"width": 8}"#).unwrap();
+        fs::write(mod_root.join("index.js"), "").unwrap();
+        fs::write(temp.join("secret.txt"), "secret").unwrap();
+        FileSystemApi::new(mod_root)
+    }
+
+    #[test]
+    fn test_reads_files_inside_mod_root() {
+        let temp = tempdir().unwrap();
+        let api = setup_mod(temp.path());
+
+        assert_eq!(api.read_file("data/map.json").unwrap(), r#"{"width": 8}"#);
+        assert!(api.exists("data/map.json").unwrap());
+        assert!(!api.exists("data/missing.json").unwrap());
+        assert!(api.read_file("data/missing.json").is_err());
+
+        let root = api.read_dir("").unwrap();
+        assert_eq!(
+            root,
+            vec![
+                DirEntryInfo { name: "data".to_string(), is_dir: true },
+                DirEntryInfo { name: "index.js".to_string(), is_dir: false },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_traversal_blocked() {
+        let temp = tempdir().unwrap();
+        let api = setup_mod(temp.path());
+
+        for path in ["../../secret.txt", "data/../../../secret.txt", ".."] {
+            let err = api.read_file(path).unwrap_err();
+            assert!(err.contains("outside the mod directory"), "{}: {}", path, err);
+            assert!(api.exists(path).is_err());
+            assert!(api.read_dir(path).is_err());
+        }
+
+        let absolute = temp.path().join("secret.txt");
+        assert!(api.read_file(&absolute.to_string_lossy()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_escape_blocked() {
+        let temp = tempdir().unwrap();
+        let api = setup_mod(temp.path());
+
+        std::os::unix::fs::symlink(temp.path().join("secret.txt"), api.mod_root().join("link.txt")).unwrap();
+        std::os::unix::fs::symlink(temp.path(), api.mod_root().join("up")).unwrap();
+
+        assert!(api.read_file("link.txt").is_err());
+        assert!(api.read_file("up/secret.txt").is_err());
+        assert!(api.read_dir("up").is_err());
+        // `..` through a missing directory must not bypass the symlink check
+        assert!(api.read_file("data/x/../../up/secret.txt").is_err());
+        assert!(api.exists("data/x/../../up/secret.txt").is_err());
+        assert!(api.read_dir("data/x/../../up").is_err());
+    }
+
+    #[test]
+    fn test_root_is_the_manifest_directory() {
+        let temp = tempdir().unwrap();
+        let mod_dir = temp.path().join("mods").join("my-mod");
+        let client_dir = mod_dir.join("client");
+        fs::create_dir_all(client_dir.join("src")).unwrap();
+        fs::write(client_dir.join(MANIFEST_FILE), "{}").unwrap();
+        fs::write(client_dir.join("map.json"), "{}").unwrap();
+        // A manifest above the mod directory is never picked up
+        fs::write(temp.path().join(MANIFEST_FILE), "{}").unwrap();
+
+        let api = FileSystemApi::for_entry_point(&client_dir.join("src").join("index.js"), "my-mod");
+        assert_eq!(api.mod_root(), client_dir);
+        assert!(api.exists("map.json").unwrap());
+
+        let other = temp.path().join("mods").join("bare");
+        let api = FileSystemApi::for_entry_point(&other.join("src").join("index.js"), "bare");
+        assert_eq!(api.mod_root(), other.join("src"));
+    }
+}
@@ -3,7 +3,7 @@
 //! Commands sent from the GraphicProxy (worker thread) to the graphic engine (main thread).
 
 use super::ecs::{ComponentSchema, DeclaredSystem, QueryOptions, QueryResult};
-use super::{GraphicEngineInfo, WindowConfig, WindowMode};
+use super::{GraphicEngineInfo, WindowConfig, WindowCursor, WindowMode};
 use crate::api::resource::{ResourceInfo, ResourceType};
 use std::collections::HashMap;
 use tokio::sync::oneshot;
@@ -78,6 +78,36 @@ pub enum GraphicCommand {
         response_tx: oneshot::Sender<Result<(), String>>,
     },
 
+    /// Set the window icon from a loaded image resource
+    SetWindowIcon {
+        /// Window ID
+        id: u64,
+        /// Image resource alias (must be loaded via Resource.load())
+        resource_id: String,
+        /// Channel to send the result back
+        response_tx: oneshot::Sender<Result<(), String>>,
+    },
+
+    /// Show or hide the OS cursor while it is over the window
+    SetCursorVisible {
+        /// Window ID
+        id: u64,
+        /// Show the cursor
+        visible: bool,
+        /// Channel to send the result back
+        response_tx: oneshot::Sender<Result<(), String>>,
+    },
+
+    /// Set the default cursor shape for the window
+    SetCursorIcon {
+        /// Window ID
+        id: u64,
+        /// Cursor shape
+        icon: WindowCursor,
+        /// Channel to send the result back
+        response_tx: oneshot::Sender<Result<(), String>>,
+    },
+
     /// Set the default font for a window
     ///
     /// All widgets in this window will inherit this font configuration
@@ -197,6 +227,37 @@ pub enum GraphicCommand {
         response_tx: oneshot::Sender<Result<(), String>>,
     },
 
+    // ========================================================================
+    // Audio Commands
+    // ========================================================================
+
+    /// Start playing a loaded audio resource
+    ///
+    /// The engine should:
+    /// 1. Look up the audio handle by resource alias in ResourceRegistry
+    /// 2. Spawn a playback for it with the given volume and loop mode
+    /// 3. Track the playback in its AudioRegistry and return its handle
+    PlayAudio {
+        /// Alias of an audio resource loaded with Resource.load()
+        resource_id: String,
+        /// Linear volume (1.0 = original volume)
+        volume: f32,
+        /// Restart from the beginning when playback ends
+        looping: bool,
+        /// Channel to send the result back (returns the playback handle)
+        response_tx: oneshot::Sender<Result<u64, String>>,
+    },
+
+    /// Stop a playback started with PlayAudio
+    ///
+    /// Stopping a playback that already ended is not an error.
+    StopAudio {
+        /// Playback handle returned by PlayAudio
+        handle: u64,
+        /// Channel to send the result back
+        response_tx: oneshot::Sender<Result<(), String>>,
+    },
+
     // ========================================================================
     // Screen/Monitor Commands
     // ========================================================================
@@ -390,6 +451,16 @@ pub enum GraphicCommand {
         /// Channel to send the result back
         response_tx: oneshot::Sender<Result<(), String>>,
     },
+
+    // ========== Batching ==========
+    /// Several commands sent as one channel message
+    ///
+    /// The engine must process them in order, in the same frame, before the next
+    /// command from the channel. Each command answers on its own response channel.
+    Batch {
+        /// Commands to process, in order
+        commands: Vec<GraphicCommand>,
+    },
 }
 
 impl std::fmt::Debug for GraphicCommand {
@@ -426,6 +497,21 @@ impl std::fmt::Debug for GraphicCommand {
                 .field("id", id)
                 .field("visible", visible)
                 .finish(),
+            Self::SetWindowIcon { id, resource_id, .. } => f
+                .debug_struct("SetWindowIcon")
+                .field("id", id)
+                .field("resource_id", resource_id)
+                .finish(),
+            Self::SetCursorVisible { id, visible, .. } => f
+                .debug_struct("SetCursorVisible")
+                .field("id", id)
+                .field("visible", visible)
+                .finish(),
+            Self::SetCursorIcon { id, icon, .. } => f
+                .debug_struct("SetCursorIcon")
+                .field("id", id)
+                .field("icon", icon)
+                .finish(),
             Self::SetWindowFont { id, family, size, .. } => f
                 .debug_struct("SetWindowFont")
                 .field("id", id)
@@ -467,6 +553,21 @@ impl std::fmt::Debug for GraphicCommand {
                 .field("asset_id", asset_id)
                 .finish(),
             Self::UnloadAllResources { .. } => f.debug_struct("UnloadAllResources").finish(),
+            // Audio commands
+            Self::PlayAudio {
+                resource_id,
+                volume,
+                looping,
+                ..
+            } => f
+                .debug_struct("PlayAudio")
+                .field("resource_id", resource_id)
+                .field("volume", volume)
+                .field("looping", looping)
+                .finish(),
+            Self::StopAudio { handle, .. } => {
+                f.debug_struct("StopAudio").field("handle", handle).finish()
+            }
             // Screen commands
             Self::GetPrimaryScreen { .. } => f.debug_struct("GetPrimaryScreen").finish(),
             Self::GetScreenResolution { screen_id, .. } => f
@@ -569,6 +670,10 @@ impl std::fmt::Debug for GraphicCommand {
                 .field("entity_id", entity_id)
                 .field("event_type", event_type)
                 .finish(),
+            Self::Batch { commands } => f
+                .debug_struct("Batch")
+                .field("commands", commands)
+                .finish(),
         }
     }
 }
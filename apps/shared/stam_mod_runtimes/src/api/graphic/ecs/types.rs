@@ -373,6 +373,262 @@ impl DeclaredSystem {
     }
 }
 
+/// Size of one tile of a sprite sheet, in pixels
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TileSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Configuration of the native `AnimatedSprite` component
+///
+/// The atlas image is split into a `columns` x `rows` grid of `tile_size` tiles,
+/// indexed left to right, top to bottom. `frames` lists the tile indices to play
+/// in order; when omitted every tile is played.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AnimatedSpriteConfig {
+    /// Alias of the sprite sheet image loaded with `Resource.load()`
+    pub atlas_resource_id: String,
+    /// Size of a single tile
+    pub tile_size: TileSize,
+    /// Number of tile columns in the sheet
+    #[serde(default = "default_grid_dimension")]
+    pub columns: u32,
+    /// Number of tile rows in the sheet
+    #[serde(default = "default_grid_dimension")]
+    pub rows: u32,
+    /// Tile indices to play, in order
+    #[serde(default)]
+    pub frames: Vec<usize>,
+    /// Frames per second
+    #[serde(default = "default_fps")]
+    pub fps: f32,
+    /// Restart from the first frame after the last one
+    #[serde(default = "default_looping")]
+    pub looping: bool,
+}
+
+fn default_grid_dimension() -> u32 {
+    1
+}
+
+fn default_fps() -> f32 {
+    10.0
+}
+
+fn default_looping() -> bool {
+    true
+}
+
+impl AnimatedSpriteConfig {
+    /// Parse and validate the component data sent by a script
+    ///
+    /// An empty `frames` list is replaced with every tile of the grid.
+    pub fn from_json(data: &serde_json::Value) -> Result<Self, String> {
+        let mut config: Self = serde_json::from_value(data.clone())
+            .map_err(|e| format!("Invalid AnimatedSprite: {}", e))?;
+
+        if config.tile_size.width == 0 || config.tile_size.height == 0 {
+            return Err("AnimatedSprite 'tile_size' must be greater than zero".to_string());
+        }
+        if config.columns == 0 || config.rows == 0 {
+            return Err("AnimatedSprite 'columns' and 'rows' must be at least 1".to_string());
+        }
+        if !config.fps.is_finite() || config.fps <= 0.0 {
+            return Err(format!(
+                "AnimatedSprite 'fps' must be a positive number, got {}",
+                config.fps
+            ));
+        }
+
+        let tile_count = config.tile_count();
+        if config.frames.is_empty() {
+            config.frames = (0..tile_count).collect();
+        } else if let Some(frame) = config.frames.iter().find(|f| **f >= tile_count) {
+            return Err(format!(
+                "AnimatedSprite frame {} is out of range (the sheet has {} tiles)",
+                frame, tile_count
+            ));
+        }
+
+        Ok(config)
+    }
+
+    /// Number of tiles in the sheet grid
+    pub fn tile_count(&self) -> usize {
+        self.columns as usize * self.rows as usize
+    }
+
+    /// Time each frame stays on screen, in seconds
+    pub fn frame_duration(&self) -> f32 {
+        1.0 / self.fps
+    }
+}
+
+/// Playback position of an `AnimatedSprite`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpriteAnimationState {
+    /// Position in the `frames` list
+    pub frame: usize,
+    /// Time accumulated on the current frame, in seconds
+    pub elapsed: f32,
+    /// Set once a non-looping animation reached its last frame
+    pub finished: bool,
+}
+
+impl SpriteAnimationState {
+    /// Atlas tile index of the current frame
+    pub fn atlas_index(&self, config: &AnimatedSpriteConfig) -> usize {
+        config.frames.get(self.frame).copied().unwrap_or(0)
+    }
+
+    /// Advance the animation by `delta` seconds
+    ///
+    /// Returns `true` on the call where a non-looping animation finishes.
+    pub fn advance(&mut self, config: &AnimatedSpriteConfig, delta: f32) -> bool {
+        if self.finished || config.frames.is_empty() {
+            return false;
+        }
+
+        let frame_duration = config.frame_duration();
+        self.elapsed += delta;
+        while self.elapsed >= frame_duration {
+            self.elapsed -= frame_duration;
+            if self.frame + 1 < config.frames.len() {
+                self.frame += 1;
+            } else if config.looping {
+                self.frame = 0;
+            } else {
+                self.elapsed = 0.0;
+                self.finished = true;
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Editable single-line text of the native `TextInput` component
+///
+/// The caret is a position in characters (not bytes), from 0 to the value length.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextInputState {
+    value: String,
+    caret: usize,
+    max_length: Option<usize>,
+}
+
+impl TextInputState {
+    /// Create the state with the caret at the end of `value`
+    ///
+    /// `value` is truncated to `max_length` characters.
+    pub fn new(value: &str, max_length: Option<usize>) -> Self {
+        let mut state = Self {
+            value: String::new(),
+            caret: 0,
+            max_length,
+        };
+        state.set_value(value);
+        state
+    }
+
+    /// Current text
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Caret position, in characters
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Maximum number of characters, if limited
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Replace the text, moving the caret to the end
+    pub fn set_value(&mut self, value: &str) {
+        let limit = self.max_length.unwrap_or(usize::MAX);
+        self.value = value.chars().filter(|c| !c.is_control()).take(limit).collect();
+        self.caret = self.len();
+    }
+
+    /// Insert typed text at the caret, returning whether the value changed
+    ///
+    /// Control characters are dropped and the text is cut at `max_length`.
+    pub fn insert(&mut self, text: &str) -> bool {
+        let room = self.max_length.map_or(usize::MAX, |max| max.saturating_sub(self.len()));
+        let typed: String = text.chars().filter(|c| !c.is_control()).take(room).collect();
+        if typed.is_empty() {
+            return false;
+        }
+        let at = self.byte_index(self.caret);
+        self.value.insert_str(at, &typed);
+        self.caret += typed.chars().count();
+        true
+    }
+
+    /// Delete the character before the caret, returning whether the value changed
+    pub fn backspace(&mut self) -> bool {
+        if self.caret == 0 {
+            return false;
+        }
+        self.caret -= 1;
+        self.value.remove(self.byte_index(self.caret));
+        true
+    }
+
+    /// Delete the character after the caret, returning whether the value changed
+    pub fn delete(&mut self) -> bool {
+        if self.caret >= self.len() {
+            return false;
+        }
+        self.value.remove(self.byte_index(self.caret));
+        true
+    }
+
+    /// Move the caret one character to the left
+    pub fn move_left(&mut self) {
+        self.caret = self.caret.saturating_sub(1);
+    }
+
+    /// Move the caret one character to the right
+    pub fn move_right(&mut self) {
+        self.caret = (self.caret + 1).min(self.len());
+    }
+
+    /// Move the caret to the start of the text
+    pub fn move_home(&mut self) {
+        self.caret = 0;
+    }
+
+    /// Move the caret to the end of the text
+    pub fn move_end(&mut self) {
+        self.caret = self.len();
+    }
+
+    /// Text to render, with `caret` drawn at the caret position when given
+    pub fn display(&self, caret: Option<char>) -> String {
+        let mut text = self.value.clone();
+        if let Some(caret) = caret {
+            text.insert(self.byte_index(self.caret), caret);
+        }
+        text
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,4 +695,102 @@ mod tests {
         assert_eq!(opts.without_components, vec!["Frozen"]);
         assert_eq!(opts.limit, Some(10));
     }
+
+    #[test]
+    fn test_animated_sprite_config_parsing() {
+        let config = AnimatedSpriteConfig::from_json(&json!({
+            "atlas_resource_id": "hero-run",
+            "tile_size": {"width": 32, "height": 32},
+            "columns": 4,
+            "rows": 2
+        }))
+        .unwrap();
+        assert_eq!(config.frames, (0..8).collect::<Vec<_>>());
+        assert_eq!(config.fps, 10.0);
+        assert!(config.looping);
+
+        let invalid = [
+            json!({"tile_size": {"width": 32, "height": 32}}),
+            json!({"atlas_resource_id": "a", "tile_size": {"width": 0, "height": 32}}),
+            json!({"atlas_resource_id": "a", "tile_size": {"width": 32, "height": 32}, "fps": 0}),
+            json!({"atlas_resource_id": "a", "tile_size": {"width": 32, "height": 32}, "columns": 2, "frames": [0, 2]}),
+        ];
+        for data in invalid {
+            assert!(AnimatedSpriteConfig::from_json(&data).is_err(), "{}", data);
+        }
+    }
+
+    #[test]
+    fn test_sprite_animation_advance() {
+        let config = AnimatedSpriteConfig::from_json(&json!({
+            "atlas_resource_id": "explosion",
+            "tile_size": {"width": 16, "height": 16},
+            "columns": 8,
+            "frames": [5, 6, 7],
+            "fps": 4,
+            "looping": false
+        }))
+        .unwrap();
+
+        let mut state = SpriteAnimationState::default();
+        assert_eq!(state.atlas_index(&config), 5);
+        assert!(!state.advance(&config, 0.1));
+        assert_eq!(state.atlas_index(&config), 5);
+        assert!(!state.advance(&config, 0.4));
+        assert_eq!(state.atlas_index(&config), 7);
+
+        // Completion is reported exactly once and the last frame stays visible
+        assert!(state.advance(&config, 0.25));
+        assert!(state.finished);
+        assert!(!state.advance(&config, 1.0));
+        assert_eq!(state.atlas_index(&config), 7);
+
+        // Looping animations wrap around instead of finishing
+        let looping = AnimatedSpriteConfig { looping: true, ..config };
+        let mut state = SpriteAnimationState::default();
+        assert!(!state.advance(&looping, 0.8));
+        assert_eq!(state.atlas_index(&looping), 5);
+        assert!(!state.finished);
+    }
+
+    #[test]
+    fn test_text_input_editing() {
+        let mut input = TextInputState::new("héllo", None);
+        assert_eq!(input.caret(), 5);
+
+        input.move_left();
+        input.move_left();
+        assert!(input.insert("X"));
+        assert_eq!(input.value(), "hélXlo");
+        assert_eq!(input.display(Some('|')), "hélX|lo");
+
+        assert!(input.backspace());
+        assert!(input.backspace());
+        assert_eq!(input.value(), "hélo");
+        assert!(input.delete());
+        assert_eq!(input.value(), "héo");
+
+        input.move_home();
+        assert!(!input.backspace());
+        input.move_end();
+        assert!(!input.delete());
+        input.move_right();
+        assert_eq!(input.caret(), 3);
+
+        // Control characters (e.g. the '\r' of Enter) are not inserted
+        assert!(!input.insert("\r"));
+        assert_eq!(input.value(), "héo");
+    }
+
+    #[test]
+    fn test_text_input_max_length() {
+        let mut input = TextInputState::new("abcdef", Some(4));
+        assert_eq!(input.value(), "abcd");
+
+        assert!(!input.insert("e"));
+        assert!(input.backspace());
+        assert!(input.insert("xyz"));
+        assert_eq!(input.value(), "abcx");
+        assert_eq!(input.caret(), 4);
+    }
 }
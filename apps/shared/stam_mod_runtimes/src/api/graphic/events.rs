@@ -156,6 +156,17 @@ pub enum GraphicEvent {
     EngineShuttingDown,
 
     // ========== Resource Events ==========
+    /// Resource has started loading in the engine
+    ///
+    /// Sent once per load, before `ResourceLoaded`/`ResourceFailed`, so a loading
+    /// UI can show a spinner right away. The engine doesn't report per-byte progress.
+    ResourceLoading {
+        /// The resource alias
+        alias: String,
+        /// The asset ID
+        asset_id: u64,
+    },
+
     /// Resource has been loaded by the engine
     ///
     /// This event is sent when an asset has finished loading in the graphic engine
@@ -204,6 +215,8 @@ pub enum GraphicEvent {
     /// - "hover": on_hover callback (triggered on hover state change)
     /// - "enter": on_enter callback (triggered when cursor enters)
     /// - "leave": on_leave callback (triggered when cursor leaves)
+    /// - "animation:complete": AnimatedSprite on_complete callback
+    /// - "input:changed" / "input:submit": TextInput on_change / on_submit callbacks
     EntityEventCallback {
         /// The entity script ID
         entity_id: u64,
@@ -213,6 +226,8 @@ pub enum GraphicEvent {
         x: f32,
         /// Cursor Y position
         y: f32,
+        /// Current value of the component, for TextInput events
+        value: Option<String>,
     },
 }
 
@@ -238,6 +253,7 @@ impl GraphicEvent {
             Self::EngineError { .. } => "graphic:engine:error",
             Self::EngineShuttingDown => "graphic:engine:shuttingDown",
             // Resource events
+            Self::ResourceLoading { .. } => "graphic:resource:loading",
             Self::ResourceLoaded { .. } => "graphic:resource:loaded",
             Self::ResourceFailed { .. } => "graphic:resource:failed",
             // Entity events
@@ -358,6 +374,12 @@ impl GraphicEvent {
             }
             Self::EngineShuttingDown => vec![],
             // Resource events
+            Self::ResourceLoading { alias, asset_id } => {
+                vec![
+                    format!("\"{}\"", alias),
+                    asset_id.to_string(),
+                ]
+            }
             Self::ResourceLoaded { alias, asset_id } => {
                 vec![
                     format!("\"{}\"", alias),
@@ -380,12 +402,13 @@ impl GraphicEvent {
                     y.to_string(),
                 ]
             }
-            Self::EntityEventCallback { entity_id, event_type, x, y } => {
+            Self::EntityEventCallback { entity_id, event_type, x, y, value } => {
                 vec![
                     entity_id.to_string(),
                     format!("\"{}\"", event_type),
                     x.to_string(),
                     y.to_string(),
+                    serde_json::to_string(value).unwrap_or_else(|_| "null".to_string()),
                 ]
             }
         }
@@ -0,0 +1,123 @@
+//! Frame timing statistics
+//!
+//! The engine sends a `FrameStart` event with the frame's delta time every frame.
+//! Forwarding each of them to mods would flood the scripting event loop, so the
+//! deltas are accumulated here and reported as a single `graphic:frame:stats`
+//! event per reporting interval.
+
+/// Custom event name mods register to receive frame statistics
+pub const FRAME_STATS_EVENT: &str = "graphic:frame:stats";
+
+/// Default number of `graphic:frame:stats` reports per second
+pub const DEFAULT_FRAME_STATS_RATE_HZ: f32 = 4.0;
+
+/// Frame timing averaged over one reporting interval
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameStats {
+    /// Average frame duration in milliseconds
+    pub delta_ms: f32,
+    /// Average frames per second
+    pub fps: f32,
+}
+
+impl FrameStats {
+    /// Event argument passed to mods: `{ deltaMs, fps }`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "deltaMs": self.delta_ms,
+            "fps": self.fps,
+        })
+    }
+}
+
+/// Accumulates frame deltas and yields a `FrameStats` once per reporting interval
+#[derive(Debug)]
+pub struct FrameStatsTracker {
+    /// Reports per second (0 disables reporting)
+    rate_hz: f32,
+    /// Frames recorded since the last report
+    frames: u32,
+    /// Seconds elapsed since the last report
+    elapsed: f32,
+}
+
+impl Default for FrameStatsTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FRAME_STATS_RATE_HZ)
+    }
+}
+
+impl FrameStatsTracker {
+    /// Create a tracker reporting `rate_hz` times per second
+    pub fn new(rate_hz: f32) -> Self {
+        Self {
+            rate_hz,
+            frames: 0,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Reports per second (0 when disabled)
+    pub fn rate_hz(&self) -> f32 {
+        self.rate_hz
+    }
+
+    /// Change the reporting rate, restarting the current interval
+    pub fn set_rate_hz(&mut self, rate_hz: f32) {
+        *self = Self::new(rate_hz);
+    }
+
+    /// Record a frame of `delta_time` seconds
+    ///
+    /// # Returns
+    /// The stats of the interval when it is complete, `None` otherwise
+    pub fn record(&mut self, delta_time: f32) -> Option<FrameStats> {
+        if self.rate_hz <= 0.0 {
+            return None;
+        }
+
+        self.frames += 1;
+        self.elapsed += delta_time;
+        if self.elapsed < 1.0 / self.rate_hz {
+            return None;
+        }
+
+        let stats = FrameStats {
+            delta_ms: self.elapsed * 1000.0 / self.frames as f32,
+            fps: if self.elapsed > 0.0 { self.frames as f32 / self.elapsed } else { 0.0 },
+        };
+        self.frames = 0;
+        self.elapsed = 0.0;
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_once_per_interval() {
+        let mut tracker = FrameStatsTracker::default();
+
+        // 60 FPS at 4 Hz: one report every 15 frames
+        let reports: Vec<FrameStats> = (0..60).filter_map(|_| tracker.record(1.0 / 60.0)).collect();
+        assert_eq!(reports.len(), 4);
+        for stats in reports {
+            assert!((stats.fps - 60.0).abs() < 0.5, "{:?}", stats);
+            assert!((stats.delta_ms - 16.67).abs() < 0.1, "{:?}", stats);
+        }
+    }
+
+    #[test]
+    fn test_disabled_and_rate_change() {
+        let mut tracker = FrameStatsTracker::new(0.0);
+        assert!((0..120).all(|_| tracker.record(1.0 / 60.0).is_none()));
+
+        tracker.set_rate_hz(1.0);
+        assert!((0..29).all(|_| tracker.record(1.0 / 30.0).is_none()));
+        let stats = tracker.record(1.0 / 30.0).unwrap();
+        assert!((stats.fps - 30.0).abs() < 0.5, "{:?}", stats);
+        assert_eq!(stats.to_json()["fps"], serde_json::json!(stats.fps));
+    }
+}
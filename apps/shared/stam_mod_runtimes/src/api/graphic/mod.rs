@@ -29,6 +29,7 @@ mod commands;
 pub mod ecs;
 mod engines;
 mod events;
+mod frame_stats;
 mod proxy;
 mod common_types;
 mod window;
@@ -36,10 +37,11 @@ mod window;
 pub use commands::GraphicCommand;
 pub use engines::{GraphicEngine, GraphicEngineInfo, GraphicEngines};
 pub use events::{GraphicEvent, KeyModifiers, MouseButton};
+pub use frame_stats::{DEFAULT_FRAME_STATS_RATE_HZ, FRAME_STATS_EVENT, FrameStats, FrameStatsTracker};
 pub use proxy::{EnableEngineRequest, GraphicProxy};
 pub use common_types::{
     AlignItems, BlendMode, ColorParseError, ColorValue, EdgeInsets, FlexDirection, FontConfig,
     FontInfo, FontStyle, FontWeight, ImageConfig, ImageScaleMode, ImageSource, JustifyContent, LayoutType,
     RectValue, ShadowConfig, SizeValue, TextAlign,
 };
-pub use window::{InitialWindowConfig, WindowConfig, WindowInfo, WindowMode, WindowPositionMode};
+pub use window::{InitialWindowConfig, WindowConfig, WindowCursor, WindowInfo, WindowMode, WindowPositionMode};
@@ -5,14 +5,14 @@
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, watch};
 
 use super::ecs::{ComponentSchema, DeclaredSystem, QueryOptions, QueryResult};
 use super::{
-    FontInfo, GraphicCommand, GraphicEngineInfo, GraphicEngines, GraphicEvent,
-    InitialWindowConfig, WindowConfig, WindowInfo, WindowMode,
+    FontInfo, FrameStats, FrameStatsTracker, GraphicCommand, GraphicEngineInfo, GraphicEngines, GraphicEvent,
+    InitialWindowConfig, WindowConfig, WindowCursor, WindowInfo, WindowMode,
 };
 use super::super::path_security::{PathSecurityConfig, validate_and_resolve_path};
 use super::super::resource::{ResourceInfo, ResourceType};
@@ -41,6 +41,31 @@ pub struct EnableEngineRequest {
     >,
 }
 
+/// Default time `GraphicProxy::enable_engine` waits for the engine to report `EngineReady`
+pub const DEFAULT_ENGINE_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Startup state of the graphic engine, used to resolve `enable_engine`
+#[derive(Clone, Debug, PartialEq)]
+enum EngineStartup {
+    /// No engine requested (or the last attempt was rolled back)
+    Idle,
+    /// Enablement requested, waiting for `EngineReady`
+    Starting,
+    /// The engine reported `EngineReady`
+    Ready,
+    /// The engine reported an error or did not become ready in time
+    Failed(String),
+}
+
+/// ECS commands queued while a batch is open (see `GraphicProxy::begin_batch`)
+#[derive(Default)]
+struct CommandBatch {
+    /// Nesting depth of `begin_batch` calls
+    depth: usize,
+    /// Queued commands, in call order
+    commands: Vec<GraphicCommand>,
+}
+
 /// Central proxy for graphic engine operations
 ///
 /// This struct is shared across all mod contexts and ALL scripting runtimes
@@ -88,6 +113,22 @@ pub struct GraphicProxy {
     /// Initially set to 1 (the primary window created at engine startup)
     /// Can be changed via setMainWindow() to promote a different window
     main_window_id: AtomicU64,
+
+    /// ECS commands waiting for the open batch to be flushed
+    batch: Mutex<CommandBatch>,
+
+    /// Engine startup state, updated from the events received by the main loop
+    startup: watch::Sender<EngineStartup>,
+
+    /// How long `enable_engine` waits for `EngineReady`
+    engine_ready_timeout: Duration,
+
+    /// Startup error not reported by the engine itself (timeout), waiting to be
+    /// dispatched to mods by the main loop
+    pending_engine_error: Mutex<Option<String>>,
+
+    /// Frame deltas accumulated for the next `graphic:frame:stats` report
+    frame_stats: Mutex<FrameStatsTracker>,
 }
 
 impl GraphicProxy {
@@ -111,6 +152,11 @@ impl GraphicProxy {
             available: true,
             asset_root,
             main_window_id: AtomicU64::new(1), // Primary window created at engine startup
+            batch: Mutex::new(CommandBatch::default()),
+            startup: watch::channel(EngineStartup::Idle).0,
+            engine_ready_timeout: DEFAULT_ENGINE_READY_TIMEOUT,
+            pending_engine_error: Mutex::new(None),
+            frame_stats: Mutex::new(FrameStatsTracker::default()),
         }
     }
 
@@ -129,9 +175,20 @@ impl GraphicProxy {
             available: false,
             asset_root: None,
             main_window_id: AtomicU64::new(1),
+            batch: Mutex::new(CommandBatch::default()),
+            startup: watch::channel(EngineStartup::Idle).0,
+            engine_ready_timeout: DEFAULT_ENGINE_READY_TIMEOUT,
+            pending_engine_error: Mutex::new(None),
+            frame_stats: Mutex::new(FrameStatsTracker::new(0.0)),
         }
     }
 
+    /// Set how long `enable_engine` waits for the engine to report `EngineReady`
+    pub fn with_engine_ready_timeout(mut self, timeout: Duration) -> Self {
+        self.engine_ready_timeout = timeout;
+        self
+    }
+
     /// Check if the graphic proxy is available
     ///
     /// Returns false on server, true on client.
@@ -225,7 +282,9 @@ impl GraphicProxy {
     ///
     /// This sends a request to the main thread to spawn the engine.
     /// The main thread will create the engine and send back the channels
-    /// for communication.
+    /// for communication. The call then waits for the engine to report
+    /// `EngineReady` (see `mark_engine_ready`), so a failed or hung startup
+    /// resolves with an error instead of hanging.
     ///
     /// # Arguments
     /// * `engine_type` - The type of engine to enable
@@ -238,6 +297,7 @@ impl GraphicProxy {
     /// - An engine is already enabled
     /// - The engine type is not supported
     /// - The main thread fails to spawn the engine
+    /// - The engine reports an error or is not ready within the configured timeout
     pub async fn enable_engine(
         &self,
         engine_type: GraphicEngines,
@@ -269,6 +329,7 @@ impl GraphicProxy {
 
         // Create response channel
         let (response_tx, response_rx) = oneshot::channel();
+        self.startup.send_replace(EngineStartup::Starting);
 
         // Clone for local use after sending to main thread
         let initial_window_config_clone = initial_window_config.clone();
@@ -281,12 +342,19 @@ impl GraphicProxy {
                 asset_root: self.asset_root.clone(),
                 response_tx,
             })
-            .map_err(|_| "Failed to send enable request to main thread")?;
+            .map_err(|_| {
+                self.startup.send_replace(EngineStartup::Idle);
+                "Failed to send enable request to main thread"
+            })?;
 
         // Wait for response
-        let (cmd_tx, event_rx) = response_rx
-            .await
-            .map_err(|_| "Main thread did not respond to enable request")??;
+        let channels = match response_rx.await {
+            Ok(result) => result,
+            Err(_) => Err("Main thread did not respond to enable request".to_string()),
+        };
+        let (cmd_tx, event_rx) = channels.inspect_err(|_| {
+            self.startup.send_replace(EngineStartup::Idle);
+        })?;
 
         // Store the command sender
         *self.command_tx.write().unwrap() = Some(cmd_tx);
@@ -305,11 +373,91 @@ impl GraphicProxy {
         main_window_info.mark_created();
         self.windows.write().unwrap().insert(1, main_window_info);
 
+        // The main loop takes the event receiver and reports EngineReady/EngineError back
+        if let Err(e) = self.wait_until_engine_ready(engine_type).await {
+            *self.command_tx.write().unwrap() = None;
+            *self.active_engine.write().unwrap() = None;
+            self.windows.write().unwrap().remove(&1);
+            *self.event_rx.lock().await = None;
+            return Err(e);
+        }
+
         tracing::info!("Graphic engine '{}' enabled", engine_type.name());
 
         Ok(())
     }
 
+    /// Wait for the engine started by `enable_engine` to report `EngineReady`
+    async fn wait_until_engine_ready(&self, engine_type: GraphicEngines) -> Result<(), String> {
+        let mut startup = self.startup.subscribe();
+        let waited = tokio::time::timeout(
+            self.engine_ready_timeout,
+            startup.wait_for(|state| matches!(state, EngineStartup::Ready | EngineStartup::Failed(_))),
+        )
+        .await;
+
+        match waited {
+            Ok(Ok(state)) => match &*state {
+                EngineStartup::Failed(message) => Err(format!(
+                    "Graphic engine '{}' failed to start: {}",
+                    engine_type.name(),
+                    message
+                )),
+                _ => Ok(()),
+            },
+            Ok(Err(_)) => Err("Graphic engine startup state is no longer available".to_string()),
+            Err(_) => {
+                let message = format!(
+                    "Graphic engine '{}' did not become ready within {}s",
+                    engine_type.name(),
+                    self.engine_ready_timeout.as_secs_f32()
+                );
+                self.startup.send_replace(EngineStartup::Failed(message.clone()));
+                *self.pending_engine_error.lock().unwrap() = Some(message.clone());
+                Err(message)
+            }
+        }
+    }
+
+    /// Record that the engine reported `EngineReady`
+    ///
+    /// Called by the main loop when it receives the event.
+    ///
+    /// # Returns
+    /// `false` if the startup had already been given up on (timed out or failed),
+    /// in which case the event must not be forwarded to mods
+    pub fn mark_engine_ready(&self) -> bool {
+        self.startup.send_if_modified(|state| {
+            let starting = *state == EngineStartup::Starting;
+            if starting {
+                *state = EngineStartup::Ready;
+            }
+            starting
+        });
+        *self.startup.borrow() == EngineStartup::Ready
+    }
+
+    /// Record that the engine reported an error
+    ///
+    /// Called by the main loop on `EngineError`. Fails a pending `enable_engine`;
+    /// errors reported after the engine became ready are left to the mods' handlers.
+    pub fn mark_engine_failed(&self, message: &str) {
+        self.startup.send_if_modified(|state| {
+            let starting = *state == EngineStartup::Starting;
+            if starting {
+                *state = EngineStartup::Failed(message.to_string());
+            }
+            starting
+        });
+    }
+
+    /// Take the startup error the engine could not report itself (e.g. timeout)
+    ///
+    /// The main loop dispatches it to mods as a `graphic:engine:error` event.
+    pub fn take_engine_error(&self) -> Option<String> {
+        self.pending_engine_error.lock().unwrap().take()
+    }
+
     /// Create a new window
     ///
     /// # Errors
@@ -535,6 +683,109 @@ impl GraphicProxy {
         Ok(())
     }
 
+    /// Set the window icon from a loaded image resource
+    ///
+    /// # Arguments
+    /// * `window_id` - The window to update
+    /// * `resource_id` - Alias of an image loaded via Resource.load()
+    pub async fn set_window_icon(&self, window_id: u64, resource_id: String) -> Result<(), String> {
+        if !self.available {
+            return Err(
+                "window.setIcon() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        // Release the sender lock before awaiting the engine
+        {
+            let tx = self.command_tx.read().unwrap();
+            let tx = tx.as_ref().ok_or("No graphic engine enabled")?;
+            tx.send(GraphicCommand::SetWindowIcon {
+                id: window_id,
+                resource_id,
+                response_tx,
+            })
+            .map_err(|_| "Failed to send command to graphic engine")?;
+        }
+
+        response_rx
+            .await
+            .map_err(|_| "Graphic engine did not respond")??;
+
+        Ok(())
+    }
+
+    /// Show or hide the OS cursor while it is over a window
+    pub async fn set_cursor_visible(&self, window_id: u64, visible: bool) -> Result<(), String> {
+        if !self.available {
+            return Err(
+                "window.setCursorVisible() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        // Release the sender lock before awaiting the engine
+        {
+            let tx = self.command_tx.read().unwrap();
+            let tx = tx.as_ref().ok_or("No graphic engine enabled")?;
+            tx.send(GraphicCommand::SetCursorVisible {
+                id: window_id,
+                visible,
+                response_tx,
+            })
+            .map_err(|_| "Failed to send command to graphic engine")?;
+        }
+
+        response_rx
+            .await
+            .map_err(|_| "Graphic engine did not respond")??;
+
+        Ok(())
+    }
+
+    /// Set the default cursor shape for a window
+    ///
+    /// # Arguments
+    /// * `window_id` - The window to update
+    /// * `icon` - Cursor name (see [`WindowCursor::name`]), e.g. "pointer"
+    pub async fn set_cursor_icon(&self, window_id: u64, icon: &str) -> Result<(), String> {
+        if !self.available {
+            return Err(
+                "window.setCursor() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+
+        let icon = WindowCursor::from_name(icon).ok_or_else(|| {
+            let names: Vec<&str> = WindowCursor::ALL.iter().map(|c| c.name()).collect();
+            format!("Invalid cursor '{}'. Valid cursors: {}", icon, names.join(", "))
+        })?;
+
+        let (response_tx, response_rx) = oneshot::channel();
+
+        // Release the sender lock before awaiting the engine
+        {
+            let tx = self.command_tx.read().unwrap();
+            let tx = tx.as_ref().ok_or("No graphic engine enabled")?;
+            tx.send(GraphicCommand::SetCursorIcon {
+                id: window_id,
+                icon,
+                response_tx,
+            })
+            .map_err(|_| "Failed to send command to graphic engine")?;
+        }
+
+        response_rx
+            .await
+            .map_err(|_| "Graphic engine did not respond")??;
+
+        Ok(())
+    }
+
     /// Set the default font for a window
     ///
     /// All widgets in this window will inherit this font configuration
@@ -619,6 +870,48 @@ impl GraphicProxy {
         self.windows.read().unwrap().keys().copied().collect()
     }
 
+    /// Set how many `graphic:frame:stats` events are sent per second
+    ///
+    /// # Arguments
+    /// * `rate_hz` - Reports per second, `0` to stop reporting
+    ///
+    /// # Errors
+    /// Returns an error if called on the server or if the rate is negative or not finite
+    pub fn set_frame_stats_rate(&self, rate_hz: f32) -> Result<(), String> {
+        if !self.available {
+            return Err(
+                "graphic.setFrameStatsRate() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+        if !rate_hz.is_finite() || rate_hz < 0.0 {
+            return Err(format!("Invalid frame stats rate {}: expected a number >= 0", rate_hz));
+        }
+        self.frame_stats.lock().unwrap().set_rate_hz(rate_hz);
+        Ok(())
+    }
+
+    /// Get how many `graphic:frame:stats` events are sent per second (0 when disabled)
+    pub fn frame_stats_rate(&self) -> f32 {
+        self.frame_stats.lock().unwrap().rate_hz()
+    }
+
+    /// Record a `FrameStart` delta (in seconds)
+    ///
+    /// Called by the main loop for every frame. Returns the stats to report
+    /// once per reporting interval.
+    pub fn record_frame(&self, delta_time: f32) -> Option<FrameStats> {
+        self.frame_stats.lock().unwrap().record(delta_time)
+    }
+
+    /// Forget a window closed by the engine (e.g. by the user)
+    ///
+    /// Called by the main loop on `WindowClosed`; `close_window` already does this
+    /// for windows closed by mods.
+    pub fn forget_window(&self, window_id: u64) {
+        self.windows.write().unwrap().remove(&window_id);
+    }
+
     /// Take the event receiver for polling by the main event loop
     ///
     /// This method takes ownership of the event receiver, so it can only be called once.
@@ -645,6 +938,81 @@ impl GraphicProxy {
         }
     }
 
+    /// Receive all currently queued events at once
+    ///
+    /// Waits until at least one event is available, then takes up to `max`
+    /// queued events in a single call and coalesces high-frequency ones
+    /// (see `coalesce_events`). This lets the worker loop process a whole
+    /// batch per wake instead of one event per `select!` iteration.
+    ///
+    /// Returns an empty Vec only when the channel is closed.
+    /// Use `rx.recv()` directly when per-event latency matters more.
+    pub async fn recv_event_batch(
+        rx: &mut mpsc::Receiver<GraphicEvent>,
+        max: usize,
+    ) -> Vec<GraphicEvent> {
+        let mut events = Vec::with_capacity(max.min(rx.len().max(1)));
+        rx.recv_many(&mut events, max.max(1)).await;
+        Self::coalesce_events(events)
+    }
+
+    /// Drain all queued events without waiting
+    ///
+    /// Batched counterpart of `try_recv_event` for when the receiver was not taken.
+    pub async fn drain_events(&self) -> Vec<GraphicEvent> {
+        let mut guard = self.event_rx.lock().await;
+        let mut events = Vec::new();
+        if let Some(ref mut rx) = *guard {
+            while let Ok(event) = rx.try_recv() {
+                events.push(event);
+            }
+        }
+        Self::coalesce_events(events)
+    }
+
+    /// Merge consecutive high-frequency events for the same window
+    ///
+    /// Runs of `MouseMoved`, `WindowResized` and `WindowMoved` keep only the
+    /// latest event, and runs of `MouseWheel` are summed. Any other event
+    /// breaks a run, so relative ordering with key presses, clicks etc. is kept.
+    pub fn coalesce_events(events: Vec<GraphicEvent>) -> Vec<GraphicEvent> {
+        let mut result: Vec<GraphicEvent> = Vec::with_capacity(events.len());
+        for event in events {
+            match (result.last_mut(), event) {
+                (
+                    Some(GraphicEvent::MouseMoved { window_id: last_id, x: last_x, y: last_y }),
+                    GraphicEvent::MouseMoved { window_id, x, y },
+                ) if *last_id == window_id => {
+                    *last_x = x;
+                    *last_y = y;
+                }
+                (
+                    Some(GraphicEvent::WindowResized { window_id: last_id, width: last_w, height: last_h }),
+                    GraphicEvent::WindowResized { window_id, width, height },
+                ) if *last_id == window_id => {
+                    *last_w = width;
+                    *last_h = height;
+                }
+                (
+                    Some(GraphicEvent::WindowMoved { window_id: last_id, x: last_x, y: last_y }),
+                    GraphicEvent::WindowMoved { window_id, x, y },
+                ) if *last_id == window_id => {
+                    *last_x = x;
+                    *last_y = y;
+                }
+                (
+                    Some(GraphicEvent::MouseWheel { window_id: last_id, delta_x: last_dx, delta_y: last_dy }),
+                    GraphicEvent::MouseWheel { window_id, delta_x, delta_y },
+                ) if *last_id == window_id => {
+                    *last_dx += delta_x;
+                    *last_dy += delta_y;
+                }
+                (_, event) => result.push(event),
+            }
+        }
+        result
+    }
+
     // ========================================================================
     // Asset Management (Fonts and Images)
     // ========================================================================
@@ -1004,10 +1372,179 @@ impl GraphicProxy {
             .map_err(|_| "Graphic engine did not respond")?
     }
 
+    // ========================================================================
+    // Audio Operations
+    // ========================================================================
+
+    /// Start playing a loaded audio resource
+    ///
+    /// # Arguments
+    /// * `resource_id` - Alias of an audio resource loaded with `Resource.load()`
+    /// * `volume` - Linear volume, must be finite and not negative (1.0 = original volume)
+    /// * `looping` - Restart from the beginning when playback ends
+    ///
+    /// # Returns
+    /// Playback handle to pass to `stop_audio`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Called on the server
+    /// - The volume is negative or not a number
+    /// - No graphic engine is enabled
+    /// - The resource is not a loaded audio resource
+    pub async fn play_audio(&self, resource_id: String, volume: f32, looping: bool) -> Result<u64, String> {
+        if !self.available {
+            return Err(
+                "Audio.play() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+
+        if !volume.is_finite() || volume < 0.0 {
+            return Err(format!(
+                "Audio.play() volume must be a number >= 0, got {}",
+                volume
+            ));
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+        {
+            // Release the lock before awaiting the engine
+            let tx = self.command_tx.read().unwrap();
+            let tx = tx
+                .as_ref()
+                .ok_or("No graphic engine enabled. Call Graphic.enableEngine() first.")?;
+
+            tx.send(GraphicCommand::PlayAudio {
+                resource_id,
+                volume,
+                looping,
+                response_tx,
+            })
+            .map_err(|_| "Failed to send command to graphic engine")?;
+        }
+
+        response_rx
+            .await
+            .map_err(|_| "Graphic engine did not respond")?
+    }
+
+    /// Stop a playback started with `play_audio`
+    ///
+    /// Stopping a playback that already ended succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on the server, no graphic engine is enabled
+    /// or the handle was never returned by `play_audio`.
+    pub async fn stop_audio(&self, handle: u64) -> Result<(), String> {
+        if !self.available {
+            return Err(
+                "Audio.stop() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+        {
+            // Release the lock before awaiting the engine
+            let tx = self.command_tx.read().unwrap();
+            let tx = tx
+                .as_ref()
+                .ok_or("No graphic engine enabled. Call Graphic.enableEngine() first.")?;
+
+            tx.send(GraphicCommand::StopAudio { handle, response_tx })
+                .map_err(|_| "Failed to send command to graphic engine")?;
+        }
+
+        response_rx
+            .await
+            .map_err(|_| "Graphic engine did not respond")?
+    }
+
     // ========================================================================
     // ECS Operations
     // ========================================================================
 
+    /// Send several commands to the engine as one `GraphicCommand::Batch`
+    ///
+    /// The engine processes them in order within a single frame. Each command
+    /// still answers on its own response channel.
+    pub fn send_batch(&self, commands: Vec<GraphicCommand>) -> Result<(), String> {
+        if !self.available {
+            return Err(
+                "World.batch() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+        if commands.is_empty() {
+            return Ok(());
+        }
+
+        let tx = self.command_tx.read().unwrap();
+        let tx = tx
+            .as_ref()
+            .ok_or("No graphic engine enabled. Call Graphic.enableEngine() first.")?;
+
+        tx.send(GraphicCommand::Batch { commands })
+            .map_err(|_| "Failed to send command to graphic engine".to_string())
+    }
+
+    /// Start queueing ECS commands instead of sending them one at a time
+    ///
+    /// Spawn, despawn, insert, update and remove commands issued before the
+    /// matching `end_batch` are sent together with `send_batch`. Batches can be
+    /// nested; only the outermost `end_batch` flushes. The responses of queued
+    /// commands arrive after the flush, so awaiting them before `end_batch`
+    /// never completes.
+    pub fn begin_batch(&self) -> Result<(), String> {
+        if !self.available {
+            return Err(
+                "World.batch() is not available on the server. This method is client-only."
+                    .to_string(),
+            );
+        }
+        self.batch.lock().unwrap().depth += 1;
+        Ok(())
+    }
+
+    /// Close a batch opened with `begin_batch`, flushing it if it is the outermost one
+    pub fn end_batch(&self) -> Result<(), String> {
+        let commands = {
+            let mut batch = self.batch.lock().unwrap();
+            batch.depth = batch.depth.saturating_sub(1);
+            if batch.depth > 0 {
+                return Ok(());
+            }
+            std::mem::take(&mut batch.commands)
+        };
+        self.send_batch(commands)
+    }
+
+    /// Number of commands queued in the open batch
+    pub fn batched_command_count(&self) -> usize {
+        self.batch.lock().unwrap().commands.len()
+    }
+
+    /// Send an ECS command, or queue it while a batch is open
+    fn send_ecs_command(&self, command: GraphicCommand) -> Result<(), String> {
+        let tx = self.command_tx.read().unwrap();
+        let tx = tx
+            .as_ref()
+            .ok_or("No graphic engine enabled. Call Graphic.enableEngine() first.")?;
+
+        let mut batch = self.batch.lock().unwrap();
+        if batch.depth > 0 {
+            batch.commands.push(command);
+            return Ok(());
+        }
+        drop(batch);
+
+        tx.send(command)
+            .map_err(|_| "Failed to send command to graphic engine".to_string())
+    }
+
     /// Spawn a new entity
     ///
     /// Creates a new entity with optional initial components.
@@ -1034,20 +1571,14 @@ impl GraphicProxy {
             );
         }
 
-        let tx = self.command_tx.read().unwrap();
-        let tx = tx
-            .as_ref()
-            .ok_or("No graphic engine enabled. Call Graphic.enableEngine() first.")?;
-
         let (response_tx, response_rx) = oneshot::channel();
 
-        tx.send(GraphicCommand::SpawnEntity {
+        self.send_ecs_command(GraphicCommand::SpawnEntity {
             components,
             owner_mod,
             parent,
             response_tx,
-        })
-        .map_err(|_| "Failed to send command to graphic engine")?;
+        })?;
 
         response_rx
             .await
@@ -1068,16 +1599,12 @@ impl GraphicProxy {
             );
         }
 
-        let tx = self.command_tx.read().unwrap();
-        let tx = tx.as_ref().ok_or("No graphic engine enabled")?;
-
         let (response_tx, response_rx) = oneshot::channel();
 
-        tx.send(GraphicCommand::DespawnEntity {
+        self.send_ecs_command(GraphicCommand::DespawnEntity {
             entity_id,
             response_tx,
-        })
-        .map_err(|_| "Failed to send command to graphic engine")?;
+        })?;
 
         response_rx
             .await
@@ -1105,18 +1632,14 @@ impl GraphicProxy {
             );
         }
 
-        let tx = self.command_tx.read().unwrap();
-        let tx = tx.as_ref().ok_or("No graphic engine enabled")?;
-
         let (response_tx, response_rx) = oneshot::channel();
 
-        tx.send(GraphicCommand::InsertComponent {
+        self.send_ecs_command(GraphicCommand::InsertComponent {
             entity_id,
             component_name,
             component_data,
             response_tx,
-        })
-        .map_err(|_| "Failed to send command to graphic engine")?;
+        })?;
 
         response_rx
             .await
@@ -1145,18 +1668,14 @@ impl GraphicProxy {
             );
         }
 
-        let tx = self.command_tx.read().unwrap();
-        let tx = tx.as_ref().ok_or("No graphic engine enabled")?;
-
         let (response_tx, response_rx) = oneshot::channel();
 
-        tx.send(GraphicCommand::UpdateComponent {
+        self.send_ecs_command(GraphicCommand::UpdateComponent {
             entity_id,
             component_name,
             component_data,
             response_tx,
-        })
-        .map_err(|_| "Failed to send command to graphic engine")?;
+        })?;
 
         response_rx
             .await
@@ -1180,17 +1699,13 @@ impl GraphicProxy {
             );
         }
 
-        let tx = self.command_tx.read().unwrap();
-        let tx = tx.as_ref().ok_or("No graphic engine enabled")?;
-
         let (response_tx, response_rx) = oneshot::channel();
 
-        tx.send(GraphicCommand::RemoveComponent {
+        self.send_ecs_command(GraphicCommand::RemoveComponent {
             entity_id,
             component_name,
             response_tx,
-        })
-        .map_err(|_| "Failed to send command to graphic engine")?;
+        })?;
 
         response_rx
             .await
@@ -1484,3 +1999,250 @@ impl GraphicProxy {
 // GraphicProxy is Send + Sync because all internal state is protected
 unsafe impl Send for GraphicProxy {}
 unsafe impl Sync for GraphicProxy {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mouse_moved(window_id: u64, x: f32) -> GraphicEvent {
+        GraphicEvent::MouseMoved { window_id, x, y: 0.0 }
+    }
+
+    #[test]
+    fn test_coalesce_keeps_latest_mouse_position() {
+        let events = vec![mouse_moved(1, 1.0), mouse_moved(1, 2.0), mouse_moved(1, 3.0)];
+        let coalesced = GraphicProxy::coalesce_events(events);
+        assert_eq!(coalesced.len(), 1);
+        assert!(matches!(coalesced[0], GraphicEvent::MouseMoved { x, .. } if x == 3.0));
+    }
+
+    #[test]
+    fn test_coalesce_preserves_order_around_other_events() {
+        let events = vec![
+            mouse_moved(1, 1.0),
+            GraphicEvent::EngineReady,
+            mouse_moved(1, 2.0),
+            mouse_moved(2, 5.0),
+        ];
+        let coalesced = GraphicProxy::coalesce_events(events);
+        assert_eq!(coalesced.len(), 4);
+        assert!(matches!(coalesced[1], GraphicEvent::EngineReady));
+    }
+
+    #[test]
+    fn test_coalesce_sums_mouse_wheel() {
+        let events = vec![
+            GraphicEvent::MouseWheel { window_id: 1, delta_x: 0.0, delta_y: 1.0 },
+            GraphicEvent::MouseWheel { window_id: 1, delta_x: 0.5, delta_y: 2.0 },
+        ];
+        let coalesced = GraphicProxy::coalesce_events(events);
+        assert_eq!(coalesced.len(), 1);
+        assert!(matches!(
+            coalesced[0],
+            GraphicEvent::MouseWheel { delta_x, delta_y, .. } if delta_x == 0.5 && delta_y == 3.0
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_play_audio_checks_side_and_volume() {
+        let server = GraphicProxy::new_server_stub();
+        let err = server.play_audio("sfx-click".into(), 1.0, false).await.unwrap_err();
+        assert!(err.contains("client-only"), "{}", err);
+        assert!(server.stop_audio(1).await.unwrap_err().contains("client-only"));
+
+        let (enable_tx, _enable_rx) = std::sync::mpsc::channel();
+        let client = GraphicProxy::new_client(enable_tx, None);
+        let err = client.play_audio("sfx-click".into(), -0.5, false).await.unwrap_err();
+        assert!(err.contains("volume"), "{}", err);
+        let err = client.play_audio("sfx-click".into(), 1.0, true).await.unwrap_err();
+        assert!(err.contains("No graphic engine enabled"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_batch_flushes_queued_commands_in_order() {
+        let (enable_tx, _enable_rx) = std::sync::mpsc::channel();
+        let client = GraphicProxy::new_client(enable_tx, None);
+        let (cmd_tx, cmd_rx) = std::sync::mpsc::channel();
+        *client.command_tx.write().unwrap() = Some(cmd_tx);
+
+        client.begin_batch().unwrap();
+        let insert = client.insert_component(1, "Health".into(), serde_json::json!({ "current": 10 }));
+        let remove = client.remove_component(2, "Frozen".into());
+        let engine = async {
+            // Let both calls queue their command
+            tokio::task::yield_now().await;
+            assert_eq!(client.batched_command_count(), 2);
+            assert!(cmd_rx.try_recv().is_err());
+
+            // Nested batches only flush at the outermost end
+            client.begin_batch().unwrap();
+            client.end_batch().unwrap();
+            assert!(cmd_rx.try_recv().is_err());
+            client.end_batch().unwrap();
+
+            let Ok(GraphicCommand::Batch { commands }) = cmd_rx.try_recv() else {
+                panic!("expected a single Batch command");
+            };
+            assert_eq!(commands.len(), 2);
+            for command in commands {
+                match command {
+                    GraphicCommand::InsertComponent { entity_id: 1, response_tx, .. } => {
+                        response_tx.send(Ok(())).unwrap();
+                    }
+                    GraphicCommand::RemoveComponent { entity_id: 2, response_tx, .. } => {
+                        response_tx.send(Err("missing".into())).unwrap();
+                    }
+                    other => panic!("unexpected command {:?}", other),
+                }
+            }
+        };
+
+        let (inserted, removed, ()) = tokio::join!(insert, remove, engine);
+        assert_eq!(inserted, Ok(()));
+        assert_eq!(removed, Err("missing".to_string()));
+
+        // An empty batch sends nothing
+        client.send_batch(Vec::new()).unwrap();
+        assert!(cmd_rx.try_recv().is_err());
+        assert!(GraphicProxy::new_server_stub().begin_batch().unwrap_err().contains("client-only"));
+    }
+
+    #[tokio::test]
+    async fn test_recv_event_batch_drains_queue() {
+        let (tx, mut rx) = mpsc::channel(16);
+        tx.send(mouse_moved(1, 1.0)).await.unwrap();
+        tx.send(GraphicEvent::EngineReady).await.unwrap();
+        tx.send(mouse_moved(1, 2.0)).await.unwrap();
+
+        let batch = GraphicProxy::recv_event_batch(&mut rx, 64).await;
+        assert_eq!(batch.len(), 3);
+
+        drop(tx);
+        assert!(GraphicProxy::recv_event_batch(&mut rx, 64).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_window_assigns_distinct_ids() {
+        let (enable_tx, _enable_rx) = std::sync::mpsc::channel();
+        let client = GraphicProxy::new_client(enable_tx, None);
+        let (cmd_tx, cmd_rx) = std::sync::mpsc::channel();
+        *client.command_tx.write().unwrap() = Some(cmd_tx);
+
+        let engine = std::thread::spawn(move || {
+            let mut created = Vec::new();
+            while let Ok(command) = cmd_rx.recv() {
+                if let GraphicCommand::CreateWindow { id, response_tx, .. } = command {
+                    created.push(id);
+                    response_tx.send(Ok(())).unwrap();
+                }
+            }
+            created
+        });
+
+        let first = client.create_window(WindowConfig::default()).await.unwrap();
+        let second = client.create_window(WindowConfig::default()).await.unwrap();
+        assert_ne!(first, second);
+        assert_ne!(first, 1, "ID 1 is reserved for the main window");
+
+        let mut ids = client.get_window_ids();
+        ids.sort();
+        assert_eq!(ids, vec![first, second]);
+
+        client.forget_window(first);
+        assert!(client.get_window_info(first).is_none());
+        assert!(client.get_window_info(second).is_some());
+
+        *client.command_tx.write().unwrap() = None;
+        assert_eq!(engine.join().unwrap(), vec![first, second]);
+    }
+
+    #[test]
+    fn test_frame_stats_rate() {
+        let (enable_tx, _enable_rx) = std::sync::mpsc::channel();
+        let client = GraphicProxy::new_client(enable_tx, None);
+        assert_eq!(client.frame_stats_rate(), super::super::DEFAULT_FRAME_STATS_RATE_HZ);
+
+        client.set_frame_stats_rate(0.0).unwrap();
+        assert!((0..100).all(|_| client.record_frame(0.016).is_none()));
+        client.set_frame_stats_rate(10.0).unwrap();
+        assert!((0..100).filter_map(|_| client.record_frame(0.01)).count() >= 9);
+
+        assert!(client.set_frame_stats_rate(-1.0).unwrap_err().contains("Invalid frame stats rate"));
+        assert!(client.set_frame_stats_rate(f32::NAN).is_err());
+        let server = GraphicProxy::new_server_stub();
+        assert!(server.set_frame_stats_rate(1.0).unwrap_err().contains("client-only"));
+    }
+
+    /// Answer the next enable request like the client main thread does
+    fn answer_enable_request(
+        enable_rx: &std::sync::mpsc::Receiver<EnableEngineRequest>,
+    ) -> (std::sync::mpsc::Receiver<GraphicCommand>, mpsc::Sender<GraphicEvent>) {
+        let request = enable_rx.recv().unwrap();
+        let (cmd_tx, cmd_rx) = std::sync::mpsc::channel();
+        let (event_tx, event_rx) = mpsc::channel(16);
+        request.response_tx.send(Ok((cmd_tx, event_rx))).unwrap();
+        (cmd_rx, event_tx)
+    }
+
+    #[tokio::test]
+    async fn test_enable_engine_waits_for_ready() {
+        let (enable_tx, enable_rx) = std::sync::mpsc::channel();
+        let client = GraphicProxy::new_client(enable_tx, None);
+        let main_thread = std::thread::spawn(move || answer_enable_request(&enable_rx));
+
+        let engine = async {
+            // The main loop reports EngineReady once it has the event receiver
+            let mut event_rx = loop {
+                match client.take_event_receiver().await {
+                    Some(rx) => break rx,
+                    None => tokio::time::sleep(Duration::from_millis(5)).await,
+                }
+            };
+            let (_cmd_rx, event_tx) = main_thread.join().unwrap();
+            event_tx.send(GraphicEvent::EngineReady).await.unwrap();
+            assert!(matches!(event_rx.recv().await, Some(GraphicEvent::EngineReady)));
+            assert!(client.mark_engine_ready());
+        };
+
+        let (enabled, ()) = tokio::join!(client.enable_engine(GraphicEngines::Bevy, None), engine);
+        assert_eq!(enabled, Ok(()));
+        assert!(client.is_engine_enabled());
+        assert!(client.take_engine_error().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enable_engine_fails_on_error_or_timeout() {
+        let (enable_tx, enable_rx) = std::sync::mpsc::channel();
+        let client = GraphicProxy::new_client(enable_tx, None)
+            .with_engine_ready_timeout(Duration::from_millis(50));
+        let main_thread = std::thread::spawn(move || {
+            let first = answer_enable_request(&enable_rx);
+            (first, enable_rx)
+        });
+
+        // Engine error while starting (e.g. no display available)
+        let report_error = async {
+            while client.take_event_receiver().await.is_none() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            client.mark_engine_failed("no display available");
+        };
+        let (enabled, ()) = tokio::join!(client.enable_engine(GraphicEngines::Bevy, None), report_error);
+        let err = enabled.unwrap_err();
+        assert!(err.contains("failed to start: no display available"), "{}", err);
+        assert!(!client.is_engine_enabled());
+        // Already reported through the engine's own EngineError event
+        assert!(client.take_engine_error().is_none());
+
+        // No EngineReady at all: the call times out and the error is queued for mods
+        let (_first, enable_rx) = main_thread.join().unwrap();
+        let _second = std::thread::spawn(move || answer_enable_request(&enable_rx));
+        let err = client.enable_engine(GraphicEngines::Bevy, None).await.unwrap_err();
+        assert!(err.contains("did not become ready"), "{}", err);
+        assert!(!client.is_engine_enabled());
+        assert_eq!(client.take_engine_error(), Some(err));
+
+        // A late EngineReady is not forwarded
+        assert!(!client.mark_engine_ready());
+    }
+}
@@ -38,6 +38,81 @@ impl WindowMode {
     }
 }
 
+/// Cursor shape shown while the pointer is over a window
+///
+/// Names follow the CSS `cursor` keywords (e.g. "pointer", "not-allowed").
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowCursor {
+    Default,
+    Pointer,
+    Text,
+    Crosshair,
+    Move,
+    Wait,
+    Progress,
+    Help,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ZoomIn,
+    ZoomOut,
+}
+
+impl WindowCursor {
+    /// Every supported cursor, in documentation order
+    pub const ALL: [Self; 17] = [
+        Self::Default,
+        Self::Pointer,
+        Self::Text,
+        Self::Crosshair,
+        Self::Move,
+        Self::Wait,
+        Self::Progress,
+        Self::Help,
+        Self::NotAllowed,
+        Self::Grab,
+        Self::Grabbing,
+        Self::EwResize,
+        Self::NsResize,
+        Self::NeswResize,
+        Self::NwseResize,
+        Self::ZoomIn,
+        Self::ZoomOut,
+    ];
+
+    /// Parse a cursor name (e.g. "pointer")
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|cursor| cursor.name() == name)
+    }
+
+    /// Name used by scripts for this cursor
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Pointer => "pointer",
+            Self::Text => "text",
+            Self::Crosshair => "crosshair",
+            Self::Move => "move",
+            Self::Wait => "wait",
+            Self::Progress => "progress",
+            Self::Help => "help",
+            Self::NotAllowed => "not-allowed",
+            Self::Grab => "grab",
+            Self::Grabbing => "grabbing",
+            Self::EwResize => "ew-resize",
+            Self::NsResize => "ns-resize",
+            Self::NeswResize => "nesw-resize",
+            Self::NwseResize => "nwse-resize",
+            Self::ZoomIn => "zoom-in",
+            Self::ZoomOut => "zoom-out",
+        }
+    }
+}
+
 /// Window position mode
 ///
 /// Controls how the window is positioned on screen.
@@ -236,3 +311,17 @@ impl WindowInfo {
         self.created = true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_cursor_names_round_trip() {
+        for cursor in WindowCursor::ALL {
+            assert_eq!(WindowCursor::from_name(cursor.name()), Some(cursor));
+        }
+        assert_eq!(WindowCursor::from_name("not-allowed"), Some(WindowCursor::NotAllowed));
+        assert_eq!(WindowCursor::from_name("hand"), None);
+    }
+}
@@ -0,0 +1,153 @@
+//! Mod Load Plan
+//!
+//! Records the resolved order in which mods are attached, with the priority of
+//! each mod and why it was included (bootstrap mod, dependency of another mod,
+//! enabled in the game config). The host logs the plan at DEBUG and mods read it
+//! through `system.getLoadOrder()` to debug bootstrap ordering.
+
+use std::fmt;
+
+/// Why a mod is part of the load plan
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadReason {
+    /// A bootstrap mod, loaded at startup
+    Bootstrap,
+    /// Loaded because other mods in the plan require it (see `PlannedMod::required_by`)
+    Dependency,
+    /// Enabled in the game config (server)
+    Configured,
+}
+
+impl LoadReason {
+    /// Name of the reason, as reported to mods
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoadReason::Bootstrap => "bootstrap",
+            LoadReason::Dependency => "dependency",
+            LoadReason::Configured => "configured",
+        }
+    }
+}
+
+/// A mod in the load plan
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedMod {
+    /// The mod ID
+    pub mod_id: String,
+    /// Priority from the manifest (lower loads first)
+    pub priority: i32,
+    /// Why the mod was included
+    pub reason: LoadReason,
+    /// Mods in the plan that require this one, in discovery order
+    pub required_by: Vec<String>,
+}
+
+/// Ordered list of the mods to attach
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoadPlan {
+    mods: Vec<PlannedMod>,
+}
+
+impl LoadPlan {
+    /// Create an empty plan
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a mod to the end of the plan
+    pub fn push(&mut self, planned: PlannedMod) {
+        self.mods.push(planned);
+    }
+
+    /// Get a planned mod by ID
+    pub fn get(&self, mod_id: &str) -> Option<&PlannedMod> {
+        self.mods.iter().find(|planned| planned.mod_id == mod_id)
+    }
+
+    /// Get a planned mod by ID, mutably
+    pub fn get_mut(&mut self, mod_id: &str) -> Option<&mut PlannedMod> {
+        self.mods.iter_mut().find(|planned| planned.mod_id == mod_id)
+    }
+
+    /// Whether the mod is part of the plan
+    pub fn contains(&self, mod_id: &str) -> bool {
+        self.get(mod_id).is_some()
+    }
+
+    /// Iterate the planned mods in load order
+    pub fn iter(&self) -> std::slice::Iter<'_, PlannedMod> {
+        self.mods.iter()
+    }
+
+    /// IDs of the planned mods in load order
+    pub fn mod_ids(&self) -> Vec<String> {
+        self.mods.iter().map(|planned| planned.mod_id.clone()).collect()
+    }
+
+    /// Number of planned mods
+    pub fn len(&self) -> usize {
+        self.mods.len()
+    }
+
+    /// Whether the plan is empty
+    pub fn is_empty(&self) -> bool {
+        self.mods.is_empty()
+    }
+
+    /// Order the plan by priority (lower first), keeping the current order among equal priorities
+    pub fn sort_by_priority(&mut self) {
+        self.mods.sort_by_key(|planned| planned.priority);
+    }
+}
+
+impl fmt::Display for LoadPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, planned) in self.mods.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "  {}. {} (priority {}, {}",
+                index + 1,
+                planned.mod_id,
+                planned.priority,
+                planned.reason.as_str()
+            )?;
+            if !planned.required_by.is_empty() {
+                write!(f, ", required by {}", planned.required_by.join(", "))?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planned(mod_id: &str, priority: i32, reason: LoadReason, required_by: &[&str]) -> PlannedMod {
+        PlannedMod {
+            mod_id: mod_id.to_string(),
+            priority,
+            reason,
+            required_by: required_by.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_priority_sort_is_stable() {
+        let mut plan = LoadPlan::new();
+        plan.push(planned("core", 0, LoadReason::Dependency, &["game"]));
+        plan.push(planned("ui", 0, LoadReason::Dependency, &["game"]));
+        plan.push(planned("game", -1, LoadReason::Bootstrap, &[]));
+        plan.sort_by_priority();
+
+        assert_eq!(plan.mod_ids(), vec!["game", "core", "ui"]);
+        assert_eq!(
+            plan.to_string(),
+            "  1. game (priority -1, bootstrap)\n  2. core (priority 0, dependency, required by game)\n  3. ui (priority 0, dependency, required by game)"
+        );
+    }
+}
@@ -20,10 +20,14 @@
 //!       it-IT/
 //!         main.ftl
 //! ```
+//!
+//! Arguments are typed: numbers stay numbers, so plural selectors
+//! (`{ $count -> [one] ... *[other] ... }`) and the `NUMBER()` / `DATETIME()`
+//! functions work in both mod and global locale files.
 
 use fluent::FluentResource;
 use fluent_bundle::concurrent::FluentBundle;
-use fluent_bundle::{FluentArgs, FluentValue};
+use fluent_bundle::{FluentArgs, FluentError, FluentValue};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -43,6 +47,52 @@ fn strip_bidi_chars(s: &str) -> String {
         .collect()
 }
 
+/// Register the functions available in locale files: `NUMBER()` and `DATETIME()`
+///
+/// `DATETIME()` takes a timestamp in milliseconds since the Unix epoch (what a
+/// JavaScript `Date` argument is converted to) and formats it as an ISO 8601
+/// UTC date, including the time when a `timeStyle` option is given.
+pub fn add_locale_functions(bundle: &mut FluentBundle<FluentResource>) -> Result<(), FluentError> {
+    bundle.add_builtins()?;
+    bundle.add_function("DATETIME", |positional, named| match positional.first() {
+        Some(FluentValue::Number(timestamp)) => {
+            FluentValue::from(format_timestamp(timestamp.value as i64, named.get("timeStyle").is_some()))
+        }
+        _ => FluentValue::Error,
+    })
+}
+
+/// Format milliseconds since the Unix epoch as `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ` (UTC)
+fn format_timestamp(millis: i64, with_time: bool) -> String {
+    const MILLIS_PER_DAY: i64 = 86_400_000;
+    let days = millis.div_euclid(MILLIS_PER_DAY);
+    let seconds_of_day = millis.rem_euclid(MILLIS_PER_DAY) / 1000;
+
+    // Civil date from days since 1970-01-01 (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let date = format!("{:04}-{:02}-{:02}", year, month, day);
+    if with_time {
+        format!(
+            "{}T{:02}:{:02}:{:02}Z",
+            date,
+            seconds_of_day / 3600,
+            seconds_of_day % 3600 / 60,
+            seconds_of_day % 60
+        )
+    } else {
+        date
+    }
+}
+
 /// Per-mod locale bundles for a single language
 struct ModLocales {
     /// Map of mod_id -> FluentBundle for that mod's locale
@@ -128,6 +178,8 @@ impl ModLocaleManager {
 
         // Create bundle with concurrent memoizer for thread-safety
         let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+        add_locale_functions(&mut bundle)
+            .map_err(|e| format!("Failed to register locale functions for mod {}: {:?}", mod_id, e))?;
 
         // Load main.ftl file
         let main_file = locale_path.join("main.ftl");
@@ -213,13 +265,18 @@ impl ModLocaleManager {
     pub fn current_locale(&self) -> String {
         self.current_locale.read().unwrap().clone()
     }
+
+    /// Locales for which at least one mod has a bundle
+    fn loaded_locales(&self) -> Vec<String> {
+        self.locales.read().unwrap().keys().cloned().collect()
+    }
 }
 
-/// Type alias for the global locale get function
-type GlobalGetFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+/// Type alias for the global locale get function (locale, id)
+type GlobalGetFn = Arc<dyn Fn(&str, &str) -> String + Send + Sync>;
 
-/// Type alias for the global locale get_with_args function
-type GlobalGetWithArgsFn = Arc<dyn Fn(&str, &HashMap<String, String>) -> String + Send + Sync>;
+/// Type alias for the global locale get_with_args function (locale, id, args)
+type GlobalGetWithArgsFn = Arc<dyn Fn(&str, &str, &FluentArgs) -> String + Send + Sync>;
 
 /// Locale API wrapper with hierarchical mod-specific locale support
 ///
@@ -228,11 +285,14 @@ type GlobalGetWithArgsFn = Arc<dyn Fn(&str, &HashMap<String, String>) -> String
 /// 2. Falls back to the global application locale
 ///
 /// The global locale functions are provided via closures to avoid tight coupling
-/// with the client's LocaleManager implementation.
+/// with the client's LocaleManager implementation. They receive the active locale,
+/// so switching it with [`LocaleApi::set_current_locale`] affects both levels.
 #[derive(Clone)]
 pub struct LocaleApi {
     /// Manager for mod-specific locales
     mod_locale_manager: Arc<ModLocaleManager>,
+    /// Locales provided by the global application locale
+    global_locales: Arc<Vec<String>>,
     /// Global locale get function (fallback)
     global_get_fn: GlobalGetFn,
     /// Global locale get_with_args function (fallback)
@@ -245,8 +305,9 @@ impl LocaleApi {
     /// # Arguments
     /// * `current_locale` - The current active locale (e.g., "en-US")
     /// * `fallback_locale` - The fallback locale (e.g., "en-US")
-    /// * `global_get_fn` - Function to get messages from global locale
-    /// * `global_get_with_args_fn` - Function to get messages with args from global locale
+    /// * `global_get_fn` - Function to get messages from global locale, given the active locale
+    /// * `global_get_with_args_fn` - Function to get messages with args from global locale,
+    ///   given the active locale
     pub fn new<G, GWA>(
         current_locale: &str,
         fallback_locale: &str,
@@ -254,16 +315,26 @@ impl LocaleApi {
         global_get_with_args_fn: GWA,
     ) -> Self
     where
-        G: Fn(&str) -> String + Send + Sync + 'static,
-        GWA: Fn(&str, &HashMap<String, String>) -> String + Send + Sync + 'static,
+        G: Fn(&str, &str) -> String + Send + Sync + 'static,
+        GWA: Fn(&str, &str, &FluentArgs) -> String + Send + Sync + 'static,
     {
         Self {
             mod_locale_manager: Arc::new(ModLocaleManager::new(current_locale, fallback_locale)),
+            global_locales: Arc::new(Vec::new()),
             global_get_fn: Arc::new(global_get_fn),
             global_get_with_args_fn: Arc::new(global_get_with_args_fn),
         }
     }
 
+    /// Declare the locales provided by the global application locale
+    ///
+    /// They are listed by [`LocaleApi::available_locales`] and can be selected with
+    /// [`LocaleApi::set_current_locale`] even if no mod translates them.
+    pub fn with_global_locales(mut self, locales: Vec<String>) -> Self {
+        self.global_locales = Arc::new(locales);
+        self
+    }
+
     /// Load locale files for a mod
     ///
     /// Should be called when loading a mod, before executing any of its code.
@@ -281,33 +352,72 @@ impl LocaleApi {
         }
 
         // Fall back to global locale
-        (self.global_get_fn)(id)
+        (self.global_get_fn)(&self.current_locale(), id)
     }
 
     /// Get a localized message with arguments for a specific mod
     ///
+    /// Arguments keep their Fluent type, so numbers drive plural selection.
     /// First checks the mod's locale, then falls back to global.
-    pub fn get_with_args(&self, mod_id: &str, id: &str, args: &HashMap<String, String>) -> String {
-        // Convert HashMap<String, String> to FluentArgs for mod locale lookup
-        let mut fluent_args = FluentArgs::new();
-        for (key, value) in args {
-            fluent_args.set(key.as_str(), FluentValue::from(value.clone()));
-        }
-
+    pub fn get_with_args(&self, mod_id: &str, id: &str, args: &FluentArgs) -> String {
         // First try mod-specific locale
-        if let Some(msg) = self.mod_locale_manager.get_mod_message(mod_id, id, Some(&fluent_args)) {
+        if let Some(msg) = self.mod_locale_manager.get_mod_message(mod_id, id, Some(args)) {
             return msg;
         }
 
         // Fall back to global locale
-        (self.global_get_with_args_fn)(id, args)
+        (self.global_get_with_args_fn)(&self.current_locale(), id, args)
     }
 
     /// Update the current locale
+    ///
+    /// The locale is not checked against the loaded ones, see
+    /// [`LocaleApi::set_current_locale`] for that.
     pub fn set_locale(&self, locale: &str) {
         self.mod_locale_manager.set_locale(locale);
     }
 
+    /// Get the sorted list of locales provided globally or by any loaded mod
+    pub fn available_locales(&self) -> Vec<String> {
+        let mut locales = self.mod_locale_manager.loaded_locales();
+        locales.extend(self.global_locales.iter().cloned());
+        locales.sort();
+        locales.dedup();
+        locales
+    }
+
+    /// Switch the active locale for every mod sharing this API
+    ///
+    /// If `locale` itself isn't loaded, a loaded locale of the same language is
+    /// used instead (e.g. "fr" or "fr-CA" selects "fr-FR").
+    ///
+    /// # Returns
+    /// * `Ok(locale)` - The locale now active
+    /// * `Err(message)` - No matching locale is loaded; the current locale is kept
+    pub fn set_current_locale(&self, locale: &str) -> Result<String, String> {
+        let available = self.available_locales();
+        let language = |tag: &str| tag.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+
+        let selected = available
+            .iter()
+            .find(|candidate| candidate.as_str() == locale)
+            .or_else(|| available.iter().find(|candidate| language(candidate) == language(locale)))
+            .ok_or_else(|| {
+                format!(
+                    "Locale '{}' is not available (available locales: {})",
+                    locale,
+                    available.join(", ")
+                )
+            })?
+            .clone();
+
+        if selected != locale {
+            debug!("Locale '{}' is not loaded, using '{}' instead", locale, selected);
+        }
+        self.mod_locale_manager.set_locale(&selected);
+        Ok(selected)
+    }
+
     /// Get the current locale
     pub fn current_locale(&self) -> String {
         self.mod_locale_manager.current_locale()
@@ -320,8 +430,91 @@ impl LocaleApi {
         Self::new(
             "en-US",
             "en-US",
-            |id| format!("[{}]", id),
-            |id, _args| format!("[{}]", id),
+            |_locale, id| format!("[{}]", id),
+            |_locale, id, _args| format!("[{}]", id),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_mod_locale(mod_dir: &Path, locale: &str, ftl: &str) {
+        let dir = mod_dir.join("locale").join(locale);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("main.ftl"), ftl).unwrap();
+    }
+
+    #[test]
+    fn test_switch_current_locale() {
+        let mod_dir = tempfile::tempdir().unwrap();
+        write_mod_locale(mod_dir.path(), "en-US", "greeting = Hello\n");
+        write_mod_locale(mod_dir.path(), "fr-FR", "greeting = Bonjour\n");
+
+        let locale_api = LocaleApi::new(
+            "en-US",
+            "en-US",
+            |locale, id| format!("[{}:{}]", locale, id),
+            |locale, id, _args| format!("[{}:{}]", locale, id),
+        )
+        .with_global_locales(vec!["en-US".to_string(), "it-IT".to_string()]);
+        locale_api.load_mod_locales("my-mod", mod_dir.path()).unwrap();
+
+        assert_eq!(locale_api.available_locales(), vec!["en-US", "fr-FR", "it-IT"]);
+        assert_eq!(locale_api.get("my-mod", "greeting"), "Hello");
+
+        assert_eq!(locale_api.set_current_locale("fr-FR").unwrap(), "fr-FR");
+        assert_eq!(locale_api.get("my-mod", "greeting"), "Bonjour");
+
+        // Global lookups follow the switch too
+        assert_eq!(locale_api.set_current_locale("it").unwrap(), "it-IT");
+        assert_eq!(locale_api.get("my-mod", "greeting"), "Hello");
+        assert_eq!(locale_api.get("my-mod", "missing"), "[it-IT:missing]");
+
+        let err = locale_api.set_current_locale("de-DE").unwrap_err();
+        assert!(err.contains("'de-DE' is not available"), "{}", err);
+        assert_eq!(locale_api.current_locale(), "it-IT");
+    }
+
+    #[test]
+    fn test_typed_args() {
+        let mod_dir = tempfile::tempdir().unwrap();
+        write_mod_locale(
+            mod_dir.path(),
+            "en-US",
+            concat!(
+                "items = { $count ->\n",
+                "    [one] One item\n",
+                "   *[other] { $count } items\n",
+                "}\n",
+                "price = { NUMBER($amount, minimumFractionDigits: 2) } coins\n",
+                "joined = Joined on { DATETIME($date) }\n",
+                "name = Hello { $name }\n",
+            ),
+        );
+        let locale_api = LocaleApi::stub();
+        locale_api.load_mod_locales("my-mod", mod_dir.path()).unwrap();
+
+        let get = |id: &str, key: &str, value: FluentValue<'static>| {
+            let mut args = FluentArgs::new();
+            args.set(key.to_string(), value);
+            locale_api.get_with_args("my-mod", id, &args)
+        };
+
+        assert_eq!(get("items", "count", FluentValue::from(1)), "One item");
+        assert_eq!(get("items", "count", FluentValue::from(3)), "3 items");
+        assert_eq!(get("items", "count", FluentValue::from(0)), "0 items");
+        assert_eq!(get("price", "amount", FluentValue::from(5)), "5.00 coins");
+        assert_eq!(get("joined", "date", FluentValue::from(1_700_000_000_000_i64)), "Joined on 2023-11-14");
+        assert_eq!(get("name", "name", FluentValue::from("Alice")), "Hello Alice");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0, false), "1970-01-01");
+        assert_eq!(format_timestamp(1_700_000_000_000, true), "2023-11-14T22:13:20Z");
+        assert_eq!(format_timestamp(951_782_400_000, false), "2000-02-29");
+        assert_eq!(format_timestamp(-86_400_000, true), "1969-12-31T00:00:00Z");
+    }
+}
@@ -4,36 +4,48 @@
 //! APIs are runtime-agnostic - they define the logic, while runtime-specific bindings
 //! (in adapters) expose them to the scripting languages.
 
+pub mod capabilities;
 pub mod console;
 pub mod events;
 pub mod file;
+pub mod filesystem;
 pub mod graphic;
+pub mod load_plan;
 pub mod locale;
 pub mod network;
+pub mod network_cache;
 pub mod path_security;
 pub mod process;
 pub mod resource;
+pub mod storage;
 pub mod system;
+pub mod timing;
 
-pub use console::ConsoleApi;
-pub use events::{EventDispatcher, EventHandler, EventKey, SystemEvents, RequestUriProtocol, RequestUri, UriResponse, SendEventRequest, TerminalKeyRequest, TerminalKeyResponse, GraphicEngineReadyRequest, GraphicEngineReadyResponse, GraphicEngineWindowClosedRequest, GraphicEngineWindowClosedResponse, CustomEventRequest, CustomEventResponse};
+pub use capabilities::{Capability, CapabilityPolicy};
+pub use console::{ConsoleApi, ConsoleFields};
+pub use events::{EventDispatcher, EventHandler, EventHandlerInfo, EventKey, HandlerRef, SystemEvents, RequestUriProtocol, RequestUri, UriResponse, SendEventRequest, TerminalKeyRequest, TerminalKeyResponse, TerminalMouseRequest, TerminalMouseResponse, TerminalPasteRequest, TerminalPasteResponse, GraphicEngineReadyRequest, GraphicEngineReadyResponse, GraphicEngineWindowClosedRequest, GraphicEngineWindowClosedResponse, CustomEventRequest, CustomEventResponse};
 pub use graphic::{
-    AlignItems, ColorValue, EdgeInsets, FlexDirection, FontConfig, FontInfo, GraphicCommand,
+    AlignItems, ColorValue, EdgeInsets, FlexDirection, FontConfig, FontInfo, FrameStats, FRAME_STATS_EVENT, GraphicCommand,
     GraphicEngine, GraphicEngineInfo, GraphicEngines, GraphicEvent, GraphicProxy,
     InitialWindowConfig, JustifyContent, KeyModifiers, MouseButton, SizeValue,
-    WindowConfig, WindowInfo, WindowMode, WindowPositionMode, EnableEngineRequest,
+    WindowConfig, WindowCursor, WindowInfo, WindowMode, WindowPositionMode, EnableEngineRequest,
     ImageScaleMode, ImageSource,
 };
+pub use load_plan::{LoadPlan, LoadReason, PlannedMod};
 pub use locale::LocaleApi;
-pub use network::{NetworkApi, NetworkConfig, DownloadResponse, ProgressCallback, parse_stam_uri, sanitize_uri};
+pub use network_cache::NetworkCache;
+pub use network::{NetworkApi, NetworkConfig, DownloadResponse, TransferStats, UriListEntry, CancelToken, STATUS_CANCELLED, DEFAULT_MAX_BUFFER_BYTES, ProgressCallback, STAM_SCHEME, STAMS_SCHEME, is_stam_uri, is_secure_stam_uri, parse_stam_uri, sanitize_uri};
 pub use path_security::{
-    PathSecurityConfig, PathValidationResult, validate_path, validate_and_resolve_path,
+    PathConfinement, PathSecurityConfig, PathValidationResult, validate_mod_id, validate_path, validate_and_resolve_path,
     is_path_permitted, make_absolute, ParsedModPath, ModPathConfig, ResolvedModPath,
     parse_mod_path, resolve_mod_path, resolve_and_validate_mod_path,
 };
 pub use file::{FileApi, ReadJsonResult};
+pub use filesystem::{DirEntryInfo, FileSystemApi};
 pub use process::{ProcessApi, AppApi};
-pub use system::{SystemApi, ModInfo, ModSide, ModPackageInfo, ModPackageManifest, ModPackagesRegistry, extract_mod_archive, extract_mod_zip, AttachModRequest, ShutdownRequest, GameInfo};
+pub use storage::StorageApi;
+pub use system::{SystemApi, ModInfo, ModSide, ModPackageInfo, ModPackageManifest, ModPackagesRegistry, extract_mod_archive, extract_mod_zip, mod_extraction_limit, DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES, ExtractProgressCallback, AttachModRequest, ShutdownRequest, BroadcastRequest, SendToClientRequest, GameInfo, GameServerInfo, JsMemoryStats};
+pub use timing::{PhaseTiming, PhaseTimer, StartupTimings};
 pub use resource::{ResourceType, ResourceState, ResourceInfo, ResourceEntry, ResourceData, ResourceProxy, LoadingState, EngineHandle, bevy_supported_extensions, is_bevy_supported_extension};
 
 use std::collections::HashMap;
@@ -44,12 +56,28 @@ use std::any::Any;
 /// This allows client and server to configure which APIs are available to mods.
 /// For example, the client might provide "process" and "client" APIs, while the
 /// server might provide "server" and "database" APIs.
+///
+/// Runtime adapters bind the APIs they know by name (see the associated constants
+/// for the names and expected types), so a new API only needs an entry here.
 pub struct ApiRegistry {
     /// Map of API name to API provider instance
     apis: HashMap<String, Box<dyn Any>>,
 }
 
 impl ApiRegistry {
+    /// `SystemApi` shared with other adapters (a fresh one is created when absent)
+    pub const SYSTEM: &'static str = "system";
+    /// `StorageApi` backing the `Storage` global (a fresh one is created when absent)
+    pub const STORAGE: &'static str = "storage";
+    /// `LocaleApi` backing the `Locale` global
+    pub const LOCALE: &'static str = "locale";
+    /// `NetworkApi` backing the `Network` global (client only)
+    pub const NETWORK: &'static str = "network";
+    /// `Arc<GraphicProxy>` backing the `Graphic` global (client only)
+    pub const GRAPHIC: &'static str = "graphic";
+    /// `Arc<ResourceProxy>` backing the `Resource` global (client only)
+    pub const RESOURCE: &'static str = "resource";
+
     /// Create a new empty API registry
     pub fn new() -> Self {
         Self {
@@ -66,6 +94,27 @@ impl ApiRegistry {
         self.apis.insert(name.into(), Box::new(api));
     }
 
+    /// Builder variant of `register`
+    pub fn with_api<T: Any>(mut self, name: impl Into<String>, api: T) -> Self {
+        self.register(name, api);
+        self
+    }
+
+    /// Remove an API and return it
+    ///
+    /// # Returns
+    /// The API if it is registered as a `T`; an API of another type stays registered
+    pub fn take<T: Any>(&mut self, name: &str) -> Option<T> {
+        let api = self.apis.remove(name)?;
+        match api.downcast::<T>() {
+            Ok(api) => Some(*api),
+            Err(api) => {
+                self.apis.insert(name.to_string(), api);
+                None
+            }
+        }
+    }
+
     /// Get an API by name
     ///
     /// # Arguments
@@ -94,3 +143,23 @@ impl Default for ApiRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_only_matching_type() {
+        let mut apis = ApiRegistry::new()
+            .with_api(ApiRegistry::LOCALE, String::from("en-US"))
+            .with_api("answer", 42u32);
+
+        assert_eq!(apis.take::<u64>("answer"), None);
+        assert!(apis.has("answer"));
+        assert_eq!(apis.take::<u32>("answer"), Some(42));
+        assert!(!apis.has("answer"));
+
+        assert_eq!(apis.take::<String>(ApiRegistry::LOCALE).as_deref(), Some("en-US"));
+        assert!(apis.list().is_empty());
+    }
+}
@@ -24,6 +24,14 @@
 //! - Regular paths are resolved relative to the current mod or data_dir
 //!
 //! Use `resolve_mod_path()` for path resolution with @mod-id support.
+//!
+//! # Filesystem Confinement
+//!
+//! File APIs that read or write (file, storage, install-from-path, ...) go through
+//! `PathConfinement`, which confines every requested path to a set of roots (the game
+//! root, a mod's data dir, ...). Paths are resolved through the real filesystem, so
+//! both `..` traversal and symlinks pointing outside a root are rejected, and paths
+//! that don't exist yet (files about to be created) are supported.
 
 use std::path::{Path, PathBuf};
 
@@ -227,10 +235,9 @@ pub fn make_absolute(path: impl AsRef<Path>, data_dir: impl AsRef<Path>) -> Path
 ///
 /// # Security
 /// This function:
-/// - Normalizes the path by resolving `.` and `..` components
-/// - Checks if the normalized path starts with the base directory
-/// - Does NOT follow symlinks (the path may not exist)
-/// - Rejects paths that would escape the base directory via `..`
+/// - Resolves the path through `PathConfinement` (existing components follow
+///   symlinks, missing ones are normalized)
+/// - Rejects paths that would escape the base directory via `..` or a symlink
 ///
 /// # Arguments
 /// * `relative_path` - The relative path to validate (must not start with `/`)
@@ -254,8 +261,7 @@ pub fn validate_path_for_creation(
         ));
     }
 
-    // Canonicalize base_dir to get the real absolute path
-    let canonical_base = base_dir.canonicalize().map_err(|e| {
+    base_dir.canonicalize().map_err(|e| {
         format!(
             "Failed to canonicalize base directory '{}': {}",
             base_dir.display(),
@@ -263,23 +269,16 @@ pub fn validate_path_for_creation(
         )
     })?;
 
-    // Build the full path
-    let full_path = canonical_base.join(relative_path);
-
-    // Normalize the path by resolving `.` and `..` components without requiring existence
-    let normalized = normalize_path_components(&full_path);
-
-    // Check that the normalized path starts with the base directory
-    if !normalized.starts_with(&canonical_base) {
-        return Err(format!(
-            "Access denied: path '{}' escapes the permitted directory '{}'. \
-             Path traversal (../) is not allowed.",
-            relative_path.display(),
-            base_dir.display()
-        ));
-    }
-
-    Ok(normalized)
+    PathConfinement::new(base_dir)
+        .confine(relative_path)
+        .map_err(|_| {
+            format!(
+                "Access denied: path '{}' escapes the permitted directory '{}'. \
+                 Path traversal (../) is not allowed.",
+                relative_path.display(),
+                base_dir.display()
+            )
+        })
 }
 
 /// Normalize a path by resolving `.` and `..` components without requiring the path to exist.
@@ -311,6 +310,157 @@ fn normalize_path_components(path: &Path) -> PathBuf {
     normalized
 }
 
+// ============================================================================
+// Filesystem Confinement
+// ============================================================================
+
+/// Confines filesystem access to one or more root directories
+///
+/// This is the single enforcement point for mod-facing file APIs. Build one with
+/// the game root and add extra roots (e.g. the mod's data dir) as needed:
+///
+/// ```ignore
+/// let confinement = PathConfinement::new(&game_root).with_root(&mod_data_dir);
+/// let path = confinement.confine("saves/slot1.json")?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathConfinement {
+    roots: Vec<PathBuf>,
+}
+
+impl PathConfinement {
+    /// Create a confinement with a single root (usually the game root)
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            roots: vec![root.into()],
+        }
+    }
+
+    /// Permit an additional root directory
+    ///
+    /// Relative paths are resolved against the roots in the order they were added.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.roots.push(root.into());
+        self
+    }
+
+    /// Get the permitted root directories
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Resolve a requested path and check that it stays inside a permitted root
+    ///
+    /// - Relative paths are resolved against each root in turn; the first root that
+    ///   contains the result wins
+    /// - Absolute paths must be inside one of the roots
+    /// - The path doesn't need to exist, but every existing component is resolved
+    ///   through the filesystem so symlinks cannot be used to escape, even behind `..`
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - The resolved absolute path
+    /// * `Err(String)` - Error message if the path escapes every root
+    pub fn confine(&self, path: impl AsRef<Path>) -> Result<PathBuf, String> {
+        let path = path.as_ref();
+
+        let canonical_roots: Vec<PathBuf> = self
+            .roots
+            .iter()
+            .filter(|root| !root.as_os_str().is_empty())
+            .filter_map(|root| root.canonicalize().ok())
+            .collect();
+
+        if canonical_roots.is_empty() {
+            return Err(format!(
+                "Access denied: no permitted directory is available to resolve '{}'",
+                path.display()
+            ));
+        }
+
+        let candidates: Vec<PathBuf> = if path.is_absolute() {
+            vec![path.to_path_buf()]
+        } else {
+            canonical_roots.iter().map(|root| root.join(path)).collect()
+        };
+
+        for candidate in candidates {
+            let resolved = resolve_through_filesystem(&candidate).map_err(|e| {
+                format!("Failed to resolve path '{}': {}", path.display(), e)
+            })?;
+            if canonical_roots.iter().any(|root| resolved.starts_with(root)) {
+                return Ok(resolved);
+            }
+        }
+
+        Err(format!(
+            "Access denied: path '{}' escapes the permitted directories. \
+             Mods can only access files within the game directory.",
+            path.display()
+        ))
+    }
+
+    /// Check whether a path stays inside a permitted root
+    pub fn is_confined(&self, path: impl AsRef<Path>) -> bool {
+        self.confine(path).is_ok()
+    }
+}
+
+/// Check that a mod id can be used as a single file or directory name
+///
+/// Mod ids become path components (`mods/<mod-id>`, `storage/<mod-id>.json`), so ids
+/// that are empty, contain separators, are `.` / `..` or use other characters are
+/// rejected before they are joined to a directory.
+pub fn validate_mod_id(mod_id: &str) -> Result<(), String> {
+    use std::path::Component;
+
+    let valid_chars = mod_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    let mut components = Path::new(mod_id).components();
+    let single_name = matches!((components.next(), components.next()), (Some(Component::Normal(_)), None));
+
+    if valid_chars && single_name {
+        Ok(())
+    } else {
+        Err(format!("Invalid mod id '{}'", mod_id))
+    }
+}
+
+/// Resolve a path through the filesystem, even if its tail doesn't exist yet
+///
+/// Components are resolved one at a time and the path is canonicalized (following
+/// symlinks) after each one that exists. Canonicalizing only the longest existing
+/// ancestor is not enough: in `dir/missing/../link/file` the `..` leads back into
+/// existing directories, and `link` must be followed like any other symlink.
+fn resolve_through_filesystem(path: &Path) -> std::io::Result<PathBuf> {
+    use std::path::Component;
+
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => continue,
+            // `resolved` has no symlinks left, so going up lexically is exact
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other),
+        }
+        match resolved.canonicalize() {
+            Ok(canonical) => resolved = canonical,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    if resolved.as_os_str().is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no existing ancestor for '{}'", path.display()),
+        ));
+    }
+    Ok(resolved)
+}
+
 // ============================================================================
 // @mod-id Path Resolution
 // ============================================================================
@@ -707,4 +857,109 @@ mod tests {
 
         matches!(result, PathValidationResult::NotFound(_));
     }
+
+    #[test]
+    fn test_confinement_blocks_traversal() {
+        let temp = tempdir().unwrap();
+        let game_root = temp.path().join("game");
+        fs::create_dir_all(game_root.join("saves")).unwrap();
+        fs::write(temp.path().join("secret.txt"), "secret").unwrap();
+
+        let confinement = PathConfinement::new(&game_root);
+
+        assert!(confinement.confine("../secret.txt").is_err());
+        assert!(confinement.confine("saves/../../secret.txt").is_err());
+        assert!(confinement.confine("missing/../../secret.txt").is_err());
+        assert!(confinement.confine(temp.path().join("secret.txt")).is_err());
+
+        let err = confinement.confine("../secret.txt").unwrap_err();
+        assert!(err.contains("Access denied"));
+    }
+
+    #[test]
+    fn test_mod_id_must_be_a_single_name() {
+        for mod_id in ["bme-core", "mods_manager", "mod.v2"] {
+            assert!(validate_mod_id(mod_id).is_ok(), "{}", mod_id);
+        }
+        for mod_id in ["", ".", "..", "../escape", "a/b", "/etc", "a\\..\\b", "mod id"] {
+            let err = validate_mod_id(mod_id).unwrap_err();
+            assert!(err.contains("Invalid mod id"), "{}: {}", mod_id, err);
+        }
+    }
+
+    #[test]
+    fn test_confinement_allows_paths_inside_root() {
+        let temp = tempdir().unwrap();
+        let game_root = temp.path().join("game");
+        fs::create_dir_all(game_root.join("saves")).unwrap();
+        let canonical_root = game_root.canonicalize().unwrap();
+
+        let confinement = PathConfinement::new(&game_root);
+
+        // Files that don't exist yet are allowed (e.g. about to be created)
+        assert_eq!(
+            confinement.confine("saves/new/slot1.json").unwrap(),
+            canonical_root.join("saves/new/slot1.json")
+        );
+        assert_eq!(
+            confinement.confine("saves/../config.json").unwrap(),
+            canonical_root.join("config.json")
+        );
+    }
+
+    #[test]
+    fn test_confinement_checks_extra_roots() {
+        let temp = tempdir().unwrap();
+        let game_root = temp.path().join("game");
+        let mod_data = temp.path().join("mod-data");
+        fs::create_dir_all(&game_root).unwrap();
+        fs::create_dir_all(&mod_data).unwrap();
+
+        let confinement = PathConfinement::new(&game_root).with_root(&mod_data);
+
+        assert!(confinement.is_confined(mod_data.join("state.json")));
+        // Relative paths can step from one root into another
+        assert!(confinement.is_confined("../mod-data/state.json"));
+        assert!(!confinement.is_confined("../../state.json"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_confinement_blocks_symlink_escape() {
+        let temp = tempdir().unwrap();
+        let game_root = temp.path().join("game");
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(&game_root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, game_root.join("link")).unwrap();
+
+        let confinement = PathConfinement::new(&game_root);
+
+        assert!(confinement.confine("link/secret.txt").is_err());
+        assert!(validate_path_for_creation("link/new.txt", &game_root).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_confinement_blocks_symlink_behind_missing_parent() {
+        let temp = tempdir().unwrap();
+        let game_root = temp.path().join("game");
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(game_root.join("dir")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(&outside, game_root.join("dir").join("link")).unwrap();
+
+        let confinement = PathConfinement::new(&game_root);
+
+        assert!(confinement.confine("dir/link/secret.txt").is_err());
+        // `missing` doesn't exist: the `..` must not skip the symlink check on `link`
+        assert!(confinement.confine("dir/missing/../link/secret.txt").is_err());
+        assert!(validate_path_for_creation("dir/missing/../link/new.txt", &game_root).is_err());
+        // A `..` through a missing directory is still fine when it stays inside
+        assert_eq!(
+            confinement.confine("dir/missing/../new.txt").unwrap(),
+            game_root.canonicalize().unwrap().join("dir").join("new.txt")
+        );
+    }
 }
@@ -1,10 +1,27 @@
 /// Process API abstraction
 ///
-/// Provides access to process and application information.
+/// Provides access to process and application information, and lets
+/// (server-side, `spawn`-capable) mods run child processes.
 /// This module is runtime-agnostic and can be used by JavaScript, Lua, C#, etc.
 
+use std::fmt;
 use std::path::PathBuf;
 use std::fs;
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// Default upper bound for a spawned child process, after which it is killed
+pub const DEFAULT_SPAWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Bytes of each output stream kept in `SpawnOutput`; anything past it is read and dropped
+pub const MAX_SPAWN_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Longest line read at once; longer lines are forwarded in pieces of this size
+const MAX_SPAWN_LINE_BYTES: u64 = 64 * 1024;
 
 /// Process API implementation
 #[derive(Clone)]
@@ -104,3 +121,212 @@ impl AppApi {
         self.process_api.app_config_path()
     }
 }
+
+/// Output stream of a child process
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    /// Name of the event emitted for each line of this stream by the streaming spawn
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "process:stdout",
+            OutputStream::Stderr => "process:stderr",
+        }
+    }
+}
+
+/// Result of a child process that ran to completion
+///
+/// A nonzero exit code is a normal result, not a `SpawnError`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpawnOutput {
+    /// Exit code (None if the process was terminated by a signal)
+    pub exit_code: Option<i32>,
+    /// What was written to stdout (lossy UTF-8, first `MAX_SPAWN_OUTPUT_BYTES`)
+    pub stdout: String,
+    /// What was written to stderr (lossy UTF-8, first `MAX_SPAWN_OUTPUT_BYTES`)
+    pub stderr: String,
+}
+
+/// Why a child process could not be run to completion
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpawnError {
+    /// The command does not exist (or is not in PATH)
+    NotFound { command: String },
+    /// The command exists but could not be started, or its output could not be read
+    Failed { command: String, message: String },
+    /// The process ran longer than the timeout and was killed
+    TimedOut { command: String, timeout: Duration },
+}
+
+impl SpawnError {
+    /// Stable error code exposed to mods (e.g. as `error.code` in JavaScript)
+    pub fn code(&self) -> &'static str {
+        match self {
+            SpawnError::NotFound { .. } => "NOT_FOUND",
+            SpawnError::Failed { .. } => "SPAWN_FAILED",
+            SpawnError::TimedOut { .. } => "TIMEOUT",
+        }
+    }
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::NotFound { command } => write!(f, "Command '{}' not found", command),
+            SpawnError::Failed { command, message } => write!(f, "Failed to run '{}': {}", command, message),
+            SpawnError::TimedOut { command, timeout } => {
+                write!(f, "Command '{}' timed out after {:.1?} and was killed", command, timeout)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpawnError {}
+
+/// Run a child process and capture its output
+///
+/// The child is killed if it is still running after `timeout`. When `lines` is
+/// set, every line of stdout/stderr is also sent there as soon as it is read
+/// (the output is still returned at the end, up to `MAX_SPAWN_OUTPUT_BYTES` per stream).
+pub async fn spawn_process(
+    command: &str,
+    args: &[String],
+    timeout: Duration,
+    lines: Option<mpsc::UnboundedSender<(OutputStream, String)>>,
+) -> Result<SpawnOutput, SpawnError> {
+    let failed = |message: String| SpawnError::Failed {
+        command: command.to_string(),
+        message,
+    };
+
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => SpawnError::NotFound {
+                command: command.to_string(),
+            },
+            _ => failed(e.to_string()),
+        })?;
+
+    let stdout = child.stdout.take().ok_or_else(|| failed("stdout not captured".to_string()))?;
+    let stderr = child.stderr.take().ok_or_else(|| failed("stderr not captured".to_string()))?;
+
+    let run = async {
+        let (stdout, stderr, status) = tokio::join!(
+            read_lines(stdout, OutputStream::Stdout, lines.clone()),
+            read_lines(stderr, OutputStream::Stderr, lines.clone()),
+            child.wait(),
+        );
+        Ok::<_, std::io::Error>(SpawnOutput {
+            exit_code: status?.code(),
+            stdout: stdout?,
+            stderr: stderr?,
+        })
+    };
+
+    match tokio::time::timeout(timeout, run).await {
+        Ok(result) => result.map_err(|e| failed(e.to_string())),
+        Err(_) => {
+            // kill_on_drop would do it too, but wait for the child so it is reaped now
+            let _ = child.kill().await;
+            Err(SpawnError::TimedOut {
+                command: command.to_string(),
+                timeout,
+            })
+        }
+    }
+}
+
+/// Read a child output stream to the end, forwarding each line if requested
+///
+/// The whole stream is drained so the child never blocks on a full pipe, but only
+/// the first `MAX_SPAWN_OUTPUT_BYTES` are kept.
+async fn read_lines(
+    reader: impl AsyncRead + Unpin,
+    stream: OutputStream,
+    lines: Option<mpsc::UnboundedSender<(OutputStream, String)>>,
+) -> std::io::Result<String> {
+    let mut reader = BufReader::new(reader);
+    let mut output = String::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if (&mut reader).take(MAX_SPAWN_LINE_BYTES).read_until(b'\n', &mut buf).await? == 0 {
+            return Ok(output);
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let room = MAX_SPAWN_OUTPUT_BYTES - output.len();
+        output.push_str(&line[..line.floor_char_boundary(room.min(line.len()))]);
+        if let Some(ref tx) = lines {
+            let _ = tx.send((stream, line.trim_end_matches(['\n', '\r']).to_string()));
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_spawn_captures_output_and_exit_code() {
+        let output = spawn_process("sh", &args(&["-c", "echo out; echo err >&2; exit 3"]), DEFAULT_SPAWN_TIMEOUT, None)
+            .await
+            .unwrap();
+        assert_eq!(output.exit_code, Some(3));
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.stderr, "err\n");
+    }
+
+    #[tokio::test]
+    async fn test_spawn_streams_lines() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let output = spawn_process("sh", &args(&["-c", "echo one; echo two"]), DEFAULT_SPAWN_TIMEOUT, Some(tx))
+            .await
+            .unwrap();
+        assert_eq!(output.exit_code, Some(0));
+
+        let mut received = Vec::new();
+        while let Ok(line) = rx.try_recv() {
+            received.push(line);
+        }
+        assert_eq!(
+            received,
+            vec![(OutputStream::Stdout, "one".to_string()), (OutputStream::Stdout, "two".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spawn_errors() {
+        let err = spawn_process("stam-no-such-command", &[], DEFAULT_SPAWN_TIMEOUT, None).await.unwrap_err();
+        assert_eq!(err.code(), "NOT_FOUND");
+
+        let err = spawn_process("sleep", &args(&["5"]), Duration::from_millis(100), None).await.unwrap_err();
+        assert_eq!(err.code(), "TIMEOUT");
+        assert!(err.to_string().contains("timed out"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_output_is_capped() {
+        // One huge line without newlines, then a normal line on stderr
+        let script = format!("head -c {} /dev/zero | tr '\\0' x; echo done >&2", MAX_SPAWN_OUTPUT_BYTES * 3);
+        let output = spawn_process("sh", &args(&["-c", &script]), DEFAULT_SPAWN_TIMEOUT, None).await.unwrap();
+        assert_eq!(output.exit_code, Some(0));
+        assert_eq!(output.stdout.len(), MAX_SPAWN_OUTPUT_BYTES);
+        assert!(output.stdout.bytes().all(|b| b == b'x'));
+        assert_eq!(output.stderr, "done\n");
+    }
+}
@@ -0,0 +1,229 @@
+//! Storage API abstraction
+//!
+//! Provides persistent key-value storage for mods (settings, save slots, ...).
+//! Each mod gets its own JSON file at `{game_root}/storage/{mod_id}.json`, so one
+//! mod can never read or overwrite another mod's keys.
+//! This module is runtime-agnostic and can be used by JavaScript, Lua, C#, etc.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+
+use serde_json::{Map, Value};
+
+use super::path_security::{PathConfinement, validate_mod_id};
+
+/// Name of the directory (under the game root) holding the storage files
+const STORAGE_DIR: &str = "storage";
+
+/// Storage API implementation
+///
+/// Cloning is cheap and all clones share the same game root, so the root can be
+/// injected after the API has been handed to the runtime (like `SystemApi::set_home_dir`).
+#[derive(Clone, Default)]
+pub struct StorageApi {
+    /// Game root directory (storage files live in `{game_root}/storage`)
+    game_root: Arc<RwLock<Option<PathBuf>>>,
+    /// Serializes read-modify-write cycles so concurrent writes don't drop keys
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl StorageApi {
+    /// Create a new StorageApi without a game root
+    ///
+    /// All operations fail until `set_game_root()` is called.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the game root directory
+    pub fn set_game_root(&self, path: PathBuf) {
+        let mut root = self.game_root.write().unwrap();
+        *root = Some(path);
+    }
+
+    /// Get the game root directory
+    pub fn get_game_root(&self) -> Option<PathBuf> {
+        self.game_root.read().unwrap().clone()
+    }
+
+    /// Get a value stored by a mod
+    ///
+    /// # Returns
+    /// * `Ok(Some(value))` - The stored value
+    /// * `Ok(None)` - The key does not exist
+    pub fn get(&self, mod_id: &str, key: &str) -> Result<Option<Value>, String> {
+        Ok(self.load(mod_id)?.remove(key))
+    }
+
+    /// Store a value for a mod, replacing any previous value
+    pub fn set(&self, mod_id: &str, key: &str, value: Value) -> Result<(), String> {
+        let _guard = self.write_lock.lock().unwrap();
+        let mut entries = self.load(mod_id)?;
+        entries.insert(key.to_string(), value);
+        self.save(mod_id, &entries)
+    }
+
+    /// Delete a key stored by a mod
+    ///
+    /// # Returns
+    /// `true` if the key existed
+    pub fn delete(&self, mod_id: &str, key: &str) -> Result<bool, String> {
+        let _guard = self.write_lock.lock().unwrap();
+        let mut entries = self.load(mod_id)?;
+        if entries.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.save(mod_id, &entries)?;
+        Ok(true)
+    }
+
+    /// List all keys stored by a mod (sorted)
+    pub fn keys(&self, mod_id: &str) -> Result<Vec<String>, String> {
+        let mut keys: Vec<String> = self.load(mod_id)?.into_iter().map(|(key, _)| key).collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Path of the storage file for a mod
+    ///
+    /// The mod id becomes a file name, so anything that could escape the
+    /// storage directory is rejected. The path is confined to the game root, so a
+    /// storage directory or file symlinked outside of it is rejected as well.
+    fn storage_file(&self, mod_id: &str) -> Result<PathBuf, String> {
+        validate_mod_id(mod_id).map_err(|e| format!("Storage is not available: {}", e))?;
+
+        let root = self
+            .get_game_root()
+            .ok_or("Storage is not available: game root directory not configured")?;
+        PathConfinement::new(root).confine(Path::new(STORAGE_DIR).join(format!("{}.json", mod_id)))
+    }
+
+    /// Read all entries of a mod (empty if nothing was stored yet)
+    fn load(&self, mod_id: &str) -> Result<Map<String, Value>, String> {
+        let path = self.storage_file(mod_id)?;
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Map::new()),
+            Err(e) => return Err(format!("Failed to read storage file '{}': {}", path.display(), e)),
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(Value::Object(entries)) => Ok(entries),
+            Ok(_) => Err(format!("Storage file '{}' is not a JSON object", path.display())),
+            Err(e) => Err(format!("Storage file '{}' contains invalid JSON: {}", path.display(), e)),
+        }
+    }
+
+    /// Write all entries of a mod atomically (temp file + rename)
+    fn save(&self, mod_id: &str, entries: &Map<String, Value>) -> Result<(), String> {
+        let path = self.storage_file(mod_id)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create storage directory '{}': {}", dir.display(), e))?;
+        }
+
+        let json = serde_json::to_string_pretty(entries)
+            .map_err(|e| format!("Failed to serialize storage for mod '{}': {}", mod_id, e))?;
+
+        // A crash mid-write leaves only the temp file behind, never a truncated store
+        let tmp_path = path.with_extension("json.tmp");
+        let write_tmp = || -> std::io::Result<()> {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()
+        };
+        write_tmp().map_err(|e| format!("Failed to write storage file '{}': {}", tmp_path.display(), e))?;
+
+        fs::rename(&tmp_path, &path)
+            .map_err(|e| format!("Failed to replace storage file '{}': {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn storage_in(dir: &std::path::Path) -> StorageApi {
+        let storage = StorageApi::new();
+        storage.set_game_root(dir.to_path_buf());
+        storage
+    }
+
+    #[test]
+    fn test_set_get_delete_keys() {
+        let temp = tempdir().unwrap();
+        let storage = storage_in(temp.path());
+
+        assert_eq!(storage.get("my-mod", "volume").unwrap(), None);
+        storage.set("my-mod", "volume", json!(0.5)).unwrap();
+        storage.set("my-mod", "slots", json!([{ "name": "slot1" }])).unwrap();
+
+        assert_eq!(storage.get("my-mod", "volume").unwrap(), Some(json!(0.5)));
+        assert_eq!(storage.keys("my-mod").unwrap(), vec!["slots", "volume"]);
+        assert!(temp.path().join("storage").join("my-mod.json").is_file());
+        assert!(!temp.path().join("storage").join("my-mod.json.tmp").exists());
+
+        assert!(storage.delete("my-mod", "volume").unwrap());
+        assert!(!storage.delete("my-mod", "volume").unwrap());
+        assert_eq!(storage.keys("my-mod").unwrap(), vec!["slots"]);
+
+        // A fresh instance reads back what was persisted
+        let reloaded = storage_in(temp.path());
+        assert_eq!(
+            reloaded.get("my-mod", "slots").unwrap(),
+            Some(json!([{ "name": "slot1" }]))
+        );
+    }
+
+    #[test]
+    fn test_mods_are_isolated() {
+        let temp = tempdir().unwrap();
+        let storage = storage_in(temp.path());
+
+        storage.set("mod-a", "secret", json!("a")).unwrap();
+        assert_eq!(storage.get("mod-b", "secret").unwrap(), None);
+        assert!(storage.keys("mod-b").unwrap().is_empty());
+
+        for mod_id in ["../mod-a", "..", "", "a/b"] {
+            let err = storage.set(mod_id, "secret", json!("b")).unwrap_err();
+            assert!(err.contains("Invalid mod id"), "{}: {}", mod_id, err);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_out_of_the_game_root_are_rejected() {
+        let temp = tempdir().unwrap();
+        let game_root = temp.path().join("game");
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(&game_root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("victim.json"), "{}").unwrap();
+        let storage = storage_in(&game_root);
+
+        // A symlinked file inside the storage dir
+        fs::create_dir_all(game_root.join(STORAGE_DIR)).unwrap();
+        std::os::unix::fs::symlink(outside.join("victim.json"), game_root.join(STORAGE_DIR).join("my-mod.json")).unwrap();
+        let err = storage.set("my-mod", "key", json!(1)).unwrap_err();
+        assert!(err.contains("Access denied"), "{}", err);
+
+        // The whole storage dir symlinked elsewhere
+        fs::remove_dir_all(game_root.join(STORAGE_DIR)).unwrap();
+        std::os::unix::fs::symlink(&outside, game_root.join(STORAGE_DIR)).unwrap();
+        let err = storage.get("victim", "key").unwrap_err();
+        assert!(err.contains("Access denied"), "{}", err);
+
+        assert_eq!(fs::read_to_string(outside.join("victim.json")).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_requires_game_root() {
+        let storage = StorageApi::new();
+        let err = storage.get("my-mod", "key").unwrap_err();
+        assert!(err.contains("not configured"));
+    }
+}
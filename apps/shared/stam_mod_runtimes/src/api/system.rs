@@ -3,12 +3,15 @@
 //! Provides access to mod information and system state.
 //! The `system.get_mods()` function returns an array of mod info objects.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc, oneshot};
-use super::events::EventDispatcher;
+use tokio::sync::{mpsc, oneshot, Notify};
+use super::events::{EventDispatcher, EventHandlerInfo};
+use super::load_plan::LoadPlan;
+use super::path_security::{PathConfinement, validate_mod_id};
+use super::timing::{PhaseTiming, StartupTimings};
 
 /// Request to attach (load and initialize) a mod at runtime
 ///
@@ -24,12 +27,89 @@ pub struct AttachModRequest {
 
 /// Request for graceful shutdown
 ///
-/// This is used by `system.exit(code)` to request a graceful shutdown
-/// instead of terminating the process immediately.
-#[derive(Debug)]
+/// This is used by `system.exit(code, message, isError)` to request a graceful shutdown
+/// instead of terminating the process immediately. The host logs the message (at ERROR
+/// when `is_error`) before shutting down, and exits with `exit_code`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ShutdownRequest {
     /// The exit code (0 = success, non-zero = error)
     pub exit_code: i32,
+    /// Why the mod requested the shutdown
+    pub message: Option<String>,
+    /// Whether the shutdown is due to an error (default: exit code is non-zero)
+    pub is_error: bool,
+    /// The mod that requested the shutdown
+    pub mod_id: Option<String>,
+}
+
+impl ShutdownRequest {
+    /// Create a request with the given exit code, an error if the code is non-zero
+    pub fn new(exit_code: i32) -> Self {
+        Self {
+            exit_code,
+            message: None,
+            is_error: exit_code != 0,
+            mod_id: None,
+        }
+    }
+
+    /// Set the reason shown before shutdown
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Set whether the shutdown is due to an error
+    pub fn with_is_error(mut self, is_error: bool) -> Self {
+        self.is_error = is_error;
+        self
+    }
+
+    /// Set the mod that requested the shutdown
+    pub fn with_mod_id(mut self, mod_id: impl Into<String>) -> Self {
+        self.mod_id = Some(mod_id.into());
+        self
+    }
+
+    /// Describe the request for logs, e.g. `Shutdown requested by mod 'game' with exit code 1: save failed`
+    pub fn describe(&self) -> String {
+        let mut text = match &self.mod_id {
+            Some(mod_id) => format!("Shutdown requested by mod '{}' with exit code {}", mod_id, self.exit_code),
+            None => format!("Shutdown requested by mod with exit code {}", self.exit_code),
+        };
+        if let Some(message) = &self.message {
+            text.push_str(": ");
+            text.push_str(message);
+        }
+        text
+    }
+}
+
+/// Request to broadcast a message to every client of the game
+///
+/// This is used by `system.broadcast(channel, data)` on the server; the main
+/// loop forwards it to the connected clients as a `GameMessage::Broadcast`.
+#[derive(Debug, Clone)]
+pub struct BroadcastRequest {
+    /// Channel name (custom event name on the client)
+    pub channel: String,
+    /// JSON-encoded payload
+    pub payload: String,
+}
+
+/// Request to send a message to a single connected client
+///
+/// This is used by `system.sendToClient(clientId, channel, data)` on the server.
+#[derive(Debug)]
+pub struct SendToClientRequest {
+    /// Client ID, as carried by the `client:connected` event
+    pub client_id: String,
+    /// Channel name (custom event name on the client)
+    pub channel: String,
+    /// JSON-encoded payload
+    pub payload: String,
+    /// Channel to send the result back to the caller
+    pub response_tx: oneshot::Sender<Result<(), String>>,
 }
 
 /// Filter for mod packages (client or server side)
@@ -62,10 +142,10 @@ pub struct ModPackageManifest {
     pub name: String,
     pub version: String,
     pub description: String,
-    /// Entry point file for the mod (e.g., "index.js")
+    /// Entry point file for the mod (e.g., "index.js"), or an array of scripts
     /// Optional - mods without entry_point are asset-only (automatically considered attached)
     #[serde(default)]
-    pub entry_point: Option<String>,
+    pub entry_point: serde_json::Value, // Can be null, string or array
     #[serde(default)]
     pub execute_on: serde_json::Value, // Can be string or array
     #[serde(default)]
@@ -147,10 +227,6 @@ struct InstalledModManifest {
     pub name: String,
     pub version: String,
     pub description: String,
-    /// Entry point file for the mod (e.g., "index.js")
-    /// Optional - mods without entry_point are asset-only (automatically considered attached)
-    #[serde(default)]
-    pub entry_point: Option<String>,
     #[serde(default)]
     pub priority: i32,
     #[serde(rename = "type", default)]
@@ -202,6 +278,30 @@ pub struct GameInfo {
     pub version: String,
 }
 
+/// Information about the game server the client is connected to (client-side only)
+#[derive(Clone, Debug, Default)]
+pub struct GameServerInfo {
+    /// Server version announced in the `Welcome` handshake
+    pub server_version: String,
+    /// The game display name
+    pub game_name: String,
+    /// The game version
+    pub game_version: String,
+    /// Wire protocol version (`stam_protocol::PROTOCOL_VERSION`)
+    pub protocol_version: u32,
+}
+
+/// JavaScript heap usage reported by QuickJS
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JsMemoryStats {
+    /// Bytes in use by JavaScript values, strings, atoms and shapes
+    pub used_bytes: u64,
+    /// Bytes currently allocated by the QuickJS allocator
+    pub allocated_bytes: u64,
+    /// Number of live JavaScript objects
+    pub object_count: u64,
+}
+
 /// System API providing access to mod registry and system state
 ///
 /// This API is shared across all mod contexts and provides read-only
@@ -225,8 +325,28 @@ pub struct SystemApi {
     shutdown_request_tx: Arc<RwLock<Option<mpsc::Sender<ShutdownRequest>>>>,
     /// Channel receiver for shutdown requests (main loop)
     shutdown_request_rx: Arc<tokio::sync::Mutex<Option<mpsc::Receiver<ShutdownRequest>>>>,
+    /// Channel sender for broadcast requests (JS -> main loop, server-only)
+    broadcast_request_tx: Arc<RwLock<Option<mpsc::Sender<BroadcastRequest>>>>,
+    /// Channel receiver for broadcast requests (main loop)
+    broadcast_request_rx: Arc<tokio::sync::Mutex<Option<mpsc::Receiver<BroadcastRequest>>>>,
+    /// Channel sender for send-to-client requests (JS -> main loop, server-only)
+    send_to_client_request_tx: Arc<RwLock<Option<mpsc::Sender<SendToClientRequest>>>>,
+    /// Channel receiver for send-to-client requests (main loop)
+    send_to_client_request_rx: Arc<tokio::sync::Mutex<Option<mpsc::Receiver<SendToClientRequest>>>>,
     /// Game information (client-side only, None on server)
     game_info: Arc<RwLock<Option<GameInfo>>>,
+    /// Connected server information (client-side only, None on server)
+    server_info: Arc<RwLock<Option<GameServerInfo>>>,
+    /// Startup phase timings for `system.getStartupTimings()`
+    startup_timings: Arc<RwLock<StartupTimings>>,
+    /// Resolved mod load order, for `System.getLoadOrder()`
+    load_plan: Arc<RwLock<LoadPlan>>,
+    /// Latest JavaScript heap sample, for `System.getJsMemoryStats()`
+    js_memory_stats: Arc<RwLock<Option<JsMemoryStats>>>,
+    /// Wakes the JavaScript memory monitor when a mod calls `System.gc()`
+    gc_request: Arc<Notify>,
+    /// Owner mod of each service registered with `System.registerService()`, by service name
+    services: Arc<RwLock<BTreeMap<String, String>>>,
 }
 
 impl SystemApi {
@@ -236,6 +356,10 @@ impl SystemApi {
         let (attach_tx, attach_rx) = mpsc::channel::<AttachModRequest>(16);
         // Create mpsc channel for shutdown requests (capacity 1 is enough)
         let (shutdown_tx, shutdown_rx) = mpsc::channel::<ShutdownRequest>(1);
+        // Create mpsc channel for broadcast requests (buffered with capacity 64)
+        let (broadcast_tx, broadcast_rx) = mpsc::channel::<BroadcastRequest>(64);
+        // Create mpsc channel for send-to-client requests (buffered with capacity 64)
+        let (send_to_client_tx, send_to_client_rx) = mpsc::channel::<SendToClientRequest>(64);
 
         Self {
             mods: Arc::new(RwLock::new(Vec::new())),
@@ -246,7 +370,17 @@ impl SystemApi {
             attach_request_rx: Arc::new(tokio::sync::Mutex::new(Some(attach_rx))),
             shutdown_request_tx: Arc::new(RwLock::new(Some(shutdown_tx))),
             shutdown_request_rx: Arc::new(tokio::sync::Mutex::new(Some(shutdown_rx))),
+            broadcast_request_tx: Arc::new(RwLock::new(Some(broadcast_tx))),
+            broadcast_request_rx: Arc::new(tokio::sync::Mutex::new(Some(broadcast_rx))),
+            send_to_client_request_tx: Arc::new(RwLock::new(Some(send_to_client_tx))),
+            send_to_client_request_rx: Arc::new(tokio::sync::Mutex::new(Some(send_to_client_rx))),
             game_info: Arc::new(RwLock::new(None)),
+            server_info: Arc::new(RwLock::new(None)),
+            startup_timings: Arc::new(RwLock::new(StartupTimings::new())),
+            load_plan: Arc::new(RwLock::new(LoadPlan::new())),
+            js_memory_stats: Arc::new(RwLock::new(None)),
+            gc_request: Arc::new(Notify::new()),
+            services: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 
@@ -276,6 +410,126 @@ impl SystemApi {
         info.clone()
     }
 
+    /// Set the information about the connected game server (client-side only)
+    ///
+    /// This should be called on the client after the handshake with the game server.
+    /// On the server, this should NOT be called - leave server_info as None.
+    pub fn set_server_info(
+        &self,
+        server_version: impl Into<String>,
+        game_name: impl Into<String>,
+        game_version: impl Into<String>,
+        protocol_version: u32,
+    ) {
+        let mut info = self.server_info.write().unwrap();
+        *info = Some(GameServerInfo {
+            server_version: server_version.into(),
+            game_name: game_name.into(),
+            game_version: game_version.into(),
+            protocol_version,
+        });
+    }
+
+    /// Get the information about the connected game server (client-side only)
+    ///
+    /// Returns None on server (server_info not set) or if not yet connected to a game.
+    pub fn get_server_info(&self) -> Option<GameServerInfo> {
+        let info = self.server_info.read().unwrap();
+        info.clone()
+    }
+
+    /// Set the startup timing collector
+    ///
+    /// The collector is shared, so phases recorded after this call are visible too.
+    pub fn set_startup_timings(&self, timings: StartupTimings) {
+        let mut guard = self.startup_timings.write().unwrap();
+        *guard = timings;
+    }
+
+    /// Get a snapshot of the completed startup phases
+    pub fn get_startup_timings(&self) -> Vec<PhaseTiming> {
+        self.startup_timings.read().unwrap().phases()
+    }
+
+    /// Set the resolved mod load order
+    pub fn set_load_plan(&self, plan: LoadPlan) {
+        *self.load_plan.write().unwrap() = plan;
+    }
+
+    /// Get the resolved mod load order (empty until the host resolved it)
+    pub fn get_load_plan(&self) -> LoadPlan {
+        self.load_plan.read().unwrap().clone()
+    }
+
+    /// Record the latest JavaScript heap sample
+    pub fn set_js_memory_stats(&self, stats: JsMemoryStats) {
+        *self.js_memory_stats.write().unwrap() = Some(stats);
+    }
+
+    /// Get the latest JavaScript heap sample (None until the host took one)
+    pub fn get_js_memory_stats(&self) -> Option<JsMemoryStats> {
+        *self.js_memory_stats.read().unwrap()
+    }
+
+    /// Ask the host to run the JavaScript garbage collector
+    ///
+    /// Called by `System.gc()`. The collection can't run inside the calling job, so
+    /// the host runs it once the job returns; requests made before then coalesce.
+    pub fn request_gc(&self) {
+        self.gc_request.notify_one();
+    }
+
+    /// Wait until a mod requests a garbage collection
+    pub async fn gc_requested(&self) {
+        self.gc_request.notified().await;
+    }
+
+    /// Record that `mod_id` provides the service `name`
+    ///
+    /// A service name belongs to the first mod that registers it; that mod can register
+    /// it again (e.g. to replace the object), any other mod gets an error. The runtime
+    /// keeps the service object itself.
+    pub fn register_service(&self, name: &str, mod_id: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("Service name cannot be empty".to_string());
+        }
+        let mut services = self.services.write().unwrap();
+        match services.get(name) {
+            Some(owner) if owner != mod_id => Err(format!(
+                "Service '{}' is already registered by mod '{}'",
+                name, owner
+            )),
+            _ => {
+                services.insert(name.to_string(), mod_id.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the mod providing the service `name`, if registered
+    pub fn service_owner(&self, name: &str) -> Option<String> {
+        self.services.read().unwrap().get(name).cloned()
+    }
+
+    /// Names of the registered services, sorted
+    pub fn service_names(&self) -> Vec<String> {
+        self.services.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Remove the services registered by a mod (on unload), returning their names
+    pub fn unregister_mod_services(&self, mod_id: &str) -> Vec<String> {
+        let mut services = self.services.write().unwrap();
+        let names: Vec<String> = services
+            .iter()
+            .filter(|(_, owner)| owner.as_str() == mod_id)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &names {
+            services.remove(name);
+        }
+        names
+    }
+
     /// Send a request to attach a mod and wait for the result
     ///
     /// This is called by the JS binding `system.attach_mod(mod_id)`.
@@ -313,9 +567,9 @@ impl SystemApi {
 
     /// Send a shutdown request
     ///
-    /// This is called by `system.exit(code)` to request a graceful shutdown
-    /// instead of terminating the process immediately.
-    pub fn request_shutdown(&self, exit_code: i32) -> Result<(), String> {
+    /// This is called by `system.exit(code, message, isError)` to request a graceful
+    /// shutdown instead of terminating the process immediately.
+    pub fn request_shutdown(&self, request: ShutdownRequest) -> Result<(), String> {
         let tx = {
             let guard = self.shutdown_request_tx.read().unwrap();
             guard.clone()
@@ -324,7 +578,7 @@ impl SystemApi {
         let tx = tx.ok_or_else(|| "Shutdown request channel not available".to_string())?;
 
         // Use try_send since we don't want to block
-        tx.try_send(ShutdownRequest { exit_code })
+        tx.try_send(request)
             .map_err(|e| format!("Failed to send shutdown request: {}", e))
     }
 
@@ -336,6 +590,60 @@ impl SystemApi {
         guard.take()
     }
 
+    /// Send a request to broadcast a message to every client of the game
+    ///
+    /// This is called by the JS binding `system.broadcast(channel, data)`.
+    /// Waits if the main loop is behind, so a burst of broadcasts is not lost.
+    pub async fn request_broadcast(&self, channel: String, payload: String) -> Result<(), String> {
+        let tx = {
+            let guard = self.broadcast_request_tx.read().unwrap();
+            guard.clone()
+        };
+
+        let tx = tx.ok_or_else(|| "Broadcast request channel not available".to_string())?;
+
+        tx.send(BroadcastRequest { channel, payload })
+            .await
+            .map_err(|_| "Failed to send broadcast request".to_string())
+    }
+
+    /// Take the broadcast request receiver (can only be called once)
+    ///
+    /// This is used by the server main loop to forward broadcasts to clients.
+    pub async fn take_broadcast_receiver(&self) -> Option<mpsc::Receiver<BroadcastRequest>> {
+        let mut guard = self.broadcast_request_rx.lock().await;
+        guard.take()
+    }
+
+    /// Send a message to a single client and wait for the delivery result
+    ///
+    /// This is called by the JS binding `system.sendToClient(clientId, channel, data)`.
+    /// Fails if the target client is not connected (or disconnected meanwhile).
+    pub async fn request_send_to_client(&self, client_id: String, channel: String, payload: String) -> Result<(), String> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let tx = {
+            let guard = self.send_to_client_request_tx.read().unwrap();
+            guard.clone()
+        };
+
+        let tx = tx.ok_or_else(|| "Send-to-client request channel not available".to_string())?;
+
+        tx.send(SendToClientRequest { client_id, channel, payload, response_tx })
+            .await
+            .map_err(|_| "Failed to send send-to-client request".to_string())?;
+
+        response_rx.await.map_err(|_| "Send-to-client request was cancelled".to_string())?
+    }
+
+    /// Take the send-to-client request receiver (can only be called once)
+    ///
+    /// This is used by the server main loop to deliver messages to clients.
+    pub async fn take_send_to_client_receiver(&self) -> Option<mpsc::Receiver<SendToClientRequest>> {
+        let mut guard = self.send_to_client_request_rx.lock().await;
+        guard.take()
+    }
+
     /// Set the mod packages registry (loaded from mod-packages.json)
     pub fn set_mod_packages(&self, registry: ModPackagesRegistry) {
         let mut packages = self.mod_packages.write().unwrap();
@@ -383,6 +691,11 @@ impl SystemApi {
         &self.event_dispatcher
     }
 
+    /// Snapshot of the registered event handlers, for `System.listEventHandlers()`
+    pub fn describe_event_handlers(&self) -> Vec<EventHandlerInfo> {
+        self.event_dispatcher.describe()
+    }
+
     /// Add a mod to the registry
     ///
     /// This should be called when a mod is loaded into the runtime.
@@ -461,24 +774,33 @@ impl SystemApi {
     /// After extraction, reads the manifest and registers the mod with `loaded=false`.
     ///
     /// # Arguments
-    /// * `archive_path` - Path to the tar.gz file to extract
-    /// * `mod_id` - The mod identifier (directory name)
+    /// * `archive_path` - Path to the tar.gz file to extract, confined to the home
+    ///   directory (downloads land in its `tmp` dir)
+    /// * `mod_id` - The mod identifier (directory name), rejected unless it is a single name
     ///
     /// # Returns
     /// Ok(PathBuf) with the mod installation path on success, or Err(String) on failure
     pub fn install_mod_from_archive(&self, archive_path: &std::path::Path, mod_id: &str) -> Result<PathBuf, String> {
-        let mods_dir = self.get_mods_dir()
+        validate_mod_id(mod_id)?;
+
+        let home_dir = self.get_home_dir()
             .ok_or_else(|| "Home directory not configured".to_string())?;
+        let archive_path = PathConfinement::new(&home_dir)
+            .with_root(home_dir.join("tmp"))
+            .confine(archive_path)?;
+        let archive_path = archive_path.as_path();
 
-        let mod_target_dir = mods_dir.join(mod_id);
+        let mod_target_dir = home_dir.join("mods").join(mod_id);
 
         tracing::debug!("Installing mod '{}' from {} to {}",
             mod_id,
             archive_path.display(),
             mod_target_dir.display());
 
-        // Use the standalone extraction function
-        extract_mod_archive(archive_path, &mod_target_dir)?;
+        // Use the standalone extraction function, capped at the size announced by the server
+        let announced = self.get_mod(mod_id).and_then(|info| info.uncompressed_bytes).unwrap_or(0);
+        let max_bytes = mod_extraction_limit(announced);
+        extract_mod_archive(archive_path, &mod_target_dir, max_bytes, None)?;
 
         // Read manifest and register the mod with loaded=false
         // Check client/ subdirectory first, then root
@@ -629,22 +951,47 @@ impl Default for SystemApi {
     }
 }
 
+/// Callback reporting extraction progress as (archive bytes processed, archive size)
+pub type ExtractProgressCallback<'a> = &'a dyn Fn(u64, u64);
+
+/// Extracted size cap of a mod archive whose uncompressed size the server did not announce
+pub const DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Extracted size cap of a mod archive
+///
+/// `announced` is the uncompressed size sent by the server; 0 (older servers, or a
+/// server that does not know it) falls back to `DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES`
+/// so extraction is never uncapped.
+pub fn mod_extraction_limit(announced: u64) -> u64 {
+    if announced > 0 { announced } else { DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES }
+}
+
 /// Extract a mod from a tar.gz archive to a target directory
 ///
 /// This is a standalone function that can be used without a SystemApi instance.
 /// It extracts the tar.gz archive contents to the specified target directory.
 /// If the target directory already exists, it is removed first.
 ///
+/// Archives come from the server, so extraction is hardened: entries whose path is
+/// absolute or contains `..`, symlinks, hard links and special files are refused,
+/// and the total uncompressed size is capped to stop decompression bombs. On
+/// any failure the partially extracted target directory is deleted.
+///
 /// # Arguments
 /// * `archive_path` - Path to the tar.gz file to extract
 /// * `target_dir` - Target directory where the mod will be extracted
+/// * `max_bytes` - Maximum total size of the extracted files (see `mod_extraction_limit`)
+/// * `progress` - Optional callback invoked after each entry with the compressed
+///   bytes consumed so far and the archive size (a final call reports completion)
 ///
 /// # Returns
 /// Ok(()) on success, or Err(String) on failure
-pub fn extract_mod_archive(archive_path: &std::path::Path, target_dir: &std::path::Path) -> Result<(), String> {
-    use flate2::read::GzDecoder;
-    use tar::Archive;
-
+pub fn extract_mod_archive(
+    archive_path: &std::path::Path,
+    target_dir: &std::path::Path,
+    max_bytes: u64,
+    progress: Option<ExtractProgressCallback<'_>>,
+) -> Result<(), String> {
     tracing::debug!("Extracting tar.gz {} to {}",
         archive_path.display(),
         target_dir.display());
@@ -659,27 +1006,318 @@ pub fn extract_mod_archive(archive_path: &std::path::Path, target_dir: &std::pat
     std::fs::create_dir_all(target_dir)
         .map_err(|e| format!("Failed to create target directory: {}", e))?;
 
+    if let Err(e) = unpack_mod_archive(archive_path, target_dir, max_bytes, progress) {
+        // Never leave a half-extracted (or tampered) mod behind
+        if let Err(cleanup) = std::fs::remove_dir_all(target_dir) {
+            tracing::warn!("Failed to remove partially extracted mod '{}': {}", target_dir.display(), cleanup);
+        }
+        return Err(e);
+    }
+
+    tracing::debug!("tar.gz extracted successfully to {}", target_dir.display());
+    Ok(())
+}
+
+/// Unpack the entries of a tar.gz archive into an existing directory (see `extract_mod_archive`)
+fn unpack_mod_archive(
+    archive_path: &std::path::Path,
+    target_dir: &std::path::Path,
+    max_bytes: u64,
+    progress: Option<ExtractProgressCallback<'_>>,
+) -> Result<(), String> {
+    use flate2::read::GzDecoder;
+    use tar::{Archive, EntryType};
+
     // Open tar.gz file
     let tar_gz_file = std::fs::File::open(archive_path)
         .map_err(|e| format!("Failed to open tar.gz file: {}", e))?;
+    let archive_size = tar_gz_file.metadata().map(|m| m.len()).unwrap_or(0);
 
-    // Create gzip decoder
-    let gz_decoder = GzDecoder::new(tar_gz_file);
+    // Create gzip decoder over a reader counting the compressed bytes consumed
+    let consumed = std::cell::Cell::new(0u64);
+    let gz_decoder = GzDecoder::new(CountingReader { inner: tar_gz_file, count: &consumed });
 
     // Create tar archive reader
     let mut archive = Archive::new(gz_decoder);
 
-    // Extract all entries to target directory
-    archive.unpack(target_dir)
-        .map_err(|e| format!("Failed to extract tar.gz archive: {}", e))?;
+    // Extract entries one by one, refusing any that could land outside the target directory
+    let entries = archive.entries()
+        .map_err(|e| format!("Failed to read tar.gz archive: {}", e))?;
+    let mut extracted_bytes: u64 = 0;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Failed to read tar.gz entry: {}", e))?;
 
-    tracing::debug!("tar.gz extracted successfully to {}", target_dir.display());
+        let path = entry.path().map_err(|e| format!("Invalid tar.gz entry path: {}", e))?;
+        if !is_contained_path(&path) {
+            return Err(format!("Archive entry '{}' escapes the mod directory", path.display()));
+        }
+        match entry.header().entry_type() {
+            EntryType::Regular | EntryType::Continuous | EntryType::Directory | EntryType::XGlobalHeader => {}
+            EntryType::Symlink | EntryType::Link => {
+                return Err(format!("Archive entry '{}' is a link, links are not allowed in mods", path.display()));
+            }
+            other => {
+                return Err(format!("Archive entry '{}' has unsupported type {:?}", path.display(), other));
+            }
+        }
+
+        // The header size is exactly what unpacking writes, so check it before writing anything
+        extracted_bytes = extracted_bytes.saturating_add(entry.size());
+        if extracted_bytes > max_bytes {
+            return Err(format!(
+                "Archive expands beyond the allowed {} bytes (entry '{}')",
+                max_bytes,
+                path.display()
+            ));
+        }
+
+        entry.unpack_in(target_dir)
+            .map_err(|e| format!("Failed to extract tar.gz archive: {}", e))?;
+
+        if let Some(progress) = progress {
+            progress(consumed.get().min(archive_size), archive_size);
+        }
+    }
+
+    if let Some(progress) = progress {
+        progress(archive_size, archive_size);
+    }
     Ok(())
 }
 
+/// Whether a relative archive path stays inside the directory it is extracted to
+fn is_contained_path(path: &std::path::Path) -> bool {
+    use std::path::Component;
+
+    path.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Reader wrapper counting the bytes read from `inner`
+struct CountingReader<'a, R> {
+    inner: R,
+    count: &'a std::cell::Cell<u64>,
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.set(self.count.get() + read as u64);
+        Ok(read)
+    }
+}
+
 // Keep old name as alias for backwards compatibility during transition
 #[doc(hidden)]
 #[deprecated(note = "Use extract_mod_archive instead - format changed from zip to tar.gz")]
 pub fn extract_mod_zip(archive_path: &std::path::Path, target_dir: &std::path::Path) -> Result<(), String> {
-    extract_mod_archive(archive_path, target_dir)
+    extract_mod_archive(archive_path, target_dir, DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_server_info_is_client_only() {
+        let system = SystemApi::new();
+        assert!(system.get_server_info().is_none());
+
+        system.set_server_info("0.2.1", "Demo", "1.0.0", 1);
+        let info = system.get_server_info().unwrap();
+        assert_eq!(info.server_version, "0.2.1");
+        assert_eq!((info.game_name.as_str(), info.game_version.as_str()), ("Demo", "1.0.0"));
+        assert_eq!(info.protocol_version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_gc_requests_coalesce() {
+        let system = SystemApi::new();
+        assert!(system.get_js_memory_stats().is_none());
+
+        // Requests made while nobody waits are kept, but only once
+        system.request_gc();
+        system.request_gc();
+        tokio::time::timeout(std::time::Duration::from_secs(1), system.gc_requested()).await.unwrap();
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(20), system.gc_requested()).await.is_err());
+
+        let stats = JsMemoryStats { used_bytes: 1024, allocated_bytes: 4096, object_count: 12 };
+        system.set_js_memory_stats(stats);
+        assert_eq!(system.clone().get_js_memory_stats(), Some(stats));
+    }
+
+    #[test]
+    fn test_service_has_a_single_owner() {
+        let system = SystemApi::new();
+        system.register_service("inventory", "items").unwrap();
+        system.register_service("quests", "items").unwrap();
+        // The owner can register it again, other mods can't take it over
+        system.register_service("inventory", "items").unwrap();
+        assert_eq!(
+            system.register_service("inventory", "shop"),
+            Err("Service 'inventory' is already registered by mod 'items'".to_string())
+        );
+        assert!(system.register_service("", "shop").is_err());
+        assert_eq!(system.service_owner("inventory").as_deref(), Some("items"));
+        assert_eq!(system.service_names(), vec!["inventory", "quests"]);
+
+        // Unloading the owner frees the names
+        assert_eq!(system.unregister_mod_services("items"), vec!["inventory", "quests"]);
+        assert!(system.service_owner("inventory").is_none());
+        system.register_service("inventory", "shop").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_request_carries_the_reason() {
+        let system = SystemApi::new();
+        let mut rx = system.take_shutdown_receiver().await.unwrap();
+        let request = ShutdownRequest::new(2).with_message("save failed").with_mod_id("game");
+        system.request_shutdown(request).unwrap();
+
+        let received = rx.recv().await.unwrap();
+        assert!(received.is_error);
+        assert_eq!(received.describe(), "Shutdown requested by mod 'game' with exit code 2: save failed");
+        assert!(!ShutdownRequest::new(0).is_error);
+    }
+
+    /// Write a tar.gz with the given (raw path, content) entries, bypassing tar's own path checks
+    fn write_archive(path: &Path, entries: &[(&str, &[u8])]) {
+        let file = std::fs::File::create(path).unwrap();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(file, flate2::Compression::default()));
+        for (name, content) in entries {
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_cksum();
+            builder.append(&header, *content).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_extract_reports_progress() {
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("mod.tar.gz");
+        write_archive(&archive, &[("manifest.json", b"{}"), ("client/main.js", b"console.log(1);")]);
+        let target = temp.path().join("mod");
+
+        let calls = std::cell::RefCell::new(Vec::new());
+        let record = |processed, total| calls.borrow_mut().push((processed, total));
+        extract_mod_archive(&archive, &target, DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES, Some(&record)).unwrap();
+
+        assert_eq!(std::fs::read(target.join("client/main.js")).unwrap(), b"console.log(1);");
+        let calls = calls.into_inner();
+        let size = std::fs::metadata(&archive).unwrap().len();
+        assert_eq!(calls.len(), 3);
+        assert!(calls.iter().all(|&(processed, total)| total == size && processed <= size));
+        assert_eq!(calls.last(), Some(&(size, size)));
+    }
+
+    #[test]
+    fn test_extract_rejects_escaping_paths() {
+        let temp = tempfile::tempdir().unwrap();
+        let target = temp.path().join("mods").join("evil");
+
+        for name in ["../escaped.txt", "/tmp/escaped.txt", "client/../../escaped.txt"] {
+            let archive = temp.path().join("evil.tar.gz");
+            write_archive(&archive, &[(name, b"owned")]);
+            let err = extract_mod_archive(&archive, &target, DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES, None).unwrap_err();
+            assert!(err.contains("escapes the mod directory"), "{}: {}", name, err);
+        }
+        assert!(!temp.path().join("mods").join("escaped.txt").exists());
+        assert!(!temp.path().join("escaped.txt").exists());
+        assert!(!target.exists(), "partial output must be removed");
+    }
+
+    #[test]
+    fn test_extract_rejects_links() {
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("links.tar.gz");
+        let file = std::fs::File::create(&archive).unwrap();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(file, flate2::Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(2);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, "manifest.json", &b"{}"[..]).unwrap();
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, "client", "/etc").unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let target = temp.path().join("mod");
+        let err = extract_mod_archive(&archive, &target, DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES, None).unwrap_err();
+        assert!(err.contains("links are not allowed"), "{}", err);
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_extract_rejects_decompression_bomb() {
+        let temp = tempfile::tempdir().unwrap();
+        let archive = temp.path().join("bomb.tar.gz");
+        // 4 MiB of zeros compresses to a few KiB
+        let zeros = vec![0u8; 4 * 1024 * 1024];
+        write_archive(&archive, &[("manifest.json", b"{}"), ("client/zeros.bin", &zeros)]);
+        assert!(std::fs::metadata(&archive).unwrap().len() < 64 * 1024);
+
+        let target = temp.path().join("mod");
+        let err = extract_mod_archive(&archive, &target, 1024, None).unwrap_err();
+        assert!(err.contains("beyond the allowed 1024 bytes"), "{}", err);
+        assert!(!target.exists());
+
+        // The same archive fits when the announced size covers it
+        extract_mod_archive(&archive, &target, 2 + zeros.len() as u64, None).unwrap();
+        assert_eq!(std::fs::metadata(target.join("client/zeros.bin")).unwrap().len(), zeros.len() as u64);
+    }
+
+    #[test]
+    fn test_install_rejects_escaping_mod_ids() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path().join("game");
+        std::fs::create_dir_all(home.join("tmp")).unwrap();
+        std::fs::create_dir_all(temp.path().join("victim")).unwrap();
+        let archive = home.join("tmp").join("mod.tar.gz");
+        write_archive(&archive, &[("manifest.json", br#"{"name": "Evil", "version": "1.0.0", "description": ""}"#)]);
+
+        let system = SystemApi::new();
+        system.set_home_dir(home.clone());
+        for mod_id in ["../../victim", "..", "nested/mod", ""] {
+            let err = system.install_mod_from_archive(&archive, mod_id).unwrap_err();
+            assert!(err.contains("Invalid mod id"), "{}: {}", mod_id, err);
+        }
+        assert!(temp.path().join("victim").exists(), "an escaping id must not remove directories");
+        assert!(!home.join("mods").exists());
+    }
+
+    #[test]
+    fn test_install_confines_the_archive_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let home = temp.path().join("game");
+        std::fs::create_dir_all(home.join("tmp")).unwrap();
+        let manifest: &[(&str, &[u8])] = &[("manifest.json", br#"{"name": "Demo", "version": "1.0.0", "description": ""}"#)];
+        let outside = temp.path().join("outside.tar.gz");
+        write_archive(&outside, manifest);
+
+        let system = SystemApi::new();
+        system.set_home_dir(home.clone());
+        for path in [outside.clone(), home.join("tmp").join("..").join("..").join("outside.tar.gz")] {
+            let err = system.install_mod_from_archive(&path, "demo").unwrap_err();
+            assert!(err.contains("Access denied"), "{}: {}", path.display(), err);
+        }
+        assert!(!home.join("mods").join("demo").exists());
+
+        let inside = home.join("tmp").join("demo.tar.gz");
+        write_archive(&inside, manifest);
+        let installed = system.install_mod_from_archive(&inside, "demo").unwrap();
+        assert_eq!(installed, home.join("mods").join("demo"));
+        assert!(system.get_mod("demo").is_some_and(|info| info.exists && !info.loaded));
+    }
+
+    #[test]
+    fn test_extraction_limit_without_announced_size() {
+        assert_eq!(mod_extraction_limit(1024), 1024);
+        assert_eq!(mod_extraction_limit(0), DEFAULT_MAX_MOD_UNCOMPRESSED_BYTES);
+    }
 }
@@ -0,0 +1,123 @@
+//! Startup Timing Instrumentation
+//!
+//! Records how long each major startup phase takes (connect, download, extract,
+//! JS init, attach, bootstrap) so slow cold starts can be diagnosed.
+//!
+//! Each phase gets a `startup_phase` tracing span, opened when the phase begins
+//! and closed when it ends. Phases run across `.await` points, so the span is not
+//! entered for the whole phase; instead its duration is recorded explicitly in the
+//! span's `duration_ms` field and logged at DEBUG when the phase ends. The collected
+//! phases are also exposed to mods through `system.getStartupTimings()`.
+
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use tracing::field::Empty;
+use tracing::{Span, debug, debug_span};
+
+/// Duration of a single completed startup phase
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhaseTiming {
+    /// Phase name (e.g., "connect", "js_init", "attach")
+    pub phase: String,
+    /// Wall-clock time spent in the phase
+    pub duration: Duration,
+}
+
+/// Shared collection of startup phase timings
+///
+/// Cloning is cheap and all clones record into the same list, so the collector
+/// can be created before the runtime exists and handed to `SystemApi` later.
+#[derive(Clone, Debug, Default)]
+pub struct StartupTimings {
+    phases: Arc<RwLock<Vec<PhaseTiming>>>,
+}
+
+impl StartupTimings {
+    /// Create an empty timing collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start timing a phase
+    ///
+    /// The phase is recorded when the returned timer is finished or dropped,
+    /// so early returns through `?` still report the partial duration.
+    pub fn begin(&self, phase: impl Into<String>) -> PhaseTimer {
+        let phase = phase.into();
+        PhaseTimer {
+            span: debug_span!("startup_phase", phase = %phase, duration_ms = Empty),
+            phase,
+            start: Instant::now(),
+            timings: self.clone(),
+        }
+    }
+
+    /// Get a snapshot of all completed phases, in completion order
+    pub fn phases(&self) -> Vec<PhaseTiming> {
+        self.phases.read().unwrap().clone()
+    }
+
+    /// Total time across all completed phases
+    pub fn total(&self) -> Duration {
+        self.phases.read().unwrap().iter().map(|p| p.duration).sum()
+    }
+
+    fn record(&self, phase: String, duration: Duration) {
+        self.phases.write().unwrap().push(PhaseTiming { phase, duration });
+    }
+}
+
+/// Running timer for one startup phase, created by `StartupTimings::begin`
+pub struct PhaseTimer {
+    phase: String,
+    start: Instant,
+    span: Span,
+    timings: StartupTimings,
+}
+
+impl PhaseTimer {
+    /// End the phase and return its duration
+    pub fn finish(self) -> Duration {
+        // Recording happens in Drop
+        self.start.elapsed()
+    }
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        let duration = self.start.elapsed();
+        self.span.record("duration_ms", duration.as_secs_f64() * 1000.0);
+        self.span.in_scope(|| {
+            debug!("Startup phase '{}' took {:.1?}", self.phase, duration);
+        });
+        self.timings.record(std::mem::take(&mut self.phase), duration);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_recorded_in_order() {
+        let timings = StartupTimings::new();
+        timings.begin("connect").finish();
+        {
+            let _timer = timings.begin("js_init");
+        }
+
+        let phases: Vec<String> = timings.phases().into_iter().map(|p| p.phase).collect();
+        assert_eq!(phases, vec!["connect", "js_init"]);
+    }
+
+    #[test]
+    fn test_clones_share_phases() {
+        let timings = StartupTimings::new();
+        let shared = timings.clone();
+        shared.begin("attach").finish();
+
+        assert_eq!(timings.phases().len(), 1);
+        assert_eq!(timings.total(), timings.phases()[0].duration);
+    }
+}
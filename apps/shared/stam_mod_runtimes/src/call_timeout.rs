@@ -0,0 +1,92 @@
+//! Call Timeout Support
+//!
+//! Shared deadline used by runtime adapters to abort mod calls that run for
+//! too long (e.g. an infinite loop in `onBootstrap`). The adapter arms the
+//! deadline around each synchronous call into a mod and its interrupt hook
+//! polls `is_expired()`.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Deadline for the mod call currently in progress
+///
+/// Cloning is cheap and all clones share the same deadline, so one clone can
+/// live inside the runtime's interrupt handler while the adapter arms another.
+#[derive(Clone, Debug, Default)]
+pub struct CallDeadline {
+    deadline: Arc<Mutex<Option<Instant>>>,
+}
+
+impl CallDeadline {
+    /// Create a disarmed deadline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Arm the deadline for a call
+    ///
+    /// With `None` the call runs without a time limit. The previous deadline is
+    /// restored when the returned guard is dropped.
+    pub fn arm(&self, timeout: Option<Duration>) -> CallDeadlineGuard {
+        let mut deadline = self.deadline.lock().unwrap();
+        let previous = *deadline;
+        *deadline = timeout.map(|t| Instant::now() + t);
+        CallDeadlineGuard {
+            owner: self.clone(),
+            previous,
+        }
+    }
+
+    /// Whether the armed deadline has passed
+    pub fn is_expired(&self) -> bool {
+        self.deadline
+            .lock()
+            .unwrap()
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// Restores the previous deadline when dropped, created by `CallDeadline::arm`
+pub struct CallDeadlineGuard {
+    owner: CallDeadline,
+    previous: Option<Instant>,
+}
+
+impl Drop for CallDeadlineGuard {
+    fn drop(&mut self) {
+        *self.owner.deadline.lock().unwrap() = self.previous;
+    }
+}
+
+/// Error message for a mod call aborted by the call timeout
+pub fn timeout_error_message(mod_id: &str, function_name: &str, timeout: Duration) -> String {
+    format!(
+        "Call to '{}' for mod '{}' aborted after exceeding the {:?} call timeout",
+        function_name, mod_id, timeout
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disarmed_never_expires() {
+        let deadline = CallDeadline::new();
+        assert!(!deadline.is_expired());
+
+        let _guard = deadline.arm(None);
+        assert!(!deadline.is_expired());
+    }
+
+    #[test]
+    fn test_armed_deadline_expires_and_resets() {
+        let deadline = CallDeadline::new();
+        let shared = deadline.clone();
+        {
+            let _guard = deadline.arm(Some(Duration::ZERO));
+            assert!(shared.is_expired());
+        }
+        assert!(!shared.is_expired());
+    }
+}
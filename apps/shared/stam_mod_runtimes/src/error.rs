@@ -0,0 +1,171 @@
+//! Runtime Errors
+//!
+//! Typed errors returned by runtime adapters and the `RuntimeManager`, so
+//! callers can tell a missing mod from a syntax error or a script exception
+//! and report something actionable instead of a bare message.
+
+use std::time::Duration;
+
+/// Error raised by a runtime adapter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuntimeError {
+    /// The mod is not loaded in any runtime
+    ModNotFound {
+        /// ID of the mod that was looked up
+        mod_id: String,
+    },
+    /// The mod could not be loaded for a reason unrelated to its code
+    /// (unreadable entry point, runtime setup failure, ...)
+    LoadFailed {
+        /// ID of the mod being loaded
+        mod_id: String,
+        /// Description of the failure
+        message: String,
+    },
+    /// The mod's code failed to parse or compile
+    CompileError {
+        /// Line of the error in the source, when the runtime reports it
+        line: Option<u32>,
+        /// Compiler message (e.g. "SyntaxError: unexpected token")
+        message: String,
+    },
+    /// The mod's code threw while running
+    RuntimeException {
+        /// Exception message (e.g. "TypeError: x is not a function")
+        message: String,
+        /// Script stack trace, when the runtime provides one
+        stack: Option<String>,
+    },
+    /// A mod call was aborted after exceeding the call timeout
+    Timeout {
+        /// ID of the mod that was called
+        mod_id: String,
+        /// Name of the function that was called
+        function_name: String,
+        /// The configured call timeout
+        timeout: Duration,
+    },
+    /// The operation is not supported (unknown runtime, unload not available, ...)
+    Unsupported {
+        /// Description of what is not supported
+        message: String,
+    },
+    /// A mod function returned a value that has no `ModReturnValue` counterpart
+    UnsupportedReturnType {
+        /// Type name of the returned value as seen by the script (e.g. "function", "bigint")
+        js_type: String,
+    },
+    /// A mod function returned arrays or objects nested deeper than `MAX_RETURN_VALUE_DEPTH`
+    /// (usually a value referencing itself)
+    ReturnValueTooDeep {
+        /// The depth limit that was exceeded
+        max_depth: usize,
+    },
+}
+
+impl RuntimeError {
+    /// Create a `RuntimeException` without a stack trace
+    pub fn exception(message: impl Into<String>) -> Self {
+        RuntimeError::RuntimeException {
+            message: message.into(),
+            stack: None,
+        }
+    }
+
+    /// Create an `Unsupported` error
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        RuntimeError::Unsupported {
+            message: message.into(),
+        }
+    }
+
+    /// Create a `LoadFailed` error for a mod
+    pub fn load_failed(mod_id: &str, message: impl Into<String>) -> Self {
+        RuntimeError::LoadFailed {
+            mod_id: mod_id.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuntimeError::ModNotFound { mod_id } => write!(f, "Mod '{}' not loaded", mod_id),
+            RuntimeError::LoadFailed { mod_id, message } => {
+                write!(f, "Failed to load mod '{}': {}", mod_id, message)
+            }
+            RuntimeError::CompileError { line: Some(line), message } => {
+                write!(f, "Compile error at line {}: {}", line, message)
+            }
+            RuntimeError::CompileError { line: None, message } => {
+                write!(f, "Compile error: {}", message)
+            }
+            RuntimeError::RuntimeException { message, .. } => write!(f, "{}", message),
+            RuntimeError::Timeout { mod_id, function_name, timeout } => write!(
+                f,
+                "{}",
+                crate::call_timeout::timeout_error_message(mod_id, function_name, *timeout)
+            ),
+            RuntimeError::Unsupported { message } => write!(f, "{}", message),
+            RuntimeError::UnsupportedReturnType { js_type } => {
+                write!(f, "Unsupported return type '{}': cannot be converted to a ModReturnValue", js_type)
+            }
+            RuntimeError::ReturnValueTooDeep { max_depth } => write!(
+                f,
+                "Return value is nested deeper than {} levels (does it reference itself?)",
+                max_depth
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {}
+
+/// Extract the line number from the first frame of a script stack trace
+///
+/// Understands frames like `at fn (path/mod.js:12:5)`, `at path/mod.js:12` and
+/// Lua's `path/mod.lua:12: message`; returns `None` if no frame has a line.
+#[cfg_attr(not(any(feature = "js", feature = "lua")), allow(dead_code))]
+pub(crate) fn line_from_stack(stack: &str) -> Option<u32> {
+    stack.lines().find_map(|frame| {
+        let frame = frame.trim();
+        let location = match (frame.rfind('('), frame.rfind(')')) {
+            (Some(open), Some(close)) if open < close => &frame[open + 1..close],
+            _ => frame.strip_prefix("at ").unwrap_or(frame),
+        };
+        location
+            .split(':')
+            .skip(1)
+            .find_map(|part| part.trim().parse::<u32>().ok())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_from_stack() {
+        assert_eq!(line_from_stack("    at onAttach (/mods/demo/main.js:12:5)\n    at <eval>"), Some(12));
+        assert_eq!(line_from_stack("    at /mods/demo/main.js:3"), Some(3));
+        assert_eq!(line_from_stack("[string \"main.lua\"]:7: unexpected symbol"), Some(7));
+        assert_eq!(line_from_stack("    at <eval>\n    at helper (lib.js:40:1)"), Some(40));
+        assert_eq!(line_from_stack("    at native"), None);
+        assert_eq!(line_from_stack(""), None);
+    }
+
+    #[test]
+    fn test_display_is_actionable() {
+        let err = RuntimeError::CompileError { line: Some(4), message: "SyntaxError: unexpected token".into() };
+        assert_eq!(err.to_string(), "Compile error at line 4: SyntaxError: unexpected token");
+        assert_eq!(RuntimeError::ModNotFound { mod_id: "demo".into() }.to_string(), "Mod 'demo' not loaded");
+
+        let err = RuntimeError::Timeout {
+            mod_id: "demo".into(),
+            function_name: "onBootstrap".into(),
+            timeout: Duration::from_secs(1),
+        };
+        assert!(err.to_string().contains("onBootstrap"));
+    }
+}
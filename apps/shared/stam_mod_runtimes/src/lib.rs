@@ -11,9 +11,12 @@
 //! - **ApiRegistry**: Registry for configuring which APIs are available to mods
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub mod api;
+pub mod call_timeout;
+pub mod error;
 pub mod runtime_type;
 pub mod terminal_input;
 
@@ -21,9 +24,11 @@ pub mod terminal_input;
 pub use stam_log as logging;
 
 // Conditional module imports based on features
-#[cfg(feature = "js")]
+#[cfg(any(feature = "js", feature = "lua", feature = "wasm"))]
 pub mod adapters;
 
+pub use call_timeout::CallDeadline;
+pub use error::RuntimeError;
 pub use runtime_type::RuntimeType;
 
 // Re-export AsyncRuntime type for event loop integration
@@ -31,15 +36,25 @@ pub use runtime_type::RuntimeType;
 pub use rquickjs::AsyncRuntime as JsAsyncRuntime;
 
 /// Return value from a mod function call
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ModReturnValue {
     None,
     String(String),
     Bool(bool),
+    /// Integral number
     Int(i32),
-    // Future: Object(HashMap<String, ModReturnValue>), Array(Vec<ModReturnValue>)
+    /// Non-integral (or out of `i32` range) number
+    Float(f64),
+    Array(Vec<ModReturnValue>),
+    Object(HashMap<String, ModReturnValue>),
 }
 
+/// Maximum nesting of arrays and objects in a `ModReturnValue`
+///
+/// Adapters convert script values recursively; the cap turns a self-referencing
+/// value (`o.o = o`) into `RuntimeError::ReturnValueTooDeep` instead of a stack overflow.
+pub const MAX_RETURN_VALUE_DEPTH: usize = 64;
+
 /// Trait that all runtime adapters must implement
 ///
 /// A runtime adapter wraps a specific scripting language runtime (QuickJS, Lua VM, etc.)
@@ -53,14 +68,32 @@ pub trait RuntimeAdapter {
     /// # Arguments
     /// * `mod_path` - Path to the mod's entry point file
     /// * `mod_id` - Unique identifier for the mod
-    fn load_mod(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), Box<dyn std::error::Error>>;
+    fn load_mod(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError>;
+
+    /// Load another entry point script into an already loaded mod
+    ///
+    /// Used for manifests declaring several entry points: the first is loaded with
+    /// `load_mod`, the others with this method. Lifecycle functions (`onAttach`, ...)
+    /// are then called in every script of the mod that defines them.
+    /// The default implementation reports that multiple scripts are not supported.
+    ///
+    /// # Arguments
+    /// * `mod_path` - Path to the additional script
+    /// * `mod_id` - ID of the loaded mod
+    fn add_mod_script(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError> {
+        Err(RuntimeError::unsupported(format!(
+            "Cannot load '{}' into mod '{}': multiple entry points not supported by this runtime",
+            mod_path.display(),
+            mod_id
+        )))
+    }
 
     /// Call a function in a mod without return value
     ///
     /// # Arguments
     /// * `mod_id` - ID of the mod
     /// * `function_name` - Name of the function to call (e.g., "onAttach", "onBootstrap")
-    fn call_mod_function(&mut self, mod_id: &str, function_name: &str) -> Result<(), Box<dyn std::error::Error>>;
+    fn call_mod_function(&mut self, mod_id: &str, function_name: &str) -> Result<(), RuntimeError>;
 
     /// Call a function in a mod with a return value
     ///
@@ -69,12 +102,56 @@ pub trait RuntimeAdapter {
     /// * `function_name` - Name of the function to call
     ///
     /// # Returns
-    /// A `ModReturnValue` which can be pattern matched to extract the actual value
+    /// A `ModReturnValue` which can be pattern matched to extract the actual value.
+    /// Values that can't be represented fail with `RuntimeError::UnsupportedReturnType`.
     fn call_mod_function_with_return(
         &mut self,
         mod_id: &str,
         function_name: &str,
-    ) -> Result<ModReturnValue, Box<dyn std::error::Error>>;
+    ) -> Result<ModReturnValue, RuntimeError>;
+
+    /// Whether this runtime can unload mods
+    ///
+    /// Checked by `RuntimeManager::unload_mod` before `onDetach` runs, so a mod is
+    /// never detached and then left loaded. Adapters implementing `unload_mod`
+    /// must return `true`. The default implementation returns `false`.
+    fn supports_unload(&self) -> bool {
+        false
+    }
+
+    /// Unload a mod and drop its module/context state
+    ///
+    /// Called by `RuntimeManager::unload_mod` after the mod's `onDetach` has run.
+    /// Adapters must also stop dispatching events to handlers registered by the mod.
+    /// The default implementation reports that unloading is not supported.
+    ///
+    /// # Arguments
+    /// * `mod_id` - ID of the mod to unload
+    fn unload_mod(&mut self, mod_id: &str) -> Result<(), RuntimeError> {
+        Err(RuntimeError::unsupported(format!(
+            "Cannot unload mod '{}': unload not supported by this runtime",
+            mod_id
+        )))
+    }
+
+    /// Get the IDs of the event handlers registered by a mod in this runtime
+    ///
+    /// Used on unload to deregister exactly the handlers the mod owns.
+    /// The default implementation reports none.
+    ///
+    /// # Arguments
+    /// * `mod_id` - ID of the mod
+    fn handler_ids_for_mod(&self, _mod_id: &str) -> Vec<u64> {
+        Vec::new()
+    }
+
+    /// Set the maximum time a single synchronous mod call may run
+    ///
+    /// Applies to `call_mod_function` and `call_mod_function_with_return`. When a
+    /// call exceeds the timeout the adapter aborts it and returns an error.
+    /// `None` disables the limit. Adapters that cannot interrupt running code
+    /// ignore this (the default).
+    fn set_call_timeout(&mut self, _timeout: Option<Duration>) {}
 
     /// Call an event handler by its handler ID
     ///
@@ -93,19 +170,57 @@ pub trait RuntimeAdapter {
         handler_id: u64,
         event_name: &str,
         args: &[String],
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<(), RuntimeError>;
 
-    /// Dispatch a TerminalKeyPressed event to all registered handlers
+    /// Call an event handler by its handler ID and return its value
     ///
-    /// This method finds all handlers registered for TerminalKeyPressed, calls them
-    /// in priority order (lowest first), and returns whether the event was handled.
+    /// The handler's return value is captured synchronously and converted to a
+    /// `ModReturnValue`; values produced after an `await` are not captured.
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Arguments
+    /// * `handler_id` - The unique handler ID returned from registration
+    /// * `event_name` - The name of the event being dispatched
+    /// * `args` - JSON-serialized arguments to pass to the handler
+    fn call_event_handler_with_return(
+        &mut self,
+        handler_id: u64,
+        _event_name: &str,
+        _args: &[String],
+    ) -> Result<ModReturnValue, RuntimeError> {
+        Err(RuntimeError::unsupported(format!(
+            "Cannot call event handler {} with return: not supported by this runtime",
+            handler_id
+        )))
+    }
+
+    /// Collect the handlers this runtime's mods registered for an event
+    ///
+    /// The `RuntimeManager` merges the handlers of every runtime, orders them by
+    /// `(priority, registration_seq)` and passes each adapter back the handlers
+    /// it must call, so dispatch order doesn't depend on runtime iteration order.
+    fn collect_handlers(&self, event: &api::EventKey) -> Vec<api::HandlerRef>;
+
+    /// Get the number of handlers this runtime's mods registered for an event
+    ///
+    /// Counts the handlers `collect_handlers` would return; adapters can override
+    /// it when they can count without building the list.
+    fn handler_count(&self, event: &api::EventKey) -> usize {
+        self.collect_handlers(event).len()
+    }
+
+    /// Dispatch a TerminalKeyPressed event to the given handlers
+    ///
+    /// This method calls the handlers in the given order and stops as soon as one
+    /// of them marks the event as handled.
     ///
     /// # Arguments
     /// * `request` - The terminal key request containing key and modifier information
+    /// * `handlers` - Handlers of this runtime to call, already in dispatch order
     ///
     /// # Returns
     /// A `TerminalKeyResponse` containing whether the event was handled
-    fn dispatch_terminal_key(&self, request: &api::TerminalKeyRequest) -> api::TerminalKeyResponse;
+    fn dispatch_terminal_key(&self, request: &api::TerminalKeyRequest, handlers: &[api::HandlerRef]) -> api::TerminalKeyResponse;
 
     /// Get the number of handlers registered for TerminalKeyPressed event
     ///
@@ -113,39 +228,64 @@ pub trait RuntimeAdapter {
     /// which affects whether the default "Ctrl+C to exit" message should be shown.
     fn terminal_key_handler_count(&self) -> usize;
 
-    /// Dispatch a GraphicEngineReady event to all registered handlers
+    /// Dispatch a TerminalMouse event to the given handlers
+    ///
+    /// This method calls the handlers in the given order and stops as soon as one
+    /// of them marks the event as handled.
+    ///
+    /// # Arguments
+    /// * `request` - The terminal mouse request (position, button and kind)
+    /// * `handlers` - Handlers of this runtime to call, already in dispatch order
+    ///
+    /// # Returns
+    /// A `TerminalMouseResponse` containing whether the event was handled
+    fn dispatch_terminal_mouse(&self, request: &api::TerminalMouseRequest, handlers: &[api::HandlerRef]) -> api::TerminalMouseResponse;
+
+    /// Dispatch a TerminalPaste event to the given handlers
+    ///
+    /// This method calls the handlers in the given order and stops as soon as one
+    /// of them marks the event as handled.
+    ///
+    /// # Arguments
+    /// * `request` - The terminal paste request containing the pasted text
+    /// * `handlers` - Handlers of this runtime to call, already in dispatch order
+    ///
+    /// # Returns
+    /// A `TerminalPasteResponse` containing whether the event was handled
+    fn dispatch_terminal_paste(&self, request: &api::TerminalPasteRequest, handlers: &[api::HandlerRef]) -> api::TerminalPasteResponse;
+
+    /// Dispatch a GraphicEngineReady event to the given handlers
     ///
-    /// This method finds all handlers registered for GraphicEngineReady, calls them
-    /// in priority order (lowest first), and returns whether the event was handled.
-    /// This event is client-only and is triggered when the graphic engine has been
-    /// initialized and is ready to receive commands.
+    /// This method calls the handlers in the given order and returns whether the
+    /// event was handled. This event is client-only and is triggered when the
+    /// graphic engine has been initialized and is ready to receive commands.
     ///
     /// # Arguments
     /// * `request` - The graphic engine ready request (currently empty but extensible)
+    /// * `handlers` - Handlers of this runtime to call, already in dispatch order
     ///
     /// # Returns
     /// A `GraphicEngineReadyResponse` containing whether the event was handled
-    fn dispatch_graphic_engine_ready(&self, request: &api::GraphicEngineReadyRequest) -> api::GraphicEngineReadyResponse;
+    fn dispatch_graphic_engine_ready(&self, request: &api::GraphicEngineReadyRequest, handlers: &[api::HandlerRef]) -> api::GraphicEngineReadyResponse;
 
-    /// Dispatch a GraphicEngineWindowClosed event to all registered handlers
+    /// Dispatch a GraphicEngineWindowClosed event to the given handlers
     ///
-    /// This method finds all handlers registered for GraphicEngineWindowClosed, calls them
-    /// in priority order (lowest first), and returns whether the event was handled.
-    /// This event is client-only and is triggered when a window managed by the
-    /// graphic engine is closed.
+    /// This method calls the handlers in the given order and returns whether the
+    /// event was handled. This event is client-only and is triggered when a window
+    /// managed by the graphic engine is closed.
     ///
     /// # Arguments
     /// * `request` - The window closed request containing the window_id
+    /// * `handlers` - Handlers of this runtime to call, already in dispatch order
     ///
     /// # Returns
     /// A `GraphicEngineWindowClosedResponse` containing whether the event was handled
-    fn dispatch_graphic_engine_window_closed(&self, request: &api::GraphicEngineWindowClosedRequest) -> api::GraphicEngineWindowClosedResponse;
+    fn dispatch_graphic_engine_window_closed(&self, request: &api::GraphicEngineWindowClosedRequest, handlers: &[api::HandlerRef]) -> api::GraphicEngineWindowClosedResponse;
 
-    /// Dispatch a custom event to all registered handlers
+    /// Dispatch a custom event to the given handlers
     ///
-    /// This method finds all handlers registered for the custom event, calls them
-    /// in priority order (lowest first), and returns the aggregated response.
-    /// Each handler receives a request object with `args` array and a response object
+    /// This method calls every given handler in order and returns the aggregated
+    /// response. Each handler receives a request object with `args` array and a response object
     /// with `handled` flag and custom properties.
     ///
     /// **IMPORTANT**: Handler response values must be set SYNCHRONOUSLY before any
@@ -153,10 +293,11 @@ pub trait RuntimeAdapter {
     ///
     /// # Arguments
     /// * `request` - The custom event request containing event_name and args
+    /// * `handlers` - Handlers of this runtime to call, already in dispatch order
     ///
     /// # Returns
     /// A `CustomEventResponse` containing whether the event was handled and any results
-    fn dispatch_custom_event(&self, request: &api::CustomEventRequest) -> api::CustomEventResponse;
+    fn dispatch_custom_event(&self, request: &api::CustomEventRequest, handlers: &[api::HandlerRef]) -> api::CustomEventResponse;
 
     // Note: dispatch_widget_event has been removed. Use ECS entity event callbacks instead.
 
@@ -182,7 +323,7 @@ pub trait RuntimeAdapter {
         _entity_id: u64,
         _event_type: &str,
         _event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, RuntimeError> {
         // Default: no callback registered
         Ok(false)
     }
@@ -206,10 +347,19 @@ pub trait RuntimeAdapter {
         _window_id: u64,
         _event_type: &str,
         _event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, RuntimeError> {
         // Default: no callback registered
         Ok(false)
     }
+
+    /// Call `onTick(deltaMs)` on every mod of this runtime that defines it
+    ///
+    /// This is the per-tick fast path of the server loop: no request objects,
+    /// no handler lookup. `delta` is the time actually elapsed since the previous
+    /// tick. Errors are logged by the runtime and don't stop other mods.
+    fn dispatch_tick(&self, _delta: Duration) {
+        // Default: runtime has no tick support
+    }
 }
 
 /// Manager for all mod runtimes
@@ -222,6 +372,13 @@ pub struct RuntimeManager {
 
     /// Map of mod_id to runtime type
     mod_to_runtime: HashMap<String, RuntimeType>,
+
+    /// Extra file extension -> runtime type mappings, consulted before
+    /// `RuntimeType::from_extension`
+    extension_overrides: HashMap<String, RuntimeType>,
+
+    /// Maximum duration of a single mod call (None = no limit)
+    call_timeout: Option<Duration>,
 }
 
 impl RuntimeManager {
@@ -230,6 +387,8 @@ impl RuntimeManager {
         Self {
             runtimes: HashMap::new(),
             mod_to_runtime: HashMap::new(),
+            extension_overrides: HashMap::new(),
+            call_timeout: None,
         }
     }
 
@@ -238,10 +397,65 @@ impl RuntimeManager {
     /// # Arguments
     /// * `runtime_type` - The type of runtime (JavaScript, Lua, etc.)
     /// * `adapter` - The adapter instance implementing RuntimeAdapter
-    pub fn register_adapter(&mut self, runtime_type: RuntimeType, adapter: Box<dyn RuntimeAdapter>) {
+    pub fn register_adapter(&mut self, runtime_type: RuntimeType, mut adapter: Box<dyn RuntimeAdapter>) {
+        if self.call_timeout.is_some() {
+            adapter.set_call_timeout(self.call_timeout);
+        }
         self.runtimes.insert(runtime_type, adapter);
     }
 
+    /// Abort mod calls that run longer than `timeout`
+    ///
+    /// Applies to `call_mod_function` and `call_mod_function_with_return` on
+    /// every registered adapter (and adapters registered later). By default
+    /// there is no timeout.
+    pub fn set_call_timeout(&mut self, timeout: Duration) {
+        self.call_timeout = Some(timeout);
+        for adapter in self.runtimes.values_mut() {
+            adapter.set_call_timeout(self.call_timeout);
+        }
+    }
+
+    /// Remove the call timeout, letting mod calls run without a time limit
+    pub fn clear_call_timeout(&mut self) {
+        self.call_timeout = None;
+        for adapter in self.runtimes.values_mut() {
+            adapter.set_call_timeout(None);
+        }
+    }
+
+    /// Get the configured call timeout, if any
+    pub fn call_timeout(&self) -> Option<Duration> {
+        self.call_timeout
+    }
+
+    /// Register a custom file extension -> runtime type mapping
+    ///
+    /// Registered mappings take precedence over the built-in table in
+    /// `RuntimeType::from_extension`. This is mainly useful for tests, which can
+    /// route a fake extension (e.g. `.mock`) to a mock adapter.
+    ///
+    /// # Arguments
+    /// * `extension` - File extension without the leading dot (e.g., "mock")
+    /// * `runtime_type` - The runtime type mods with this extension are loaded into
+    pub fn register_extension(&mut self, extension: &str, runtime_type: RuntimeType) {
+        self.extension_overrides.insert(extension.to_string(), runtime_type);
+    }
+
+    /// Resolve the runtime type for an entry point
+    ///
+    /// Custom mappings registered via `register_extension` are checked first,
+    /// then the built-in mapping from `RuntimeType::from_extension`.
+    pub fn resolve_runtime_type(&self, entry_point: &Path) -> Result<RuntimeType, Box<dyn std::error::Error>> {
+        if let Some(runtime_type) = entry_point.extension()
+            .and_then(|e| e.to_str())
+            .and_then(|ext| self.extension_overrides.get(ext))
+        {
+            return Ok(*runtime_type);
+        }
+        RuntimeType::from_extension(entry_point)
+    }
+
     /// Load a mod into the appropriate runtime based on its entry_point extension
     ///
     /// # Arguments
@@ -250,17 +464,21 @@ impl RuntimeManager {
     ///
     /// The runtime type is determined by the file extension:
     /// - .js -> JavaScript
-    /// - .lua -> Lua (future)
+    /// - .lua -> Lua (requires the `lua` feature)
+    /// - .wasm -> WebAssembly (requires the `wasm` feature)
     /// - .cs -> C# (future)
     /// - .rs -> Rust (future)
     /// - .cpp -> C++ (future)
-    pub fn load_mod(&mut self, mod_id: &str, entry_point: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// Extensions registered via `register_extension` take precedence.
+    pub fn load_mod(&mut self, mod_id: &str, entry_point: &Path) -> Result<(), RuntimeError> {
         // Determine runtime type from file extension
-        let runtime_type = RuntimeType::from_extension(entry_point)?;
+        let runtime_type = self.resolve_runtime_type(entry_point)
+            .map_err(|e| RuntimeError::unsupported(e.to_string()))?;
 
         // Get the runtime for this type
         let runtime = self.runtimes.get_mut(&runtime_type)
-            .ok_or_else(|| format!("Runtime not initialized for type: {:?}", runtime_type))?;
+            .ok_or_else(|| RuntimeError::unsupported(format!("Runtime not initialized for type: {:?}", runtime_type)))?;
 
         // Load the mod
         runtime.load_mod(entry_point, mod_id)?;
@@ -271,6 +489,44 @@ impl RuntimeManager {
         Ok(())
     }
 
+    /// Load a mod declaring one or more entry point scripts
+    ///
+    /// The first script is loaded like `load_mod`, the others are added to the same
+    /// mod in order. All scripts must resolve to the same runtime.
+    ///
+    /// # Arguments
+    /// * `mod_id` - Unique identifier for the mod
+    /// * `entry_points` - Paths to the mod's entry point scripts, in load order
+    pub fn load_mod_scripts(&mut self, mod_id: &str, entry_points: &[PathBuf]) -> Result<(), RuntimeError> {
+        let Some((first, others)) = entry_points.split_first() else {
+            return Err(RuntimeError::load_failed(mod_id, "no entry point to load"));
+        };
+        let runtime_type = self.resolve_runtime_type(first)
+            .map_err(|e| RuntimeError::unsupported(e.to_string()))?;
+        for entry_point in others {
+            let other_type = self.resolve_runtime_type(entry_point)
+                .map_err(|e| RuntimeError::unsupported(e.to_string()))?;
+            if other_type != runtime_type {
+                return Err(RuntimeError::unsupported(format!(
+                    "Mod '{}' mixes runtimes: '{}' is {:?}, '{}' is {:?}",
+                    mod_id,
+                    first.display(),
+                    runtime_type,
+                    entry_point.display(),
+                    other_type
+                )));
+            }
+        }
+
+        self.load_mod(mod_id, first)?;
+        let runtime = self.runtimes.get_mut(&runtime_type)
+            .ok_or_else(|| RuntimeError::unsupported(format!("Runtime not initialized for type: {:?}", runtime_type)))?;
+        for entry_point in others {
+            runtime.add_mod_script(entry_point, mod_id)?;
+        }
+        Ok(())
+    }
+
     /// Call a function in a mod without expecting a return value
     ///
     /// This abstracts away the runtime type - the caller doesn't need to know
@@ -279,14 +535,14 @@ impl RuntimeManager {
     /// # Arguments
     /// * `mod_id` - ID of the mod
     /// * `function_name` - Name of the function to call (e.g., "onAttach", "onBootstrap")
-    pub fn call_mod_function(&mut self, mod_id: &str, function_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn call_mod_function(&mut self, mod_id: &str, function_name: &str) -> Result<(), RuntimeError> {
         // Look up which runtime this mod uses
         let runtime_type = self.mod_to_runtime.get(mod_id)
-            .ok_or_else(|| format!("Mod '{}' not loaded", mod_id))?;
+            .ok_or_else(|| RuntimeError::ModNotFound { mod_id: mod_id.to_string() })?;
 
         // Get the runtime adapter
         let runtime = self.runtimes.get_mut(runtime_type)
-            .ok_or_else(|| format!("Runtime {:?} not available", runtime_type))?;
+            .ok_or_else(|| RuntimeError::unsupported(format!("Runtime {:?} not available", runtime_type)))?;
 
         // Call the function
         runtime.call_mod_function(mod_id, function_name)
@@ -304,19 +560,65 @@ impl RuntimeManager {
         &mut self,
         mod_id: &str,
         function_name: &str,
-    ) -> Result<ModReturnValue, Box<dyn std::error::Error>> {
+    ) -> Result<ModReturnValue, RuntimeError> {
         // Look up which runtime this mod uses
         let runtime_type = self.mod_to_runtime.get(mod_id)
-            .ok_or_else(|| format!("Mod '{}' not loaded", mod_id))?;
+            .ok_or_else(|| RuntimeError::ModNotFound { mod_id: mod_id.to_string() })?;
 
         // Get the runtime adapter
         let runtime = self.runtimes.get_mut(runtime_type)
-            .ok_or_else(|| format!("Runtime {:?} not available", runtime_type))?;
+            .ok_or_else(|| RuntimeError::unsupported(format!("Runtime {:?} not available", runtime_type)))?;
 
         // Call the function
         runtime.call_mod_function_with_return(mod_id, function_name)
     }
 
+    /// Unload a mod and reclaim its resources
+    ///
+    /// Calls the mod's `onDetach` function (if it defines one), asks the adapter to
+    /// drop the mod's state and event handlers, then forgets the mod. If the adapter
+    /// can't unload mods, `onDetach` is not called, an error is returned and the mod
+    /// stays registered.
+    ///
+    /// # Arguments
+    /// * `mod_id` - ID of the mod to unload
+    pub fn unload_mod(&mut self, mod_id: &str) -> Result<(), RuntimeError> {
+        // Look up which runtime this mod uses
+        let runtime_type = self.mod_to_runtime.get(mod_id)
+            .ok_or_else(|| RuntimeError::ModNotFound { mod_id: mod_id.to_string() })?;
+
+        // Get the runtime adapter
+        let runtime = self.runtimes.get_mut(runtime_type)
+            .ok_or_else(|| RuntimeError::unsupported(format!("Runtime {:?} not available", runtime_type)))?;
+
+        // Don't detach a mod that would then stay loaded
+        if !runtime.supports_unload() {
+            return Err(RuntimeError::unsupported(format!(
+                "Cannot unload mod '{}': unload not supported by this runtime",
+                mod_id
+            )));
+        }
+
+        // A failing onDetach must not keep the mod loaded
+        if let Err(e) = runtime.call_mod_function(mod_id, "onDetach") {
+            tracing::warn!("onDetach failed for mod '{}' during unload: {}", mod_id, e);
+        }
+
+        runtime.unload_mod(mod_id)?;
+        self.mod_to_runtime.remove(mod_id);
+
+        Ok(())
+    }
+
+    /// Get the IDs of the event handlers registered by a loaded mod
+    pub fn handler_ids_for_mod(&self, mod_id: &str) -> Vec<u64> {
+        self.mod_to_runtime
+            .get(mod_id)
+            .and_then(|runtime_type| self.runtimes.get(runtime_type))
+            .map(|runtime| runtime.handler_ids_for_mod(mod_id))
+            .unwrap_or_default()
+    }
+
     /// Get the runtime type for a loaded mod
     pub fn get_mod_runtime_type(&self, mod_id: &str) -> Option<RuntimeType> {
         self.mod_to_runtime.get(mod_id).copied()
@@ -341,20 +643,84 @@ impl RuntimeManager {
         handler_id: u64,
         event_name: &str,
         args: &[String],
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), RuntimeError> {
         // Event handlers are currently only supported in JavaScript runtime
         // Get the JavaScript runtime adapter
         let runtime = self.runtimes.get_mut(&RuntimeType::JavaScript)
-            .ok_or_else(|| "JavaScript runtime not available for event handlers")?;
+            .ok_or_else(|| RuntimeError::unsupported("JavaScript runtime not available for event handlers"))?;
 
         runtime.call_event_handler(handler_id, event_name, args)
     }
 
+    /// Call an event handler by its handler ID and return its value
+    ///
+    /// Lets a mod query another mod and get back a structured result. Like
+    /// `call_event_handler` this targets the JavaScript runtime. Only the value
+    /// returned synchronously is captured: an `async` handler yields
+    /// `ModReturnValue::None`.
+    ///
+    /// # Arguments
+    /// * `handler_id` - The unique handler ID returned from registration
+    /// * `event_name` - The name of the event being dispatched
+    /// * `args` - JSON-serialized arguments to pass to the handler
+    pub fn call_event_handler_with_return(
+        &mut self,
+        handler_id: u64,
+        event_name: &str,
+        args: &[String],
+    ) -> Result<ModReturnValue, RuntimeError> {
+        let runtime = self.runtimes.get_mut(&RuntimeType::JavaScript)
+            .ok_or_else(|| RuntimeError::unsupported("JavaScript runtime not available for event handlers"))?;
+
+        runtime.call_event_handler_with_return(handler_id, event_name, args)
+    }
+
+    /// Collect the handlers of every runtime for an event, in dispatch order
+    ///
+    /// Handlers are ordered by `(priority, registration_seq)` so equal priorities
+    /// fire in registration order regardless of which runtime hosts them.
+    /// Consecutive handlers of the same runtime are grouped into one batch.
+    fn handler_batches(
+        &self,
+        event: &api::EventKey,
+        filter: impl Fn(&api::HandlerRef) -> bool,
+    ) -> Vec<(&dyn RuntimeAdapter, Vec<api::HandlerRef>)> {
+        let mut handlers: Vec<(RuntimeType, api::HandlerRef)> = self
+            .runtimes
+            .iter()
+            .flat_map(|(runtime_type, runtime)| {
+                runtime
+                    .collect_handlers(event)
+                    .into_iter()
+                    .map(move |handler| (*runtime_type, handler))
+            })
+            .filter(|(_, handler)| filter(handler))
+            .collect();
+        handlers.sort_by_key(|(_, handler)| (handler.wildcard, handler.priority, handler.registration_seq));
+
+        let mut batches: Vec<(RuntimeType, Vec<api::HandlerRef>)> = Vec::new();
+        for (runtime_type, handler) in handlers {
+            match batches.last_mut() {
+                Some((last_type, batch)) if *last_type == runtime_type => batch.push(handler),
+                _ => batches.push((runtime_type, vec![handler])),
+            }
+        }
+
+        batches
+            .into_iter()
+            .filter_map(|(runtime_type, batch)| {
+                self.runtimes
+                    .get(&runtime_type)
+                    .map(|runtime| (runtime.as_ref(), batch))
+            })
+            .collect()
+    }
+
     /// Dispatch a TerminalKeyPressed event to all registered handlers
     ///
-    /// This method iterates through all runtime adapters and dispatches the
-    /// TerminalKeyPressed event. If any handler marks the event as handled,
-    /// the loop stops and returns immediately.
+    /// Handlers of every runtime are called in priority order (lowest first, then
+    /// registration order). If any handler marks the event as handled, dispatch
+    /// stops and returns immediately.
     ///
     /// # Arguments
     /// * `request` - The terminal key request containing key and modifier information
@@ -362,10 +728,9 @@ impl RuntimeManager {
     /// # Returns
     /// A `TerminalKeyResponse` containing whether the event was handled by any runtime
     pub fn dispatch_terminal_key(&self, request: &api::TerminalKeyRequest) -> api::TerminalKeyResponse {
-        // Dispatch to all runtimes (currently only JavaScript)
-        // If any runtime handles the event, stop and return
-        for runtime in self.runtimes.values() {
-            let response = runtime.dispatch_terminal_key(request);
+        let event = api::EventKey::System(api::SystemEvents::TerminalKeyPressed);
+        for (runtime, handlers) in self.handler_batches(&event, |_| true) {
+            let response = runtime.dispatch_terminal_key(request, &handlers);
             if response.handled {
                 return response;
             }
@@ -374,6 +739,52 @@ impl RuntimeManager {
         api::TerminalKeyResponse::default()
     }
 
+    /// Dispatch a TerminalMouse event to all registered handlers
+    ///
+    /// Handlers of every runtime are called in priority order (lowest first, then
+    /// registration order). If any handler marks the event as handled, dispatch
+    /// stops and returns immediately.
+    ///
+    /// # Arguments
+    /// * `request` - The terminal mouse request (position, button and kind)
+    ///
+    /// # Returns
+    /// A `TerminalMouseResponse` containing whether the event was handled by any runtime
+    pub fn dispatch_terminal_mouse(&self, request: &api::TerminalMouseRequest) -> api::TerminalMouseResponse {
+        let event = api::EventKey::System(api::SystemEvents::TerminalMouse);
+        for (runtime, handlers) in self.handler_batches(&event, |_| true) {
+            let response = runtime.dispatch_terminal_mouse(request, &handlers);
+            if response.handled {
+                return response;
+            }
+        }
+        // No runtime handled the event
+        api::TerminalMouseResponse::default()
+    }
+
+    /// Dispatch a TerminalPaste event to all registered handlers
+    ///
+    /// Handlers of every runtime are called in priority order (lowest first, then
+    /// registration order). If any handler marks the event as handled, dispatch
+    /// stops and returns immediately.
+    ///
+    /// # Arguments
+    /// * `request` - The terminal paste request containing the pasted text
+    ///
+    /// # Returns
+    /// A `TerminalPasteResponse` containing whether the event was handled by any runtime
+    pub fn dispatch_terminal_paste(&self, request: &api::TerminalPasteRequest) -> api::TerminalPasteResponse {
+        let event = api::EventKey::System(api::SystemEvents::TerminalPaste);
+        for (runtime, handlers) in self.handler_batches(&event, |_| true) {
+            let response = runtime.dispatch_terminal_paste(request, &handlers);
+            if response.handled {
+                return response;
+            }
+        }
+        // No runtime handled the event
+        api::TerminalPasteResponse::default()
+    }
+
     /// Get the total number of handlers registered for TerminalKeyPressed across all runtimes
     ///
     /// This is used to determine if any mod has registered to handle terminal input,
@@ -382,11 +793,29 @@ impl RuntimeManager {
         self.runtimes.values().map(|r| r.terminal_key_handler_count()).sum()
     }
 
+    /// Get the total number of handlers registered for a custom event across all runtimes
+    ///
+    /// Lets the host skip building events nobody listens to.
+    pub fn custom_event_handler_count(&self, event_name: &str) -> usize {
+        let event = api::EventKey::custom(event_name);
+        self.runtimes.values().map(|r| r.handler_count(&event)).sum()
+    }
+
+    /// Get the total number of handlers registered for an event across all runtimes
+    ///
+    /// `event_name` is either a system event key (e.g. "system:TerminalKeyPressed") or
+    /// a custom event name. Lets callers skip building a request and serializing its
+    /// arguments when nobody listens.
+    pub fn handler_count(&self, event_name: &str) -> usize {
+        let event = api::EventKey::from_name(event_name);
+        self.runtimes.values().map(|r| r.handler_count(&event)).sum()
+    }
+
     /// Dispatch a GraphicEngineReady event to all registered handlers
     ///
-    /// This method iterates through all runtime adapters and dispatches the
-    /// GraphicEngineReady event. If any handler marks the event as handled,
-    /// the loop stops and returns immediately.
+    /// Handlers of every runtime are called in priority order (lowest first, then
+    /// registration order). If any handler marks the event as handled, dispatch
+    /// stops and returns immediately.
     ///
     /// # Arguments
     /// * `request` - The graphic engine ready request (currently empty but extensible)
@@ -394,10 +823,9 @@ impl RuntimeManager {
     /// # Returns
     /// A `GraphicEngineReadyResponse` containing whether the event was handled by any runtime
     pub fn dispatch_graphic_engine_ready(&self, request: &api::GraphicEngineReadyRequest) -> api::GraphicEngineReadyResponse {
-        // Dispatch to all runtimes (currently only JavaScript)
-        // If any runtime handles the event, stop and return
-        for runtime in self.runtimes.values() {
-            let response = runtime.dispatch_graphic_engine_ready(request);
+        let event = api::EventKey::System(api::SystemEvents::GraphicEngineReady);
+        for (runtime, handlers) in self.handler_batches(&event, |_| true) {
+            let response = runtime.dispatch_graphic_engine_ready(request, &handlers);
             if response.handled {
                 return response;
             }
@@ -408,9 +836,9 @@ impl RuntimeManager {
 
     /// Dispatch a GraphicEngineWindowClosed event to all registered handlers
     ///
-    /// This method iterates through all runtime adapters and dispatches the
-    /// GraphicEngineWindowClosed event. If any handler marks the event as handled,
-    /// the loop stops and returns immediately.
+    /// Handlers of every runtime are called in priority order (lowest first, then
+    /// registration order). If any handler marks the event as handled, dispatch
+    /// stops and returns immediately.
     ///
     /// # Arguments
     /// * `request` - The window closed request containing the window_id
@@ -418,10 +846,9 @@ impl RuntimeManager {
     /// # Returns
     /// A `GraphicEngineWindowClosedResponse` containing whether the event was handled by any runtime
     pub fn dispatch_graphic_engine_window_closed(&self, request: &api::GraphicEngineWindowClosedRequest) -> api::GraphicEngineWindowClosedResponse {
-        // Dispatch to all runtimes (currently only JavaScript)
-        // If any runtime handles the event, stop and return
-        for runtime in self.runtimes.values() {
-            let response = runtime.dispatch_graphic_engine_window_closed(request);
+        let event = api::EventKey::System(api::SystemEvents::GraphicEngineWindowClosed);
+        for (runtime, handlers) in self.handler_batches(&event, |_| true) {
+            let response = runtime.dispatch_graphic_engine_window_closed(request, &handlers);
             if response.handled {
                 return response;
             }
@@ -432,9 +859,13 @@ impl RuntimeManager {
 
     /// Dispatch a custom event to all registered handlers
     ///
-    /// This method iterates through all runtime adapters and dispatches the
-    /// custom event. Unlike other events, custom events aggregate results from
-    /// all handlers (handled flag and results array are combined).
+    /// Handlers of every runtime are called in priority order (lowest first, then
+    /// registration order). Unlike other events, custom events aggregate results
+    /// from all handlers (handled flag and properties are combined).
+    ///
+    /// When the request has a target (`CustomEventRequest::with_target`), only the
+    /// target mod's handlers run. Wildcard handlers (`"ui:*"`) run after the handlers
+    /// of the exact name, and never for events sent by their own mod.
     ///
     /// # Arguments
     /// * `request` - The custom event request containing event_name and args
@@ -444,14 +875,21 @@ impl RuntimeManager {
     pub fn dispatch_custom_event(&self, request: &api::CustomEventRequest) -> api::CustomEventResponse {
         let mut aggregated = api::CustomEventResponse::default();
 
-        // Dispatch to all runtimes (currently only JavaScript)
-        // Aggregate properties from all handlers
-        for runtime in self.runtimes.values() {
-            let response = runtime.dispatch_custom_event(request);
+        let event = api::EventKey::Custom(request.event_name.clone());
+        let batches = self.handler_batches(&event, |handler| request.accepts(handler));
+        if batches.is_empty() {
+            if let Some(target) = &request.target {
+                tracing::debug!("Custom event '{}' has no handlers in target mod '{}'", request.event_name, target);
+            }
+            return aggregated;
+        }
+
+        for (runtime, handlers) in batches {
+            let response = runtime.dispatch_custom_event(request, &handlers);
             if response.handled {
                 aggregated.handled = true;
             }
-            // Merge properties from this runtime into aggregated response
+            // Merge properties from this batch into aggregated response
             for (key, value) in response.properties {
                 aggregated.properties.insert(key, value);
             }
@@ -479,7 +917,7 @@ impl RuntimeManager {
         entity_id: u64,
         event_type: &str,
         event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
+    ) -> Result<bool, RuntimeError> {
         // Dispatch to all runtimes - the one that registered the callback will handle it
         for runtime in self.runtimes.values() {
             if runtime.dispatch_entity_event_callback(entity_id, event_type, event_data.clone())? {
@@ -506,14 +944,44 @@ impl RuntimeManager {
         window_id: u64,
         event_type: &str,
         event_data: serde_json::Value,
-    ) -> Result<bool, Box<dyn std::error::Error>> {
-        // Dispatch to all runtimes - the one that registered the callback will handle it
+    ) -> Result<bool, RuntimeError> {
+        // Dispatch to all runtimes - each invokes the callbacks its mods set on this window
+        let mut handled = false;
+        let mut first_error = None;
         for runtime in self.runtimes.values() {
-            if runtime.dispatch_window_event_callback(window_id, event_type, event_data.clone())? {
-                return Ok(true);
+            match runtime.dispatch_window_event_callback(window_id, event_type, event_data.clone()) {
+                Ok(invoked) => handled |= invoked,
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(handled),
+        }
+    }
+
+    /// Call `onTick(deltaMs)` on every mod that defines it, in all runtimes
+    pub fn dispatch_tick(&self, delta: Duration) {
+        for runtime in self.runtimes.values() {
+            runtime.dispatch_tick(delta);
+        }
+    }
+
+    /// Call `onShutdown` on every loaded mod that defines it, before the process exits
+    ///
+    /// Mods are called in ID order; a failing `onShutdown` is logged and the
+    /// remaining mods are still called. Promises started by the handlers keep
+    /// running in their runtime, so JavaScript hosts should drain pending jobs next.
+    pub fn dispatch_shutdown(&mut self) {
+        let mut mod_ids: Vec<String> = self.mod_to_runtime.keys().cloned().collect();
+        mod_ids.sort();
+        for mod_id in mod_ids {
+            if let Err(e) = self.call_mod_function(&mod_id, "onShutdown") {
+                tracing::warn!("onShutdown failed for mod '{}': {}", mod_id, e);
             }
         }
-        Ok(false)
     }
 }
 
@@ -522,3 +990,362 @@ impl Default for RuntimeManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    type LoadedMods = Rc<RefCell<Vec<(String, PathBuf)>>>;
+
+    /// Minimal adapter that records which mods were loaded into it
+    #[derive(Default)]
+    struct MockRuntimeAdapter {
+        loaded: LoadedMods,
+        call_timeout: Rc<Cell<Option<Duration>>>,
+        /// Whether `unload_mod` is supported (otherwise the trait default is used)
+        unloadable: bool,
+        /// Handlers reported by `collect_handlers` for every event
+        handlers: Vec<api::HandlerRef>,
+        /// Mod IDs of the handlers passed to `dispatch_custom_event`, in call order
+        dispatched: Rc<RefCell<Vec<String>>>,
+        /// Deltas passed to `dispatch_tick`
+        ticks: Rc<RefCell<Vec<Duration>>>,
+        /// `(mod_id, function_name)` of every `call_mod_function`, in call order
+        calls: Rc<RefCell<Vec<(String, String)>>>,
+        /// Real registrations to report instead of `handlers`, keyed by event
+        dispatcher: Option<api::EventDispatcher>,
+    }
+
+    impl RuntimeAdapter for MockRuntimeAdapter {
+        fn load_mod(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError> {
+            self.loaded.borrow_mut().push((mod_id.to_string(), mod_path.to_path_buf()));
+            Ok(())
+        }
+
+        fn add_mod_script(&mut self, mod_path: &Path, mod_id: &str) -> Result<(), RuntimeError> {
+            self.load_mod(mod_path, mod_id)
+        }
+
+        fn call_mod_function(&mut self, mod_id: &str, function_name: &str) -> Result<(), RuntimeError> {
+            self.calls.borrow_mut().push((mod_id.to_string(), function_name.to_string()));
+            if mod_id == "broken" {
+                return Err(RuntimeError::exception("onShutdown threw"));
+            }
+            Ok(())
+        }
+
+        fn call_mod_function_with_return(
+            &mut self,
+            _mod_id: &str,
+            _function_name: &str,
+        ) -> Result<ModReturnValue, RuntimeError> {
+            Ok(ModReturnValue::None)
+        }
+
+        fn call_event_handler(
+            &mut self,
+            _handler_id: u64,
+            _event_name: &str,
+            _args: &[String],
+        ) -> Result<(), RuntimeError> {
+            Ok(())
+        }
+
+        fn set_call_timeout(&mut self, timeout: Option<Duration>) {
+            self.call_timeout.set(timeout);
+        }
+
+        fn supports_unload(&self) -> bool {
+            self.unloadable
+        }
+
+        fn unload_mod(&mut self, mod_id: &str) -> Result<(), RuntimeError> {
+            if !self.unloadable {
+                return Err(RuntimeError::unsupported("unload not supported"));
+            }
+            self.loaded.borrow_mut().retain(|(id, _)| id != mod_id);
+            Ok(())
+        }
+
+        fn collect_handlers(&self, event: &api::EventKey) -> Vec<api::HandlerRef> {
+            match &self.dispatcher {
+                Some(dispatcher) => dispatcher.get_handlers_for_key(event).iter().map(api::HandlerRef::from).collect(),
+                None => self.handlers.clone(),
+            }
+        }
+
+        fn dispatch_terminal_key(&self, _request: &api::TerminalKeyRequest, _handlers: &[api::HandlerRef]) -> api::TerminalKeyResponse {
+            api::TerminalKeyResponse::default()
+        }
+
+        fn terminal_key_handler_count(&self) -> usize {
+            0
+        }
+
+        fn dispatch_terminal_mouse(&self, _request: &api::TerminalMouseRequest, _handlers: &[api::HandlerRef]) -> api::TerminalMouseResponse {
+            api::TerminalMouseResponse::default()
+        }
+
+        fn dispatch_terminal_paste(&self, _request: &api::TerminalPasteRequest, _handlers: &[api::HandlerRef]) -> api::TerminalPasteResponse {
+            api::TerminalPasteResponse::default()
+        }
+
+        fn dispatch_graphic_engine_ready(&self, _request: &api::GraphicEngineReadyRequest, _handlers: &[api::HandlerRef]) -> api::GraphicEngineReadyResponse {
+            api::GraphicEngineReadyResponse::default()
+        }
+
+        fn dispatch_graphic_engine_window_closed(&self, _request: &api::GraphicEngineWindowClosedRequest, _handlers: &[api::HandlerRef]) -> api::GraphicEngineWindowClosedResponse {
+            api::GraphicEngineWindowClosedResponse::default()
+        }
+
+        fn dispatch_custom_event(&self, _request: &api::CustomEventRequest, handlers: &[api::HandlerRef]) -> api::CustomEventResponse {
+            self.dispatched.borrow_mut().extend(handlers.iter().map(|h| h.mod_id.clone()));
+            api::CustomEventResponse::default()
+        }
+
+        fn dispatch_tick(&self, delta: Duration) {
+            self.ticks.borrow_mut().push(delta);
+        }
+    }
+
+    fn manager_with_mock() -> (RuntimeManager, LoadedMods) {
+        manager_with_mock_unloadable(false)
+    }
+
+    fn manager_with_mock_unloadable(unloadable: bool) -> (RuntimeManager, LoadedMods) {
+        let loaded = Rc::new(RefCell::new(Vec::new()));
+        let mut manager = RuntimeManager::new();
+        manager.register_adapter(
+            RuntimeType::JavaScript,
+            Box::new(MockRuntimeAdapter { loaded: loaded.clone(), unloadable, ..Default::default() }),
+        );
+        (manager, loaded)
+    }
+
+    #[test]
+    fn test_unknown_extension_is_rejected() {
+        let (mut manager, loaded) = manager_with_mock();
+        assert!(matches!(
+            manager.load_mod("mock-mod", Path::new("mods/mock-mod/index.mock")),
+            Err(RuntimeError::Unsupported { .. })
+        ));
+        assert!(loaded.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_registered_extension_routes_to_adapter() {
+        let (mut manager, loaded) = manager_with_mock();
+        manager.register_extension("mock", RuntimeType::JavaScript);
+
+        manager.load_mod("mock-mod", Path::new("mods/mock-mod/index.mock")).unwrap();
+
+        assert_eq!(loaded.borrow().len(), 1);
+        assert_eq!(loaded.borrow()[0].0, "mock-mod");
+        assert_eq!(manager.get_mod_runtime_type("mock-mod"), Some(RuntimeType::JavaScript));
+    }
+
+    #[test]
+    fn test_load_mod_scripts_in_order() {
+        let (mut manager, loaded) = manager_with_mock();
+        let scripts = [PathBuf::from("mods/multi/shared.js"), PathBuf::from("mods/multi/client.js")];
+
+        manager.load_mod_scripts("multi", &scripts).unwrap();
+        let paths: Vec<PathBuf> = loaded.borrow().iter().map(|(_, path)| path.clone()).collect();
+        assert_eq!(paths, scripts);
+        assert_eq!(manager.get_mod_runtime_type("multi"), Some(RuntimeType::JavaScript));
+
+        // Scripts for different runtimes are refused before anything is loaded
+        let mixed = [PathBuf::from("mods/mixed/main.js"), PathBuf::from("mods/mixed/extra.lua")];
+        assert!(matches!(manager.load_mod_scripts("mixed", &mixed), Err(RuntimeError::Unsupported { .. })));
+        assert!(manager.get_mod_runtime_type("mixed").is_none());
+        assert!(matches!(manager.load_mod_scripts("none", &[]), Err(RuntimeError::LoadFailed { .. })));
+    }
+
+    #[test]
+    fn test_builtin_extension_still_resolves() {
+        let (manager, _) = manager_with_mock();
+        assert_eq!(
+            manager.resolve_runtime_type(Path::new("index.js")).unwrap(),
+            RuntimeType::JavaScript
+        );
+        assert_eq!(
+            manager.resolve_runtime_type(Path::new("mod.wasm")).unwrap(),
+            RuntimeType::WebAssembly
+        );
+    }
+
+    #[test]
+    fn test_call_timeout_propagates_to_adapters() {
+        let timeout = Rc::new(Cell::new(None));
+        let mut manager = RuntimeManager::new();
+        assert_eq!(manager.call_timeout(), None);
+
+        manager.set_call_timeout(Duration::from_secs(5));
+        // Adapters registered after the timeout was set receive it too
+        manager.register_adapter(
+            RuntimeType::JavaScript,
+            Box::new(MockRuntimeAdapter { call_timeout: timeout.clone(), ..Default::default() }),
+        );
+        assert_eq!(timeout.get(), Some(Duration::from_secs(5)));
+
+        manager.clear_call_timeout();
+        assert_eq!(timeout.get(), None);
+        assert_eq!(manager.call_timeout(), None);
+    }
+
+    #[test]
+    fn test_unload_mod_forgets_mod() {
+        let (mut manager, loaded) = manager_with_mock_unloadable(true);
+        manager.load_mod("mock-mod", Path::new("mods/mock-mod/index.js")).unwrap();
+
+        manager.unload_mod("mock-mod").unwrap();
+
+        assert!(loaded.borrow().is_empty());
+        assert_eq!(manager.get_mod_runtime_type("mock-mod"), None);
+        assert_eq!(
+            manager.unload_mod("mock-mod"),
+            Err(RuntimeError::ModNotFound { mod_id: "mock-mod".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_unload_unsupported_keeps_mod() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let mut manager = RuntimeManager::new();
+        manager.register_adapter(
+            RuntimeType::JavaScript,
+            Box::new(MockRuntimeAdapter { calls: calls.clone(), ..Default::default() }),
+        );
+        manager.load_mod("mock-mod", Path::new("mods/mock-mod/index.js")).unwrap();
+
+        assert!(matches!(manager.unload_mod("mock-mod"), Err(RuntimeError::Unsupported { .. })));
+        assert_eq!(manager.get_mod_runtime_type("mock-mod"), Some(RuntimeType::JavaScript));
+        // The mod stays loaded, so it must not have been detached
+        assert!(calls.borrow().is_empty());
+    }
+
+    fn handler_ref(mod_id: &str, priority: i32, registration_seq: u64) -> api::HandlerRef {
+        api::HandlerRef {
+            mod_id: mod_id.to_string(),
+            handler_id: registration_seq,
+            priority,
+            registration_seq,
+            wildcard: false,
+        }
+    }
+
+    #[test]
+    fn test_dispatch_orders_handlers_across_runtimes() {
+        let dispatched = Rc::new(RefCell::new(Vec::new()));
+        let mut manager = RuntimeManager::new();
+        manager.register_adapter(
+            RuntimeType::JavaScript,
+            Box::new(MockRuntimeAdapter {
+                handlers: vec![handler_ref("js-first", 0, 1), handler_ref("js-last", 10, 4)],
+                dispatched: dispatched.clone(),
+                ..Default::default()
+            }),
+        );
+        manager.register_adapter(
+            RuntimeType::Lua,
+            Box::new(MockRuntimeAdapter {
+                handlers: vec![handler_ref("lua-tie", 0, 2), handler_ref("lua-middle", 5, 3)],
+                dispatched: dispatched.clone(),
+                ..Default::default()
+            }),
+        );
+
+        // Equal priorities fall back to registration order, whatever the HashMap order
+        for _ in 0..3 {
+            manager.dispatch_custom_event(&api::CustomEventRequest::new("ping", Vec::new()));
+            assert_eq!(*dispatched.borrow(), ["js-first", "lua-tie", "lua-middle", "js-last"]);
+            dispatched.borrow_mut().clear();
+        }
+
+        manager.dispatch_custom_event(&api::CustomEventRequest::new("ping", Vec::new()).with_target("lua-middle"));
+        assert_eq!(*dispatched.borrow(), ["lua-middle"]);
+    }
+
+    #[test]
+    fn test_wildcard_handlers_run_after_exact_ones() {
+        let dispatched = Rc::new(RefCell::new(Vec::new()));
+        let mut manager = RuntimeManager::new();
+        let js = api::EventDispatcher::new();
+        js.register_custom_handler("ui:*", "js-logger", -10);
+        let lua = api::EventDispatcher::new();
+        lua.register_custom_handler("ui:click", "lua-button", 10);
+        for (runtime_type, dispatcher) in [(RuntimeType::JavaScript, js), (RuntimeType::Lua, lua)] {
+            manager.register_adapter(
+                runtime_type,
+                Box::new(MockRuntimeAdapter {
+                    dispatched: dispatched.clone(),
+                    dispatcher: Some(dispatcher),
+                    ..Default::default()
+                }),
+            );
+        }
+
+        manager.dispatch_custom_event(&api::CustomEventRequest::new("ui:click", Vec::new()));
+        assert_eq!(*dispatched.borrow(), ["lua-button", "js-logger"]);
+        dispatched.borrow_mut().clear();
+
+        // The logger re-emitting the event doesn't receive it again
+        manager.dispatch_custom_event(&api::CustomEventRequest::new("ui:click", Vec::new()).with_sender("js-logger"));
+        assert_eq!(*dispatched.borrow(), ["lua-button"]);
+    }
+
+    #[test]
+    fn test_tick_reaches_every_runtime() {
+        let ticks = Rc::new(RefCell::new(Vec::new()));
+        let mut manager = RuntimeManager::new();
+        for runtime_type in [RuntimeType::JavaScript, RuntimeType::Lua] {
+            manager.register_adapter(
+                runtime_type,
+                Box::new(MockRuntimeAdapter { ticks: ticks.clone(), ..Default::default() }),
+            );
+        }
+
+        manager.dispatch_tick(Duration::from_millis(17));
+        assert_eq!(*ticks.borrow(), [Duration::from_millis(17); 2]);
+    }
+
+    #[test]
+    fn test_handler_count_per_event() {
+        let mut manager = RuntimeManager::new();
+        for (runtime_type, mod_id) in [(RuntimeType::JavaScript, "js-mod"), (RuntimeType::Lua, "lua-mod")] {
+            let dispatcher = api::EventDispatcher::new();
+            dispatcher.register_custom_handler("inventory:changed", mod_id, 0);
+            dispatcher.register_handler(api::SystemEvents::TerminalKeyPressed, mod_id, 0, api::RequestUriProtocol::All, "");
+            if runtime_type == RuntimeType::JavaScript {
+                dispatcher.register_custom_handler("inventory:changed", mod_id, 10);
+            }
+            manager.register_adapter(runtime_type, Box::new(MockRuntimeAdapter { dispatcher: Some(dispatcher), ..Default::default() }));
+        }
+
+        assert_eq!(manager.handler_count("inventory:changed"), 3);
+        assert_eq!(manager.handler_count("system:TerminalKeyPressed"), 2);
+        assert_eq!(manager.handler_count("chat:message"), 0);
+        assert_eq!(manager.custom_event_handler_count("inventory:changed"), 3);
+    }
+
+    #[test]
+    fn test_shutdown_reaches_every_mod() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let mut manager = RuntimeManager::new();
+        manager.register_adapter(
+            RuntimeType::JavaScript,
+            Box::new(MockRuntimeAdapter { calls: calls.clone(), ..Default::default() }),
+        );
+        for mod_id in ["saver", "broken", "another"] {
+            let entry_point = PathBuf::from(format!("mods/{}/index.js", mod_id));
+            manager.load_mod(mod_id, &entry_point).unwrap();
+        }
+
+        // A failing mod doesn't keep the others from saving
+        manager.dispatch_shutdown();
+        let called: Vec<String> = calls.borrow().iter().map(|(mod_id, function)| format!("{}.{}", mod_id, function)).collect();
+        assert_eq!(called, ["another.onShutdown", "broken.onShutdown", "saver.onShutdown"]);
+    }
+}
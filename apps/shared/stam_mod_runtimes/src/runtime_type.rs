@@ -7,8 +7,9 @@ use std::path::Path;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RuntimeType {
     JavaScript,
+    Lua,
+    WebAssembly,
     // Future runtime types:
-    // Lua,
     // CSharp,
     // Rust,
     // Cpp,
@@ -32,8 +33,9 @@ impl RuntimeType {
 
         match extension {
             "js" => Ok(RuntimeType::JavaScript),
+            "lua" => Ok(RuntimeType::Lua),
+            "wasm" => Ok(RuntimeType::WebAssembly),
             // Future extensions:
-            // "lua" => Ok(RuntimeType::Lua),
             // "cs" => Ok(RuntimeType::CSharp),
             // "rs" => Ok(RuntimeType::Rust),
             // "cpp" | "cc" | "cxx" => Ok(RuntimeType::Cpp),
@@ -45,8 +47,9 @@ impl RuntimeType {
     pub fn name(&self) -> &'static str {
         match self {
             RuntimeType::JavaScript => "JavaScript",
+            RuntimeType::Lua => "Lua",
+            RuntimeType::WebAssembly => "WebAssembly",
             // Future:
-            // RuntimeType::Lua => "Lua",
             // RuntimeType::CSharp => "C#",
             // RuntimeType::Rust => "Rust",
             // RuntimeType::Cpp => "C++",
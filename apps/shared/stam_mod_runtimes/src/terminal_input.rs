@@ -3,21 +3,30 @@
 //! Provides cross-platform terminal input handling with raw mode support.
 //! This module allows intercepting all keyboard events before they are
 //! processed by the default terminal handler.
+//!
+//! Mouse reporting (SGR extended mode) is enabled together with raw mode, so
+//! clicks, drags and scrolls are reported as well. crossterm decodes the
+//! mouse escape sequences as whole events, so they never leak into key events.
+//!
+//! Bracketed paste is enabled too: text pasted into the terminal arrives as a
+//! single paste event instead of one key event per character.
 
 use std::io;
 use std::time::Duration;
 
 use crossterm::event::{
-    self, Event, KeyCode, KeyEvent, KeyModifiers,
-    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+    KeyCode, KeyEvent, KeyModifiers, KeyboardEnhancementFlags, MouseButton, MouseEvent, MouseEventKind,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
 };
 use stam_log::set_raw_mode_active;
 use crossterm::terminal;
 use crossterm::execute;
 use tokio::sync::mpsc;
-use tracing::{debug, error};
+use tokio::sync::mpsc::error::TrySendError;
+use tracing::{debug, error, trace};
 
-use crate::api::TerminalKeyRequest;
+use crate::api::{TerminalKeyRequest, TerminalMouseRequest, TerminalPasteRequest};
 
 // Re-export is_raw_mode_active from stam_log for backwards compatibility
 pub use stam_log::is_raw_mode_active;
@@ -145,6 +154,39 @@ fn key_code_to_string(code: &KeyCode) -> String {
     }
 }
 
+/// Convert a crossterm MouseEvent to a TerminalMouseRequest
+pub fn mouse_event_to_request(mouse_event: &MouseEvent) -> TerminalMouseRequest {
+    let (button, kind) = match mouse_event.kind {
+        MouseEventKind::Down(button) => (Some(button), "Down"),
+        MouseEventKind::Up(button) => (Some(button), "Up"),
+        MouseEventKind::Drag(button) => (Some(button), "Drag"),
+        MouseEventKind::Moved => (None, "Moved"),
+        MouseEventKind::ScrollUp => (None, "ScrollUp"),
+        MouseEventKind::ScrollDown => (None, "ScrollDown"),
+        MouseEventKind::ScrollLeft => (None, "ScrollLeft"),
+        MouseEventKind::ScrollRight => (None, "ScrollRight"),
+    };
+    let button = match button {
+        Some(MouseButton::Left) => "Left",
+        Some(MouseButton::Right) => "Right",
+        Some(MouseButton::Middle) => "Middle",
+        None => "None",
+    };
+
+    TerminalMouseRequest::new(mouse_event.column, mouse_event.row, button, kind)
+}
+
+/// Receivers for the events produced by the terminal event reader
+pub struct TerminalEventReceivers {
+    /// Receives a TerminalKeyRequest for each key press
+    pub keys: mpsc::Receiver<TerminalKeyRequest>,
+    /// Receives a TerminalMouseRequest for each mouse event. Mouse events are
+    /// dropped (never blocking key input) while this channel is full.
+    pub mouse: mpsc::Receiver<TerminalMouseRequest>,
+    /// Receives a TerminalPasteRequest for each block of pasted text
+    pub paste: mpsc::Receiver<TerminalPasteRequest>,
+}
+
 /// Handle for the terminal event reader thread
 ///
 /// When dropped, this will signal the thread to stop and wait for cleanup to complete.
@@ -190,22 +232,44 @@ impl Drop for TerminalReaderHandle {
 
 /// Spawn a task that reads terminal events and sends them to a channel
 ///
-/// This function enables raw mode and starts reading keyboard events.
-/// Events are sent to the returned receiver channel.
+/// This function enables raw mode, mouse reporting and bracketed paste, then
+/// starts reading keyboard, mouse and paste events. Events are sent to the
+/// returned receiver channels.
 ///
 /// # Returns
-/// A tuple of (receiver, handle) where:
-/// - receiver: Receives TerminalKeyRequest for each key press
+/// A tuple of (receivers, handle) where:
+/// - receivers: One channel per kind of event (see `TerminalEventReceivers`)
 /// - handle: Handle to stop the reader and ensure cleanup. When dropped, it will
 ///           automatically stop the reader and wait for the terminal to be restored.
-pub fn spawn_terminal_event_reader() -> io::Result<(
-    mpsc::Receiver<TerminalKeyRequest>,
-    TerminalReaderHandle,
-)> {
+pub fn spawn_terminal_event_reader() -> io::Result<(TerminalEventReceivers, TerminalReaderHandle)> {
     // Enable raw mode
     terminal::enable_raw_mode()?;
     set_raw_mode_active(true);
 
+    // Enable mouse reporting (xterm SGR mode)
+    let mouse_capture_enabled = match execute!(std::io::stdout(), EnableMouseCapture) {
+        Ok(()) => {
+            debug!("Terminal mouse reporting enabled");
+            true
+        }
+        Err(e) => {
+            debug!("Failed to enable terminal mouse reporting: {}", e);
+            false
+        }
+    };
+
+    // Enable bracketed paste, so pasted text is not read as individual key presses
+    let bracketed_paste_enabled = match execute!(std::io::stdout(), EnableBracketedPaste) {
+        Ok(()) => {
+            debug!("Terminal bracketed paste enabled");
+            true
+        }
+        Err(e) => {
+            debug!("Failed to enable terminal bracketed paste: {}", e);
+            false
+        }
+    };
+
     // Try to enable keyboard enhancement for better modifier key detection
     // This uses the kitty keyboard protocol which is supported by modern terminals
     // (kitty, foot, WezTerm, alacritty, etc.)
@@ -225,6 +289,8 @@ pub fn spawn_terminal_event_reader() -> io::Result<(
     };
 
     let (event_tx, event_rx) = mpsc::channel::<TerminalKeyRequest>(32);
+    let (mouse_tx, mouse_rx) = mpsc::channel::<TerminalMouseRequest>(64);
+    let (paste_tx, paste_rx) = mpsc::channel::<TerminalPasteRequest>(8);
     let (cancel_tx, mut cancel_rx) = mpsc::channel::<()>(1);
 
     // Spawn blocking task to read terminal events
@@ -250,8 +316,25 @@ pub fn spawn_terminal_event_reader() -> io::Result<(
                                 break;
                             }
                         }
+                        Ok(Event::Mouse(mouse_event)) => {
+                            let request = mouse_event_to_request(&mouse_event);
+                            trace!("Mouse event: {} {} at ({}, {})", request.kind, request.button, request.x, request.y);
+
+                            // Never block on mouse events: drop them if mods can't keep up
+                            if let Err(TrySendError::Closed(_)) = mouse_tx.try_send(request) {
+                                break;
+                            }
+                        }
+                        Ok(Event::Paste(text)) => {
+                            debug!("Paste event: {} characters", text.chars().count());
+
+                            if paste_tx.blocking_send(TerminalPasteRequest::new(text)).is_err() {
+                                // Channel closed, exit
+                                break;
+                            }
+                        }
                         Ok(_) => {
-                            // Ignore non-key events (mouse, resize, etc.)
+                            // Ignore other events (resize, focus, etc.)
                         }
                         Err(e) => {
                             error!("Error reading terminal event: {}", e);
@@ -274,6 +357,16 @@ pub fn spawn_terminal_event_reader() -> io::Result<(
             let _ = execute!(std::io::stdout(), PopKeyboardEnhancementFlags);
         }
 
+        // Disable mouse reporting so the terminal gets its normal selection back
+        if mouse_capture_enabled {
+            let _ = execute!(std::io::stdout(), DisableMouseCapture);
+        }
+
+        // Disable bracketed paste so the shell doesn't receive paste brackets after exit
+        if bracketed_paste_enabled {
+            let _ = execute!(std::io::stdout(), DisableBracketedPaste);
+        }
+
         // Disable raw mode when done
         set_raw_mode_active(false);
         if let Err(e) = terminal::disable_raw_mode() {
@@ -287,7 +380,13 @@ pub fn spawn_terminal_event_reader() -> io::Result<(
         join_handle: Some(join_handle),
     };
 
-    Ok((event_rx, handle))
+    let receivers = TerminalEventReceivers {
+        keys: event_rx,
+        mouse: mouse_rx,
+        paste: paste_rx,
+    };
+
+    Ok((receivers, handle))
 }
 
 /// RAII guard that enables raw mode on creation and disables it on drop
@@ -381,6 +480,33 @@ mod tests {
         assert_eq!(request.combo, "Ctrl+Shift+c");
     }
 
+    #[test]
+    fn test_mouse_event_to_request() {
+        let click = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 12,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(mouse_event_to_request(&click), TerminalMouseRequest::new(12, 3, "Left", "Down"));
+
+        let drag = MouseEvent {
+            kind: MouseEventKind::Drag(MouseButton::Right),
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::SHIFT,
+        };
+        assert_eq!(mouse_event_to_request(&drag), TerminalMouseRequest::new(0, 0, "Right", "Drag"));
+
+        let scroll = MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 40,
+            row: 10,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(mouse_event_to_request(&scroll), TerminalMouseRequest::new(40, 10, "None", "ScrollDown"));
+    }
+
     #[test]
     fn test_key_event_lowercase_no_shift() {
         let event = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
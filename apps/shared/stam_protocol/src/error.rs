@@ -15,4 +15,7 @@ pub enum ProtocolError {
 
     #[error("Connection closed")]
     ConnectionClosed,
+
+    #[error("TLS error: {0}")]
+    Tls(String),
 }
@@ -7,6 +7,8 @@ pub struct ModInfo {
     pub mod_id: String,
     /// Mod type (e.g., "bootstrap", "library", etc.)
     pub mod_type: String,
+    /// Mod version from the packaged manifest (empty if unknown)
+    pub version: String,
     /// Download URL for this mod
     pub download_url: String,
     /// SHA-512 hash of the mod archive
@@ -17,6 +19,37 @@ pub struct ModInfo {
     pub uncompressed_bytes: u64,
 }
 
+/// Why the server closes a game session, sent with `GameMessage::Disconnect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisconnectReason {
+    /// The server is shutting down for good
+    Shutdown,
+    /// The server is restarting and will be back shortly
+    Restart,
+    /// The server is going down for maintenance
+    Maintenance,
+    /// The player was kicked
+    Kicked,
+    /// The player was banned
+    Banned,
+    /// The client sent nothing for longer than the server's idle timeout
+    IdleTimeout,
+}
+
+impl DisconnectReason {
+    /// Reason string exposed to mods
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DisconnectReason::Shutdown => "shutdown",
+            DisconnectReason::Restart => "restart",
+            DisconnectReason::Maintenance => "maintenance",
+            DisconnectReason::Kicked => "kicked",
+            DisconnectReason::Banned => "banned",
+            DisconnectReason::IdleTimeout => "idle-timeout",
+        }
+    }
+}
+
 /// Game protocol messages for authenticated game clients
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GameMessage {
@@ -41,8 +74,33 @@ pub enum GameMessage {
 
     /// Disconnect message from server to client (graceful disconnection)
     Disconnect {
-        /// Disconnect reason/message
+        /// Locale ID of the message to display (e.g., "disconnect-server-shutdown")
         message: String,
+        /// Why the session is closed
+        reason: DisconnectReason,
+    },
+
+    // Keepalive (both directions)
+    /// Keepalive probe, the receiver must answer with a `Pong` carrying the same nonce
+    Ping {
+        /// Sequence number identifying this ping
+        nonce: u64,
+    },
+
+    /// Answer to a `Ping`
+    Pong {
+        /// Nonce of the ping being answered
+        nonce: u64,
+    },
+
+    // Server -> Client (mods)
+    /// Message pushed by a server mod, to every client of the game (`system.broadcast`)
+    /// or to a single one (`system.sendToClient`)
+    Broadcast {
+        /// Channel name, dispatched to client mods as a custom event of the same name
+        channel: String,
+        /// JSON-encoded payload
+        payload: String,
     },
 
     // Future game messages will be added here
@@ -2,8 +2,13 @@ pub mod error;
 pub mod game_message;
 pub mod primal_message;
 pub mod stream;
+pub mod transport;
+pub mod version;
 
 pub use error::{ProtocolError, Result};
-pub use game_message::{GameMessage, ModInfo};
-pub use primal_message::{IntentType, PrimalMessage, ServerInfo};
-pub use stream::{GameStream, PrimalStream, DEFAULT_MAX_MESSAGE_SIZE};
+pub use game_message::{DisconnectReason, GameMessage, ModInfo};
+pub use primal_message::{Compression, IntentType, PrimalMessage, ServerInfo};
+pub use stream::{GameStream, MessageLimit, PrimalStream, DEFAULT_MAX_MESSAGE_SIZE};
+pub use transport::{StamStream, load_tls_acceptor};
+pub use version::{check_compatibility, Incompatible, PROTOCOL_VERSION};
+pub use tokio_rustls::TlsAcceptor;
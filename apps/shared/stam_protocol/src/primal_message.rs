@@ -11,6 +11,49 @@ pub struct ServerInfo {
     pub server_name: String,
     /// Server URI (e.g., "stam://game.example.com:9999")
     pub uri: String,
+    /// Short description of the game for server browsers
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Number of game clients currently playing this game
+    #[serde(default)]
+    pub player_count: u32,
+    /// Advertised player capacity (None = no limit announced)
+    #[serde(default)]
+    pub max_players: Option<u32>,
+    /// URI of an icon for the game (e.g., "stam://game.example.com:9999/icon.png")
+    #[serde(default)]
+    pub icon_uri: Option<String>,
+}
+
+/// Compression algorithm for chunked transfers
+///
+/// The server advertises what it supports in `Welcome`, the client picks one in
+/// its `Intent`. Chunks are then compressed individually, and a chunk that would
+/// not shrink is sent as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    /// gzip (deflate) compression
+    Gzip,
+    /// Zstandard compression, better ratios on text assets
+    Zstd,
+}
+
+impl Compression {
+    /// Level used when none is configured (fast, favouring CPU over ratio)
+    pub fn default_level(self) -> u8 {
+        match self {
+            Compression::Gzip => 1,
+            Compression::Zstd => 3,
+        }
+    }
+
+    /// Levels accepted by the algorithm
+    pub fn level_range(self) -> std::ops::RangeInclusive<u8> {
+        match self {
+            Compression::Gzip => 0..=9,
+            Compression::Zstd => 1..=22,
+        }
+    }
 }
 
 /// Client intent type - determines how the connection will be handled
@@ -24,6 +67,8 @@ pub enum IntentType {
     ServerLogin,
     /// URI request - one-shot request for downloading resources via stam:// protocol
     RequestUri,
+    /// URI listing - one-shot request for the entries of a stam:// directory
+    ListUri,
 }
 
 /// Primal protocol messages for initial connection handling
@@ -37,6 +82,10 @@ pub enum PrimalMessage {
     Welcome {
         /// Server version string
         version: String,
+        /// Wire protocol version spoken by the server (`PROTOCOL_VERSION`)
+        protocol_version: u32,
+        /// Compression the server supports for chunked transfers (None = uncompressed only)
+        compression: Option<Compression>,
     },
 
     /// Error message from server to client (causes immediate disconnection)
@@ -62,8 +111,10 @@ pub enum PrimalMessage {
     Intent {
         /// Type of connection intent
         intent_type: IntentType,
-        /// Client version for compatibility check
+        /// Client application version (informational, compatibility is checked on `protocol_version`)
         client_version: String,
+        /// Wire protocol version spoken by the client, must match the server's
+        protocol_version: u32,
         /// Username
         username: String,
         /// SHA-512 hash of the password (not plaintext)
@@ -72,6 +123,10 @@ pub enum PrimalMessage {
         game_id: Option<String>,
         /// URI being requested (required for RequestUri intent, sanitized without credentials)
         uri: Option<String>,
+        /// Compression accepted for chunked transfers, picked from the Welcome (None = uncompressed)
+        compression: Option<Compression>,
+        /// Byte offset to resume a RequestUri download from (None = whole file)
+        range_start: Option<u64>,
     },
 
     // Server -> Client messages for RequestUri
@@ -83,8 +138,11 @@ pub enum PrimalMessage {
         buffer_string: Option<String>,
         /// File name (if response is a file)
         file_name: Option<String>,
-        /// File size in bytes (if response is a file)
+        /// File size in bytes (if response is a file), always the size of the whole file
         file_size: Option<u64>,
+        /// Offset the chunk stream starts at, echoed when the server honored the
+        /// requested `range_start` (None = the whole file is sent)
+        range_start: Option<u64>,
     },
 
     /// URI response chunk - sent for large file transfers
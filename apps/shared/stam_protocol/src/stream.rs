@@ -1,16 +1,44 @@
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 use crate::error::{ProtocolError, Result};
-use crate::primal_message::PrimalMessage;
+use crate::primal_message::{Compression, PrimalMessage};
 use crate::game_message::GameMessage;
 
 /// Default maximum message size: 25MB (configurable via network_max_chunk_size)
 pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 25 * 1024 * 1024;
 
-/// Extension trait for TcpStream to read/write PrimalMessages
+/// Streams that carry a limit on the size of incoming frames
+///
+/// The readers of `PrimalStream` and `GameStream` check every length prefix
+/// against this limit before allocating anything for the frame, so a peer
+/// can't make us allocate by announcing a huge message or chunk.
+pub trait MessageLimit {
+    /// Largest message or chunk accepted from the peer, in bytes
+    fn max_message_bytes(&self) -> usize {
+        DEFAULT_MAX_MESSAGE_SIZE
+    }
+}
+
+impl MessageLimit for tokio::net::TcpStream {}
+
+impl MessageLimit for tokio::io::DuplexStream {}
+
+/// Raw chunk flag: this is the last chunk of the transfer
+const CHUNK_FLAG_FINAL: u8 = 0x01;
+
+/// Raw chunk flag: the chunk data is gzip-compressed
+const CHUNK_FLAG_GZIP: u8 = 0x02;
+
+/// Raw chunk flag: the chunk data is zstd-compressed
+const CHUNK_FLAG_ZSTD: u8 = 0x04;
+
+/// Extension trait for streams (plain TCP or TLS) to read/write PrimalMessages
 pub trait PrimalStream {
-    /// Read a PrimalMessage from the stream with default max size
+    /// Read a PrimalMessage from the stream, limited to the stream's `max_message_bytes`
     /// Format: [4 bytes length][message bytes]
     async fn read_primal_message(&mut self) -> Result<PrimalMessage>;
 
@@ -18,7 +46,7 @@ pub trait PrimalStream {
     /// Format: [4 bytes length][message bytes]
     async fn read_primal_message_with_max_size(&mut self, max_size: usize) -> Result<PrimalMessage>;
 
-    /// Write a PrimalMessage to the stream with default max size
+    /// Write a PrimalMessage to the stream, limited to the stream's `max_message_bytes`
     /// Format: [4 bytes length][message bytes]
     async fn write_primal_message(&mut self, message: &PrimalMessage) -> Result<()>;
 
@@ -27,19 +55,36 @@ pub trait PrimalStream {
     async fn write_primal_message_with_max_size(&mut self, message: &PrimalMessage, max_size: usize) -> Result<()>;
 
     /// Write a raw data chunk directly without extra allocations
-    /// Format: [4 bytes total_len][1 byte is_final][4 bytes data_len][data bytes]
+    /// Format: [4 bytes total_len][1 byte flags][4 bytes data_len][data bytes]
     /// This is optimized for streaming large files - avoids serialization overhead
     async fn write_raw_chunk(&mut self, data: &[u8], is_final: bool) -> Result<()>;
 
+    /// Write a raw data chunk, compressing it with the negotiated compression
+    /// The chunk is sent uncompressed when compression is None or would not shrink it.
+    /// Readers inflate compressed chunks transparently.
+    async fn write_raw_chunk_compressed(&mut self, data: &[u8], is_final: bool, compression: Option<Compression>) -> Result<()>;
+
+    /// Write a raw data chunk, compressing it with the negotiated compression at a custom level
+    /// `level` must be within `Compression::level_range` of the algorithm.
+    async fn write_raw_chunk_compressed_with_level(&mut self, data: &[u8], is_final: bool, compression: Option<Compression>, level: u8) -> Result<()>;
+
     /// Read a raw data chunk into a pre-allocated buffer, returning bytes read and is_final flag
     /// Returns (bytes_read, is_final). The data is written to the provided buffer.
+    /// Compressed chunks are inflated, so bytes_read is always the uncompressed length.
     /// This is optimized for streaming large files - avoids deserialization overhead
     async fn read_raw_chunk(&mut self, buffer: &mut [u8]) -> Result<(usize, bool)>;
+
+    /// Read a raw data chunk into a growable buffer, returning bytes read and is_final flag
+    /// The buffer is only grown when the incoming chunk exceeds its current length
+    /// (up to the stream's `max_message_bytes`), so small transfers keep a small buffer.
+    /// Compressed chunks are inflated, so bytes_read is always the uncompressed length.
+    async fn read_raw_chunk_growable(&mut self, buffer: &mut Vec<u8>) -> Result<(usize, bool)>;
 }
 
-impl PrimalStream for TcpStream {
+impl<S: AsyncRead + AsyncWrite + Unpin + MessageLimit> PrimalStream for S {
     async fn read_primal_message(&mut self) -> Result<PrimalMessage> {
-        self.read_primal_message_with_max_size(DEFAULT_MAX_MESSAGE_SIZE).await
+        let max_size = self.max_message_bytes();
+        self.read_primal_message_with_max_size(max_size).await
     }
 
     async fn read_primal_message_with_max_size(&mut self, max_size: usize) -> Result<PrimalMessage> {
@@ -68,8 +113,9 @@ impl PrimalStream for TcpStream {
         let data = message.to_bytes()?;
 
         // Check max size
-        if data.len() > DEFAULT_MAX_MESSAGE_SIZE {
-            return Err(ProtocolError::MessageTooLarge(data.len(), DEFAULT_MAX_MESSAGE_SIZE));
+        let max_size = self.max_message_bytes();
+        if data.len() > max_size {
+            return Err(ProtocolError::MessageTooLarge(data.len(), max_size));
         }
 
         // Write length (4 bytes, big-endian)
@@ -106,20 +152,46 @@ impl PrimalStream for TcpStream {
     }
 
     async fn write_raw_chunk(&mut self, data: &[u8], is_final: bool) -> Result<()> {
-        // Format: [4 bytes total_len][1 byte is_final][4 bytes data_len][data bytes]
+        self.write_raw_chunk_compressed(data, is_final, None).await
+    }
+
+    async fn write_raw_chunk_compressed(&mut self, data: &[u8], is_final: bool, compression: Option<Compression>) -> Result<()> {
+        let level = compression.map_or(0, Compression::default_level);
+        self.write_raw_chunk_compressed_with_level(data, is_final, compression, level).await
+    }
+
+    async fn write_raw_chunk_compressed_with_level(&mut self, data: &[u8], is_final: bool, compression: Option<Compression>, level: u8) -> Result<()> {
+        // Format: [4 bytes total_len][1 byte flags][4 bytes data_len][data bytes]
         // total_len = 1 + 4 + data.len()
+        let compressed = match compression {
+            Some(compression) => compress_chunk(data, compression, level)?.map(|bytes| (compression, bytes)),
+            None => None,
+        };
+
+        let mut flags = if is_final { CHUNK_FLAG_FINAL } else { 0 };
+        let data = match &compressed {
+            Some((compression, compressed)) => {
+                flags |= match compression {
+                    Compression::Gzip => CHUNK_FLAG_GZIP,
+                    Compression::Zstd => CHUNK_FLAG_ZSTD,
+                };
+                compressed.as_slice()
+            }
+            None => data,
+        };
+
         let total_len = 1 + 4 + data.len();
 
         // Write total length
         self.write_u32(total_len as u32).await?;
 
-        // Write is_final flag (1 byte)
-        self.write_u8(if is_final { 1 } else { 0 }).await?;
+        // Write flags (1 byte)
+        self.write_u8(flags).await?;
 
         // Write data length
         self.write_u32(data.len() as u32).await?;
 
-        // Write data directly from the slice - no allocation!
+        // Write data directly from the slice - no allocation for uncompressed chunks
         self.write_all(data).await?;
 
         // Don't flush - let TCP buffer for throughput
@@ -127,21 +199,17 @@ impl PrimalStream for TcpStream {
     }
 
     async fn read_raw_chunk(&mut self, buffer: &mut [u8]) -> Result<(usize, bool)> {
-        // Format: [4 bytes total_len][1 byte is_final][4 bytes data_len][data bytes]
-
-        // Read total length
-        let total_len = self.read_u32().await? as usize;
-
-        if total_len < 5 {
-            return Err(ProtocolError::ConnectionClosed);
+        let max_size = self.max_message_bytes();
+        let (flags, data_len) = read_raw_chunk_header(self, max_size).await?;
+        let is_final = flags & CHUNK_FLAG_FINAL != 0;
+
+        if let Some(compression) = chunk_compression(flags) {
+            let compressed = read_compressed_payload(self, data_len).await?;
+            let inflated = inflate_chunk(&compressed, compression, buffer.len())?;
+            buffer[..inflated.len()].copy_from_slice(&inflated);
+            return Ok((inflated.len(), is_final));
         }
 
-        // Read is_final flag
-        let is_final = self.read_u8().await? != 0;
-
-        // Read data length
-        let data_len = self.read_u32().await? as usize;
-
         // Validate buffer is large enough
         if data_len > buffer.len() {
             return Err(ProtocolError::MessageTooLarge(data_len, buffer.len()));
@@ -152,9 +220,115 @@ impl PrimalStream for TcpStream {
 
         Ok((data_len, is_final))
     }
+
+    async fn read_raw_chunk_growable(&mut self, buffer: &mut Vec<u8>) -> Result<(usize, bool)> {
+        let max_size = self.max_message_bytes();
+        let (flags, data_len) = read_raw_chunk_header(self, max_size).await?;
+        let is_final = flags & CHUNK_FLAG_FINAL != 0;
+
+        if let Some(compression) = chunk_compression(flags) {
+            let compressed = read_compressed_payload(self, data_len).await?;
+            let inflated = inflate_chunk(&compressed, compression, max_size)?;
+            if inflated.len() > buffer.len() {
+                buffer.resize(inflated.len(), 0);
+            }
+            buffer[..inflated.len()].copy_from_slice(&inflated);
+            return Ok((inflated.len(), is_final));
+        }
+
+        // Grow only when this chunk doesn't fit
+        if data_len > buffer.len() {
+            buffer.resize(data_len, 0);
+        }
+
+        self.read_exact(&mut buffer[..data_len]).await?;
+
+        Ok((data_len, is_final))
+    }
+}
+
+/// Read the header of a raw chunk, returning (flags, data_len)
+/// Format: [4 bytes total_len][1 byte flags][4 bytes data_len]
+///
+/// The announced payload is checked against `max_size` (and against `total_len`)
+/// before the caller allocates anything for it.
+async fn read_raw_chunk_header<S: AsyncRead + Unpin>(stream: &mut S, max_size: usize) -> Result<(u8, usize)> {
+    // Read total length
+    let total_len = stream.read_u32().await? as usize;
+
+    if total_len < 5 {
+        return Err(ProtocolError::ConnectionClosed);
+    }
+
+    // Read flags (final / compressed)
+    let flags = stream.read_u8().await?;
+
+    // Read data length
+    let data_len = stream.read_u32().await? as usize;
+
+    if data_len > max_size {
+        return Err(ProtocolError::MessageTooLarge(data_len, max_size));
+    }
+    if data_len != total_len - 5 {
+        return Err(ProtocolError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("raw chunk length mismatch: frame of {} bytes carries {} data bytes", total_len, data_len),
+        )));
+    }
+
+    Ok((flags, data_len))
+}
+
+/// Compression of a raw chunk's payload, from its flags
+fn chunk_compression(flags: u8) -> Option<Compression> {
+    if flags & CHUNK_FLAG_GZIP != 0 {
+        Some(Compression::Gzip)
+    } else if flags & CHUNK_FLAG_ZSTD != 0 {
+        Some(Compression::Zstd)
+    } else {
+        None
+    }
+}
+
+/// Read the compressed payload of a raw chunk (its size was checked by `read_raw_chunk_header`)
+async fn read_compressed_payload<S: AsyncRead + Unpin>(stream: &mut S, data_len: usize) -> Result<Vec<u8>> {
+    let mut compressed = vec![0u8; data_len];
+    stream.read_exact(&mut compressed).await?;
+    Ok(compressed)
+}
+
+/// Compress a chunk, returning None when compression would not make it smaller
+fn compress_chunk(data: &[u8], compression: Compression, level: u8) -> Result<Option<Vec<u8>>> {
+    let compressed = match compression {
+        Compression::Gzip => {
+            let level = flate2::Compression::new(level.into());
+            let mut encoder = GzEncoder::new(Vec::with_capacity(data.len() / 2), level);
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        Compression::Zstd => zstd::bulk::compress(data, level.into())?,
+    };
+
+    Ok((compressed.len() < data.len()).then_some(compressed))
+}
+
+/// Inflate a compressed chunk, refusing output larger than max_size
+fn inflate_chunk(compressed: &[u8], compression: Compression, max_size: usize) -> Result<Vec<u8>> {
+    let limit = max_size as u64 + 1;
+    let mut inflated = Vec::new();
+    match compression {
+        Compression::Gzip => GzDecoder::new(compressed).take(limit).read_to_end(&mut inflated)?,
+        Compression::Zstd => zstd::stream::read::Decoder::new(compressed)?.take(limit).read_to_end(&mut inflated)?,
+    };
+
+    if inflated.len() > max_size {
+        return Err(ProtocolError::MessageTooLarge(inflated.len(), max_size));
+    }
+
+    Ok(inflated)
 }
 
-/// Extension trait for TcpStream to read/write GameMessages
+/// Extension trait for streams (plain TCP or TLS) to read/write GameMessages
 pub trait GameStream {
     /// Read a GameMessage from the stream
     /// Format: [4 bytes length][message bytes]
@@ -165,14 +339,15 @@ pub trait GameStream {
     async fn write_game_message(&mut self, message: &GameMessage) -> Result<()>;
 }
 
-impl GameStream for TcpStream {
+impl<S: AsyncRead + AsyncWrite + Unpin + MessageLimit> GameStream for S {
     async fn read_game_message(&mut self) -> Result<GameMessage> {
         // Read message length (4 bytes, big-endian)
         let len = self.read_u32().await? as usize;
 
-        // Check max size
-        if len > DEFAULT_MAX_MESSAGE_SIZE {
-            return Err(ProtocolError::MessageTooLarge(len, DEFAULT_MAX_MESSAGE_SIZE));
+        // Check max size before allocating
+        let max_size = self.max_message_bytes();
+        if len > max_size {
+            return Err(ProtocolError::MessageTooLarge(len, max_size));
         }
 
         if len == 0 {
@@ -192,8 +367,9 @@ impl GameStream for TcpStream {
         let data = message.to_bytes()?;
 
         // Check max size
-        if data.len() > DEFAULT_MAX_MESSAGE_SIZE {
-            return Err(ProtocolError::MessageTooLarge(data.len(), DEFAULT_MAX_MESSAGE_SIZE));
+        let max_size = self.max_message_bytes();
+        if data.len() > max_size {
+            return Err(ProtocolError::MessageTooLarge(data.len(), max_size));
         }
 
         // Write length (4 bytes, big-endian)
@@ -208,3 +384,115 @@ impl GameStream for TcpStream {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_oversized_message_is_rejected_before_reading() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+
+        // A peer announcing a 4GB message (and sending nothing else)
+        client.write_u32(u32::MAX).await.unwrap();
+        let err = server.read_game_message().await.unwrap_err();
+        assert!(matches!(err, ProtocolError::MessageTooLarge(len, max) if len == u32::MAX as usize && max == DEFAULT_MAX_MESSAGE_SIZE), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_raw_chunk_is_rejected() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+
+        let data_len = DEFAULT_MAX_MESSAGE_SIZE as u32 + 1;
+        client.write_u32(data_len + 5).await.unwrap();
+        client.write_u8(CHUNK_FLAG_FINAL).await.unwrap();
+        client.write_u32(data_len).await.unwrap();
+
+        let mut buffer = Vec::new();
+        let err = server.read_raw_chunk_growable(&mut buffer).await.unwrap_err();
+        assert!(matches!(err, ProtocolError::MessageTooLarge(..)), "{}", err);
+        assert!(buffer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_raw_chunk_length_mismatch_is_rejected() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+
+        // The frame claims 10 bytes but the header says 1000 follow
+        client.write_u32(10).await.unwrap();
+        client.write_u8(CHUNK_FLAG_FINAL).await.unwrap();
+        client.write_u32(1000).await.unwrap();
+
+        let mut buffer = Vec::new();
+        assert!(server.read_raw_chunk_growable(&mut buffer).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_compressed_chunks_round_trip() {
+        let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+        let text = "locale.greeting = Hello\n".repeat(200);
+
+        client.write_raw_chunk_compressed_with_level(text.as_bytes(), false, Some(Compression::Zstd), 19).await.unwrap();
+        client.write_raw_chunk_compressed_with_level(text.as_bytes(), false, Some(Compression::Gzip), 9).await.unwrap();
+        // Incompressible data is sent as-is
+        client.write_raw_chunk_compressed(&[7], true, Some(Compression::Zstd)).await.unwrap();
+
+        let mut buffer = Vec::new();
+        for _ in 0..2 {
+            let (len, is_final) = server.read_raw_chunk_growable(&mut buffer).await.unwrap();
+            assert_eq!(&buffer[..len], text.as_bytes());
+            assert!(!is_final);
+        }
+        assert_eq!(server.read_raw_chunk_growable(&mut buffer).await.unwrap(), (1, true));
+        assert_eq!(buffer[0], 7);
+    }
+
+    /// Stream with a custom limit, as `StamStream::set_max_message_bytes` configures
+    struct Limited(tokio::io::DuplexStream, usize);
+
+    impl AsyncRead for Limited {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(&mut self.0).poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncWrite for Limited {
+        fn poll_write(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            buf: &[u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            std::pin::Pin::new(&mut self.0).poll_write(cx, buf)
+        }
+
+        fn poll_flush(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(&mut self.0).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<std::io::Result<()>> {
+            std::pin::Pin::new(&mut self.0).poll_shutdown(cx)
+        }
+    }
+
+    impl MessageLimit for Limited {
+        fn max_message_bytes(&self) -> usize {
+            self.1
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_limit_applies_to_messages() {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut client = Limited(client, DEFAULT_MAX_MESSAGE_SIZE);
+        let mut server = Limited(server, 16);
+
+        let message = PrimalMessage::Error { message: "x".repeat(64) };
+        client.write_primal_message(&message).await.unwrap();
+        let err = server.read_primal_message().await.unwrap_err();
+        assert!(matches!(err, ProtocolError::MessageTooLarge(_, 16)), "{}", err);
+    }
+}
@@ -0,0 +1,208 @@
+//! Transport for the `stam://` (plain TCP) and `stams://` (TLS) schemes
+//!
+//! Both transports are wrapped in [`StamStream`], which implements
+//! `AsyncRead + AsyncWrite` so [`PrimalStream`](crate::PrimalStream) and
+//! [`GameStream`](crate::GameStream) work the same over either of them.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{self, ClientConfig, RootCertStore, ServerConfig};
+use tokio_rustls::{TlsAcceptor, TlsConnector, TlsStream};
+
+use crate::error::{ProtocolError, Result};
+use crate::stream::{DEFAULT_MAX_MESSAGE_SIZE, MessageLimit};
+
+/// A connection to a Staminal server or client, either plain TCP or TLS
+pub struct StamStream {
+    transport: Transport,
+    /// Largest frame accepted from the peer (see [`MessageLimit`])
+    max_message_bytes: usize,
+}
+
+/// The underlying connection of a [`StamStream`]
+enum Transport {
+    /// Plain TCP (`stam://`)
+    Plain(TcpStream),
+    /// TLS over TCP (`stams://`)
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl StamStream {
+    fn new(transport: Transport) -> Self {
+        Self {
+            transport,
+            max_message_bytes: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+
+    /// Connect to `host_port`, performing a TLS handshake when `tls` is set
+    ///
+    /// The server certificate is verified against the bundled web PKI roots
+    /// and the host part of `host_port`.
+    pub async fn connect(host_port: &str, tls: bool) -> Result<Self> {
+        let tcp = TcpStream::connect(host_port).await?;
+        if !tls {
+            return Ok(Self::new(Transport::Plain(tcp)));
+        }
+
+        let server_name = ServerName::try_from(host_of(host_port).to_string())
+            .map_err(|e| ProtocolError::Tls(format!("invalid server name '{}': {}", host_port, e)))?;
+        let stream = client_connector()?.connect(server_name, tcp).await?;
+        Ok(Self::new(Transport::Tls(Box::new(stream.into()))))
+    }
+
+    /// Wrap an accepted connection, performing the server-side TLS handshake
+    /// when an acceptor is configured
+    pub async fn accept(tcp: TcpStream, acceptor: Option<&TlsAcceptor>) -> Result<Self> {
+        match acceptor {
+            Some(acceptor) => {
+                let stream = acceptor.accept(tcp).await?;
+                Ok(Self::new(Transport::Tls(Box::new(stream.into()))))
+            }
+            None => Ok(Self::new(Transport::Plain(tcp))),
+        }
+    }
+
+    /// Whether this connection is encrypted
+    pub fn is_tls(&self) -> bool {
+        matches!(self.transport, Transport::Tls(_))
+    }
+
+    /// Set the largest message or chunk accepted from the peer (default: `DEFAULT_MAX_MESSAGE_SIZE`)
+    ///
+    /// Frames announcing a larger size are rejected with `ProtocolError::MessageTooLarge`
+    /// before anything is allocated for them.
+    pub fn set_max_message_bytes(&mut self, max_message_bytes: usize) {
+        self.max_message_bytes = max_message_bytes;
+    }
+
+    /// Address of the remote peer
+    pub fn peer_addr(&self) -> std::io::Result<SocketAddr> {
+        match &self.transport {
+            Transport::Plain(stream) => stream.peer_addr(),
+            Transport::Tls(stream) => stream.get_ref().0.peer_addr(),
+        }
+    }
+}
+
+impl MessageLimit for StamStream {
+    fn max_message_bytes(&self) -> usize {
+        self.max_message_bytes
+    }
+}
+
+impl AsyncRead for StamStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        match &mut self.get_mut().transport {
+            Transport::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+            Transport::Tls(stream) => Pin::new(stream.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for StamStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        match &mut self.get_mut().transport {
+            Transport::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+            Transport::Tls(stream) => Pin::new(stream.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match &mut self.get_mut().transport {
+            Transport::Plain(stream) => Pin::new(stream).poll_flush(cx),
+            Transport::Tls(stream) => Pin::new(stream.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match &mut self.get_mut().transport {
+            Transport::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+            Transport::Tls(stream) => Pin::new(stream.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Build a TLS acceptor from a PEM certificate chain and private key
+pub fn load_tls_acceptor(cert_path: &Path, key_path: &Path) -> Result<TlsAcceptor> {
+    let open = |path: &Path| {
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|e| ProtocolError::Tls(format!("cannot open '{}': {}", path.display(), e)))
+    };
+
+    let certs = rustls_pemfile::certs(&mut open(cert_path)?)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| ProtocolError::Tls(format!("invalid certificate '{}': {}", cert_path.display(), e)))?;
+    if certs.is_empty() {
+        return Err(ProtocolError::Tls(format!("no certificate found in '{}'", cert_path.display())));
+    }
+
+    let key = rustls_pemfile::private_key(&mut open(key_path)?)
+        .map_err(|e| ProtocolError::Tls(format!("invalid private key '{}': {}", key_path.display(), e)))?
+        .ok_or_else(|| ProtocolError::Tls(format!("no private key found in '{}'", key_path.display())))?;
+
+    let config = ServerConfig::builder_with_provider(crypto_provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| ProtocolError::Tls(e.to_string()))?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| ProtocolError::Tls(e.to_string()))?;
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Shared client connector, built once with the web PKI roots
+fn client_connector() -> Result<TlsConnector> {
+    static CONNECTOR: OnceLock<TlsConnector> = OnceLock::new();
+    if let Some(connector) = CONNECTOR.get() {
+        return Ok(connector.clone());
+    }
+
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = ClientConfig::builder_with_provider(crypto_provider())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| ProtocolError::Tls(e.to_string()))?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    Ok(CONNECTOR.get_or_init(|| TlsConnector::from(Arc::new(config))).clone())
+}
+
+/// Crypto backend used for both sides (ring, no C toolchain needed)
+fn crypto_provider() -> Arc<rustls::crypto::CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+/// Host part of a `host:port` (or `[ipv6]:port`) address
+fn host_of(host_port: &str) -> &str {
+    let host = match host_port.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => host_port,
+    };
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_of() {
+        assert_eq!(host_of("game.example.com:9999"), "game.example.com");
+        assert_eq!(host_of("game.example.com"), "game.example.com");
+        assert_eq!(host_of("127.0.0.1:7777"), "127.0.0.1");
+        assert_eq!(host_of("[::1]:7777"), "::1");
+    }
+}
@@ -0,0 +1,43 @@
+use thiserror::Error;
+
+/// Version of the wire protocol, bumped on every incompatible message change
+///
+/// Sent by the server in `PrimalMessage::Welcome` and by the client in
+/// `PrimalMessage::Intent`. It is independent from the application version:
+/// client and server releases stay compatible as long as this number matches.
+///
+/// - v1: initial protocol
+/// - v2: a `RequestUri`/`ListUri` connection stays open for further URI intents
+/// - v3: `Compression::Zstd` and the zstd raw chunk flag
+/// - v4: `GameMessage::Disconnect` carries a `DisconnectReason`
+pub const PROTOCOL_VERSION: u32 = 4;
+
+/// Reason why a client and a server cannot talk to each other
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Incompatible {
+    #[error("Protocol version mismatch: client speaks v{client}, server speaks v{server}")]
+    Protocol { client: u32, server: u32 },
+}
+
+/// Check that a client and a server speaking the given protocol versions are compatible
+pub fn check_compatibility(client: u32, server: u32) -> Result<(), Incompatible> {
+    if client == server {
+        Ok(())
+    } else {
+        Err(Incompatible::Protocol { client, server })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_compatibility() {
+        assert_eq!(check_compatibility(PROTOCOL_VERSION, PROTOCOL_VERSION), Ok(()));
+
+        let err = check_compatibility(1, 2).unwrap_err();
+        assert_eq!(err, Incompatible::Protocol { client: 1, server: 2 });
+        assert_eq!(err.to_string(), "Protocol version mismatch: client speaks v1, server speaks v2");
+    }
+}
@@ -12,4 +12,7 @@ pub enum SchemaError {
 
     #[error("Schema validation failed: {0}")]
     ValidationError(String),
+
+    #[error("Invalid version: {0}")]
+    InvalidVersion(String),
 }
@@ -1,21 +1,36 @@
 use serde::Deserialize;
 use schemars::JsonSchema;
 use std::fs;
+use std::path::Path;
 
 pub mod error;
 pub mod mod_manifest;
 pub mod mod_validation;
 
 pub use error::{SchemaError, Result};
-pub use mod_manifest::{ModManifest, parse_version_requirement, StringOrArray};
-pub use mod_validation::{validate_mod_dependencies, validate_version_range};
+pub use mod_manifest::{ModManifest, parse_version_requirement, StringOrArray, KNOWN_CAPABILITIES, SUPPORTED_ENTRY_POINT_EXTENSIONS};
+pub use mod_validation::{validate_mod_dependencies, validate_version_range, version_satisfies};
+
+/// Format a schema violation with the path of the offending field
+///
+/// Errors on the document root are reported without a location.
+fn describe_validation_error(error: &jsonschema::ValidationError) -> String {
+    let path = error.instance_path.to_string();
+    let field = path.trim_start_matches('/').replace('/', ".");
+    if field.is_empty() {
+        error.to_string()
+    } else {
+        format!("'{}': {}", field, error)
+    }
+}
 
 /// Trait for types that can be validated against JSON Schema
 pub trait Validatable: JsonSchema + for<'de> Deserialize<'de> {
     /// Load and validate from JSON file
-    fn from_json_file(path: &str) -> Result<Self> {
+    fn from_json_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
         let content = fs::read_to_string(path)
-            .map_err(|e| SchemaError::IoError(path.to_string(), e))?;
+            .map_err(|e| SchemaError::IoError(path.display().to_string(), e))?;
 
         Self::from_json_str(&content)
     }
@@ -36,11 +51,20 @@ pub trait Validatable: JsonSchema + for<'de> Deserialize<'de> {
 
         // Validate the JSON against the schema
         compiled.validate(&value)
-            .map_err(|e| SchemaError::ValidationError(format!("{}", e)))?;
+            .map_err(|e| SchemaError::ValidationError(describe_validation_error(&e)))?;
 
-        // Finally deserialize to target type
-        serde_json::from_value(value)
-            .map_err(SchemaError::ParseError)
+        // Deserialize to target type
+        let parsed: Self = serde_json::from_value(value)
+            .map_err(SchemaError::ParseError)?;
+
+        // Finally run the checks the schema can't express
+        parsed.validate()?;
+        Ok(parsed)
+    }
+
+    /// Semantic checks that JSON Schema can't express, run after deserialization
+    fn validate(&self) -> Result<()> {
+        Ok(())
     }
 
     /// Generate JSON Schema for this type
@@ -3,9 +3,19 @@ use schemars::JsonSchema;
 use schemars::r#gen::SchemaGenerator;
 use schemars::schema::{Schema, SchemaObject, SingleOrVec, InstanceType};
 use std::collections::HashMap;
-use crate::Validatable;
+use std::path::Path;
+use crate::{SchemaError, Validatable};
 
-/// Wrapper type for execute_on that can be either a string or array of strings
+/// Entry point extensions with a mod runtime (mirrors `RuntimeType::from_extension`)
+pub const SUPPORTED_ENTRY_POINT_EXTENSIONS: &[&str] = &["js", "lua", "wasm"];
+
+/// Capabilities a mod can declare in `capabilities` (mirrors `Capability::name` in the mod runtimes)
+///
+/// Core APIs (console, timers, system, locale, text) are always available and
+/// are not listed here.
+pub const KNOWN_CAPABILITIES: &[&str] = &["process", "file", "filesystem", "storage", "network", "graphic", "resource", "spawn"];
+
+/// Wrapper type for fields that can be either a string or array of strings (execute_on, entry_point)
 /// This type handles both JSON Schema generation and serde deserialization
 #[derive(Debug, Clone, Default)]
 pub struct StringOrArray(pub Vec<String>);
@@ -20,6 +30,11 @@ impl StringOrArray {
     pub fn iter(&self) -> impl Iterator<Item = &String> {
         self.0.iter()
     }
+
+    /// Get the strings as a slice
+    pub fn as_slice(&self) -> &[String] {
+        &self.0
+    }
 }
 
 impl JsonSchema for StringOrArray {
@@ -64,7 +79,11 @@ impl Serialize for StringOrArray {
     where
         S: serde::Serializer,
     {
-        self.0.serialize(serializer)
+        // A single value is written back the way it is usually declared
+        match self.0.as_slice() {
+            [single] => single.serialize(serializer),
+            values => values.serialize(serializer),
+        }
     }
 }
 
@@ -125,12 +144,14 @@ pub struct ModManifest {
     #[schemars(description = "Brief description of the mod's purpose")]
     pub description: String,
 
-    /// Entry point file for the mod (e.g., "index.js")
+    /// Entry point file(s) for the mod (e.g., "index.js" or ["shared.js", "client.js"])
+    /// Several scripts are loaded in order into the same mod; the first one is
+    /// what `@mod-id` imports resolve to.
     /// Optional - mods without entry_point are automatically considered "attached"
     /// (they provide assets/resources only, no executable code)
-    #[schemars(description = "Main entry point file for the mod runtime. Optional - mods without entry_point are asset-only.")]
+    #[schemars(description = "Main entry point file for the mod runtime, or an array of scripts loaded in order (all for the same runtime). Optional - mods without entry_point are asset-only.")]
     #[serde(default)]
-    pub entry_point: Option<String>,
+    pub entry_point: Option<StringOrArray>,
 
     /// Load priority (lower numbers load first)
     #[schemars(description = "Loading priority - lower values load earlier")]
@@ -154,9 +175,71 @@ pub struct ModManifest {
     #[schemars(description = "Where this mod executes: 'server', 'client', or ['server', 'client'] for both")]
     #[serde(default)]
     pub execute_on: StringOrArray,
+
+    /// Optional APIs the mod needs (e.g. ["network", "storage"])
+    /// When omitted the mod only gets the core APIs (console, timers, System, Locale, Text)
+    #[schemars(description = "APIs the mod is allowed to use: 'process', 'file', 'filesystem', 'storage', 'network', 'graphic', 'resource', 'spawn'. When omitted, none of them are available.")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Vec<String>>,
 }
 
-impl Validatable for ModManifest {}
+impl ModManifest {
+    /// Entry point scripts in load order (empty for asset-only mods)
+    pub fn entry_points(&self) -> &[String] {
+        self.entry_point.as_ref().map(StringOrArray::as_slice).unwrap_or_default()
+    }
+}
+
+impl Validatable for ModManifest {
+    /// Reject unknown capabilities and entry points that no mod runtime can execute
+    ///
+    /// Asset-only mods (no `entry_point`) are always valid.
+    fn validate(&self) -> crate::Result<()> {
+        for capability in self.capabilities.iter().flatten() {
+            if !KNOWN_CAPABILITIES.contains(&capability.as_str()) {
+                return Err(SchemaError::ValidationError(format!(
+                    "unknown capability '{}' (known capabilities: {})",
+                    capability,
+                    KNOWN_CAPABILITIES.join(", ")
+                )));
+            }
+        }
+
+        if self.entry_point.is_some() && self.entry_points().is_empty() {
+            return Err(SchemaError::ValidationError(
+                "entry_point must not be an empty array (omit it for asset-only mods)".to_string(),
+            ));
+        }
+
+        let mut runtime_extension: Option<&str> = None;
+        for entry_point in self.entry_points() {
+            let extension = Path::new(entry_point).extension().and_then(|e| e.to_str());
+            let Some(extension) = extension.filter(|ext| SUPPORTED_ENTRY_POINT_EXTENSIONS.contains(ext)) else {
+                let supported: Vec<String> = SUPPORTED_ENTRY_POINT_EXTENSIONS
+                    .iter()
+                    .map(|ext| format!(".{}", ext))
+                    .collect();
+                return Err(SchemaError::ValidationError(format!(
+                    "entry_point '{}' has no supported runtime (supported extensions: {})",
+                    entry_point,
+                    supported.join(", ")
+                )));
+            };
+
+            // All scripts of a mod run in the same runtime
+            match runtime_extension {
+                Some(first) if first != extension => {
+                    return Err(SchemaError::ValidationError(format!(
+                        "entry_point '{}' needs a different runtime than the other entry points (.{})",
+                        entry_point, first
+                    )));
+                }
+                _ => runtime_extension = Some(extension),
+            }
+        }
+        Ok(())
+    }
+}
 
 /// Parse a version requirement string
 /// Returns (min_version, max_version) tuple
@@ -187,7 +270,7 @@ mod tests {
         let manifest = ModManifest::from_json_str(json).unwrap();
         assert_eq!(manifest.name, "test-mod");
         assert_eq!(manifest.version, "1.0.0");
-        assert_eq!(manifest.entry_point, Some("index.js".to_string()));
+        assert_eq!(manifest.entry_points(), ["index.js"]);
     }
 
     #[test]
@@ -270,6 +353,102 @@ mod tests {
         let manifest = ModManifest::from_json_str(json).unwrap();
         assert_eq!(manifest.name, "asset-pack");
         assert_eq!(manifest.version, "1.0.0");
-        assert_eq!(manifest.entry_point, None);
+        assert!(manifest.entry_point.is_none());
+        assert!(manifest.entry_points().is_empty());
+    }
+
+    #[test]
+    fn test_entry_point_extension_must_have_runtime() {
+        let manifest_with = |entry_point: &str| {
+            format!(
+                r#"{{"name": "test-mod", "version": "1.0.0", "description": "A test mod", "entry_point": "{}"}}"#,
+                entry_point
+            )
+        };
+
+        assert!(ModManifest::from_json_str(&manifest_with("main.js")).is_ok());
+        assert!(ModManifest::from_json_str(&manifest_with("scripts/main.lua")).is_ok());
+        assert!(ModManifest::from_json_str(&manifest_with("mod.wasm")).is_ok());
+
+        for entry_point in ["main.py", "main"] {
+            let err = ModManifest::from_json_str(&manifest_with(entry_point)).unwrap_err();
+            assert!(matches!(err, SchemaError::ValidationError(_)));
+            assert!(err.to_string().contains(".js, .lua, .wasm"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_entry_point_array() {
+        let manifest_with = |entry_point: &str| {
+            format!(
+                r#"{{"name": "test-mod", "version": "1.0.0", "description": "A test mod", "entry_point": {}}}"#,
+                entry_point
+            )
+        };
+
+        let manifest = ModManifest::from_json_str(&manifest_with(r#"["shared.js", "client.js"]"#)).unwrap();
+        assert_eq!(manifest.entry_points(), ["shared.js", "client.js"]);
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["entry_point"], serde_json::json!(["shared.js", "client.js"]));
+
+        // A single string is written back unchanged
+        let manifest = ModManifest::from_json_str(&manifest_with(r#""index.js""#)).unwrap();
+        assert_eq!(serde_json::to_value(&manifest).unwrap()["entry_point"], "index.js");
+
+        let err = ModManifest::from_json_str(&manifest_with(r#"["shared.js", "client.lua"]"#)).unwrap_err();
+        assert!(err.to_string().contains("'client.lua' needs a different runtime"), "{}", err);
+        let err = ModManifest::from_json_str(&manifest_with("[]")).unwrap_err();
+        assert!(err.to_string().contains("empty array"), "{}", err);
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let base = r#""name": "test-mod", "version": "1.0.0", "description": "A test mod""#;
+
+        let manifest = ModManifest::from_json_str(&format!("{{{}}}", base)).unwrap();
+        assert_eq!(manifest.capabilities, None);
+
+        let manifest =
+            ModManifest::from_json_str(&format!(r#"{{{}, "capabilities": ["network", "storage"]}}"#, base)).unwrap();
+        assert_eq!(manifest.capabilities, Some(vec!["network".to_string(), "storage".to_string()]));
+
+        let err = ModManifest::from_json_str(&format!(r#"{{{}, "capabilities": ["sockets"]}}"#, base)).unwrap_err();
+        assert!(err.to_string().contains("unknown capability 'sockets'"), "{}", err);
+    }
+
+    #[test]
+    fn test_schema_error_names_field() {
+        let json = r#"{
+            "name": "test-mod",
+            "version": "1.0.0",
+            "description": "A test mod",
+            "priority": "high"
+        }"#;
+
+        let err = ModManifest::from_json_str(json).unwrap_err();
+        assert!(matches!(err, SchemaError::ValidationError(_)));
+        assert!(err.to_string().contains("'priority'"), "{}", err);
+    }
+
+    #[test]
+    fn test_schema_json_describes_fields() {
+        let schema: serde_json::Value = serde_json::from_str(&ModManifest::schema_json().unwrap()).unwrap();
+        let properties = &schema["properties"];
+
+        assert_eq!(properties["requires"]["type"], "object");
+        assert_eq!(properties["requires"]["additionalProperties"]["type"], "string");
+        assert_eq!(
+            properties["entry_point"]["anyOf"],
+            serde_json::json!([{"$ref": "#/definitions/StringOrArray"}, {"type": "null"}])
+        );
+        let string_or_array = &schema["definitions"]["StringOrArray"]["anyOf"];
+        assert_eq!(string_or_array[0]["type"], "string");
+        assert_eq!(string_or_array[1]["items"]["type"], "string");
+        assert_eq!(properties["priority"]["type"], "integer");
+
+        for field in ["requires", "entry_point", "priority"] {
+            let description = properties[field]["description"].as_str().unwrap_or_default();
+            assert!(!description.is_empty(), "'{}' has no description", field);
+        }
     }
 }
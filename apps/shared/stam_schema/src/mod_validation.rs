@@ -1,8 +1,37 @@
 use std::collections::HashMap;
 
-use semver::Version;
+use semver::{Version, VersionReq};
 
-use crate::{ModManifest, parse_version_requirement};
+use crate::{ModManifest, SchemaError, parse_version_requirement};
+
+/// Check whether a concrete version satisfies a requirement.
+///
+/// Supported requirements:
+/// - `*`: any version
+/// - `^1.2.0`, `~1.2.0`, `>=1.2.0` (and the other semver operators): semver rules
+/// - `1.2.0`: exactly that version
+/// - `1.0.0,2.0.0`: inclusive range, as parsed by `parse_version_requirement`
+pub fn version_satisfies(version: &str, requirement: &str) -> crate::Result<bool> {
+    let parse = |v: &str| {
+        Version::parse(v).map_err(|e| SchemaError::InvalidVersion(format!("'{}': {}", v, e)))
+    };
+    let installed = parse(version.trim())?;
+    let requirement = requirement.trim();
+
+    if requirement == "*" {
+        return Ok(true);
+    }
+
+    if requirement.starts_with(['^', '~', '>', '<', '=']) {
+        let req = VersionReq::parse(requirement)
+            .map_err(|e| SchemaError::InvalidVersion(format!("requirement '{}': {}", requirement, e)))?;
+        return Ok(req.matches(&installed));
+    }
+
+    // Bare version or "min,max" range (min == max for a bare version)
+    let (min, max) = parse_version_requirement(requirement);
+    Ok(parse(&min)? <= installed && installed <= parse(&max)?)
+}
 
 /// Validate if a version is within the specified range.
 /// min_version and max_version should be in format "major.minor.patch".
@@ -111,3 +140,41 @@ pub fn validate_mod_dependencies(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_satisfies() {
+        let cases = [
+            ("1.4.2", "*", true),
+            ("1.4.2", "1.4.2", true),
+            ("1.4.3", "1.4.2", false),
+            ("1.9.0", "^1.4.0", true),
+            ("2.0.0", "^1.4.0", false),
+            ("1.4.9", "~1.4.0", true),
+            ("1.5.0", "~1.4.0", false),
+            ("3.0.0", ">=1.4.0", true),
+            ("1.3.0", ">=1.4.0", false),
+            ("1.5.0", "1.0.0, 2.0.0", true),
+            ("2.0.1", "1.0.0,2.0.0", false),
+        ];
+        for (version, requirement, expected) in cases {
+            assert_eq!(
+                version_satisfies(version, requirement).unwrap(),
+                expected,
+                "{} against '{}'",
+                version,
+                requirement
+            );
+        }
+    }
+
+    #[test]
+    fn test_version_satisfies_rejects_invalid_input() {
+        assert!(matches!(version_satisfies("1.x", "*"), Err(SchemaError::InvalidVersion(_))));
+        assert!(matches!(version_satisfies("1.0.0", "^one"), Err(SchemaError::InvalidVersion(_))));
+        assert!(matches!(version_satisfies("1.0.0", "latest"), Err(SchemaError::InvalidVersion(_))));
+    }
+}
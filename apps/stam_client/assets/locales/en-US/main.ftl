@@ -22,6 +22,9 @@ server-list-empty = Server list is empty, no game servers available
 server-error = Server error: {$message}
 game-not-found = Game "{$game}" not found in server list. Available games: {$available}
 game-selected = Selected game: {$game_name} ({$game_id})
+game-ambiguous = The server offers {$count} games, choose one with --game. Available games: {$available}
+server-choose = Choose a game [1-{$count}]:{" "}
+server-choice-invalid = Invalid choice, enter a number between 1 and {$count}
 
 ## Game client
 game-connecting = Connecting to game server at {$host}
@@ -40,10 +43,23 @@ disconnect-version-mismatch = Client version incompatible with server
 disconnect-maintenance = Server is under maintenance
 disconnect-unknown = Disconnected from server
 
+## Reconnection
+reconnect-scheduled = Connection lost, reconnecting in {$seconds}s (attempt {$attempt}/{$max})...
+reconnect-attempt = Reconnecting to {$host} (attempt {$attempt}/{$max})...
+reconnect-failed = Reconnection attempt {$attempt} failed: {$error}
+reconnect-success = Reconnected to game server
+reconnect-gave-up = Could not reconnect after {$attempts} attempt(s), giving up
+
+## Hot Reload
+watch-enabled = Watching {$count} mod(s) for changes
+mod-reloaded = Mod '{$mod}' reloaded
+mod-reload-failed = Failed to reload mod '{$mod}': {$error}
+
 ## Errors
 error-invalid-uri = Invalid URI scheme: {$uri}
 error-unexpected-message = Unexpected message received
 error-parse-failed = Failed to parse server response
+error-server-full = The server is full, try again later
 js-fatal-error = Fatal JavaScript error in mod, client shutting down
 
 ## General
@@ -22,6 +22,9 @@ server-list-empty = Lista server vuota, nessun game server disponibile
 server-error = Errore del server: {$message}
 game-not-found = Game "{$game}" non trovato nella lista server. Game disponibili: {$available}
 game-selected = Game selezionato: {$game_name} ({$game_id})
+game-ambiguous = Il server offre {$count} game, scegline uno con --game. Game disponibili: {$available}
+server-choose = Scegli un game [1-{$count}]:{" "}
+server-choice-invalid = Scelta non valida, inserisci un numero tra 1 e {$count}
 
 ## Client di gioco
 game-connecting = Connessione al game server a {$host}
@@ -40,10 +43,23 @@ disconnect-version-mismatch = Versione del client incompatibile con il server
 disconnect-maintenance = Il server è in manutenzione
 disconnect-unknown = Disconnesso dal server
 
+## Riconnessione
+reconnect-scheduled = Connessione persa, nuovo tentativo tra {$seconds}s (tentativo {$attempt}/{$max})...
+reconnect-attempt = Riconnessione a {$host} (tentativo {$attempt}/{$max})...
+reconnect-failed = Tentativo di riconnessione {$attempt} fallito: {$error}
+reconnect-success = Riconnesso al game server
+reconnect-gave-up = Impossibile riconnettersi dopo {$attempts} tentativi, rinuncio
+
+## Ricaricamento Automatico
+watch-enabled = Monitoraggio modifiche attivo su {$count} mod
+mod-reloaded = Mod '{$mod}' ricaricata
+mod-reload-failed = Impossibile ricaricare la mod '{$mod}': {$error}
+
 ## Errori
 error-invalid-uri = Schema URI non valido: {$uri}
 error-unexpected-message = Ricevuto messaggio inaspettato
 error-parse-failed = Impossibile interpretare la risposta del server
+error-server-full = Il server è pieno, riprova più tardi
 js-fatal-error = Errore JavaScript fatale nel mod, arresto del client
 
 ## Generale
@@ -1,7 +1,8 @@
 /// Application paths configuration module
 ///
 /// Centralizes all path management for the application.
-/// Initialized once at startup with custom_home parameter.
+/// Initialized once at startup with custom_home parameter, or in portable mode
+/// with everything under a `staminal-data` folder next to the executable.
 
 use std::path::PathBuf;
 use std::fs;
@@ -38,6 +39,9 @@ pub fn sanitize_dirname(name: &str) -> String {
     result.trim_end().to_string()
 }
 
+/// Name of the data folder of portable mode, next to the executable
+pub const PORTABLE_DIR_NAME: &str = "staminal-data";
+
 #[derive(Clone)]
 pub struct AppPaths {
     config_dir: PathBuf,
@@ -67,7 +71,30 @@ impl AppPaths {
             (config, data)
         };
 
-        // Create directories if they don't exist
+        Self::with_dirs(config_dir, data_dir)
+    }
+
+    /// Initialize application paths in portable mode
+    ///
+    /// Config and data (mods, storage, caches) all live in `base`, independent of
+    /// the platform directory conventions.
+    ///
+    /// # Arguments
+    /// * `base` - The portable data folder (see `portable_dir`)
+    pub fn portable(base: impl Into<PathBuf>) -> Result<Self, Box<dyn std::error::Error>> {
+        let base = base.into();
+        Self::with_dirs(base.join("config"), base)
+    }
+
+    /// Get the portable data folder: `staminal-data` next to the executable
+    pub fn portable_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let exe = std::env::current_exe()?;
+        let exe_dir = exe.parent().ok_or("Could not determine the executable directory")?;
+        Ok(exe_dir.join(PORTABLE_DIR_NAME))
+    }
+
+    /// Use the given directories, creating them if they don't exist
+    fn with_dirs(config_dir: PathBuf, data_dir: PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
         if !config_dir.exists() {
             fs::create_dir_all(&config_dir)?;
             debug!("Created Staminal config directory: {}", config_dir.display());
@@ -107,6 +134,27 @@ impl AppPaths {
         Ok(tmp_dir)
     }
 
+    /// Get the directory for log files (created on first use)
+    ///
+    /// Only used in portable mode; otherwise the log file is written to the current directory.
+    pub fn logs_dir(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let logs_dir = self.data_dir.join("logs");
+
+        if !logs_dir.exists() {
+            fs::create_dir_all(&logs_dir)?;
+            debug!("Created logs directory: {}", logs_dir.display());
+        }
+
+        Ok(logs_dir)
+    }
+
+    /// Get the shared cache of downloaded mod archives (created on first use)
+    ///
+    /// Directory structure: data_dir/cache/mods/
+    pub fn mod_cache_dir(&self) -> PathBuf {
+        self.data_dir.join("cache").join("mods")
+    }
+
     /// Get the game-specific data directory
     ///
     /// Directory structure: data_dir/<server_dir>/<game_id>/
@@ -145,3 +193,26 @@ impl AppPaths {
         Ok(game_root)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_portable_paths_derive_from_base() {
+        let base = std::env::temp_dir().join(format!("stam_app_paths_portable_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+
+        let paths = AppPaths::portable(&base).unwrap();
+        assert_eq!(paths.config_dir(), &base.join("config"));
+        assert_eq!(paths.data_dir(), &base);
+        assert!(paths.tmp_dir().unwrap().starts_with(&base));
+        assert!(paths.logs_dir().unwrap().starts_with(&base));
+        assert!(paths.mod_cache_dir().starts_with(&base));
+
+        let game_root = paths.game_root("127.0.0.1", "Develop Realm", "demo").unwrap();
+        assert_eq!(game_root, base.join("127.0.0.1 - Develop Realm").join("demo"));
+        assert!(game_root.join("mods").is_dir());
+        let _ = fs::remove_dir_all(&base);
+    }
+}
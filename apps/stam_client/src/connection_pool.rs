@@ -0,0 +1,211 @@
+/// Reuse of stam:// connections across `RequestUri`/`ListUri` requests
+///
+/// Since protocol v2 the server keeps a URI connection open once it has answered, so
+/// a connection whose response was read to the end can carry the next request instead
+/// of reconnecting and redoing the Welcome handshake. Idle connections are keyed by
+/// transport and `host:port`, and dropped once idle for longer than `MAX_IDLE` (well
+/// below the server's own idle timeout).
+
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use stam_protocol::{Compression, StamStream};
+use tracing::trace;
+
+/// Longest time a pooled connection may stay unused
+const MAX_IDLE: Duration = Duration::from_secs(15);
+
+/// A connection waiting for its next request
+struct IdleConnection {
+    stream: StamStream,
+    compression: Option<Compression>,
+    since: Instant,
+}
+
+/// Pool of idle stam:// connections, shared by every clone
+#[derive(Clone, Default)]
+pub struct ConnectionPool {
+    idle: Arc<Mutex<HashMap<String, Vec<IdleConnection>>>>,
+    handshakes: Arc<AtomicU64>,
+    handshake_micros: Arc<AtomicU64>,
+    reused: Arc<AtomicU64>,
+}
+
+/// Counters of the connections opened and reused by a pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Connections opened (each one a connect + Welcome handshake)
+    pub handshakes: u64,
+    /// Time spent on those handshakes
+    pub handshake_time: Duration,
+    /// Requests served over an already open connection
+    pub reused: u64,
+}
+
+impl PoolStats {
+    /// Handshake time saved by the reused connections, estimated from the average handshake
+    pub fn estimated_savings(&self) -> Duration {
+        if self.handshakes == 0 {
+            return Duration::ZERO;
+        }
+        self.handshake_time / self.handshakes as u32 * self.reused as u32
+    }
+}
+
+impl ConnectionPool {
+    /// Create an empty pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Key of the connections to `host_port` over the given transport
+    pub fn key(host_port: &str, tls: bool) -> String {
+        format!("{}://{}", if tls { "stams" } else { "stam" }, host_port)
+    }
+
+    /// Take an idle connection for `key`, with the compression negotiated in its Welcome
+    pub fn take(&self, key: &str) -> Option<(StamStream, Option<Compression>)> {
+        let mut idle = self.idle.lock().unwrap();
+        let connections = idle.get_mut(key)?;
+        connections.retain(|connection| connection.since.elapsed() < MAX_IDLE);
+        let connection = connections.pop();
+        if connections.is_empty() {
+            idle.remove(key);
+        }
+        connection.map(|connection| {
+            self.reused.fetch_add(1, Ordering::Relaxed);
+            (connection.stream, connection.compression)
+        })
+    }
+
+    /// Make a connection whose last response was fully read available again
+    fn put(&self, key: String, stream: StamStream, compression: Option<Compression>) {
+        trace!("Keeping connection to {} for the next request", key);
+        self.idle.lock().unwrap().entry(key).or_default().push(IdleConnection {
+            stream,
+            compression,
+            since: Instant::now(),
+        });
+    }
+
+    /// Count a newly opened connection and the time its handshake took
+    pub fn record_handshake(&self, elapsed: Duration) {
+        self.handshakes.fetch_add(1, Ordering::Relaxed);
+        self.handshake_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Counters since the pool was created
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            handshakes: self.handshakes.load(Ordering::Relaxed),
+            handshake_time: Duration::from_micros(self.handshake_micros.load(Ordering::Relaxed)),
+            reused: self.reused.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A connection used for one request, returned to its pool when dropped after `release`
+///
+/// Connections that are not released (errors, cancelled or partially read responses)
+/// are simply closed, since the next message on them is unknown.
+pub struct PooledStream {
+    stream: Option<StamStream>,
+    compression: Option<Compression>,
+    pool: Option<(ConnectionPool, String)>,
+    released: bool,
+}
+
+impl PooledStream {
+    /// Wrap a connection, to be returned to `pool` under `key` (if any)
+    pub fn new(stream: StamStream, compression: Option<Compression>, pool: Option<(ConnectionPool, String)>) -> Self {
+        Self {
+            stream: Some(stream),
+            compression,
+            pool,
+            released: false,
+        }
+    }
+
+    /// Mark the response as fully read: the connection can serve another request
+    pub fn release(&mut self) {
+        self.released = true;
+    }
+}
+
+impl Deref for PooledStream {
+    type Target = StamStream;
+
+    fn deref(&self) -> &StamStream {
+        self.stream.as_ref().expect("stream is only taken on drop")
+    }
+}
+
+impl DerefMut for PooledStream {
+    fn deref_mut(&mut self) -> &mut StamStream {
+        self.stream.as_mut().expect("stream is only taken on drop")
+    }
+}
+
+impl Drop for PooledStream {
+    fn drop(&mut self) {
+        if !self.released {
+            return;
+        }
+        if let (Some((pool, key)), Some(stream)) = (self.pool.take(), self.stream.take()) {
+            pool.put(key, stream, self.compression);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    async fn connect() -> (StamStream, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        (StamStream::connect(&addr, false).await.unwrap(), addr)
+    }
+
+    #[tokio::test]
+    async fn test_only_released_connections_are_reused() {
+        let pool = ConnectionPool::new();
+        let (stream, addr) = connect().await;
+        let key = ConnectionPool::key(&addr, false);
+        assert!(pool.take(&key).is_none());
+
+        // Dropped without release: closed, not pooled
+        drop(PooledStream::new(stream, None, Some((pool.clone(), key.clone()))));
+        assert!(pool.take(&key).is_none());
+
+        let (stream, addr) = connect().await;
+        let key = ConnectionPool::key(&addr, false);
+        let mut pooled = PooledStream::new(stream, Some(Compression::Gzip), Some((pool.clone(), key.clone())));
+        pooled.release();
+        drop(pooled);
+
+        let (_stream, compression) = pool.take(&key).expect("released connection should be pooled");
+        assert_eq!(compression, Some(Compression::Gzip));
+        assert!(pool.take(&key).is_none());
+        assert!(pool.take(&ConnectionPool::key(&addr, true)).is_none());
+        assert_eq!(pool.stats().reused, 1);
+    }
+
+    #[test]
+    fn test_estimated_savings() {
+        let stats = PoolStats {
+            handshakes: 2,
+            handshake_time: Duration::from_millis(40),
+            reused: 10,
+        };
+        assert_eq!(stats.estimated_savings(), Duration::from_millis(200));
+    }
+}
@@ -4,10 +4,10 @@
 //! Bevy runs on the main thread and communicates with the worker thread via channels.
 
 use bevy::prelude::*;
-use bevy::window::{PrimaryWindow, VideoModeSelection, WindowMode, WindowResolution, WindowRef, CursorIcon, SystemCursorIcon};
+use bevy::window::{PrimaryWindow, VideoModeSelection, WindowMode, WindowResolution, WindowRef, CursorIcon, CursorOptions, SystemCursorIcon};
 use bevy::winit::{UpdateMode, WinitSettings, WINIT_WINDOWS};
 use bevy::camera::RenderTarget;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::mpsc::Receiver;
 use std::sync::Mutex;
 use tokio::sync::mpsc::Sender;
@@ -15,9 +15,9 @@ use tokio::sync::mpsc::Sender;
 use stam_mod_runtimes::api::{
     ColorValue, EdgeInsets, FlexDirection, GraphicCommand, GraphicEngine, GraphicEngineInfo,
     GraphicEngines, GraphicEvent, InitialWindowConfig, JustifyContent, KeyModifiers, MouseButton,
-    SizeValue, WindowPositionMode, AlignItems, WindowMode as StamWindowMode,
+    SizeValue, WindowCursor, WindowPositionMode, AlignItems, WindowMode as StamWindowMode,
     ResourceType, ResourceState, ResourceInfo, ImageScaleMode, ImageSource,
-    graphic::ecs::{ComponentSchema, DeclaredSystem, QueryOptions, QueryResult, FieldType, SystemBehavior},
+    graphic::ecs::{AnimatedSpriteConfig, ComponentSchema, DeclaredSystem, QueryOptions, QueryResult, FieldType, SpriteAnimationState, SystemBehavior, TextInputState},
 };
 
 /// System sets for ordering Bevy systems
@@ -120,6 +120,7 @@ impl GraphicEngine for BevyEngine {
         app.insert_resource(FocusedWindow::default());
         app.insert_resource(FontRegistry::default());
         app.insert_resource(ResourceRegistry::default());
+        app.insert_resource(AudioRegistry::default());
         app.insert_resource(PendingAssetRegistry::default());
         app.insert_resource(EngineReadySent::default());
         // ECS scripting resources
@@ -157,7 +158,10 @@ impl GraphicEngine for BevyEngine {
         // Note: process_commands has too many parameters to use .in_set() directly.
         // We use a separate add_systems call and configure the set to run first.
         app.add_systems(Update, process_commands);
-        app.add_systems(Update, run_declared_systems.in_set(BevySystemSet::DeclaredSystems));
+        app.add_systems(
+            Update,
+            (run_declared_systems, animate_sprites).in_set(BevySystemSet::DeclaredSystems),
+        );
         app.add_systems(
             Update,
             (
@@ -165,6 +169,7 @@ impl GraphicEngine for BevyEngine {
                 check_pending_assets,
                 send_frame_events,
                 handle_keyboard_input,
+                (handle_text_input_focus, handle_text_input_keyboard, render_text_inputs).chain(),
                 handle_mouse_input,
                 handle_window_events,
                 handle_script_entity_interactions,
@@ -172,6 +177,7 @@ impl GraphicEngine for BevyEngine {
                 apply_disabled_button_colors,
                 apply_enabled_button_colors,
                 update_cover_contain_images,
+                prune_finished_audio,
             ).in_set(BevySystemSet::AfterCommands),
         );
 
@@ -206,6 +212,7 @@ impl GraphicEngine for BevyEngine {
                 "Input Handling".to_string(),
                 "Window Management".to_string(),
                 "Asset Loading".to_string(),
+                "Audio Playback".to_string(),
             ],
             // Backend is determined at runtime by wgpu, but we can provide a general description
             // In a more sophisticated implementation, we could query wgpu for the actual backend
@@ -213,7 +220,7 @@ impl GraphicEngine for BevyEngine {
             supports_2d: true,
             supports_3d: true,
             supports_ui: true,
-            supports_audio: false, // We didn't enable audio feature
+            supports_audio: true,
         }
     }
 }
@@ -385,6 +392,131 @@ struct CoverContainImage {
     anchor_position: Vec2,
 }
 
+/// Sprite sheet animation of a script entity (the `AnimatedSprite` native component)
+///
+/// `animate_sprites` creates the texture atlas layout on first run, attaches it to the
+/// entity's Sprite (inserting one if missing) and then steps the atlas index every frame.
+#[derive(Component, Clone, Debug)]
+struct ScriptAnimatedSprite {
+    config: AnimatedSpriteConfig,
+    state: SpriteAnimationState,
+    /// Sprite sheet image, resolved from the resource registry when inserted
+    image: Handle<Image>,
+    /// Grid layout, created by `animate_sprites` on first run
+    layout: Option<Handle<TextureAtlasLayout>>,
+}
+
+impl ScriptAnimatedSprite {
+    /// Build the component, looking up the sprite sheet in the resource registry
+    fn from_json(data: &serde_json::Value, resource_registry: &ResourceRegistry) -> Result<Self, String> {
+        let config = AnimatedSpriteConfig::from_json(data)?;
+        let image = resource_registry
+            .get_image_handle(&config.atlas_resource_id)
+            .ok_or_else(|| format!(
+                "Resource '{}' not found. Make sure to call Resource.load() first.",
+                config.atlas_resource_id
+            ))?;
+        Ok(Self {
+            config,
+            state: SpriteAnimationState::default(),
+            image,
+            layout: None,
+        })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::to_value(&self.config).unwrap_or_default();
+        json["frame"] = serde_json::json!(self.state.frame);
+        json["finished"] = serde_json::json!(self.state.finished);
+        json
+    }
+}
+
+/// Editable single-line text field of a script entity (the `TextInput` native component)
+///
+/// The entity's Text shows the value, or the placeholder while empty and unfocused.
+/// Clicking the field focuses it and `handle_text_input_keyboard` edits the value.
+#[derive(Component, Clone, Debug)]
+struct ScriptTextInput {
+    state: TextInputState,
+    placeholder: String,
+    text_color: Color,
+    placeholder_color: Color,
+    focused: bool,
+}
+
+impl ScriptTextInput {
+    /// Character drawn at the caret position while focused
+    const CARET: char = '|';
+
+    /// Build the field from its Text config plus the TextInput-only fields
+    fn new(config: &native_component_converters::TextConfig, data: &serde_json::Value) -> Self {
+        let max_length = data
+            .get("max_length")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize);
+        let placeholder_color = data
+            .get("placeholder_color")
+            .and_then(|v| native_component_converters::json_to_color(v).ok())
+            .unwrap_or_else(|| config.color.with_alpha(0.5));
+        Self {
+            state: TextInputState::new(&config.content, max_length),
+            placeholder: data
+                .get("placeholder")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            text_color: config.color,
+            placeholder_color,
+            focused: false,
+        }
+    }
+
+    /// Text and color to render
+    fn display(&self) -> (String, Color) {
+        if self.focused {
+            (self.state.display(Some(Self::CARET)), self.text_color)
+        } else if self.state.value().is_empty() {
+            (self.placeholder.clone(), self.placeholder_color)
+        } else {
+            (self.state.display(None), self.text_color)
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "value": self.state.value(),
+            "placeholder": self.placeholder,
+            "max_length": self.state.max_length(),
+            "focused": self.focused,
+        })
+    }
+}
+
+/// Resolve the TextFont for a Text config, falling back to the window's default font
+fn text_font_for(
+    config: &native_component_converters::TextConfig,
+    font_registry: &FontRegistry,
+    window_ui_registry: &WindowUIRegistry,
+) -> bevy::text::TextFont {
+    let font_handle = config.font_alias
+        .as_ref()
+        .and_then(|alias| font_registry.get_font(alias))
+        .or_else(|| {
+            window_ui_registry.window_roots.keys().next()
+                .and_then(|&window_id| {
+                    let window_font = font_registry.get_window_font(window_id);
+                    font_registry.get_font(&window_font.family)
+                })
+        });
+
+    bevy::text::TextFont {
+        font: font_handle.unwrap_or_default(),
+        font_size: config.font_size,
+        ..default()
+    }
+}
+
 impl Default for ButtonColors {
     fn default() -> Self {
         Self {
@@ -511,7 +643,7 @@ struct ResourceRegistryEntry {
 enum ResourceHandle {
     Image(Handle<Image>),
     Font(Handle<Font>),
-    // Audio(Handle<AudioSource>), // Add when audio is enabled
+    Audio(Handle<AudioSource>),
     // TODO: Add other handle types as needed
 }
 
@@ -589,6 +721,63 @@ impl ResourceRegistry {
             }
         })
     }
+
+    /// Get audio handle by alias (for Audio.play())
+    fn get_audio_handle(&self, alias: &str) -> Option<Handle<AudioSource>> {
+        self.get_by_alias(alias).and_then(|entry| {
+            if let ResourceHandle::Audio(handle) = &entry.handle {
+                Some(handle.clone())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+// ============================================================================
+// Audio Registry
+// ============================================================================
+
+/// Registry of audio playbacks started with `Audio.play()`
+///
+/// Maps the playback handles given to scripts to the Bevy entities carrying
+/// the `AudioPlayer`. Finished one-shot sounds despawn themselves and are
+/// pruned by `prune_finished_audio`.
+#[derive(Resource)]
+struct AudioRegistry {
+    /// Map from playback handle to the entity playing it
+    playing: HashMap<u64, Entity>,
+    /// Next handle to hand out (handles start at 1)
+    next_handle: u64,
+}
+
+impl Default for AudioRegistry {
+    fn default() -> Self {
+        Self {
+            playing: HashMap::new(),
+            next_handle: 1,
+        }
+    }
+}
+
+impl AudioRegistry {
+    /// Track a new playback and return its handle
+    fn register(&mut self, entity: Entity) -> u64 {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.playing.insert(handle, entity);
+        handle
+    }
+
+    /// Stop tracking a playback, returning its entity if it is still playing
+    fn unregister(&mut self, handle: u64) -> Option<Entity> {
+        self.playing.remove(&handle)
+    }
+
+    /// Whether the handle was ever returned by `register`
+    fn was_issued(&self, handle: u64) -> bool {
+        handle > 0 && handle < self.next_handle
+    }
 }
 
 // ============================================================================
@@ -650,6 +839,8 @@ struct PendingAssetEntry {
     alias: String,
     /// Untyped handle ID for checking load state
     handle_id: bevy::asset::UntypedAssetId,
+    /// Whether the ResourceLoading event has been sent for this entry
+    loading_reported: bool,
 }
 
 /// Registry for assets that are loading in Bevy
@@ -670,6 +861,7 @@ impl PendingAssetRegistry {
             asset_id,
             alias,
             handle_id,
+            loading_reported: false,
         });
     }
 }
@@ -874,6 +1066,12 @@ enum NativeComponent {
     Button,
     /// ImageNode component for UI image rendering
     ImageNode,
+    /// Editable single-line text field
+    TextInput,
+
+    // === Animation Components ===
+    /// Sprite sheet animation (drives the entity's Sprite texture atlas)
+    AnimatedSprite,
 }
 
 impl NativeComponent {
@@ -890,6 +1088,8 @@ impl NativeComponent {
             NativeComponent::Interaction => "Interaction",
             NativeComponent::Button => "Button",
             NativeComponent::ImageNode => "ImageNode",
+            NativeComponent::TextInput => "TextInput",
+            NativeComponent::AnimatedSprite => "AnimatedSprite",
         }
     }
 
@@ -906,6 +1106,8 @@ impl NativeComponent {
             "Interaction" => Some(NativeComponent::Interaction),
             "Button" => Some(NativeComponent::Button),
             "ImageNode" => Some(NativeComponent::ImageNode),
+            "TextInput" => Some(NativeComponent::TextInput),
+            "AnimatedSprite" => Some(NativeComponent::AnimatedSprite),
             _ => None,
         }
     }
@@ -1994,6 +2196,7 @@ fn process_commands(
         ResMut<FontRegistry>,
         ResMut<ResourceRegistry>,
         ResMut<PendingAssetRegistry>,
+        ResMut<AudioRegistry>,
     ),
     mut ecs_registries: (
         ResMut<ScriptEntityRegistry>,
@@ -2005,6 +2208,8 @@ fn process_commands(
     mut windows: Query<&mut Window>,
     mut app_exit: EventWriter<bevy::app::AppExit>,
     primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    // Window icon pixels and per-window cursor options
+    mut window_extras: (Res<Assets<Image>>, Query<&mut CursorOptions>),
     mut widget_queries: (
         Query<&mut Text, Without<ScriptEntity>>,
         Query<&mut BackgroundColor, Without<ScriptEntity>>,
@@ -2014,12 +2219,15 @@ fn process_commands(
     ),
     // ECS queries for script components
     script_entity_query: Query<(Entity, &ScriptEntity)>,
-    mut script_component_query: Query<(Entity, &mut ScriptComponent)>,
+    // Custom components are children of the script entity that owns them
+    mut script_component_query: Query<(Entity, &mut ScriptComponent, &ChildOf)>,
     // ECS queries for native components
     mut native_queries: (
         Query<&mut Transform, With<ScriptEntity>>,
         Query<&mut Sprite, With<ScriptEntity>>,
         Query<&mut Visibility, With<ScriptEntity>>,
+        Query<&ScriptAnimatedSprite, With<ScriptEntity>>,
+        Query<&mut ScriptTextInput, With<ScriptEntity>>,
     ),
     // Additional UI component queries for ECS API
     mut ui_queries: (
@@ -2035,10 +2243,11 @@ fn process_commands(
     mut button_colors_query: Query<&mut ScriptButtonColors, With<ScriptEntity>>,
 ) {
     let (cmd_rx, event_tx) = channels;
-    let (registry, window_ui_registry, font_registry, resource_registry, pending_assets) = &mut registries;
+    let (registry, window_ui_registry, font_registry, resource_registry, pending_assets, audio_registry) = &mut registries;
     let (script_entity_registry, script_component_registry, declared_system_registry, entity_event_callback_registry) = &mut ecs_registries;
+    let (images, cursor_options_query) = &mut window_extras;
     let (text_query, bg_color_query, node_query, text_color_query, button_query) = &mut widget_queries;
-    let (transform_query, sprite_query, visibility_query) = &mut native_queries;
+    let (transform_query, sprite_query, visibility_query, animated_sprite_query, text_input_query) = &mut native_queries;
     let (ecs_node_query, ecs_bg_color_query, ecs_text_query, ecs_border_radius_query, ecs_interaction_query, ecs_button_query, ecs_image_node_query) = &mut ui_queries;
     // Lock the receiver and process all available commands (non-blocking)
     let receiver = match cmd_rx.0.lock() {
@@ -2048,8 +2257,18 @@ fn process_commands(
             return;
         }
     };
-    while let Ok(cmd) = receiver.try_recv() {
+    // Commands unpacked from a GraphicCommand::Batch run before the next channel message
+    let mut batched: VecDeque<GraphicCommand> = VecDeque::new();
+    while let Some(cmd) = batched.pop_front().or_else(|| receiver.try_recv().ok()) {
         match cmd {
+            GraphicCommand::Batch { commands } => {
+                tracing::trace!("Processing batch of {} commands", commands.len());
+                // Prepend in reverse so a nested batch keeps its place in the outer one
+                for command in commands.into_iter().rev() {
+                    batched.push_front(command);
+                }
+            }
+
             GraphicCommand::CreateWindow {
                 id,
                 config,
@@ -2222,6 +2441,81 @@ fn process_commands(
                 }
             }
 
+            GraphicCommand::SetWindowIcon {
+                id,
+                resource_id,
+                response_tx,
+            } => {
+                let result = (|| -> Result<(), String> {
+                    let entity = registry
+                        .get_entity(id)
+                        .ok_or_else(|| format!("Window {} not found", id))?;
+                    let handle = resource_registry
+                        .get_image_handle(&resource_id)
+                        .ok_or_else(|| format!("Resource '{}' is not a loaded image", resource_id))?;
+                    let image = images
+                        .get(&handle)
+                        .ok_or_else(|| format!("Image '{}' has not finished loading", resource_id))?;
+                    let rgba = image
+                        .clone()
+                        .try_into_dynamic()
+                        .map_err(|e| format!("Image '{}' cannot be used as an icon: {}", resource_id, e))?
+                        .to_rgba8();
+                    let (width, height) = rgba.dimensions();
+                    let icon = winit::window::Icon::from_rgba(rgba.into_raw(), width, height)
+                        .map_err(|e| format!("Image '{}' cannot be used as an icon: {}", resource_id, e))?;
+
+                    // Icons are a winit-only feature, set them on the native window directly
+                    WINIT_WINDOWS.with(|winit_windows| {
+                        let winit_wins = winit_windows.borrow();
+                        let winit_window = winit_wins
+                            .get_window(entity)
+                            .ok_or_else(|| format!("Window {} entity not found", id))?;
+                        winit_window.set_window_icon(Some(icon));
+                        Ok(())
+                    })
+                })();
+                let _ = response_tx.send(result);
+            }
+
+            GraphicCommand::SetCursorVisible {
+                id,
+                visible,
+                response_tx,
+            } => {
+                if let Some(entity) = registry.get_entity(id) {
+                    if let Ok(mut cursor_options) = cursor_options_query.get_mut(entity) {
+                        cursor_options.visible = visible;
+                        let _ = response_tx.send(Ok(()));
+                    } else {
+                        let _ = response_tx.send(Err(format!("Window {} entity not found", id)));
+                    }
+                } else {
+                    let _ = response_tx.send(Err(format!("Window {} not found", id)));
+                }
+            }
+
+            GraphicCommand::SetCursorIcon {
+                id,
+                icon,
+                response_tx,
+            } => {
+                if let Some(entity) = registry.get_entity(id) {
+                    if windows.contains(entity) {
+                        // Remembered so hover feedback falls back to it instead of the arrow
+                        let cursor = system_cursor_icon(icon);
+                        commands
+                            .entity(entity)
+                            .insert((ScriptWindowCursor(cursor), CursorIcon::System(cursor)));
+                        let _ = response_tx.send(Ok(()));
+                    } else {
+                        let _ = response_tx.send(Err(format!("Window {} entity not found", id)));
+                    }
+                } else {
+                    let _ = response_tx.send(Err(format!("Window {} not found", id)));
+                }
+            }
+
             // Note: SetWindowResizable was removed - resizable is set at window creation time
 
             GraphicCommand::Shutdown { response_tx } => {
@@ -2255,12 +2549,13 @@ fn process_commands(
                         "Input Handling".to_string(),
                         "Window Management".to_string(),
                         "Asset Loading".to_string(),
+                        "Audio Playback".to_string(),
                     ],
                     backend: detect_rendering_backend(),
                     supports_2d: true,
                     supports_3d: true,
                     supports_ui: true,
-                    supports_audio: false,
+                    supports_audio: true,
                 };
                 let _ = response_tx.send(info);
             }
@@ -2505,7 +2800,33 @@ fn process_commands(
                             error: None,
                         })
                     }
-                    // TODO: Add Audio, Shader, Model3D handlers when Bevy features are enabled
+                    ResourceType::Audio => {
+                        let handle: Handle<AudioSource> = asset_server.load(&path);
+                        let untyped_id = handle.id().untyped();
+                        resource_registry.register(
+                            asset_id,
+                            alias.clone(),
+                            path.clone(),
+                            resource_type,
+                            ResourceHandle::Audio(handle),
+                        );
+                        // Track this asset as pending
+                        pending_assets.add(asset_id, alias.clone(), untyped_id);
+                        tracing::debug!(
+                            "Audio '{}' (asset_id={}) queued for loading, tracking in PendingAssetRegistry",
+                            alias, asset_id
+                        );
+                        Ok(ResourceInfo {
+                            alias,
+                            path: path.clone(),
+                            resolved_path: path,
+                            resource_type,
+                            state: ResourceState::Loading, // NOT Loaded yet!
+                            size: None,
+                            error: None,
+                        })
+                    }
+                    // TODO: Add Shader, Model3D handlers when Bevy features are enabled
                     _ => {
                         Err(format!(
                             "Resource type {:?} is not yet supported by Bevy engine",
@@ -2536,6 +2857,59 @@ fn process_commands(
                 let _ = response_tx.send(Ok(()));
             }
 
+            // ================================================================
+            // Audio Commands
+            // ================================================================
+            GraphicCommand::PlayAudio {
+                resource_id,
+                volume,
+                looping,
+                response_tx,
+            } => {
+                let Some(handle) = resource_registry.get_audio_handle(&resource_id) else {
+                    let _ = response_tx.send(Err(format!(
+                        "Audio resource '{}' is not loaded. Load it with Resource.load() first.",
+                        resource_id
+                    )));
+                    continue;
+                };
+
+                // One-shot sounds despawn themselves when done (see prune_finished_audio)
+                let settings = if looping {
+                    PlaybackSettings::LOOP
+                } else {
+                    PlaybackSettings::DESPAWN
+                };
+                let entity = commands
+                    .spawn((
+                        AudioPlayer::new(handle),
+                        settings.with_volume(bevy::audio::Volume::Linear(volume)),
+                    ))
+                    .id();
+                let playback = audio_registry.register(entity);
+                tracing::debug!(
+                    "Playing audio '{}' (handle={}, volume={}, loop={})",
+                    resource_id, playback, volume, looping
+                );
+                let _ = response_tx.send(Ok(playback));
+            }
+
+            GraphicCommand::StopAudio { handle, response_tx } => {
+                let result = match audio_registry.unregister(handle) {
+                    Some(entity) => {
+                        // Dropping the sink together with the entity stops the sound
+                        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                            entity_commands.despawn();
+                        }
+                        Ok(())
+                    }
+                    // Already finished on its own
+                    None if audio_registry.was_issued(handle) => Ok(()),
+                    None => Err(format!("Unknown audio handle {}", handle)),
+                };
+                let _ = response_tx.send(result);
+            }
+
             // ================================================================
             // ECS Commands
             // ================================================================
@@ -2791,6 +3165,38 @@ fn process_commands(
                                     }
                                 }
                             }
+                            NativeComponent::AnimatedSprite => {
+                                match ScriptAnimatedSprite::from_json(&component_data, resource_registry) {
+                                    Ok(animated_sprite) => {
+                                        entity_commands.insert(animated_sprite);
+                                        tracing::debug!("Added native AnimatedSprite component to entity {}", script_id);
+                                    }
+                                    Err(e) => {
+                                        tracing::warn!("Failed to create AnimatedSprite component: {}", e);
+                                    }
+                                }
+                            }
+                            NativeComponent::TextInput => {
+                                match json_to_text_config(&component_data) {
+                                    Ok(config) => {
+                                        let text_input = ScriptTextInput::new(&config, &component_data);
+                                        let (text, color) = text_input.display();
+                                        // Button provides the Interaction used to focus the field
+                                        entity_commands.insert((
+                                            bevy::prelude::Text::new(text),
+                                            text_font_for(&config, font_registry, window_ui_registry),
+                                            bevy::text::TextColor(color),
+                                            bevy::ui::widget::Button,
+                                            text_input,
+                                        ));
+                                        is_ui_entity = true;  // Mark as UI entity
+                                        tracing::debug!("Added native TextInput component to entity {}", script_id);
+                                    }
+                                    Err(e) => {
+                                        tracing::warn!("Failed to create TextInput component: {}", e);
+                                    }
+                                }
+                            }
                         }
                     } else {
                         // Custom script component
@@ -3048,6 +3454,25 @@ fn process_commands(
                                     return Err(format!("Resource '{}' not found. Make sure to call Resource.load() first.", config.resource_id));
                                 }
                             }
+                            NativeComponent::AnimatedSprite => {
+                                // Replacing the component restarts the animation from its first frame
+                                let animated_sprite = ScriptAnimatedSprite::from_json(&component_data, resource_registry)?;
+                                commands.entity(entity).insert(animated_sprite);
+                                tracing::debug!("Inserted native AnimatedSprite on entity {}", entity_id);
+                            }
+                            NativeComponent::TextInput => {
+                                let config = json_to_text_config(&component_data)?;
+                                let text_input = ScriptTextInput::new(&config, &component_data);
+                                let (text, color) = text_input.display();
+                                commands.entity(entity).insert((
+                                    bevy::prelude::Text::new(text),
+                                    text_font_for(&config, font_registry, window_ui_registry),
+                                    bevy::text::TextColor(color),
+                                    bevy::ui::widget::Button,
+                                    text_input,
+                                ));
+                                tracing::debug!("Inserted native TextInput on entity {}", entity_id);
+                            }
                         }
                         return Ok(());
                     }
@@ -3061,10 +3486,8 @@ fn process_commands(
                     // Check if this component type already exists on the entity
                     // We need to iterate children to find ScriptComponents
                     let mut found = false;
-                    for (_comp_entity, mut comp) in script_component_query.iter_mut() {
-                        // Check if this component belongs to our entity (is a child)
-                        // For now, we use a simpler approach: spawn as child
-                        if comp.type_name == component_name {
+                    for (_comp_entity, mut comp, child_of) in script_component_query.iter_mut() {
+                        if child_of.parent() == entity && comp.type_name == component_name {
                             // Component exists, update it
                             comp.data = component_data.clone();
                             found = true;
@@ -3222,6 +3645,20 @@ fn process_commands(
                                 commands.entity(entity).insert(radius);
                                 tracing::debug!("Updated native BorderRadius on entity {}", entity_id);
                             }
+                            NativeComponent::TextInput => {
+                                // Only value and placeholder can be updated; styling requires a full insert
+                                if let Ok(mut text_input) = text_input_query.get_mut(entity) {
+                                    if let Some(value) = component_data.get("value").and_then(|v| v.as_str()) {
+                                        text_input.state.set_value(value);
+                                    }
+                                    if let Some(placeholder) = component_data.get("placeholder").and_then(|v| v.as_str()) {
+                                        text_input.placeholder = placeholder.to_string();
+                                    }
+                                    tracing::debug!("Updated native TextInput on entity {}", entity_id);
+                                } else {
+                                    return Err(format!("Entity {} does not have TextInput component", entity_id));
+                                }
+                            }
                             _ => {
                                 return Err(format!("Update not supported for component '{}'", component_name));
                             }
@@ -3230,8 +3667,8 @@ fn process_commands(
                     }
 
                     // Custom script component - merge data
-                    for (_comp_entity, mut comp) in script_component_query.iter_mut() {
-                        if comp.type_name == component_name {
+                    for (_comp_entity, mut comp, child_of) in script_component_query.iter_mut() {
+                        if child_of.parent() == entity && comp.type_name == component_name {
                             let merged = merge_json(&comp.data, &component_data);
                             comp.data = merged;
                             tracing::debug!("Updated component '{}' on entity {}", component_name, entity_id);
@@ -3324,6 +3761,22 @@ fn process_commands(
                                 tracing::debug!("Removed native ImageNode from entity {}", entity_id);
                                 return Ok(());
                             }
+                            NativeComponent::AnimatedSprite => {
+                                // The Sprite keeps showing the frame it was on
+                                if animated_sprite_query.get(entity).is_ok() {
+                                    commands.entity(entity).remove::<ScriptAnimatedSprite>();
+                                    tracing::debug!("Removed native AnimatedSprite from entity {}", entity_id);
+                                    return Ok(());
+                                }
+                            }
+                            NativeComponent::TextInput => {
+                                // The Text keeps showing the last value
+                                if text_input_query.get(entity).is_ok() {
+                                    commands.entity(entity).remove::<ScriptTextInput>();
+                                    tracing::debug!("Removed native TextInput from entity {}", entity_id);
+                                    return Ok(());
+                                }
+                            }
                         }
                         return Err(format!(
                             "Native component '{}' not found on entity {}",
@@ -3334,8 +3787,8 @@ fn process_commands(
                     // Custom script component
                     // Find and despawn the component child entity
                     let mut removed = false;
-                    for (comp_entity, comp) in script_component_query.iter() {
-                        if comp.type_name == component_name {
+                    for (comp_entity, comp, child_of) in script_component_query.iter() {
+                        if child_of.parent() == entity && comp.type_name == component_name {
                             commands.entity(comp_entity).despawn();
                             removed = true;
                             break;
@@ -3423,14 +3876,24 @@ fn process_commands(
                                     return Ok(Some(image_node_to_json(&image_node)));
                                 }
                             }
+                            NativeComponent::AnimatedSprite => {
+                                if let Ok(animated_sprite) = animated_sprite_query.get(entity) {
+                                    return Ok(Some(animated_sprite.to_json()));
+                                }
+                            }
+                            NativeComponent::TextInput => {
+                                if let Ok(text_input) = text_input_query.get(entity) {
+                                    return Ok(Some(text_input.to_json()));
+                                }
+                            }
                         }
                         return Ok(None);
                     }
 
                     // Custom script component
                     // Find the component among children
-                    for (_comp_entity, comp) in script_component_query.iter() {
-                        if comp.type_name == component_name {
+                    for (_comp_entity, comp, child_of) in script_component_query.iter() {
+                        if child_of.parent() == entity && comp.type_name == component_name {
                             return Ok(Some(comp.data.clone()));
                         }
                     }
@@ -3485,13 +3948,19 @@ fn process_commands(
                             NativeComponent::ImageNode => {
                                 return Ok(ecs_image_node_query.get(entity).is_ok());
                             }
+                            NativeComponent::AnimatedSprite => {
+                                return Ok(animated_sprite_query.get(entity).is_ok());
+                            }
+                            NativeComponent::TextInput => {
+                                return Ok(text_input_query.get(entity).is_ok());
+                            }
                         }
                     }
 
                     // Custom script component
                     // Check if component exists among children
-                    for (_comp_entity, comp) in script_component_query.iter() {
-                        if comp.type_name == component_name {
+                    for (_comp_entity, comp, child_of) in script_component_query.iter() {
+                        if child_of.parent() == entity && comp.type_name == component_name {
                             return Ok(true);
                         }
                     }
@@ -3522,6 +3991,12 @@ fn process_commands(
                     if let Ok(visibility) = visibility_query.get(entity) {
                         components_data.insert("Visibility".to_string(), visibility_to_json(&visibility));
                     }
+                    if let Ok(animated_sprite) = animated_sprite_query.get(entity) {
+                        components_data.insert("AnimatedSprite".to_string(), animated_sprite.to_json());
+                    }
+                    if let Ok(text_input) = text_input_query.get(entity) {
+                        components_data.insert("TextInput".to_string(), text_input.to_json());
+                    }
 
                     // Collect UI components for this entity
                     if let Ok(node) = ecs_node_query.get(entity) {
@@ -3542,12 +4017,15 @@ fn process_commands(
                     if ecs_button_query.get(entity).is_ok() {
                         components_data.insert("Button".to_string(), serde_json::json!(true));
                     }
+                    if let Ok(image_node) = ecs_image_node_query.get(entity) {
+                        components_data.insert("ImageNode".to_string(), image_node_to_json(&image_node));
+                    }
 
-                    // Collect custom script components for this entity
-                    // Note: This is a simplified approach. In a real implementation,
-                    // we'd need to properly associate ScriptComponents with their parent entities.
-                    for (_comp_entity, comp) in script_component_query.iter() {
-                        components_data.insert(comp.type_name.clone(), comp.data.clone());
+                    // Collect custom script components for this entity (its ScriptComponent children)
+                    for (_comp_entity, comp, child_of) in script_component_query.iter() {
+                        if child_of.parent() == entity {
+                            components_data.insert(comp.type_name.clone(), comp.data.clone());
+                        }
                     }
 
                     // Check "with" conditions
@@ -3724,11 +4202,27 @@ fn send_engine_ready_event(event_tx: Res<EventSenderRes>, mut sent: ResMut<Engin
     }
 }
 
-/// System to check pending assets and send ResourceLoaded events when ready
+/// Forget playbacks whose entity was despawned (finished one-shot sounds)
+fn prune_finished_audio(
+    mut audio_registry: ResMut<AudioRegistry>,
+    mut finished: RemovedComponents<AudioPlayer>,
+) {
+    let finished: Vec<Entity> = finished.read().collect();
+    if finished.is_empty() {
+        return;
+    }
+    audio_registry
+        .playing
+        .retain(|_, entity| !finished.contains(entity));
+}
+
+/// System to check pending assets and send ResourceLoading/ResourceLoaded events
 ///
 /// This system runs every frame and checks if any pending assets have finished
-/// loading in Bevy's AssetServer. When an asset is ready (is_loaded_with_dependencies),
-/// we send a ResourceLoaded event to notify the ResourceProxy.
+/// loading in Bevy's AssetServer. A ResourceLoading event is sent the first time
+/// an asset is seen, so mods can show loading UI immediately. When an asset is
+/// ready (is_loaded_with_dependencies), we send a ResourceLoaded event to notify
+/// the ResourceProxy.
 fn check_pending_assets(
     mut pending_assets: ResMut<PendingAssetRegistry>,
     asset_server: Res<AssetServer>,
@@ -3744,7 +4238,16 @@ fn check_pending_assets(
     // Take all pending assets and check each one
     let mut still_pending = Vec::new();
 
-    for entry in std::mem::take(&mut pending_assets.pending) {
+    for mut entry in std::mem::take(&mut pending_assets.pending) {
+        // Report the start of the load once, before any terminal event
+        if !entry.loading_reported {
+            entry.loading_reported = true;
+            let _ = event_tx.0.try_send(GraphicEvent::ResourceLoading {
+                alias: entry.alias.clone(),
+                asset_id: entry.asset_id,
+            });
+        }
+
         // Check if the asset is loaded
         let load_state = asset_server.get_load_state(entry.handle_id);
         // tracing::trace!(
@@ -3838,6 +4341,100 @@ fn handle_keyboard_input(
     }
 }
 
+/// Focus the TextInput that was clicked, unfocusing the others
+fn handle_text_input_focus(
+    mouse_button: Res<ButtonInput<bevy::input::mouse::MouseButton>>,
+    mut inputs: Query<(&Interaction, &mut ScriptTextInput)>,
+) {
+    if !mouse_button.just_pressed(bevy::input::mouse::MouseButton::Left) {
+        return;
+    }
+    for (interaction, mut text_input) in inputs.iter_mut() {
+        let focused = *interaction == Interaction::Pressed;
+        if text_input.focused != focused {
+            text_input.focused = focused;
+        }
+    }
+}
+
+/// Edit the focused TextInput from keyboard input
+///
+/// Sends `input:changed` after an edit and `input:submit` on Enter, when the script
+/// registered the matching callback. Escape removes the focus. Key presses are still
+/// reported to mods by `handle_keyboard_input`.
+fn handle_text_input_keyboard(
+    mut keyboard_events: EventReader<bevy::input::keyboard::KeyboardInput>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    event_tx: Res<EventSenderRes>,
+    event_callback_registry: Res<EntityEventCallbackRegistry>,
+    mut inputs: Query<(&ScriptEntity, &mut ScriptTextInput)>,
+) {
+    use bevy::input::ButtonState;
+    use bevy::input::keyboard::Key;
+
+    let Some((script_entity, mut text_input)) = inputs.iter_mut().find(|(_, input)| input.focused) else {
+        keyboard_events.clear();
+        return;
+    };
+
+    // Ctrl/Cmd shortcuts are not text (there is no clipboard support)
+    let shortcut = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+
+    let mut changed = false;
+    let mut submitted = false;
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => submitted = true,
+            Key::Escape => text_input.focused = false,
+            Key::Backspace => changed |= text_input.state.backspace(),
+            Key::Delete => changed |= text_input.state.delete(),
+            Key::ArrowLeft => text_input.state.move_left(),
+            Key::ArrowRight => text_input.state.move_right(),
+            Key::Home => text_input.state.move_home(),
+            Key::End => text_input.state.move_end(),
+            Key::Space if !shortcut => changed |= text_input.state.insert(" "),
+            Key::Character(text) if !shortcut => changed |= text_input.state.insert(text),
+            _ => {}
+        }
+    }
+
+    let entity_id = script_entity.script_id;
+    for (happened, event_type) in [(changed, "input:changed"), (submitted, "input:submit")] {
+        if happened && event_callback_registry.has_callback(entity_id, event_type) {
+            let _ = event_tx.0.try_send(GraphicEvent::EntityEventCallback {
+                entity_id,
+                event_type: event_type.to_string(),
+                x: 0.0,
+                y: 0.0,
+                value: Some(text_input.state.value().to_string()),
+            });
+        }
+    }
+}
+
+/// Refresh the Text of TextInputs whose value, caret or focus changed
+fn render_text_inputs(
+    mut inputs: Query<(&ScriptTextInput, &mut Text, &mut bevy::text::TextColor), Changed<ScriptTextInput>>,
+) {
+    for (text_input, mut text, mut text_color) in inputs.iter_mut() {
+        let (display, color) = text_input.display();
+        if **text != display {
+            **text = display;
+        }
+        if text_color.0 != color {
+            text_color.0 = color;
+        }
+    }
+}
+
 /// System to handle mouse input
 fn handle_mouse_input(
     event_tx: Res<EventSenderRes>,
@@ -3963,6 +4560,33 @@ fn handle_window_events(
 #[derive(Component, Default)]
 struct ScriptEntityPreviousInteraction(Interaction);
 
+/// Default cursor chosen by a script via window.setCursor()
+#[derive(Component)]
+struct ScriptWindowCursor(SystemCursorIcon);
+
+/// Map a script cursor name to the Bevy system cursor
+fn system_cursor_icon(cursor: WindowCursor) -> SystemCursorIcon {
+    match cursor {
+        WindowCursor::Default => SystemCursorIcon::Default,
+        WindowCursor::Pointer => SystemCursorIcon::Pointer,
+        WindowCursor::Text => SystemCursorIcon::Text,
+        WindowCursor::Crosshair => SystemCursorIcon::Crosshair,
+        WindowCursor::Move => SystemCursorIcon::Move,
+        WindowCursor::Wait => SystemCursorIcon::Wait,
+        WindowCursor::Progress => SystemCursorIcon::Progress,
+        WindowCursor::Help => SystemCursorIcon::Help,
+        WindowCursor::NotAllowed => SystemCursorIcon::NotAllowed,
+        WindowCursor::Grab => SystemCursorIcon::Grab,
+        WindowCursor::Grabbing => SystemCursorIcon::Grabbing,
+        WindowCursor::EwResize => SystemCursorIcon::EwResize,
+        WindowCursor::NsResize => SystemCursorIcon::NsResize,
+        WindowCursor::NeswResize => SystemCursorIcon::NeswResize,
+        WindowCursor::NwseResize => SystemCursorIcon::NwseResize,
+        WindowCursor::ZoomIn => SystemCursorIcon::ZoomIn,
+        WindowCursor::ZoomOut => SystemCursorIcon::ZoomOut,
+    }
+}
+
 /// System to handle interactions on ECS entities with Button + Interaction components
 fn handle_script_entity_interactions(
     mut commands: Commands,
@@ -3976,14 +4600,20 @@ fn handle_script_entity_interactions(
         ),
         (Changed<Interaction>, With<bevy::ui::widget::Button>),
     >,
-    all_buttons_query: Query<&Interaction, With<bevy::ui::widget::Button>>,
-    windows: Query<(Entity, &Window), With<PrimaryWindow>>,
+    all_buttons_query: Query<(&Interaction, Has<ScriptTextInput>), With<bevy::ui::widget::Button>>,
+    windows: Query<(Entity, &Window, Option<&ScriptWindowCursor>), With<PrimaryWindow>>,
 ) {
     // Get window entity and cursor position for events
-    let (window_entity, cursor_pos) = windows
+    let (window_entity, cursor_pos, default_cursor) = windows
         .single()
-        .map(|(entity, w)| (Some(entity), w.cursor_position().unwrap_or(Vec2::ZERO)))
-        .unwrap_or((None, Vec2::ZERO));
+        .map(|(entity, w, cursor)| {
+            (
+                Some(entity),
+                w.cursor_position().unwrap_or(Vec2::ZERO),
+                cursor.map_or(SystemCursorIcon::Default, |c| c.0),
+            )
+        })
+        .unwrap_or((None, Vec2::ZERO, SystemCursorIcon::Default));
 
     // Process changed interactions and send events
     for (script_entity, interaction, mut prev_interaction) in changed_query.iter_mut() {
@@ -4008,6 +4638,7 @@ fn handle_script_entity_interactions(
                     event_type: "click".to_string(),
                     x: cursor_pos.x,
                     y: cursor_pos.y,
+                    value: None,
                 });
             } else {
                 // Send generic interaction changed event
@@ -4036,16 +4667,16 @@ fn handle_script_entity_interactions(
     }
 
     // Check if any button is hovered to update cursor
-    let any_hovered = all_buttons_query.iter().any(|interaction| {
-        *interaction == Interaction::Hovered || *interaction == Interaction::Pressed
+    let hovered = all_buttons_query.iter().find(|(interaction, _)| {
+        **interaction == Interaction::Hovered || **interaction == Interaction::Pressed
     });
 
-    // Update window cursor based on hover state
+    // Update window cursor based on hover state (text cursor over text inputs)
     if let Some(window_entity) = window_entity {
-        let cursor = if any_hovered {
-            CursorIcon::System(SystemCursorIcon::Pointer)
-        } else {
-            CursorIcon::System(SystemCursorIcon::Default)
+        let cursor = match hovered {
+            Some((_, true)) => CursorIcon::System(SystemCursorIcon::Text),
+            Some(_) => CursorIcon::System(SystemCursorIcon::Pointer),
+            None => CursorIcon::System(default_cursor),
         };
         commands.entity(window_entity).insert(cursor);
     }
@@ -4263,6 +4894,87 @@ fn update_cover_contain_images(
     }
 }
 
+// ============================================================================
+// Sprite Animation
+// ============================================================================
+
+/// Advance every `AnimatedSprite` by the frame time
+///
+/// Runs in the DeclaredSystems set. When a non-looping animation shows its last
+/// frame, an `animation:complete` entity event is sent if the script registered one.
+fn animate_sprites(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    event_callback_registry: Res<EntityEventCallbackRegistry>,
+    event_tx: Res<EventSenderRes>,
+    mut query: Query<(Entity, &ScriptEntity, &mut ScriptAnimatedSprite, Option<&mut Sprite>)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, script_entity, mut animated_sprite, sprite) in query.iter_mut() {
+        let animated_sprite = &mut *animated_sprite;
+
+        // First run: build the grid layout and point the Sprite at the sheet
+        let Some(layout) = animated_sprite.layout.clone() else {
+            let config = &animated_sprite.config;
+            let layout = layouts.add(TextureAtlasLayout::from_grid(
+                UVec2::new(config.tile_size.width, config.tile_size.height),
+                config.columns,
+                config.rows,
+                None,
+                None,
+            ));
+            let atlas = TextureAtlas {
+                layout: layout.clone(),
+                index: animated_sprite.state.atlas_index(config),
+            };
+            match sprite {
+                Some(mut sprite) => {
+                    sprite.image = animated_sprite.image.clone();
+                    sprite.texture_atlas = Some(atlas);
+                }
+                None => {
+                    commands
+                        .entity(entity)
+                        .insert(Sprite::from_atlas_image(animated_sprite.image.clone(), atlas));
+                }
+            }
+            animated_sprite.layout = Some(layout);
+            continue;
+        };
+
+        let completed = animated_sprite.state.advance(&animated_sprite.config, dt);
+        let index = animated_sprite.state.atlas_index(&animated_sprite.config);
+
+        if let Some(mut sprite) = sprite {
+            match sprite.texture_atlas.as_mut() {
+                Some(atlas) if atlas.index != index => atlas.index = index,
+                Some(_) => {}
+                // The Sprite was replaced by the script: re-attach the sheet
+                None => {
+                    sprite.image = animated_sprite.image.clone();
+                    sprite.texture_atlas = Some(TextureAtlas { layout, index });
+                }
+            }
+        }
+
+        if completed {
+            let entity_id = script_entity.script_id;
+            tracing::trace!("AnimatedSprite on entity {} completed", entity_id);
+            if event_callback_registry.has_callback(entity_id, "animation:complete") {
+                let _ = event_tx.0.try_send(GraphicEvent::EntityEventCallback {
+                    entity_id,
+                    event_type: "animation:complete".to_string(),
+                    x: 0.0,
+                    y: 0.0,
+                    value: None,
+                });
+            }
+        }
+    }
+}
+
 // ============================================================================
 // Declared Systems Execution
 // ============================================================================
@@ -3,6 +3,7 @@ use fluent_bundle::concurrent::FluentBundle;
 use fluent_bundle::{FluentArgs, FluentValue};
 use std::collections::HashMap;
 use std::fs;
+use stam_mod_runtimes::api::locale::add_locale_functions;
 use std::path::Path;
 use tracing::{error, info, warn};
 use unic_langid::LanguageIdentifier;
@@ -117,6 +118,7 @@ impl LocaleManager {
 
         // Create bundle with concurrent memoizer for thread-safety
         let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+        add_locale_functions(&mut bundle).map_err(|e| format!("Failed to register locale functions: {:?}", e))?;
 
         // Load main.ftl file
         let main_file = locale_path.join("main.ftl");
@@ -147,8 +149,15 @@ impl LocaleManager {
 
     /// Get a localized message with arguments
     pub fn get_with_args(&self, id: &str, args: Option<&FluentArgs>) -> String {
-        // Try current locale first
-        if let Some(bundle) = self.bundles.get(&self.current_locale) {
+        self.get_with_args_in(&self.current_locale, id, args)
+    }
+
+    /// Get a localized message with arguments in a given locale
+    ///
+    /// Falls back to the fallback locale (and then to `[id]`) like `get_with_args`.
+    pub fn get_with_args_in(&self, locale: &str, id: &str, args: Option<&FluentArgs>) -> String {
+        // Try requested locale first
+        if let Some(bundle) = self.bundles.get(locale) {
             if let Some(message) = bundle.get_message(id) {
                 if let Some(pattern) = message.value() {
                     let mut errors = vec![];
@@ -164,7 +173,7 @@ impl LocaleManager {
         }
 
         // Fallback to default locale
-        if self.current_locale != self.fallback_locale {
+        if locale != self.fallback_locale {
             if let Some(bundle) = self.bundles.get(&self.fallback_locale) {
                 if let Some(message) = bundle.get_message(id) {
                     if let Some(pattern) = message.value() {
@@ -1,19 +1,24 @@
 use clap::Parser;
 use sha2::{Digest, Sha512};
 use std::collections::HashMap;
+use std::io::{IsTerminal, Write};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc as std_mpsc;
-use tokio::net::TcpStream;
 use tracing::{Level, debug, error, info, trace, warn};
 
 use stam_mod_runtimes::api::{
-    DownloadResponse, EnableEngineRequest, GraphicCommand, GraphicEngineReadyRequest,
-    GraphicEngineWindowClosedRequest, GraphicEngines, GraphicEvent, GraphicProxy, LocaleApi,
-    NetworkApi, NetworkConfig, extract_mod_archive, parse_stam_uri, sanitize_uri,
+    ApiRegistry, DownloadResponse, EnableEngineRequest, GraphicCommand, GraphicEngineReadyRequest,
+    GraphicEngineWindowClosedRequest, FRAME_STATS_EVENT, GraphicEngines, GraphicEvent, GraphicProxy, LocaleApi,
+    CancelToken, DEFAULT_MAX_BUFFER_BYTES, NetworkApi, NetworkConfig, STATUS_CANCELLED, StartupTimings, TransferStats, UriListEntry, extract_mod_archive, mod_extraction_limit,
+    validate_mod_id,
+    is_secure_stam_uri, parse_stam_uri, sanitize_uri, STAM_SCHEME, STAMS_SCHEME,
+};
+use stam_log::{LogConfig, LogHandle, ModLogLimit, init_logging};
+use stam_protocol::{
+    check_compatibility, Compression, DisconnectReason, GameMessage, GameStream, Incompatible, IntentType, MessageLimit,
+    PrimalMessage, PrimalStream, ServerInfo, StamStream, PROTOCOL_VERSION,
 };
-use stam_log::{LogConfig, init_logging};
-use stam_protocol::{GameMessage, GameStream, IntentType, PrimalMessage, PrimalStream};
 use stam_schema::{ModManifest, Validatable, validate_mod_dependencies, validate_version_range};
 
 mod engines;
@@ -45,14 +50,35 @@ mod locale;
 use locale::LocaleManager;
 
 mod app_paths;
+mod connection_pool;
+mod mod_cache;
+mod mod_deps;
+mod mod_dirs;
+mod mod_progress;
 mod mod_runtime;
+mod mod_validation;
+mod mod_set;
+mod mod_watcher;
+mod reconnect;
 
 use app_paths::AppPaths;
-use mod_runtime::js_adapter::{create_js_runtime_config, run_js_event_loop};
+use connection_pool::{ConnectionPool, PooledStream};
+use mod_cache::ModArchiveCache;
+use mod_dirs::ModDirs;
+use mod_progress::{LoadPhase, ModLoadProgress};
+use mod_runtime::js_adapter::{
+    create_js_runtime_config, drain_pending_jobs, run_js_event_loop, run_js_memory_monitor, MEMORY_STATS_INTERVAL,
+    SHUTDOWN_DRAIN_TIMEOUT,
+};
 use mod_runtime::{JsRuntimeAdapter, JsRuntimeConfig, ModInfo, ModRuntimeManager};
+use mod_watcher::ModWatcher;
+use reconnect::{ReconnectPolicy, Reconnector};
 
 const VERSION: &str = "0.1.0";
 
+/// Maximum number of graphic events taken from the engine channel per worker wake
+const GRAPHIC_EVENT_BATCH_SIZE: usize = 64;
+
 /// Compute SHA-512 hash of a string and return as hex string
 fn sha512_hash(input: &str) -> String {
     let mut hasher = Sha512::new();
@@ -61,33 +87,245 @@ fn sha512_hash(input: &str) -> String {
     format!("{:x}", result)
 }
 
-/// Perform a stam:// URI request and return the response
+/// Check the server's `Welcome` against this client and log the outcome
+///
+/// Compatibility only depends on the wire protocol version; the application
+/// versions are shown for context.
+fn check_server_compatibility(locale: &LocaleManager, server_version: &str, server_protocol: u32) -> Result<(), Incompatible> {
+    let client = format!("{} (protocol v{})", VERSION, PROTOCOL_VERSION);
+    let server = format!("{} (protocol v{})", server_version, server_protocol);
+    let result = check_compatibility(PROTOCOL_VERSION, server_protocol);
+    let message = if result.is_ok() { "version-compatible" } else { "version-mismatch" };
+    let text = locale.get_with_args(
+        message,
+        Some(&fluent_args! {
+            "client" => client.as_str(),
+            "server" => server.as_str()
+        }),
+    );
+    match &result {
+        Ok(()) => info!("{}", text),
+        Err(e) => error!("{} ({})", text, e),
+    }
+    result
+}
+
+/// Verify that the file at `path` has the SHA-512 digest `expected`
+///
+/// `expected` is a hex digest as sent in `ModInfo::archive_sha512`, optionally
+/// prefixed with `sha512:`; the comparison ignores case. The file is hashed in a
+/// streaming fashion so large archives are never fully loaded into memory.
+fn verify_sha512(path: &std::path::Path, expected: &str) -> Result<(), String> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open '{}' for verification: {}", path.display(), e))?;
+    let mut hasher = Sha512::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to read '{}' for verification: {}", path.display(), e))?;
+    let actual = format!("{:x}", hasher.finalize());
+
+    let expected = expected.trim();
+    let expected = expected.strip_prefix("sha512:").unwrap_or(expected);
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(format!("SHA-512 mismatch: expected {}, got {}", expected, actual))
+    }
+}
+
+/// Check that every mod id sent by the server can be used as a directory name
+///
+/// Ids are joined to the mods directory (and extraction replaces the directory),
+/// so an id like `../..` must be rejected before any path is built from it.
+fn validate_server_mod_ids<'a>(mod_ids: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+    for mod_id in mod_ids {
+        validate_mod_id(mod_id).map_err(|e| format!("Server sent an unusable mod: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Initial size of the chunk buffer used for transfers without a known size.
+/// The buffer grows to fit larger chunks, so small responses stay cheap.
+const UNKNOWN_SIZE_CHUNK_BUFFER_INITIAL: usize = 64 * 1024;
+
+/// Create the temporary chunk buffer for a transfer
+///
+/// Known-size transfers read straight into the final buffer and empty files
+/// have no chunks, so only unknown-size transfers get a (small) buffer.
+fn unknown_size_chunk_buffer(file_size: Option<u64>) -> Vec<u8> {
+    match file_size {
+        None => vec![0u8; UNKNOWN_SIZE_CHUNK_BUFFER_INITIAL],
+        Some(_) => Vec::new(),
+    }
+}
+
+/// Name of the `.part` file holding a partial download of `uri` from `host_port`
+///
+/// The name is derived from the request (not from a timestamp) so an interrupted
+/// download can be found and resumed by a later request for the same URI.
+fn partial_download_name(host_port: &str, uri: &str) -> String {
+    let hash = sha512_hash(&format!("{}|{}", host_port, uri));
+    format!("download_{}.part", &hash[..32])
+}
+
+/// Receive a chunked transfer into a `.part` file, appending from `resume_from` bytes
+///
+/// The part file is kept if the transfer is interrupted so the next request can resume it.
+/// Once the final chunk arrives its length is checked against `file_size` (a mismatch
+/// discards the part file) and it is renamed to a unique temp file, whose path is returned
+/// along with the stats of this transfer (resumed bytes are not counted).
+///
+/// `cancel` is checked before every chunk: a cancelled transfer removes the part file
+/// and returns `Ok(None)`.
+async fn receive_chunks_to_part_file(
+    stream: &mut StamStream,
+    part_path: &std::path::Path,
+    resume_from: u64,
+    file_name: Option<&str>,
+    file_size: Option<u64>,
+    progress_callback: Option<&stam_mod_runtimes::api::ProgressCallback>,
+    cancel: Option<&CancelToken>,
+) -> Result<Option<(std::path::PathBuf, TransferStats)>, String> {
+    use tokio::io::AsyncWriteExt;
+
+    if let Some(dir) = part_path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    }
+
+    // Append when resuming, otherwise start over (discarding any stale part file)
+    let mut options = tokio::fs::OpenOptions::new();
+    options.create(true);
+    if resume_from > 0 {
+        options.append(true);
+    } else {
+        options.write(true).truncate(true);
+    }
+    let mut part_file = options
+        .open(part_path)
+        .await
+        .map_err(|e| format!("Failed to open part file '{}': {}", part_path.display(), e))?;
+
+    let total_size = file_size.unwrap_or(0);
+    let mut received_bytes = resume_from;
+    let mut chunk_buffer = vec![0u8; UNKNOWN_SIZE_CHUNK_BUFFER_INITIAL];
+    let started = std::time::Instant::now();
+
+    loop {
+        if cancel.is_some_and(|token| token.is_cancelled()) {
+            drop(part_file);
+            let _ = tokio::fs::remove_file(part_path).await;
+            return Ok(None);
+        }
+
+        let (bytes_read, is_final) = stream
+            .read_raw_chunk_growable(&mut chunk_buffer)
+            .await
+            .map_err(|e| format!("Failed to read raw chunk: {}", e))?;
+
+        part_file
+            .write_all(&chunk_buffer[..bytes_read])
+            .await
+            .map_err(|e| format!("Failed to write part file '{}': {}", part_path.display(), e))?;
+        received_bytes += bytes_read as u64;
+
+        if let Some(callback) = progress_callback {
+            let percentage = if total_size > 0 {
+                (received_bytes as f64 / total_size as f64) * 100.0
+            } else {
+                0.0
+            };
+            callback(percentage, received_bytes, total_size);
+            // Yield to allow other tasks to run (UI updates, input handling)
+            tokio::task::yield_now().await;
+        }
+
+        if is_final {
+            debug!("Received final chunk, total {} bytes", received_bytes);
+            break;
+        }
+    }
+
+    part_file
+        .flush()
+        .await
+        .map_err(|e| format!("Failed to flush part file '{}': {}", part_path.display(), e))?;
+    drop(part_file);
+
+    let written = tokio::fs::metadata(part_path)
+        .await
+        .map_err(|e| format!("Failed to stat part file '{}': {}", part_path.display(), e))?
+        .len();
+    if let Some(expected) = file_size
+        && written != expected
+    {
+        let _ = tokio::fs::remove_file(part_path).await;
+        return Err(format!(
+            "Downloaded file has {} bytes but the server announced {} bytes",
+            written, expected
+        ));
+    }
+
+    let temp_path = part_path.with_file_name(generate_temp_filename(file_name));
+    tokio::fs::rename(part_path, &temp_path)
+        .await
+        .map_err(|e| format!("Failed to rename part file '{}': {}", part_path.display(), e))?;
+    Ok(Some((temp_path, TransferStats::new(received_bytes - resume_from, started.elapsed()))))
+}
+
+/// Perform a stam:// (or stams://) URI request and return the response
 ///
-/// This function creates a new TCP connection to the server, performs
-/// the RequestUri protocol exchange, and returns the response.
+/// This function connects to the server (TLS for stams://), or reuses an idle
+/// connection from `pool`, performs the RequestUri protocol exchange, and returns
+/// the response.
 ///
 /// If `tmp_dir` is provided and the response contains file content, the content
 /// will be saved to a temp file and `temp_file_path` will be set in the response.
 /// The `file_content` field will be cleared to avoid memory duplication.
 ///
+/// File downloads into `tmp_dir` are resumable: chunks are written to a `.part`
+/// file named after the URI, and if one is already present its length is sent
+/// as `range_start`. If the server does not honor the range the file is
+/// downloaded again from the start.
+///
+/// At most `max_buffer_bytes` are kept in memory: larger known-size responses
+/// (buffers included) are streamed to a temp file in `tmp_dir` as well.
+///
+/// With `IntentType::ListUri` the server answers a directory URI with a JSON
+/// listing, which is returned parsed in `DownloadResponse::listing`. Listings
+/// are never resumed nor written to `tmp_dir`.
+///
 /// # Arguments
-/// * `uri` - The stam:// URI to request
+/// * `uri` - The stam:// or stams:// URI to request
+/// * `intent_type` - `IntentType::RequestUri` to fetch the resource, `IntentType::ListUri` to list a directory
 /// * `username` - Default username if not in URI
 /// * `password_hash` - Default password hash if not in URI
 /// * `game_id` - The game ID for the request
 /// * `client_version` - Client version string
 /// * `default_server` - Default server address (host:port) to use if URI has no host
+/// * `default_server_tls` - Whether the default server is reached over TLS
 /// * `tmp_dir` - Optional temp directory for saving file downloads
+/// * `max_buffer_bytes` - Largest response kept in memory when a `tmp_dir` is available
 /// * `progress_callback` - Optional callback for progress updates (percentage, received, total)
+/// * `cancel` - Optional token checked between chunks: a cancelled transfer drops the
+///   connection and its partial temp file and returns status `STATUS_CANCELLED`
+/// * `pool` - Optional pool to take the connection from, and to return it to once the
+///   response was read to the end (see `connection_pool`)
+#[allow(clippy::too_many_arguments)]
 async fn perform_stam_request(
     uri: &str,
+    intent_type: IntentType,
     username: &str,
     password_hash: &str,
     game_id: &str,
     client_version: &str,
     default_server: &str,
+    default_server_tls: bool,
     tmp_dir: Option<&std::path::Path>,
+    max_buffer_bytes: u64,
     progress_callback: Option<stam_mod_runtimes::api::ProgressCallback>,
+    cancel: Option<&CancelToken>,
+    pool: Option<&ConnectionPool>,
 ) -> DownloadResponse {
     // Parse the URI to extract host:port
     let (mut host_port, path, uri_username, uri_password) = match parse_stam_uri(uri) {
@@ -100,13 +338,18 @@ async fn perform_stam_request(
                 file_name: None,
                 file_content: None,
                 temp_file_path: None,
+                is_binary: false,
+                transfer_stats: TransferStats::default(),
+                listing: None,
             };
         }
     };
 
-    // If URI has no host, use the default server
+    // If URI has no host, use the default server (and its transport)
+    let mut tls = is_secure_stam_uri(uri);
     if host_port.is_empty() {
         host_port = default_server.to_string();
+        tls |= default_server_tls;
     }
 
     // Use credentials from URI if provided, otherwise use default
@@ -120,79 +363,95 @@ async fn perform_stam_request(
     // Sanitize URI (remove credentials) for sending to server
     let sanitized_uri = sanitize_uri(uri);
 
-    debug!("Performing stam:// request: host={}, path={}", host_port, path);
+    let listing = matches!(intent_type, IntentType::ListUri);
+    let tmp_dir = tmp_dir.filter(|_| !listing);
 
-    // Connect to server
-    let mut stream = match TcpStream::connect(&host_port).await {
-        Ok(s) => s,
-        Err(e) => {
-            error!("Failed to connect to {}: {}", host_port, e);
-            return DownloadResponse {
-                status: 503, // Service Unavailable
-                buffer_string: None,
-                file_name: None,
-                file_content: None,
-                temp_file_path: None,
-            };
-        }
-    };
+    // Look for a partial download of this URI left behind by an interrupted transfer
+    let part_path = tmp_dir.map(|dir| dir.join(partial_download_name(&host_port, &sanitized_uri)));
+    let part_len = part_path
+        .as_ref()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .filter(|len| *len > 0);
+
+    debug!("Performing stam:// request: host={}, path={}, tls={}", host_port, path, tls);
+
+    // Reuse a pooled connection when possible. A pooled connection the server has
+    // closed in the meantime fails on the first exchange: retry once on a new one.
+    let pool_key = ConnectionPool::key(&host_port, tls);
+    let mut pooled = pool.and_then(|pool| pool.take(&pool_key));
+    let (mut stream, header) = loop {
+        let reused = pooled.is_some();
+        let (connection, compression) = match pooled.take() {
+            Some(connection) => connection,
+            None => match open_uri_connection(&host_port, tls, pool).await {
+                Ok(connection) => connection,
+                Err(status) => return DownloadResponse { status, ..Default::default() },
+            },
+        };
+        let mut stream = PooledStream::new(connection, compression, pool.map(|pool| (pool.clone(), pool_key.clone())));
+
+        // Send RequestUri Intent
+        let intent = PrimalMessage::Intent {
+            intent_type: intent_type.clone(),
+            client_version: client_version.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            username: effective_username.to_string(),
+            password_hash: effective_password_hash.clone(),
+            game_id: Some(game_id.to_string()),
+            uri: Some(sanitized_uri.clone()),
+            compression,
+            range_start: part_len,
+        };
 
-    // Read Welcome message
-    match stream.read_primal_message().await {
-        Ok(PrimalMessage::Welcome { version: _ }) => {
-            // Version check could be done here, but for now we just proceed
-        }
-        Ok(msg) => {
-            error!("Unexpected message during RequestUri: {:?}", msg);
-            return DownloadResponse {
-                status: 500,
-                buffer_string: None,
-                file_name: None,
-                file_content: None,
-                temp_file_path: None,
-            };
-        }
-        Err(e) => {
-            error!("Failed to read Welcome during RequestUri: {}", e);
+        if let Err(e) = stream.write_primal_message(&intent).await {
+            if reused {
+                debug!("Pooled connection to {} is no longer usable ({}), reconnecting", host_port, e);
+                continue;
+            }
+            error!("Failed to send RequestUri Intent: {}", e);
             return DownloadResponse {
                 status: 500,
                 buffer_string: None,
                 file_name: None,
                 file_content: None,
                 temp_file_path: None,
+                is_binary: false,
+                transfer_stats: TransferStats::default(),
+                listing: None,
             };
         }
-    }
 
-    // Send RequestUri Intent
-    let intent = PrimalMessage::Intent {
-        intent_type: IntentType::RequestUri,
-        client_version: client_version.to_string(),
-        username: effective_username.to_string(),
-        password_hash: effective_password_hash,
-        game_id: Some(game_id.to_string()),
-        uri: Some(sanitized_uri),
+        // Wait for UriResponse header
+        match stream.read_primal_message().await {
+            Err(e) if reused => {
+                debug!("Pooled connection to {} is no longer usable ({}), reconnecting", host_port, e);
+            }
+            header => break (stream, header),
+        }
     };
 
-    if let Err(e) = stream.write_primal_message(&intent).await {
-        error!("Failed to send RequestUri Intent: {}", e);
-        return DownloadResponse {
-            status: 500,
-            buffer_string: None,
-            file_name: None,
-            file_content: None,
-            temp_file_path: None,
-        };
-    }
+    match header {
+        Ok(PrimalMessage::UriResponse { status, buffer_string, file_name, file_size, range_start }) => {
+            debug!("Received UriResponse: status={}, file_name={:?}, file_size={:?}, range_start={:?}, buffer_string_len={:?}",
+                status, file_name, file_size, range_start, buffer_string.as_ref().map(|s| s.len()));
 
-    // Wait for UriResponse header
-    match stream.read_primal_message().await {
-        Ok(PrimalMessage::UriResponse { status, buffer_string, file_name, file_size }) => {
-            debug!("Received UriResponse: status={}, file_name={:?}, file_size={:?}, buffer_string_len={:?}",
-                status, file_name, file_size, buffer_string.as_ref().map(|s| s.len()));
+            if listing {
+                stream.release();
+                return listing_response(status, buffer_string);
+            }
+
+            // Without buffer, file name nor size the response has no body (e.g. a 404)
+            if buffer_string.is_none() && file_name.is_none() && file_size.is_none() {
+                stream.release();
+                return DownloadResponse { status, ..Default::default() };
+            }
 
             // If buffer_string is present in response, this is a non-chunked transfer (small data or simple response)
             if let Some(content_string) = buffer_string {
+                // The whole response came in this message
+                stream.release();
+
                 // Convert string to bytes for file operations
                 let content_bytes = content_string.as_bytes().to_vec();
 
@@ -212,6 +471,9 @@ async fn perform_stam_request(
                                 file_name,
                                 file_content: Some(content_bytes.clone()),
                                 temp_file_path: None,
+                                is_binary: false,
+                                transfer_stats: TransferStats::default(),
+                                listing: None,
                             };
                         }
                     }
@@ -224,6 +486,9 @@ async fn perform_stam_request(
                                 file_name,
                                 file_content: None,
                                 temp_file_path: Some(temp_path.to_string_lossy().to_string()),
+                                is_binary: false,
+                                transfer_stats: TransferStats::default(),
+                                listing: None,
                             };
                         }
                         Err(e) => {
@@ -234,6 +499,9 @@ async fn perform_stam_request(
                                 file_name,
                                 file_content: Some(content_bytes),
                                 temp_file_path: None,
+                                is_binary: false,
+                                transfer_stats: TransferStats::default(),
+                                listing: None,
                             };
                         }
                     }
@@ -245,6 +513,9 @@ async fn perform_stam_request(
                         file_name,
                         file_content: Some(content_bytes),
                         temp_file_path: None,
+                        is_binary: false,
+                        transfer_stats: TransferStats::default(),
+                        listing: None,
                     };
                 } else {
                     // Return as buffer_string
@@ -254,14 +525,105 @@ async fn perform_stam_request(
                         file_name: None,
                         file_content: None,
                         temp_file_path: None,
+                        is_binary: false,
+                        transfer_stats: TransferStats::default(),
+                        listing: None,
                     };
                 }
             }
 
+            // File downloads into tmp_dir go through a resumable .part file, and so do
+            // responses too large to keep in memory
+            let exceeds_buffer = file_size.is_some_and(|size| size > max_buffer_bytes);
+            if let Some(ref part_path) = part_path
+                && (file_name.is_some() || exceeds_buffer)
+                && file_size != Some(0)
+            {
+                // Only append if the server resumed exactly where our part file ends
+                let resume_from = match (part_len, range_start) {
+                    (Some(len), Some(start)) if len == start => len,
+                    (Some(len), _) => {
+                        debug!("Server ignored range_start {} for '{}', downloading from the start", len, part_path.display());
+                        0
+                    }
+                    (None, _) => 0,
+                };
+
+                return match receive_chunks_to_part_file(
+                    &mut stream,
+                    part_path,
+                    resume_from,
+                    file_name.as_deref(),
+                    file_size,
+                    progress_callback.as_ref(),
+                    cancel,
+                )
+                .await
+                {
+                    Ok(None) => {
+                        debug!("Download of '{}' cancelled", sanitize_uri(uri));
+                        DownloadResponse { status: STATUS_CANCELLED, ..Default::default() }
+                    }
+                    Ok(Some((temp_path, transfer_stats))) => {
+                        stream.release();
+                        DownloadResponse {
+                            status,
+                            buffer_string: None,
+                            file_name,
+                            file_content: None,
+                            temp_file_path: Some(temp_path.to_string_lossy().to_string()),
+                            is_binary: false,
+                            transfer_stats,
+                            listing: None,
+                        }
+                    }
+                    Err(e) => {
+                        error!("{}", e);
+                        DownloadResponse {
+                            status: 500,
+                            buffer_string: None,
+                            file_name: None,
+                            file_content: None,
+                            temp_file_path: None,
+                            is_binary: false,
+                            transfer_stats: TransferStats::default(),
+                            listing: None,
+                        }
+                    }
+                };
+            }
+
             // No buffer means chunked transfer - read chunks until final
-            // Use raw chunk reading for zero-copy performance
+            // Use raw chunk reading for zero-copy performance. Gzip chunks are inflated by
+            // the stream, so byte counts (and progress) always refer to uncompressed data.
             let total_size = file_size.unwrap_or(0);
             let mut received_bytes: u64 = 0;
+            let started = std::time::Instant::now();
+            // Never pre-allocate whatever size the peer claims: past the stream's frame
+            // limit (and the buffer limit) the response must go through a part file
+            let memory_limit = max_buffer_bytes.max(stream.max_message_bytes() as u64);
+            if total_size > memory_limit {
+                error!(
+                    "Refusing to buffer a {} byte response in memory (limit {} bytes): no temp directory available",
+                    total_size, memory_limit
+                );
+                return DownloadResponse {
+                    status: 413,
+                    buffer_string: None,
+                    file_name: None,
+                    file_content: None,
+                    temp_file_path: None,
+                    is_binary: false,
+                    transfer_stats: TransferStats::default(),
+                    listing: None,
+                };
+            }
+            if exceeds_buffer {
+                warn!(
+                    "Buffering {} bytes in memory (more than the {} byte limit): no temp directory available",
+                    total_size, max_buffer_bytes
+                );
+            }
 
             // Pre-allocate the final buffer with exact size (or grow as needed)
             let mut all_data: Vec<u8> = if total_size > 0 {
@@ -270,80 +632,107 @@ async fn perform_stam_request(
                 Vec::new()
             };
 
-            // Temporary chunk buffer for unknown-size transfers
-            let mut chunk_buffer: Vec<u8> = if total_size == 0 {
-                vec![0u8; 16 * 1024 * 1024]  // 16MB default chunk buffer
+            // A zero-length file has no chunks at all: the server only sends the header,
+            // so there is nothing to read (and no final flag to wait for)
+            let is_empty_file = file_size == Some(0);
+
+            // Temporary chunk buffer for unknown-size transfers (grown on demand)
+            let mut chunk_buffer = unknown_size_chunk_buffer(file_size);
+
+            if is_empty_file {
+                debug!("Received empty file, skipping chunk transfer");
+                if let Some(ref callback) = progress_callback {
+                    callback(100.0, 0, 0);
+                }
             } else {
-                Vec::new()
-            };
+                loop {
+                    // Checked between chunks; returning drops the connection and the buffer
+                    if cancel.is_some_and(|token| token.is_cancelled()) {
+                        debug!("Download of '{}' cancelled", sanitize_uri(uri));
+                        return DownloadResponse { status: STATUS_CANCELLED, ..Default::default() };
+                    }
 
-            loop {
-                if total_size > 0 {
-                    // Known size: read directly into final buffer position
-                    let offset = received_bytes as usize;
-
-                    match stream.read_raw_chunk(&mut all_data[offset..]).await {
-                        Ok((bytes_read, is_final)) => {
-                            received_bytes += bytes_read as u64;
-
-                            // Call progress callback if provided
-                            if let Some(ref callback) = progress_callback {
-                                let percentage = (received_bytes as f64 / total_size as f64) * 100.0;
-                                callback(percentage, received_bytes, total_size);
-                                // Yield to allow other tasks to run (UI updates, input handling)
-                                tokio::task::yield_now().await;
-                            }
+                    if total_size > 0 {
+                        // Known size: read directly into final buffer position
+                        let offset = received_bytes as usize;
 
-                            if is_final {
-                                // Truncate to actual size received
-                                all_data.truncate(received_bytes as usize);
-                                debug!("Received final chunk, total {} bytes", received_bytes);
-                                break;
+                        match stream.read_raw_chunk(&mut all_data[offset..]).await {
+                            Ok((bytes_read, is_final)) => {
+                                received_bytes += bytes_read as u64;
+
+                                // Call progress callback if provided
+                                if let Some(ref callback) = progress_callback {
+                                    let percentage = (received_bytes as f64 / total_size as f64) * 100.0;
+                                    callback(percentage, received_bytes, total_size);
+                                    // Yield to allow other tasks to run (UI updates, input handling)
+                                    tokio::task::yield_now().await;
+                                }
+
+                                if is_final {
+                                    // Truncate to actual size received
+                                    all_data.truncate(received_bytes as usize);
+                                    debug!("Received final chunk, total {} bytes", received_bytes);
+                                    break;
+                                }
                             }
-                        }
-                        Err(e) => {
-                            error!("Failed to read raw chunk: {}", e);
-                            return DownloadResponse {
-                                status: 500,
-                                buffer_string: None,
-                                file_name: None,
-                                file_content: None,
-                                temp_file_path: None,
-                            };
-                        }
-                    }
-                } else {
-                    // Unknown size: read into temp buffer then append
-                    match stream.read_raw_chunk(&mut chunk_buffer).await {
-                        Ok((bytes_read, is_final)) => {
-                            received_bytes += bytes_read as u64;
-                            all_data.extend_from_slice(&chunk_buffer[..bytes_read]);
-
-                            // Call progress callback if provided
-                            if let Some(ref callback) = progress_callback {
-                                callback(0.0, received_bytes, 0);
-                                tokio::task::yield_now().await;
+                            Err(e) => {
+                                error!("Failed to read raw chunk: {}", e);
+                                return DownloadResponse {
+                                    status: 500,
+                                    buffer_string: None,
+                                    file_name: None,
+                                    file_content: None,
+                                    temp_file_path: None,
+                                    is_binary: false,
+                                    transfer_stats: TransferStats::default(),
+                                    listing: None,
+                                };
                             }
+                        }
+                    } else {
+                        // Unknown size: read into temp buffer then append
+                        match stream.read_raw_chunk_growable(&mut chunk_buffer).await {
+                            Ok((bytes_read, is_final)) => {
+                                received_bytes += bytes_read as u64;
+                                all_data.extend_from_slice(&chunk_buffer[..bytes_read]);
+
+                                // Call progress callback if provided
+                                if let Some(ref callback) = progress_callback {
+                                    callback(0.0, received_bytes, 0);
+                                    tokio::task::yield_now().await;
+                                }
 
-                            if is_final {
-                                debug!("Received final chunk, total {} bytes", received_bytes);
-                                break;
+                                if is_final {
+                                    debug!("Received final chunk, total {} bytes", received_bytes);
+                                    break;
+                                }
+                            }
+                            Err(e) => {
+                                error!("Failed to read raw chunk: {}", e);
+                                return DownloadResponse {
+                                    status: 500,
+                                    buffer_string: None,
+                                    file_name: None,
+                                    file_content: None,
+                                    temp_file_path: None,
+                                    is_binary: false,
+                                    transfer_stats: TransferStats::default(),
+                                    listing: None,
+                                };
                             }
-                        }
-                        Err(e) => {
-                            error!("Failed to read raw chunk: {}", e);
-                            return DownloadResponse {
-                                status: 500,
-                                buffer_string: None,
-                                file_name: None,
-                                file_content: None,
-                                temp_file_path: None,
-                            };
                         }
                     }
                 }
             }
 
+            // Every chunk up to the final one was read
+            stream.release();
+            let transfer_stats = TransferStats::new(received_bytes, started.elapsed());
+            debug!(
+                "Transferred {} bytes in {:?} ({:.0} B/s)",
+                transfer_stats.bytes_received, transfer_stats.elapsed, transfer_stats.bytes_per_sec
+            );
+
             // Save to temp file if tmp_dir is provided and file_name is present
             if file_name.is_some() && tmp_dir.is_some() {
                 let tmp_dir = tmp_dir.unwrap();
@@ -359,6 +748,9 @@ async fn perform_stam_request(
                             file_name,
                             file_content: Some(all_data),
                             temp_file_path: None,
+                            is_binary: false,
+                            transfer_stats,
+                            listing: None,
                         };
                     }
                 }
@@ -371,6 +763,9 @@ async fn perform_stam_request(
                             file_name,
                             file_content: None,
                             temp_file_path: Some(temp_path.to_string_lossy().to_string()),
+                            is_binary: false,
+                            transfer_stats,
+                            listing: None,
                         }
                     }
                     Err(e) => {
@@ -381,6 +776,9 @@ async fn perform_stam_request(
                             file_name,
                             file_content: Some(all_data),
                             temp_file_path: None,
+                            is_binary: false,
+                            transfer_stats,
+                            listing: None,
                         }
                     }
                 }
@@ -392,16 +790,14 @@ async fn perform_stam_request(
                     file_name,
                     file_content: Some(all_data),
                     temp_file_path: None,
+                    is_binary: false,
+                    transfer_stats,
+                    listing: None,
                 }
             } else {
-                // Return as buffer_string (convert bytes to UTF-8 string)
-                let buffer_str = String::from_utf8_lossy(&all_data).to_string();
                 DownloadResponse {
-                    status,
-                    buffer_string: Some(buffer_str),
-                    file_name: None,
-                    file_content: None,
-                    temp_file_path: None,
+                    transfer_stats,
+                    ..buffer_response(status, all_data)
                 }
             }
         }
@@ -413,6 +809,9 @@ async fn perform_stam_request(
                 file_name: None,
                 file_content: None,
                 temp_file_path: None,
+                is_binary: false,
+                transfer_stats: TransferStats::default(),
+                listing: None,
             }
         }
         Ok(msg) => {
@@ -423,6 +822,9 @@ async fn perform_stam_request(
                 file_name: None,
                 file_content: None,
                 temp_file_path: None,
+                is_binary: false,
+                transfer_stats: TransferStats::default(),
+                listing: None,
             }
         }
         Err(e) => {
@@ -433,11 +835,96 @@ async fn perform_stam_request(
                 file_name: None,
                 file_content: None,
                 temp_file_path: None,
+                is_binary: false,
+                transfer_stats: TransferStats::default(),
+                listing: None,
+            }
+        }
+    }
+}
+
+/// Build the response for a buffer (a body without file name)
+///
+/// Valid UTF-8 is returned as `buffer_string`; anything else is returned raw in
+/// `file_content` with `is_binary` set, so no bytes are lost to lossy decoding.
+fn buffer_response(status: u16, data: Vec<u8>) -> DownloadResponse {
+    match String::from_utf8(data) {
+        Ok(text) => DownloadResponse {
+            status,
+            buffer_string: Some(text),
+            ..Default::default()
+        },
+        Err(e) => {
+            debug!("Buffer response is not valid UTF-8, returning it as binary");
+            DownloadResponse {
+                status,
+                file_content: Some(e.into_bytes()),
+                is_binary: true,
+                ..Default::default()
             }
         }
     }
 }
 
+/// Connect to `host_port` for a URI request and read the server's Welcome
+///
+/// # Returns
+/// The connection and the chunk compression to request, or the status to answer with
+async fn open_uri_connection(
+    host_port: &str,
+    tls: bool,
+    pool: Option<&ConnectionPool>,
+) -> Result<(StamStream, Option<Compression>), u16> {
+    let started = std::time::Instant::now();
+    let mut stream = match StamStream::connect(host_port, tls).await {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to connect to {}: {}", host_port, e);
+            return Err(503); // Service Unavailable
+        }
+    };
+
+    // Read Welcome message
+    let compression = match stream.read_primal_message().await {
+        Ok(PrimalMessage::Welcome { compression, .. }) => {
+            // The server rejects the Intent if the protocol versions differ.
+            // Request whatever algorithm the server offers; None keeps chunks uncompressed.
+            compression
+        }
+        Ok(msg) => {
+            error!("Unexpected message during RequestUri: {:?}", msg);
+            return Err(500);
+        }
+        Err(e) => {
+            error!("Failed to read Welcome during RequestUri: {}", e);
+            return Err(500);
+        }
+    };
+
+    if let Some(pool) = pool {
+        pool.record_handshake(started.elapsed());
+    }
+    Ok((stream, compression))
+}
+
+/// Build the response to a `ListUri` request from the JSON listing sent by the server
+fn listing_response(status: u16, body: Option<String>) -> DownloadResponse {
+    if status != 200 {
+        return DownloadResponse { status, ..Default::default() };
+    }
+    match serde_json::from_str::<Vec<UriListEntry>>(body.as_deref().unwrap_or_default()) {
+        Ok(entries) => DownloadResponse {
+            status,
+            listing: Some(entries),
+            ..Default::default()
+        },
+        Err(e) => {
+            error!("Invalid directory listing from server: {}", e);
+            DownloadResponse { status: 502, ..Default::default() }
+        }
+    }
+}
+
 /// Generate a unique temp filename with optional extension from original name
 fn generate_temp_filename(original_name: Option<&str>) -> String {
     let timestamp = std::time::SystemTime::now()
@@ -466,9 +953,18 @@ async fn connect_to_game_server(
     locale: Arc<LocaleManager>,
     app_paths: &AppPaths,
     engine_request_tx: std_mpsc::Sender<EnableEngineRequest>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Parse game server URI (stam://host:port)
-    if !uri.starts_with("stam://") {
+    reconnect_policy: Option<ReconnectPolicy>,
+    watch: bool,
+    network_cache: bool,
+    validate_only: bool,
+    mod_cache: Option<ModArchiveCache>,
+    offline: bool,
+    connection_pool: Option<ConnectionPool>,
+    mods_dir_override: Option<std::path::PathBuf>,
+) -> Result<i32, Box<dyn std::error::Error>> {
+    // Parse game server URI (stam://host:port or stams://host:port for TLS)
+    let tls = is_secure_stam_uri(uri);
+    let Some(host_port) = uri.strip_prefix(STAM_SCHEME).or_else(|| uri.strip_prefix(STAMS_SCHEME)) else {
         return Err(locale
             .get_with_args(
                 "error-invalid-uri",
@@ -477,9 +973,7 @@ async fn connect_to_game_server(
                 }),
             )
             .into());
-    }
-
-    let host_port = uri.strip_prefix("stam://").unwrap();
+    };
 
     // Extract host (without port) for directory naming (will be used after LoginSuccess)
     let host = host_port.split(':').next().unwrap_or(host_port).to_string();
@@ -494,15 +988,19 @@ async fn connect_to_game_server(
         )
     );
 
+    // Time each startup phase so slow cold starts can be broken down
+    let startup_timings = StartupTimings::new();
+
     // Connect to game server
-    let mut stream = TcpStream::connect(host_port).await?;
+    let connect_timer = startup_timings.begin("connect");
+    let mut stream = StamStream::connect(host_port, tls).await?;
     info!("{}", locale.get("game-connected"));
 
     // Read Welcome message
     let mut server_version = String::new();
 
     match stream.read_primal_message().await {
-        Ok(PrimalMessage::Welcome { version }) => {
+        Ok(PrimalMessage::Welcome { version, protocol_version, .. }) => {
             info!(
                 "{}",
                 locale.get_with_args(
@@ -513,40 +1011,9 @@ async fn connect_to_game_server(
                 )
             );
 
-            // Check version compatibility
-            let client_version_parts: Vec<&str> = VERSION.split('.').collect();
-            let server_version_parts: Vec<&str> = version.split('.').collect();
-
-            if client_version_parts.len() >= 2 && server_version_parts.len() >= 2 {
-                let client_major_minor =
-                    format!("{}.{}", client_version_parts[0], client_version_parts[1]);
-                let server_major_minor =
-                    format!("{}.{}", server_version_parts[0], server_version_parts[1]);
-
-                if client_major_minor != server_major_minor {
-                    error!(
-                        "{}",
-                        locale.get_with_args(
-                            "version-mismatch",
-                            Some(&fluent_args! {
-                                "client" => VERSION,
-                                "server" => version.as_str()
-                            })
-                        )
-                    );
-                    return Err(locale.get("disconnect-version-mismatch").into());
-                }
-
-                info!(
-                    "{}",
-                    locale.get_with_args(
-                        "version-compatible",
-                        Some(&fluent_args! {
-                            "client" => VERSION,
-                            "server" => version.as_str()
-                        })
-                    )
-                );
+            // Check protocol compatibility (the app versions may differ)
+            if check_server_compatibility(&locale, &version, protocol_version).is_err() {
+                return Err(locale.get("disconnect-version-mismatch").into());
             }
 
             server_version = version;
@@ -560,18 +1027,23 @@ async fn connect_to_game_server(
             return Err(e.into());
         }
     }
+    connect_timer.finish();
 
     // Send GameLogin Intent
+    let login_timer = startup_timings.begin("login");
     info!("{}", locale.get("login-sending"));
     let password_hash = sha512_hash(password);
 
     let intent = PrimalMessage::Intent {
         intent_type: IntentType::GameLogin,
         client_version: VERSION.to_string(),
+        protocol_version: PROTOCOL_VERSION,
         username: username.to_string(),
         password_hash: password_hash.clone(),
         game_id: Some(game_id.to_string()),
         uri: None,
+        compression: None,
+        range_start: None,
     };
 
     stream.write_primal_message(&intent).await?;
@@ -582,15 +1054,21 @@ async fn connect_to_game_server(
     // Runtime manager and system API for dynamic mod loading
     let mut runtime_manager_opt: Option<ModRuntimeManager> = None;
     let mut system_api_opt: Option<stam_mod_runtimes::api::SystemApi> = None;
+    let mut capability_policy_opt: Option<stam_mod_runtimes::api::CapabilityPolicy> = None;
     // Graphic proxy for polling graphic engine events
     let mut graphic_proxy_opt: Option<Arc<GraphicProxy>> = None;
     // Resource proxy for resource loading queue processing
     let mut resource_proxy_opt: Option<Arc<stam_mod_runtimes::api::ResourceProxy>> = None;
-    // Game root directory for resolving mod paths
-    let mut game_root_opt: Option<std::path::PathBuf> = None;
+    // Mod directories (game mods dir and --mods-dir override) for resolving mod paths
+    let mut mod_dirs_opt: Option<ModDirs> = None;
+    // Mods loaded for this session, diffed against the mod list after a reconnect
+    let mut loaded_mod_set = mod_set::LoadedModSet::default();
+    // Entry points of the attached mods, watched for changes with --watch
+    let mut attached_entry_points: Vec<(String, Vec<std::path::PathBuf>)> = Vec::new();
 
     match stream.read_game_message().await {
         Ok(GameMessage::LoginSuccess { server_name, game_name, game_version, mods }) => {
+            login_timer.finish();
             info!("{} {} [{}] on {}", locale.get("game-login-success"), game_name, game_version, server_name);
             let active_game_version = game_version.clone();
 
@@ -598,10 +1076,14 @@ async fn connect_to_game_server(
             debug!("Initializing directories for game '{}' on server '{}' ({})...", game_id, server_name, host);
             let game_root = app_paths.game_root(&host, &server_name, game_id)?;
             let mods_dir = game_root.join("mods");
+            let mod_dirs = ModDirs::new(&mods_dir, mods_dir_override);
 
             debug!("Game directories:");
             debug!("  Root: {}", game_root.display());
             debug!("  Mods: {}", mods_dir.display());
+            if let Some(override_dir) = mod_dirs.override_dir() {
+                info!("Mods in {} override the installed ones (--mods-dir)", override_dir.display());
+            }
 
             // Log mod list received
             if !mods.is_empty() {
@@ -614,44 +1096,40 @@ async fn connect_to_game_server(
             } else {
                 debug!("No mods required for this game");
             }
+            if let Err(e) = validate_server_mod_ids(mods.iter().map(|m| m.mod_id.as_str())) {
+                error!("FATAL: {}", e);
+                return Err(e.into());
+            }
 
             // Load manifests only for mods that are present locally
             // Missing mods are tracked separately - we only fail if a required mod is missing
             // Tuple stores (manifest, actual_mod_dir) since mod_dir might be in client/ subdirectory
             let mut available_manifests: HashMap<String, (ModManifest, std::path::PathBuf)> = HashMap::new();
             let mut missing_mods: Vec<String> = Vec::new();
+            // Progress lines and summary shown instead of the per-mod lines at INFO
+            let mut progress = ModLoadProgress::new();
 
             if !mods.is_empty() {
                 debug!("Server requires {} mod(s), checking local availability...", mods.len());
+                progress.begin(LoadPhase::Manifests, mods.len());
 
                 // First pass: load manifests for available mods, track missing ones
                 for mod_info in &mods {
-                    let mod_dir = mods_dir.join(&mod_info.mod_id);
-
-                    // Check if mod directory exists
-                    if !mod_dir.exists() {
-                        debug!("Mod '{}' not found locally", mod_info.mod_id);
-                        missing_mods.push(mod_info.mod_id.clone());
-                        continue;
-                    }
-
-                    // Read manifest - check client/ subdirectory first, then root
-                    // If manifest is in client/, use that as the mod_dir for entry_point resolution
-                    let client_dir = mod_dir.join("client");
-                    let client_manifest_path = client_dir.join("manifest.json");
-                    let root_manifest_path = mod_dir.join("manifest.json");
-
-                    let (actual_mod_dir, manifest_path) = if client_manifest_path.exists() {
-                        (client_dir, client_manifest_path)
-                    } else if root_manifest_path.exists() {
-                        (mod_dir.clone(), root_manifest_path)
-                    } else {
-                        warn!("Mod '{}' directory exists but missing manifest.json (checked client/ and root)", mod_info.mod_id);
+                    progress.advance();
+                    // Read manifest - override dir first, then the game mods dir; in each,
+                    // client/ subdirectory first, then root. If manifest is in client/,
+                    // that is the directory for entry_point resolution
+                    let Some(location) = mod_dirs.find(&mod_info.mod_id) else {
+                        if mod_dirs.has_dir(&mod_info.mod_id) {
+                            warn!("Mod '{}' directory exists but missing manifest.json (checked client/ and root)", mod_info.mod_id);
+                        } else {
+                            debug!("Mod '{}' not found locally", mod_info.mod_id);
+                        }
                         missing_mods.push(mod_info.mod_id.clone());
                         continue;
                     };
 
-                    let manifest = match ModManifest::from_json_file(manifest_path.to_str().unwrap()) {
+                    let manifest = match ModManifest::from_json_file(&location.manifest_path) {
                         Ok(m) => m,
                         Err(e) => {
                             warn!("Failed to load manifest for mod '{}': {}", mod_info.mod_id, e);
@@ -660,9 +1138,12 @@ async fn connect_to_game_server(
                         }
                     };
 
+                    if location.overridden {
+                        info!("Mod '{}' loaded from {}", mod_info.mod_id, location.dir.display());
+                    }
                     debug!(" ✓ {} [{}:{}] found (from {})", mod_info.mod_id, mod_info.mod_type, manifest.version,
-                        if actual_mod_dir != mod_dir { "client/" } else { "root" });
-                    available_manifests.insert(mod_info.mod_id.clone(), (manifest, actual_mod_dir));
+                        location.describe());
+                    available_manifests.insert(mod_info.mod_id.clone(), (manifest, location.dir));
                 }
 
                 if !missing_mods.is_empty() {
@@ -679,7 +1160,10 @@ async fn connect_to_game_server(
                 .filter(|m| m.mod_type == "bootstrap")
                 .collect();
 
-            if !required_bootstrap_mods.is_empty() {
+            // With --validate every mod is downloaded, so all manifests can be checked
+            if validate_only || !required_bootstrap_mods.is_empty() {
+                let _download_timer = startup_timings.begin("download");
+
                 // Get tmp directory for downloads (once, outside the loop)
                 // Use game-specific tmp directory: data_dir/{game_id}/tmp
                 let tmp_dir = game_root.join("tmp");
@@ -707,6 +1191,8 @@ async fn connect_to_game_server(
                 }
 
                 // Keep downloading until all dependencies are satisfied
+                // Each mod is downloaded at most once, even if its manifest turns out unreadable
+                let mut downloaded_mods: std::collections::HashSet<String> = std::collections::HashSet::new();
                 let mut download_iteration = 0;
                 const MAX_DOWNLOAD_ITERATIONS: u32 = 100; // Safety limit to prevent infinite loops
 
@@ -718,51 +1204,23 @@ async fn connect_to_game_server(
                     }
 
                     // Calculate all mods needed for bootstrap (bootstrap mods + their dependencies recursively)
-                    // We need to use manifests from available mods to calculate dependencies
-                    fn collect_required_mods_recursive(
-                        mod_id: &str,
-                        available_manifests: &HashMap<String, (ModManifest, std::path::PathBuf)>,
-                        all_mods: &[stam_protocol::ModInfo],
-                        required: &mut Vec<String>,
-                        chain: &mut Vec<String>,
-                    ) {
-                        // Avoid infinite loops
-                        if chain.contains(&mod_id.to_string()) || required.contains(&mod_id.to_string()) {
-                            return;
-                        }
-                        chain.push(mod_id.to_string());
-
-                        // If mod is available locally, check its dependencies from manifest
-                        if let Some((manifest, _)) = available_manifests.get(mod_id) {
-                            for (dep_id, _) in &manifest.requires {
-                                if !dep_id.starts_with('@') {
-                                    collect_required_mods_recursive(dep_id, available_manifests, all_mods, required, chain);
-                                }
-                            }
-                        }
-                        // Note: if mod is NOT available locally, we can't know its dependencies yet
-                        // They will be resolved after download
-
-                        chain.pop();
-                        required.push(mod_id.to_string());
-                    }
-
-                    let mut mods_required_for_bootstrap: Vec<String> = Vec::new();
-                    let mut chain: Vec<String> = Vec::new();
-
-                    for bootstrap_mod in &required_bootstrap_mods {
-                        collect_required_mods_recursive(
-                            &bootstrap_mod.mod_id,
-                            &available_manifests,
-                            &mods,
-                            &mut mods_required_for_bootstrap,
-                            &mut chain,
-                        );
-                    }
+                    // We need to use manifests from available mods to calculate dependencies;
+                    // mods not available locally get their dependencies resolved after download
+                    let mods_required_for_bootstrap = if validate_only {
+                        // Dependency cycles are reported by the validation instead of aborting here
+                        mods.iter().map(|m| m.mod_id.clone()).collect()
+                    } else {
+                        let bootstrap_ids: Vec<&str> = required_bootstrap_mods.iter().map(|m| m.mod_id.as_str()).collect();
+                        mod_deps::collect_required_mods(&bootstrap_ids, |mod_id| {
+                            available_manifests.get(mod_id).map(|(manifest, _)| manifest)
+                        })
+                        .inspect_err(|e| error!("FATAL: {}", e))?
+                    };
 
                     // Find which of the required mods are missing
                     let mods_to_download: Vec<&stam_protocol::ModInfo> = mods_required_for_bootstrap.iter()
                         .filter(|mod_id| !available_manifests.contains_key(*mod_id))
+                        .filter(|mod_id| !downloaded_mods.contains(*mod_id))
                         .filter_map(|mod_id| mods.iter().find(|m| &m.mod_id == mod_id))
                         .collect();
 
@@ -770,53 +1228,108 @@ async fn connect_to_game_server(
                     if mods_to_download.is_empty() {
                         if download_iteration > 1 {
                             debug!("All bootstrap mods and dependencies downloaded after {} iteration(s)", download_iteration - 1);
+                            if let Some(pool) = &connection_pool {
+                                let stats = pool.stats();
+                                debug!(
+                                    "Mod downloads opened {} connection(s) ({:?} of handshakes) and reused {} (~{:?} saved)",
+                                    stats.handshakes, stats.handshake_time, stats.reused, stats.estimated_savings()
+                                );
+                            }
                         }
                         break;
                     }
 
+                    // Offline mode only runs what is installed: report everything missing at once
+                    if offline {
+                        let missing: Vec<&str> = mods_to_download.iter().map(|m| m.mod_id.as_str()).collect();
+                        error!("FATAL: Offline mode and required mods are not installed: {}", missing.join(", "));
+                        return Err(format!(
+                            "Cannot download mods in offline mode, missing required mods: {}",
+                            missing.join(", ")
+                        ).into());
+                    }
+
                     debug!("[Iteration {}] Need to download {} mod(s) for bootstrap: {:?}",
                         download_iteration,
                         mods_to_download.len(),
                         mods_to_download.iter().map(|m| &m.mod_id).collect::<Vec<_>>());
+                    progress.begin(LoadPhase::Download, mods_to_download.len());
 
                     // Download each missing mod
                     for mod_info in &mods_to_download {
-                        if mod_info.download_url.is_empty() {
-                            error!("FATAL: Mod '{}' has no download URL", mod_info.mod_id);
-                            return Err(format!(
-                                "Cannot download mod '{}': no download URL provided by server",
-                                mod_info.mod_id
-                            ).into());
-                        }
-
-                        debug!("Downloading mod '{}' from {}...", mod_info.mod_id, mod_info.download_url);
+                        // Reuse an identical archive from the shared cache when possible
+                        let cached_archive = mod_cache.as_ref().and_then(|cache| {
+                            let cached_path = tmp_dir.join(format!("{}.cached.tar.gz", mod_info.mod_id));
+                            cache.fetch(&mod_info.archive_sha512, &cached_path).then_some(cached_path)
+                        });
 
-                        let response = perform_stam_request(
-                            &mod_info.download_url,
-                            &username,
-                            &password_hash,
-                            game_id,
-                            VERSION,
-                            host_port,
-                            Some(&tmp_dir),
-                            None, // No progress callback for initial mod download
-                        ).await;
+                        let (archive_path, archive_filename) = if let Some(archive_path) = cached_archive {
+                            debug!("Using cached archive for mod '{}'", mod_info.mod_id);
+                            (archive_path, format!("{}.tar.gz", mod_info.mod_id))
+                        } else {
+                            if mod_info.download_url.is_empty() {
+                                error!("FATAL: Mod '{}' has no download URL", mod_info.mod_id);
+                                return Err(format!(
+                                    "Cannot download mod '{}': no download URL provided by server",
+                                    mod_info.mod_id
+                                ).into());
+                            }
 
-                        if response.status != 200 {
-                            error!("FATAL: Failed to download mod '{}': server returned status {}",
-                                mod_info.mod_id, response.status);
-                            return Err(format!(
-                                "Failed to download mod '{}': HTTP {}",
-                                mod_info.mod_id, response.status
-                            ).into());
-                        }
+                            debug!("Downloading mod '{}' from {}...", mod_info.mod_id, mod_info.download_url);
+
+                            let response = perform_stam_request(
+                                &mod_info.download_url,
+                                IntentType::RequestUri,
+                                &username,
+                                &password_hash,
+                                game_id,
+                                VERSION,
+                                host_port,
+                                tls,
+                                Some(&tmp_dir),
+                                DEFAULT_MAX_BUFFER_BYTES,
+                                None, // No progress callback for initial mod download
+                                None,
+                                connection_pool.as_ref(),
+                            ).await;
+
+                            if response.status != 200 {
+                                error!("FATAL: Failed to download mod '{}': server returned status {}",
+                                    mod_info.mod_id, response.status);
+                                return Err(format!(
+                                    "Failed to download mod '{}': HTTP {}",
+                                    mod_info.mod_id, response.status
+                                ).into());
+                            }
 
-                        // Get the temp file path (file was already saved by perform_stam_request)
-                        let archive_path = std::path::PathBuf::from(response.temp_file_path.ok_or_else(|| {
-                            format!("Server returned empty content for mod '{}'", mod_info.mod_id)
-                        })?);
+                            // Get the temp file path (file was already saved by perform_stam_request)
+                            let archive_path = std::path::PathBuf::from(response.temp_file_path.ok_or_else(|| {
+                                format!("Server returned empty content for mod '{}'", mod_info.mod_id)
+                            })?);
+
+                            // Verify integrity before extracting anything from the archive
+                            if !mod_info.archive_sha512.is_empty() {
+                                if let Err(e) = verify_sha512(&archive_path, &mod_info.archive_sha512) {
+                                    error!("FATAL: Integrity check failed for mod '{}': {}", mod_info.mod_id, e);
+                                    if let Err(e) = std::fs::remove_file(&archive_path) {
+                                        warn!("Failed to clean up temp file '{}': {}", archive_path.display(), e);
+                                    }
+                                    return Err(format!(
+                                        "Downloaded archive for mod '{}' failed integrity verification: {}",
+                                        mod_info.mod_id, e
+                                    ).into());
+                                }
+                                debug!("  Verified SHA-512 of archive for mod '{}'", mod_info.mod_id);
+                                if let Some(cache) = &mod_cache {
+                                    cache.store(&mod_info.archive_sha512, &archive_path);
+                                }
+                            } else {
+                                warn!("Mod '{}' has no archive_sha512, skipping integrity verification", mod_info.mod_id);
+                            }
 
-                        let archive_filename = response.file_name.unwrap_or_else(|| format!("{}.tar.gz", mod_info.mod_id));
+                            let archive_filename = response.file_name.unwrap_or_else(|| format!("{}.tar.gz", mod_info.mod_id));
+                            (archive_path, archive_filename)
+                        };
 
                         // Get file size for logging
                         let file_size = std::fs::metadata(&archive_path).map(|m| m.len()).unwrap_or(0);
@@ -827,7 +1340,19 @@ async fn connect_to_game_server(
                         debug!("  Extracting to {}...", mod_target_dir.display());
 
                         // Extract and always clean up temp file (even on error)
-                        let extract_result = extract_mod_archive(&archive_path, &mod_target_dir);
+                        let extract_timer = startup_timings.begin(format!("extract:{}", mod_info.mod_id));
+                        let reported_decile = std::cell::Cell::new(0);
+                        let report_extraction = |processed: u64, total: u64| {
+                            let decile = (processed * 10).checked_div(total).unwrap_or(10);
+                            if decile > reported_decile.get() {
+                                reported_decile.set(decile);
+                                debug!("  Extracting '{}': {}%", mod_info.mod_id, decile * 10);
+                            }
+                        };
+                        let max_bytes = mod_extraction_limit(mod_info.uncompressed_bytes);
+                        let extract_result =
+                            extract_mod_archive(&archive_path, &mod_target_dir, max_bytes, Some(&report_extraction));
+                        extract_timer.finish();
 
                         // Always clean up archive file after extraction attempt
                         if let Err(e) = std::fs::remove_file(&archive_path) {
@@ -836,30 +1361,24 @@ async fn connect_to_game_server(
 
                         // Now check extraction result
                         extract_result.map_err(|e| format!("Failed to extract mod '{}': {}", mod_info.mod_id, e))?;
+                        downloaded_mods.insert(mod_info.mod_id.clone());
 
                         debug!("  ✓ Mod '{}' installed successfully", mod_info.mod_id);
+                        progress.advance();
 
                         // Immediately load the manifest of the newly downloaded mod
                         // so that its dependencies can be discovered in the next iteration
-                        let client_dir = mod_target_dir.join("client");
-                        let client_manifest_path = client_dir.join("manifest.json");
-                        let root_manifest_path = mod_target_dir.join("manifest.json");
-
-                        let (actual_mod_dir, manifest_path) = if client_manifest_path.exists() {
-                            (client_dir, client_manifest_path)
-                        } else if root_manifest_path.exists() {
-                            (mod_target_dir.clone(), root_manifest_path)
-                        } else {
+                        let Some(location) = mod_dirs::locate(&mod_target_dir, false) else {
                             warn!("Downloaded mod '{}' has no manifest.json", mod_info.mod_id);
                             continue;
                         };
 
-                        match ModManifest::from_json_file(manifest_path.to_str().unwrap()) {
+                        match ModManifest::from_json_file(&location.manifest_path) {
                             Ok(manifest) => {
                                 debug!("  Loaded manifest: {} v{} (dependencies: {:?})",
                                     manifest.name, manifest.version,
                                     manifest.requires.keys().filter(|k| !k.starts_with('@')).collect::<Vec<_>>());
-                                available_manifests.insert(mod_info.mod_id.clone(), (manifest, actual_mod_dir));
+                                available_manifests.insert(mod_info.mod_id.clone(), (manifest, location.dir));
                                 // Remove from missing_mods if it was there
                                 missing_mods.retain(|id| id != &mod_info.mod_id);
                             }
@@ -878,7 +1397,8 @@ async fn connect_to_game_server(
                     .map(|m| m.mod_id.as_str())
                     .collect();
 
-                if !still_missing.is_empty() {
+                // (--validate lists them in its report instead)
+                if !still_missing.is_empty() && !validate_only {
                     error!("FATAL: Bootstrap mod(s) still missing after download: {:?}", still_missing);
                     return Err(format!(
                         "Failed to install bootstrap mod(s): {:?}",
@@ -887,35 +1407,61 @@ async fn connect_to_game_server(
                 }
             }
 
+            // --validate stops here: report the mod set without running any mod code
+            if validate_only {
+                let all_manifests: HashMap<String, ModManifest> = available_manifests
+                    .iter()
+                    .map(|(mod_id, (manifest, _))| (mod_id.clone(), manifest.clone()))
+                    .collect();
+                let mod_ids: Vec<&str> = mods.iter().map(|m| m.mod_id.as_str()).collect();
+                let report = mod_validation::ValidationReport::build(
+                    &mod_ids,
+                    |mod_id| all_manifests.get(mod_id),
+                    |mod_id, manifest| {
+                        validate_mod_dependencies(
+                            mod_id,
+                            manifest,
+                            &all_manifests,
+                            VERSION,
+                            &active_game_version,
+                            &server_version,
+                            false,
+                        )
+                    },
+                );
+                println!("{}", report);
+
+                if !report.is_ok() {
+                    return Err(format!(
+                        "mod validation failed ({} missing, {} circular, {} broken)",
+                        report.missing.len(),
+                        report.circular.len(),
+                        report.broken.len()
+                    )
+                    .into());
+                }
+                info!("All {} mod(s) passed validation", report.loaded.len());
+                return Ok(0);
+            }
+
             // Initialize mod runtime manager and load ONLY bootstrap mods + their dependencies
             if !available_manifests.is_empty() {
                 debug!("Initializing mod runtime system...");
+                let js_init_timer = startup_timings.begin("js_init");
 
                 // Create mod runtime manager
                 let mut runtime_manager = ModRuntimeManager::new();
 
-                // Initialize JavaScript runtime (one shared runtime for all JS mods)
-                let runtime_config = create_js_runtime_config(&game_root)?;
-                let mut js_adapter = JsRuntimeAdapter::new(runtime_config)?;
-
-                // Set home directory for mod installation (used by system.install_mod_from_path)
-                js_adapter.system_api().set_home_dir(game_root.clone());
-
-                // Set game info for system.get_game_info() (client-only API)
-                js_adapter.system_api().set_game_info(game_id, &game_name, &game_version);
-
                 // Setup graphic proxy for graphic engine operations (client-only)
                 // Pass game_root as asset_root so Bevy can load assets from mods directory
                 let graphic_proxy = Arc::new(GraphicProxy::new_client(engine_request_tx.clone(), Some(game_root.clone())));
-                js_adapter.set_graphic_proxy(graphic_proxy.clone());
                 // Save for main loop to poll graphic events
                 graphic_proxy_opt = Some(graphic_proxy.clone());
 
                 // Setup resource proxy for resource loading and caching (client-only)
                 let resource_proxy = Arc::new(stam_mod_runtimes::api::ResourceProxy::new_client());
-                js_adapter.set_resource_proxy(resource_proxy.clone());
                 // Save for main loop to process resource loading queue
-                resource_proxy_opt = Some(resource_proxy);
+                resource_proxy_opt = Some(resource_proxy.clone());
 
                 // Setup locale API for internationalization in JavaScript mods
                 // LocaleApi now supports hierarchical lookup: mod locale -> global locale
@@ -926,21 +1472,16 @@ async fn connect_to_game_server(
                 let locale_api = LocaleApi::new(
                     locale.current_locale(),  // current locale (e.g., "it-IT")
                     "en-US",                  // fallback locale
-                    move |id| {
+                    move |active_locale, id| {
                         let guard = locale_for_get.lock().unwrap();
-                        guard.get(id)
+                        guard.get_with_args_in(active_locale, id, None)
                     },
-                    move |id, args| {
+                    move |active_locale, id, args| {
                         let guard = locale_for_get_args.lock().unwrap();
-                        // Convert HashMap<String, String> to FluentArgs
-                        let mut fluent_args = fluent_bundle::FluentArgs::new();
-                        for (key, value) in args {
-                            fluent_args.set(key.as_str(), fluent_bundle::FluentValue::from(value.clone()));
-                        }
-                        guard.get_with_args(id, Some(&fluent_args))
+                        guard.get_with_args_in(active_locale, id, Some(args))
                     },
-                );
-                js_adapter.set_locale_api(locale_api);
+                )
+                .with_global_locales(locale.available_locales());
 
                 // Setup network API for downloading resources via stam:// protocol
                 // Capture credentials, game_id, and server address for use in the download callback
@@ -948,29 +1489,139 @@ async fn connect_to_game_server(
                 let network_password_hash = password_hash.clone();
                 let network_game_id = game_id.to_string();
                 let network_server = host_port.to_string();  // Default server for URIs without host
+                let network_server_tls = tls;
+                let network_tmp_dir = game_root.join("tmp");
+                let network_offline = offline;
                 let network_config = NetworkConfig {
                     game_id: game_id.to_string(),
                     username: username.to_string(),
                     password_hash: password_hash.clone(),
                     client_version: VERSION.to_string(),
+                    max_buffer_bytes: DEFAULT_MAX_BUFFER_BYTES,
+                    cache_dir: network_cache.then(|| game_root.join("cache").join("network")),
                 };
+                let network_max_buffer_bytes = network_config.max_buffer_bytes;
                 let mut network_api = NetworkApi::new(network_config);
+                // Cached mod archives are only served while they match the server's digest
+                for mod_info in mods.iter().filter(|m| !m.download_url.is_empty() && !m.archive_sha512.is_empty()) {
+                    network_api.set_expected_sha512(&mod_info.download_url, &mod_info.archive_sha512);
+                }
+
+                // Set the list callback that performs stam:// directory listings
+                let list_username = network_username.clone();
+                let list_password_hash = network_password_hash.clone();
+                let list_game_id = network_game_id.clone();
+                let list_server = network_server.clone();
+                let list_pool = connection_pool.clone();
+                network_api.set_list_callback(Arc::new(move |uri: String| {
+                    let username = list_username.clone();
+                    let password_hash = list_password_hash.clone();
+                    let game_id = list_game_id.clone();
+                    let default_server = list_server.clone();
+                    let pool = list_pool.clone();
+
+                    Box::pin(async move {
+                        if network_offline {
+                            warn!("Offline mode: refusing to list '{}'", sanitize_uri(&uri));
+                            return DownloadResponse { status: 503, ..Default::default() };
+                        }
+
+                        perform_stam_request(
+                            &uri,
+                            IntentType::ListUri,
+                            &username,
+                            &password_hash,
+                            &game_id,
+                            VERSION,
+                            &default_server,
+                            network_server_tls,
+                            None,
+                            network_max_buffer_bytes,
+                            None,
+                            None,
+                            pool.as_ref(),
+                        )
+                        .await
+                    })
+                }));
 
                 // Set the download callback that performs stam:// requests
-                // Note: We pass None for tmp_dir because the JS runtime's TempFileManager
-                // handles temp file creation after this callback returns
-                network_api.set_download_callback(Arc::new(move |uri: String, progress_callback| {
+                // Note: file downloads are streamed into the game tmp directory (the same one
+                // the JS runtime's TempFileManager uses), which then tracks them for cleanup
+                let network_pool = connection_pool.clone();
+                network_api.set_download_callback(Arc::new(move |uri: String, progress_callback, cancel: Option<CancelToken>| {
                     let username = network_username.clone();
                     let password_hash = network_password_hash.clone();
                     let game_id = network_game_id.clone();
                     let client_version = VERSION.to_string();
                     let default_server = network_server.clone();
+                    let tmp_dir = network_tmp_dir.clone();
+                    let pool = network_pool.clone();
 
                     Box::pin(async move {
-                        perform_stam_request(&uri, &username, &password_hash, &game_id, &client_version, &default_server, None, progress_callback).await
+                        // Offline mode never reaches out; mods see the service as unavailable
+                        if network_offline {
+                            warn!("Offline mode: refusing to download '{}'", sanitize_uri(&uri));
+                            return DownloadResponse {
+                                status: 503,
+                                buffer_string: Some("Downloads are disabled in offline mode".to_string()),
+                                file_name: None,
+                                file_content: None,
+                                temp_file_path: None,
+                                is_binary: false,
+                                transfer_stats: TransferStats::default(),
+                                listing: None,
+                            };
+                        }
+
+                        perform_stam_request(
+                            &uri,
+                            IntentType::RequestUri,
+                            &username,
+                            &password_hash,
+                            &game_id,
+                            &client_version,
+                            &default_server,
+                            network_server_tls,
+                            Some(&tmp_dir),
+                            network_max_buffer_bytes,
+                            progress_callback,
+                            cancel.as_ref(),
+                            pool.as_ref(),
+                        )
+                        .await
                     })
                 }));
-                js_adapter.set_network_api(network_api);
+
+                // Initialize JavaScript runtime (one shared runtime for all JS mods)
+                // with every API the client exposes to mods
+                let runtime_config = create_js_runtime_config(&game_root)?;
+                let apis = ApiRegistry::new()
+                    .with_api(ApiRegistry::GRAPHIC, graphic_proxy)
+                    .with_api(ApiRegistry::RESOURCE, resource_proxy)
+                    .with_api(ApiRegistry::LOCALE, locale_api)
+                    .with_api(ApiRegistry::NETWORK, network_api);
+                let js_adapter = JsRuntimeAdapter::new(runtime_config, apis)?;
+
+                // Set home directory for mod installation (used by system.install_mod_from_path)
+                js_adapter.system_api().set_home_dir(game_root.clone());
+
+                // Persist mod Storage under {game_root}/storage
+                js_adapter.storage_api().set_game_root(game_root.clone());
+
+                // Set game info for system.get_game_info() (client-only API)
+                js_adapter.system_api().set_game_info(game_id, &game_name, &game_version);
+
+                // Set server info for System.getServerInfo() (client-only API)
+                js_adapter.system_api().set_server_info(
+                    &server_version,
+                    &game_name,
+                    &game_version,
+                    stam_protocol::PROTOCOL_VERSION,
+                );
+
+                // Expose startup phase timings for system.getStartupTimings()
+                js_adapter.system_api().set_startup_timings(startup_timings.clone());
 
                 // Get runtime handle BEFORE moving the adapter to the manager
                 let js_runtime = js_adapter.get_runtime();
@@ -979,9 +1630,9 @@ async fn connect_to_game_server(
                 struct ModData {
                     mod_id: String,
                     manifest: ModManifest,
-                    /// Entry point path - None for asset-only mods (no executable code)
-                    entry_point_path: Option<std::path::PathBuf>,
-                    /// Absolute entry point - None for asset-only mods
+                    /// Entry point paths in load order - empty for asset-only mods (no executable code)
+                    entry_point_paths: Vec<std::path::PathBuf>,
+                    /// Absolute path of the first entry point - None for asset-only mods
                     absolute_entry_point: Option<std::path::PathBuf>,
                 }
 
@@ -994,7 +1645,7 @@ async fn connect_to_game_server(
 
                 for (mod_id, (manifest, actual_mod_dir)) in &available_manifests {
                     // Check if mod has an entry_point (asset-only mods don't)
-                    if let Some(ref entry_point) = manifest.entry_point {
+                    if let Some(entry_point) = manifest.entry_points().first() {
                         // Use actual_mod_dir (could be root or client/ subdirectory)
                         let entry_point_path = actual_mod_dir.join(entry_point);
 
@@ -1005,7 +1656,7 @@ async fn connect_to_game_server(
                             std::env::current_dir()?.join(&entry_point_path)
                         };
 
-                        // Register alias before loading
+                        // Register alias before loading (imports of "@mod-id" get the first entry point)
                         stam_mod_runtimes::adapters::js::register_mod_alias(
                             mod_id,
                             absolute_entry_point.clone(),
@@ -1014,7 +1665,11 @@ async fn connect_to_game_server(
                         mod_data_map.insert(mod_id.clone(), ModData {
                             mod_id: mod_id.clone(),
                             manifest: manifest.clone(),
-                            entry_point_path: Some(entry_point_path),
+                            entry_point_paths: manifest
+                                .entry_points()
+                                .iter()
+                                .map(|entry_point| actual_mod_dir.join(entry_point))
+                                .collect(),
                             absolute_entry_point: Some(absolute_entry_point),
                         });
                     } else {
@@ -1023,7 +1678,7 @@ async fn connect_to_game_server(
                         mod_data_map.insert(mod_id.clone(), ModData {
                             mod_id: mod_id.clone(),
                             manifest: manifest.clone(),
-                            entry_point_path: None,
+                            entry_point_paths: Vec::new(),
                             absolute_entry_point: None,
                         });
                     }
@@ -1039,7 +1694,11 @@ async fn connect_to_game_server(
                         // Available mod - use manifest info, exists=true
                         // Include archive info from server for display/validation
                         // Asset-only mods (no entry_point) are auto-attached (loaded=true)
-                        let is_asset_only = mod_data.entry_point_path.is_none();
+                        let is_asset_only = mod_data.entry_point_paths.is_empty();
+                        // Only the APIs listed in the manifest's capabilities (if any) get bound
+                        js_adapter
+                            .capability_policy()
+                            .declare(&mod_info.mod_id, mod_data.manifest.capabilities.as_deref())?;
                         js_adapter.register_mod_info(ModInfo {
                             id: mod_info.mod_id.clone(),
                             version: mod_data.manifest.version.clone(),
@@ -1078,6 +1737,7 @@ async fn connect_to_game_server(
 
                 // Store reference to system API for setting bootstrapped/loaded state later
                 let system_api = js_adapter.system_api().clone();
+                let capability_policy = js_adapter.capability_policy().clone();
 
                 // Now register the adapter with the runtime manager
                 runtime_manager.register_adapter(
@@ -1085,89 +1745,51 @@ async fn connect_to_game_server(
                     Box::new(js_adapter),
                 );
 
+                // Lua mods (.lua entry points) get their own runtime alongside JavaScript
+                #[cfg(feature = "lua")]
+                runtime_manager.register_adapter(
+                    stam_mod_runtimes::RuntimeType::Lua,
+                    Box::new(stam_mod_runtimes::adapters::LuaRuntimeAdapter::new()?),
+                );
+                // WebAssembly mods (.wasm entry points) run sandboxed in wasmtime
+                #[cfg(feature = "wasm")]
+                runtime_manager.register_adapter(
+                    stam_mod_runtimes::RuntimeType::WebAssembly,
+                    Box::new(stam_mod_runtimes::adapters::WasmRuntimeAdapter::new()?),
+                );
+                js_init_timer.finish();
+
                 // Collect bootstrap mods (only from available mods)
-                let bootstrap_mod_ids: Vec<String> = mod_data_map
+                // Sorted so the load plan doesn't depend on HashMap order
+                let mut bootstrap_mod_ids: Vec<String> = mod_data_map
                     .values()
                     .filter(|md| md.manifest.mod_type.as_deref() == Some("bootstrap"))
                     .map(|md| md.mod_id.clone())
                     .collect();
+                bootstrap_mod_ids.sort();
 
-                // Recursive function to collect dependencies
-                fn collect_dependencies(
-                    mod_id: &str,
-                    mod_data_map: &HashMap<String, ModData>,
-                    to_load: &mut Vec<String>,
-                    loading_chain: &mut Vec<String>,
-                ) -> Result<(), String> {
-                    // Loop detection
-                    if loading_chain.contains(&mod_id.to_string()) {
-                        return Err(format!(
-                            "Circular dependency detected: {} -> {}",
-                            loading_chain.join(" -> "),
-                            mod_id
-                        ));
-                    }
-
-                    // Already scheduled to load
-                    if to_load.contains(&mod_id.to_string()) {
-                        return Ok(());
-                    }
-
-                    let mod_data = mod_data_map.get(mod_id).ok_or_else(|| {
-                        format!("Dependency '{}' not found in available mods", mod_id)
-                    })?;
-
-                    // Add to loading chain for loop detection
-                    loading_chain.push(mod_id.to_string());
-
-                    // First load all dependencies
-                    for (dep_id, _version_req) in &mod_data.manifest.requires {
-                        // Skip special requirements like @client, @server, @game
-                        if dep_id.starts_with('@') {
-                            continue;
-                        }
-                        // Recursively collect dependencies
-                        collect_dependencies(dep_id, mod_data_map, to_load, loading_chain)?;
-                    }
-
-                    // Remove from loading chain
-                    loading_chain.pop();
-
-                    // Add this mod to load list
-                    to_load.push(mod_id.to_string());
-
-                    Ok(())
-                }
-
-                // Collect all mods to load (bootstrap + their dependencies, sorted by dependency order)
-                let mut mods_to_load: Vec<String> = Vec::new();
-                let mut loading_chain: Vec<String> = Vec::new();
+                // Plan the mods to load (bootstrap + their dependencies, dependencies first)
+                let bootstrap_ids: Vec<&str> = bootstrap_mod_ids.iter().map(String::as_str).collect();
+                let mut load_plan = mod_deps::collect_load_order(&bootstrap_ids, |mod_id| {
+                    mod_data_map.get(mod_id).map(|md| &md.manifest)
+                })?;
 
-                for bootstrap_mod_id in &bootstrap_mod_ids {
-                    collect_dependencies(
-                        bootstrap_mod_id,
-                        &mod_data_map,
-                        &mut mods_to_load,
-                        &mut loading_chain,
-                    )?;
-                }
-
-                // Sort mods_to_load by priority (lower priority loads first)
-                mods_to_load.sort_by_key(|mod_id| {
-                    mod_data_map.get(mod_id).map(|md| md.manifest.priority).unwrap_or(0)
-                });
+                // Sort by priority (lower priority loads first)
+                load_plan.sort_by_priority();
 
                 // Determine which mods are NOT loaded (for mods_notyetloaded list)
                 // This includes both available mods not in the load list AND missing mods
                 let mut mods_not_loaded: Vec<String> = mod_data_map
                     .keys()
-                    .filter(|mod_id| !mods_to_load.contains(mod_id))
+                    .filter(|mod_id| !load_plan.contains(mod_id))
                     .cloned()
                     .collect();
                 // Add missing mods to the not loaded list
                 mods_not_loaded.extend(missing_mods.clone());
 
-                debug!("Mods to load (bootstrap + dependencies): {:?}", mods_to_load);
+                debug!("Load plan (bootstrap + dependencies):\n{}", load_plan);
+                // Expose the plan to mods (system.getLoadOrder) before any of them attaches
+                system_api.set_load_plan(load_plan.clone());
                 if !mods_not_loaded.is_empty() {
                     debug!("Mods deferred for later loading: {:?}", mods_not_loaded);
                 }
@@ -1177,25 +1799,32 @@ async fn connect_to_game_server(
 
                 // Load ONLY bootstrap mods + their dependencies
                 // Non-bootstrap mods will be loaded by mods-manager when needed
-                // mods_to_load already contains bootstrap + dependencies in correct order
+                // load_plan already contains bootstrap + dependencies in correct order
                 // Skip asset-only mods (no entry_point) - they are already auto-attached
-                debug!("Attaching {} mods (bootstrap + dependencies)...", mods_to_load.len());
-                for mod_id in &mods_to_load {
+                debug!("Attaching {} mods (bootstrap + dependencies)...", load_plan.len());
+                let attach_timer = startup_timings.begin("attach");
+                progress.begin(LoadPhase::Attach, load_plan.len());
+                for planned in load_plan.iter() {
+                    let mod_id = &planned.mod_id;
                     let mod_data = mod_data_map.get(mod_id).unwrap();
                     // Skip asset-only mods - they have no code to load
-                    if let Some(ref entry_point_path) = mod_data.entry_point_path {
-                        runtime_manager.load_mod(mod_id, entry_point_path)?;
+                    if !mod_data.entry_point_paths.is_empty() {
+                        runtime_manager.load_mod_scripts(mod_id, &mod_data.entry_point_paths)?;
                         runtime_manager.call_mod_function(mod_id, "onAttach")?;
                         // Mark mod as loaded in SystemApi
                         system_api.set_loaded(mod_id, true);
+                        attached_entry_points.push((mod_id.clone(), mod_data.entry_point_paths.clone()));
                     }
                     // Asset-only mods are already marked as loaded=true during registration
+                    progress.advance();
                 }
+                attach_timer.finish();
 
                 // Call onBootstrap ONLY for bootstrap mods (not for dependencies)
                 // Note: Missing bootstrap mods check is done earlier, before runtime initialization
                 if !bootstrap_mod_ids.is_empty() {
                     debug!("Bootstrapping {} mod(s)...", bootstrap_mod_ids.len());
+                    let _bootstrap_timer = startup_timings.begin("bootstrap");
                     for mod_id in &bootstrap_mod_ids {
                         runtime_manager.call_mod_function(mod_id, "onBootstrap")?;
                         // Mark mod as bootstrapped
@@ -1204,15 +1833,22 @@ async fn connect_to_game_server(
                 }
 
                 // Count deferred mods (available but not loaded yet)
-                let deferred_count = mod_data_map.len() - mods_to_load.len();
+                let deferred_count = mod_data_map.len() - load_plan.len();
                 debug!("Mod system initialized successfully ({} loaded, {} deferred, {} missing)",
-                    mods_to_load.len(), deferred_count, missing_mods.len());
+                    load_plan.len(), deferred_count, missing_mods.len());
+                progress.finish(load_plan.len(), missing_mods.len());
+                debug!("Startup phases completed in {:.1?}", startup_timings.total());
                 js_runtime_handle = Some(js_runtime);
 
+                // Remember what was loaded, so a later LoginSuccess can be diffed against it
+                loaded_mod_set = mod_set::LoadedModSet::from_server_mods(&mods);
+                debug!("Tracking {} loaded mod(s) for change detection", loaded_mod_set.len());
+
                 // Save for dynamic mod loading in main loop
                 runtime_manager_opt = Some(runtime_manager);
                 system_api_opt = Some(system_api);
-                game_root_opt = Some(game_root);
+                capability_policy_opt = Some(capability_policy);
+                mod_dirs_opt = Some(mod_dirs);
             }
         }
         Ok(GameMessage::Error { message }) => {
@@ -1271,6 +1907,19 @@ async fn connect_to_game_server(
         });
     }
 
+    // Reconnection state: while reconnecting, mods keep running but there is no stream to read
+    let session = GameSession {
+        host_port: host_port.to_string(),
+        tls,
+        username: username.to_string(),
+        password_hash,
+        game_id: game_id.to_string(),
+    };
+    let mut reconnector = reconnect_policy.map(Reconnector::new);
+    let mut connected = true;
+    // Exit code requested by a mod through system.exit()
+    let mut exit_code = 0;
+
     // Run the JS event loop if we have JS mods loaded
     // This is necessary for setTimeout/setInterval to work properly
     if let Some(js_runtime) = js_runtime_handle {
@@ -1307,24 +1956,43 @@ async fn connect_to_game_server(
 
         // Start terminal input reader if running in a terminal
         let terminal_input_enabled = stam_mod_runtimes::terminal_input::is_terminal();
-        let (mut terminal_rx, mut terminal_handle) = if terminal_input_enabled {
+        let (mut terminal_rx, mut terminal_mouse_rx, mut terminal_paste_rx, mut terminal_handle) = if terminal_input_enabled {
             match stam_mod_runtimes::terminal_input::spawn_terminal_event_reader() {
-                Ok((rx, handle)) => (Some(rx), Some(handle)),
+                Ok((receivers, handle)) => {
+                    (Some(receivers.keys), Some(receivers.mouse), Some(receivers.paste), Some(handle))
+                }
                 Err(e) => {
                     debug!("Failed to start terminal input reader: {}", e);
-                    (None, None)
+                    (None, None, None, None)
                 }
             }
         } else {
             debug!("Not running in terminal, terminal input disabled");
-            (None, None)
+            (None, None, None, None)
         };
         let terminal_input_active = terminal_rx.is_some();
 
+        // Watch the attached mods for changes (development hot-reload)
+        let mut mod_watcher = if watch {
+            create_mod_watcher(&attached_entry_points, &locale)
+        } else {
+            None
+        };
+
         // Pin the JS event loop future to avoid recreating it on each loop iteration
         // This matches the server's optimized pattern for persistent async futures
         let mut js_loop = std::pin::pin!(run_js_event_loop(js_runtime.clone()));
 
+        // Sample the JS heap (trace log, System.getJsMemoryStats) and serve System.gc()
+        let monitored_system_api = system_api_opt.clone();
+        let monitored_runtime = js_runtime.clone();
+        let mut memory_monitor = std::pin::pin!(async move {
+            match monitored_system_api {
+                Some(system_api) => run_js_memory_monitor(monitored_runtime, system_api, MEMORY_STATS_INTERVAL).await,
+                None => std::future::pending().await,
+            }
+        });
+
         // Main event loop - handles JS events, attach requests, send_event, shutdown, terminal input, and connection
         loop {
             tokio::select! {
@@ -1339,7 +2007,8 @@ async fn connect_to_game_server(
                     }
                 } => {
                     if let Some(request) = request {
-                        info!("Shutdown requested by mod with exit code {}", request.exit_code);
+                        log_shutdown_request(&request);
+                        exit_code = request.exit_code;
                         break;
                     }
                 }
@@ -1375,6 +2044,36 @@ async fn connect_to_game_server(
                     }
                 }
 
+                // Handle pasted text (bracketed paste)
+                paste_request = async {
+                    if let Some(ref mut rx) = terminal_paste_rx {
+                        rx.recv().await
+                    } else {
+                        std::future::pending().await
+                    }
+                } => {
+                    if let Some(paste_request) = paste_request {
+                        if let Some(ref runtime_manager) = runtime_manager_opt {
+                            runtime_manager.dispatch_terminal_paste(&paste_request);
+                        }
+                    }
+                }
+
+                // Handle terminal mouse events (xterm mouse reporting)
+                mouse_request = async {
+                    if let Some(ref mut rx) = terminal_mouse_rx {
+                        rx.recv().await
+                    } else {
+                        std::future::pending().await
+                    }
+                } => {
+                    if let Some(mouse_request) = mouse_request {
+                        if let Some(ref runtime_manager) = runtime_manager_opt {
+                            runtime_manager.dispatch_terminal_mouse(&mouse_request);
+                        }
+                    }
+                }
+
                 // Fallback Ctrl+C handler when terminal input is not available
                 _ = async {
                     if !terminal_input_active {
@@ -1401,10 +2100,32 @@ async fn connect_to_game_server(
                             &request.mod_id,
                             &mut runtime_manager_opt,
                             &system_api_opt,
-                            &game_root_opt,
+                            &capability_policy_opt,
+                            &mod_dirs_opt,
                         ).await;
+                        if let (Ok(Some(entry_points)), Some(watcher)) = (&result, mod_watcher.as_mut()) {
+                            if let Err(e) = watcher.watch(&request.mod_id, entry_points) {
+                                warn!("Failed to watch mod '{}': {}", request.mod_id, e);
+                            }
+                        }
                         // Send response back to JS
-                        let _ = request.response_tx.send(result);
+                        let _ = request.response_tx.send(result.map(|_| ()));
+                    }
+                }
+
+                // Reload mods whose files changed (--watch)
+                // Handled here, between dispatches, so a mod is never reloaded mid-dispatch
+                changed_mods = async {
+                    if let Some(ref mut watcher) = mod_watcher {
+                        watcher.next_changed_mods().await
+                    } else {
+                        std::future::pending().await
+                    }
+                } => {
+                    if let Some(ref watcher) = mod_watcher {
+                        for mod_id in changed_mods {
+                            reload_watched_mod(&mod_id, watcher, &mut runtime_manager_opt, &system_api_opt, &locale);
+                        }
                     }
                 }
 
@@ -1419,29 +2140,32 @@ async fn connect_to_game_server(
                     }
                 } => {
                     if let Some(request) = request {
-                        let response = handle_send_event_request(
-                            &request.event_name,
-                            &request.args,
-                            &mut runtime_manager_opt,
-                        );
+                        let response = handle_send_event_request(&request.to_custom_event(), &mut runtime_manager_opt);
                         // Send response back to JS
                         let _ = request.response_tx.send(response);
                     }
                 }
 
                 // Handle graphic engine events
-                event = async {
+                // All queued events are taken per wake and high-frequency ones are coalesced
+                events = async {
                     if let Some(ref mut rx) = graphic_event_rx {
-                        rx.recv().await
+                        GraphicProxy::recv_event_batch(rx, GRAPHIC_EVENT_BATCH_SIZE).await
                     } else {
                         std::future::pending().await
                     }
                 } => {
-                    if let Some(event) = event {
+                    if events.is_empty() {
+                        // The engine has exited and dropped its sender
+                        debug!("Graphic event channel closed");
+                        graphic_event_rx = None;
+                    }
+                    for event in events {
                         handle_graphic_event(
                             event,
                             &mut runtime_manager_opt,
                             resource_proxy_opt.as_ref(),
+                            graphic_proxy_opt.as_ref(),
                         );
                     }
                 }
@@ -1474,9 +2198,42 @@ async fn connect_to_game_server(
                 }
 
                 // Maintain game connection
-                _ = maintain_game_connection(&mut stream, locale.clone()) => {
-                    info!("{}", locale.get("connection-closed"));
-                    break;
+                event = async {
+                    if connected {
+                        maintain_game_connection(&mut stream, locale.clone()).await
+                    } else {
+                        std::future::pending().await
+                    }
+                } => {
+                    match event {
+                        ConnectionEvent::Broadcast { channel, payload } => {
+                            dispatch_server_broadcast(&channel, payload, &mut runtime_manager_opt);
+                        }
+                        ConnectionEvent::Disconnecting { reason, message } => {
+                            dispatch_server_disconnecting(reason, &message, &locale, &mut runtime_manager_opt);
+                            info!("{}", locale.get("connection-closed"));
+                            break;
+                        }
+                        ConnectionEvent::Ended(end) => {
+                            info!("{}", locale.get("connection-closed"));
+                            if !start_reconnecting(end, &mut reconnector, &locale) {
+                                break;
+                            }
+                            connected = false;
+                        }
+                    }
+                }
+
+                // Retry the game login once the backoff delay has elapsed
+                _ = wait_for_reconnect_attempt(&reconnector) => {
+                    match try_reconnect(&session, &mut reconnector, &loaded_mod_set, &locale).await {
+                        ReconnectOutcome::Connected(new_stream) => {
+                            stream = new_stream;
+                            connected = true;
+                        }
+                        ReconnectOutcome::Retrying => {}
+                        ReconnectOutcome::GaveUp => break,
+                    }
                 }
 
                 // Run JS event loop for timer callbacks (uses pinned future reference)
@@ -1489,6 +2246,9 @@ async fn connect_to_game_server(
                     break;
                 }
 
+                // Never completes: the monitor runs until the loop exits
+                _ = &mut memory_monitor => {}
+
                 // Check for SIGTERM and graphic event receiver (polled periodically)
                 _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
                     if sigterm_received.load(Ordering::Relaxed) {
@@ -1505,6 +2265,16 @@ async fn connect_to_game_server(
                             }
                         }
                     }
+
+                    // Report engine startup errors the engine could not send itself (timeout)
+                    if let Some(message) = graphic_proxy_opt.as_ref().and_then(|proxy| proxy.take_engine_error()) {
+                        handle_graphic_event(
+                            GraphicEvent::EngineError { message },
+                            &mut runtime_manager_opt,
+                            resource_proxy_opt.as_ref(),
+                            graphic_proxy_opt.as_ref(),
+                        );
+                    }
                 }
             }
         }
@@ -1513,6 +2283,8 @@ async fn connect_to_game_server(
         if let Some(ref mut handle) = terminal_handle {
             handle.stop_async().await;
         }
+
+        shutdown_mods(&mut runtime_manager_opt, Some(&js_runtime)).await;
     } else {
         // No JS runtime, just wait for connection or Ctrl+C
         // Still dispatch TerminalKeyPressed to allow other runtimes to handle it
@@ -1525,17 +2297,19 @@ async fn connect_to_game_server(
         };
 
         // Start terminal input reader if running in a terminal
-        let (mut terminal_rx, mut terminal_handle) = if stam_mod_runtimes::terminal_input::is_terminal() {
+        let (mut terminal_rx, mut terminal_mouse_rx, mut terminal_paste_rx, mut terminal_handle) = if stam_mod_runtimes::terminal_input::is_terminal() {
             match stam_mod_runtimes::terminal_input::spawn_terminal_event_reader() {
-                Ok((rx, handle)) => (Some(rx), Some(handle)),
+                Ok((receivers, handle)) => {
+                    (Some(receivers.keys), Some(receivers.mouse), Some(receivers.paste), Some(handle))
+                }
                 Err(e) => {
                     debug!("Failed to start terminal input reader: {}", e);
-                    (None, None)
+                    (None, None, None, None)
                 }
             }
         } else {
             debug!("Not running in terminal, terminal input disabled");
-            (None, None)
+            (None, None, None, None)
         };
         let terminal_input_active = terminal_rx.is_some();
 
@@ -1552,7 +2326,8 @@ async fn connect_to_game_server(
                     }
                 } => {
                     if let Some(request) = request {
-                        info!("Shutdown requested by mod with exit code {}", request.exit_code);
+                        log_shutdown_request(&request);
+                        exit_code = request.exit_code;
                         break;
                     }
                 }
@@ -1581,6 +2356,36 @@ async fn connect_to_game_server(
                     }
                 }
 
+                // Handle pasted text (bracketed paste)
+                paste_request = async {
+                    if let Some(ref mut rx) = terminal_paste_rx {
+                        rx.recv().await
+                    } else {
+                        std::future::pending().await
+                    }
+                } => {
+                    if let Some(paste_request) = paste_request {
+                        if let Some(ref runtime_manager) = runtime_manager_opt {
+                            runtime_manager.dispatch_terminal_paste(&paste_request);
+                        }
+                    }
+                }
+
+                // Handle terminal mouse events (xterm mouse reporting)
+                mouse_request = async {
+                    if let Some(ref mut rx) = terminal_mouse_rx {
+                        rx.recv().await
+                    } else {
+                        std::future::pending().await
+                    }
+                } => {
+                    if let Some(mouse_request) = mouse_request {
+                        if let Some(ref runtime_manager) = runtime_manager_opt {
+                            runtime_manager.dispatch_terminal_mouse(&mouse_request);
+                        }
+                    }
+                }
+
                 // Fallback Ctrl+C handler when terminal input is not available
                 _ = async {
                     if !terminal_input_active {
@@ -1594,9 +2399,42 @@ async fn connect_to_game_server(
                 }
 
                 // Maintain game connection
-                _ = maintain_game_connection(&mut stream, locale.clone()) => {
-                    info!("{}", locale.get("connection-closed"));
-                    break;
+                event = async {
+                    if connected {
+                        maintain_game_connection(&mut stream, locale.clone()).await
+                    } else {
+                        std::future::pending().await
+                    }
+                } => {
+                    match event {
+                        ConnectionEvent::Broadcast { channel, payload } => {
+                            dispatch_server_broadcast(&channel, payload, &mut runtime_manager_opt);
+                        }
+                        ConnectionEvent::Disconnecting { reason, message } => {
+                            dispatch_server_disconnecting(reason, &message, &locale, &mut runtime_manager_opt);
+                            info!("{}", locale.get("connection-closed"));
+                            break;
+                        }
+                        ConnectionEvent::Ended(end) => {
+                            info!("{}", locale.get("connection-closed"));
+                            if !start_reconnecting(end, &mut reconnector, &locale) {
+                                break;
+                            }
+                            connected = false;
+                        }
+                    }
+                }
+
+                // Retry the game login once the backoff delay has elapsed
+                _ = wait_for_reconnect_attempt(&reconnector) => {
+                    match try_reconnect(&session, &mut reconnector, &loaded_mod_set, &locale).await {
+                        ReconnectOutcome::Connected(new_stream) => {
+                            stream = new_stream;
+                            connected = true;
+                        }
+                        ReconnectOutcome::Retrying => {}
+                        ReconnectOutcome::GaveUp => break,
+                    }
                 }
 
                 // Check for SIGTERM (polled periodically)
@@ -1612,6 +2450,8 @@ async fn connect_to_game_server(
         if let Some(ref mut handle) = terminal_handle {
             handle.stop_async().await;
         }
+
+        shutdown_mods(&mut runtime_manager_opt, None).await;
     }
 
     // Shutdown graphic engine if one was enabled
@@ -1626,19 +2466,55 @@ async fn connect_to_game_server(
     }
 
     info!("{}", locale.get("game-shutdown"));
-    Ok(())
+    Ok(exit_code)
+}
+
+/// Log a shutdown requested by a mod, with its reason at ERROR when it is an error
+fn log_shutdown_request(request: &stam_mod_runtimes::api::ShutdownRequest) {
+    if request.is_error {
+        error!("{}", request.describe());
+    } else {
+        info!("{}", request.describe());
+    }
+}
+
+/// Let mods persist their state before the client exits
+///
+/// Calls `onShutdown` on every loaded mod, then runs the JavaScript jobs the handlers
+/// left pending (e.g. an async save) until they drain or `SHUTDOWN_DRAIN_TIMEOUT` elapses.
+/// The graphic engine is still running, so handlers may use it.
+async fn shutdown_mods(
+    runtime_manager_opt: &mut Option<ModRuntimeManager>,
+    js_runtime: Option<&Arc<stam_mod_runtimes::JsAsyncRuntime>>,
+) {
+    let Some(runtime_manager) = runtime_manager_opt.as_mut() else {
+        return;
+    };
+    debug!("Calling onShutdown on {} mods", runtime_manager.loaded_mods().len());
+    runtime_manager.dispatch_shutdown();
+
+    if let Some(js_runtime) = js_runtime {
+        if !drain_pending_jobs(js_runtime, SHUTDOWN_DRAIN_TIMEOUT).await {
+            warn!(
+                "JavaScript jobs still pending after {:?}, shutting down anyway",
+                SHUTDOWN_DRAIN_TIMEOUT
+            );
+        }
+    }
 }
 
 /// Handle a request to attach (load and initialize) a mod at runtime
 ///
 /// This is called when JavaScript code calls `system.attach_mod(mod_id)`.
 /// It reads the mod's manifest, loads the mod into the runtime, and calls onAttach.
+/// Returns the entry points that were loaded (None for asset-only mods).
 async fn handle_attach_mod_request(
     mod_id: &str,
     runtime_manager_opt: &mut Option<ModRuntimeManager>,
     system_api_opt: &Option<stam_mod_runtimes::api::SystemApi>,
-    game_root_opt: &Option<std::path::PathBuf>,
-) -> Result<(), String> {
+    capability_policy_opt: &Option<stam_mod_runtimes::api::CapabilityPolicy>,
+    mod_dirs_opt: &Option<ModDirs>,
+) -> Result<Option<Vec<std::path::PathBuf>>, String> {
     debug!("Attaching mod '{}' at runtime...", mod_id);
 
     let runtime_manager = runtime_manager_opt.as_mut()
@@ -1647,35 +2523,34 @@ async fn handle_attach_mod_request(
     let system_api = system_api_opt.as_ref()
         .ok_or_else(|| "System API not available".to_string())?;
 
-    let game_root = game_root_opt.as_ref()
-        .ok_or_else(|| "Game root not available".to_string())?;
-
-    // Find the mod directory (check client/ subdirectory first, then root)
-    let mods_dir = game_root.join("mods");
-    let mod_dir = mods_dir.join(mod_id);
-
-    if !mod_dir.exists() {
-        return Err(format!("Mod directory '{}' not found", mod_dir.display()));
-    }
+    let mod_dirs = mod_dirs_opt.as_ref()
+        .ok_or_else(|| "Mod directories not available".to_string())?;
 
-    // Check for client/ subdirectory first
-    let client_dir = mod_dir.join("client");
-    let actual_mod_dir = if client_dir.exists() && client_dir.join("manifest.json").exists() {
-        client_dir
-    } else {
-        mod_dir.clone()
+    // Find the mod directory (override dir first; client/ subdirectory first, then root)
+    let Some(location) = mod_dirs.find(mod_id) else {
+        let mod_dir = mod_dirs.game_dir().join(mod_id);
+        if !mod_dir.exists() {
+            return Err(format!("Mod directory '{}' not found", mod_dir.display()));
+        }
+        return Err(format!("Failed to read manifest: no manifest.json in '{}'", mod_dir.display()));
     };
+    if location.overridden {
+        info!("Mod '{}' loaded from {}", mod_id, location.dir.display());
+    } else {
+        debug!("Mod '{}' found in {}", mod_id, location.dir.display());
+    }
+    let actual_mod_dir = location.dir;
 
     // Read manifest
-    let manifest_path = actual_mod_dir.join("manifest.json");
-    let manifest_content = std::fs::read_to_string(&manifest_path)
+    let manifest_content = std::fs::read_to_string(&location.manifest_path)
         .map_err(|e| format!("Failed to read manifest: {}", e))?;
 
-    let manifest: stam_schema::ModManifest = serde_json::from_str(&manifest_content)
-        .map_err(|e| format!("Failed to parse manifest: {}", e))?;
+    // Schema and entry point validation, so a mod without a runtime fails here
+    let manifest = ModManifest::from_json_str(&manifest_content)
+        .map_err(|e| format!("Invalid manifest for mod '{}': {}", mod_id, e))?;
 
     // Check if mod has an entry_point - asset-only mods are auto-attached (skip loading)
-    if let Some(ref entry_point) = manifest.entry_point {
+    if let Some(entry_point) = manifest.entry_points().first() {
         // Build entry point path
         let entry_point_path = actual_mod_dir.join(entry_point);
 
@@ -1688,42 +2563,137 @@ async fn handle_attach_mod_request(
                 .join(&entry_point_path)
         };
 
-        // Register mod alias before loading
+        // Register mod alias before loading (imports of "@mod-id" get the first entry point)
         stam_mod_runtimes::adapters::js::register_mod_alias(mod_id, absolute_entry_point.clone());
 
-        // Load the mod
-        runtime_manager.load_mod(mod_id, &entry_point_path)
-            .map_err(|e| format!("Failed to load mod: {}", e))?;
+        // The mod may have been downloaded after startup, so declare its capabilities now
+        if let Some(capability_policy) = capability_policy_opt {
+            capability_policy.declare(mod_id, manifest.capabilities.as_deref())?;
+        }
+
+        // Load the mod, with every entry point script in order
+        let entry_point_paths: Vec<std::path::PathBuf> =
+            manifest.entry_points().iter().map(|entry_point| actual_mod_dir.join(entry_point)).collect();
+        runtime_manager.load_mod_scripts(mod_id, &entry_point_paths)
+            .map_err(|e| describe_attach_error(mod_id, "load", &e))?;
 
         // Call onAttach
         runtime_manager.call_mod_function(mod_id, "onAttach")
-            .map_err(|e| format!("Failed to call onAttach: {}", e))?;
+            .map_err(|e| describe_attach_error(mod_id, "call onAttach", &e))?;
 
         // Mark mod as loaded in SystemApi
         system_api.set_loaded(mod_id, true);
 
         debug!("Mod '{}' attached successfully", mod_id);
+        Ok(Some(entry_point_paths))
     } else {
         // Asset-only mod (no entry_point) - already considered attached
         debug!("Mod '{}' is asset-only (no entry_point), skipping load", mod_id);
         // Still mark as loaded in case it wasn't already
         system_api.set_loaded(mod_id, true);
+        Ok(None)
     }
-
-    Ok(())
 }
 
-/// Handle a request to dispatch a custom event to all registered handlers
-///
-/// This is called when JavaScript code calls `system.sendEvent()`.
-/// It dispatches the event to all registered handlers using the request/response pattern.
+/// Create the file watcher for `--watch`, watching the given mods
+///
+/// Returns None (hot-reload disabled) if the watcher can't be created.
+fn create_mod_watcher(
+    entry_points: &[(String, Vec<std::path::PathBuf>)],
+    locale: &LocaleManager,
+) -> Option<ModWatcher> {
+    let mut watcher = match ModWatcher::new() {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            warn!("Failed to start mod watcher, hot-reload disabled: {}", e);
+            return None;
+        }
+    };
+    for (mod_id, entry_point) in entry_points {
+        if let Err(e) = watcher.watch(mod_id, entry_point) {
+            warn!("Failed to watch mod '{}': {}", mod_id, e);
+        }
+    }
+    info!(
+        "{}",
+        locale.get_with_args("watch-enabled", Some(&fluent_args! { "count" => watcher.len() }))
+    );
+    Some(watcher)
+}
+
+/// Reload a watched mod after its files changed
+///
+/// Unloads the mod (calling `onDetach`), then loads its entry points again and
+/// calls `onAttach`. A mod whose previous reload failed is no longer loaded and
+/// is simply loaded again, so fixing the error and saving recovers it.
+fn reload_watched_mod(
+    mod_id: &str,
+    watcher: &ModWatcher,
+    runtime_manager_opt: &mut Option<ModRuntimeManager>,
+    system_api_opt: &Option<stam_mod_runtimes::api::SystemApi>,
+    locale: &LocaleManager,
+) {
+    let (Some(runtime_manager), Some(watched)) = (runtime_manager_opt.as_mut(), watcher.get(mod_id)) else {
+        return;
+    };
+
+    let result = match runtime_manager.unload_mod(mod_id) {
+        Ok(()) | Err(stam_mod_runtimes::RuntimeError::ModNotFound { .. }) => runtime_manager
+            .load_mod_scripts(mod_id, &watched.entry_points)
+            .map_err(|e| describe_attach_error(mod_id, "load", &e))
+            .and_then(|_| {
+                runtime_manager
+                    .call_mod_function(mod_id, "onAttach")
+                    .map_err(|e| describe_attach_error(mod_id, "call onAttach", &e))
+            }),
+        Err(e) => Err(format!("Failed to unload mod '{}': {}", mod_id, e)),
+    };
+
+    if let Some(system_api) = system_api_opt {
+        system_api.set_loaded(mod_id, runtime_manager.get_mod_runtime_type(mod_id).is_some());
+    }
+
+    match result {
+        Ok(()) => info!("{}", locale.get_with_args("mod-reloaded", Some(&fluent_args! { "mod" => mod_id }))),
+        Err(e) => error!(
+            "{}",
+            locale.get_with_args("mod-reload-failed", Some(&fluent_args! { "mod" => mod_id, "error" => e }))
+        ),
+    }
+}
+
+/// Turn a runtime error raised while attaching a mod into an actionable message
+fn describe_attach_error(mod_id: &str, stage: &str, error: &stam_mod_runtimes::RuntimeError) -> String {
+    use stam_mod_runtimes::RuntimeError;
+
+    match error {
+        RuntimeError::CompileError { line: Some(line), message } => {
+            format!("Mod '{}' has a syntax error at line {}: {}", mod_id, line, message)
+        }
+        RuntimeError::CompileError { line: None, message } => {
+            format!("Mod '{}' has a syntax error: {}", mod_id, message)
+        }
+        RuntimeError::RuntimeException { message, stack: Some(stack) } => {
+            format!("Mod '{}' threw during {}: {}\n{}", mod_id, stage, message, stack)
+        }
+        RuntimeError::RuntimeException { message, stack: None } => {
+            format!("Mod '{}' threw during {}: {}", mod_id, stage, message)
+        }
+        RuntimeError::Timeout { .. } => error.to_string(),
+        _ => format!("Failed to {} mod '{}': {}", stage, mod_id, error),
+    }
+}
+
+/// Handle a request to dispatch a custom event to all registered handlers
+///
+/// This is called when JavaScript code calls `system.sendEvent()`.
+/// It dispatches the event to all registered handlers using the request/response pattern.
 ///
 /// **IMPORTANT**: Handler response values must be set SYNCHRONOUSLY before any
 /// `await` points. Values set after an `await` will not be captured.
 ///
 /// # Arguments
-/// * `event_name` - The event name to dispatch
-/// * `args` - JSON-serialized arguments to pass to handlers
+/// * `request` - The event to dispatch, with its JSON-serialized arguments, target and sender
 /// * `runtime_manager_opt` - The runtime manager (if available)
 ///
 /// # Returns
@@ -1731,11 +2701,11 @@ async fn handle_attach_mod_request(
 /// - `handled: bool` - Whether any handler marked the event as handled
 /// - `properties: HashMap` - Custom properties set by handlers
 fn handle_send_event_request(
-    event_name: &str,
-    args: &[String],
+    request: &stam_mod_runtimes::api::CustomEventRequest,
     runtime_manager_opt: &mut Option<ModRuntimeManager>,
 ) -> stam_mod_runtimes::api::CustomEventResponse {
-    trace!("Dispatching custom event '{}' with {} args", event_name, args.len());
+    let event_name = &request.event_name;
+    trace!("Dispatching custom event '{}' with {} args", event_name, request.args.len());
 
     let runtime_manager = match runtime_manager_opt.as_mut() {
         Some(rm) => rm,
@@ -1745,11 +2715,8 @@ fn handle_send_event_request(
         }
     };
 
-    // Create the request object
-    let request = stam_mod_runtimes::api::CustomEventRequest::new(event_name, args.to_vec());
-
-    // Dispatch to all handlers and get the aggregated response
-    let response = runtime_manager.dispatch_custom_event(&request);
+    // Dispatch to all handlers (a single mod when targeted) and get the aggregated response
+    let response = runtime_manager.dispatch_custom_event(request);
 
     trace!("Custom event '{}' dispatched (handled={}, properties={})",
         event_name, response.handled, response.properties.len());
@@ -1757,17 +2724,83 @@ fn handle_send_event_request(
     response
 }
 
+/// Dispatch a server broadcast to mods as a custom event named after its channel
+///
+/// The JSON payload is passed as the only argument; handler responses are not
+/// sent back to the server.
+fn dispatch_server_broadcast(
+    channel: &str,
+    payload: String,
+    runtime_manager_opt: &mut Option<ModRuntimeManager>,
+) {
+    let request = stam_mod_runtimes::api::CustomEventRequest::new(channel, vec![payload]);
+    let response = handle_send_event_request(&request, runtime_manager_opt);
+    if !response.handled {
+        trace!("Broadcast on channel '{}' was not handled by any mod", channel);
+    }
+}
+
+/// Tell client mods that the server is closing the session, before the connection ends
+///
+/// `req.args[0]` is `{ reason, message, text }`: the `DisconnectReason` as a string
+/// (e.g. "restart"), the locale ID sent by the server and its localized text.
+fn dispatch_server_disconnecting(
+    reason: DisconnectReason,
+    message: &str,
+    locale: &LocaleManager,
+    runtime_manager_opt: &mut Option<ModRuntimeManager>,
+) {
+    let args = serde_json::json!({
+        "reason": reason.as_str(),
+        "message": message,
+        "text": locale.get(message),
+    });
+    let request = stam_mod_runtimes::api::CustomEventRequest::new(SERVER_DISCONNECTING_EVENT, vec![args.to_string()]);
+    let response = handle_send_event_request(&request, runtime_manager_opt);
+    trace!("Server disconnect ({}) dispatched to mods (handled={})", reason.as_str(), response.handled);
+}
+
 /// Handle a graphic engine event
 ///
 /// This is called when the worker thread receives an event from the graphic engine.
 /// It dispatches the event to the appropriate handlers in the mod runtime.
+/// Invoke a window's `on<Event>` callback (e.g. `onResize`), logging the outcome
+fn dispatch_window_event(
+    runtime_manager: &ModRuntimeManager,
+    window_id: u64,
+    event_type: &str,
+    event_data: serde_json::Value,
+) {
+    match runtime_manager.dispatch_window_event_callback(window_id, event_type, event_data) {
+        Ok(true) => trace!("Window {} event '{}' dispatched to mods", window_id, event_type),
+        Ok(false) => {}
+        Err(e) => error!("Window {} event '{}' callback failed: {}", window_id, event_type, e),
+    }
+}
+
+/// Broadcast a graphic event to mods as a custom event (e.g. "graphic:engine:error")
+fn dispatch_graphic_custom_event(runtime_manager: &ModRuntimeManager, event: &GraphicEvent) {
+    // Most graphic events (mouse moves, resizes) have no listeners; skip serializing them
+    if runtime_manager.handler_count(event.event_name()) == 0 {
+        return;
+    }
+    let request = stam_mod_runtimes::api::CustomEventRequest::new(event.event_name(), event.to_json_args());
+    let response = runtime_manager.dispatch_custom_event(&request);
+    trace!("Graphic event '{}' dispatched to mods (handled={})", event.event_name(), response.handled);
+}
+
 fn handle_graphic_event(
     event: GraphicEvent,
     runtime_manager_opt: &mut Option<ModRuntimeManager>,
     resource_proxy_opt: Option<&std::sync::Arc<stam_mod_runtimes::api::ResourceProxy>>,
+    graphic_proxy_opt: Option<&Arc<GraphicProxy>>,
 ) {
     match event {
         GraphicEvent::EngineReady => {
+            if graphic_proxy_opt.is_some_and(|proxy| !proxy.mark_engine_ready()) {
+                warn!("Graphic engine became ready after enableEngine() gave up on it, ignoring");
+                return;
+            }
             debug!("Graphic engine is ready, dispatching GraphicEngineReady event");
 
             // Dispatch GraphicEngineReady to all registered handlers
@@ -1785,14 +2818,22 @@ fn handle_graphic_event(
             }
         }
         GraphicEvent::WindowCreated { window_id } => {
-            warn!("TODO: Dispatch window:created event to mods (window_id={})", window_id);
+            trace!("Window {} created", window_id);
+            // No window callbacks can be attached before creation, so notify through the event system
+            if let Some(runtime_manager) = runtime_manager_opt.as_ref() {
+                dispatch_graphic_custom_event(runtime_manager, &event);
+            }
         }
         GraphicEvent::WindowClosed { window_id } => {
+            // The window may have been closed by the user rather than by window.close()
+            if let Some(graphic_proxy) = graphic_proxy_opt {
+                graphic_proxy.forget_window(window_id);
+            }
 
-            // Dispatch to window.onClose callback first
+            // Dispatch to window.onClose callback first (this also drops the window's callbacks)
             if let Some(runtime_manager) = runtime_manager_opt.as_ref() {
                 let event_data = serde_json::json!({});
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "close", event_data);
+                dispatch_window_event(runtime_manager, window_id, "close", event_data);
 
                 // Also dispatch GraphicEngineWindowClosed to all registered handlers (for cross-script events)
                 let request = GraphicEngineWindowClosedRequest::new(window_id);
@@ -1805,7 +2846,7 @@ fn handle_graphic_event(
                     "width": width,
                     "height": height
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "resize", event_data);
+                dispatch_window_event(runtime_manager, window_id, "resize", event_data);
             }
         }
         GraphicEvent::WindowFocused { window_id, focused } => {
@@ -1813,7 +2854,7 @@ fn handle_graphic_event(
                 let event_data = serde_json::json!({
                     "focused": focused
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "focus", event_data);
+                dispatch_window_event(runtime_manager, window_id, "focus", event_data);
             }
         }
         GraphicEvent::WindowMoved { window_id, x, y } => {
@@ -1822,7 +2863,7 @@ fn handle_graphic_event(
                     "x": x,
                     "y": y
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "move", event_data);
+                dispatch_window_event(runtime_manager, window_id, "move", event_data);
             }
         }
         GraphicEvent::KeyPressed { window_id, key, modifiers } => {
@@ -1836,7 +2877,7 @@ fn handle_graphic_event(
                         "meta": modifiers.meta
                     }
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "keyPressed", event_data);
+                dispatch_window_event(runtime_manager, window_id, "keyPressed", event_data);
             }
         }
         GraphicEvent::KeyReleased { window_id, key, modifiers } => {
@@ -1850,7 +2891,7 @@ fn handle_graphic_event(
                         "meta": modifiers.meta
                     }
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "keyReleased", event_data);
+                dispatch_window_event(runtime_manager, window_id, "keyReleased", event_data);
             }
         }
         GraphicEvent::CharacterInput { window_id, character } => {
@@ -1858,7 +2899,7 @@ fn handle_graphic_event(
                 let event_data = serde_json::json!({
                     "character": character.to_string()
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "character", event_data);
+                dispatch_window_event(runtime_manager, window_id, "character", event_data);
             }
         }
         GraphicEvent::MouseMoved { window_id, x, y } => {
@@ -1869,7 +2910,7 @@ fn handle_graphic_event(
                     "x": x,
                     "y": y
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "mouseMove", event_data);
+                dispatch_window_event(runtime_manager, window_id, "mouseMove", event_data);
             }
         }
         GraphicEvent::MouseButtonPressed { window_id, button, x, y } => {
@@ -1880,7 +2921,7 @@ fn handle_graphic_event(
                     "x": x,
                     "y": y
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "mousePressed", event_data);
+                dispatch_window_event(runtime_manager, window_id, "mousePressed", event_data);
             }
         }
         GraphicEvent::MouseButtonReleased { window_id, button, x, y } => {
@@ -1891,7 +2932,7 @@ fn handle_graphic_event(
                     "x": x,
                     "y": y
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "mouseReleased", event_data);
+                dispatch_window_event(runtime_manager, window_id, "mouseReleased", event_data);
             }
         }
         GraphicEvent::MouseWheel { window_id, delta_x, delta_y } => {
@@ -1900,30 +2941,64 @@ fn handle_graphic_event(
                     "deltaX": delta_x,
                     "deltaY": delta_y
                 });
-                let _ = runtime_manager.dispatch_window_event_callback(window_id, "mouseWheel", event_data);
+                dispatch_window_event(runtime_manager, window_id, "mouseWheel", event_data);
             }
         }
         GraphicEvent::FrameStart { window_id, delta_time } => {
-            // Too verbose for debug
-            let _ = (window_id, delta_time);
-            //warn!("TODO: Dispatch frame:start event to mods if needed");
+            // Too verbose to dispatch every frame: only an averaged report per interval,
+            // and only when some mod listens to it
+            let _ = window_id;
+            let stats = graphic_proxy_opt.and_then(|proxy| proxy.record_frame(delta_time));
+            if let (Some(stats), Some(runtime_manager)) = (stats, runtime_manager_opt.as_ref()) {
+                if runtime_manager.custom_event_handler_count(FRAME_STATS_EVENT) > 0 {
+                    let request = stam_mod_runtimes::api::CustomEventRequest::new(
+                        FRAME_STATS_EVENT,
+                        vec![stats.to_json().to_string()],
+                    );
+                    runtime_manager.dispatch_custom_event(&request);
+                }
+            }
         }
         GraphicEvent::FrameEnd { window_id, frame_time } => {
             // Too verbose for debug
             let _ = (window_id, frame_time);
             //warn!("TODO: Dispatch frame:end event to mods if needed");
         }
-        GraphicEvent::EngineError { message } => {
+        GraphicEvent::EngineError { ref message } => {
             error!("Graphic engine error: {}", message);
-            warn!("TODO: Dispatch engine:error event to mods");
+            if let Some(graphic_proxy) = graphic_proxy_opt {
+                graphic_proxy.mark_engine_failed(message);
+            }
+            if let Some(runtime_manager) = runtime_manager_opt.as_ref() {
+                dispatch_graphic_custom_event(runtime_manager, &event);
+            }
         }
         GraphicEvent::EngineShuttingDown => {
             info!("Graphic engine is shutting down");
-            warn!("TODO: Dispatch engine:shuttingDown event to mods");
+            if let Some(runtime_manager) = runtime_manager_opt.as_ref() {
+                dispatch_graphic_custom_event(runtime_manager, &event);
+            }
         }
         // Note: Widget events removed - use ECS entity event callbacks instead
 
         // Resource events
+        GraphicEvent::ResourceLoading { alias, asset_id } => {
+            trace!("Resource '{}' (asset_id={}) started loading in graphic engine", alias, asset_id);
+
+            // Let mods show loading UI before the resource is ready
+            if let Some(runtime_manager) = runtime_manager_opt.as_ref() {
+                let args_json = serde_json::json!({
+                    "alias": alias,
+                    "assetId": asset_id,
+                })
+                .to_string();
+                let request = stam_mod_runtimes::api::CustomEventRequest::new(
+                    "graphic:resource:loading",
+                    vec![args_json],
+                );
+                runtime_manager.dispatch_custom_event(&request);
+            }
+        }
         GraphicEvent::ResourceLoaded { alias, asset_id } => {
             debug!("Resource '{}' (asset_id={}) loaded by graphic engine", alias, asset_id);
 
@@ -1969,7 +3044,7 @@ fn handle_graphic_event(
                 runtime_manager.dispatch_custom_event(&request);
             }
         }
-        GraphicEvent::EntityEventCallback { entity_id, event_type, x, y } => {
+        GraphicEvent::EntityEventCallback { entity_id, event_type, x, y, value } => {
             trace!("Entity {} event '{}' callback triggered at ({}, {})", entity_id, event_type, x, y);
 
             // Dispatch direct callback to the mod that registered it
@@ -1978,7 +3053,8 @@ fn handle_graphic_event(
                 // Build event data
                 let event_data = serde_json::json!({
                     "x": x,
-                    "y": y
+                    "y": y,
+                    "value": value
                 });
 
                 // Invoke the callback directly in the runtime
@@ -1998,15 +3074,45 @@ fn handle_graphic_event(
     }
 }
 
+/// Why the game connection ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectionEnd {
+    /// The server closed the session on purpose (Disconnect or Error message)
+    Closed,
+    /// The connection dropped without a goodbye (network failure, server crash)
+    Lost,
+}
+
+/// Custom event dispatched to client mods when the server announces it is closing the session
+const SERVER_DISCONNECTING_EVENT: &str = "server:disconnecting";
+
+/// Something the game connection reports to the main loop
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConnectionEvent {
+    /// A server mod broadcast a message to every client (`system.broadcast`)
+    Broadcast { channel: String, payload: String },
+    /// The server is closing the session (`GameMessage::Disconnect`), the connection ends next
+    Disconnecting { reason: DisconnectReason, message: String },
+    /// The connection ended
+    Ended(ConnectionEnd),
+}
+
 /// Maintain game connection - read messages from server
-async fn maintain_game_connection(stream: &mut TcpStream, locale: Arc<LocaleManager>) {
+///
+/// Keepalive is answered here; returns when the connection ends or when a
+/// message must be handled by the main loop (which has the mod runtimes).
+async fn maintain_game_connection(stream: &mut StamStream, locale: Arc<LocaleManager>) -> ConnectionEvent {
     loop {
         match stream.read_game_message().await {
-            Ok(GameMessage::Disconnect { message }) => {
+            Ok(GameMessage::Disconnect { message, reason }) => {
                 // Message is a locale ID (e.g., "disconnect-server-shutdown")
                 let localized_msg = locale.get(&message);
                 info!("{}", localized_msg);
-                break;
+                return ConnectionEvent::Disconnecting { reason, message };
+            }
+            Ok(GameMessage::Broadcast { channel, payload }) => {
+                trace!("Received broadcast on channel '{}'", channel);
+                return ConnectionEvent::Broadcast { channel, payload };
             }
             Ok(GameMessage::Error { message }) => {
                 // Message could be a locale ID
@@ -2020,7 +3126,15 @@ async fn maintain_game_connection(stream: &mut TcpStream, locale: Arc<LocaleMana
                         })
                     )
                 );
-                break;
+                return ConnectionEvent::Ended(ConnectionEnd::Closed);
+            }
+            Ok(GameMessage::Ping { nonce }) => {
+                // Answer keepalive probes so the server doesn't drop us as a dead connection
+                trace!("Received ping {}, sending pong", nonce);
+                if let Err(e) = stream.write_game_message(&GameMessage::Pong { nonce }).await {
+                    debug!("Failed to send pong: {}", e);
+                    return ConnectionEvent::Ended(ConnectionEnd::Lost);
+                }
             }
             Ok(msg) => {
                 debug!("Received game message: {:?}", msg);
@@ -2028,12 +3142,203 @@ async fn maintain_game_connection(stream: &mut TcpStream, locale: Arc<LocaleMana
             }
             Err(e) => {
                 debug!("Connection closed: {}", e);
-                break;
+                return ConnectionEvent::Ended(ConnectionEnd::Lost);
             }
         }
     }
 }
 
+/// Everything needed to repeat the game login handshake after a connection loss
+struct GameSession {
+    host_port: String,
+    /// Whether the session was opened over TLS (stams://)
+    tls: bool,
+    username: String,
+    password_hash: String,
+    game_id: String,
+}
+
+/// Result of a single reconnection attempt
+enum ReconnectOutcome {
+    /// Logged in again, the new stream replaces the lost one
+    Connected(StamStream),
+    /// The attempt failed and the next one has been scheduled
+    Retrying,
+    /// All attempts failed (or reconnection is disabled)
+    GaveUp,
+}
+
+/// Upper bound for a single reconnection handshake (connect + Welcome + LoginSuccess)
+const RECONNECT_HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Decide what to do after the game connection ended
+///
+/// Returns true if a reconnection attempt has been scheduled, false if the client
+/// should shut down (server closed the session, reconnect disabled or exhausted).
+fn start_reconnecting(
+    end: ConnectionEnd,
+    reconnector: &mut Option<Reconnector>,
+    locale: &LocaleManager,
+) -> bool {
+    if end != ConnectionEnd::Lost {
+        return false;
+    }
+    let Some(reconnector) = reconnector.as_mut() else {
+        return false;
+    };
+    schedule_reconnect_attempt(reconnector, locale)
+}
+
+/// Schedule the next reconnection attempt, logging it (or that we gave up)
+fn schedule_reconnect_attempt(reconnector: &mut Reconnector, locale: &LocaleManager) -> bool {
+    match reconnector.schedule_next() {
+        Some((attempt, delay)) => {
+            info!(
+                "{}",
+                locale.get_with_args(
+                    "reconnect-scheduled",
+                    Some(&fluent_args! {
+                        "attempt" => attempt,
+                        "max" => reconnector.policy().max_attempts,
+                        "seconds" => delay.as_secs()
+                    })
+                )
+            );
+            true
+        }
+        None => {
+            error!(
+                "{}",
+                locale.get_with_args(
+                    "reconnect-gave-up",
+                    Some(&fluent_args! {
+                        "attempts" => reconnector.policy().max_attempts
+                    })
+                )
+            );
+            false
+        }
+    }
+}
+
+/// Wait until the scheduled reconnection attempt is due (forever if none is scheduled)
+///
+/// This is a plain timer, so the surrounding `select!` still reacts to Ctrl+C,
+/// SIGTERM and shutdown requests immediately while waiting.
+async fn wait_for_reconnect_attempt(reconnector: &Option<Reconnector>) {
+    match reconnector.as_ref().and_then(|r| r.next_attempt_at()) {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Make the scheduled reconnection attempt
+///
+/// Mods stay loaded across reconnects (no `onBootstrap`), so only the login
+/// handshake is repeated. If the server now requires a different mod set, the
+/// difference is logged; applying it needs a client restart.
+async fn try_reconnect(
+    session: &GameSession,
+    reconnector: &mut Option<Reconnector>,
+    loaded_mod_set: &mod_set::LoadedModSet,
+    locale: &LocaleManager,
+) -> ReconnectOutcome {
+    let Some(reconnector) = reconnector.as_mut() else {
+        return ReconnectOutcome::GaveUp;
+    };
+    reconnector.begin_attempt();
+
+    info!(
+        "{}",
+        locale.get_with_args(
+            "reconnect-attempt",
+            Some(&fluent_args! {
+                "attempt" => reconnector.attempt(),
+                "max" => reconnector.policy().max_attempts,
+                "host" => session.host_port.as_str()
+            })
+        )
+    );
+
+    let result = tokio::time::timeout(
+        RECONNECT_HANDSHAKE_TIMEOUT,
+        reestablish_game_session(session, loaded_mod_set),
+    )
+    .await
+    .unwrap_or_else(|_| Err("handshake timed out".into()));
+
+    match result {
+        Ok(stream) => {
+            info!("{}", locale.get("reconnect-success"));
+            reconnector.reset();
+            ReconnectOutcome::Connected(stream)
+        }
+        Err(e) => {
+            warn!(
+                "{}",
+                locale.get_with_args(
+                    "reconnect-failed",
+                    Some(&fluent_args! {
+                        "attempt" => reconnector.attempt(),
+                        "error" => e
+                    })
+                )
+            );
+            if schedule_reconnect_attempt(reconnector, locale) {
+                ReconnectOutcome::Retrying
+            } else {
+                ReconnectOutcome::GaveUp
+            }
+        }
+    }
+}
+
+/// Repeat the GameLogin handshake and return the new game stream
+async fn reestablish_game_session(
+    session: &GameSession,
+    loaded_mod_set: &mod_set::LoadedModSet,
+) -> Result<StamStream, Box<dyn std::error::Error + Send + Sync>> {
+    let mut stream = StamStream::connect(&session.host_port, session.tls).await?;
+
+    match stream.read_primal_message().await? {
+        PrimalMessage::Welcome { version, protocol_version, .. } => {
+            check_compatibility(PROTOCOL_VERSION, protocol_version)?;
+            debug!("Reconnected to server version {}", version);
+        }
+        msg => return Err(format!("unexpected message instead of Welcome: {:?}", msg).into()),
+    }
+
+    stream
+        .write_primal_message(&PrimalMessage::Intent {
+            intent_type: IntentType::GameLogin,
+            client_version: VERSION.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            username: session.username.clone(),
+            password_hash: session.password_hash.clone(),
+            game_id: Some(session.game_id.clone()),
+            uri: None,
+            compression: None,
+            range_start: None,
+        })
+        .await?;
+
+    match stream.read_game_message().await? {
+        GameMessage::LoginSuccess { mods, .. } => {
+            validate_server_mod_ids(mods.iter().map(|m| m.mod_id.as_str()))?;
+            let diff = loaded_mod_set.diff(&mods);
+            if !diff.is_empty() {
+                warn!(
+                    "Server mod set changed while disconnected (added: {:?}, removed: {:?}, updated: {:?}); restart the client to apply it",
+                    diff.added, diff.removed, diff.updated
+                );
+            }
+            Ok(stream)
+        }
+        GameMessage::Error { message } => Err(message.into()),
+        msg => Err(format!("unexpected message instead of LoginSuccess: {:?}", msg).into()),
+    }
+}
+
 /// Staminal Client - Connect to Staminal servers
 #[derive(Parser, Debug)]
 #[command(name = "stam_client")]
@@ -2058,7 +3363,12 @@ struct Args {
     #[arg(long, env = "STAM_HOME")]
     home: Option<String>,
 
-    /// Enable logging to file (stam_client.log in current directory)
+    /// Portable mode: store all data (config, mods, storage, logs) in a staminal-data folder
+    /// next to the executable, ignoring the system directories and STAM_HOME
+    #[arg(long, env = "STAM_PORTABLE")]
+    portable: bool,
+
+    /// Enable logging to file (stam_client.log in current directory, or in staminal-data/logs with --portable)
     #[arg(long, env = "STAM_LOG_FILE")]
     log_file: bool,
 
@@ -2066,9 +3376,54 @@ struct Args {
     #[arg(long, env = "STAM_LOG_LEVEL", default_value = "info")]
     log_level: String,
 
-    /// Game to connect to (by game_id or game_name). If not specified, connects to first available.
-    #[arg(short, long, env = "STAM_GAME")]
+    /// Game to connect to (by game_id or game_name). If not specified, the only available game
+    /// is used, or the user picks one when the server offers several and the terminal is interactive.
+    #[arg(short, long, visible_alias = "game-id", env = "STAM_GAME")]
     game: Option<String>,
+
+    /// Reconnect automatically (with exponential backoff) when the game connection is lost
+    #[arg(long, env = "STAM_RECONNECT")]
+    reconnect: bool,
+
+    /// Maximum number of reconnection attempts before giving up (used with --reconnect)
+    #[arg(long, env = "STAM_RECONNECT_ATTEMPTS", default_value_t = reconnect::DEFAULT_RECONNECT_ATTEMPTS)]
+    reconnect_attempts: u32,
+
+    /// Reload a mod when a file in its directory changes (for mod development)
+    #[arg(long, env = "STAM_WATCH")]
+    watch: bool,
+
+    /// Cache stam:// downloads requested by mods on disk and serve repeated requests from it
+    #[arg(long, env = "STAM_NETWORK_CACHE")]
+    network_cache: bool,
+
+    /// Download and validate every mod (manifests, dependencies, version requirements),
+    /// print a report and exit without running mods or starting the graphic engine.
+    /// Exits with a nonzero code if any mod is missing, circular or broken.
+    #[arg(long, env = "STAM_VALIDATE")]
+    validate: bool,
+
+    /// Always download mod archives instead of reusing identical ones from the shared cache
+    #[arg(long, env = "STAM_NO_CACHE")]
+    no_cache: bool,
+
+    /// Size cap of the shared mod archive cache in megabytes; least recently used archives are evicted
+    #[arg(long, env = "STAM_MOD_CACHE_MAX_MB", default_value_t = mod_cache::DEFAULT_MOD_CACHE_MAX_MB)]
+    mod_cache_max_mb: u64,
+
+    /// Run only locally installed mods: fail listing the missing required mods instead of
+    /// downloading them, and answer every download requested by mods with status 503
+    #[arg(long, env = "STAM_OFFLINE")]
+    offline: bool,
+
+    /// Open a new connection for every stam:// request instead of reusing idle ones
+    #[arg(long, env = "STAM_NO_CONNECTION_POOL")]
+    no_connection_pool: bool,
+
+    /// Extra mods directory searched before the game one, for mod development: a mod found
+    /// there (e.g. a working copy) is loaded instead of the installed one
+    #[arg(long, env = "STAM_MODS_DIR")]
+    mods_dir: Option<std::path::PathBuf>,
 }
 
 // ============================================================================
@@ -2085,7 +3440,7 @@ fn main() {
     let args = Args::parse();
 
     // Setup logging (must happen on main thread before spawning worker)
-    setup_logging(&args);
+    let log = setup_logging(&args);
 
     info!("========================================");
     info!("   STAMINAL CLIENT v{}", VERSION);
@@ -2128,9 +3483,24 @@ fn main() {
 
                     // Create and run Bevy engine on main thread (blocks until shutdown)
                     // Pass the initial window config and asset_root from the request
+                    // A panic during startup (e.g. no display available) is reported to the
+                    // worker as EngineError, which fails the pending enableEngine() call
+                    let error_tx = event_tx.clone();
                     let mut engine = BevyEngine::new(event_tx);
                     use stam_mod_runtimes::api::GraphicEngine;
-                    engine.run(cmd_rx, request.initial_window_config, request.asset_root);
+                    let run_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        engine.run(cmd_rx, request.initial_window_config, request.asset_root);
+                    }));
+
+                    if let Err(panic) = run_result {
+                        let message = panic
+                            .downcast_ref::<&str>()
+                            .map(|s| s.to_string())
+                            .or_else(|| panic.downcast_ref::<String>().cloned())
+                            .unwrap_or_else(|| "unknown panic".to_string());
+                        error!("Bevy engine panicked: {}", message);
+                        let _ = error_tx.blocking_send(GraphicEvent::EngineError { message });
+                    }
 
                     info!("Bevy engine has shut down");
                     // Engine has exited, continue to check for worker termination
@@ -2178,6 +3548,8 @@ fn main() {
     }
 
     debug!("Main thread exiting with code {}", exit_code);
+    // process::exit skips destructors, so flush buffered stdout and the log file explicitly
+    log.flush();
     std::process::exit(exit_code);
 }
 
@@ -2187,7 +3559,9 @@ fn main() {
 /// Uses STAM_LOGDEPS environment variable to control dependency logging:
 /// - STAM_LOGDEPS=0 (default): Only show logs from Staminal code
 /// - STAM_LOGDEPS=1: Show all logs including external dependencies (bevy, wgpu, etc.)
-fn setup_logging(args: &Args) {
+///
+/// Returns the handle that flushes the log writers before the process exits.
+fn setup_logging(args: &Args) -> LogHandle {
     let level = match args.log_level.to_lowercase().as_str() {
         "trace" => Level::TRACE,
         "debug" => Level::DEBUG,
@@ -2198,7 +3572,16 @@ fn setup_logging(args: &Args) {
     };
 
     let config = if args.log_file {
-        let file = std::fs::File::create("stam_client.log")
+        let log_path = if args.portable {
+            AppPaths::portable_dir()
+                .and_then(AppPaths::portable)
+                .and_then(|paths| paths.logs_dir())
+                .expect("Unable to create the portable logs directory")
+                .join("stam_client.log")
+        } else {
+            std::path::PathBuf::from("stam_client.log")
+        };
+        let file = std::fs::File::create(&log_path)
             .expect("Unable to create stam_client.log");
         LogConfig::new("stam_client::")
             .with_level(level)
@@ -2207,8 +3590,10 @@ fn setup_logging(args: &Args) {
         LogConfig::<std::fs::File>::new("stam_client::")
             .with_level(level)
     };
+    // Keep a mod logging in a loop from flooding the terminal and the log file
+    let config = config.with_mod_log_limit(ModLogLimit::default());
 
-    init_logging(config).expect("Failed to initialize logging");
+    init_logging(config).expect("Failed to initialize logging")
 }
 
 // ============================================================================
@@ -2244,15 +3629,86 @@ fn worker_thread_main(
 /// Main async client logic (runs in worker thread)
 ///
 /// Returns an exit code (0 = success, non-zero = error)
+/// Outcome of picking a game from the primal server's list
+#[derive(Debug, PartialEq, Eq)]
+enum ServerSelection {
+    /// Index of the chosen `ServerInfo`
+    Selected(usize),
+    /// The requested game is not in the list
+    NotFound,
+    /// No game was requested and the list has more than one entry
+    Ambiguous,
+}
+
+/// Pick a game by `game_id` or `game_name`, or the only game when none is requested
+fn select_server(servers: &[ServerInfo], game_filter: Option<&str>) -> ServerSelection {
+    match game_filter {
+        Some(filter) => servers
+            .iter()
+            .position(|s| s.game_id == filter || s.game_name == filter)
+            .map_or(ServerSelection::NotFound, ServerSelection::Selected),
+        None if servers.len() == 1 => ServerSelection::Selected(0),
+        None => ServerSelection::Ambiguous,
+    }
+}
+
+/// Whether the user can be asked to choose (both stdin and stdout are a TTY)
+fn is_interactive_terminal() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Print a numbered list of games and read the user's choice from stdin
+///
+/// Asks again on invalid input. Returns None when stdin is closed.
+async fn prompt_server_choice(servers: Vec<ServerInfo>, locale: Arc<LocaleManager>) -> Option<usize> {
+    // Reading stdin blocks, so keep it off the async runtime
+    tokio::task::spawn_blocking(move || {
+        for (i, server) in servers.iter().enumerate() {
+            println!("  [{}] {} on {} ({})", i + 1, server.game_name, server.server_name, server.game_id);
+        }
+
+        let count = servers.len();
+        loop {
+            print!("{}", locale.get_with_args("server-choose", Some(&fluent_args! { "count" => count })));
+            let _ = std::io::stdout().flush();
+
+            let mut line = String::new();
+            match std::io::stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => return None,
+                Ok(_) => {}
+            }
+            match line.trim().parse::<usize>() {
+                Ok(choice) if (1..=count).contains(&choice) => return Some(choice - 1),
+                _ => println!("{}", locale.get_with_args("server-choice-invalid", Some(&fluent_args! { "count" => count }))),
+            }
+        }
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
 async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngineRequest>) -> i32 {
     // Check if custom home is specified
     let custom_home = args.home.as_deref();
     if let Some(home) = custom_home {
-        debug!("Using custom home directory: {}", home);
+        if args.portable {
+            warn!("Ignoring custom home directory {} in portable mode", home);
+        } else {
+            debug!("Using custom home directory: {}", home);
+        }
     }
 
     // Initialize application paths (once at startup)
-    let app_paths = match AppPaths::new(custom_home) {
+    let app_paths = if args.portable {
+        AppPaths::portable_dir().and_then(|base| {
+            info!("Portable mode: storing data in {}", base.display());
+            AppPaths::portable(base)
+        })
+    } else {
+        AppPaths::new(custom_home)
+    };
+    let app_paths = match app_paths {
         Ok(paths) => paths,
         Err(e) => {
             error!("Failed to initialize application paths: {}", e);
@@ -2270,8 +3726,9 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
         }
     };
 
-    // Parse URI
-    if !args.uri.starts_with("stam://") {
+    // Parse URI (stams:// connects over TLS)
+    let tls = is_secure_stam_uri(&args.uri);
+    let Some(uri_without_scheme) = args.uri.strip_prefix(STAM_SCHEME).or_else(|| args.uri.strip_prefix(STAMS_SCHEME)) else {
         error!(
             "{}",
             locale.get_with_args(
@@ -2282,9 +3739,7 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
             )
         );
         return 1;
-    }
-
-    let uri_without_scheme = args.uri.strip_prefix("stam://").unwrap();
+    };
 
     // Parse username:password@host:port
     let (credentials, host_port) = if let Some(at_pos) = uri_without_scheme.find('@') {
@@ -2345,7 +3800,7 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
     );
 
     // Connect to server
-    let mut stream = match TcpStream::connect(host_port).await {
+    let mut stream = match StamStream::connect(host_port, tls).await {
         Ok(s) => {
             info!(
                 "{}",
@@ -2364,7 +3819,7 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
                 locale.get_with_args(
                     "connection-failed",
                     Some(&fluent_args! {
-                        "error" => e.to_string().as_str()
+                        "error" => e.as_str()
                     })
                 )
             );
@@ -2374,7 +3829,7 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
 
     // Read Welcome message
     match stream.read_primal_message().await {
-        Ok(PrimalMessage::Welcome { version }) => {
+        Ok(PrimalMessage::Welcome { version, protocol_version, .. }) => {
             info!(
                 "{}",
                 locale.get_with_args(
@@ -2385,40 +3840,9 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
                 )
             );
 
-            // Check version compatibility (major.minor must match)
-            let client_version_parts: Vec<&str> = VERSION.split('.').collect();
-            let server_version_parts: Vec<&str> = version.split('.').collect();
-
-            if client_version_parts.len() >= 2 && server_version_parts.len() >= 2 {
-                let client_major_minor =
-                    format!("{}.{}", client_version_parts[0], client_version_parts[1]);
-                let server_major_minor =
-                    format!("{}.{}", server_version_parts[0], server_version_parts[1]);
-
-                if client_major_minor != server_major_minor {
-                    error!(
-                        "{}",
-                        locale.get_with_args(
-                            "version-mismatch",
-                            Some(&fluent_args! {
-                                "client" => VERSION,
-                                "server" => version.as_str()
-                            })
-                        )
-                    );
-                    return 1;
-                }
-
-                info!(
-                    "{}",
-                    locale.get_with_args(
-                        "version-compatible",
-                        Some(&fluent_args! {
-                            "client" => VERSION,
-                            "server" => version.as_str()
-                        })
-                    )
-                );
+            // Check protocol compatibility (the app versions may differ)
+            if check_server_compatibility(&locale, &version, protocol_version).is_err() {
+                return 1;
             }
         }
         Ok(msg) => {
@@ -2440,10 +3864,13 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
     let intent = PrimalMessage::Intent {
         intent_type: IntentType::PrimalLogin,
         client_version: VERSION.to_string(),
+        protocol_version: PROTOCOL_VERSION,
         username: username.clone(),
         password_hash,
         game_id: None, // Not needed for PrimalLogin
         uri: None,
+        compression: None,
+        range_start: None,
     };
 
     if let Err(e) = stream.write_primal_message(&intent).await {
@@ -2471,53 +3898,76 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
 
             for (i, server) in servers.iter().enumerate() {
                 debug!(
-                    "  [{}] {} on {} (game_id: {}) - {}",
+                    "  [{}] {} on {} (game_id: {}) - {} - players: {}/{}",
                     i + 1,
                     server.game_name,
                     server.server_name,
                     server.game_id,
-                    server.uri
+                    server.uri,
+                    server.player_count,
+                    server.max_players.map(|m| m.to_string()).unwrap_or_else(|| "-".to_string())
                 );
+                if let Some(ref description) = server.description {
+                    debug!("      {}", description);
+                }
+                if let Some(ref icon_uri) = server.icon_uri {
+                    debug!("      icon: {}", icon_uri);
+                }
             }
 
-            // Select server based on --game argument or use first available
-            // Treat empty string as None (use first available)
+            // Select server based on --game argument, the only available game,
+            // or an interactive choice when several games are offered
+            // Treat empty string as None
             let game_filter = args.game.as_ref().and_then(|g| {
                 let trimmed = g.trim();
                 if trimmed.is_empty() { None } else { Some(trimmed.to_string()) }
             });
 
-            let selected_server = if let Some(ref filter) = game_filter {
-                // Try to find server by game_id or game_name
+            let available = || {
                 servers
                     .iter()
-                    .find(|s| s.game_id == *filter || s.game_name == *filter)
-            } else {
-                // Default to first server
-                servers.first()
+                    .map(|s| format!("{} ({})", s.game_name, s.game_id))
+                    .collect::<Vec<String>>()
+                    .join(", ")
             };
 
-            let selected_server = match selected_server {
-                Some(server) => server,
-                None => {
+            let selected_index = match select_server(&servers, game_filter.as_deref()) {
+                ServerSelection::Selected(index) => index,
+                ServerSelection::NotFound => {
                     // Game not found - show available games
-                    let available: Vec<String> = servers
-                        .iter()
-                        .map(|s| format!("{} ({})", s.game_name, s.game_id))
-                        .collect();
                     error!(
                         "{}",
                         locale.get_with_args(
                             "game-not-found",
                             Some(&fluent_args! {
                                 "game" => game_filter.as_deref().unwrap_or(""),
-                                "available" => available.join(", ").as_str()
+                                "available" => available().as_str()
+                            })
+                        )
+                    );
+                    return 1;
+                }
+                ServerSelection::Ambiguous if is_interactive_terminal() => {
+                    match prompt_server_choice(servers.clone(), locale.clone()).await {
+                        Some(index) => index,
+                        None => return 1,
+                    }
+                }
+                ServerSelection::Ambiguous => {
+                    error!(
+                        "{}",
+                        locale.get_with_args(
+                            "game-ambiguous",
+                            Some(&fluent_args! {
+                                "count" => servers.len(),
+                                "available" => available().as_str()
                             })
                         )
                     );
                     return 1;
                 }
             };
+            let selected_server = &servers[selected_index];
 
             info!(
                 "{}",
@@ -2536,7 +3986,7 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
             );
 
             // Parse game server URI and connect
-            if let Err(e) = connect_to_game_server(
+            match connect_to_game_server(
                 &selected_server.uri,
                 &username,
                 &password,
@@ -2544,19 +3994,34 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
                 locale.clone(),
                 &app_paths,
                 engine_request_tx,
+                args.reconnect.then(|| ReconnectPolicy::new(args.reconnect_attempts)),
+                args.watch,
+                args.network_cache,
+                args.validate,
+                (!args.no_cache).then(|| {
+                    ModArchiveCache::new(app_paths.mod_cache_dir(), args.mod_cache_max_mb.saturating_mul(1024 * 1024))
+                }),
+                args.offline,
+                (!args.no_connection_pool).then(ConnectionPool::new),
+                args.mods_dir.clone(),
             )
             .await
             {
-                error!(
-                    "{}",
-                    locale.get_with_args(
-                        "connection-failed",
-                        Some(&fluent_args! {
-                            "error" => e.to_string().as_str()
-                        })
-                    )
-                );
-                return 1;
+                Ok(0) => {}
+                // A mod asked to exit with an error code (system.exit)
+                Ok(exit_code) => return exit_code,
+                Err(e) => {
+                    error!(
+                        "{}",
+                        locale.get_with_args(
+                            "connection-failed",
+                            Some(&fluent_args! {
+                                "error" => e.as_str()
+                            })
+                        )
+                    );
+                    return 1;
+                }
             }
         }
         Ok(PrimalMessage::Error { message }) => {
@@ -2586,3 +4051,639 @@ async fn run_client(args: Args, engine_request_tx: std_mpsc::Sender<EnableEngine
     // Success
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    /// Spawn a one-shot fake server that answers a RequestUri with the given header
+    async fn spawn_uri_server(file_name: &str, file_size: Option<u64>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let file_name = file_name.to_string();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::Welcome {
+                    version: VERSION.to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    compression: None,
+                })
+                .await
+                .unwrap();
+            let _intent = socket.read_primal_message().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::UriResponse {
+                    status: 200,
+                    buffer_string: None,
+                    file_name: Some(file_name),
+                    file_size,
+                    range_start: None,
+                })
+                .await
+                .unwrap();
+            // Keep the connection open: the client must not wait for any chunk
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        addr
+    }
+
+    /// Spawn a one-shot fake server that streams the given raw chunks without a file size
+    async fn spawn_chunked_uri_server(file_name: &str, chunks: Vec<Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let file_name = file_name.to_string();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::Welcome {
+                    version: VERSION.to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    compression: None,
+                })
+                .await
+                .unwrap();
+            let _intent = socket.read_primal_message().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::UriResponse {
+                    status: 200,
+                    buffer_string: None,
+                    file_name: Some(file_name),
+                    file_size: None,
+                    range_start: None,
+                })
+                .await
+                .unwrap();
+            let last = chunks.len().saturating_sub(1);
+            for (i, chunk) in chunks.iter().enumerate() {
+                socket.write_raw_chunk(chunk, i == last).await.unwrap();
+            }
+            socket.flush().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        addr
+    }
+
+    /// Spawn a one-shot fake server that advertises `offered` and streams compressed chunks
+    async fn spawn_compressed_uri_server(file_name: &str, offered: Compression, chunks: Vec<Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let file_name = file_name.to_string();
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::Welcome {
+                    version: VERSION.to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    compression: Some(offered),
+                })
+                .await
+                .unwrap();
+            let compression = match socket.read_primal_message().await.unwrap() {
+                PrimalMessage::Intent { compression, .. } => compression,
+                other => panic!("expected Intent, got {:?}", other),
+            };
+            assert_eq!(compression, Some(offered));
+
+            let file_size = chunks.iter().map(|c| c.len() as u64).sum();
+            socket
+                .write_primal_message(&PrimalMessage::UriResponse {
+                    status: 200,
+                    buffer_string: None,
+                    file_name: Some(file_name),
+                    file_size: Some(file_size),
+                    range_start: None,
+                })
+                .await
+                .unwrap();
+            let last = chunks.len().saturating_sub(1);
+            for (i, chunk) in chunks.iter().enumerate() {
+                socket
+                    .write_raw_chunk_compressed(chunk, i == last, compression)
+                    .await
+                    .unwrap();
+            }
+            socket.flush().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        addr
+    }
+
+    /// Spawn a one-shot fake server that serves `content`, resuming from the requested
+    /// `range_start` only if `honor_range` is set
+    async fn spawn_range_uri_server(file_name: Option<&str>, content: Vec<u8>, honor_range: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let file_name = file_name.map(str::to_string);
+
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::Welcome {
+                    version: VERSION.to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    compression: None,
+                })
+                .await
+                .unwrap();
+            let requested = match socket.read_primal_message().await.unwrap() {
+                PrimalMessage::Intent { range_start, .. } => range_start,
+                other => panic!("expected Intent, got {:?}", other),
+            };
+            let range_start = requested.filter(|_| honor_range);
+            socket
+                .write_primal_message(&PrimalMessage::UriResponse {
+                    status: 200,
+                    buffer_string: None,
+                    file_name,
+                    file_size: Some(content.len() as u64),
+                    range_start,
+                })
+                .await
+                .unwrap();
+            let start = range_start.unwrap_or(0) as usize;
+            socket.write_raw_chunk(&content[start..], true).await.unwrap();
+            socket.flush().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        addr
+    }
+
+    #[test]
+    fn test_verify_sha512_detects_corrupted_archive() {
+        let dir = std::env::temp_dir().join(format!("stam_client_sha512_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("mod.tar.gz");
+        let content = b"pretend this is a mod archive";
+        std::fs::write(&archive, content).unwrap();
+
+        let mut hasher = Sha512::new();
+        hasher.update(content);
+        let digest = format!("{:x}", hasher.finalize());
+
+        // Good archive: plain, prefixed and uppercase digests all match
+        assert!(verify_sha512(&archive, &digest).is_ok());
+        assert!(verify_sha512(&archive, &format!("sha512:{}", digest)).is_ok());
+        assert!(verify_sha512(&archive, &digest.to_uppercase()).is_ok());
+
+        // Corrupted archive: a single flipped byte must be detected
+        let mut corrupted = content.to_vec();
+        corrupted[0] ^= 0xFF;
+        std::fs::write(&archive, &corrupted).unwrap();
+        let err = verify_sha512(&archive, &digest).unwrap_err();
+        assert!(err.contains("SHA-512 mismatch"), "unexpected error: {}", err);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_server_mod_ids_cannot_escape_the_mods_dir() {
+        assert!(validate_server_mod_ids(["bme-core", "mods-manager"]).is_ok());
+        for mod_id in ["..", "../../home", "mods/../../etc", "/etc", ""] {
+            let err = validate_server_mod_ids(["bme-core", mod_id]).unwrap_err();
+            assert!(err.contains("Invalid mod id"), "{}: {}", mod_id, err);
+        }
+    }
+
+    #[test]
+    fn test_unknown_size_chunk_buffer_starts_small() {
+        let buffer = unknown_size_chunk_buffer(None);
+        assert_eq!(buffer.len(), UNKNOWN_SIZE_CHUNK_BUFFER_INITIAL);
+        assert!(buffer.capacity() < 16 * 1024 * 1024);
+
+        // Known sizes read straight into the final buffer
+        assert_eq!(unknown_size_chunk_buffer(Some(0)).capacity(), 0);
+        assert_eq!(unknown_size_chunk_buffer(Some(1024)).capacity(), 0);
+    }
+
+    #[test]
+    fn test_buffer_response_keeps_valid_utf8_as_string() {
+        let response = buffer_response(200, "ciao è già".as_bytes().to_vec());
+        assert_eq!(response.buffer_string.as_deref(), Some("ciao è già"));
+        assert!(!response.is_binary);
+        assert!(response.file_content.is_none());
+    }
+
+    #[test]
+    fn test_buffer_response_returns_invalid_utf8_as_binary() {
+        let png_header = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        let response = buffer_response(200, png_header.clone());
+        assert!(response.is_binary);
+        assert!(response.buffer_string.is_none());
+        assert_eq!(response.file_content, Some(png_header));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_size_download_grows_chunk_buffer() {
+        let small = b"hello".to_vec();
+        let large = vec![7u8; UNKNOWN_SIZE_CHUNK_BUFFER_INITIAL * 2 + 3];
+        let addr = spawn_chunked_uri_server("data.bin", vec![small.clone(), large.clone()]).await;
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            perform_stam_request(
+                &format!("stam://{}/data.bin", addr),
+                IntentType::RequestUri,
+                "user",
+                "hash",
+                "demo",
+                VERSION,
+                &addr,
+                false,
+                None,
+                DEFAULT_MAX_BUFFER_BYTES,
+                None,
+                None,
+                None,
+            ),
+        )
+        .await
+        .expect("chunked download should complete");
+
+        assert_eq!(response.status, 200);
+        let content = response.file_content.expect("content should be kept in memory without tmp_dir");
+        assert_eq!(content.len(), small.len() + large.len());
+        assert_eq!(&content[..small.len()], &small[..]);
+    }
+
+    #[tokio::test]
+    async fn test_list_request_returns_parsed_listing() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::Welcome {
+                    version: VERSION.to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    compression: None,
+                })
+                .await
+                .unwrap();
+            let intent = socket.read_primal_message().await.unwrap();
+            assert!(matches!(intent, PrimalMessage::Intent { intent_type: IntentType::ListUri, range_start: None, .. }));
+            socket
+                .write_primal_message(&PrimalMessage::UriResponse {
+                    status: 200,
+                    buffer_string: Some(r#"[{"name":"maps","size":0,"is_dir":true},{"name":"a.txt","size":3,"is_dir":false}]"#.to_string()),
+                    file_name: None,
+                    file_size: None,
+                    range_start: None,
+                })
+                .await
+                .unwrap();
+        });
+
+        let response = perform_stam_request(
+            &format!("stam://{}/data", addr),
+            IntentType::ListUri,
+            "user",
+            "hash",
+            "demo",
+            VERSION,
+            &addr,
+            false,
+            None,
+            DEFAULT_MAX_BUFFER_BYTES,
+            None,
+            None,
+            None,
+        )
+        .await;
+
+        assert_eq!(response.status, 200);
+        let listing = response.listing.expect("listing should be parsed");
+        assert_eq!(listing.len(), 2);
+        assert_eq!(listing[0], UriListEntry { name: "maps".into(), size: 0, is_dir: true });
+        assert!(response.buffer_string.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_gzip_download_reports_uncompressed_progress() {
+        let first = vec![b'a'; 64 * 1024];
+        let second = b"tail".repeat(1024);
+        let total = (first.len() + second.len()) as u64;
+        let addr = spawn_compressed_uri_server("data.txt", Compression::Gzip, vec![first.clone(), second.clone()]).await;
+
+        let last_progress = Arc::new(std::sync::Mutex::new((0.0, 0u64, 0u64)));
+        let progress = last_progress.clone();
+        let callback: stam_mod_runtimes::api::ProgressCallback = Arc::new(move |pct, received, size| {
+            *progress.lock().unwrap() = (pct, received, size);
+        });
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            perform_stam_request(
+                &format!("stam://{}/data.txt", addr),
+                IntentType::RequestUri,
+                "user",
+                "hash",
+                "demo",
+                VERSION,
+                &addr,
+                false,
+                None,
+                DEFAULT_MAX_BUFFER_BYTES,
+                Some(callback),
+                None,
+                None,
+            ),
+        )
+        .await
+        .expect("compressed download should complete");
+
+        assert_eq!(response.status, 200);
+        let content = response.file_content.expect("content should be kept in memory without tmp_dir");
+        assert_eq!(content, [first, second].concat());
+
+        // Progress counts decompressed bytes, so it matches the advertised file size
+        let (pct, received, size) = *last_progress.lock().unwrap();
+        assert_eq!(received, total);
+        assert_eq!(size, total);
+        assert_eq!(pct, 100.0);
+        assert_eq!(response.transfer_stats.bytes_received, total);
+    }
+
+    #[tokio::test]
+    async fn test_zstd_download_uses_the_offered_algorithm() {
+        let content = b"menu.play = Play\n".repeat(512);
+        let addr = spawn_compressed_uri_server("en.ftl", Compression::Zstd, vec![content.clone()]).await;
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            perform_stam_request(
+                &format!("stam://{}/en.ftl", addr),
+                IntentType::RequestUri,
+                "user",
+                "hash",
+                "demo",
+                VERSION,
+                &addr,
+                false,
+                None,
+                DEFAULT_MAX_BUFFER_BYTES,
+                None,
+                None,
+                None,
+            ),
+        )
+        .await
+        .expect("zstd download should complete");
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.file_content.expect("content should be kept in memory"), content);
+    }
+
+    #[tokio::test]
+    async fn test_pooled_connection_serves_several_requests() {
+        // The fake server accepts a single connection: a second handshake would hang
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_primal_message(&PrimalMessage::Welcome {
+                    version: VERSION.to_string(),
+                    protocol_version: PROTOCOL_VERSION,
+                    compression: None,
+                })
+                .await
+                .unwrap();
+            for body in ["first", "second"] {
+                let _intent = socket.read_primal_message().await.unwrap();
+                socket
+                    .write_primal_message(&PrimalMessage::UriResponse {
+                        status: 200,
+                        buffer_string: Some(body.to_string()),
+                        file_name: None,
+                        file_size: None,
+                        range_start: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        let pool = ConnectionPool::new();
+        for expected in ["first", "second"] {
+            let response = tokio::time::timeout(
+                std::time::Duration::from_secs(2),
+                perform_stam_request(
+                    &format!("stam://{}/data.txt", addr),
+                    IntentType::RequestUri,
+                    "user",
+                    "hash",
+                    "demo",
+                    VERSION,
+                    &addr,
+                    false,
+                    None,
+                    DEFAULT_MAX_BUFFER_BYTES,
+                    None,
+                    None,
+                    Some(&pool),
+                ),
+            )
+            .await
+            .expect("request over the pooled connection should complete");
+            assert_eq!(response.status, 200);
+            assert_eq!(response.buffer_string.as_deref(), Some(expected));
+        }
+
+        let stats = pool.stats();
+        assert_eq!((stats.handshakes, stats.reused), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_download_removes_part_file() {
+        let addr = spawn_chunked_uri_server("data.bin", vec![vec![1u8; 16], vec![2u8; 16], vec![3u8; 16]]).await;
+        let tmp_dir = std::env::temp_dir().join(format!("stam_client_cancel_{}", std::process::id()));
+
+        // Cancel as soon as the first chunk is received
+        let token = CancelToken::new();
+        let canceller = token.clone();
+        let callback: stam_mod_runtimes::api::ProgressCallback = Arc::new(move |_, _, _| canceller.cancel());
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            perform_stam_request(
+                &format!("stam://{}/data.bin", addr),
+                IntentType::RequestUri,
+                "user",
+                "hash",
+                "demo",
+                VERSION,
+                &addr,
+                false,
+                Some(&tmp_dir),
+                DEFAULT_MAX_BUFFER_BYTES,
+                Some(callback),
+                Some(&token),
+                None,
+            ),
+        )
+        .await
+        .expect("cancelled download should return");
+
+        assert_eq!(response.status, STATUS_CANCELLED);
+        assert!(response.temp_file_path.is_none());
+        let leftovers = std::fs::read_dir(&tmp_dir).map(|entries| entries.count()).unwrap_or(0);
+        assert_eq!(leftovers, 0, "the partial download must be removed");
+        let _ = std::fs::remove_dir_all(&tmp_dir);
+    }
+
+    /// Download `content` from a range-aware fake server with a pre-existing part file
+    ///
+    /// Returns the downloaded content and the number of bytes actually transferred.
+    async fn download_with_part_file(test_name: &str, content: &[u8], part: &[u8], honor_range: bool) -> (Vec<u8>, u64) {
+        let addr = spawn_range_uri_server(Some("mod.zip"), content.to_vec(), honor_range).await;
+        let uri = format!("stam://{}/mod.zip", addr);
+        let tmp_dir = std::env::temp_dir().join(format!("stam_client_{}_{}", test_name, std::process::id()));
+        std::fs::create_dir_all(&tmp_dir).unwrap();
+        let part_path = tmp_dir.join(partial_download_name(&addr, &sanitize_uri(&uri)));
+        std::fs::write(&part_path, part).unwrap();
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            perform_stam_request(&uri, IntentType::RequestUri, "user", "hash", "demo", VERSION, &addr, false, Some(&tmp_dir), DEFAULT_MAX_BUFFER_BYTES, None, None, None),
+        )
+        .await
+        .expect("download should complete");
+
+        assert_eq!(response.status, 200);
+        assert!(!part_path.exists(), "part file should be renamed once complete");
+        let temp_path = response.temp_file_path.expect("download should be written to disk");
+        let downloaded = std::fs::read(&temp_path).unwrap();
+        let _ = std::fs::remove_dir_all(&tmp_dir);
+        (downloaded, response.transfer_stats.bytes_received)
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_from_part_file() {
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let (downloaded, transferred) = download_with_part_file("resume", &content, &content[..4_000], true).await;
+        assert_eq!(downloaded, content);
+        assert_eq!(transferred, 6_000, "resumed bytes are not part of the transfer");
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_when_range_is_ignored() {
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        // Stale part file contents must be discarded, not prepended
+        let (downloaded, transferred) = download_with_part_file("restart", &content, &[0xFF; 4_000], false).await;
+        assert_eq!(downloaded, content);
+        assert_eq!(transferred, 10_000);
+    }
+
+    #[tokio::test]
+    async fn test_large_buffer_is_streamed_to_disk() {
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let total = content.len() as u64;
+        let addr = spawn_range_uri_server(None, content.clone(), false).await;
+        let tmp_dir = std::env::temp_dir().join(format!("stam_client_stream_{}", std::process::id()));
+
+        let last_progress = Arc::new(std::sync::Mutex::new((0.0, 0u64, 0u64)));
+        let progress = last_progress.clone();
+        let callback: stam_mod_runtimes::api::ProgressCallback = Arc::new(move |pct, received, size| {
+            *progress.lock().unwrap() = (pct, received, size);
+        });
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            perform_stam_request(
+                &format!("stam://{}/data.bin", addr),
+                IntentType::RequestUri,
+                "user",
+                "hash",
+                "demo",
+                VERSION,
+                &addr,
+                false,
+                Some(&tmp_dir),
+                1024,
+                Some(callback),
+                None,
+                None,
+            ),
+        )
+        .await
+        .expect("streamed download should complete");
+
+        assert_eq!(response.status, 200);
+        assert!(response.buffer_string.is_none());
+        assert!(response.file_content.is_none(), "content above the limit must not be kept in memory");
+        let temp_path = response.temp_file_path.expect("content above the limit should be written to disk");
+        assert_eq!(std::fs::read(&temp_path).unwrap(), content);
+        assert_eq!(*last_progress.lock().unwrap(), (100.0, total, total));
+        assert_eq!(response.transfer_stats.bytes_received, total);
+
+        let _ = std::fs::remove_dir_all(&tmp_dir);
+    }
+
+    #[tokio::test]
+    async fn test_empty_file_download() {
+        let addr = spawn_uri_server("empty.txt", Some(0)).await;
+        let tmp_dir = std::env::temp_dir().join(format!("stam_client_test_{}", std::process::id()));
+
+        let response = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            perform_stam_request(
+                &format!("stam://{}/empty.txt", addr),
+                IntentType::RequestUri,
+                "user",
+                "hash",
+                "demo",
+                VERSION,
+                &addr,
+                false,
+                Some(&tmp_dir),
+                DEFAULT_MAX_BUFFER_BYTES,
+                None,
+                None,
+                None,
+            ),
+        )
+        .await
+        .expect("empty file download must not wait for chunks");
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.file_name.as_deref(), Some("empty.txt"));
+        let temp_path = response.temp_file_path.expect("empty file should be written to disk");
+        assert_eq!(std::fs::metadata(&temp_path).unwrap().len(), 0);
+
+        let _ = std::fs::remove_dir_all(&tmp_dir);
+    }
+
+    #[test]
+    fn test_select_server() {
+        let server = |game_id: &str, game_name: &str| ServerInfo {
+            game_id: game_id.to_string(),
+            game_name: game_name.to_string(),
+            server_name: "Test Realm".to_string(),
+            uri: "stam://127.0.0.1:7777".to_string(),
+            description: None,
+            player_count: 0,
+            max_players: None,
+            icon_uri: None,
+        };
+
+        let single = vec![server("demo", "Demo Game")];
+        assert_eq!(select_server(&single, None), ServerSelection::Selected(0));
+
+        let servers = vec![server("demo", "Demo Game"), server("arena", "Arena")];
+        assert_eq!(select_server(&servers, None), ServerSelection::Ambiguous);
+        assert_eq!(select_server(&servers, Some("arena")), ServerSelection::Selected(1));
+        assert_eq!(select_server(&servers, Some("Demo Game")), ServerSelection::Selected(0));
+        assert_eq!(select_server(&servers, Some("missing")), ServerSelection::NotFound);
+    }
+}
@@ -0,0 +1,234 @@
+/// Content-addressed cache of mod archives
+///
+/// Archives are stored once under `data_dir/cache/mods/<sha512>.tar.gz`, keyed by
+/// the `archive_sha512` announced by the server, so a mod version required by
+/// several games (or re-installed after its directory was removed) is extracted
+/// from the cache instead of being downloaded again. Entries are hard-linked (or
+/// copied when linking fails) in and out of the cache, and the least recently
+/// used ones are evicted once the cache grows beyond its size cap.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha512};
+use tracing::{debug, warn};
+
+/// Default size cap of the cache, in megabytes
+pub const DEFAULT_MOD_CACHE_MAX_MB: u64 = 1024;
+
+/// Shared mod archive cache
+#[derive(Clone, Debug)]
+pub struct ModArchiveCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl ModArchiveCache {
+    /// Create a cache stored in `dir` (created on first write) holding at most `max_bytes`
+    pub fn new(dir: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes,
+        }
+    }
+
+    /// Path of the entry for `sha512`, `None` if it is not a SHA-512 hex digest
+    fn entry_path(&self, sha512: &str) -> Option<PathBuf> {
+        let valid = sha512.len() == 128 && sha512.bytes().all(|b| b.is_ascii_hexdigit());
+        valid.then(|| self.dir.join(format!("{}.tar.gz", sha512.to_ascii_lowercase())))
+    }
+
+    /// Place the cached archive with digest `sha512` at `dest`
+    ///
+    /// The entry is verified against its digest first (a corrupted entry is
+    /// removed) and becomes the most recently used one.
+    ///
+    /// # Returns
+    /// `true` if `dest` now holds the archive, `false` on a cache miss
+    pub fn fetch(&self, sha512: &str, dest: &Path) -> bool {
+        let Some(entry) = self.entry_path(sha512) else {
+            return false;
+        };
+        if !entry.is_file() {
+            return false;
+        }
+
+        match file_sha512(&entry) {
+            Ok(actual) if actual.eq_ignore_ascii_case(sha512) => {}
+            Ok(_) => {
+                warn!("Cached mod archive '{}' is corrupted, removing it", entry.display());
+                let _ = fs::remove_file(&entry);
+                return false;
+            }
+            Err(e) => {
+                warn!("Failed to read cached mod archive '{}': {}", entry.display(), e);
+                return false;
+            }
+        }
+
+        if let Err(e) = link_or_copy(&entry, dest) {
+            warn!("Failed to take mod archive from the cache: {}", e);
+            return false;
+        }
+        touch(&entry);
+        true
+    }
+
+    /// Add a verified archive with digest `sha512` to the cache, then enforce the size cap
+    pub fn store(&self, sha512: &str, archive: &Path) {
+        let Some(entry) = self.entry_path(sha512) else {
+            warn!("Not caching mod archive: '{}' is not a SHA-512 digest", sha512);
+            return;
+        };
+
+        if entry.is_file() {
+            touch(&entry);
+        } else if let Err(e) = fs::create_dir_all(&self.dir).and_then(|_| link_or_copy(archive, &entry)) {
+            warn!("Failed to cache mod archive '{}': {}", archive.display(), e);
+            return;
+        }
+
+        match self.evict() {
+            Ok(0) => {}
+            Ok(freed) => debug!("Evicted {} bytes of least recently used mod archives", freed),
+            Err(e) => warn!("Failed to evict mod archive cache entries: {}", e),
+        }
+    }
+
+    /// Remove the least recently used entries until the cache fits its size cap
+    ///
+    /// # Returns
+    /// The number of bytes freed
+    fn evict(&self) -> io::Result<u64> {
+        let mut entries: Vec<(SystemTime, u64, PathBuf)> = Vec::new();
+        for dir_entry in fs::read_dir(&self.dir)?.flatten() {
+            let path = dir_entry.path();
+            if !path.to_string_lossy().ends_with(".tar.gz") {
+                continue;
+            }
+            let metadata = dir_entry.metadata()?;
+            if metadata.is_file() {
+                entries.push((metadata.modified()?, metadata.len(), path));
+            }
+        }
+
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        entries.sort();
+        let mut freed = 0;
+        for (_, size, path) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(&path)?;
+            total -= size;
+            freed += size;
+        }
+        Ok(freed)
+    }
+}
+
+/// Hard-link `from` to `to`, copying through a `.part` file when linking is not possible
+fn link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    if to.exists() {
+        fs::remove_file(to)?;
+    }
+    if fs::hard_link(from, to).is_ok() {
+        return Ok(());
+    }
+    let part = to.with_extension("part");
+    fs::copy(from, &part)?;
+    fs::rename(&part, to)
+}
+
+/// Mark an entry as just used (recency is tracked through the modification time)
+fn touch(path: &Path) {
+    if let Err(e) = fs::File::options().write(true).open(path).and_then(|f| f.set_modified(SystemTime::now())) {
+        debug!("Failed to refresh cache entry '{}': {}", path.display(), e);
+    }
+}
+
+/// SHA-512 (lowercase hex) of a file's content
+fn file_sha512(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha512::new();
+    io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn temp_dir(test_name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("stam_mod_cache_{}_{}", test_name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_archive(dir: &Path, name: &str, content: &[u8]) -> (PathBuf, String) {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        let sha512 = file_sha512(&path).unwrap();
+        (path, sha512)
+    }
+
+    #[test]
+    fn test_store_and_fetch() {
+        let temp = temp_dir("store_and_fetch");
+        let cache = ModArchiveCache::new(temp.join("cache"), 1024);
+        let (archive, sha512) = write_archive(&temp, "mod.tar.gz", b"archive");
+
+        let dest = temp.join("fetched.tar.gz");
+        assert!(!cache.fetch(&sha512, &dest));
+
+        cache.store(&sha512, &archive);
+        fs::remove_file(&archive).unwrap();
+        assert!(cache.fetch(&sha512.to_ascii_uppercase(), &dest));
+        assert_eq!(fs::read(&dest).unwrap(), b"archive");
+
+        // Invalid digests never touch the filesystem
+        assert!(!cache.fetch("../escape", &dest));
+        let _ = fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn test_corrupted_entry_is_dropped() {
+        let temp = temp_dir("corrupted");
+        let cache = ModArchiveCache::new(temp.join("cache"), 1024);
+        let (archive, sha512) = write_archive(&temp, "mod.tar.gz", b"archive");
+        cache.store(&sha512, &archive);
+        fs::remove_file(&archive).unwrap();
+
+        let entry = cache.entry_path(&sha512).unwrap();
+        fs::write(&entry, b"tampered").unwrap();
+        assert!(!cache.fetch(&sha512, &temp.join("fetched.tar.gz")));
+        assert!(!entry.exists());
+        let _ = fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let temp = temp_dir("lru");
+        let cache = ModArchiveCache::new(temp.join("cache"), 10);
+        let (first, first_sha) = write_archive(&temp, "first.tar.gz", b"123456");
+        let (second, second_sha) = write_archive(&temp, "second.tar.gz", b"abcdef");
+
+        cache.store(&first_sha, &first);
+        let entry = cache.entry_path(&first_sha).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&entry)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(60))
+            .unwrap();
+
+        // 12 bytes > 10: the older entry goes
+        cache.store(&second_sha, &second);
+        assert!(!entry.exists());
+        assert!(cache.entry_path(&second_sha).unwrap().exists());
+        let _ = fs::remove_dir_all(&temp);
+    }
+}
@@ -0,0 +1,296 @@
+/// Mod dependency resolution for the download and attach phases
+///
+/// Walks the `requires` of the bootstrap mods to find every mod needed before
+/// bootstrapping. Mods that are not available locally yet have no manifest, so
+/// their dependencies are discovered once they are downloaded. Once everything is
+/// downloaded, `collect_load_order` checks the graph strictly before attaching.
+
+use std::fmt;
+
+use stam_mod_runtimes::api::{LoadPlan, LoadReason, PlannedMod};
+use stam_schema::{ModManifest, version_satisfies};
+
+/// Error raised while resolving mod dependencies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyError {
+    /// The mods require each other in a loop
+    CircularDependency {
+        /// The loop, starting and ending with the same mod (e.g. `[a, b, a]`)
+        cycle: Vec<String>,
+    },
+    /// A required mod is not available locally
+    MissingDependency {
+        /// ID of the unavailable mod
+        mod_id: String,
+    },
+    /// A mod's requirement on another mod cannot be checked or is not satisfied
+    VersionMismatch {
+        /// ID of the mod declaring the requirement
+        mod_id: String,
+        /// ID of the required mod
+        dependency: String,
+        /// What went wrong (e.g. "requires ^2.0.0 but version 1.4.0 is installed")
+        message: String,
+    },
+}
+
+impl fmt::Display for DependencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DependencyError::CircularDependency { cycle } => {
+                write!(f, "Circular dependency detected: {}", cycle.join(" -> "))
+            }
+            DependencyError::MissingDependency { mod_id } => {
+                write!(f, "Dependency '{}' not found in available mods", mod_id)
+            }
+            DependencyError::VersionMismatch { mod_id, dependency, message } => {
+                write!(f, "Mod '{}' has an unsatisfied requirement on '{}': {}", mod_id, dependency, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DependencyError {}
+
+/// Collect the given mods and their dependencies, dependencies first
+///
+/// `manifest_of` returns the manifest of a locally available mod; mods without
+/// one are included but their dependencies can't be followed yet. Engine
+/// requirements (`@client`, `@server`, `@game`) are skipped.
+pub fn collect_required_mods<'a>(
+    mod_ids: &[&str],
+    manifest_of: impl Fn(&str) -> Option<&'a ModManifest>,
+) -> Result<Vec<String>, DependencyError> {
+    let mut required = Vec::new();
+    let mut chain = Vec::new();
+    for mod_id in mod_ids {
+        collect_recursive(mod_id, &manifest_of, &mut required, &mut chain)?;
+    }
+    Ok(required)
+}
+
+/// Collect the given bootstrap mods and their dependencies in attach order, dependencies first
+///
+/// Unlike `collect_required_mods`, every dependency must be available and its
+/// installed version must satisfy the requirement. The plan records each mod's
+/// priority and which mods required it; it is not sorted by priority yet.
+pub fn collect_load_order<'a>(
+    mod_ids: &[&str],
+    manifest_of: impl Fn(&str) -> Option<&'a ModManifest>,
+) -> Result<LoadPlan, DependencyError> {
+    let mut plan = LoadPlan::new();
+    let mut chain = Vec::new();
+    for mod_id in mod_ids {
+        collect_load_order_recursive(mod_id, None, &manifest_of, &mut plan, &mut chain)?;
+    }
+    Ok(plan)
+}
+
+fn collect_load_order_recursive<'a>(
+    mod_id: &str,
+    required_by: Option<&str>,
+    manifest_of: &impl Fn(&str) -> Option<&'a ModManifest>,
+    plan: &mut LoadPlan,
+    chain: &mut Vec<String>,
+) -> Result<(), DependencyError> {
+    if let Some(start) = chain.iter().position(|id| id == mod_id) {
+        let mut cycle = chain[start..].to_vec();
+        cycle.push(mod_id.to_string());
+        return Err(DependencyError::CircularDependency { cycle });
+    }
+    if let Some(planned) = plan.get_mut(mod_id) {
+        // Already planned as a dependency of an earlier mod
+        match required_by {
+            Some(requirer) => planned.required_by.push(requirer.to_string()),
+            None => planned.reason = LoadReason::Bootstrap,
+        }
+        return Ok(());
+    }
+
+    let manifest = manifest_of(mod_id).ok_or_else(|| DependencyError::MissingDependency {
+        mod_id: mod_id.to_string(),
+    })?;
+
+    chain.push(mod_id.to_string());
+    let mut dependencies: Vec<(&String, &String)> = manifest
+        .requires
+        .iter()
+        .filter(|(dep_id, _)| !dep_id.starts_with('@'))
+        .collect();
+    dependencies.sort();
+    for (dep_id, version_req) in dependencies {
+        // Refuse a dependency whose installed version doesn't match the constraint
+        if let Some(dep_manifest) = manifest_of(dep_id) {
+            let installed = &dep_manifest.version;
+            let mismatch = |message: String| DependencyError::VersionMismatch {
+                mod_id: mod_id.to_string(),
+                dependency: dep_id.clone(),
+                message,
+            };
+            let satisfied = version_satisfies(installed, version_req)
+                .map_err(|e| mismatch(format!("invalid requirement: {}", e)))?;
+            if !satisfied {
+                return Err(mismatch(format!(
+                    "requires {} but version {} is installed",
+                    version_req, installed
+                )));
+            }
+        }
+        collect_load_order_recursive(dep_id, Some(mod_id), manifest_of, plan, chain)?;
+    }
+    chain.pop();
+
+    plan.push(PlannedMod {
+        mod_id: mod_id.to_string(),
+        priority: manifest.priority,
+        reason: if required_by.is_some() { LoadReason::Dependency } else { LoadReason::Bootstrap },
+        required_by: required_by.map(str::to_string).into_iter().collect(),
+    });
+    Ok(())
+}
+
+fn collect_recursive<'a>(
+    mod_id: &str,
+    manifest_of: &impl Fn(&str) -> Option<&'a ModManifest>,
+    required: &mut Vec<String>,
+    chain: &mut Vec<String>,
+) -> Result<(), DependencyError> {
+    if let Some(start) = chain.iter().position(|id| id == mod_id) {
+        let mut cycle = chain[start..].to_vec();
+        cycle.push(mod_id.to_string());
+        return Err(DependencyError::CircularDependency { cycle });
+    }
+    if required.iter().any(|id| id == mod_id) {
+        return Ok(());
+    }
+
+    chain.push(mod_id.to_string());
+    if let Some(manifest) = manifest_of(mod_id) {
+        // Sorted so the reported cycle doesn't depend on HashMap order
+        let mut dependencies: Vec<&String> = manifest
+            .requires
+            .keys()
+            .filter(|dep_id| !dep_id.starts_with('@'))
+            .collect();
+        dependencies.sort();
+        for dep_id in dependencies {
+            collect_recursive(dep_id, manifest_of, required, chain)?;
+        }
+    }
+    chain.pop();
+
+    required.push(mod_id.to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stam_schema::Validatable;
+    use std::collections::HashMap;
+
+    fn manifest(name: &str, requires: &[&str]) -> ModManifest {
+        let requires: Vec<String> = requires.iter().map(|dep| format!(r#""{}": "*""#, dep)).collect();
+        ModManifest::from_json_str(&format!(
+            r#"{{"name": "{}", "version": "1.0.0", "description": "test", "requires": {{{}}}}}"#,
+            name,
+            requires.join(", ")
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dependencies_come_first() {
+        let manifests: HashMap<&str, ModManifest> = [
+            ("game", manifest("game", &["ui", "@client"])),
+            ("ui", manifest("ui", &["core"])),
+        ]
+        .into_iter()
+        .collect();
+
+        // "core" is not downloaded yet: it is still required, its deps unknown
+        let required = collect_required_mods(&["game"], |id| manifests.get(id)).unwrap();
+        assert_eq!(required, vec!["core", "ui", "game"]);
+    }
+
+    #[test]
+    fn test_cycle_reports_every_mod_in_the_loop() {
+        let manifests: HashMap<&str, ModManifest> = [
+            ("game", manifest("game", &["alpha"])),
+            ("alpha", manifest("alpha", &["beta"])),
+            ("beta", manifest("beta", &["gamma"])),
+            ("gamma", manifest("gamma", &["alpha"])),
+        ]
+        .into_iter()
+        .collect();
+
+        let err = collect_required_mods(&["game"], |id| manifests.get(id)).unwrap_err();
+        assert_eq!(
+            err,
+            DependencyError::CircularDependency {
+                cycle: vec!["alpha".into(), "beta".into(), "gamma".into(), "alpha".into()],
+            }
+        );
+        assert_eq!(err.to_string(), "Circular dependency detected: alpha -> beta -> gamma -> alpha");
+    }
+
+    #[test]
+    fn test_load_order_requires_every_dependency() {
+        let mut manifests: HashMap<&str, ModManifest> = [
+            ("game", manifest("game", &["ui", "@client"])),
+            ("ui", manifest("ui", &["core"])),
+        ]
+        .into_iter()
+        .collect();
+
+        let err = collect_load_order(&["game"], |id| manifests.get(id)).unwrap_err();
+        assert_eq!(err, DependencyError::MissingDependency { mod_id: "core".into() });
+
+        manifests.insert("core", manifest("core", &[]));
+        let order = collect_load_order(&["game"], |id| manifests.get(id)).unwrap();
+        assert_eq!(order.mod_ids(), vec!["core", "ui", "game"]);
+    }
+
+    #[test]
+    fn test_load_plan_records_reasons() {
+        let mut game = manifest("game", &["ui", "core"]);
+        game.priority = 5;
+        let manifests: HashMap<&str, ModManifest> = [
+            ("game", game),
+            ("ui", manifest("ui", &["core"])),
+            ("core", manifest("core", &[])),
+            ("menu", manifest("menu", &["ui"])),
+        ]
+        .into_iter()
+        .collect();
+
+        // "ui" is a bootstrap mod too, but "game" pulls it in first
+        let mut plan = collect_load_order(&["game", "menu", "ui"], |id| manifests.get(id)).unwrap();
+        assert_eq!(plan.mod_ids(), vec!["core", "ui", "game", "menu"]);
+
+        let core = plan.get("core").unwrap();
+        assert_eq!(core.reason, LoadReason::Dependency);
+        assert_eq!(core.required_by, vec!["game", "ui"]);
+        let ui = plan.get("ui").unwrap();
+        assert_eq!(ui.reason, LoadReason::Bootstrap);
+        assert_eq!(ui.required_by, vec!["game", "menu"]);
+        assert_eq!(plan.get("game").unwrap().priority, 5);
+
+        plan.sort_by_priority();
+        assert_eq!(plan.mod_ids(), vec!["core", "ui", "menu", "game"]);
+    }
+
+    #[test]
+    fn test_load_order_checks_versions() {
+        let game = ModManifest::from_json_str(
+            r#"{"name": "game", "version": "1.0.0", "description": "test", "requires": {"core": "^2.0.0"}}"#,
+        )
+        .unwrap();
+        let manifests: HashMap<&str, ModManifest> =
+            [("game", game), ("core", manifest("core", &[]))].into_iter().collect();
+
+        let err = collect_load_order(&["game"], |id| manifests.get(id)).unwrap_err();
+        assert!(matches!(err, DependencyError::VersionMismatch { ref dependency, .. } if dependency == "core"), "{:?}", err);
+        assert!(err.to_string().contains("requires ^2.0.0 but version 1.0.0 is installed"), "{}", err);
+    }
+}
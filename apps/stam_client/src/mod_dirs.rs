@@ -0,0 +1,137 @@
+/// Mod directory lookup (`--mods-dir`)
+///
+/// Mods are installed under `game_root/mods/<mod-id>`. For mod development an extra
+/// directory can be overlaid on top: a mod found there (e.g. a working copy of the mod)
+/// wins over the installed one, so it can be edited and run without being installed.
+/// Downloads always go to the game mods directory.
+
+use std::path::{Path, PathBuf};
+
+/// Name of the manifest file of a mod
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Where a mod was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModLocation {
+    /// The mod directory (`<mods dir>/<mod-id>`)
+    pub mod_dir: PathBuf,
+    /// Directory holding the manifest: `client/` inside the mod directory, or the mod directory itself.
+    /// Entry points are resolved against it.
+    pub dir: PathBuf,
+    /// Path of the manifest
+    pub manifest_path: PathBuf,
+    /// Whether the mod comes from the `--mods-dir` override
+    pub overridden: bool,
+}
+
+impl ModLocation {
+    /// Short description of where the mod was loaded from, for logs
+    pub fn describe(&self) -> String {
+        let part = if self.dir != self.mod_dir { "client/" } else { "root" };
+        if self.overridden {
+            format!("{} of {} (--mods-dir)", part, self.mod_dir.display())
+        } else {
+            part.to_string()
+        }
+    }
+}
+
+/// The directories mods are searched in, override first
+#[derive(Debug, Clone)]
+pub struct ModDirs {
+    /// Mods directory of the game (`game_root/mods`), where mods are installed
+    game_dir: PathBuf,
+    /// Development mods directory searched before the game one
+    override_dir: Option<PathBuf>,
+}
+
+impl ModDirs {
+    /// Create the lookup for the game mods directory, with an optional override directory
+    pub fn new(game_dir: impl Into<PathBuf>, override_dir: Option<PathBuf>) -> Self {
+        Self {
+            game_dir: game_dir.into(),
+            override_dir,
+        }
+    }
+
+    /// Mods directory of the game, where downloaded mods are installed
+    pub fn game_dir(&self) -> &Path {
+        &self.game_dir
+    }
+
+    /// The override directory, if any
+    pub fn override_dir(&self) -> Option<&Path> {
+        self.override_dir.as_deref()
+    }
+
+    /// Find the mod with a manifest, in the override directory first
+    pub fn find(&self, mod_id: &str) -> Option<ModLocation> {
+        let override_location = self
+            .override_dir
+            .as_ref()
+            .and_then(|dir| locate(&dir.join(mod_id), true));
+        override_location.or_else(|| locate(&self.game_dir.join(mod_id), false))
+    }
+
+    /// Whether a directory exists for the mod (with or without a manifest)
+    pub fn has_dir(&self, mod_id: &str) -> bool {
+        self.override_dir.iter().chain(std::iter::once(&self.game_dir)).any(|dir| dir.join(mod_id).is_dir())
+    }
+}
+
+/// Find the manifest of the mod in `mod_dir`: `client/manifest.json` first, then `manifest.json`
+pub fn locate(mod_dir: &Path, overridden: bool) -> Option<ModLocation> {
+    let client_dir = mod_dir.join("client");
+    [client_dir, mod_dir.to_path_buf()].into_iter().find_map(|dir| {
+        let manifest_path = dir.join(MANIFEST_FILE);
+        manifest_path.is_file().then(|| ModLocation {
+            mod_dir: mod_dir.to_path_buf(),
+            dir,
+            manifest_path,
+            overridden,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(test_name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("stam_mod_dirs_{}_{}", test_name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_manifest(dir: &Path) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(MANIFEST_FILE), "{}").unwrap();
+    }
+
+    #[test]
+    fn test_override_dir_wins() {
+        let temp = temp_dir("override_dir_wins");
+        let (game_dir, dev_dir) = (temp.join("mods"), temp.join("dev"));
+        write_manifest(&game_dir.join("ui"));
+        write_manifest(&game_dir.join("core").join("client"));
+        write_manifest(&dev_dir.join("core"));
+        // A directory without manifest in the override doesn't hide the installed mod
+        fs::create_dir_all(dev_dir.join("ui")).unwrap();
+
+        let dirs = ModDirs::new(&game_dir, Some(dev_dir.clone()));
+        let core = dirs.find("core").unwrap();
+        assert!(core.overridden);
+        assert_eq!(core.dir, dev_dir.join("core"));
+
+        let ui = dirs.find("ui").unwrap();
+        assert!(!ui.overridden);
+        assert_eq!(ui.describe(), "root");
+        assert_eq!(ModDirs::new(&game_dir, None).find("core").unwrap().describe(), "client/");
+
+        assert!(dirs.find("missing").is_none());
+        assert!(!dirs.has_dir("missing"));
+        let _ = fs::remove_dir_all(&temp);
+    }
+}
@@ -0,0 +1,170 @@
+/// Mod loading progress for end users
+///
+/// `connect_to_game_server` logs every mod it checks, downloads and attaches at DEBUG.
+/// At INFO those lines are hidden, so this aggregator reports the same phases as a few
+/// progress lines ("Loading mods 12/45...") and a final summary ("45 mods loaded, 0 missing").
+/// When DEBUG is enabled it stays silent: the detailed lines already tell the story.
+
+use std::time::{Duration, Instant};
+
+use tracing::{Level, info};
+
+/// Minimum time between two progress lines of the same phase
+const REPORT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A step of the mod loading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadPhase {
+    /// Reading the manifests of the locally installed mods
+    Manifests,
+    /// Downloading and installing the missing mods
+    Download,
+    /// Running the mods (load + onAttach)
+    Attach,
+}
+
+impl LoadPhase {
+    /// Label of the phase in progress lines
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoadPhase::Manifests => "Checking mods",
+            LoadPhase::Download => "Downloading mods",
+            LoadPhase::Attach => "Loading mods",
+        }
+    }
+}
+
+/// Aggregates the per-mod steps into progress lines and a summary
+#[derive(Debug)]
+pub struct ModLoadProgress {
+    /// Whether lines are written (INFO enabled, DEBUG disabled)
+    enabled: bool,
+    phase: Option<LoadPhase>,
+    total: usize,
+    done: usize,
+    /// When the last progress line of the phase was written
+    last_report: Option<Instant>,
+    downloaded: usize,
+}
+
+impl ModLoadProgress {
+    /// Create a progress reporter that writes lines only at INFO level
+    pub fn new() -> Self {
+        Self::with_enabled(tracing::enabled!(Level::INFO) && !tracing::enabled!(Level::DEBUG))
+    }
+
+    /// Create a progress reporter, forcing whether lines are written
+    pub fn with_enabled(enabled: bool) -> Self {
+        Self {
+            enabled,
+            phase: None,
+            total: 0,
+            done: 0,
+            last_report: None,
+            downloaded: 0,
+        }
+    }
+
+    /// Start a phase with `total` mods to process
+    ///
+    /// Starting the phase already in progress adds to its total, so the download
+    /// iterations (dependencies discovered after each download) count as one phase.
+    pub fn begin(&mut self, phase: LoadPhase, total: usize) {
+        if self.phase == Some(phase) {
+            self.total += total;
+            return;
+        }
+        self.phase = Some(phase);
+        self.total = total;
+        self.done = 0;
+        self.last_report = None;
+    }
+
+    /// Record that a mod of the current phase is done
+    pub fn advance(&mut self) {
+        if self.phase == Some(LoadPhase::Download) {
+            self.downloaded += 1;
+        }
+        if let Some(line) = self.advance_at(Instant::now()) {
+            info!("{}", line);
+        }
+    }
+
+    /// Write the final summary of the mod loading
+    pub fn finish(&mut self, loaded: usize, missing: usize) {
+        self.phase = None;
+        if self.enabled {
+            info!("{}", self.summary(loaded, missing));
+        }
+    }
+
+    /// Count a mod as done and return the progress line to write, if any
+    ///
+    /// The first and the last mod of a phase are always reported, the ones in between
+    /// at most once per `REPORT_INTERVAL`.
+    fn advance_at(&mut self, now: Instant) -> Option<String> {
+        let phase = self.phase?;
+        self.done += 1;
+        if !self.enabled {
+            return None;
+        }
+        let due = match self.last_report {
+            None => true,
+            Some(last) => self.done >= self.total || now.duration_since(last) >= REPORT_INTERVAL,
+        };
+        if !due {
+            return None;
+        }
+        self.last_report = Some(now);
+        Some(format!("{} {}/{}...", phase.label(), self.done, self.total))
+    }
+
+    /// Summary line of the mod loading
+    fn summary(&self, loaded: usize, missing: usize) -> String {
+        let mods = if loaded == 1 { "mod" } else { "mods" };
+        let mut line = format!("{} {} loaded, {} missing", loaded, mods, missing);
+        if self.downloaded > 0 {
+            line.push_str(&format!(", {} downloaded", self.downloaded));
+        }
+        line
+    }
+}
+
+impl Default for ModLoadProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_lines_are_throttled() {
+        let mut progress = ModLoadProgress::with_enabled(true);
+        let start = Instant::now();
+        progress.begin(LoadPhase::Attach, 3);
+
+        assert_eq!(progress.advance_at(start).as_deref(), Some("Loading mods 1/3..."));
+        assert_eq!(progress.advance_at(start), None);
+        // The last mod of the phase is always reported
+        assert_eq!(progress.advance_at(start).as_deref(), Some("Loading mods 3/3..."));
+
+        progress.begin(LoadPhase::Download, 2);
+        progress.advance_at(start);
+        // Another download iteration extends the same phase
+        progress.begin(LoadPhase::Download, 2);
+        assert_eq!(progress.advance_at(start + REPORT_INTERVAL).as_deref(), Some("Downloading mods 2/4..."));
+    }
+
+    #[test]
+    fn test_disabled_progress_still_counts() {
+        let mut progress = ModLoadProgress::with_enabled(false);
+        progress.begin(LoadPhase::Download, 1);
+        progress.advance();
+
+        assert_eq!(progress.summary(45, 0), "45 mods loaded, 0 missing, 1 downloaded");
+        assert_eq!(ModLoadProgress::with_enabled(false).summary(1, 2), "1 mod loaded, 2 missing");
+    }
+}
@@ -7,6 +7,8 @@ pub use stam_mod_runtimes::adapters::{
     JsRuntimeAdapter, JsRuntimeConfig,
     run_js_event_loop,
 };
+pub use stam_mod_runtimes::adapters::js::{drain_pending_jobs, SHUTDOWN_DRAIN_TIMEOUT};
+pub use stam_mod_runtimes::adapters::js::{run_js_memory_monitor, MEMORY_STATS_INTERVAL};
 
 /// Helper function to create JsRuntimeConfig using the game_root directory
 ///
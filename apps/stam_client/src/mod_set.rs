@@ -0,0 +1,174 @@
+/// Loaded mod set tracking
+///
+/// Remembers which mods (and which versions) the client has loaded for a game,
+/// so a later `LoginSuccess` (e.g. after a reconnect) can be diffed against it
+/// and only the changed mods need to be touched instead of re-running the whole bootstrap.
+
+use std::collections::HashMap;
+
+use stam_protocol::ModInfo;
+
+/// A mod as it was loaded by the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedMod {
+    /// Mod version (from the server mod list)
+    pub version: String,
+    /// SHA-512 of the mod archive (empty if the server did not provide one)
+    pub archive_sha512: String,
+}
+
+/// The set of mods last loaded for a game, keyed by mod ID
+#[derive(Debug, Clone, Default)]
+pub struct LoadedModSet {
+    mods: HashMap<String, LoadedMod>,
+}
+
+impl LoadedModSet {
+    /// Build the loaded set from the mod list received in `LoginSuccess`
+    pub fn from_server_mods(mods: &[ModInfo]) -> Self {
+        Self {
+            mods: mods
+                .iter()
+                .map(|m| {
+                    (
+                        m.mod_id.clone(),
+                        LoadedMod {
+                            version: m.version.clone(),
+                            archive_sha512: m.archive_sha512.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Number of mods in the set
+    pub fn len(&self) -> usize {
+        self.mods.len()
+    }
+
+    /// Whether the set is empty
+    #[allow(dead_code)] // Counterpart of `len`, only `len` is logged for now
+    pub fn is_empty(&self) -> bool {
+        self.mods.is_empty()
+    }
+
+    /// Get a loaded mod by ID
+    #[allow(dead_code)] // The reconnect flow only needs the diff for now
+    pub fn get(&self, mod_id: &str) -> Option<&LoadedMod> {
+        self.mods.get(mod_id)
+    }
+
+    /// Compare this set against a new server mod list
+    ///
+    /// A mod counts as updated when its version changed, or when both sides
+    /// have an archive hash and the hashes differ (same version, new content).
+    /// All lists are sorted by mod ID for deterministic output.
+    pub fn diff(&self, server_mods: &[ModInfo]) -> ModSetDiff {
+        let mut diff = ModSetDiff::default();
+
+        for server_mod in server_mods {
+            match self.mods.get(&server_mod.mod_id) {
+                None => diff.added.push(server_mod.mod_id.clone()),
+                Some(loaded) => {
+                    let version_changed = loaded.version != server_mod.version;
+                    let archive_changed = !loaded.archive_sha512.is_empty()
+                        && !server_mod.archive_sha512.is_empty()
+                        && loaded.archive_sha512 != server_mod.archive_sha512;
+                    if version_changed || archive_changed {
+                        diff.updated.push(server_mod.mod_id.clone());
+                    }
+                }
+            }
+        }
+
+        diff.removed = self
+            .mods
+            .keys()
+            .filter(|mod_id| !server_mods.iter().any(|m| &m.mod_id == *mod_id))
+            .cloned()
+            .collect();
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.updated.sort();
+        diff
+    }
+}
+
+/// Difference between the loaded mod set and a new server mod list
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModSetDiff {
+    /// Mods required by the server that are not loaded
+    pub added: Vec<String>,
+    /// Loaded mods the server no longer requires
+    pub removed: Vec<String>,
+    /// Loaded mods whose version or archive changed on the server
+    pub updated: Vec<String>,
+}
+
+impl ModSetDiff {
+    /// Whether the mod set is unchanged (nothing to add, remove or update)
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mod_info(mod_id: &str, version: &str, sha: &str) -> ModInfo {
+        ModInfo {
+            mod_id: mod_id.to_string(),
+            mod_type: "library".to_string(),
+            version: version.to_string(),
+            download_url: format!("stam://localhost/mods/{}", mod_id),
+            archive_sha512: sha.to_string(),
+            archive_bytes: 0,
+            uncompressed_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn test_unchanged_set() {
+        let mods = vec![mod_info("a", "1.0.0", "aa"), mod_info("b", "2.0.0", "bb")];
+        let loaded = LoadedModSet::from_server_mods(&mods);
+        assert!(loaded.diff(&mods).is_empty());
+    }
+
+    #[test]
+    fn test_added_mod() {
+        let loaded = LoadedModSet::from_server_mods(&[mod_info("a", "1.0.0", "aa")]);
+        let diff = loaded.diff(&[mod_info("a", "1.0.0", "aa"), mod_info("b", "1.0.0", "bb")]);
+        assert_eq!(diff.added, vec!["b".to_string()]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.updated.is_empty());
+    }
+
+    #[test]
+    fn test_removed_mod() {
+        let loaded = LoadedModSet::from_server_mods(&[mod_info("a", "1.0.0", "aa"), mod_info("b", "1.0.0", "bb")]);
+        let diff = loaded.diff(&[mod_info("b", "1.0.0", "bb")]);
+        assert_eq!(diff.removed, vec!["a".to_string()]);
+        assert!(diff.added.is_empty());
+        assert!(diff.updated.is_empty());
+    }
+
+    #[test]
+    fn test_version_changed_mod() {
+        let loaded = LoadedModSet::from_server_mods(&[mod_info("a", "1.0.0", "aa")]);
+        let diff = loaded.diff(&[mod_info("a", "1.1.0", "aa")]);
+        assert_eq!(diff.updated, vec!["a".to_string()]);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn test_archive_changed_same_version() {
+        let loaded = LoadedModSet::from_server_mods(&[mod_info("a", "1.0.0", "aa")]);
+        assert_eq!(loaded.diff(&[mod_info("a", "1.0.0", "ab")]).updated, vec!["a".to_string()]);
+        // A missing hash on either side is not treated as a change
+        assert!(loaded.diff(&[mod_info("a", "1.0.0", "")]).is_empty());
+    }
+}
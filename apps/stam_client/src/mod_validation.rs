@@ -0,0 +1,163 @@
+/// Validate-only mode (`--validate`)
+///
+/// Checks the mods of a game without running them: every mod listed by the server
+/// must have a readable manifest, its dependencies must be available at a matching
+/// version and the dependency graph must be acyclic. Meant for CI and mod packaging.
+
+use std::fmt;
+
+use stam_schema::ModManifest;
+
+use crate::mod_deps::{self, DependencyError};
+
+/// Outcome of validating a game's mods
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Mods that would load (manifest and dependencies valid)
+    pub loaded: Vec<String>,
+    /// Mods listed by the server without a (readable) manifest
+    pub missing: Vec<String>,
+    /// Dependency cycles, each starting and ending with the same mod
+    pub circular: Vec<Vec<String>>,
+    /// Mods that cannot load, with the reason
+    pub broken: Vec<(String, String)>,
+}
+
+impl ValidationReport {
+    /// Validate `mod_ids` and their dependency graph
+    ///
+    /// # Arguments
+    /// * `mod_ids` - Mods listed by the server
+    /// * `manifest_of` - Manifest of a locally available mod
+    /// * `check_requirements` - Extra manifest checks (e.g. `@client`/`@game` version ranges)
+    pub fn build<'a>(
+        mod_ids: &[&str],
+        manifest_of: impl Fn(&str) -> Option<&'a ModManifest>,
+        check_requirements: impl Fn(&str, &ModManifest) -> Result<(), String>,
+    ) -> Self {
+        let mut report = Self::default();
+        let mut mod_ids = mod_ids.to_vec();
+        mod_ids.sort();
+        mod_ids.dedup();
+
+        for mod_id in mod_ids {
+            let Some(manifest) = manifest_of(mod_id) else {
+                report.missing.push(mod_id.to_string());
+                continue;
+            };
+
+            match mod_deps::collect_load_order(&[mod_id], &manifest_of) {
+                Ok(_) => match check_requirements(mod_id, manifest) {
+                    Ok(()) => report.loaded.push(mod_id.to_string()),
+                    Err(e) => report.broken.push((mod_id.to_string(), e)),
+                },
+                Err(DependencyError::CircularDependency { cycle }) => {
+                    // Mods outside the loop are broken by it; each loop is listed once
+                    if !cycle.iter().any(|id| id == mod_id) {
+                        report.broken.push((mod_id.to_string(), format!("depends on a circular dependency: {}", cycle.join(" -> "))));
+                    }
+                    let mut members = cycle[1..].to_vec();
+                    members.sort();
+                    let known = report.circular.iter().any(|known| {
+                        let mut known_members = known[1..].to_vec();
+                        known_members.sort();
+                        known_members == members
+                    });
+                    if !known {
+                        report.circular.push(cycle);
+                    }
+                }
+                Err(e) => report.broken.push((mod_id.to_string(), e.to_string())),
+            }
+        }
+
+        report
+    }
+
+    /// Whether every mod would load
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.circular.is_empty() && self.broken.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Mod validation: {} loaded, {} missing, {} circular, {} broken",
+            self.loaded.len(),
+            self.missing.len(),
+            self.circular.len(),
+            self.broken.len()
+        )?;
+        for mod_id in &self.loaded {
+            writeln!(f, " ✓ {}", mod_id)?;
+        }
+        for mod_id in &self.missing {
+            writeln!(f, " ? {} (missing or invalid manifest)", mod_id)?;
+        }
+        for cycle in &self.circular {
+            writeln!(f, " ↻ {}", cycle.join(" -> "))?;
+        }
+        for (mod_id, reason) in &self.broken {
+            writeln!(f, " ✗ {}: {}", mod_id, reason)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stam_schema::Validatable;
+    use std::collections::HashMap;
+
+    fn manifest(name: &str, requires: &[&str]) -> ModManifest {
+        let requires: Vec<String> = requires.iter().map(|dep| format!(r#""{}": "*""#, dep)).collect();
+        ModManifest::from_json_str(&format!(
+            r#"{{"name": "{}", "version": "1.0.0", "description": "test", "requires": {{{}}}}}"#,
+            name,
+            requires.join(", ")
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_mod_set() {
+        let manifests: HashMap<&str, ModManifest> =
+            [("game", manifest("game", &["core", "@client"])), ("core", manifest("core", &[]))]
+                .into_iter()
+                .collect();
+
+        let report = ValidationReport::build(&["game", "core"], |id| manifests.get(id), |_, _| Ok(()));
+        assert!(report.is_ok(), "{}", report);
+        assert_eq!(report.loaded, vec!["core", "game"]);
+    }
+
+    #[test]
+    fn test_reports_missing_circular_and_broken() {
+        let manifests: HashMap<&str, ModManifest> = [
+            ("alpha", manifest("alpha", &["beta"])),
+            ("beta", manifest("beta", &["alpha"])),
+            ("game", manifest("game", &["alpha"])),
+            ("ui", manifest("ui", &["absent"])),
+            ("old", manifest("old", &[])),
+        ]
+        .into_iter()
+        .collect();
+
+        let report = ValidationReport::build(
+            &["alpha", "beta", "game", "ui", "old", "absent"],
+            |id| manifests.get(id),
+            |mod_id, _| if mod_id == "old" { Err("requires client 2.0.0".to_string()) } else { Ok(()) },
+        );
+
+        assert!(!report.is_ok());
+        assert!(report.loaded.is_empty());
+        assert_eq!(report.missing, vec!["absent"]);
+        assert_eq!(report.circular, vec![vec!["alpha".to_string(), "beta".into(), "alpha".into()]]);
+        let broken: Vec<&str> = report.broken.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(broken, vec!["game", "old", "ui"]);
+        assert!(report.to_string().starts_with("Mod validation: 0 loaded, 1 missing, 1 circular, 3 broken"));
+    }
+}
@@ -0,0 +1,183 @@
+/// Mod hot-reload for development
+///
+/// When `--watch` is enabled the client watches the (first) entry-point directory of
+/// every loaded mod. Changes are debounced and reported to the main event
+/// loop, which reloads the affected mods between dispatches.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// Quiet period after the last change before the affected mods are reloaded
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// A watched mod
+#[derive(Debug, Clone)]
+pub struct WatchedMod {
+    /// Entry points to load again on reload, in load order
+    pub entry_points: Vec<PathBuf>,
+    /// Directory watched for changes (parent of the first entry point)
+    dir: PathBuf,
+}
+
+/// Watches loaded mods and reports which ones changed
+pub struct ModWatcher {
+    watcher: RecommendedWatcher,
+    mods: HashMap<String, WatchedMod>,
+    changes_rx: mpsc::UnboundedReceiver<PathBuf>,
+}
+
+impl ModWatcher {
+    /// Create a watcher with no mods
+    pub fn new() -> notify::Result<Self> {
+        let (changes_tx, changes_rx) = mpsc::unbounded_channel();
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+            Ok(event) => {
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                for path in event.paths {
+                    let _ = changes_tx.send(path);
+                }
+            }
+            Err(e) => warn!("File watcher error: {}", e),
+        })?;
+
+        Ok(Self {
+            watcher,
+            mods: HashMap::new(),
+            changes_rx,
+        })
+    }
+
+    /// Start watching the directory of a mod's first entry point
+    ///
+    /// Watching an already watched mod only updates its entry points.
+    pub fn watch(&mut self, mod_id: &str, entry_points: &[PathBuf]) -> notify::Result<()> {
+        let Some(dir) = entry_points.first().and_then(|entry_point| entry_point.parent()).map(Path::to_path_buf) else {
+            return Ok(());
+        };
+        // Event paths are absolute, so match them against the canonical directory
+        let dir = dir.canonicalize().unwrap_or(dir);
+
+        if !self.mods.values().any(|watched| watched.dir == dir) {
+            self.watcher.watch(&dir, RecursiveMode::Recursive)?;
+        }
+        debug!("Watching mod '{}' in {}", mod_id, dir.display());
+        self.mods.insert(
+            mod_id.to_string(),
+            WatchedMod {
+                entry_points: entry_points.to_vec(),
+                dir,
+            },
+        );
+        Ok(())
+    }
+
+    /// Get a watched mod
+    pub fn get(&self, mod_id: &str) -> Option<&WatchedMod> {
+        self.mods.get(mod_id)
+    }
+
+    /// Number of watched mods
+    pub fn len(&self) -> usize {
+        self.mods.len()
+    }
+
+    /// Wait for the next batch of changes, returning the IDs of the changed mods
+    ///
+    /// Changes are collected until none arrived for `WATCH_DEBOUNCE`, so an
+    /// editor saving several files at once triggers a single reload per mod.
+    pub async fn next_changed_mods(&mut self) -> Vec<String> {
+        loop {
+            let paths = collect_debounced(&mut self.changes_rx, WATCH_DEBOUNCE).await;
+            if paths.is_empty() {
+                // The watcher is gone: no more changes will ever arrive
+                return std::future::pending().await;
+            }
+
+            let changed: BTreeSet<String> = paths
+                .iter()
+                .filter_map(|path| self.mod_for_path(path))
+                .collect();
+            if !changed.is_empty() {
+                return changed.into_iter().collect();
+            }
+        }
+    }
+
+    /// Find the mod whose directory contains `path` (the deepest one wins)
+    fn mod_for_path(&self, path: &Path) -> Option<String> {
+        self.mods
+            .iter()
+            .filter(|(_, watched)| path.starts_with(&watched.dir))
+            .max_by_key(|(_, watched)| watched.dir.components().count())
+            .map(|(mod_id, _)| mod_id.clone())
+    }
+}
+
+/// Wait for a path, then keep collecting until the channel is quiet for `debounce`
+///
+/// Returns an empty list if the channel is closed before anything arrives.
+async fn collect_debounced(rx: &mut mpsc::UnboundedReceiver<PathBuf>, debounce: Duration) -> Vec<PathBuf> {
+    let Some(first) = rx.recv().await else {
+        return Vec::new();
+    };
+    let mut paths = vec![first];
+    while let Ok(Some(path)) = tokio::time::timeout(debounce, rx.recv()).await {
+        paths.push(path);
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rapid_changes_are_debounced_into_one_batch() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tx.send(PathBuf::from("/mods/a/main.js")).unwrap();
+        tx.send(PathBuf::from("/mods/a/lib.js")).unwrap();
+
+        let sender = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            tx.send(PathBuf::from("/mods/b/main.js")).unwrap();
+            // Arrives after the quiet period, so it belongs to the next batch
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            tx.send(PathBuf::from("/mods/c/main.js")).unwrap();
+        });
+
+        let batch = collect_debounced(&mut rx, Duration::from_millis(50)).await;
+        assert_eq!(batch.len(), 3);
+
+        let next = collect_debounced(&mut rx, Duration::from_millis(50)).await;
+        assert_eq!(next, vec![PathBuf::from("/mods/c/main.js")]);
+
+        sender.await.unwrap();
+        assert!(collect_debounced(&mut rx, Duration::from_millis(50)).await.is_empty());
+    }
+
+    #[test]
+    fn test_changed_path_maps_to_deepest_mod() {
+        let temp = std::env::temp_dir().join(format!("stam_mod_watcher_{}", std::process::id()));
+        let outer = temp.join("mods").join("outer");
+        let inner = outer.join("client");
+        std::fs::create_dir_all(&inner).unwrap();
+
+        let mut watcher = ModWatcher::new().unwrap();
+        watcher.watch("outer", &[outer.join("main.js")]).unwrap();
+        watcher.watch("inner", &[inner.join("main.js")]).unwrap();
+
+        let outer = outer.canonicalize().unwrap();
+        assert_eq!(watcher.mod_for_path(&outer.join("lib/util.js")), Some("outer".to_string()));
+        assert_eq!(watcher.mod_for_path(&outer.join("client/main.js")), Some("inner".to_string()));
+        assert_eq!(watcher.mod_for_path(Path::new("/elsewhere/main.js")), None);
+
+        std::fs::remove_dir_all(&temp).unwrap();
+    }
+}
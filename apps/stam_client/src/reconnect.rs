@@ -0,0 +1,143 @@
+/// Automatic reconnection with exponential backoff
+///
+/// When `--reconnect` is enabled and the game connection is lost, the client
+/// keeps its mods running and retries the game login handshake, waiting
+/// longer between each attempt until a maximum number of attempts is reached.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// Default number of reconnection attempts before giving up
+pub const DEFAULT_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// Delay before the first reconnection attempt
+const INITIAL_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the delay between two attempts
+const MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// How many times, and how often, to retry a lost connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Maximum number of attempts before giving up
+    pub max_attempts: u32,
+    /// Delay before the first attempt (doubled for each following attempt)
+    pub initial_delay: Duration,
+    /// Cap for the delay between attempts
+    pub max_delay: Duration,
+}
+
+impl ReconnectPolicy {
+    /// Create a policy with the default backoff (1s doubling up to 30s)
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_delay: INITIAL_DELAY,
+            max_delay: MAX_DELAY,
+        }
+    }
+
+    /// Delay to wait before the given attempt (1-based)
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        self.initial_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay)
+    }
+}
+
+/// Tracks the reconnection attempts after a connection loss
+#[derive(Debug)]
+pub struct Reconnector {
+    policy: ReconnectPolicy,
+    attempt: u32,
+    next_attempt_at: Option<Instant>,
+}
+
+impl Reconnector {
+    /// Create an idle reconnector for the given policy
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self {
+            policy,
+            attempt: 0,
+            next_attempt_at: None,
+        }
+    }
+
+    /// The policy this reconnector follows
+    pub fn policy(&self) -> &ReconnectPolicy {
+        &self.policy
+    }
+
+    /// Number of the attempt scheduled (or last made), 0 when idle
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// When the next attempt is due (None when no attempt is scheduled)
+    pub fn next_attempt_at(&self) -> Option<Instant> {
+        self.next_attempt_at
+    }
+
+    /// Schedule the next attempt after the backoff delay
+    ///
+    /// Returns the attempt number and its delay, or None once all attempts
+    /// have been used (the reconnector then stays idle).
+    pub fn schedule_next(&mut self) -> Option<(u32, Duration)> {
+        if self.attempt >= self.policy.max_attempts {
+            self.next_attempt_at = None;
+            return None;
+        }
+        self.attempt += 1;
+        let delay = self.policy.delay_for_attempt(self.attempt);
+        self.next_attempt_at = Some(Instant::now() + delay);
+        Some((self.attempt, delay))
+    }
+
+    /// Mark the scheduled attempt as started, so it isn't fired again
+    pub fn begin_attempt(&mut self) {
+        self.next_attempt_at = None;
+    }
+
+    /// Reset after a successful reconnection
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+        self.next_attempt_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_up_to_cap() {
+        let policy = ReconnectPolicy::new(10);
+        let delays: Vec<u64> = (1..=7)
+            .map(|attempt| policy.delay_for_attempt(attempt).as_secs())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(policy.delay_for_attempt(u32::MAX), MAX_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_reconnector_gives_up_after_max_attempts() {
+        let mut reconnector = Reconnector::new(ReconnectPolicy::new(2));
+        assert_eq!(reconnector.next_attempt_at(), None);
+
+        assert_eq!(reconnector.schedule_next(), Some((1, Duration::from_secs(1))));
+        assert!(reconnector.next_attempt_at().is_some());
+        reconnector.begin_attempt();
+        assert_eq!(reconnector.next_attempt_at(), None);
+
+        assert_eq!(reconnector.schedule_next(), Some((2, Duration::from_secs(2))));
+        assert_eq!(reconnector.schedule_next(), None);
+        assert_eq!(reconnector.next_attempt_at(), None);
+
+        // A successful reconnection makes the full budget available again
+        reconnector.reset();
+        assert_eq!(reconnector.attempt(), 0);
+        assert_eq!(reconnector.schedule_next(), Some((1, Duration::from_secs(1))));
+    }
+}
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use stam_protocol::{DisconnectReason, GameMessage};
 use tokio::sync::{RwLock, mpsc};
-use tracing::info;
+use tracing::{debug, info};
 
 /// Type of client connection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -15,8 +17,10 @@ pub enum ClientType {
 /// Commands that can be sent to client handlers
 #[derive(Debug, Clone)]
 pub enum ClientCommand {
-    /// Disconnect with a message ID
-    Disconnect { message_id: String },
+    /// Disconnect with a message ID and the reason reported to the client
+    Disconnect { message_id: String, reason: DisconnectReason },
+    /// Write a message to the client (the handler disconnects it if the write fails)
+    Send { message: GameMessage },
 }
 
 /// Client connection handle
@@ -25,15 +29,35 @@ pub struct ClientHandle {
     pub addr: SocketAddr,
     pub client_type: ClientType,
     pub username: Option<String>,
+    /// Game the client is playing (game clients only)
+    pub game_id: Option<String>,
     /// Channel to send commands to this client's handler
     pub command_tx: mpsc::UnboundedSender<ClientCommand>,
 }
 
+/// An open connection counted against `max_connections`
+///
+/// Held by the connection's task for its whole life; dropping it frees the slot.
+#[derive(Debug)]
+pub struct ConnectionSlot {
+    active: Arc<AtomicUsize>,
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
 /// Manager for tracking active client connections
 #[derive(Clone)]
 pub struct ClientManager {
     /// Map of client address to client handle
     clients: Arc<RwLock<HashMap<SocketAddr, ClientHandle>>>,
+    /// Open connections, from accept to close (including ones not registered yet)
+    active_connections: Arc<AtomicUsize>,
+    /// Maximum number of open connections (0 = unlimited)
+    max_connections: usize,
 }
 
 impl ClientManager {
@@ -41,12 +65,44 @@ impl ClientManager {
     pub fn new() -> Self {
         Self {
             clients: Arc::new(RwLock::new(HashMap::new())),
+            active_connections: Arc::new(AtomicUsize::new(0)),
+            max_connections: 0,
         }
     }
 
+    /// Limit the number of open connections (0 = unlimited)
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
+    /// Maximum number of open connections (0 = unlimited)
+    pub fn max_connections(&self) -> usize {
+        self.max_connections
+    }
+
+    /// Count a new connection, None if the server is full
+    pub fn try_acquire_connection(&self) -> Option<ConnectionSlot> {
+        let max = self.max_connections;
+        self.active_connections
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (max == 0 || active < max).then_some(active + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot {
+                active: Arc::clone(&self.active_connections),
+            })
+    }
+
+    /// Number of open connections
+    #[cfg(test)]
+    pub fn active_connections(&self) -> usize {
+        self.active_connections.load(Ordering::Acquire)
+    }
+
     /// Register a new client connection
     /// Returns a receiver for commands that should be handled by the client handler
-    pub async fn register_client(&self, addr: SocketAddr, client_type: ClientType, username: Option<String>) -> mpsc::UnboundedReceiver<ClientCommand> {
+    pub async fn register_client(&self, addr: SocketAddr, client_type: ClientType, username: Option<String>, game_id: Option<String>) -> mpsc::UnboundedReceiver<ClientCommand> {
         let mut clients = self.clients.write().await;
         let (command_tx, command_rx) = mpsc::unbounded_channel();
 
@@ -54,6 +110,7 @@ impl ClientManager {
             addr,
             client_type,
             username: username.clone(),
+            game_id,
             command_tx,
         };
         clients.insert(addr, handle);
@@ -87,8 +144,8 @@ impl ClientManager {
         clients.len()
     }
 
-    /// Disconnect all clients with a message ID
-    pub async fn disconnect_all(&self, message_id: &str) {
+    /// Disconnect all clients with a message ID and a reason
+    pub async fn disconnect_all(&self, message_id: &str, reason: DisconnectReason) {
         let clients = self.clients.read().await;
         let count = clients.len();
 
@@ -96,12 +153,13 @@ impl ClientManager {
             return;
         }
 
-        info!("Sending disconnect command to {} clients with message ID: {}", count, message_id);
+        info!("Sending disconnect command to {} clients with message ID: {} ({})", count, message_id, reason.as_str());
 
         // Send disconnect command to all client handlers
         for (addr, handle) in clients.iter() {
             let command = ClientCommand::Disconnect {
                 message_id: message_id.to_string(),
+                reason,
             };
 
             if let Err(e) = handle.command_tx.send(command) {
@@ -112,6 +170,89 @@ impl ClientManager {
         info!("Disconnect commands sent to all clients");
     }
 
+    /// Disconnect a single client with a message ID and a reason
+    ///
+    /// The client's handler sends the `Disconnect` message and closes the connection.
+    /// Returns false if the client is not connected (or its handler is gone).
+    pub async fn disconnect(&self, addr: &SocketAddr, message_id: &str, reason: DisconnectReason) -> bool {
+        let clients = self.clients.read().await;
+        let Some(handle) = clients.get(addr) else {
+            return false;
+        };
+        info!("Sending disconnect command to {} with message ID: {} ({})", addr, message_id, reason.as_str());
+        handle
+            .command_tx
+            .send(ClientCommand::Disconnect {
+                message_id: message_id.to_string(),
+                reason,
+            })
+            .is_ok()
+    }
+
+    /// Send a message to every game client playing `game_id`
+    ///
+    /// Each client handler writes the message to its own `GameStream` and drops
+    /// the connection if the write fails. Clients whose handler is already gone
+    /// are unregistered. Returns the number of clients the message was queued for.
+    pub async fn broadcast(&self, game_id: &str, message: GameMessage) -> usize {
+        let mut sent = 0;
+        let mut stale = Vec::new();
+        {
+            let clients = self.clients.read().await;
+            for (addr, handle) in clients.iter() {
+                if handle.client_type != ClientType::Game || handle.game_id.as_deref() != Some(game_id) {
+                    continue;
+                }
+                let command = ClientCommand::Send { message: message.clone() };
+                if handle.command_tx.send(command).is_ok() {
+                    sent += 1;
+                } else {
+                    stale.push(*addr);
+                }
+            }
+        }
+
+        for addr in stale {
+            debug!("Client handler for {} is gone, dropping it from broadcasts", addr);
+            self.unregister_client(&addr).await;
+        }
+        sent
+    }
+
+    /// Send a message to a single client, identified by its address
+    ///
+    /// The client's handler writes it to its `GameStream` (and drops the
+    /// connection if the write fails). Fails if the client is not connected.
+    pub async fn send_to(&self, client_id: &SocketAddr, message: GameMessage) -> Result<(), String> {
+        let sent = {
+            let clients = self.clients.read().await;
+            let handle = clients
+                .get(client_id)
+                .ok_or_else(|| format!("Client '{}' is not connected", client_id))?;
+            handle.command_tx.send(ClientCommand::Send { message }).is_ok()
+        };
+
+        if !sent {
+            self.unregister_client(client_id).await;
+            return Err(format!("Client '{}' has disconnected", client_id));
+        }
+        Ok(())
+    }
+
+    /// Get the number of game clients currently playing `game_id`
+    pub async fn game_player_count(&self, game_id: &str) -> usize {
+        let clients = self.clients.read().await;
+        clients.values()
+            .filter(|h| h.client_type == ClientType::Game && h.game_id.as_deref() == Some(game_id))
+            .count()
+    }
+
+    /// Get the game a connected client is playing
+    pub async fn game_of(&self, client_id: &SocketAddr) -> Option<String> {
+        let clients = self.clients.read().await;
+        clients.get(client_id).and_then(|h| h.game_id.clone())
+    }
+
     /// Get list of client addresses by type
     pub async fn get_clients_by_type(&self, client_type: ClientType) -> Vec<SocketAddr> {
         let clients = self.clients.read().await;
@@ -121,3 +262,87 @@ impl ClientManager {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_send_to_reaches_only_live_clients() {
+        let manager = ClientManager::new();
+        let addr: SocketAddr = "127.0.0.1:40001".parse().unwrap();
+        let mut command_rx = manager.register_client(addr, ClientType::Game, Some("alice".into()), Some("demo".into())).await;
+        assert_eq!(manager.game_of(&addr).await.as_deref(), Some("demo"));
+
+        let message = GameMessage::Broadcast { channel: "chat".into(), payload: "\"hi\"".into() };
+        manager.send_to(&addr, message).await.unwrap();
+        assert!(matches!(
+            command_rx.try_recv(),
+            Ok(ClientCommand::Send { message: GameMessage::Broadcast { ref channel, .. } }) if channel == "chat"
+        ));
+
+        // The handler is gone: the client is reported as disconnected and dropped
+        drop(command_rx);
+        let err = manager.send_to(&addr, GameMessage::Ping { nonce: 1 }).await.unwrap_err();
+        assert!(err.contains("disconnected"));
+        assert_eq!(manager.get_total_count().await, 0);
+
+        let unknown: SocketAddr = "127.0.0.1:40002".parse().unwrap();
+        assert!(manager.send_to(&unknown, GameMessage::Ping { nonce: 2 }).await.is_err());
+    }
+
+    #[test]
+    fn test_connection_slots() {
+        let manager = ClientManager::new().with_max_connections(2);
+        let first = manager.try_acquire_connection().unwrap();
+        let _second = manager.try_acquire_connection().unwrap();
+        assert!(manager.try_acquire_connection().is_none());
+        assert_eq!(manager.active_connections(), 2);
+
+        // Closing a connection frees its slot
+        drop(first);
+        assert_eq!(manager.active_connections(), 1);
+        assert!(manager.try_acquire_connection().is_some());
+
+        let unlimited = ClientManager::new();
+        let slots: Vec<_> = (0..100).filter_map(|_| unlimited.try_acquire_connection()).collect();
+        assert_eq!(slots.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_single_client() {
+        let manager = ClientManager::new();
+        let addr: SocketAddr = "127.0.0.1:40021".parse().unwrap();
+        let other: SocketAddr = "127.0.0.1:40022".parse().unwrap();
+        let mut command_rx = manager.register_client(addr, ClientType::Game, Some("alice".into()), Some("demo".into())).await;
+        let mut other_rx = manager.register_client(other, ClientType::Game, Some("bob".into()), Some("demo".into())).await;
+
+        assert!(manager.disconnect(&addr, "disconnect-idle-timeout", DisconnectReason::IdleTimeout).await);
+        assert!(matches!(
+            command_rx.try_recv(),
+            Ok(ClientCommand::Disconnect { ref message_id, reason: DisconnectReason::IdleTimeout })
+                if message_id == "disconnect-idle-timeout"
+        ));
+        assert!(other_rx.try_recv().is_err());
+
+        let unknown: SocketAddr = "127.0.0.1:40023".parse().unwrap();
+        assert!(!manager.disconnect(&unknown, "disconnect-idle-timeout", DisconnectReason::IdleTimeout).await);
+    }
+
+    #[tokio::test]
+    async fn test_game_player_count() {
+        let manager = ClientManager::new();
+        let addr = |port: u16| -> SocketAddr { format!("127.0.0.1:{}", port).parse().unwrap() };
+        let _a = manager.register_client(addr(40011), ClientType::Game, Some("alice".into()), Some("demo".into())).await;
+        let _b = manager.register_client(addr(40012), ClientType::Game, Some("bob".into()), Some("demo".into())).await;
+        let _c = manager.register_client(addr(40013), ClientType::Game, Some("carol".into()), Some("other".into())).await;
+        let _p = manager.register_client(addr(40014), ClientType::Primal, None, None).await;
+
+        assert_eq!(manager.game_player_count("demo").await, 2);
+        assert_eq!(manager.game_player_count("other").await, 1);
+        assert_eq!(manager.game_player_count("missing").await, 0);
+
+        manager.unregister_client(&addr(40011)).await;
+        assert_eq!(manager.game_player_count("demo").await, 1);
+    }
+}
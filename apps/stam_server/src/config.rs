@@ -1,7 +1,7 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use schemars::JsonSchema;
-use stam_schema::{ModManifest, Validatable, StringOrArray};
-use stam_protocol::ModInfo;
+use stam_schema::{ModManifest, Validatable, StringOrArray, KNOWN_CAPABILITIES};
+use stam_protocol::{Compression, ModInfo};
 use std::collections::HashMap;
 use std::path::Path;
 use std::fmt;
@@ -140,6 +140,65 @@ impl JsonSchema for ByteSize {
     }
 }
 
+/// Highest tick rate the server loop honours; higher configured values are clamped
+pub const MAX_TICK_RATE: u64 = 1000;
+
+/// Compression algorithm for chunked transfers, as named in the config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    /// Chunks are always sent uncompressed
+    None,
+    /// gzip (deflate)
+    Gzip,
+    /// Zstandard
+    Zstd,
+}
+
+impl CompressionAlgorithm {
+    /// Protocol compression advertised in `Welcome` (None for "none")
+    pub fn protocol(self) -> Option<Compression> {
+        match self {
+            CompressionAlgorithm::None => None,
+            CompressionAlgorithm::Gzip => Some(Compression::Gzip),
+            CompressionAlgorithm::Zstd => Some(Compression::Zstd),
+        }
+    }
+}
+
+/// Compression of chunked transfers (file downloads)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CompressionConfig {
+    /// Algorithm advertised to clients
+    #[serde(default = "default_compression_algorithm")]
+    #[schemars(description = "Compression offered for file transfers: 'none', 'gzip' or 'zstd' (default: gzip). zstd compresses text assets better, gzip costs less CPU")]
+    pub algorithm: CompressionAlgorithm,
+    /// Compression level (omit for the algorithm's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Compression level: 0-9 for gzip (default: 1), 1-22 for zstd (default: 3). Higher levels trade CPU for smaller transfers")]
+    pub level: Option<u8>,
+}
+
+impl CompressionConfig {
+    /// Level applied by the chunk writer
+    pub fn level(&self) -> u8 {
+        match (self.level, self.algorithm.protocol()) {
+            (Some(level), _) => level,
+            (None, Some(compression)) => compression.default_level(),
+            (None, None) => 0,
+        }
+    }
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: default_compression_algorithm(),
+            level: None,
+        }
+    }
+}
+
 /// Mod configuration for a game
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModConfig {
@@ -166,6 +225,15 @@ pub struct GameConfig {
     pub name: String,
     /// Game version
     pub version: String,
+    /// Short description shown in the client's server list (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Player capacity advertised in the server list (optional, informational)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_players: Option<u32>,
+    /// URI of an icon shown in the client's server list (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_uri: Option<String>,
     /// Custom URI for this game (optional, overrides server's public_uri)
     /// Use this to redirect clients to a different server for this game
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -211,12 +279,12 @@ pub struct Config {
 
     /// Server tick rate in Hz
     #[serde(default = "default_tick_rate")]
-    #[schemars(description = "Server update frequency in ticks per second", range(min = 1, max = 1000))]
+    #[schemars(description = "Server update frequency in ticks per second. Values above 1000 are clamped to 1000", range(min = 1))]
     pub tick_rate: u64,
 
     /// Public URI for server list (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[schemars(description = "Public URI advertised in server list (e.g., 'stam://game.example.com:9999')")]
+    #[schemars(description = "Public URI advertised in server list (e.g., 'stam://game.example.com:9999', or 'stams://...' when TLS is enabled)")]
     pub public_uri: Option<String>,
 
     /// Games configuration
@@ -229,10 +297,55 @@ pub struct Config {
     #[schemars(description = "Maximum chunk size for network file transfers. Accepts numbers or strings with K/M/G suffix (default: 25M)")]
     pub network_max_chunk_size: ByteSize,
 
+    /// Largest message or chunk accepted from a client, in bytes
+    #[serde(default)]
+    #[schemars(description = "Largest protocol message or chunk accepted from a client; larger frames are rejected before anything is allocated. Accepts numbers or strings with K/M/G suffix (default: 25M)")]
+    pub network_max_message_size: ByteSize,
+
     /// Download bandwidth limit per client in bytes per second
     #[serde(default)]
     #[schemars(description = "Maximum download bandwidth per client in bytes per second. Set to 0 or omit for unlimited. Accepts numbers or strings with K/M/G suffix")]
     pub download_bandwidth_limit_x_client_ps: ByteSize,
+
+    /// Interval between keepalive pings sent to game clients, in seconds
+    #[serde(default = "default_keepalive_secs")]
+    #[schemars(description = "Seconds between keepalive pings sent to game clients. A client that does not answer before the next ping is disconnected. Set to 0 to disable (default: 15)")]
+    pub keepalive_secs: u64,
+
+    /// Maximum number of simultaneous connections (0 = unlimited)
+    #[serde(default = "default_max_connections")]
+    #[schemars(description = "Maximum number of simultaneous connections (all kinds: server list, game, downloads). Connections over the limit get an 'error-server-full' error and are closed. Set to 0 for unlimited (default: 1024)")]
+    pub max_connections: usize,
+
+    /// Seconds a client may stay silent before it is disconnected (0 = disabled)
+    #[serde(default)]
+    #[schemars(description = "Seconds a client may send no message (keepalive pings and pongs excluded) before it is disconnected with 'disconnect-idle-timeout'. Also bounds the wait for the first Intent of a connection. Set to 0 to disable (default: 0)")]
+    pub client_idle_timeout_secs: u64,
+
+    /// PEM certificate chain used to accept stams:// (TLS) connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Path to the PEM certificate chain. When set together with 'tls_key_path', every connection must use TLS (stams://)")]
+    pub tls_cert_path: Option<String>,
+
+    /// PEM private key matching `tls_cert_path`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Path to the PEM private key matching 'tls_cert_path'")]
+    pub tls_key_path: Option<String>,
+
+    /// Capabilities denied to every server mod, whatever their manifests declare
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(description = "Capabilities no server mod may use, even if declared in its manifest (e.g. ['process', 'network'])")]
+    pub denied_capabilities: Vec<String>,
+
+    /// Seconds a child process started by `Process.spawn()` may run before it is killed
+    #[serde(default = "default_process_spawn_timeout_secs")]
+    #[schemars(description = "Seconds a child process started by a mod (Process.spawn) may run before it is killed (default: 30)", range(min = 1))]
+    pub process_spawn_timeout_secs: u64,
+
+    /// Compression of file transfers
+    #[serde(default)]
+    #[schemars(description = "Compression of file transfers, advertised to clients in the Welcome handshake")]
+    pub compression: CompressionConfig,
 }
 
 fn default_name() -> String {
@@ -259,6 +372,22 @@ fn default_tick_rate() -> u64 {
     64
 }
 
+fn default_keepalive_secs() -> u64 {
+    15
+}
+
+fn default_max_connections() -> usize {
+    1024
+}
+
+fn default_process_spawn_timeout_secs() -> u64 {
+    30
+}
+
+fn default_compression_algorithm() -> CompressionAlgorithm {
+    CompressionAlgorithm::Gzip
+}
+
 fn default_true() -> bool {
     true
 }
@@ -275,12 +404,37 @@ impl Default for Config {
             public_uri: None,
             games: HashMap::new(),
             network_max_chunk_size: ByteSize::default(),
+            network_max_message_size: ByteSize::default(),
             download_bandwidth_limit_x_client_ps: ByteSize(0), // 0 = unlimited
+            keepalive_secs: default_keepalive_secs(),
+            max_connections: default_max_connections(),
+            client_idle_timeout_secs: 0,
+            tls_cert_path: None,
+            tls_key_path: None,
+            denied_capabilities: Vec::new(),
+            process_spawn_timeout_secs: default_process_spawn_timeout_secs(),
+            compression: CompressionConfig::default(),
         }
     }
 }
 
 impl Config {
+    /// How long a client may stay silent before it is disconnected, None if disabled
+    pub fn client_idle_timeout(&self) -> Option<std::time::Duration> {
+        (self.client_idle_timeout_secs > 0).then(|| std::time::Duration::from_secs(self.client_idle_timeout_secs))
+    }
+
+    /// Clamp `tick_rate` to `MAX_TICK_RATE`
+    /// Returns the configured value when it had to be lowered, so the caller can warn about it
+    pub fn clamp_tick_rate(&mut self) -> Option<u64> {
+        if self.tick_rate <= MAX_TICK_RATE {
+            return None;
+        }
+        let configured = self.tick_rate;
+        self.tick_rate = MAX_TICK_RATE;
+        Some(configured)
+    }
+
     /// Validate the configuration and build mod lists for all games
     /// Reads mod_type and execute_on from each mod's manifest.json file
     /// Returns an error if any game has mods with missing required fields
@@ -358,7 +512,7 @@ impl Config {
                 // Check root manifest.json
                 let root_manifest_path = mod_dir.join("manifest.json");
                 if root_manifest_path.exists() {
-                    let manifest = ModManifest::from_json_file(root_manifest_path.to_str().unwrap_or(""))
+                    let manifest = ModManifest::from_json_file(&root_manifest_path)
                         .map_err(|e| format!(
                             "Game '{}': Failed to read root manifest for mod '{}': {}",
                             game_id, mod_id, e
@@ -374,7 +528,7 @@ impl Config {
                 // Check server/manifest.json
                 let server_manifest_path = mod_dir.join("server").join("manifest.json");
                 if server_manifest_path.exists() {
-                    let manifest = ModManifest::from_json_file(server_manifest_path.to_str().unwrap_or(""))
+                    let manifest = ModManifest::from_json_file(&server_manifest_path)
                         .map_err(|e| format!(
                             "Game '{}': Failed to read server manifest for mod '{}': {}",
                             game_id, mod_id, e
@@ -392,7 +546,7 @@ impl Config {
                 // Check client/manifest.json
                 let client_manifest_path = mod_dir.join("client").join("manifest.json");
                 if client_manifest_path.exists() {
-                    let manifest = ModManifest::from_json_file(client_manifest_path.to_str().unwrap_or(""))
+                    let manifest = ModManifest::from_json_file(&client_manifest_path)
                         .map_err(|e| format!(
                             "Game '{}': Failed to read client manifest for mod '{}': {}",
                             game_id, mod_id, e
@@ -482,6 +636,7 @@ impl Config {
                     ModInfo {
                         mod_id: mod_id.clone(),
                         mod_type: mod_config.mod_type.clone().unwrap_or_default(),
+                        version: package_info.map(|p| p.manifest.version.clone()).unwrap_or_default(),
                         download_url: mod_config.client_download.clone(),
                         archive_sha512: package_info.map(|p| p.archive_sha512.clone()).unwrap_or_default(),
                         archive_bytes: package_info.map(|p| p.archive_bytes).unwrap_or(0),
@@ -496,7 +651,54 @@ impl Config {
 }
 
 // Implement Validatable for Config
-impl Validatable for Config {}
+impl Validatable for Config {
+    /// Reject a zero tick rate, which would divide by zero in the tick loop,
+    /// a zero spawn timeout, which would kill every child process at once,
+    /// a TLS certificate without its key (or the other way around), unknown
+    /// denied capabilities and a compression level the algorithm doesn't support
+    fn validate(&self) -> stam_schema::Result<()> {
+        if self.tick_rate == 0 {
+            return Err(stam_schema::SchemaError::ValidationError(
+                "'tick_rate' must be at least 1 tick per second".to_string(),
+            ));
+        }
+        if self.process_spawn_timeout_secs == 0 {
+            return Err(stam_schema::SchemaError::ValidationError(
+                "'process_spawn_timeout_secs' must be at least 1 second".to_string(),
+            ));
+        }
+        if self.tls_cert_path.is_some() != self.tls_key_path.is_some() {
+            return Err(stam_schema::SchemaError::ValidationError(
+                "'tls_cert_path' and 'tls_key_path' must be set together".to_string(),
+            ));
+        }
+        if let Some(unknown) = self.denied_capabilities.iter().find(|c| !KNOWN_CAPABILITIES.contains(&c.as_str())) {
+            return Err(stam_schema::SchemaError::ValidationError(format!(
+                "'denied_capabilities': unknown capability '{}' (known capabilities: {})",
+                unknown,
+                KNOWN_CAPABILITIES.join(", ")
+            )));
+        }
+        if let Some(level) = self.compression.level {
+            let Some(compression) = self.compression.algorithm.protocol() else {
+                return Err(stam_schema::SchemaError::ValidationError(
+                    "'compression.level' cannot be set when 'compression.algorithm' is \"none\"".to_string(),
+                ));
+            };
+            let range = compression.level_range();
+            if !range.contains(&level) {
+                return Err(stam_schema::SchemaError::ValidationError(format!(
+                    "'compression.level' {} is out of range for {:?} ({}-{})",
+                    level,
+                    compression,
+                    range.start(),
+                    range.end()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -508,6 +710,34 @@ mod tests {
         assert_eq!(config.local_ip, "0.0.0.0");
         assert_eq!(config.local_port, 7777);
         assert_eq!(config.log_level, "info");
+        assert_eq!(config.keepalive_secs, 15);
+        assert_eq!(config.client_idle_timeout_secs, 0);
+        assert_eq!(config.max_connections, 1024);
+        assert_eq!(config.process_spawn_timeout_secs, 30);
+    }
+
+    #[test]
+    fn test_keepalive_secs() {
+        let json = r#"{
+            "local_port": 8080,
+            "keepalive_secs": 0
+        }"#;
+
+        let config = Config::from_json_str(json).unwrap();
+        assert_eq!(config.keepalive_secs, 0);
+    }
+
+    #[test]
+    fn test_client_idle_timeout_secs() {
+        let json = r#"{
+            "local_port": 8080,
+            "client_idle_timeout_secs": 300
+        }"#;
+
+        let config = Config::from_json_str(json).unwrap();
+        assert_eq!(config.client_idle_timeout_secs, 300);
+        assert_eq!(config.client_idle_timeout(), Some(std::time::Duration::from_secs(300)));
+        assert_eq!(Config::default().client_idle_timeout(), None);
     }
 
     #[test]
@@ -554,6 +784,108 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_zero_tick_rate_rejected() {
+        // The tick loop divides by tick_rate, so the schema minimum must reject 0
+        let json = r#"{
+            "local_port": 8080,
+            "tick_rate": 0
+        }"#;
+
+        let err = Config::from_json_str(json).unwrap_err();
+        assert!(matches!(err, stam_schema::SchemaError::ValidationError(_)));
+        assert!(err.to_string().contains("'tick_rate'"), "{}", err);
+    }
+
+    #[test]
+    fn test_tls_paths_must_be_paired() {
+        let json = r#"{
+            "tls_cert_path": "./certs/server.pem"
+        }"#;
+        let err = Config::from_json_str(json).unwrap_err();
+        assert!(err.to_string().contains("'tls_cert_path' and 'tls_key_path'"), "{}", err);
+
+        let json = r#"{
+            "tls_cert_path": "./certs/server.pem",
+            "tls_key_path": "./certs/server.key"
+        }"#;
+        let config = Config::from_json_str(json).unwrap();
+        assert_eq!(config.tls_key_path.as_deref(), Some("./certs/server.key"));
+    }
+
+    #[test]
+    fn test_denied_capabilities() {
+        let config = Config::from_json_str(r#"{ "denied_capabilities": ["process", "network"] }"#).unwrap();
+        assert_eq!(config.denied_capabilities, vec!["process", "network"]);
+
+        let err = Config::from_json_str(r#"{ "denied_capabilities": ["sockets"] }"#).unwrap_err();
+        assert!(err.to_string().contains("unknown capability 'sockets'"), "{}", err);
+    }
+
+    #[test]
+    fn test_compression_config() {
+        let config = Config::default();
+        assert_eq!(config.compression.algorithm.protocol(), Some(Compression::Gzip));
+        assert_eq!(config.compression.level(), 1);
+
+        let config = Config::from_json_str(r#"{ "compression": { "algorithm": "zstd", "level": 19 } }"#).unwrap();
+        assert_eq!(config.compression.algorithm.protocol(), Some(Compression::Zstd));
+        assert_eq!(config.compression.level(), 19);
+
+        let config = Config::from_json_str(r#"{ "compression": { "algorithm": "none" } }"#).unwrap();
+        assert_eq!(config.compression.algorithm.protocol(), None);
+
+        let err = Config::from_json_str(r#"{ "compression": { "algorithm": "gzip", "level": 12 } }"#).unwrap_err();
+        assert!(err.to_string().contains("'compression.level' 12 is out of range for Gzip (0-9)"), "{}", err);
+        let err = Config::from_json_str(r#"{ "compression": { "algorithm": "none", "level": 3 } }"#).unwrap_err();
+        assert!(err.to_string().contains("'compression.level'"), "{}", err);
+        assert!(Config::from_json_str(r#"{ "compression": { "algorithm": "brotli" } }"#).is_err());
+    }
+
+    #[test]
+    fn test_process_spawn_timeout_validation() {
+        let config = Config { process_spawn_timeout_secs: 0, ..Config::default() };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("'process_spawn_timeout_secs' must be at least 1"), "{}", err);
+
+        let config = Config::from_json_str(r#"{"process_spawn_timeout_secs": 1}"#).unwrap();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_tick_rate_validation() {
+        let config = Config { tick_rate: 0, ..Config::default() };
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("'tick_rate' must be at least 1"), "{}", err);
+
+        let mut config = Config::from_json_str(r#"{"tick_rate": 1}"#).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.clamp_tick_rate(), None);
+        assert_eq!(config.tick_rate, 1);
+
+        let mut config = Config::from_json_str(r#"{"tick_rate": 10000}"#).unwrap();
+        assert_eq!(config.clamp_tick_rate(), Some(10000));
+        assert_eq!(config.tick_rate, MAX_TICK_RATE);
+    }
+
+    #[test]
+    fn test_type_errors_name_field() {
+        let err = Config::from_json_str(r#"{"tick_rate": "64"}"#).unwrap_err();
+        assert!(err.to_string().contains("'tick_rate'"), "{}", err);
+
+        let json = r#"{
+            "games": {
+                "demo": {
+                    "name": "Demo",
+                    "version": "1.0.0",
+                    "mods": { "core": { "enabled": "yes" } }
+                }
+            }
+        }"#;
+        let err = Config::from_json_str(json).unwrap_err();
+        assert!(err.to_string().contains("'games.demo.mods.core.enabled'"), "{}", err);
+    }
+
     #[test]
     fn test_byte_size_parse() {
         // Test numeric values
@@ -1,18 +1,48 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::net::TcpStream;
+use std::time::Duration;
 use tokio::sync::mpsc;
-use tracing::{info, debug, error};
+use tracing::{info, debug, error, trace, warn};
 
-use stam_protocol::{GameMessage, GameStream, ModInfo};
+use stam_protocol::{DisconnectReason as ProtocolDisconnectReason, GameMessage, GameStream, ModInfo, StamStream};
+use stam_mod_runtimes::api::CustomEventRequest;
 use crate::client_manager::{ClientManager, ClientType, ClientCommand};
 use crate::config::Config;
+use crate::primal_client::GameRuntimes;
+
+/// Why a game connection ended, as reported to mods by `client:disconnected`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// The client closed the connection (or it dropped)
+    Closed,
+    /// The client stopped answering keepalive pings
+    Timeout,
+    /// The client sent nothing for `client_idle_timeout_secs`
+    IdleTimeout,
+    /// Writing to the client failed
+    Error,
+    /// The server disconnected the client (e.g. shutdown)
+    ServerDisconnect,
+}
+
+impl DisconnectReason {
+    /// Reason string exposed to mods
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DisconnectReason::Closed => "closed",
+            DisconnectReason::Timeout => "timeout",
+            DisconnectReason::IdleTimeout => "idle-timeout",
+            DisconnectReason::Error => "error",
+            DisconnectReason::ServerDisconnect => "server-disconnect",
+        }
+    }
+}
 
 /// GameClient represents an authenticated game client connection
 /// Handles game-specific protocol messages
 pub struct GameClient {
     /// TCP stream for this client connection
-    stream: TcpStream,
+    stream: StamStream,
     /// Remote address of the client
     addr: SocketAddr,
     /// Username after authentication
@@ -23,13 +53,40 @@ pub struct GameClient {
     config: Arc<Config>,
     /// Client manager for tracking connections
     client_manager: ClientManager,
+    /// Game runtimes, to notify mods when the client connects or disconnects
+    game_runtimes: GameRuntimes,
 }
 
 impl GameClient {
     /// Create a new GameClient from an authenticated connection
-    pub fn new(stream: TcpStream, addr: SocketAddr, username: String, game_id: String, config: Arc<Config>, client_manager: ClientManager) -> Self {
+    pub fn new(stream: StamStream, addr: SocketAddr, username: String, game_id: String, config: Arc<Config>, client_manager: ClientManager, game_runtimes: GameRuntimes) -> Self {
         info!("Game client created for user '{}' on game '{}' from {}", username, game_id, addr);
-        Self { stream, addr, username, game_id, config, client_manager }
+        Self { stream, addr, username, game_id, config, client_manager, game_runtimes }
+    }
+
+    /// Stable ID of this client for mods (`system.sendToClient`)
+    ///
+    /// The remote address identifies the connection in the `ClientManager`.
+    pub fn client_id(&self) -> String {
+        self.addr.to_string()
+    }
+
+    /// Dispatch a `client:*` lifecycle event to the game's mods
+    ///
+    /// The only argument is `{ clientId, username }`, plus `reason` on disconnect.
+    async fn notify_mods(&self, event_name: &str, reason: Option<DisconnectReason>) {
+        let Some(runtime) = self.game_runtimes.get(&self.game_id) else {
+            return;
+        };
+        let mut info = serde_json::json!({
+            "clientId": self.client_id(),
+            "username": self.username,
+        });
+        if let Some(reason) = reason {
+            info["reason"] = reason.as_str().into();
+        }
+        let request = CustomEventRequest::new(event_name, vec![info.to_string()]);
+        runtime.dispatch_custom_event(&request).await;
     }
 
     /// Get the client's remote address
@@ -78,7 +135,7 @@ impl GameClient {
         let username = self.username.clone();
 
         // Register as Game client and get command receiver
-        let mut command_rx = self.client_manager.register_client(addr, ClientType::Game, Some(username.clone())).await;
+        let mut command_rx = self.client_manager.register_client(addr, ClientType::Game, Some(username.clone()), Some(self.game_id.clone())).await;
 
         debug!("Handling authenticated game client from {}", addr);
 
@@ -99,45 +156,132 @@ impl GameClient {
         }
 
         info!("Sent LoginSuccess to user '{}'", username);
+        self.notify_mods("client:connected", None).await;
 
         // Keep connection alive - wait for game messages or commands
-        self.maintain_connection(&mut command_rx).await;
+        let reason = self.maintain_connection(&mut command_rx).await;
 
-        // Unregister when connection ends
+        // Unregister when connection ends, then tell mods exactly once
         self.client_manager.unregister_client(&addr).await;
-        info!("Game client {} disconnected", addr);
+        info!("Game client {} disconnected ({})", addr, reason.as_str());
+        self.notify_mods("client:disconnected", Some(reason)).await;
     }
 
     /// Maintain the connection alive until client disconnects or server shuts down
-    async fn maintain_connection(&mut self, command_rx: &mut mpsc::UnboundedReceiver<ClientCommand>) {
+    ///
+    /// Every `keepalive_secs` a `Ping` is sent; if the previous one is still unanswered
+    /// when the next is due, the connection is considered dead (e.g. half-open TCP after
+    /// the client machine went to sleep) and dropped.
+    ///
+    /// With `client_idle_timeout_secs` set, a client that sends no message (keepalive
+    /// pings and pongs don't count) for that long is disconnected through the `ClientManager`
+    /// with `disconnect-idle-timeout`.
+    ///
+    /// Returns why the connection ended. Each branch returns directly, so a read
+    /// error racing a server shutdown still yields a single reason.
+    async fn maintain_connection(&mut self, command_rx: &mut mpsc::UnboundedReceiver<ClientCommand>) -> DisconnectReason {
         debug!("Maintaining connection for {}", self.addr);
 
+        let keepalive = Duration::from_secs(self.config.keepalive_secs);
+        let mut ping_interval = (!keepalive.is_zero()).then(|| {
+            let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + keepalive, keepalive);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            interval
+        });
+        let mut next_nonce: u64 = 0;
+        let mut awaiting_pong: Option<u64> = None;
+        let idle_timeout = self.config.client_idle_timeout();
+        let mut last_activity = tokio::time::Instant::now();
+        let mut idle_disconnect_requested = false;
+
         loop {
             tokio::select! {
                 // Handle incoming game messages from client
                 msg_result = self.stream.read_game_message() => {
+                    // Keepalive traffic doesn't count as activity
+                    if matches!(msg_result, Ok(ref msg) if !matches!(msg, GameMessage::Ping { .. } | GameMessage::Pong { .. })) {
+                        last_activity = tokio::time::Instant::now();
+                    }
                     match msg_result {
+                        Ok(GameMessage::Pong { nonce }) => {
+                            if awaiting_pong == Some(nonce) {
+                                trace!("Received pong {} from {}", nonce, self.addr);
+                                awaiting_pong = None;
+                            } else {
+                                debug!("Ignoring unexpected pong {} from {}", nonce, self.addr);
+                            }
+                        }
+                        Ok(GameMessage::Ping { nonce }) => {
+                            if let Err(e) = self.stream.write_game_message(&GameMessage::Pong { nonce }).await {
+                                debug!("Failed to send pong to {}: {}", self.addr, e);
+                                return DisconnectReason::Error;
+                            }
+                        }
                         Ok(msg) => {
                             debug!("Received message from {}: {:?}", self.addr, msg);
                             // TODO: Handle game messages
                         }
                         Err(e) => {
                             debug!("Connection closed for {}: {}", self.addr, e);
-                            break;
+                            return DisconnectReason::Closed;
                         }
                     }
                 }
+                // Send keepalive pings, dropping clients that stopped answering
+                _ = async {
+                    match ping_interval.as_mut() {
+                        Some(interval) => { interval.tick().await; }
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    if let Some(nonce) = awaiting_pong {
+                        warn!("No pong for ping {} from {} within {}s, dropping connection",
+                            nonce, self.addr, keepalive.as_secs());
+                        return DisconnectReason::Timeout;
+                    }
+                    next_nonce += 1;
+                    trace!("Sending ping {} to {}", next_nonce, self.addr);
+                    if let Err(e) = self.stream.write_game_message(&GameMessage::Ping { nonce: next_nonce }).await {
+                        debug!("Failed to send ping to {}: {}", self.addr, e);
+                        return DisconnectReason::Error;
+                    }
+                    awaiting_pong = Some(next_nonce);
+                }
+                // Disconnect clients that have been silent for too long
+                _ = async {
+                    match idle_timeout {
+                        Some(timeout) => tokio::time::sleep_until(last_activity + timeout).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                }, if !idle_disconnect_requested => {
+                    let timeout = idle_timeout.unwrap_or_default();
+                    info!("Client {} idle for {}s, disconnecting", self.addr, timeout.as_secs());
+                    idle_disconnect_requested = true;
+                    if !self.client_manager.disconnect(&self.addr, "disconnect-idle-timeout", ProtocolDisconnectReason::IdleTimeout).await {
+                        return DisconnectReason::IdleTimeout;
+                    }
+                }
                 // Handle commands from server (e.g., disconnect)
                 Some(command) = command_rx.recv() => {
                     match command {
-                        ClientCommand::Disconnect { message_id } => {
-                            info!("Sending disconnect message to {}: {}", self.addr, message_id);
+                        ClientCommand::Disconnect { message_id, reason } => {
+                            info!("Sending disconnect message to {}: {} ({})", self.addr, message_id, reason.as_str());
                             if let Err(e) = self.stream.write_game_message(&GameMessage::Disconnect {
                                 message: message_id,
+                                reason,
                             }).await {
                                 error!("Failed to send disconnect to {}: {}", self.addr, e);
                             }
-                            break;
+                            if reason == ProtocolDisconnectReason::IdleTimeout {
+                                return DisconnectReason::IdleTimeout;
+                            }
+                            return DisconnectReason::ServerDisconnect;
+                        }
+                        ClientCommand::Send { message } => {
+                            if let Err(e) = self.stream.write_game_message(&message).await {
+                                warn!("Failed to send message to {}, disconnecting: {}", self.addr, e);
+                                return DisconnectReason::Error;
+                            }
                         }
                     }
                 }
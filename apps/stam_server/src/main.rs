@@ -1,15 +1,18 @@
 use std::env;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use tokio::net::TcpListener;
 use tokio::signal;
 use tokio::time::{Duration, interval};
 use tracing::{Level, debug, error, info, trace, warn};
 
-use stam_mod_runtimes::adapters::js::run_js_event_loop;
-use stam_log::{LogConfig, init_logging};
+use stam_mod_runtimes::adapters::js::{run_js_event_loop, run_js_memory_monitor, MEMORY_STATS_INTERVAL};
+use stam_mod_runtimes::api::ShutdownRequest;
+use stam_log::{LogConfig, ModLogLimit, init_logging};
+use stam_protocol::{DisconnectReason, StamStream, load_tls_acceptor};
 use stam_schema::Validatable;
 
 mod config;
@@ -25,8 +28,14 @@ use client_manager::ClientManager;
 
 mod mod_loader;
 
+mod tick_stats;
+use tick_stats::TickStats;
+
 const VERSION: &str = "0.1.0";
 
+/// Upper bound for the TLS handshake of an incoming stams:// connection
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 async fn wait_for_shutdown(flag: Arc<AtomicBool>) {
     while !flag.load(Ordering::Relaxed) {
         tokio::time::sleep(Duration::from_millis(100)).await;
@@ -64,23 +73,57 @@ struct Args {
     /// Enable logging to file (stam_server.log in current directory)
     #[arg(long, env = "STAM_LOG_FILE")]
     log_file: bool,
+
+    /// Print the JSON Schema of a configuration format to stdout and exit
+    #[arg(long, value_enum, value_name = "KIND")]
+    dump_schema: Option<SchemaKind>,
+}
+
+/// Configuration formats whose JSON Schema can be exported with `--dump-schema`
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SchemaKind {
+    /// Mod manifest (manifest.json)
+    Manifest,
+    /// Server configuration file
+    Config,
+}
+
+/// Print the JSON Schema for `kind` to stdout
+fn dump_schema(kind: SchemaKind) -> stam_schema::Result<()> {
+    let schema = match kind {
+        SchemaKind::Manifest => stam_schema::ModManifest::schema_json()?,
+        SchemaKind::Config => Config::schema_json()?,
+    };
+    println!("{}", schema);
+    Ok(())
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
+    // Schema export runs before any config loading so it works without a config file
+    if let Some(kind) = args.dump_schema {
+        if let Err(e) = dump_schema(kind) {
+            eprintln!("Failed to generate schema: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Load configuration first to get log level
     // We can't log errors yet, so we use eprintln! for early failures
-    let config = match Config::from_json_file(&args.config) {
+    let (config, clamped_tick_rate) = match Config::from_json_file(&args.config) {
         Ok(mut cfg) => {
+            let clamped_tick_rate = cfg.clamp_tick_rate();
+
             // Validate mod configuration and build mod lists
             // Pass custom_home to resolve mods path correctly
             if let Err(e) = cfg.validate_mods(args.home.as_deref()) {
                 eprintln!("Configuration validation error: {}", e);
                 std::process::exit(1);
             }
-            cfg
+            (cfg, clamped_tick_rate)
         }
         Err(e) => {
             eprintln!("Failed to load config from '{}': {}", args.config, e);
@@ -112,12 +155,20 @@ async fn main() {
         LogConfig::<std::fs::File>::new("stam_server::")
             .with_level(log_level)
     };
+    // Keep a mod logging in a loop from flooding the terminal and the log file
+    let log_config = log_config.with_mod_log_limit(ModLogLimit::default());
 
-    init_logging(log_config).expect("Failed to initialize logging");
+    let log = init_logging(log_config).expect("Failed to initialize logging");
 
     info!("Staminal Core Server v{}", VERSION);
     info!("Copyright (C) 2025 Magius(CHE)");
     info!("Configuration: {}", args.config);
+    if let Some(configured) = clamped_tick_rate {
+        warn!(
+            "tick_rate {} Hz is above the supported maximum, clamped to {} Hz",
+            configured, config.tick_rate
+        );
+    }
 
     debug!("Settings:");
     debug!("  Local IP: {}", config.local_ip);
@@ -154,11 +205,20 @@ async fn main() {
         if let Some(js_runtime) = runtime.js_runtime.clone() {
             let gid = game_id.clone();
             let shutdown_token = shutdown.clone();
+            let system_api = runtime.system_api.clone();
             tokio::spawn(async move {
                 info!("Running JS event loop for game '{}'", gid);
-                let mut js_loop = std::pin::pin!(run_js_event_loop(js_runtime));
+                let memory_monitor = async {
+                    match system_api {
+                        Some(system_api) => run_js_memory_monitor(js_runtime.clone(), system_api, MEMORY_STATS_INTERVAL).await,
+                        None => std::future::pending().await,
+                    }
+                };
+                let mut js_loop = std::pin::pin!(run_js_event_loop(js_runtime.clone()));
                 let shutdown_for_wait = shutdown_token.clone();
                 tokio::select! {
+                    // Never completes: samples the JS heap and serves System.gc() until the loop ends
+                    _ = memory_monitor => {},
                     fatal_error = &mut js_loop => {
                         if fatal_error {
                             error!("Fatal JavaScript error in game '{}', mod event loop terminated", gid);
@@ -188,6 +248,23 @@ async fn main() {
         }
     };
 
+    // With a certificate configured every connection must use TLS (stams://):
+    // the server speaks first, so plain and TLS clients can't share the port
+    let tls_acceptor = match (&config.tls_cert_path, &config.tls_key_path) {
+        (Some(cert_path), Some(key_path)) => match load_tls_acceptor(Path::new(cert_path), Path::new(key_path)) {
+            Ok(acceptor) => {
+                info!("TLS enabled: accepting stams:// connections only");
+                Some(acceptor)
+            }
+            Err(e) => {
+                error!("Failed to load TLS certificate: {}", e);
+                error!("Cannot start server without its configured TLS certificate");
+                return;
+            }
+        },
+        _ => None,
+    };
+
     // Check if any game has registered TerminalKeyPressed handlers
     let mut total_terminal_handlers = 0;
     for runtime in game_runtimes.values() {
@@ -202,19 +279,18 @@ async fn main() {
     }
 
     // Create client manager for tracking active connections
-    let client_manager = ClientManager::new();
+    let client_manager = ClientManager::new().with_max_connections(config.max_connections);
 
     // Collect shutdown receivers from all game runtimes and aggregate them
     // into a single channel for the main loop
-    let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::channel::<i32>(1);
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::channel::<(String, ShutdownRequest)>(1);
     for (game_id, runtime) in game_runtimes.iter() {
         if let Some(mut game_shutdown_rx) = runtime.take_shutdown_receiver().await {
             let tx = shutdown_tx.clone();
             let gid = game_id.clone();
             tokio::spawn(async move {
                 if let Some(request) = game_shutdown_rx.recv().await {
-                    info!("Shutdown requested by mod in game '{}' with exit code {}", gid, request.exit_code);
-                    let _ = tx.send(request.exit_code).await;
+                    let _ = tx.send((gid, request)).await;
                 }
             });
         }
@@ -242,21 +318,65 @@ async fn main() {
     // Drop the original sender so the channel closes when all game senders are done
     drop(send_event_tx);
 
+    // Forward broadcast requests from each game's mods to the clients of that game
+    for (game_id, runtime) in game_runtimes.iter() {
+        if let Some(mut game_broadcast_rx) = runtime.take_broadcast_receiver().await {
+            let client_manager = client_manager.clone();
+            let gid = game_id.clone();
+            tokio::spawn(async move {
+                while let Some(request) = game_broadcast_rx.recv().await {
+                    let channel = request.channel.clone();
+                    let sent = client_manager
+                        .broadcast(&gid, stam_protocol::GameMessage::Broadcast {
+                            channel: request.channel,
+                            payload: request.payload,
+                        })
+                        .await;
+                    trace!("Broadcast on channel '{}' of game '{}' queued for {} clients", channel, gid, sent);
+                }
+            });
+        }
+    }
+
+    // Deliver targeted messages from each game's mods, only to clients of that game
+    for (game_id, runtime) in game_runtimes.iter() {
+        if let Some(mut game_send_to_client_rx) = runtime.take_send_to_client_receiver().await {
+            let client_manager = client_manager.clone();
+            let gid = game_id.clone();
+            tokio::spawn(async move {
+                while let Some(request) = game_send_to_client_rx.recv().await {
+                    let result = match request.client_id.parse::<std::net::SocketAddr>() {
+                        Ok(addr) if client_manager.game_of(&addr).await.as_deref() == Some(gid.as_str()) => {
+                            client_manager
+                                .send_to(&addr, stam_protocol::GameMessage::Broadcast {
+                                    channel: request.channel,
+                                    payload: request.payload,
+                                })
+                                .await
+                        }
+                        _ => Err(format!("Client '{}' is not connected to game '{}'", request.client_id, gid)),
+                    };
+                    let _ = request.response_tx.send(result);
+                }
+            });
+        }
+    }
+
     // Start terminal input reader if running in a terminal
-    let (mut terminal_rx, mut terminal_handle) = if stam_mod_runtimes::terminal_input::is_terminal() {
+    let (mut terminal_rx, mut terminal_mouse_rx, mut terminal_paste_rx, mut terminal_handle) = if stam_mod_runtimes::terminal_input::is_terminal() {
         match stam_mod_runtimes::terminal_input::spawn_terminal_event_reader() {
-            Ok((rx, handle)) => {
+            Ok((receivers, handle)) => {
                 debug!("Terminal input reader started");
-                (Some(rx), Some(handle))
+                (Some(receivers.keys), Some(receivers.mouse), Some(receivers.paste), Some(handle))
             }
             Err(e) => {
                 debug!("Failed to start terminal input reader: {}", e);
-                (None, None)
+                (None, None, None, None)
             }
         }
     } else {
         debug!("Not running in terminal, terminal input disabled");
-        (None, None)
+        (None, None, None, None)
     };
     let terminal_input_active = terminal_rx.is_some();
 
@@ -278,19 +398,50 @@ async fn main() {
         });
     }
 
+    // Setup SIGHUP handler (Linux/Unix only) - reloads server-side mods without restarting
+    let (reload_tx, mut reload_rx) = tokio::sync::mpsc::channel::<()>(1);
+    #[cfg(unix)]
+    {
+        tokio::spawn(async move {
+            match signal::unix::signal(signal::unix::SignalKind::hangup()) {
+                Ok(mut stream) => {
+                    while stream.recv().await.is_some() {
+                        info!("Received SIGHUP signal, reloading server mods");
+                        // A full channel means a reload is already pending
+                        let _ = reload_tx.try_send(());
+                    }
+                }
+                Err(err) => {
+                    warn!("Error setting up SIGHUP handler: {}", err);
+                }
+            }
+        });
+    }
+    #[cfg(not(unix))]
+    drop(reload_tx);
+
     // 3. Main Loop (Game Loop + TCP Accept + Signal Handling)
     let tick_duration = Duration::from_millis(1000 / config.tick_rate);
     let mut tick_interval = interval(tick_duration);
+    let mut last_tick = tokio::time::Instant::now();
+    // Report the onTick dispatch overhead about every 10 seconds
+    let mut tick_stats = TickStats::new((config.tick_rate * 10) as u32);
+    // Exit code requested by a mod through system.exit()
+    let mut exit_code = 0;
 
     loop {
         tokio::select! {
             biased;
 
             // Handle shutdown requests from mods (system.exit)
-            exit_code = shutdown_rx.recv(), if !shutdown_rx.is_closed() => {
-                if let Some(code) = exit_code {
-                    info!("Graceful shutdown requested with exit code {}", code);
-                    // TODO: Could store exit_code and use it when process exits
+            request = shutdown_rx.recv(), if !shutdown_rx.is_closed() => {
+                if let Some((game_id, request)) = request {
+                    if request.is_error {
+                        error!("{} (game '{}')", request.describe(), game_id);
+                    } else {
+                        info!("{} (game '{}')", request.describe(), game_id);
+                    }
+                    exit_code = request.exit_code;
                     break;
                 }
             }
@@ -301,13 +452,41 @@ async fn main() {
                     Ok((stream, addr)) => {
                         warn!(">>> Accepted TCP connection from {}", addr);
 
+                        // Over max_connections: only tell the client the server is full
+                        let Some(connection_slot) = client_manager.try_acquire_connection() else {
+                            warn!("Server full ({} connections), rejecting {}", client_manager.max_connections(), addr);
+                            tokio::spawn(primal_client::reject_server_full(stream, addr, tls_acceptor.clone()));
+                            continue;
+                        };
+
                         // Clone config, client_manager, and game_runtimes for the spawned task
                         let config_clone = config.clone();
                         let client_manager_clone = client_manager.clone();
                         let game_runtimes_clone = Arc::clone(&game_runtimes);
+                        let tls_acceptor_clone = tls_acceptor.clone();
 
                         // Spawn a task to handle this client
                         tokio::spawn(async move {
+                            // Counted against max_connections until the connection ends
+                            let _connection_slot = connection_slot;
+                            // The TLS handshake runs here so a slow client can't stall the accept loop
+                            let mut stream = match tokio::time::timeout(
+                                TLS_HANDSHAKE_TIMEOUT,
+                                StamStream::accept(stream, tls_acceptor_clone.as_ref()),
+                            )
+                            .await
+                            {
+                                Ok(Ok(stream)) => stream,
+                                Ok(Err(e)) => {
+                                    warn!("TLS handshake with {} failed: {}", addr, e);
+                                    return;
+                                }
+                                Err(_) => {
+                                    warn!("TLS handshake with {} timed out", addr);
+                                    return;
+                                }
+                            };
+                            stream.set_max_message_bytes(config_clone.network_max_message_size.as_bytes());
                             let client = PrimalClient::new(stream, addr, config_clone, client_manager_clone, game_runtimes_clone);
                             client.handle().await;
                         });
@@ -349,6 +528,42 @@ async fn main() {
                 }
             }
 
+            // Handle pasted text (bracketed paste)
+            paste_request = async {
+                if let Some(ref mut rx) = terminal_paste_rx {
+                    rx.recv().await
+                } else {
+                    std::future::pending().await
+                }
+            } => {
+                if let Some(paste_request) = paste_request {
+                    // Dispatch to all game runtimes until one handles it
+                    for runtime in game_runtimes.values() {
+                        if runtime.dispatch_terminal_paste(&paste_request).await.handled {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // Handle terminal mouse events (xterm mouse reporting)
+            mouse_request = async {
+                if let Some(ref mut rx) = terminal_mouse_rx {
+                    rx.recv().await
+                } else {
+                    std::future::pending().await
+                }
+            } => {
+                if let Some(mouse_request) = mouse_request {
+                    // Dispatch to all game runtimes until one handles it
+                    for runtime in game_runtimes.values() {
+                        if runtime.dispatch_terminal_mouse(&mouse_request).await.handled {
+                            break;
+                        }
+                    }
+                }
+            }
+
             // Handle send_event requests from JavaScript mods
             // This implements the channel-based dispatch pattern described in docs/event-system.md
             request = send_event_rx.recv() => {
@@ -357,10 +572,7 @@ async fn main() {
 
                     // Get the runtime for this game and dispatch the event
                     let response = if let Some(runtime) = game_runtimes.get(&game_id) {
-                        let event_request = stam_mod_runtimes::api::CustomEventRequest::new(
-                            request.event_name.clone(),
-                            request.args.clone(),
-                        );
+                        let event_request = request.to_custom_event();
                         runtime.dispatch_custom_event(&event_request).await
                     } else {
                         warn!("Game runtime '{}' not found for send_event", game_id);
@@ -384,6 +596,13 @@ async fn main() {
                 break;
             }
 
+            // Handle mod reload requests (SIGHUP)
+            reload = reload_rx.recv(), if !reload_rx.is_closed() => {
+                if reload.is_some() {
+                    mod_loader::reload_all_games(&game_runtimes).await;
+                }
+            }
+
             // Handle tick for game loop
             _ = tick_interval.tick() => {
                 // Check shutdown (from SIGTERM handler)
@@ -391,9 +610,22 @@ async fn main() {
                     break;
                 }
 
-                // In un vero engine, qui calcoleremmo il "Delta Time"
-                // Simula lavoro del server
-                // server.process_packets();
+                // Pass the actual elapsed time: ticks can be late when the loop is busy
+                let now = tokio::time::Instant::now();
+                let delta = now - last_tick;
+                last_tick = now;
+
+                for runtime in game_runtimes.values() {
+                    runtime.dispatch_tick(delta).await;
+                }
+
+                let cost = now.elapsed();
+                if cost > tick_duration {
+                    warn!("Tick took {:?}, longer than the {:?} tick interval", cost, tick_duration);
+                }
+                if let Some(report) = tick_stats.record(cost) {
+                    debug!("onTick dispatch over {} ticks: avg {:?}, max {:?}", report.ticks, report.average, report.max);
+                }
             }
         }
     }
@@ -408,12 +640,25 @@ async fn main() {
 
     // Disconnect all active clients with locale ID
     client_manager
-        .disconnect_all("disconnect-server-shutdown")
+        .disconnect_all("disconnect-server-shutdown", DisconnectReason::Shutdown)
         .await;
 
     // Give clients time to receive disconnect message before closing
     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
+    // Let server mods persist their state
+    for (game_id, runtime) in game_runtimes.iter() {
+        runtime.shutdown_mods(game_id).await;
+    }
+
     // TODO: Cleanup resources, save state, etc.
     info!("Shutdown complete.");
+
+    // Make sure buffered log output reaches stdout and the log file before the process ends
+    log.flush();
+
+    // Pass the exit code requested by a mod (system.exit) to the OS
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
 }
@@ -4,12 +4,12 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use tracing::info;
+use tracing::{debug, info, warn};
 
 use stam_mod_runtimes::{
     RuntimeAdapter,
-    adapters::js::{JsRuntimeAdapter, JsRuntimeConfig, register_mod_alias, has_fatal_error},
-    api::{LocaleApi, ModInfo, SystemApi, UriResponse, ModPackagesRegistry},
+    adapters::js::{JsRuntimeAdapter, JsRuntimeConfig, register_mod_alias, has_fatal_error, drain_pending_jobs, SHUTDOWN_DRAIN_TIMEOUT},
+    api::{ApiRegistry, Capability, LocaleApi, LoadPlan, LoadReason, ModInfo, PlannedMod, SystemApi, UriResponse, ModPackagesRegistry, StartupTimings},
     JsAsyncRuntime,
 };
 use stam_schema::{ModManifest, validate_mod_dependencies, Validatable};
@@ -26,6 +26,8 @@ pub struct GameModRuntime {
     /// Direct reference to the JS adapter for event dispatch
     /// Wrapped in Arc<RwLock> to allow async access from multiple handlers
     pub js_adapter: Option<Arc<RwLock<JsRuntimeAdapter>>>,
+    /// Root directory containing the mods, used to re-resolve manifests on reload
+    pub mods_root: PathBuf,
 }
 
 impl GameModRuntime {
@@ -55,6 +57,32 @@ impl GameModRuntime {
         }
     }
 
+    /// Dispatch a TerminalMouse event to registered handlers
+    ///
+    /// Returns a TerminalMouseResponse indicating whether the event was handled
+    pub async fn dispatch_terminal_mouse(&self, request: &stam_mod_runtimes::api::TerminalMouseRequest) -> stam_mod_runtimes::api::TerminalMouseResponse {
+        if let Some(ref adapter) = self.js_adapter {
+            let adapter = adapter.read().await;
+            adapter.dispatch_terminal_mouse(request).await
+        } else {
+            // No JS adapter, return default unhandled response
+            stam_mod_runtimes::api::TerminalMouseResponse::default()
+        }
+    }
+
+    /// Dispatch a TerminalPaste event to registered handlers
+    ///
+    /// Returns a TerminalPasteResponse indicating whether the event was handled
+    pub async fn dispatch_terminal_paste(&self, request: &stam_mod_runtimes::api::TerminalPasteRequest) -> stam_mod_runtimes::api::TerminalPasteResponse {
+        if let Some(ref adapter) = self.js_adapter {
+            let adapter = adapter.read().await;
+            adapter.dispatch_terminal_paste(request).await
+        } else {
+            // No JS adapter, return default unhandled response
+            stam_mod_runtimes::api::TerminalPasteResponse::default()
+        }
+    }
+
     /// Dispatch a custom event to registered handlers
     ///
     /// Returns a CustomEventResponse containing whether the event was handled
@@ -72,6 +100,16 @@ impl GameModRuntime {
         }
     }
 
+    /// Call `onTick(deltaMs)` on every server mod that exports it
+    ///
+    /// `delta` is the time actually elapsed since the previous tick.
+    pub async fn dispatch_tick(&self, delta: std::time::Duration) {
+        if let Some(ref adapter) = self.js_adapter {
+            let adapter = adapter.read().await;
+            adapter.dispatch_tick(delta).await;
+        }
+    }
+
     /// Take the send_event request receiver from EventDispatcher (if available)
     ///
     /// This is used by the main loop to receive and process send_event requests
@@ -100,6 +138,29 @@ impl GameModRuntime {
         }
     }
 
+    /// Take the broadcast request receiver from SystemApi (if available)
+    ///
+    /// This is used by the main loop to forward `system.broadcast()` calls to
+    /// the clients of this game.
+    pub async fn take_broadcast_receiver(&self) -> Option<tokio::sync::mpsc::Receiver<stam_mod_runtimes::api::BroadcastRequest>> {
+        if let Some(ref system_api) = self.system_api {
+            system_api.take_broadcast_receiver().await
+        } else {
+            None
+        }
+    }
+
+    /// Take the send-to-client request receiver from SystemApi (if available)
+    ///
+    /// This is used by the main loop to deliver `system.sendToClient()` calls.
+    pub async fn take_send_to_client_receiver(&self) -> Option<tokio::sync::mpsc::Receiver<stam_mod_runtimes::api::SendToClientRequest>> {
+        if let Some(ref system_api) = self.system_api {
+            system_api.take_send_to_client_receiver().await
+        } else {
+            None
+        }
+    }
+
     /// Get the number of handlers registered for TerminalKeyPressed event
     ///
     /// This is used to determine if any mod has registered to handle terminal input,
@@ -112,6 +173,100 @@ impl GameModRuntime {
             0
         }
     }
+
+    /// Call `onShutdown` on every server mod, then let their pending JavaScript jobs drain
+    ///
+    /// Jobs still pending after `SHUTDOWN_DRAIN_TIMEOUT` (e.g. a running interval) are
+    /// abandoned, so a mod can't keep the server from exiting.
+    pub async fn shutdown_mods(&self, game_id: &str) {
+        let Some(ref adapter) = self.js_adapter else {
+            return;
+        };
+        {
+            let mut adapter = adapter.write().await;
+            for mod_id in &self.server_mods {
+                if let Err(e) = adapter.call_mod_function_async(mod_id, "onShutdown").await {
+                    warn!("{}::{} onShutdown failed: {}", game_id, mod_id, e);
+                }
+            }
+        }
+        if let Some(ref js_runtime) = self.js_runtime
+            && !drain_pending_jobs(js_runtime, SHUTDOWN_DRAIN_TIMEOUT).await
+        {
+            warn!("JavaScript jobs of game '{}' still pending after {:?}, shutting down anyway", game_id, SHUTDOWN_DRAIN_TIMEOUT);
+        }
+    }
+
+    /// Reload this game's server-side mods in place
+    ///
+    /// Manifests are resolved again so an updated `entry_point` is honored.
+    /// Each mod receives `onDetach` on its old instance and `onAttach` on the
+    /// new one. Connected clients and other games are not affected.
+    ///
+    /// Returns the number of mods that were reloaded.
+    pub async fn reload_server_mods(&self, game_id: &str) -> Result<usize, String> {
+        let Some(ref adapter) = self.js_adapter else {
+            return Ok(0);
+        };
+
+        // Resolve all entry points before touching the running mods, so a broken
+        // manifest leaves the current instances in place
+        let mut entries: Vec<(String, Vec<PathBuf>, Option<Vec<String>>)> = Vec::new();
+        for mod_id in &self.server_mods {
+            let mod_dir = self.mods_root.join(mod_id);
+            let (manifest, base_dir) = resolve_manifest(game_id, mod_id, &mod_dir, Some("server"))?;
+            if manifest.entry_points().is_empty() {
+                info!("  - Mod '{}' has no entry_point, nothing to reload", mod_id);
+                continue;
+            }
+            let entry_point_paths = manifest.entry_points().iter().map(|entry_point| base_dir.join(entry_point)).collect();
+            entries.push((mod_id.clone(), entry_point_paths, manifest.capabilities.clone()));
+        }
+
+        info!("> Reloading server mods for game '{}'", game_id);
+        let mut adapter = adapter.write().await;
+        for (mod_id, entry_point_paths, capabilities) in &entries {
+            // The manifest may have changed its capabilities since the last load
+            adapter
+                .capability_policy()
+                .declare(mod_id, capabilities.as_deref())
+                .map_err(|e| format!("{}::{} {}", game_id, mod_id, e))?;
+
+            adapter
+                .reload_mod_async(entry_point_paths, mod_id)
+                .await
+                .map_err(|e| format!("{}::{} Failed to reload mod: {}", game_id, mod_id, e))?;
+
+            if has_fatal_error() {
+                return Err(format!(
+                    "{}::{} Fatal JavaScript error during reload",
+                    game_id, mod_id
+                ));
+            }
+        }
+        info!("< Reload complete for game '{}' ({} mods)", game_id, entries.len());
+
+        Ok(entries.len())
+    }
+}
+
+/// Reload server-side mods for every game, isolating failures per game
+///
+/// A game whose reload fails is logged and skipped; the others are still reloaded.
+pub async fn reload_all_games(runtimes: &HashMap<String, GameModRuntime>) {
+    for (game_id, runtime) in runtimes {
+        if let Err(e) = runtime.reload_server_mods(game_id).await {
+            warn!("Failed to reload mods for game '{}': {}", game_id, e);
+        }
+    }
+}
+
+/// Server-wide restrictions applied to the mods of every game
+struct ModSandbox {
+    /// Capabilities no mod gets, whatever its manifest declares
+    denied_capabilities: Vec<Capability>,
+    /// Upper bound for child processes started by `Process.spawn()`
+    spawn_timeout: std::time::Duration,
 }
 
 /// Initialize mods for all games defined in configuration.
@@ -123,6 +278,14 @@ pub fn initialize_all_games(
     custom_home: Option<&str>,
 ) -> Result<HashMap<String, GameModRuntime>, String> {
     let mods_root = resolve_mods_root(&config.mods_path, custom_home)?;
+    let sandbox = ModSandbox {
+        denied_capabilities: Capability::parse_list(&config.denied_capabilities)?,
+        spawn_timeout: std::time::Duration::from_secs(config.process_spawn_timeout_secs),
+    };
+    if !sandbox.denied_capabilities.is_empty() {
+        let names: Vec<&str> = sandbox.denied_capabilities.iter().map(|c| c.name()).collect();
+        info!("Capabilities denied to all server mods: {}", names.join(", "));
+    }
 
     // Determine home directory for mod-packages.json
     let home_dir = if let Some(home) = custom_home {
@@ -142,8 +305,12 @@ pub fn initialize_all_games(
         })
         .collect();
 
+    // Time each startup phase so slow cold starts can be broken down
+    let startup_timings = StartupTimings::new();
+
     // Load mod packages registry from STAM_HOME/mod-packages/mod-packages.json
     // and filter to only include packages for enabled mods
+    let packages_timer = startup_timings.begin("mod_packages");
     let full_registry = ModPackagesRegistry::load_from_home(&home_dir)
         .map_err(|e| format!("Failed to load mod-packages.json: {}", e))?;
 
@@ -156,6 +323,8 @@ pub fn initialize_all_games(
             .collect(),
     };
 
+    packages_timer.finish();
+
     info!(
         "Loaded mod-packages: {} client packages, {} server packages (from {} enabled mods)",
         mod_packages.client.len(),
@@ -172,10 +341,14 @@ pub fn initialize_all_games(
             continue;
         }
 
-        let game_runtime = initialize_game_mods(game_id, game_config, &mods_root, server_version, &home_dir, &mod_packages)?;
+        let game_timer = startup_timings.begin(format!("game:{}", game_id));
+        let game_runtime = initialize_game_mods(game_id, game_config, &mods_root, server_version, &home_dir, &mod_packages, &sandbox)?;
+        game_timer.finish();
         runtimes.insert(game_id.clone(), game_runtime);
     }
 
+    debug!("Mod initialization completed in {:.1?}", startup_timings.total());
+
     Ok(runtimes)
 }
 
@@ -186,6 +359,7 @@ fn initialize_game_mods(
     server_version: &str,
     home_dir: &std::path::Path,
     mod_packages: &ModPackagesRegistry,
+    sandbox: &ModSandbox,
 ) -> Result<GameModRuntime, String> {
     // Load manifests for all enabled mods first (per side based on execute_on from manifest)
     let mut client_manifests: HashMap<String, ModManifest> = HashMap::new();
@@ -194,6 +368,10 @@ fn initialize_game_mods(
     let mut server_mods: Vec<String> = Vec::new();
     let mut server_manifest_dirs: HashMap<String, PathBuf> = HashMap::new();
     info!("> Initializing mods for game '{}'", game_id);
+
+    // Per-game phase timings, exposed to this game's mods via system.getStartupTimings()
+    let startup_timings = StartupTimings::new();
+    let manifests_timer = startup_timings.begin("manifests");
     for (mod_id, mod_cfg) in &game_config.mods {
         if !mod_cfg.enabled {
             continue;
@@ -223,7 +401,10 @@ fn initialize_game_mods(
         }
     }
 
+    manifests_timer.finish();
+
     // Validate dependencies for client-side manifests (skip @client)
+    let validate_timer = startup_timings.begin("validate");
     for mod_id in &client_mods {
         if let Some(manifest) = client_manifests.get(mod_id) {
             let skip_client_requirement = true;
@@ -255,18 +436,24 @@ fn initialize_game_mods(
         }
     }
 
+    validate_timer.finish();
+
+    // Attach server mods by priority (lower first), then by ID: the game config is a map,
+    // so its order can't be relied on
+    server_mods.sort_by_cached_key(|mod_id| {
+        (server_manifests.get(mod_id).map(|m| m.priority).unwrap_or(0), mod_id.clone())
+    });
+
     // Prepare JS adapter (only if we have server mods)
     let mut js_runtime_handle: Option<Arc<JsAsyncRuntime>> = None;
     let mut system_api_ref = None;
     let mut js_adapter_ref: Option<Arc<RwLock<JsRuntimeAdapter>>> = None;
 
     if !server_mods.is_empty() {
+        let js_init_timer = startup_timings.begin("js_init");
         let (data_dir, config_dir) = server_runtime_paths(game_id)?;
         let js_config = JsRuntimeConfig::new(data_dir, config_dir)
             .with_game_id(game_id);
-        let mut js_adapter = JsRuntimeAdapter::new(js_config)
-            .map_err(|e| format!("Game '{}': Failed to initialize JS runtime: {}", game_id, e))?;
-        js_runtime_handle = Some(js_adapter.get_runtime());
 
         // Setup locale API for server-side mods (using stub fallback)
         // Server-side mods can have their own locale/ directories for translations
@@ -274,18 +461,29 @@ fn initialize_game_mods(
         let locale_api = LocaleApi::new(
             "en-US",  // default locale
             "en-US",  // fallback locale
-            |id| format!("[{}]", id),  // global fallback: return ID in brackets
-            |id, _args| format!("[{}]", id),  // global fallback with args
+            |_locale, id| format!("[{}]", id),  // global fallback: return ID in brackets
+            |_locale, id, _args| format!("[{}]", id),  // global fallback with args
         );
-        js_adapter.set_locale_api(locale_api);
+
+        // Client-only APIs (graphic, resource, network) are simply not registered
+        let apis = ApiRegistry::new().with_api(ApiRegistry::LOCALE, locale_api);
+        let mut js_adapter = JsRuntimeAdapter::new(js_config, apis)
+            .map_err(|e| format!("Game '{}': Failed to initialize JS runtime: {}", game_id, e))?;
+        js_runtime_handle = Some(js_adapter.get_runtime());
 
         // Set mod packages registry and home directory for system.get_mod_packages()
         js_adapter.system_api().set_mod_packages(mod_packages.clone());
         js_adapter.system_api().set_home_dir(home_dir.to_path_buf());
+        // Each game has its own storage: the same mod enabled in two games must not share
+        // (and race on) one `{mod_id}.json`
+        js_adapter.storage_api().set_game_root(home_dir.join("games").join(game_id));
+        js_adapter.system_api().set_startup_timings(startup_timings.clone());
+        js_adapter.capability_policy().set_denied(sandbox.denied_capabilities.iter().copied());
+        js_adapter.enable_process_spawn(sandbox.spawn_timeout);
 
         // First pass: register aliases and mod info for all server mods
         // Mods without entry_point are asset-only and automatically considered attached
-        let mut mod_entries: Vec<(String, PathBuf, String)> = Vec::new();
+        let mut mod_entries: Vec<(String, Vec<PathBuf>, String)> = Vec::new();
         for mod_id in &server_mods {
             let manifest = server_manifests.get(mod_id).ok_or_else(|| {
                 format!("Game '{}': Missing manifest for mod '{}'", game_id, mod_id)
@@ -293,8 +491,8 @@ fn initialize_game_mods(
 
             let base_dir = server_manifest_dirs.get(mod_id).cloned().unwrap_or_else(|| mods_root.join(mod_id));
 
-            // Check if mod has an entry_point
-            if let Some(ref entry_point) = manifest.entry_point {
+            // Check if mod has an entry_point (the first script is the one `@mod-id` resolves to)
+            if let Some(entry_point) = manifest.entry_points().first() {
                 let entry_point_path = base_dir.join(entry_point);
                 let absolute_entry_point = if entry_point_path.is_absolute() {
                     entry_point_path.clone()
@@ -306,6 +504,12 @@ fn initialize_game_mods(
 
                 register_mod_alias(mod_id, absolute_entry_point);
 
+                // Only the APIs listed in the manifest's capabilities (if any) get bound
+                js_adapter
+                    .capability_policy()
+                    .declare(mod_id, manifest.capabilities.as_deref())
+                    .map_err(|e| format!("{}::{} {}", game_id, mod_id, e))?;
+
                 // Register mod info with the system API
                 // Server loads all mods immediately, so loaded: true
                 // download_url is None on server (mods are already local)
@@ -327,7 +531,8 @@ fn initialize_game_mods(
                     uncompressed_bytes: None,
                 });
 
-                mod_entries.push((mod_id.clone(), entry_point_path, manifest.mod_type.clone().unwrap_or_default()));
+                let entry_point_paths = manifest.entry_points().iter().map(|entry_point| base_dir.join(entry_point)).collect();
+                mod_entries.push((mod_id.clone(), entry_point_paths, manifest.mod_type.clone().unwrap_or_default()));
             } else {
                 // Asset-only mod (no entry_point) - automatically considered attached
                 info!("  - Mod '{}' has no entry_point, registering as asset-only (auto-attached)", mod_id);
@@ -350,15 +555,41 @@ fn initialize_game_mods(
             }
         }
 
+        // Every enabled server mod is loaded, so the plan is the config in attach order
+        let mut load_plan = LoadPlan::new();
+        for mod_id in &server_mods {
+            load_plan.push(PlannedMod {
+                mod_id: mod_id.clone(),
+                priority: server_manifests.get(mod_id).map(|m| m.priority).unwrap_or(0),
+                reason: LoadReason::Configured,
+                required_by: server_mods
+                    .iter()
+                    .filter(|other| server_manifests.get(*other).is_some_and(|m| m.requires.contains_key(mod_id)))
+                    .cloned()
+                    .collect(),
+            });
+        }
+        debug!("Load plan for game '{}':\n{}", game_id, load_plan);
+        js_adapter.system_api().set_load_plan(load_plan);
+
         // Store reference to system API for setting bootstrapped state later
         system_api_ref = Some(js_adapter.system_api().clone());
 
+        js_init_timer.finish();
+
         // Second pass: load mods and call onAttach
         info!("  - Attaching server mods for game '{}'", game_id);
-        for (mod_id, entry_point_path, _mod_type) in &mod_entries {
-            js_adapter
-                .load_mod(&entry_point_path, mod_id)
-                .map_err(|e| format!("{}::{} Failed to load mod: {}", game_id, mod_id, e))?;
+        let attach_timer = startup_timings.begin("attach");
+        for (mod_id, entry_point_paths, _mod_type) in &mod_entries {
+            // Entry points are evaluated in manifest order into the same mod context
+            for (index, entry_point_path) in entry_point_paths.iter().enumerate() {
+                let loaded = if index == 0 {
+                    js_adapter.load_mod(entry_point_path, mod_id)
+                } else {
+                    js_adapter.add_mod_script(entry_point_path, mod_id)
+                };
+                loaded.map_err(|e| format!("{}::{} Failed to load mod: {}", game_id, mod_id, e))?;
+            }
             js_adapter
                 .call_mod_function(mod_id, "onAttach")
                 .map_err(|e| format!("{}::{} Failed to call onAttach: {}", game_id, mod_id, e))?;
@@ -373,6 +604,7 @@ fn initialize_game_mods(
             }
             //debug!("Attached '{}'", mod_id);
         }
+        attach_timer.finish();
 
         // Third pass: call onBootstrap for bootstrap mods
         let bootstrap_mods: Vec<_> = mod_entries
@@ -382,6 +614,7 @@ fn initialize_game_mods(
 
         if !bootstrap_mods.is_empty() {
             info!("  - Bootstrapping server mods for game '{}'", game_id);
+            let _bootstrap_timer = startup_timings.begin("bootstrap");
             for (mod_id, _, _) in &bootstrap_mods {
                 js_adapter
                     .call_mod_function(mod_id, "onBootstrap")
@@ -421,6 +654,7 @@ fn initialize_game_mods(
         client_mods,
         system_api: system_api_ref,
         js_adapter: js_adapter_ref,
+        mods_root: mods_root.to_path_buf(),
     })
 }
 
@@ -438,10 +672,7 @@ fn server_runtime_paths(game_id: &str) -> Result<(PathBuf, PathBuf), String> {
 }
 
 fn load_manifest(game_id: &str, mod_id: &str, path: &PathBuf) -> Result<(ModManifest, PathBuf), String> {
-    let path_str = path
-        .to_str()
-        .ok_or_else(|| format!("Invalid manifest path for mod '{}' in game '{}'", mod_id, game_id))?;
-    let manifest = ModManifest::from_json_file(path_str)
+    let manifest = ModManifest::from_json_file(path)
         .map_err(|e| format!("Game '{}': Failed to load manifest for mod '{}': {}", game_id, mod_id, e))?;
     let base_dir = path.parent()
         .map(PathBuf::from)
@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::net::TcpStream;
 use tracing::{info, debug, error, warn, trace};
 
-use stam_protocol::{IntentType, PrimalMessage, PrimalStream, ServerInfo};
+use stam_protocol::{check_compatibility, Compression, IntentType, PrimalMessage, PrimalStream, ServerInfo, StamStream, TlsAcceptor, PROTOCOL_VERSION};
+use tokio::net::TcpStream;
 
 use crate::game_client::GameClient;
 use crate::config::Config;
@@ -12,6 +12,12 @@ use crate::client_manager::{ClientManager, ClientType};
 use crate::mod_loader::GameModRuntime;
 use crate::VERSION;
 
+/// How long a URI connection may stay idle between requests before it is closed
+const URI_CONNECTION_IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long a connection rejected because the server is full may take to receive the error
+const SERVER_FULL_REJECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// Shared registry of GameModRuntime instances for each game
 /// Used for dispatching RequestUri events to mod handlers
 pub type GameRuntimes = Arc<HashMap<String, GameModRuntime>>;
@@ -20,7 +26,7 @@ pub type GameRuntimes = Arc<HashMap<String, GameModRuntime>>;
 /// Used for authentication and server list distribution
 pub struct PrimalClient {
     /// TCP stream for this client connection
-    stream: TcpStream,
+    stream: StamStream,
     /// Remote address of the client
     addr: SocketAddr,
     /// Server configuration
@@ -34,7 +40,7 @@ pub struct PrimalClient {
 impl PrimalClient {
     /// Create a new PrimalClient from an accepted TCP connection
     pub fn new(
-        stream: TcpStream,
+        stream: StamStream,
         addr: SocketAddr,
         config: Config,
         client_manager: ClientManager,
@@ -56,14 +62,16 @@ impl PrimalClient {
         let client_manager = self.client_manager.clone();  // Clone client_manager before moves
 
         // Register as Primal client (temporary, will transition to Game if needed)
-        let _command_rx = client_manager.register_client(addr, ClientType::Primal, None).await;
+        let _command_rx = client_manager.register_client(addr, ClientType::Primal, None, None).await;
         // Note: PrimalClient connections are short-lived, so we don't need to handle commands
 
         debug!("Handling client {}", addr);
 
-        // Send Welcome message with server version
+        // Send Welcome message with server version and supported chunk compression
         let welcome = PrimalMessage::Welcome {
             version: VERSION.to_string(),
+            protocol_version: PROTOCOL_VERSION,
+            compression: self.config.compression.algorithm.protocol(),
         };
 
         if let Err(e) = self.stream.write_primal_message(&welcome).await {
@@ -72,25 +80,39 @@ impl PrimalClient {
             return;
         }
 
-        debug!("Sent Welcome (version {}) to {}", VERSION, addr);
+        debug!("Sent Welcome (version {}, protocol v{}, compression {:?}) to {}", VERSION, PROTOCOL_VERSION, self.config.compression.algorithm, addr);
 
-        // Wait for Intent message
-        match self.stream.read_primal_message().await {
-            Ok(PrimalMessage::Intent { intent_type, client_version, username, password_hash, game_id, uri }) => {
-                debug!("Received Intent from {}: {:?}, user={}, client_version={}, game_id={:?}, uri={:?}", addr, intent_type, username, client_version, game_id, uri);
+        // Wait for Intent message (at most client_idle_timeout_secs, when set)
+        let intent = match self.config.client_idle_timeout() {
+            Some(timeout) => match tokio::time::timeout(timeout, self.stream.read_primal_message()).await {
+                Ok(result) => result,
+                Err(_) => {
+                    info!("Client {} sent no Intent within {}s, disconnecting", addr, timeout.as_secs());
+                    let _ = self.stream.write_primal_message(&PrimalMessage::Error {
+                        message: "disconnect-idle-timeout".to_string(),
+                    }).await;
+                    client_manager.unregister_client(&addr).await;
+                    return;
+                }
+            },
+            None => self.stream.read_primal_message().await,
+        };
+        match intent {
+            Ok(PrimalMessage::Intent { intent_type, client_version, protocol_version, username, password_hash, game_id, uri, compression, range_start }) => {
+                debug!("Received Intent from {}: {:?}, user={}, client_version={}, protocol_version={}, game_id={:?}, uri={:?}, compression={:?}, range_start={:?}", addr, intent_type, username, client_version, protocol_version, game_id, uri, compression, range_start);
 
-                // Validate client version (major.minor must match server)
-                if !self.is_version_compatible(&client_version) {
-                    error!("Version mismatch from {}: client={}, server={}", addr, client_version, VERSION);
+                // Validate the wire protocol version (the app versions may differ)
+                if let Err(e) = check_compatibility(protocol_version, PROTOCOL_VERSION) {
+                    error!("Incompatible client {} (version {}): {}", addr, client_version, e);
                     let _ = self.stream.write_primal_message(&PrimalMessage::Error {
-                        message: format!("Version incompatible. Server requires {}.x", self.get_major_minor(VERSION)),
+                        message: format!("{}. Server {} requires protocol v{}", e, VERSION, PROTOCOL_VERSION),
                     }).await;
                     client_manager.unregister_client(&addr).await;
-                    info!("Client {} disconnected (version mismatch)", addr);
+                    info!("Client {} disconnected (protocol version mismatch)", addr);
                     return;
                 }
 
-                debug!("Client version {} compatible with server {}", client_version, VERSION);
+                debug!("Client version {} (protocol v{}) compatible with server {}", client_version, protocol_version, VERSION);
 
                 match intent_type {
                     IntentType::PrimalLogin => {
@@ -146,51 +168,21 @@ impl PrimalClient {
                         client_manager.unregister_client(&addr).await;
                         info!("Client {} disconnected", addr);
                     }
-                    IntentType::RequestUri => {
-                        // Validate required fields
-                        if game_id.is_none() {
-                            error!("Missing game_id for RequestUri from {}", addr);
-                            let _ = self.stream.write_primal_message(&PrimalMessage::Error {
-                                message: "game_id required for RequestUri".to_string(),
-                            }).await;
-                            client_manager.unregister_client(&addr).await;
-                            info!("Client {} disconnected (missing game_id)", addr);
-                            return;
-                        }
-                        if uri.is_none() {
-                            error!("Missing uri for RequestUri from {}", addr);
-                            let _ = self.stream.write_primal_message(&PrimalMessage::Error {
-                                message: "uri required for RequestUri".to_string(),
-                            }).await;
-                            client_manager.unregister_client(&addr).await;
-                            info!("Client {} disconnected (missing uri)", addr);
-                            return;
-                        }
-
-                        let gid = game_id.unwrap();
-                        match self.config.games.get(&gid) {
-                            Some(game_config) if !game_config.enabled => {
-                                error!("Game '{}' is disabled for RequestUri from {}", gid, addr);
-                                let _ = self.stream.write_primal_message(&PrimalMessage::Error {
-                                    message: format!("Game '{}' is not available", gid),
-                                }).await;
+                    IntentType::RequestUri | IntentType::ListUri => {
+                        let listing = matches!(intent_type, IntentType::ListUri);
+                        let gid = match self.check_uri_intent(game_id, uri.is_some()) {
+                            Ok(gid) => gid,
+                            Err(message) => {
+                                error!("Rejecting {:?} from {}: {}", intent_type, addr, message);
+                                let _ = self.stream.write_primal_message(&PrimalMessage::Error { message }).await;
                                 client_manager.unregister_client(&addr).await;
-                                info!("Client {} disconnected (game disabled)", addr);
+                                info!("Client {} disconnected (invalid {:?})", addr, intent_type);
                                 return;
                             }
-                            None => {
-                                error!("Invalid game_id '{}' for RequestUri from {}", gid, addr);
-                                let _ = self.stream.write_primal_message(&PrimalMessage::Error {
-                                    message: format!("Invalid game_id: {}", gid),
-                                }).await;
-                                client_manager.unregister_client(&addr).await;
-                                info!("Client {} disconnected (invalid game_id)", addr);
-                                return;
-                            }
-                            _ => {} // Game exists and is enabled
-                        }
+                        };
 
-                        self.handle_request_uri(username, password_hash, gid, uri.unwrap()).await;
+                        self.handle_request_uri(username, password_hash, gid, uri.unwrap(), compression, range_start, listing).await;
+                        self.serve_further_uri_requests().await;
                         client_manager.unregister_client(&addr).await;
                         info!("Client {} disconnected (RequestUri completed)", addr);
                     }
@@ -230,14 +222,22 @@ impl PrimalClient {
         info!("User '{}' authenticated successfully", username);
 
         // Get server list (may be empty if no games are enabled)
-        let server_list = self.get_server_list();
+        let server_list = self.get_server_list().await;
 
         if server_list.is_empty() {
             warn!("No enabled games available for user '{}'", username);
         } else {
             info!("Sending server list with {} games to user '{}'", server_list.len(), username);
             for server in &server_list {
-                debug!("  - {} (game_id: {}, server: {}, uri: {})", server.game_name, server.game_id, server.server_name, server.uri);
+                debug!(
+                    "  - {} (game_id: {}, server: {}, uri: {}, players: {}/{})",
+                    server.game_name,
+                    server.game_id,
+                    server.server_name,
+                    server.uri,
+                    server.player_count,
+                    server.max_players.map(|m| m.to_string()).unwrap_or_else(|| "-".to_string())
+                );
             }
         }
 
@@ -269,14 +269,101 @@ impl PrimalClient {
         info!("Game user '{}' authenticated for game '{}', transitioning to GameClient", username, game_id);
 
         // Create GameClient and hand off the connection
-        let game_client = GameClient::new(self.stream, self.addr, username, game_id, Arc::new(self.config.clone()), self.client_manager);
+        let game_client = GameClient::new(self.stream, self.addr, username, game_id, Arc::new(self.config.clone()), self.client_manager, self.game_runtimes);
         game_client.handle().await;
     }
 
+    /// Validate the game and URI of a RequestUri/ListUri intent
+    ///
+    /// # Returns
+    /// The game ID, or the message of the `Error` to send back
+    fn check_uri_intent(&self, game_id: Option<String>, has_uri: bool) -> Result<String, String> {
+        let Some(gid) = game_id else {
+            return Err("game_id required for RequestUri".to_string());
+        };
+        if !has_uri {
+            return Err("uri required for RequestUri".to_string());
+        }
+        match self.config.games.get(&gid) {
+            Some(game_config) if !game_config.enabled => Err(format!("Game '{}' is not available", gid)),
+            None => Err(format!("Invalid game_id: {}", gid)),
+            _ => Ok(gid),
+        }
+    }
+
+    /// Serve further RequestUri/ListUri intents sent on the same connection
+    ///
+    /// Since protocol v2 a URI connection stays open after its response so clients
+    /// can reuse it instead of reconnecting. Returns when the client closes it, stays
+    /// idle for `URI_CONNECTION_IDLE_TIMEOUT` or sends anything else.
+    async fn serve_further_uri_requests(&mut self) {
+        loop {
+            let message = match tokio::time::timeout(URI_CONNECTION_IDLE_TIMEOUT, self.stream.read_primal_message()).await {
+                Ok(Ok(message)) => message,
+                Ok(Err(e)) => {
+                    trace!("URI connection {} closed: {}", self.addr, e);
+                    return;
+                }
+                Err(_) => {
+                    debug!("URI connection {} idle for {:?}, closing it", self.addr, URI_CONNECTION_IDLE_TIMEOUT);
+                    return;
+                }
+            };
+
+            let PrimalMessage::Intent { intent_type, protocol_version, username, password_hash, game_id, uri, compression, range_start, .. } = message else {
+                warn!("Unexpected message on URI connection {}: {:?}", self.addr, message);
+                return;
+            };
+            let listing = match intent_type {
+                IntentType::RequestUri => false,
+                IntentType::ListUri => true,
+                other => {
+                    warn!("Refusing {:?} on reused URI connection {}", other, self.addr);
+                    let _ = self.stream.write_primal_message(&PrimalMessage::Error {
+                        message: "Only RequestUri and ListUri can follow a RequestUri".to_string(),
+                    }).await;
+                    return;
+                }
+            };
+            if let Err(e) = check_compatibility(protocol_version, PROTOCOL_VERSION) {
+                let _ = self.stream.write_primal_message(&PrimalMessage::Error { message: e.to_string() }).await;
+                return;
+            }
+            let gid = match self.check_uri_intent(game_id, uri.is_some()) {
+                Ok(gid) => gid,
+                Err(message) => {
+                    error!("Rejecting {:?} from {}: {}", intent_type, self.addr, message);
+                    let _ = self.stream.write_primal_message(&PrimalMessage::Error { message }).await;
+                    return;
+                }
+            };
+
+            trace!("Reusing URI connection {} for another request", self.addr);
+            self.handle_request_uri(username, password_hash, gid, uri.unwrap(), compression, range_start, listing).await;
+        }
+    }
+
     /// Handle RequestUri intent - one-shot URI request for resource download
-    async fn handle_request_uri(mut self, username: String, password_hash: String, game_id: String, uri: String) {
+    ///
+    /// With `listing` (ListUri intent) a URI resolving to a directory is answered
+    /// with a JSON array of its entries instead of file content.
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_request_uri(
+        &mut self,
+        username: String,
+        password_hash: String,
+        game_id: String,
+        uri: String,
+        compression: Option<Compression>,
+        range_start: Option<u64>,
+        listing: bool,
+    ) {
         debug!("Processing RequestUri for user '{}' on game '{}': {}", username, game_id, uri);
 
+        // Only the advertised compression may be used, whatever the client asked for
+        let compression = compression.filter(|c| Some(*c) == self.config.compression.algorithm.protocol());
+        let compression_level = self.config.compression.level();
+
         // Authenticate with provided credentials
         let authenticated = self.authenticate(&username, &password_hash, IntentType::RequestUri).await;
 
@@ -287,6 +374,7 @@ impl PrimalClient {
                 buffer_string: None,
                 file_name: None,
                 file_size: None,
+                range_start: None,
             }).await;
             return;
         }
@@ -350,7 +438,34 @@ impl PrimalClient {
                 None
             };
 
-            if let Some(ref path) = resolved_path {
+            if let Some(ref path) = resolved_path
+                && listing
+            {
+                let message = match list_directory(path) {
+                    Ok(body) => {
+                        debug!("Sending listing of '{}' for URI '{}'", path.display(), uri);
+                        PrimalMessage::UriResponse {
+                            status: response.status,
+                            buffer_string: Some(body),
+                            file_name: None,
+                            file_size: None,
+                            range_start: None,
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Cannot list '{}' for URI '{}': {}", path.display(), uri, e);
+                        let status = if path.is_dir() { 500 } else { 400 };
+                        PrimalMessage::UriResponse {
+                            status,
+                            buffer_string: None,
+                            file_name: None,
+                            file_size: None,
+                            range_start: None,
+                        }
+                    }
+                };
+                let _ = self.stream.write_primal_message(&message).await;
+            } else if let Some(ref path) = resolved_path {
                 // Get file metadata
                 let file_size = match std::fs::metadata(path) {
                     Ok(meta) => meta.len(),
@@ -361,6 +476,7 @@ impl PrimalClient {
                             buffer_string: None,
                             file_name: None,
                             file_size: None,
+                            range_start: None,
                         }).await;
                         return;
                     }
@@ -371,7 +487,15 @@ impl PrimalClient {
                     .and_then(|n| n.to_str())
                     .map(|s| s.to_string());
 
-                debug!("Sending file '{}' ({} bytes) in chunks for URI '{}'", path.display(), file_size, uri);
+                // Honor a resume request only if it points inside the file; otherwise send it whole
+                let resume_from = range_start.filter(|start| *start > 0 && *start < file_size);
+                if range_start.is_some() && resume_from.is_none() {
+                    debug!("Ignoring range_start {:?} for '{}' ({} bytes), sending whole file",
+                        range_start, path.display(), file_size);
+                }
+
+                debug!("Sending file '{}' ({} bytes, from offset {}) in chunks for URI '{}'",
+                    path.display(), file_size, resume_from.unwrap_or(0), uri);
 
                 // Send initial UriResponse with metadata (buffer_string is None for chunked transfer)
                 if let Err(e) = self.stream.write_primal_message(&PrimalMessage::UriResponse {
@@ -379,14 +503,15 @@ impl PrimalClient {
                     buffer_string: None,
                     file_name,
                     file_size: Some(file_size),
+                    range_start: resume_from,
                 }).await {
                     error!("Failed to send UriResponse header: {}", e);
                     return;
                 }
 
                 // Stream file content in chunks
-                use tokio::io::AsyncReadExt;
-                let file = match tokio::fs::File::open(path).await {
+                use tokio::io::{AsyncReadExt, AsyncSeekExt};
+                let mut file = match tokio::fs::File::open(path).await {
                     Ok(f) => f,
                     Err(e) => {
                         error!("Failed to open file '{}': {}", path.display(), e);
@@ -399,11 +524,24 @@ impl PrimalClient {
                     }
                 };
 
+                if let Some(start) = resume_from
+                    && let Err(e) = file.seek(std::io::SeekFrom::Start(start)).await
+                {
+                    error!("Failed to seek file '{}' to offset {}: {}", path.display(), start, e);
+                    // Send empty final chunk to signal error
+                    let _ = self.stream.write_primal_message(&PrimalMessage::UriResponseChunk {
+                        data: Vec::new(),
+                        is_final: true,
+                    }).await;
+                    return;
+                }
+
                 // Use a larger buffer for BufReader to enable bigger reads
                 let mut reader = tokio::io::BufReader::with_capacity(max_chunk_size, file);
                 let mut current_chunk_size = INITIAL_CHUNK_SIZE.min(max_chunk_size);
                 let mut buffer = vec![0u8; max_chunk_size]; // Allocate max size once
-                let mut total_sent: u64 = 0;
+                // Offsets are absolute, so a resumed transfer ends at file_size like a full one
+                let mut total_sent: u64 = resume_from.unwrap_or(0);
                 let start_time = std::time::Instant::now();
 
                 // Bandwidth limiting configuration
@@ -445,8 +583,8 @@ impl PrimalClient {
                     // Measure time to send this chunk
                     let chunk_start = std::time::Instant::now();
 
-                    // Use raw chunk writing to avoid allocations
-                    if let Err(e) = self.stream.write_raw_chunk(&buffer[..bytes_read], is_final).await {
+                    // Use raw chunk writing to avoid allocations (compressed if the client accepted it)
+                    if let Err(e) = self.stream.write_raw_chunk_compressed_with_level(&buffer[..bytes_read], is_final, compression, compression_level).await {
                         error!("Failed to send file chunk: {}", e);
                         return;
                     }
@@ -506,12 +644,12 @@ impl PrimalClient {
 
                 let total_elapsed = start_time.elapsed();
                 let speed_mbps = if total_elapsed.as_secs_f64() > 0.0 {
-                    (total_sent as f64 / 1024.0 / 1024.0) / total_elapsed.as_secs_f64()
+                    ((total_sent - resume_from.unwrap_or(0)) as f64 / 1024.0 / 1024.0) / total_elapsed.as_secs_f64()
                 } else {
                     0.0
                 };
-                debug!("Finished sending file '{}' ({} bytes in {:?}, {:.2} MB/s, final chunk size: {} KB)",
-                    path.display(), total_sent, total_elapsed, speed_mbps, current_chunk_size / 1024);
+                debug!("Finished sending file '{}' ({} bytes in {:?}, {:.2} MB/s, final chunk size: {} KB, compression: {:?})",
+                    path.display(), total_sent, total_elapsed, speed_mbps, current_chunk_size / 1024, compression);
             } else {
                 // Path resolution failed
                 let _ = self.stream.write_primal_message(&PrimalMessage::UriResponse {
@@ -519,6 +657,7 @@ impl PrimalClient {
                     buffer_string: None,
                     file_name: None,
                     file_size: None,
+                    range_start: None,
                 }).await;
             }
         } else if !response.buffer_string.is_empty() {
@@ -530,6 +669,7 @@ impl PrimalClient {
                 buffer_string: Some(response.buffer_string),
                 file_name: None,
                 file_size: None,
+                range_start: None,
             }).await;
         } else {
             // No content
@@ -540,6 +680,7 @@ impl PrimalClient {
                 buffer_string: None,
                 file_name: None,
                 file_size: None,
+                range_start: None,
             }).await;
         }
     }
@@ -562,42 +703,127 @@ impl PrimalClient {
     /// Games without a URI (neither game-specific nor server public_uri) are skipped
     /// Note: server_name here is this server's name (used for display in server list).
     /// The actual server_name for directory creation comes from LoginSuccess on the target server.
-    fn get_server_list(&self) -> Vec<ServerInfo> {
+    /// The player count only covers game clients connected to this server.
+    async fn get_server_list(&self) -> Vec<ServerInfo> {
         let server_name = self.config.name.clone();
+        let mut servers = Vec::new();
+
+        for (game_id, game_config) in &self.config.games {
+            if !game_config.enabled {
+                continue;
+            }
 
-        self.config.games.iter()
-            .filter(|(_, game_config)| game_config.enabled)
-            .filter_map(|(game_id, game_config)| {
-                // Use game-specific URI if present, otherwise fall back to server's public_uri
-                let uri = game_config.uri.clone()
-                    .or_else(|| self.config.public_uri.clone())?;
-
-                Some(ServerInfo {
-                    game_id: game_id.clone(),
-                    game_name: game_config.name.clone(),
-                    server_name: server_name.clone(),
-                    uri,
-                })
-            })
-            .collect()
+            // Use game-specific URI if present, otherwise fall back to server's public_uri
+            let Some(uri) = game_config.uri.clone().or_else(|| self.config.public_uri.clone()) else {
+                continue;
+            };
+
+            let player_count = self.client_manager.game_player_count(game_id).await;
+            servers.push(ServerInfo {
+                game_id: game_id.clone(),
+                game_name: game_config.name.clone(),
+                server_name: server_name.clone(),
+                uri,
+                description: game_config.description.clone(),
+                player_count: u32::try_from(player_count).unwrap_or(u32::MAX),
+                max_players: game_config.max_players,
+                icon_uri: game_config.icon_uri.clone(),
+            });
+        }
+
+        servers
     }
+}
 
-    /// Check if client version is compatible with server version
-    /// Returns true if major.minor versions match
-    fn is_version_compatible(&self, client_version: &str) -> bool {
-        let server_major_minor = self.get_major_minor(VERSION);
-        let client_major_minor = self.get_major_minor(client_version);
+/// Tell a connection over the `max_connections` limit that the server is full, then close it
+///
+/// Only the (TLS handshake and) `PrimalMessage::Error` with the `error-server-full` locale ID
+/// happen, bounded by `SERVER_FULL_REJECT_TIMEOUT`; no `PrimalClient` is created.
+pub async fn reject_server_full(tcp: TcpStream, addr: SocketAddr, tls_acceptor: Option<TlsAcceptor>) {
+    let reject = async {
+        let mut stream = StamStream::accept(tcp, tls_acceptor.as_ref()).await?;
+        stream.write_primal_message(&PrimalMessage::Error {
+            message: "error-server-full".to_string(),
+        }).await
+    };
+    match tokio::time::timeout(SERVER_FULL_REJECT_TIMEOUT, reject).await {
+        Ok(Ok(())) => debug!("Told {} that the server is full", addr),
+        Ok(Err(e)) => debug!("Failed to tell {} that the server is full: {}", addr, e),
+        Err(_) => debug!("Timed out telling {} that the server is full", addr),
+    }
+}
 
-        server_major_minor == client_major_minor
+/// JSON body of a directory listing: the entries of `dir`, sorted by name
+fn list_directory(dir: &std::path::Path) -> std::io::Result<String> {
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        entries.push(stam_mod_runtimes::api::UriListEntry {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            is_dir: metadata.is_dir(),
+        });
     }
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    serde_json::to_string(&entries).map_err(std::io::Error::other)
+}
 
-    /// Extract major.minor from a version string (e.g., "0.1.0-alpha" -> "0.1")
-    fn get_major_minor(&self, version: &str) -> String {
-        let parts: Vec<&str> = version.split('.').collect();
-        if parts.len() >= 2 {
-            format!("{}.{}", parts[0], parts[1])
-        } else {
-            version.to_string()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_connections_over_the_limit_get_server_full() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = listener.local_addr().unwrap().to_string();
+        let client_manager = ClientManager::new().with_max_connections(2);
+
+        // Same admission as the server's accept loop
+        let accept_manager = client_manager.clone();
+        tokio::spawn(async move {
+            loop {
+                let (tcp, addr) = listener.accept().await.unwrap();
+                let Some(slot) = accept_manager.try_acquire_connection() else {
+                    tokio::spawn(reject_server_full(tcp, addr, None));
+                    continue;
+                };
+                let client_manager = accept_manager.clone();
+                tokio::spawn(async move {
+                    let _slot = slot;
+                    let stream = StamStream::accept(tcp, None).await.unwrap();
+                    PrimalClient::new(stream, addr, Config::default(), client_manager, Arc::new(HashMap::new()))
+                        .handle()
+                        .await;
+                });
+            }
+        });
+
+        let mut clients = Vec::new();
+        for _ in 0..2 {
+            let mut stream = StamStream::connect(&server_addr, false).await.unwrap();
+            assert!(matches!(stream.read_primal_message().await.unwrap(), PrimalMessage::Welcome { .. }));
+            clients.push(stream);
+        }
+
+        let mut rejected = StamStream::connect(&server_addr, false).await.unwrap();
+        match rejected.read_primal_message().await.unwrap() {
+            PrimalMessage::Error { message } => assert_eq!(message, "error-server-full"),
+            other => panic!("expected the server-full error, got {:?}", other),
         }
+        assert_eq!(client_manager.active_connections(), 2);
+
+        // A closed connection frees its slot for the next one
+        drop(clients.pop());
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while client_manager.active_connections() > 1 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        let mut next = StamStream::connect(&server_addr, false).await.unwrap();
+        assert!(matches!(next.read_primal_message().await.unwrap(), PrimalMessage::Welcome { .. }));
     }
 }
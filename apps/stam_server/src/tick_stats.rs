@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+/// Summary of the time spent dispatching `onTick` over a window of ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickReport {
+    /// Number of ticks in the window
+    pub ticks: u32,
+    /// Average dispatch time per tick
+    pub average: Duration,
+    /// Slowest tick dispatch in the window
+    pub max: Duration,
+}
+
+/// Accumulates the per-tick dispatch overhead of the server loop
+///
+/// Every `window` ticks a `TickReport` is produced and the counters restart,
+/// so the overhead can be logged periodically without a per-tick log line.
+#[derive(Debug)]
+pub struct TickStats {
+    window: u32,
+    ticks: u32,
+    total: Duration,
+    max: Duration,
+}
+
+impl TickStats {
+    /// Create stats reporting every `window` ticks (at least 1)
+    pub fn new(window: u32) -> Self {
+        Self {
+            window: window.max(1),
+            ticks: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+        }
+    }
+
+    /// Record the dispatch time of one tick, returning a report when the window is full
+    pub fn record(&mut self, cost: Duration) -> Option<TickReport> {
+        self.ticks += 1;
+        self.total += cost;
+        self.max = self.max.max(cost);
+        if self.ticks < self.window {
+            return None;
+        }
+
+        let report = TickReport {
+            ticks: self.ticks,
+            average: self.total / self.ticks,
+            max: self.max,
+        };
+        *self = Self::new(self.window);
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_every_window() {
+        let mut stats = TickStats::new(3);
+        assert_eq!(stats.record(Duration::from_micros(10)), None);
+        assert_eq!(stats.record(Duration::from_micros(50)), None);
+        assert_eq!(
+            stats.record(Duration::from_micros(30)),
+            Some(TickReport {
+                ticks: 3,
+                average: Duration::from_micros(30),
+                max: Duration::from_micros(50),
+            })
+        );
+
+        // Counters restart after a report
+        assert_eq!(stats.record(Duration::from_micros(5)), None);
+        assert_eq!(stats.record(Duration::from_micros(5)), None);
+        assert_eq!(stats.record(Duration::from_micros(5)).map(|r| r.max), Some(Duration::from_micros(5)));
+    }
+}